@@ -0,0 +1,348 @@
+//! `tambourine-cli`, for driving the running app from a terminal or script.
+//!
+//! The CLI is a client of the app's local HTTP API, which has to be turned on in
+//! settings. The port comes from the app's settings file and the token from the OS
+//! keyring, so it works for the same user without setup; `--port` and
+//! `TAMBOURINE_TOKEN` override them. Output is meant for people, or the API's JSON
+//! with `--json`.
+
+use keyring::Entry;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// Must match `identifier` in tauri.conf.json, which names the app data directory
+/// and the keyring service
+const APP_IDENTIFIER: &str = "com.tambourine-voice.app";
+
+/// Must match `api::DEFAULT_PORT` in the app
+const DEFAULT_API_PORT: u16 = 47654;
+
+/// Keyring account of the control token, as `remote_control` stores it in the app
+const CONTROL_TOKEN_ACCOUNT: &str = "control-token";
+
+/// Long enough for diagnostics, which test the server connection
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const USAGE: &str = "\
+Usage: tambourine-cli [--port <port>] [--json] <command>
+
+Commands:
+  start              Start recording
+  stop               Stop recording and insert the dictation
+  toggle             Start recording if idle, otherwise stop
+  status             Show whether dictation is recording
+  last               Print the last transcript
+  insert <text>      Type text into the focused app
+  providers          List the server's STT providers
+  provider <value>   Switch the STT provider
+  diagnostics        Run the troubleshooting checks
+
+The HTTP API must be turned on in Settings > Remote Control.";
+
+/// What the CLI was asked to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CliCommand {
+    Start,
+    Stop,
+    Toggle,
+    Status,
+    Last,
+    Insert(String),
+    Providers,
+    Provider(String),
+    Diagnostics,
+    Help,
+}
+
+/// Parsed command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CliArgs {
+    command: CliCommand,
+    port: Option<u16>,
+    json: bool,
+}
+
+/// Parse the arguments after the program name
+fn parse_args<I, S>(args: I) -> Result<CliArgs, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut port = None;
+    let mut json = false;
+    let mut help = false;
+    let mut words = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "--port" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--port needs a value".to_string())?;
+                port = Some(
+                    value
+                        .as_ref()
+                        .parse()
+                        .map_err(|_| format!("Invalid port: {}", value.as_ref()))?,
+                );
+            }
+            "--json" => json = true,
+            "-h" | "--help" => help = true,
+            other => words.push(other.to_string()),
+        }
+    }
+
+    if help {
+        words = vec!["help".to_string()];
+    }
+    let command = match words.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["start"] => CliCommand::Start,
+        ["stop"] => CliCommand::Stop,
+        ["toggle"] => CliCommand::Toggle,
+        ["status"] => CliCommand::Status,
+        ["last"] => CliCommand::Last,
+        ["insert", ref text @ ..] if !text.is_empty() => CliCommand::Insert(text.join(" ")),
+        ["providers"] => CliCommand::Providers,
+        ["provider", value] => CliCommand::Provider(value.to_string()),
+        ["diagnostics"] => CliCommand::Diagnostics,
+        ["help"] | [] => CliCommand::Help,
+        _ => return Err(format!("Unknown command: {}", words.join(" "))),
+    };
+    Ok(CliArgs {
+        command,
+        port,
+        json,
+    })
+}
+
+/// The API request for a command: method, path and JSON body
+fn request_for(command: &CliCommand) -> Option<(&'static str, &'static str, Option<Value>)> {
+    Some(match command {
+        CliCommand::Start => ("POST", "/start", None),
+        CliCommand::Stop => ("POST", "/stop", None),
+        CliCommand::Toggle => ("POST", "/toggle", None),
+        CliCommand::Status => ("GET", "/status", None),
+        CliCommand::Last => ("GET", "/last-transcript", None),
+        CliCommand::Insert(text) => ("POST", "/insert", Some(json!({ "text": text }))),
+        CliCommand::Providers => ("GET", "/providers", None),
+        CliCommand::Provider(value) => ("POST", "/provider", Some(json!({ "provider": value }))),
+        CliCommand::Diagnostics => ("GET", "/diagnostics", None),
+        CliCommand::Help => return None,
+    })
+}
+
+fn describe_status(status: &Value) -> String {
+    let flag = |name: &str| status.get(name).and_then(Value::as_bool) == Some(true);
+    let state = match (flag("recording"), flag("paused")) {
+        (true, true) => "Recording (paused)",
+        (true, false) => "Recording",
+        _ => "Idle",
+    };
+    if flag("connected") {
+        state.to_string()
+    } else {
+        format!("{}, not connected to the server", state)
+    }
+}
+
+/// Human-readable output for a command's response
+fn format_output(command: &CliCommand, response: &Value) -> String {
+    let text = |value: &Value, name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    match command {
+        CliCommand::Start | CliCommand::Stop | CliCommand::Toggle | CliCommand::Status => {
+            describe_status(response)
+        }
+        CliCommand::Help => String::new(),
+        CliCommand::Last => text(response, "text"),
+        CliCommand::Insert(_) => format!(
+            "Inserted {} characters",
+            response["inserted_chars"].as_u64().unwrap_or_default()
+        ),
+        CliCommand::Providers => {
+            let selected = response.get("selected").and_then(Value::as_str);
+            let providers = response["providers"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if providers.is_empty() {
+                return "The server hasn't offered any STT providers yet".to_string();
+            }
+            providers
+                .iter()
+                .map(|provider| {
+                    let value = text(provider, "value");
+                    let marker = if Some(value.as_str()) == selected {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    format!("{} {:<20} {}", marker, value, text(provider, "label"))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        CliCommand::Provider(_) => format!("Switched to {}", text(response, "label")),
+        CliCommand::Diagnostics => response["checks"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .iter()
+            .map(|check| {
+                let mut line = format!(
+                    "[{}] {}: {}",
+                    text(check, "status"),
+                    text(check, "label"),
+                    text(check, "detail")
+                );
+                if let Some(fix) = check.get("fix").and_then(Value::as_str) {
+                    line.push_str(&format!("\n       {}", fix));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// The `api_port` of the app's settings, if set
+fn port_from_settings(settings: &Value) -> Option<u16> {
+    settings
+        .get("api_port")
+        .and_then(Value::as_u64)
+        .and_then(|port| u16::try_from(port).ok())
+}
+
+/// The app's settings file, where Tauri puts app data on each platform
+fn settings_path() -> Option<PathBuf> {
+    let data_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+            })
+    }?;
+    Some(data_dir.join(APP_IDENTIFIER).join("settings.json"))
+}
+
+fn read_settings() -> Value {
+    settings_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// The token the app generated for remote control and the HTTP API
+fn control_token() -> Result<String, String> {
+    let entry = Entry::new(APP_IDENTIFIER, CONTROL_TOKEN_ACCOUNT)
+        .map_err(|e| format!("Failed to open keyring entry: {}", e))?;
+    match entry.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => Err("Tambourine hasn't made a control token yet. Turn \
+             on the HTTP API in Settings > Remote Control"
+            .to_string()),
+        Err(e) => Err(format!("Failed to read control token: {}", e)),
+    }
+}
+
+/// Send a request to the app's API and return its JSON response
+fn send(port: u16, token: &str, command: &CliCommand) -> Result<Value, String> {
+    let Some((method, path, body)) = request_for(command) else {
+        return Ok(Value::Null);
+    };
+    let client = reqwest::blocking::Client::builder()
+        .no_proxy()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("http://127.0.0.1:{}{}", port, path);
+    let mut request = match method {
+        "POST" => client.post(&url),
+        _ => client.get(&url),
+    }
+    .bearer_auth(token);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let response = request.send().map_err(|e| {
+        if e.is_connect() {
+            "Tambourine isn't running or its HTTP API is off. Turn it on in Settings > \
+             Remote Control"
+                .to_string()
+        } else {
+            format!("Request failed: {}", e)
+        }
+    })?;
+    let status = response.status();
+    let body: Value = response
+        .json()
+        .map_err(|e| format!("Unreadable response: {}", e))?;
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(body
+            .get("error")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("The app answered {}", status)))
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if args.command == CliCommand::Help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let port = args
+        .port
+        .or_else(|| port_from_settings(&read_settings()))
+        .unwrap_or(DEFAULT_API_PORT);
+    let token = match std::env::var("TAMBOURINE_TOKEN") {
+        Ok(token) => token,
+        Err(_) => match control_token() {
+            Ok(token) => token,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    match send(port, &token, &args.command) {
+        Ok(response) => {
+            if args.json {
+                println!("{:#}", response);
+            } else {
+                println!("{}", format_output(&args.command, &response));
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{format_output, parse_args, port_from_settings, request_for, CliCommand};
+use serde_json::json;
+
+fn command(args: &[&str]) -> Result<CliCommand, String> {
+    parse_args(args).map(|args| args.command)
+}
+
+#[test]
+fn test_parse_args_commands() {
+    assert_eq!(command(&["toggle"]), Ok(CliCommand::Toggle));
+    assert_eq!(command(&["last"]), Ok(CliCommand::Last));
+    assert_eq!(
+        command(&["provider", "deepgram"]),
+        Ok(CliCommand::Provider("deepgram".to_string()))
+    );
+    // Unquoted text is joined back up
+    assert_eq!(
+        command(&["insert", "Dear", "team,"]),
+        Ok(CliCommand::Insert("Dear team,".to_string()))
+    );
+    assert_eq!(command(&[]), Ok(CliCommand::Help));
+    assert_eq!(command(&["status", "--help"]), Ok(CliCommand::Help));
+
+    assert!(command(&["insert"]).is_err());
+    assert!(command(&["provider"]).is_err());
+    assert!(command(&["record"]).is_err());
+}
+
+#[test]
+fn test_parse_args_flags() {
+    let args = parse_args(["--port", "5000", "status", "--json"]).unwrap();
+    assert_eq!(args.command, CliCommand::Status);
+    assert_eq!(args.port, Some(5000));
+    assert!(args.json);
+
+    assert!(parse_args(["status", "--port"]).is_err());
+    assert!(parse_args(["status", "--port", "70000"]).is_err());
+}
+
+#[test]
+fn test_request_for_commands() {
+    assert_eq!(
+        request_for(&CliCommand::Stop),
+        Some(("POST", "/stop", None))
+    );
+    assert_eq!(
+        request_for(&CliCommand::Insert("hi".to_string())),
+        Some(("POST", "/insert", Some(json!({"text": "hi"}))))
+    );
+    assert_eq!(
+        request_for(&CliCommand::Provider("local-whisper".to_string())),
+        Some((
+            "POST",
+            "/provider",
+            Some(json!({"provider": "local-whisper"}))
+        ))
+    );
+    assert_eq!(request_for(&CliCommand::Help), None);
+}
+
+#[test]
+fn test_format_output() {
+    let status = json!({"recording": true, "paused": false, "connected": false});
+    assert_eq!(
+        format_output(&CliCommand::Status, &status),
+        "Recording, not connected to the server"
+    );
+    assert_eq!(
+        format_output(&CliCommand::Last, &json!({"text": "Hello there"})),
+        "Hello there"
+    );
+
+    let providers = json!({
+        "selected": "whisper",
+        "providers": [
+            {"value": "deepgram", "label": "Deepgram"},
+            {"value": "whisper", "label": "Whisper (local)"},
+        ],
+    });
+    assert_eq!(
+        format_output(&CliCommand::Providers, &providers),
+        format!(
+            "  {:<20} Deepgram\n* {:<20} Whisper (local)",
+            "deepgram", "whisper"
+        )
+    );
+
+    let diagnostics = json!({"checks": [{
+        "status": "fail",
+        "label": "Microphone access",
+        "detail": "Denied",
+        "fix": "Allow it in System Settings",
+    }]});
+    assert_eq!(
+        format_output(&CliCommand::Diagnostics, &diagnostics),
+        "[fail] Microphone access: Denied\n       Allow it in System Settings"
+    );
+}
+
+#[test]
+fn test_port_from_settings() {
+    assert_eq!(port_from_settings(&json!({"api_port": 5000})), Some(5000));
+    assert_eq!(port_from_settings(&json!({"api_port": 70000})), None);
+    assert_eq!(port_from_settings(&json!({})), None);
+}
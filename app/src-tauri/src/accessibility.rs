@@ -0,0 +1,330 @@
+//! Text insertion through the platform accessibility API.
+//!
+//! On macOS the text is written into the focused element's selected text, which
+//! inserts it at the caret (or replaces the selection) without touching the
+//! clipboard or simulating keys. Many apps (Electron and most browsers among them)
+//! accept the write without inserting anything, so the element's value is compared
+//! before and after and an unchanged value counts as a failure.
+//!
+//! On Windows, UI Automation has no way to insert at the caret, so the focused
+//! control's value is rebuilt around its selection (read through `TextPattern`) and
+//! written back through `ValuePattern`. Other platforms report it as unsupported so
+//! callers can fall back to another insertion method.
+
+use crate::settings::InsertionMethod;
+
+/// Check if accessibility insertion is supported on this platform
+pub fn is_supported() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+/// Method to insert with when the accessibility API can't. Windows types rather than
+/// pastes, since the Electron and UWP apps without UI Automation support often drop
+/// simulated pastes too.
+pub fn fallback_method() -> InsertionMethod {
+    if cfg!(target_os = "windows") {
+        InsertionMethod::Type
+    } else {
+        InsertionMethod::Paste
+    }
+}
+
+/// Whether macOS lets this app control other apps, which simulated keys and the
+/// accessibility API both need. `None` on platforms without such a permission.
+#[cfg(target_os = "macos")]
+pub fn is_trusted() -> Option<bool> {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> u8;
+    }
+
+    // SAFETY: takes no arguments and only reads this process's trust state
+    Some(unsafe { AXIsProcessTrusted() != 0 })
+}
+
+/// Whether macOS lets this app control other apps, which simulated keys and the
+/// accessibility API both need. `None` on platforms without such a permission.
+#[cfg(not(target_os = "macos"))]
+pub fn is_trusted() -> Option<bool> {
+    None
+}
+
+/// Replace the selection between UTF-16 offsets `start` and `end` of `value` with
+/// `text`. Returns the new value and the caret position after the inserted text, in
+/// characters, or `None` if the offsets don't fall on character boundaries in `value`.
+pub fn splice_value(value: &str, start: usize, end: usize, text: &str) -> Option<(String, usize)> {
+    let byte_offset = |utf16_offset: usize| {
+        let mut units = 0;
+        for (index, c) in value.char_indices() {
+            if units == utf16_offset {
+                return Some(index);
+            }
+            units += c.len_utf16();
+        }
+        (units == utf16_offset).then_some(value.len())
+    };
+    let start = byte_offset(start)?;
+    let end = byte_offset(end)?;
+    if end < start {
+        return None;
+    }
+    let spliced = format!("{}{}{}", &value[..start], text, &value[end..]);
+    let caret = value[..start].chars().count() + text.chars().count();
+    Some((spliced, caret))
+}
+
+/// Whether an element ignored inserted `text`, given its value before and after the
+/// write. Elements that don't expose their value (such as password fields) can't be
+/// checked and are assumed to have taken it.
+pub fn text_was_ignored(before: Option<&str>, after: Option<&str>, text: &str) -> bool {
+    match (before, after) {
+        (Some(before), Some(after)) => !text.is_empty() && before == after,
+        _ => false,
+    }
+}
+
+/// Insert text into the focused element via the accessibility API
+#[cfg(target_os = "macos")]
+pub fn insert_text(text: &str) -> Result<(), String> {
+    macos::insert_text(text)
+}
+
+/// Insert text into the focused element via the accessibility API
+#[cfg(target_os = "windows")]
+pub fn insert_text(text: &str) -> Result<(), String> {
+    uia::insert_text(text)
+}
+
+/// Insert text into the focused element via the accessibility API
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn insert_text(_text: &str) -> Result<(), String> {
+    Err("Accessibility insertion is not supported on this platform".to_string())
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use core_foundation::base::{CFGetTypeID, CFRelease, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use std::ffi::c_void;
+    use std::ptr;
+
+    type AXUIElementRef = *const c_void;
+    type AXError = i32;
+
+    const AX_ERROR_SUCCESS: AXError = 0;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXUIElementSetAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: CFTypeRef,
+        ) -> AXError;
+        fn AXUIElementIsAttributeSettable(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            settable: *mut u8,
+        ) -> AXError;
+    }
+
+    /// Read a string attribute of an element, or `None` if it has no string value
+    ///
+    /// # Safety
+    /// `element` must be a valid AXUIElementRef
+    unsafe fn copy_string_attribute(
+        element: AXUIElementRef,
+        attribute: &CFString,
+    ) -> Option<String> {
+        let mut value: CFTypeRef = ptr::null();
+        let error =
+            AXUIElementCopyAttributeValue(element, attribute.as_concrete_TypeRef(), &mut value);
+        if error != AX_ERROR_SUCCESS || value.is_null() {
+            return None;
+        }
+        if CFGetTypeID(value) != CFString::type_id() {
+            CFRelease(value);
+            return None;
+        }
+        Some(CFString::wrap_under_create_rule(value as CFStringRef).to_string())
+    }
+
+    pub fn insert_text(text: &str) -> Result<(), String> {
+        let focused_attribute = CFString::from_static_string("AXFocusedUIElement");
+        let selected_text_attribute = CFString::from_static_string("AXSelectedText");
+        let value_attribute = CFString::from_static_string("AXValue");
+        let value = CFString::new(text);
+
+        // SAFETY: every element returned by a Create/Copy call is released exactly once,
+        // and the attribute and value strings outlive the calls that use them
+        unsafe {
+            let system_wide = AXUIElementCreateSystemWide();
+            if system_wide.is_null() {
+                return Err("Failed to access the accessibility API".to_string());
+            }
+
+            let mut focused: CFTypeRef = ptr::null();
+            let error = AXUIElementCopyAttributeValue(
+                system_wide,
+                focused_attribute.as_concrete_TypeRef(),
+                &mut focused,
+            );
+            CFRelease(system_wide);
+            if error != AX_ERROR_SUCCESS || focused.is_null() {
+                return Err(format!(
+                    "No focused element to insert into (AXError {})",
+                    error
+                ));
+            }
+
+            let mut settable = 0u8;
+            let error = AXUIElementIsAttributeSettable(
+                focused,
+                selected_text_attribute.as_concrete_TypeRef(),
+                &mut settable,
+            );
+            if error != AX_ERROR_SUCCESS || settable == 0 {
+                CFRelease(focused);
+                return Err("Focused element doesn't accept inserted text".to_string());
+            }
+
+            let before = copy_string_attribute(focused, &value_attribute);
+            let error = AXUIElementSetAttributeValue(
+                focused,
+                selected_text_attribute.as_concrete_TypeRef(),
+                value.as_CFTypeRef(),
+            );
+            let after = copy_string_attribute(focused, &value_attribute);
+            CFRelease(focused);
+            if error != AX_ERROR_SUCCESS {
+                return Err(format!(
+                    "Focused element rejected the text (AXError {})",
+                    error
+                ));
+            }
+            if super::text_was_ignored(before.as_deref(), after.as_deref(), text) {
+                return Err("Focused element ignored the text".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod uia {
+    use windows::core::BSTR;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Accessibility::{
+        CUIAutomation, IUIAutomation, IUIAutomationElement, IUIAutomationTextPattern,
+        IUIAutomationValuePattern, TextPatternRangeEndpoint_End, TextPatternRangeEndpoint_Start,
+        TextUnit_Character, UIA_TextPatternId, UIA_ValuePatternId,
+    };
+
+    /// UTF-16 offsets of the start and end of the element's selection (equal for a
+    /// caret), or `None` if the element doesn't expose a text pattern
+    ///
+    /// # Safety
+    /// COM must be initialized on the calling thread
+    unsafe fn selection_offsets(element: &IUIAutomationElement) -> Option<(usize, usize)> {
+        let text_pattern: IUIAutomationTextPattern =
+            element.GetCurrentPatternAs(UIA_TextPatternId).ok()?;
+        let selections = text_pattern.GetSelection().ok()?;
+        if selections.Length().ok()? < 1 {
+            return None;
+        }
+        let selection = selections.GetElement(0).ok()?;
+        let preceding = text_pattern.DocumentRange().ok()?;
+        preceding
+            .MoveEndpointByRange(
+                TextPatternRangeEndpoint_End,
+                &selection,
+                TextPatternRangeEndpoint_Start,
+            )
+            .ok()?;
+        let start = preceding.GetText(-1).ok()?.len();
+        let selected = selection.GetText(-1).ok()?.len();
+        Some((start, start + selected))
+    }
+
+    /// Move the caret to `offset` characters into the element's text. Best effort, as
+    /// a control that took the new value is usable even with the caret elsewhere.
+    ///
+    /// # Safety
+    /// COM must be initialized on the calling thread
+    unsafe fn place_caret(element: &IUIAutomationElement, offset: usize) {
+        let Ok(text_pattern) =
+            element.GetCurrentPatternAs::<IUIAutomationTextPattern>(UIA_TextPatternId)
+        else {
+            return;
+        };
+        let Ok(range) = text_pattern.DocumentRange() else {
+            return;
+        };
+        let _ = range.MoveEndpointByRange(
+            TextPatternRangeEndpoint_End,
+            &range,
+            TextPatternRangeEndpoint_Start,
+        );
+        let _ = range.Move(
+            TextUnit_Character,
+            i32::try_from(offset).unwrap_or(i32::MAX),
+        );
+        let _ = range.Select();
+    }
+
+    pub fn insert_text(text: &str) -> Result<(), String> {
+        // SAFETY: COM is initialized on this thread before any UI Automation call, and
+        // every interface is released when dropped
+        unsafe {
+            // Already initialized on the main thread, in which case this is a no-op
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+            let automation: IUIAutomation =
+                CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)
+                    .map_err(|e| format!("Failed to start UI Automation: {}", e))?;
+            let element = automation
+                .GetFocusedElement()
+                .map_err(|e| format!("No focused element to insert into: {}", e))?;
+
+            let value_pattern: IUIAutomationValuePattern = element
+                .GetCurrentPatternAs(UIA_ValuePatternId)
+                .map_err(|_| "Focused element doesn't accept inserted text".to_string())?;
+            if value_pattern
+                .CurrentIsReadOnly()
+                .map_or(true, |read_only| read_only.as_bool())
+            {
+                return Err("Focused element is read-only".to_string());
+            }
+
+            let before = value_pattern
+                .CurrentValue()
+                .map_err(|e| format!("Failed to read the focused element: {}", e))?
+                .to_string();
+            let (start, end) = selection_offsets(&element)
+                .ok_or_else(|| "Focused element doesn't report its caret".to_string())?;
+            let (value, caret) = super::splice_value(&before, start, end, text)
+                .ok_or_else(|| "Caret is outside the focused element's text".to_string())?;
+
+            value_pattern
+                .SetValue(&BSTR::from(value.as_str()))
+                .map_err(|e| format!("Focused element rejected the text: {}", e))?;
+            let after = value_pattern
+                .CurrentValue()
+                .ok()
+                .map(|value| value.to_string());
+            if super::text_was_ignored(Some(&before), after.as_deref(), text) {
+                return Err("Focused element ignored the text".to_string());
+            }
+            place_caret(&element, caret);
+        }
+
+        Ok(())
+    }
+}
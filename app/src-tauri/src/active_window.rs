@@ -0,0 +1,138 @@
+//! Focused application detection.
+//!
+//! Used to apply per-app behavior (e.g. newline handling) at insertion time, and
+//! captured when a recording starts so the formatter knows where the text goes.
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Patterns, as for [`ActiveApp::matches`], of common web browsers
+const WEB_BROWSERS: &[&str] = &[
+    "chrome",
+    "chromium",
+    "firefox",
+    "safari",
+    "msedge",
+    "microsoft edge",
+    "brave",
+    "opera",
+    "vivaldi",
+    "librewolf",
+    "waterfox",
+    "company.thebrowser.browser",
+];
+
+/// The application that currently has keyboard focus
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ActiveApp {
+    /// Application name as reported by the OS (e.g. "Slack")
+    pub app_name: String,
+    /// Title of the focused window
+    pub title: String,
+    /// Path to the executable of the owning process
+    pub process_path: String,
+    /// Bundle identifier on macOS (e.g. "com.tinyspeck.slackmacgap")
+    pub bundle_id: Option<String>,
+}
+
+impl ActiveApp {
+    /// Check if this app matches a user-supplied pattern.
+    ///
+    /// Matching is case-insensitive against the app name, the executable file name
+    /// and the bundle identifier, so "slack" matches "Slack", "slack.exe" and
+    /// "com.tinyspeck.slackmacgap".
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = pattern.trim().to_lowercase();
+        if pattern.is_empty() {
+            return false;
+        }
+
+        let executable = Path::new(&self.process_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        self.app_name.to_lowercase().contains(&pattern)
+            || executable.contains(&pattern)
+            || self
+                .bundle_id
+                .as_ref()
+                .is_some_and(|id| id.to_lowercase().contains(&pattern))
+    }
+
+    /// Whether this is a web browser, whose pages can read what is pasted into them
+    pub fn is_web_browser(&self) -> bool {
+        WEB_BROWSERS.iter().any(|pattern| self.matches(pattern))
+    }
+}
+
+/// Get the currently focused application, if it can be determined. Our own windows
+/// (settings, overlay) say nothing about where text goes, so they count as unknown.
+pub fn get_active_app() -> Option<ActiveApp> {
+    match active_win_pos_rs::get_active_window() {
+        Ok(window) if window.process_id == u64::from(std::process::id()) => {
+            log::debug!("Active window belongs to this app, ignoring it");
+            None
+        }
+        Ok(window) => Some(ActiveApp {
+            bundle_id: bundle_id(&window.process_path),
+            app_name: window.app_name,
+            title: window.title,
+            process_path: window.process_path.to_string_lossy().into_owned(),
+        }),
+        Err(()) => {
+            log::debug!("Could not determine the active window");
+            None
+        }
+    }
+}
+
+/// Bundle identifier of the `.app` bundle containing an executable
+#[cfg(target_os = "macos")]
+fn bundle_id(process_path: &Path) -> Option<String> {
+    let bundle_path = process_path
+        .ancestors()
+        .find(|path| path.extension().is_some_and(|ext| ext == "app"))?;
+    macos::bundle_identifier(bundle_path)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn bundle_id(_process_path: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use core_foundation::base::{CFAllocatorRef, CFRelease, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use core_foundation::url::{CFURLRef, CFURL};
+    use std::ffi::c_void;
+    use std::path::Path;
+    use std::ptr;
+
+    type CFBundleRef = *const c_void;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFBundleCreate(allocator: CFAllocatorRef, bundle_url: CFURLRef) -> CFBundleRef;
+        fn CFBundleGetIdentifier(bundle: CFBundleRef) -> CFStringRef;
+    }
+
+    pub fn bundle_identifier(bundle_path: &Path) -> Option<String> {
+        let url = CFURL::from_path(bundle_path, true)?;
+
+        // SAFETY: the bundle is released exactly once, and the identifier follows the
+        // get rule so it is retained by the wrapper before the bundle is released
+        unsafe {
+            let bundle = CFBundleCreate(ptr::null(), url.as_concrete_TypeRef());
+            if bundle.is_null() {
+                return None;
+            }
+            let identifier = CFBundleGetIdentifier(bundle);
+            let result = (!identifier.is_null())
+                .then(|| CFString::wrap_under_get_rule(identifier).to_string());
+            CFRelease(bundle);
+            result
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Local HTTP API for scripts and editor plugins.
+//!
+//! When `api_enabled` is on, an HTTP server listens on `127.0.0.1:<api_port>`.
+//! Requests must send the remote control token as `Authorization: Bearer <token>`,
+//! and every response is JSON:
+//!
+//! - `GET /status`: whether dictation is recording, paused and connected
+//! - `POST /start`, `POST /stop`, `POST /toggle`: control recording, answering
+//!   with the status after
+//! - `GET /last-transcript`: the latest dictation in history
+//! - `POST /insert` with `{"text": "..."}`: type text into the focused app,
+//!   unless it is a blocked app
+//! - `GET /providers`: the server's STT providers and the selected one
+//! - `POST /provider` with `{"provider": "..."}`: switch the STT provider
+//! - `GET /diagnostics`: the troubleshooting checks
+//!
+//! Browsers can't send the token header to another origin without a CORS
+//! preflight, which the server never allows, so web pages can't use the API.
+
+use crate::commands::active_window::{ensure_not_blocked, ensure_not_secure_input};
+use crate::commands::diagnostics::run_diagnostics;
+use crate::commands::stt::{set_stt_provider, ServerSttProviders};
+use crate::commands::text::insert_text;
+use crate::history::HistoryStorage;
+use crate::local_server::{self, LocalServer, LocalService, StopSignal};
+use crate::remote_control::{self, RemoteCommand};
+use crate::settings::get_setting_from_store;
+use serde_json::json;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Port the API listens on unless `api_port` is set. tambourine-cli assumes it too.
+pub const DEFAULT_PORT: u16 = 47654;
+
+/// How long a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest request head accepted
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Largest request body accepted, enough for any text worth inserting
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Most headers parsed from a request
+const MAX_HEADERS: usize = 32;
+
+/// What a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    Command(RemoteCommand),
+    LastTranscript,
+    Insert,
+    Providers,
+    SelectProvider,
+    Diagnostics,
+}
+
+/// A request that can't be served, with the HTTP status to answer it with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// The parts of a request head the API looks at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestHead {
+    pub method: String,
+    pub path: String,
+    pub authorization: Option<String>,
+    pub content_length: usize,
+    /// Length of the head, after which the body starts
+    pub head_len: usize,
+}
+
+/// Parse a request head from the bytes read so far. Returns `None` until the
+/// whole head has arrived.
+pub fn parse_head(buf: &[u8]) -> Result<Option<RequestHead>, ApiError> {
+    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut request = httparse::Request::new(&mut headers);
+    let head_len = match request.parse(buf) {
+        Ok(httparse::Status::Complete(len)) => len,
+        Ok(httparse::Status::Partial) => return Ok(None),
+        Err(e) => return Err(ApiError::new(400, format!("Invalid request: {}", e))),
+    };
+
+    let header = |name: &str| {
+        request
+            .headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case(name))
+            .map(|header| String::from_utf8_lossy(header.value).trim().to_string())
+    };
+    let content_length = match header("content-length") {
+        Some(value) => value
+            .parse()
+            .map_err(|_| ApiError::new(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    Ok(Some(RequestHead {
+        method: request.method.unwrap_or_default().to_string(),
+        path: request.path.unwrap_or_default().to_string(),
+        authorization: header("authorization"),
+        content_length,
+        head_len,
+    }))
+}
+
+/// The token of an `Authorization: Bearer <token>` header
+pub fn bearer_token(authorization: Option<&str>) -> Option<&str> {
+    let (scheme, token) = authorization?.split_once(' ')?;
+    scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+}
+
+/// The endpoint for a method and path, ignoring any query string
+pub fn route(method: &str, path: &str) -> Result<Endpoint, ApiError> {
+    let path = path.split('?').next().unwrap_or_default();
+    let (endpoint, expected_method) = match path.trim_end_matches('/') {
+        "/status" => (Endpoint::Command(RemoteCommand::Status), "GET"),
+        "/start" => (Endpoint::Command(RemoteCommand::Start), "POST"),
+        "/stop" => (Endpoint::Command(RemoteCommand::Stop), "POST"),
+        "/toggle" => (Endpoint::Command(RemoteCommand::Toggle), "POST"),
+        "/last-transcript" => (Endpoint::LastTranscript, "GET"),
+        "/insert" => (Endpoint::Insert, "POST"),
+        "/providers" => (Endpoint::Providers, "GET"),
+        "/provider" => (Endpoint::SelectProvider, "POST"),
+        "/diagnostics" => (Endpoint::Diagnostics, "GET"),
+        _ => return Err(ApiError::new(404, format!("No endpoint {}", path))),
+    };
+    if method != expected_method {
+        return Err(ApiError::new(
+            405,
+            format!("{} needs {}", path, expected_method),
+        ));
+    }
+    Ok(endpoint)
+}
+
+/// The non-empty string `field` of a JSON body, e.g. `text` of `POST /insert`
+pub fn string_field(body: &[u8], field: &str) -> Result<String, ApiError> {
+    let expected = || format!("Expected {{\"{}\": \"...\"}}", field);
+    let value: serde_json::Value =
+        serde_json::from_slice(body).map_err(|_| ApiError::new(400, expected()))?;
+    match value.get(field).and_then(|value| value.as_str()) {
+        Some("") => Err(ApiError::new(400, format!("{} cannot be empty", field))),
+        Some(text) => Ok(text.to_string()),
+        None => Err(ApiError::new(400, expected())),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+/// A complete HTTP response with a JSON body, closing the connection after it
+pub fn response_bytes(status: u16, body: &serde_json::Value) -> Vec<u8> {
+    let body = body.to_string();
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+fn error_response(error: &ApiError) -> Vec<u8> {
+    response_bytes(error.status, &json!({ "error": error.message }))
+}
+
+/// Run an authorized request
+fn handle(app: &AppHandle, endpoint: Endpoint, body: &[u8]) -> Result<serde_json::Value, ApiError> {
+    let internal = |e: String| ApiError::new(500, e);
+    match endpoint {
+        Endpoint::Command(command) => serde_json::to_value(remote_control::execute(app, command))
+            .map_err(|e| internal(e.to_string())),
+        Endpoint::LastTranscript => {
+            let entry = app
+                .state::<HistoryStorage>()
+                .latest()
+                .map_err(internal)?
+                .ok_or_else(|| ApiError::new(404, "No dictations yet"))?;
+            Ok(json!({
+                "id": entry.id,
+                "timestamp": entry.timestamp,
+                "text": entry.text,
+                "app_name": entry.app_name,
+            }))
+        }
+        Endpoint::Insert => {
+            let text = string_field(body, "text")?;
+            ensure_not_blocked(app).map_err(|e| ApiError::new(409, e))?;
+            ensure_not_secure_input(app).map_err(|e| ApiError::new(409, e))?;
+            insert_text(app, &text).map_err(internal)?;
+            Ok(json!({ "inserted_chars": text.chars().count() }))
+        }
+        Endpoint::Providers => {
+            let selected: Option<String> = get_setting_from_store(app, "stt_provider", None);
+            Ok(json!({
+                "selected": selected,
+                "providers": app.state::<ServerSttProviders>().get(),
+            }))
+        }
+        Endpoint::SelectProvider => {
+            let value = string_field(body, "provider")?;
+            let provider = set_stt_provider(app, &value).map_err(|e| ApiError::new(400, e))?;
+            serde_json::to_value(provider).map_err(|e| internal(e.to_string()))
+        }
+        Endpoint::Diagnostics => {
+            let report =
+                tauri::async_runtime::block_on(run_diagnostics(app.clone())).map_err(internal)?;
+            serde_json::to_value(report).map_err(|e| internal(e.to_string()))
+        }
+    }
+}
+
+/// Read a request, check its token and run it
+fn respond(app: &AppHandle, stream: &mut TcpStream) -> Result<serde_json::Value, ApiError> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head = loop {
+        let read = stream
+            .read(&mut chunk)
+            .map_err(|_| ApiError::new(408, "Timed out reading the request"))?;
+        if read == 0 {
+            return Err(ApiError::new(400, "Incomplete request"));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(head) = parse_head(&buf)? {
+            break head;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(ApiError::new(413, "Request head too large"));
+        }
+    };
+
+    let token = remote_control::control_token().map_err(|e| ApiError::new(500, e))?;
+    let authorized = bearer_token(head.authorization.as_deref())
+        .is_some_and(|given| remote_control::tokens_match(given, &token));
+    if !authorized {
+        return Err(ApiError::new(401, "Invalid token"));
+    }
+    let endpoint = route(&head.method, &head.path)?;
+
+    if head.content_length > MAX_BODY_BYTES {
+        return Err(ApiError::new(413, "Request body too large"));
+    }
+    let mut body = buf.split_off(head.head_len);
+    if body.len() < head.content_length {
+        let mut rest = vec![0u8; head.content_length - body.len()];
+        stream
+            .read_exact(&mut rest)
+            .map_err(|_| ApiError::new(408, "Timed out reading the request body"))?;
+        body.extend(rest);
+    }
+    body.truncate(head.content_length);
+
+    handle(app, endpoint, &body)
+}
+
+fn serve(app: &AppHandle, mut stream: TcpStream) {
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(REQUEST_TIMEOUT)).is_err()
+    {
+        return;
+    }
+    let response = match respond(app, &mut stream) {
+        Ok(body) => response_bytes(200, &body),
+        Err(error) => {
+            log::debug!("API request failed: {}", error.message);
+            error_response(&error)
+        }
+    };
+    let _ = stream.write_all(&response);
+    let _ = stream.flush();
+}
+
+/// Runs the API server while it is enabled
+#[derive(Default)]
+pub struct ApiServer {
+    server: LocalServer,
+}
+
+impl LocalService for ApiServer {
+    const NAME: &'static str = "API";
+    const ENABLED_SETTING: &'static str = "api_enabled";
+    const PORT_SETTING: &'static str = "api_port";
+    const DEFAULT_PORT: u16 = DEFAULT_PORT;
+
+    fn server(&self) -> &LocalServer {
+        &self.server
+    }
+
+    /// Requests are answered at once, so there's no need to watch for the server stopping
+    fn serve(app: &AppHandle, stream: TcpStream, _stop: StopSignal) {
+        serve(app, stream);
+    }
+}
+
+/// Start the API if enabled and follow changes to its settings
+pub fn init(app: &AppHandle) {
+    local_server::init::<ApiServer>(app);
+}
@@ -1,41 +1,51 @@
+use crate::sound_theme::{Sound, SoundTheme};
 use rodio::source::Source;
 use rodio::{Decoder, OutputStreamBuilder};
+use std::fs;
 use std::io::Cursor;
 use std::thread;
 use std::time::Duration;
 
-/// Types of sounds that can be played
-#[derive(Debug, Clone, Copy)]
-pub enum SoundType {
-    RecordingStart,
-    RecordingStop,
-}
+pub use crate::sound_theme::SoundType;
 
 // Embed audio files at compile time
 const START_SOUND: &[u8] = include_bytes!("assets/start.mp3");
 const STOP_SOUND: &[u8] = include_bytes!("assets/stop.mp3");
 
-/// Play a sound effect (non-blocking)
-pub fn play_sound(sound_type: SoundType) {
+/// Full volume of the built-in sounds; custom files play at their own volume
+const BUILTIN_VOLUME: f32 = 0.3;
+
+/// Play the theme's sound for an event at `volume` (0 to 1), if it has one
+/// (non-blocking). Returns whether a sound plays.
+pub fn play_sound(theme: &SoundTheme, sound_type: SoundType, volume: f32) -> bool {
+    let Some(sound) = theme.resolve(sound_type) else {
+        return false;
+    };
     thread::spawn(move || {
-        if let Err(e) = play_sound_blocking(sound_type) {
+        if let Err(e) = play_sound_blocking(sound, volume) {
             log::warn!("Failed to play sound: {}", e);
         }
     });
+    true
 }
 
 fn play_sound_blocking(
-    sound_type: SoundType,
+    sound: Sound,
+    volume: f32,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let stream = OutputStreamBuilder::open_default_stream()?;
 
-    let sound_data = match sound_type {
-        SoundType::RecordingStart => START_SOUND,
-        SoundType::RecordingStop => STOP_SOUND,
+    let (sound_data, file_volume) = match sound {
+        Sound::Builtin(SoundType::RecordingStart) => (START_SOUND.to_vec(), BUILTIN_VOLUME),
+        Sound::Builtin(SoundType::RecordingStop) => (STOP_SOUND.to_vec(), BUILTIN_VOLUME),
+        Sound::Builtin(sound_type) => {
+            return Err(format!("No built-in sound for {:?}", sound_type).into())
+        }
+        Sound::File(path) => (fs::read(path)?, 1.0),
     };
 
     let cursor = Cursor::new(sound_data);
-    let source = Decoder::new(cursor)?.amplify(0.3);
+    let source = Decoder::new(cursor)?.amplify(file_volume * volume);
 
     // Get duration for sleep, default to 500ms if unknown
     let duration = source
@@ -0,0 +1,99 @@
+//! Linux audio mute control implementation using PulseAudio.
+//!
+//! Drives the default sink through `pactl`, which also works with PipeWire's
+//! PulseAudio server, so no client library needs to be linked.
+
+use super::{AudioControlError, SystemAudioControl};
+use std::process::Command;
+
+/// The sink that plays system audio
+const DEFAULT_SINK: &str = "@DEFAULT_SINK@";
+
+/// Linux audio controller using `pactl`.
+pub struct PulseAudioController;
+
+impl PulseAudioController {
+    /// Create a new PulseAudio controller.
+    ///
+    /// Fails if `pactl` is missing or can't reach a sound server.
+    pub fn new() -> Result<Self, AudioControlError> {
+        pactl(&["info"]).map_err(AudioControlError::InitializationFailed)?;
+        Ok(Self)
+    }
+
+    /// Check whether `pactl` can reach a sound server.
+    pub fn is_available() -> bool {
+        pactl(&["info"]).is_ok()
+    }
+}
+
+/// Run `pactl` and return its output.
+fn pactl(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("pactl")
+        .args(args)
+        .output()
+        .map_err(|e| format!("pactl unavailable: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "pactl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `pactl get-sink-mute` output, e.g. `Mute: yes`.
+pub fn parse_mute(output: &str) -> Option<bool> {
+    match output.trim().strip_prefix("Mute:")?.trim() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse `pactl get-sink-volume` output into a volume from 0 to 1, averaging
+/// the channels, e.g. `Volume: front-left: 32768 /  50% / -18.06 dB, ...`.
+pub fn parse_volume(output: &str) -> Option<f32> {
+    let percents: Vec<f32> = output
+        .lines()
+        .next()?
+        .split('/')
+        .filter_map(|part| part.trim().strip_suffix('%')?.trim().parse().ok())
+        .collect();
+    if percents.is_empty() {
+        return None;
+    }
+    Some(percents.iter().sum::<f32>() / percents.len() as f32 / 100.0)
+}
+
+impl SystemAudioControl for PulseAudioController {
+    fn is_muted(&self) -> Result<bool, AudioControlError> {
+        let output = pactl(&["get-sink-mute", DEFAULT_SINK])
+            .map_err(AudioControlError::GetPropertyFailed)?;
+        parse_mute(&output).ok_or_else(|| {
+            AudioControlError::GetPropertyFailed(format!("Unexpected mute: {}", output.trim()))
+        })
+    }
+
+    fn set_muted(&self, muted: bool) -> Result<(), AudioControlError> {
+        pactl(&["set-sink-mute", DEFAULT_SINK, if muted { "1" } else { "0" }])
+            .map(|_| ())
+            .map_err(AudioControlError::SetPropertyFailed)
+    }
+
+    fn volume(&self) -> Result<f32, AudioControlError> {
+        let output = pactl(&["get-sink-volume", DEFAULT_SINK])
+            .map_err(AudioControlError::GetPropertyFailed)?;
+        parse_volume(&output).ok_or_else(|| {
+            AudioControlError::GetPropertyFailed(format!("Unexpected volume: {}", output.trim()))
+        })
+    }
+
+    fn set_volume(&self, volume: f32) -> Result<(), AudioControlError> {
+        let percent = format!("{}%", (volume.max(0.0) * 100.0).round() as u32);
+        pactl(&["set-sink-volume", DEFAULT_SINK, &percent])
+            .map(|_| ())
+            .map_err(AudioControlError::SetPropertyFailed)
+    }
+}
@@ -1,14 +1,14 @@
 //! macOS audio mute control implementation using CoreAudio.
 //!
 //! Uses the CoreAudio framework to control the default audio output device's
-//! mute state via AudioObject property APIs.
+//! mute state and volume via AudioObject property APIs.
 
 use super::{AudioControlError, SystemAudioControl};
 use objc2_core_audio::{
-    kAudioDevicePropertyMute, kAudioDevicePropertyScopeOutput,
+    kAudioDevicePropertyMute, kAudioDevicePropertyScopeOutput, kAudioDevicePropertyVolumeScalar,
     kAudioHardwarePropertyDefaultOutputDevice, kAudioObjectPropertyElementMain,
     kAudioObjectPropertyScopeGlobal, kAudioObjectSystemObject, AudioObjectGetPropertyData,
-    AudioObjectPropertyAddress, AudioObjectSetPropertyData,
+    AudioObjectHasProperty, AudioObjectPropertyAddress, AudioObjectSetPropertyData,
 };
 use std::ffi::c_void;
 use std::ptr::NonNull;
@@ -69,16 +69,51 @@ impl MacOSAudioController {
         Ok(device_id)
     }
 
-    /// Get a u32 property from the default output device.
-    fn get_u32_property(&self, selector: u32) -> Result<u32, AudioControlError> {
+    /// Whether the default output device has a property on an element.
+    fn has_property(&self, selector: u32, element: u32) -> bool {
         let address = AudioObjectPropertyAddress {
             mSelector: selector,
             mScope: kAudioDevicePropertyScopeOutput,
-            mElement: kAudioObjectPropertyElementMain,
+            mElement: element,
         };
 
-        let mut value: u32 = 0;
-        let mut size = std::mem::size_of::<u32>() as u32;
+        unsafe {
+            AudioObjectHasProperty(
+                self.device_id,
+                NonNull::new(&address as *const _ as *mut _).unwrap(),
+            )
+        }
+    }
+
+    /// Elements the device's volume is set on: the main element if it has a
+    /// volume, otherwise the left and right channels.
+    fn volume_elements(&self) -> Vec<u32> {
+        if self.has_property(
+            kAudioDevicePropertyVolumeScalar,
+            kAudioObjectPropertyElementMain,
+        ) {
+            return vec![kAudioObjectPropertyElementMain];
+        }
+        [1, 2]
+            .into_iter()
+            .filter(|&channel| self.has_property(kAudioDevicePropertyVolumeScalar, channel))
+            .collect()
+    }
+
+    /// Get a property from the default output device.
+    fn get_property<T: Copy + Default>(
+        &self,
+        selector: u32,
+        element: u32,
+    ) -> Result<T, AudioControlError> {
+        let address = AudioObjectPropertyAddress {
+            mSelector: selector,
+            mScope: kAudioDevicePropertyScopeOutput,
+            mElement: element,
+        };
+
+        let mut value = T::default();
+        let mut size = std::mem::size_of::<T>() as u32;
 
         let status = unsafe {
             AudioObjectGetPropertyData(
@@ -101,15 +136,20 @@ impl MacOSAudioController {
         Ok(value)
     }
 
-    /// Set a u32 property on the default output device.
-    fn set_u32_property(&self, selector: u32, value: u32) -> Result<(), AudioControlError> {
+    /// Set a property on the default output device.
+    fn set_property<T: Copy>(
+        &self,
+        selector: u32,
+        element: u32,
+        value: T,
+    ) -> Result<(), AudioControlError> {
         let address = AudioObjectPropertyAddress {
             mSelector: selector,
             mScope: kAudioDevicePropertyScopeOutput,
-            mElement: kAudioObjectPropertyElementMain,
+            mElement: element,
         };
 
-        let size = std::mem::size_of::<u32>() as u32;
+        let size = std::mem::size_of::<T>() as u32;
 
         let status = unsafe {
             AudioObjectSetPropertyData(
@@ -135,11 +175,46 @@ impl MacOSAudioController {
 
 impl SystemAudioControl for MacOSAudioController {
     fn is_muted(&self) -> Result<bool, AudioControlError> {
-        self.get_u32_property(kAudioDevicePropertyMute)
+        self.get_property::<u32>(kAudioDevicePropertyMute, kAudioObjectPropertyElementMain)
             .map(|v| v != 0)
     }
 
     fn set_muted(&self, muted: bool) -> Result<(), AudioControlError> {
-        self.set_u32_property(kAudioDevicePropertyMute, if muted { 1 } else { 0 })
+        self.set_property::<u32>(
+            kAudioDevicePropertyMute,
+            kAudioObjectPropertyElementMain,
+            if muted { 1 } else { 0 },
+        )
+    }
+
+    fn volume(&self) -> Result<f32, AudioControlError> {
+        let elements = self.volume_elements();
+        if elements.is_empty() {
+            return Err(AudioControlError::GetPropertyFailed(
+                "Output device has no volume control".to_string(),
+            ));
+        }
+        let mut total = 0.0;
+        for &element in &elements {
+            total += self.get_property::<f32>(kAudioDevicePropertyVolumeScalar, element)?;
+        }
+        Ok(total / elements.len() as f32)
+    }
+
+    fn set_volume(&self, volume: f32) -> Result<(), AudioControlError> {
+        let elements = self.volume_elements();
+        if elements.is_empty() {
+            return Err(AudioControlError::SetPropertyFailed(
+                "Output device has no volume control".to_string(),
+            ));
+        }
+        for element in elements {
+            self.set_property(
+                kAudioDevicePropertyVolumeScalar,
+                element,
+                volume.clamp(0.0, 1.0),
+            )?;
+        }
+        Ok(())
     }
 }
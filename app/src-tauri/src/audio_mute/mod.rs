@@ -2,21 +2,51 @@
 //!
 //! This module provides a minimal trait interface for controlling system audio,
 //! making it easy to swap implementations or migrate to a cross-platform library.
+//!
+//! Audio can either be muted outright or ducked: faded down to a percentage of
+//! its volume while recording, then faded back up afterwards.
 
 use std::fmt;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
 // Platform-specific implementations
+#[cfg(target_os = "linux")]
+pub(crate) mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
-#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
 mod stub;
 #[cfg(target_os = "windows")]
 mod windows;
 
+/// Default ducking level; 0 mutes system audio outright
+pub const DEFAULT_DUCK_PERCENT: u8 = 0;
+
+/// Highest ducking level, as ducking to 100% would leave audio unchanged
+pub const MAX_DUCK_PERCENT: u8 = 90;
+
+/// How long ducking takes to fade audio down
+const DUCK_FADE: Duration = Duration::from_millis(200);
+
+/// How long restoring takes to fade audio back up
+const RESTORE_FADE: Duration = Duration::from_millis(500);
+
+/// Number of volume changes in a fade
+const FADE_STEPS: usize = 10;
+
+/// How far the volume may drift from the last level we set before it counts as
+/// the user having changed it
+const VOLUME_TOLERANCE: f32 = 0.02;
+
+/// Attempts at unmuting before giving up
+const RESTORE_ATTEMPTS: usize = 3;
+
 /// Error type for audio control operations
 #[derive(Debug)]
-#[allow(dead_code)] // Variants used on Windows/macOS, not Linux
+#[allow(dead_code)] // Not every variant is used on every platform
 pub enum AudioControlError {
     /// Platform-specific initialization failed
     InitializationFailed(String),
@@ -51,6 +81,12 @@ pub trait SystemAudioControl: Send + Sync {
 
     /// Set system mute state
     fn set_muted(&self, muted: bool) -> Result<(), AudioControlError>;
+
+    /// Get the system output volume, from 0 to 1
+    fn volume(&self) -> Result<f32, AudioControlError>;
+
+    /// Set the system output volume, from 0 to 1
+    fn set_volume(&self, volume: f32) -> Result<(), AudioControlError>;
 }
 
 /// Check if audio mute is supported on this platform.
@@ -59,7 +95,11 @@ pub fn is_supported() -> bool {
     {
         true
     }
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[cfg(target_os = "linux")]
+    {
+        linux::PulseAudioController::is_available()
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
     {
         false
     }
@@ -80,22 +120,49 @@ pub fn create_controller() -> Result<Box<dyn SystemAudioControl>, AudioControlEr
         macos::MacOSAudioController::new().map(|c| Box::new(c) as Box<dyn SystemAudioControl>)
     }
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[cfg(target_os = "linux")]
+    {
+        linux::PulseAudioController::new().map(|c| Box::new(c) as Box<dyn SystemAudioControl>)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
     {
         Ok(Box::new(stub::StubAudioController::new()))
     }
 }
 
+/// Volume to duck audio at `original` volume to, `percent` of it
+pub fn ducked_volume(original: f32, percent: u8) -> f32 {
+    original.clamp(0.0, 1.0) * f32::from(percent.min(MAX_DUCK_PERCENT)) / 100.0
+}
+
+/// Volumes to step through when fading from `from` to `to`, ending at `to`
+pub fn fade_steps(from: f32, to: f32, steps: usize) -> Vec<f32> {
+    let steps = steps.max(1);
+    (1..=steps)
+        .map(|step| from + (to - from) * step as f32 / steps as f32)
+        .collect()
+}
+
+/// How system audio was quieted for the current recording
+#[derive(Debug, Clone, Copy)]
+enum Quieted {
+    Muted { was_muted: bool },
+    Ducked { original: f32 },
+}
+
 /// Manages muting/unmuting system audio during recording.
 ///
-/// Tracks whether audio was muted before we started, so we can restore
-/// the correct state after recording ends.
+/// Tracks whether audio was muted before we started, or its volume before we
+/// ducked it, so we can restore the correct state after recording ends.
 pub struct AudioMuteManager {
-    controller: Box<dyn SystemAudioControl>,
-    /// Was audio already muted before we started muting?
-    was_muted_before: AtomicBool,
-    /// Are we currently in a muted state (that we caused)?
-    is_currently_muting: AtomicBool,
+    controller: Arc<dyn SystemAudioControl>,
+    /// How audio is quieted, while we are quieting it
+    quieted: Mutex<Option<Quieted>>,
+    /// Bumped whenever a fade starts, so an older fade stops
+    fade_generation: Arc<AtomicU64>,
+    /// Last volume a fade set, locked while a fade step changes it
+    last_volume: Arc<Mutex<f32>>,
 }
 
 impl AudioMuteManager {
@@ -104,11 +171,7 @@ impl AudioMuteManager {
     /// Returns None if audio control is not available on this platform.
     pub fn new() -> Option<Self> {
         match create_controller() {
-            Ok(controller) => Some(Self {
-                controller,
-                was_muted_before: AtomicBool::new(false),
-                is_currently_muting: AtomicBool::new(false),
-            }),
+            Ok(controller) => Some(Self::with_controller(Arc::from(controller))),
             Err(e) => {
                 log::warn!("Audio mute not available: {}", e);
                 None
@@ -116,58 +179,161 @@ impl AudioMuteManager {
         }
     }
 
-    /// Mute system audio for recording.
+    /// Create a manager for a specific controller
+    pub fn with_controller(controller: Arc<dyn SystemAudioControl>) -> Self {
+        Self {
+            controller,
+            quieted: Mutex::new(None),
+            fade_generation: Arc::new(AtomicU64::new(0)),
+            last_volume: Arc::new(Mutex::new(0.0)),
+        }
+    }
+
+    /// Quiet system audio for recording, muting it when `duck_percent` is 0 and
+    /// otherwise fading it down to that percentage of its current volume.
     ///
-    /// Saves the current mute state so it can be restored later.
-    /// If already muting, this is a no-op.
-    pub fn mute(&self) -> Result<(), AudioControlError> {
-        // Check if we're already muting
-        if self.is_currently_muting.swap(true, Ordering::SeqCst) {
-            return Ok(()); // Already muting, nothing to do
+    /// Saves the current state so it can be restored later.
+    /// If already quieting, this is a no-op.
+    pub fn mute(&self, duck_percent: u8) -> Result<(), AudioControlError> {
+        let Ok(mut quieted) = self.quieted.lock() else {
+            return Err(AudioControlError::SetPropertyFailed(
+                "Audio mute state is unavailable".to_string(),
+            ));
+        };
+        if quieted.is_some() {
+            return Ok(()); // Already quieting, nothing to do
         }
 
-        // Check current mute state and save it
+        // Check current mute state; muted audio has nothing to duck
         let was_muted = self.controller.is_muted().unwrap_or(false);
-        self.was_muted_before.store(was_muted, Ordering::SeqCst);
-
-        // Only mute if not already muted
-        if !was_muted {
-            self.controller.set_muted(true)?;
-            log::info!("System audio muted for recording");
-        } else {
-            log::info!("System audio already muted, skipping");
+        if was_muted || duck_percent == 0 {
+            if !was_muted {
+                self.controller.set_muted(true)?;
+                log::info!("System audio muted for recording");
+            } else {
+                log::info!("System audio already muted, skipping");
+            }
+            *quieted = Some(Quieted::Muted { was_muted });
+            return Ok(());
         }
 
+        let original = self.controller.volume()?;
+        let target = ducked_volume(original, duck_percent);
+        self.fade(original, target, DUCK_FADE);
+        log::info!(
+            "System audio ducked from {:.0}% to {:.0}% for recording",
+            original * 100.0,
+            target * 100.0
+        );
+        *quieted = Some(Quieted::Ducked { original });
         Ok(())
     }
 
-    /// Unmute system audio after recording.
+    /// Unmute or fade system audio back up after recording.
     ///
-    /// Only unmutes if we were the ones who muted it.
-    /// If not currently muting, this is a no-op.
+    /// Only restores what we changed, and leaves a volume the user changed
+    /// while recording alone. If not currently quieting, this is a no-op.
     pub fn unmute(&self) -> Result<(), AudioControlError> {
-        // Check if we're currently muting
-        if !self.is_currently_muting.swap(false, Ordering::SeqCst) {
-            return Ok(()); // Not muting, nothing to do
+        let Ok(mut quieted) = self.quieted.lock() else {
+            return Err(AudioControlError::SetPropertyFailed(
+                "Audio mute state is unavailable".to_string(),
+            ));
+        };
+        let Some(state) = quieted.take() else {
+            return Ok(()); // Not quieting, nothing to do
+        };
+
+        match state {
+            Quieted::Muted { was_muted: true } => {
+                log::info!("System audio was already muted, leaving muted");
+                Ok(())
+            }
+            Quieted::Muted { was_muted: false } => {
+                self.restore_unmuted()?;
+                log::info!("System audio unmuted after recording");
+                Ok(())
+            }
+            Quieted::Ducked { original } => {
+                // Stop the fade down, waiting out a step that is changing the volume
+                self.fade_generation.fetch_add(1, Ordering::SeqCst);
+                let last = self.last_volume.lock().map(|last| *last).unwrap_or(0.0);
+                let current = self.controller.volume()?;
+                if (current - last).abs() > VOLUME_TOLERANCE {
+                    log::info!("System volume changed while recording, leaving it");
+                    return Ok(());
+                }
+                self.fade(current, original, RESTORE_FADE);
+                log::info!("System audio restored after recording");
+                Ok(())
+            }
         }
+    }
 
-        // Only unmute if it wasn't already muted before we started
-        if !self.was_muted_before.load(Ordering::SeqCst) {
-            self.controller.set_muted(false)?;
-            log::info!("System audio unmuted after recording");
-        } else {
-            log::info!("System audio was already muted, leaving muted");
+    /// Unmute, retrying as the audio device can briefly refuse changes
+    fn restore_unmuted(&self) -> Result<(), AudioControlError> {
+        let mut attempt = 1;
+        loop {
+            match self.controller.set_muted(false) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < RESTORE_ATTEMPTS => {
+                    log::warn!("Failed to unmute audio (attempt {}): {}", attempt, e);
+                    attempt += 1;
+                    thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => return Err(e),
+            }
         }
+    }
 
-        Ok(())
+    /// Fade the volume from `from` to `to` in the background, replacing any
+    /// fade in progress
+    fn fade(&self, from: f32, to: f32, duration: Duration) {
+        let generation = self.fade_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Ok(mut last) = self.last_volume.lock() {
+            *last = from;
+        }
+        let controller = Arc::clone(&self.controller);
+        let fade_generation = Arc::clone(&self.fade_generation);
+        let last_volume = Arc::clone(&self.last_volume);
+        let step_delay = duration / FADE_STEPS as u32;
+        thread::spawn(move || {
+            for volume in fade_steps(from, to, FADE_STEPS) {
+                {
+                    let Ok(mut last) = last_volume.lock() else {
+                        return;
+                    };
+                    if fade_generation.load(Ordering::SeqCst) != generation {
+                        return;
+                    }
+                    if let Err(e) = controller.set_volume(volume) {
+                        log::warn!("Failed to fade audio: {}", e);
+                        return;
+                    }
+                    *last = volume;
+                }
+                thread::sleep(step_delay);
+            }
+        });
     }
 }
 
 impl Drop for AudioMuteManager {
     fn drop(&mut self) {
-        // Try to unmute on drop (app exit/crash)
-        if self.is_currently_muting.load(Ordering::SeqCst) {
-            let _ = self.unmute();
+        // Restore immediately on drop (app exit/crash), without fading
+        self.fade_generation.fetch_add(1, Ordering::SeqCst);
+        let quieted = self
+            .quieted
+            .get_mut()
+            .ok()
+            .and_then(|quieted| quieted.take());
+        match quieted {
+            Some(Quieted::Muted { was_muted: false }) => {
+                let _ = self.controller.set_muted(false);
+            }
+            Some(Quieted::Ducked { original }) => {
+                let _ = self.controller.set_volume(original);
+            }
+            _ => {}
         }
     }
 }
@@ -1,4 +1,4 @@
-//! Stub implementation for unsupported platforms (Linux, etc.)
+//! Stub implementation for unsupported platforms (BSDs, etc.)
 //!
 //! This provides a no-op implementation that logs warnings but doesn't fail.
 
@@ -39,4 +39,14 @@ impl SystemAudioControl for StubAudioController {
         self.warn_once();
         Ok(())
     }
+
+    fn volume(&self) -> Result<f32, AudioControlError> {
+        self.warn_once();
+        Ok(1.0) // Pretend full volume
+    }
+
+    fn set_volume(&self, _volume: f32) -> Result<(), AudioControlError> {
+        self.warn_once();
+        Ok(())
+    }
 }
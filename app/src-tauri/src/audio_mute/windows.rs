@@ -1,7 +1,7 @@
 //! Windows audio mute control implementation using WASAPI.
 //!
 //! Uses the Windows Audio Session API (WASAPI) to control the default audio
-//! output device's mute state.
+//! output device's mute state and volume.
 
 use super::{AudioControlError, SystemAudioControl};
 use windows::Win32::{
@@ -81,4 +81,30 @@ impl SystemAudioControl for WindowsAudioController {
                 .map_err(|e| AudioControlError::SetPropertyFailed(format!("SetMute: {}", e)))
         }
     }
+
+    fn volume(&self) -> Result<f32, AudioControlError> {
+        unsafe {
+            self.endpoint_volume
+                .GetMasterVolumeLevelScalar()
+                .map_err(|e| {
+                    AudioControlError::GetPropertyFailed(format!(
+                        "GetMasterVolumeLevelScalar: {}",
+                        e
+                    ))
+                })
+        }
+    }
+
+    fn set_volume(&self, volume: f32) -> Result<(), AudioControlError> {
+        unsafe {
+            self.endpoint_volume
+                .SetMasterVolumeLevelScalar(volume.clamp(0.0, 1.0), std::ptr::null())
+                .map_err(|e| {
+                    AudioControlError::SetPropertyFailed(format!(
+                        "SetMasterVolumeLevelScalar: {}",
+                        e
+                    ))
+                })
+        }
+    }
 }
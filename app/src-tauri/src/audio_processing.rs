@@ -0,0 +1,95 @@
+//! Noise suppression, echo cancellation and automatic gain control of captured audio.
+//!
+//! All are done by the WebRTC audio processing module on 10 ms frames of 48 kHz
+//! mono audio, between the microphone and the frames the rest of the app gets.
+//! Noise suppression also runs the transient suppressor, which takes out keyboard
+//! clicks. Echo cancellation removes what the speakers play from what the
+//! microphone hears, so it needs the audio being played as a reference; where
+//! the system can't provide it, it has no effect. Gain control evens out quiet
+//! and loud speech, as the webview's `autoGainControl` does.
+
+use crate::capture::FrameProcessor;
+use webrtc_audio_processing::{
+    Config, EchoCancellation, EchoCancellationSuppressionLevel, InitializationConfig,
+    NoiseSuppression, NoiseSuppressionLevel, Processor,
+};
+
+/// Sample rate audio is processed at
+pub const PROCESSING_SAMPLE_RATE: u32 = 48_000;
+
+/// Samples in each processed frame (10 ms)
+pub const PROCESSING_FRAME_LEN: usize = 480;
+
+/// Which processing is applied to captured audio, from settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessingOptions {
+    pub noise_suppression: bool,
+    pub echo_cancellation: bool,
+    pub auto_gain_control: bool,
+}
+
+impl ProcessingOptions {
+    pub fn is_enabled(&self) -> bool {
+        self.noise_suppression || self.echo_cancellation || self.auto_gain_control
+    }
+}
+
+/// WebRTC audio processing of the microphone. Clones share their state, so a
+/// clone can be given the playback reference from another thread.
+#[derive(Clone)]
+pub struct WebRtcProcessor {
+    processor: Processor,
+}
+
+impl WebRtcProcessor {
+    /// A processor applying `options`, or `None` if they turn everything off
+    pub fn new(options: ProcessingOptions) -> Result<Option<Self>, String> {
+        if !options.is_enabled() {
+            return Ok(None);
+        }
+        let mut processor = Processor::new(&InitializationConfig {
+            num_capture_channels: 1,
+            num_render_channels: 1,
+            ..InitializationConfig::default()
+        })
+        .map_err(|e| format!("Failed to start audio processing: {:?}", e))?;
+        processor.set_config(Config {
+            echo_cancellation: options.echo_cancellation.then_some(EchoCancellation {
+                suppression_level: EchoCancellationSuppressionLevel::High,
+                enable_extended_filter: true,
+                // The delay between playback and capture isn't known
+                enable_delay_agnostic: true,
+                stream_delay_ms: None,
+            }),
+            // WebRTC's defaults: aim for -3 dBFS, boosting quiet speech by up to 9 dB
+            gain_control: options.auto_gain_control.then_some(GainControl {
+                mode: GainControlMode::AdaptiveDigital,
+                target_level_dbfs: 3,
+                compression_gain_db: 9,
+                enable_limiter: true,
+            }),
+            noise_suppression: options.noise_suppression.then_some(NoiseSuppression {
+                suppression_level: NoiseSuppressionLevel::High,
+            }),
+            enable_transient_suppressor: options.noise_suppression,
+            enable_high_pass_filter: true,
+            ..Config::default()
+        });
+        Ok(Some(Self { processor }))
+    }
+
+    /// Give echo cancellation a frame of what is being played
+    pub fn process_render_frame(&mut self, frame: &mut [f32]) {
+        if let Err(e) = self.processor.process_render_frame(frame) {
+            log::warn!("Failed to process playback audio: {:?}", e);
+        }
+    }
+}
+
+impl FrameProcessor for WebRtcProcessor {
+    fn process(&mut self, frame: &mut [f32]) {
+        if let Err(e) = self.processor.process_capture_frame(frame) {
+            log::warn!("Failed to process captured audio: {:?}", e);
+        }
+    }
+}
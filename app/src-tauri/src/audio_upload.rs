@@ -0,0 +1,124 @@
+//! Encoding of audio the app uploads to the server itself.
+//!
+//! Live dictation reaches the server over WebRTC, which compresses it already.
+//! Audio replayed later, such as dictations queued while offline and retried
+//! recordings, goes over the data channel instead, where 16-bit PCM at 16 kHz
+//! takes 256 kbit/s. Servers that list Opus among their `audio_encodings` get it
+//! as Opus packets instead, at the `opus_bitrate_kbps` setting.
+
+use audiopus::coder::Encoder;
+use audiopus::{Application, Bitrate, Channels, SampleRate};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Opus bitrate used when none is set, plenty for speech
+pub const DEFAULT_OPUS_BITRATE_KBPS: u32 = 24;
+
+/// Bitrate range offered in settings
+pub const MIN_OPUS_BITRATE_KBPS: u32 = 6;
+pub const MAX_OPUS_BITRATE_KBPS: u32 = 128;
+
+/// Length of the audio in each Opus packet
+pub const OPUS_FRAME_DURATION: Duration = Duration::from_millis(20);
+
+/// Largest packet the encoder is allowed to produce, as recommended by libopus
+const MAX_PACKET_BYTES: usize = 4000;
+
+/// How uploaded audio is encoded, named as in the server's `audio_encodings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadEncoding {
+    Pcm16,
+    Opus,
+}
+
+impl UploadEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pcm16 => "pcm16",
+            Self::Opus => "opus",
+        }
+    }
+}
+
+/// Opus bitrate from settings, within [`MIN_OPUS_BITRATE_KBPS`] and
+/// [`MAX_OPUS_BITRATE_KBPS`]
+pub fn clamp_opus_bitrate(kbps: u32) -> u32 {
+    kbps.clamp(MIN_OPUS_BITRATE_KBPS, MAX_OPUS_BITRATE_KBPS)
+}
+
+fn opus_sample_rate(sample_rate: u32) -> Option<SampleRate> {
+    match sample_rate {
+        8_000 => Some(SampleRate::Hz8000),
+        12_000 => Some(SampleRate::Hz12000),
+        16_000 => Some(SampleRate::Hz16000),
+        24_000 => Some(SampleRate::Hz24000),
+        48_000 => Some(SampleRate::Hz48000),
+        _ => None,
+    }
+}
+
+/// How to upload mono audio at `sample_rate` to a server that decodes
+/// `server_encodings`. Opus when the server decodes it and the rate is one Opus
+/// takes, otherwise PCM, which every server decodes.
+pub fn negotiate_encoding(server_encodings: &[String], sample_rate: u32) -> UploadEncoding {
+    let server_decodes_opus = server_encodings
+        .iter()
+        .any(|encoding| encoding == UploadEncoding::Opus.as_str());
+    if server_decodes_opus && opus_sample_rate(sample_rate).is_some() {
+        UploadEncoding::Opus
+    } else {
+        UploadEncoding::Pcm16
+    }
+}
+
+/// Encode mono `samples` at `sample_rate` to Opus packets of
+/// [`OPUS_FRAME_DURATION`], padding the last with silence
+pub fn encode_opus(
+    sample_rate: u32,
+    samples: &[i16],
+    bitrate_kbps: u32,
+) -> Result<Vec<Vec<u8>>, String> {
+    let rate = opus_sample_rate(sample_rate)
+        .ok_or_else(|| format!("Opus can't encode audio at {} Hz", sample_rate))?;
+    let mut encoder = Encoder::new(rate, Channels::Mono, Application::Voip)
+        .map_err(|e| format!("Failed to start the Opus encoder: {}", e))?;
+    let bits_per_second = clamp_opus_bitrate(bitrate_kbps) * 1000;
+    encoder
+        .set_bitrate(Bitrate::BitsPerSecond(bits_per_second as i32))
+        .map_err(|e| format!("Failed to set the Opus bitrate: {}", e))?;
+
+    let frame_len = (u128::from(sample_rate) * OPUS_FRAME_DURATION.as_millis() / 1000) as usize;
+    let mut frame = vec![0; frame_len];
+    let mut output = vec![0; MAX_PACKET_BYTES];
+    let mut packets = Vec::with_capacity(samples.len().div_ceil(frame_len));
+    for chunk in samples.chunks(frame_len) {
+        frame[..chunk.len()].copy_from_slice(chunk);
+        frame[chunk.len()..].fill(0);
+        let len = encoder
+            .encode(&frame, &mut output)
+            .map_err(|e| format!("Failed to encode audio to Opus: {}", e))?;
+        packets.push(output[..len].to_vec());
+    }
+    Ok(packets)
+}
+
+/// Encodings the connected server decodes, as listed with its providers
+#[derive(Default)]
+pub struct ServerAudioEncodings(Mutex<Vec<String>>);
+
+impl ServerAudioEncodings {
+    pub fn get(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|encodings| encodings.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, encodings: Vec<String>) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = encodings;
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Server auth token storage and refresh.
+//!
+//! Servers that require sign-in hand out an access token, usually with a refresh
+//! token and an expiry. Both are kept in the OS keyring next to the provider API
+//! keys, never in `settings.json`. The expiry comes from the server or, for JWTs,
+//! from the token's `exp` claim. Shortly before it passes, the access token is
+//! refreshed through the server's `/api/auth/refresh` endpoint.
+
+use crate::proxy::{self, ProxyConfig};
+use crate::secrets::KEYRING_SERVICE;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, TimeDelta, Utc};
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Keyring account holding the token
+const KEYRING_ACCOUNT: &str = "auth-token";
+
+/// How long before expiry the access token is refreshed
+pub const REFRESH_BEFORE_EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+/// Wait before retrying a failed refresh, while the token is still valid
+pub const REFRESH_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Timeout for a refresh request
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Tokens issued by the server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AuthToken {
+    /// A token entered by the user or returned by the server. The expiry is
+    /// `expires_in` from `now` if given, otherwise read from a JWT access token.
+    pub fn new(
+        access_token: &str,
+        refresh_token: Option<&str>,
+        expires_in: Option<Duration>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        let access_token = access_token.trim();
+        if access_token.is_empty() {
+            return Err("Auth token cannot be empty".to_string());
+        }
+        let expires_at = match expires_in {
+            Some(expires_in) => TimeDelta::from_std(expires_in)
+                .ok()
+                .and_then(|expires_in| now.checked_add_signed(expires_in)),
+            None => jwt_expiry(access_token),
+        };
+        Ok(Self {
+            access_token: access_token.to_string(),
+            refresh_token: refresh_token
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(String::from),
+            expires_at,
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Time until the access token should be refreshed, or `None` if it never
+    /// expires or can't be refreshed
+    pub fn refresh_in(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.refresh_token.as_ref()?;
+        let refresh_at = self.expires_at? - TimeDelta::from_std(REFRESH_BEFORE_EXPIRY).ok()?;
+        Some((refresh_at - now).to_std().unwrap_or(Duration::ZERO))
+    }
+}
+
+/// The `exp` claim of a JWT, if the token is one
+pub fn jwt_expiry(token: &str) -> Option<DateTime<Utc>> {
+    let mut parts = token.split('.');
+    let (_, payload, _) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+    DateTime::from_timestamp(claims.get("exp")?.as_i64()?, 0)
+}
+
+/// Auth token state shown to the frontend, without the token itself
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AuthStatus {
+    pub is_set: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub is_expired: bool,
+    pub can_refresh: bool,
+    /// Why the last refresh failed, until a token is stored again
+    pub refresh_error: Option<String>,
+}
+
+impl AuthStatus {
+    pub fn new(
+        token: Option<&AuthToken>,
+        refresh_error: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let Some(token) = token else {
+            return Self::default();
+        };
+        Self {
+            is_set: true,
+            expires_at: token.expires_at,
+            is_expired: token.is_expired(now),
+            can_refresh: token.refresh_token.is_some(),
+            refresh_error,
+        }
+    }
+}
+
+fn entry() -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .map_err(|e| format!("Failed to open keyring entry: {}", e))
+}
+
+/// Store the token in the keyring, replacing any existing one
+pub fn save(token: &AuthToken) -> Result<(), String> {
+    let json = serde_json::to_string(token).map_err(|e| e.to_string())?;
+    entry()?
+        .set_password(&json)
+        .map_err(|e| format!("Failed to store auth token: {}", e))
+}
+
+/// The stored token, if any
+pub fn load() -> Result<Option<AuthToken>, String> {
+    match entry()?.get_password() {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Stored auth token is unreadable: {}", e)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read auth token: {}", e)),
+    }
+}
+
+/// Delete the stored token. Returns false if none was stored.
+pub fn delete() -> Result<bool, String> {
+    match entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(format!("Failed to delete auth token: {}", e)),
+    }
+}
+
+#[derive(Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// Lifetime of the new access token in seconds
+    expires_in: Option<u64>,
+}
+
+/// Exchange the refresh token for a new access token. The refresh token is kept
+/// unless the server rotates it.
+pub fn refresh(
+    server_url: &str,
+    token: &AuthToken,
+    proxy: Option<&ProxyConfig>,
+) -> Result<AuthToken, String> {
+    let refresh_token = token
+        .refresh_token
+        .as_deref()
+        .ok_or_else(|| "Auth token has no refresh token".to_string())?;
+    let client = proxy::with_proxy(reqwest::blocking::Client::builder(), proxy)?
+        .timeout(REFRESH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response: RefreshResponse = client
+        .post(format!(
+            "{}/api/auth/refresh",
+            server_url.trim().trim_end_matches('/')
+        ))
+        .json(&serde_json::json!({ "refresh_token": refresh_token }))
+        .send()
+        .map_err(|e| format!("Token refresh request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Server rejected the token refresh: {}", e))?
+        .json()
+        .map_err(|e| format!("Invalid token refresh response: {}", e))?;
+    AuthToken::new(
+        &response.access_token,
+        response.refresh_token.as_deref().or(Some(refresh_token)),
+        response.expires_in.map(Duration::from_secs),
+        Utc::now(),
+    )
+}
+
+/// Which scheduled refresh is current, and why the last one failed
+#[derive(Default)]
+pub struct AuthRefresh {
+    generation: AtomicU64,
+    error: Mutex<Option<String>>,
+}
+
+impl AuthRefresh {
+    /// Supersede any scheduled refresh, returning the generation of the next one
+    pub fn next_generation(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().ok().and_then(|error| error.clone())
+    }
+
+    pub fn set_error(&self, error: Option<String>) {
+        if let Ok(mut current) = self.error.lock() {
+            *current = error;
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! End-to-end latency benchmark.
+//!
+//! A benchmark replays a fixed piece of audio through the configured STT and
+//! formatting providers and times each stage, so providers can be compared on
+//! real numbers. The audio is either a synthetic voice-like signal or the last
+//! dictation, so nothing has to be spoken while the benchmark runs.
+
+use crate::stt::AudioFormat;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+/// Length of the synthetic audio
+pub const SYNTHETIC_AUDIO_DURATION: Duration = Duration::from_secs(3);
+
+/// Text formatted when the audio produced no transcript, as synthetic audio usually
+/// doesn't. Has the filler words and missing punctuation of a typical dictation.
+pub const BENCHMARK_TEXT: &str = "um so this is a quick test of the dictation pipeline \
+uh it should come back with punctuation and without the filler words";
+
+/// Audio to benchmark with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkSource {
+    /// A generated voice-like signal
+    #[default]
+    Synthetic,
+    /// The last dictation, or the newest archived recording
+    Recorded,
+}
+
+/// How long each stage of one benchmark run took, in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StageTimings {
+    /// Preparing the audio, as the overlay does when recording stops
+    pub capture_ms: u64,
+    /// Round trip of a health check to the server, `None` if it's unreachable
+    pub network_ms: Option<u64>,
+    /// Transcription, including formatting when `stt_includes_formatting`
+    pub stt_ms: u64,
+    /// Formatting the transcript with the formatter chain
+    pub formatting_ms: u64,
+    /// Putting the text on the clipboard and restoring it, as paste insertion does.
+    /// No keys are pressed.
+    pub insertion_ms: u64,
+}
+
+impl StageTimings {
+    /// Time from the end of recording until the text is inserted. The network
+    /// round trip is already part of the transcription and formatting times.
+    pub fn total_ms(&self, stt_includes_formatting: bool) -> u64 {
+        let formatting_ms = if stt_includes_formatting {
+            0
+        } else {
+            self.formatting_ms
+        };
+        self.capture_ms + self.stt_ms + formatting_ms + self.insertion_ms
+    }
+}
+
+/// Result of one benchmark run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LatencyBenchmark {
+    pub source: BenchmarkSource,
+    /// Length of the audio transcribed
+    pub audio_ms: u64,
+    /// STT provider that transcribed the audio
+    pub stt_provider: String,
+    /// Server LLM provider, `None` for the server's default
+    pub llm_provider: Option<String>,
+    /// Whether the server transcribed and formatted the audio in one turn, so
+    /// `stt_ms` includes formatting and `formatting_ms` was measured separately
+    pub stt_includes_formatting: bool,
+    pub timings: StageTimings,
+    pub total_ms: u64,
+    /// What the audio was transcribed as, empty if nothing was recognized
+    pub transcript: String,
+    /// Formatted text
+    pub formatted: String,
+}
+
+/// `duration` of a voice-like signal: a 140 Hz tone with a few harmonics, pulsing
+/// at about four syllables a second so voice activity detection treats it as speech
+pub fn synthetic_audio(format: AudioFormat, duration: Duration) -> Vec<f32> {
+    let sample_rate = format.sample_rate.max(1) as f32;
+    let channels = usize::from(format.channels.max(1));
+    let frames = (duration.as_secs_f32() * sample_rate) as usize;
+
+    let mut samples = Vec::with_capacity(frames * channels);
+    for frame in 0..frames {
+        let t = frame as f32 / sample_rate;
+        let voice: f32 = [(1.0, 1.0), (2.0, 0.5), (3.0, 0.3), (5.0, 0.15)]
+            .iter()
+            .map(|(harmonic, gain)| gain * (TAU * 140.0 * harmonic * t).sin())
+            .sum();
+        let envelope = (TAU * 4.0 * t).sin().max(0.0);
+        let sample = 0.2 * voice * envelope;
+        samples.extend(std::iter::repeat_n(sample, channels));
+    }
+    samples
+}
+
+/// Milliseconds in `duration`, saturating
+pub fn as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,637 @@
+//! Microphone capture in the backend.
+//!
+//! The microphone is opened here with cpal rather than by the overlay webview, so
+//! every part of the app hears the same audio as soon as it is captured. It is
+//! opened in its default format unless the `sample_rate` and `channels` settings
+//! ask for one it supports (see [`pick_format`]). Frames are converted to 16 kHz
+//! mono, cleaned up by noise suppression and echo cancellation
+//! when they are on (see [`crate::audio_processing`]) and amplified by the input
+//! gain. They are then sent to the overlay, which plays them into the audio track
+//! of its server connection, and fed to in-app transcription, the pre-roll buffer
+//! and the silence detector. The level of each frame is shared with the UI.
+//!
+//! cpal streams can't move between threads on every platform, so each capture
+//! runs on its own thread holding its streams until it is stopped.
+
+use crate::audio_processing::{
+    ProcessingOptions, WebRtcProcessor, PROCESSING_FRAME_LEN, PROCESSING_SAMPLE_RATE,
+};
+use crate::stt::AudioFormat;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SampleFormat, SizedSample};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Format of captured frames, the format in-app transcription expects
+pub const CAPTURE_FORMAT: AudioFormat = AudioFormat::WHISPER;
+
+/// Length of each frame passed on from the microphone
+pub const FRAME_DURATION: Duration = Duration::from_millis(40);
+
+/// Input gain range, matching the gain slider
+pub const MIN_MIC_GAIN: f32 = 0.5;
+pub const MAX_MIC_GAIN: f32 = 4.0;
+
+/// Input gain from settings, within [`MIN_MIC_GAIN`] and [`MAX_MIC_GAIN`]
+pub fn clamp_mic_gain(gain: f32) -> f32 {
+    if gain.is_finite() {
+        gain.clamp(MIN_MIC_GAIN, MAX_MIC_GAIN)
+    } else {
+        1.0
+    }
+}
+
+/// Average the channels of interleaved `samples` into one
+pub fn downmix(samples: &[f32], channels: u16) -> Vec<f32> {
+    let channels = usize::from(channels.max(1));
+    if channels == 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Multiply `samples` by `gain`, clipping to the valid range
+pub fn apply_gain(samples: &mut [f32], gain: f32) {
+    if gain == 1.0 {
+        return;
+    }
+    for sample in samples {
+        *sample = (*sample * gain).clamp(-1.0, 1.0);
+    }
+}
+
+/// RMS level of `samples`, from 0 (silence) to 1
+pub fn rms_level(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = samples.iter().map(|sample| sample * sample).sum();
+    (sum / samples.len() as f32).sqrt().min(1.0)
+}
+
+/// Peak level of `samples`, the loudest sample from 0 (silence) to 1
+pub fn peak_level(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()))
+        .min(1.0)
+}
+
+/// `samples` as little-endian 16-bit PCM, half the size of the float samples
+pub fn pcm16_bytes(samples: &[f32]) -> Vec<u8> {
+    crate::recordings::to_pcm16(samples)
+        .into_iter()
+        .flat_map(i16::to_le_bytes)
+        .collect()
+}
+
+/// Converts mono audio between sample rates by linear interpolation, carrying
+/// its position over from one chunk to the next
+#[derive(Debug)]
+pub struct Resampler {
+    /// Input samples per output sample
+    step: f64,
+    /// Position of the next output sample, where 0 is the last sample of the
+    /// previous chunk and 1 the first of the next
+    position: f64,
+    last: f32,
+}
+
+impl Resampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            step: f64::from(from_rate.max(1)) / f64::from(to_rate.max(1)),
+            position: 1.0,
+            last: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        if self.step == 1.0 {
+            return input.to_vec();
+        }
+        let sample_at = |index: usize| match index {
+            0 => self.last,
+            index => input[index - 1],
+        };
+        let mut output = Vec::with_capacity((input.len() as f64 / self.step) as usize + 1);
+        while (self.position as usize) < input.len() {
+            let index = self.position as usize;
+            let fraction = (self.position - index as f64) as f32;
+            let (a, b) = (sample_at(index), sample_at(index + 1));
+            output.push(a + (b - a) * fraction);
+            self.position += self.step;
+        }
+        if let Some(&last) = input.last() {
+            self.position -= input.len() as f64;
+            self.last = last;
+        }
+        output
+    }
+}
+
+/// Split the complete `len` sample chunks off the front of `pending`
+pub fn take_chunks(pending: &mut Vec<f32>, len: usize) -> Vec<Vec<f32>> {
+    let mut chunks = Vec::new();
+    while len > 0 && pending.len() >= len {
+        let rest = pending.split_off(len);
+        chunks.push(std::mem::replace(pending, rest));
+    }
+    chunks
+}
+
+/// Processing such as noise suppression, applied in place to mono frames of
+/// [`PROCESSING_FRAME_LEN`] samples at [`PROCESSING_SAMPLE_RATE`]
+pub trait FrameProcessor: Send {
+    fn process(&mut self, frame: &mut [f32]);
+}
+
+/// Audio on its way through a [`FrameProcessor`]
+struct ProcessingStage {
+    to_processing: Resampler,
+    pending: Vec<f32>,
+    processor: Box<dyn FrameProcessor>,
+    from_processing: Resampler,
+}
+
+impl ProcessingStage {
+    fn process(&mut self, mono: &[f32]) -> Vec<f32> {
+        self.pending.extend(self.to_processing.process(mono));
+        let mut processed = Vec::new();
+        for mut frame in take_chunks(&mut self.pending, PROCESSING_FRAME_LEN) {
+            self.processor.process(&mut frame);
+            processed.extend(self.from_processing.process(&frame));
+        }
+        processed
+    }
+}
+
+/// Turns audio from the microphone, in whatever format it delivers, into
+/// [`CAPTURE_FORMAT`] frames of [`FRAME_DURATION`], passing it through a
+/// [`FrameProcessor`] on the way if one is given
+pub struct FrameConverter {
+    channels: u16,
+    resampler: Resampler,
+    processing: Option<ProcessingStage>,
+    frame_len: usize,
+    pending: Vec<f32>,
+}
+
+impl FrameConverter {
+    pub fn new(
+        sample_rate: u32,
+        channels: u16,
+        processor: Option<Box<dyn FrameProcessor>>,
+    ) -> Self {
+        let frame_len =
+            (u128::from(CAPTURE_FORMAT.sample_rate) * FRAME_DURATION.as_millis() / 1000) as usize;
+        let processing = processor.map(|processor| ProcessingStage {
+            to_processing: Resampler::new(sample_rate, PROCESSING_SAMPLE_RATE),
+            pending: Vec::with_capacity(PROCESSING_FRAME_LEN),
+            processor,
+            from_processing: Resampler::new(PROCESSING_SAMPLE_RATE, CAPTURE_FORMAT.sample_rate),
+        });
+        Self {
+            channels,
+            resampler: Resampler::new(sample_rate, CAPTURE_FORMAT.sample_rate),
+            processing,
+            frame_len,
+            pending: Vec::with_capacity(frame_len),
+        }
+    }
+
+    /// The frames completed by `samples`, which are interleaved in the device's
+    /// format. Audio left over waits for the next call.
+    pub fn push(&mut self, samples: &[f32]) -> Vec<Vec<f32>> {
+        let mono = downmix(samples, self.channels);
+        let converted = match &mut self.processing {
+            Some(processing) => processing.process(&mono),
+            None => self.resampler.process(&mono),
+        };
+        self.pending.extend(converted);
+        take_chunks(&mut self.pending, self.frame_len)
+    }
+}
+
+/// Index of the device in `names` that best matches `wanted`, a device name or
+/// the label the webview gave it. Labels often add to the name, such as
+/// "Default - MacBook Pro Microphone", so containment counts as a match.
+pub fn pick_device(names: &[String], wanted: Option<&str>) -> Option<usize> {
+    let wanted = wanted.map(str::trim).filter(|wanted| !wanted.is_empty())?;
+    let wanted_lower = wanted.to_lowercase();
+    names
+        .iter()
+        .position(|name| name == wanted)
+        .or_else(|| {
+            names
+                .iter()
+                .position(|name| name.to_lowercase() == wanted_lower)
+        })
+        .or_else(|| {
+            names.iter().position(|name| {
+                let name = name.to_lowercase();
+                !name.is_empty() && (wanted_lower.contains(&name) || name.contains(&wanted_lower))
+            })
+        })
+}
+
+/// Format to open the microphone in, from the `sample_rate` and `channels`
+/// settings. Either left as `None` is the device's default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamFormat {
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+}
+
+impl StreamFormat {
+    pub fn is_default(&self) -> bool {
+        self.sample_rate.is_none() && self.channels.is_none()
+    }
+}
+
+/// Channel count and sample rates a microphone can be opened with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FormatRange {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+}
+
+impl FormatRange {
+    fn contains(&self, sample_rate: u32) -> bool {
+        (self.min_sample_rate..=self.max_sample_rate).contains(&sample_rate)
+    }
+}
+
+/// Index of the range in `ranges` to open a device with for `wanted`, and the
+/// sample rate to open it at. A rate that isn't asked for stays at the device's
+/// `default_rate` where the range allows, or the nearest rate it does. `None` if
+/// the device supports nothing matching.
+pub fn pick_format(
+    ranges: &[FormatRange],
+    wanted: StreamFormat,
+    default_rate: u32,
+) -> Option<(usize, u32)> {
+    let channels_match = |range: &FormatRange| {
+        wanted
+            .channels
+            .is_none_or(|channels| channels == range.channels)
+    };
+    if let Some(rate) = wanted.sample_rate {
+        return ranges
+            .iter()
+            .position(|range| channels_match(range) && range.contains(rate))
+            .map(|index| (index, rate));
+    }
+    ranges
+        .iter()
+        .position(|range| channels_match(range) && range.contains(default_rate))
+        .or_else(|| ranges.iter().position(channels_match))
+        .map(|index| {
+            let range = ranges[index];
+            let rate = default_rate.clamp(range.min_sample_rate, range.max_sample_rate);
+            (index, rate)
+        })
+}
+
+/// Describe `format` for messages, such as "48000 Hz stereo"
+pub fn describe_format(format: StreamFormat) -> String {
+    let rate = format
+        .sample_rate
+        .map_or("the default rate".to_string(), |rate| {
+            format!("{} Hz", rate)
+        });
+    match format.channels {
+        Some(1) => format!("{} mono", rate),
+        Some(2) => format!("{} stereo", rate),
+        Some(channels) => format!("{} with {} channels", rate, channels),
+        None => rate,
+    }
+}
+
+/// The microphone capture currently running, if any
+pub struct NativeCapture {
+    /// Stops the capture thread, which drops the stream
+    stop: Mutex<Option<mpsc::Sender<()>>>,
+    /// Input gain, as `f32` bits so the audio thread can read it without locking
+    gain: AtomicU32,
+}
+
+impl Default for NativeCapture {
+    fn default() -> Self {
+        Self {
+            stop: Mutex::new(None),
+            gain: AtomicU32::new(1.0_f32.to_bits()),
+        }
+    }
+}
+
+impl NativeCapture {
+    pub fn gain(&self) -> f32 {
+        f32::from_bits(self.gain.load(Ordering::Relaxed))
+    }
+
+    pub fn set_gain(&self, gain: f32) {
+        self.gain
+            .store(clamp_mic_gain(gain).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.stop.lock().is_ok_and(|stop| stop.is_some())
+    }
+
+    /// Open the microphone best matching `device_label`, or the system default, in
+    /// `format`, replacing any capture already running, and process its audio as
+    /// `options` ask. `on_frame` gets each frame on the audio thread. Returns the
+    /// name of the device opened.
+    pub fn start(
+        &self,
+        device_label: Option<&str>,
+        format: StreamFormat,
+        options: ProcessingOptions,
+        on_frame: impl FnMut(Vec<f32>) + Send + 'static,
+    ) -> Result<String, String> {
+        self.stop();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let device_label = device_label.map(str::to_string);
+        thread::Builder::new()
+            .name("mic-capture".to_string())
+            .spawn(move || {
+                let opened = open_streams(device_label.as_deref(), format, options, on_frame);
+                let streams = match opened {
+                    Ok((streams, name)) => {
+                        let _ = ready_tx.send(Ok(name));
+                        streams
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                // Returns once stopped, or when the sender is dropped
+                let _ = stop_rx.recv();
+                drop(streams);
+            })
+            .map_err(|e| format!("Failed to start microphone thread: {}", e))?;
+
+        let name = ready_rx
+            .recv()
+            .map_err(|_| "Microphone thread exited".to_string())??;
+        *self.stop.lock().map_err(|e| e.to_string())? = Some(stop_tx);
+        Ok(name)
+    }
+
+    /// Close the microphone, if it is open
+    pub fn stop(&self) {
+        if let Ok(mut stop) = self.stop.lock() {
+            if let Some(stop) = stop.take() {
+                let _ = stop.send(());
+            }
+        }
+    }
+}
+
+/// Tracks the overlay sending the last of a turn's audio after it was asked to stop
+/// capturing, so the turn is only transcribed once all of it has arrived
+#[derive(Default)]
+pub struct LocalCaptureFlush {
+    /// Last stop requested and last one the overlay confirmed
+    ids: Mutex<(u64, u64)>,
+    flushed: Condvar,
+}
+
+impl LocalCaptureFlush {
+    /// Start a stop request, returning the ID the overlay confirms it with
+    pub fn request(&self) -> u64 {
+        let Ok(mut ids) = self.ids.lock() else {
+            return 0;
+        };
+        ids.0 += 1;
+        ids.0
+    }
+
+    /// The overlay has sent everything it captured before stop request `id`
+    pub fn confirm(&self, id: u64) {
+        if let Ok(mut ids) = self.ids.lock() {
+            ids.1 = ids.1.max(id);
+        }
+        self.flushed.notify_all();
+    }
+
+    /// Wait up to `timeout` for stop request `id` to be confirmed.
+    /// Returns false if it wasn't.
+    pub fn wait(&self, id: u64, timeout: Duration) -> bool {
+        let Ok(ids) = self.ids.lock() else {
+            return false;
+        };
+        self.flushed
+            .wait_timeout_while(ids, timeout, |ids| ids.1 < id)
+            .is_ok_and(|(_, result)| !result.timed_out())
+    }
+}
+
+/// The formats the microphone best matching `device_label`, or the system
+/// default, can be opened in, and its name
+pub fn device_formats(device_label: Option<&str>) -> Result<(String, Vec<FormatRange>), String> {
+    let (device, name) = find_device(&cpal::default_host(), device_label)?;
+    let ranges = supported_ranges(&device, &name)?
+        .iter()
+        .map(format_range)
+        .collect();
+    Ok((name, ranges))
+}
+
+fn find_device(
+    host: &cpal::Host,
+    device_label: Option<&str>,
+) -> Result<(cpal::Device, String), String> {
+    let devices: Vec<cpal::Device> = host
+        .input_devices()
+        .map_err(|e| format!("Failed to list microphones: {}", e))?
+        .collect();
+    let names: Vec<String> = devices
+        .iter()
+        .map(|device| device.name().unwrap_or_default())
+        .collect();
+    let device = match pick_device(&names, device_label) {
+        Some(index) => devices.into_iter().nth(index),
+        None => {
+            if let Some(label) = device_label {
+                log::warn!("No microphone matches {}, using the default", label);
+            }
+            host.default_input_device()
+        }
+    }
+    .ok_or_else(|| "No microphone found".to_string())?;
+    let name = device
+        .name()
+        .unwrap_or_else(|_| "Unknown microphone".to_string());
+    Ok((device, name))
+}
+
+/// The device's input formats, most suitable first
+fn supported_ranges(
+    device: &cpal::Device,
+    name: &str,
+) -> Result<Vec<cpal::SupportedStreamConfigRange>, String> {
+    let mut ranges: Vec<_> = device
+        .supported_input_configs()
+        .map_err(|e| format!("Failed to read the formats of {}: {}", name, e))?
+        .collect();
+    ranges.sort_by(|a, b| b.cmp_default_heuristics(a));
+    Ok(ranges)
+}
+
+fn format_range(range: &cpal::SupportedStreamConfigRange) -> FormatRange {
+    FormatRange {
+        channels: range.channels(),
+        min_sample_rate: range.min_sample_rate().0,
+        max_sample_rate: range.max_sample_rate().0,
+    }
+}
+
+/// The config to open `device` with for `format`, falling back to its default
+/// when it doesn't support `format`
+fn stream_config(
+    device: &cpal::Device,
+    name: &str,
+    format: StreamFormat,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    let default = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to read the format of {}: {}", name, e))?;
+    if format.is_default() {
+        return Ok(default);
+    }
+    let ranges = supported_ranges(device, name)?;
+    let format_ranges: Vec<FormatRange> = ranges.iter().map(format_range).collect();
+    match pick_format(&format_ranges, format, default.sample_rate().0) {
+        Some((index, rate)) => Ok(ranges[index].with_sample_rate(cpal::SampleRate(rate))),
+        None => {
+            log::warn!(
+                "{} can't capture {}, using its default format",
+                name,
+                describe_format(format)
+            );
+            Ok(default)
+        }
+    }
+}
+
+/// Open the microphone in `format` and, for echo cancellation, a capture of what
+/// the default output device plays. Returns the streams, which stop when dropped,
+/// and the name of the microphone.
+fn open_streams(
+    device_label: Option<&str>,
+    format: StreamFormat,
+    options: ProcessingOptions,
+    mut on_frame: impl FnMut(Vec<f32>) + Send + 'static,
+) -> Result<(Vec<cpal::Stream>, String), String> {
+    let host = cpal::default_host();
+    let (device, name) = find_device(&host, device_label)?;
+
+    let processor = WebRtcProcessor::new(options)?;
+    let mut streams = Vec::new();
+    if let Some(processor) = processor.clone().filter(|_| options.echo_cancellation) {
+        match open_echo_reference(&host, processor) {
+            Ok(stream) => streams.push(stream),
+            Err(e) => log::warn!("Echo cancellation has no playback to compare with: {}", e),
+        }
+    }
+
+    let config = stream_config(&device, &name, format)?;
+    let (sample_rate, channels) = (config.sample_rate().0, config.channels());
+    let mut converter = FrameConverter::new(
+        sample_rate,
+        channels,
+        processor.map(|processor| Box::new(processor) as Box<dyn FrameProcessor>),
+    );
+    let stream = build_stream(&device, config, move |samples| {
+        for frame in converter.push(samples) {
+            on_frame(frame);
+        }
+    })
+    .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+    streams.insert(0, stream);
+    log::info!(
+        "Capturing from microphone {} at {} Hz with {} channels",
+        name,
+        sample_rate,
+        channels
+    );
+    Ok((streams, name))
+}
+
+/// Capture what the default output device plays and give it to echo cancellation.
+/// Only some systems, such as Windows, can capture an output device.
+fn open_echo_reference(
+    host: &cpal::Host,
+    mut processor: WebRtcProcessor,
+) -> Result<cpal::Stream, String> {
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| "No output device".to_string())?;
+    let config = device.default_output_config().map_err(|e| e.to_string())?;
+    let channels = config.channels();
+    let mut resampler = Resampler::new(config.sample_rate().0, PROCESSING_SAMPLE_RATE);
+    let mut pending = Vec::with_capacity(PROCESSING_FRAME_LEN);
+    build_stream(&device, config, move |samples| {
+        pending.extend(resampler.process(&downmix(samples, channels)));
+        for mut frame in take_chunks(&mut pending, PROCESSING_FRAME_LEN) {
+            processor.process_render_frame(&mut frame);
+        }
+    })
+}
+
+/// Start a stream from `device` in its own `config`, passing its audio on as
+/// interleaved `f32` samples
+fn build_stream(
+    device: &cpal::Device,
+    config: cpal::SupportedStreamConfig,
+    on_samples: impl FnMut(&[f32]) + Send + 'static,
+) -> Result<cpal::Stream, String> {
+    let sample_format = config.sample_format();
+    let config = config.config();
+    let stream = match sample_format {
+        SampleFormat::F32 => build_typed_stream::<f32>(device, &config, on_samples),
+        SampleFormat::I16 => build_typed_stream::<i16>(device, &config, on_samples),
+        SampleFormat::I32 => build_typed_stream::<i32>(device, &config, on_samples),
+        SampleFormat::U16 => build_typed_stream::<u16>(device, &config, on_samples),
+        other => Err(format!("Unsupported sample format {}", other)),
+    }?;
+    stream.play().map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+fn build_typed_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut on_samples: impl FnMut(&[f32]) + Send + 'static,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                let samples: Vec<f32> = data
+                    .iter()
+                    .map(|sample| f32::from_sample(*sample))
+                    .collect();
+                on_samples(&samples);
+            },
+            |e| log::error!("Audio stream error: {}", e),
+            None,
+        )
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,76 @@
+//! Preserving the user's clipboard around paste insertion.
+//!
+//! Pasting replaces whatever the user had copied, so the previous contents are
+//! saved before inserting and put back once the target app has read the paste.
+
+use arboard::{Clipboard, Error as ClipboardError, ImageData};
+use std::thread;
+use std::time::Duration;
+
+/// Clipboard contents saved before pasting
+pub enum ClipboardSnapshot {
+    Text(String),
+    Image(ImageData<'static>),
+    /// The clipboard was empty, or held a format that can't be restored
+    Empty,
+    /// The clipboard couldn't be read, so it is left alone afterwards
+    Unreadable,
+}
+
+impl ClipboardSnapshot {
+    /// Save the current clipboard contents
+    pub fn capture() -> Self {
+        let Ok(mut clipboard) = Clipboard::new() else {
+            return Self::Unreadable;
+        };
+
+        match clipboard.get_text() {
+            Ok(text) => return Self::Text(text),
+            Err(ClipboardError::ContentNotAvailable) => {}
+            Err(e) => {
+                log::warn!("Failed to read clipboard text: {}", e);
+                return Self::Unreadable;
+            }
+        }
+
+        match clipboard.get_image() {
+            Ok(image) => Self::Image(image),
+            Err(ClipboardError::ContentNotAvailable) => Self::Empty,
+            Err(e) => {
+                log::warn!("Failed to read clipboard image: {}", e);
+                Self::Unreadable
+            }
+        }
+    }
+
+    /// Wait `delay` for the target app to read the paste, then put the saved contents
+    /// back. Skipped if the clipboard no longer holds `inserted`, since the user (or
+    /// another app) has copied something new in the meantime.
+    pub fn restore_after(self, delay: Duration, inserted: &str) {
+        if matches!(self, Self::Unreadable) {
+            return;
+        }
+
+        thread::sleep(delay);
+        let Ok(mut clipboard) = Clipboard::new() else {
+            return;
+        };
+        if !clipboard
+            .get_text()
+            .is_ok_and(|current| current == inserted)
+        {
+            log::debug!("Clipboard changed after paste, not restoring it");
+            return;
+        }
+
+        let result = match self {
+            Self::Text(text) => clipboard.set_text(text),
+            Self::Image(image) => clipboard.set_image(image),
+            Self::Empty => clipboard.clear(),
+            Self::Unreadable => Ok(()),
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to restore clipboard: {}", e);
+        }
+    }
+}
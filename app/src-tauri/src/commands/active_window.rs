@@ -0,0 +1,58 @@
+use crate::active_window::{get_active_app, ActiveApp};
+use crate::events::{self, AppEvent};
+use crate::secure_input;
+use crate::settings::{get_setting_from_store, is_blocked_app};
+use crate::state::AppState;
+use tauri::{AppHandle, Manager};
+
+/// App the current or last recording was started in, or the focused app if that
+/// is unknown
+pub fn recording_app(app: &AppHandle) -> Option<ActiveApp> {
+    app.state::<AppState>()
+        .recording_app
+        .lock()
+        .ok()
+        .and_then(|recording_app| recording_app.clone())
+        .or_else(get_active_app)
+}
+
+/// App the current recording was started in, sent with the turn so the server can
+/// format for it
+#[tauri::command]
+pub fn get_recording_app(app: AppHandle) -> Option<ActiveApp> {
+    recording_app(&app)
+}
+
+/// The focused app, if it is in the `blocked_apps` list
+pub fn focused_blocked_app(app: &AppHandle) -> Option<ActiveApp> {
+    let blocked_apps: Vec<String> = get_setting_from_store(app, "blocked_apps", Vec::new());
+    if blocked_apps.is_empty() {
+        return None;
+    }
+    get_active_app().filter(|focused| is_blocked_app(&blocked_apps, Some(focused)))
+}
+
+/// Fail if the focused app is blocked, so nothing is typed or pasted into it
+pub fn ensure_not_blocked(app: &AppHandle) -> Result<(), String> {
+    match focused_blocked_app(app) {
+        Some(blocked) => Err(format!(
+            "{} is a blocked app, so nothing was inserted",
+            blocked.app_name
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Fail if macOS secure input is on, since simulated keys would be silently dropped.
+/// The dictation stays in history for paste last once secure input is off.
+pub fn ensure_not_secure_input(app: &AppHandle) -> Result<(), String> {
+    if !secure_input::is_enabled() {
+        return Ok(());
+    }
+    events::publish(app, AppEvent::SecureInputBlocked);
+    Err(
+        "Secure input is on (a password field or terminal has it), so nothing was inserted. \
+         Use paste last once it's off"
+            .to_string(),
+    )
+}
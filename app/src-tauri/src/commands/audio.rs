@@ -0,0 +1,141 @@
+use crate::audio;
+use crate::events::{self, AppEvent};
+use crate::mic_monitor::{preferred_mics, ActiveMic, AudioInputDevice, MicMonitor};
+use crate::settings::get_setting_from_store;
+use crate::sound_theme::{self, SoundSettings, SoundTheme, SoundType};
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_opener::OpenerExt;
+
+/// OS settings page where denied microphone access can be allowed again
+#[cfg(target_os = "macos")]
+const MIC_PRIVACY_SETTINGS_URL: Option<&str> =
+    Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone");
+#[cfg(target_os = "windows")]
+const MIC_PRIVACY_SETTINGS_URL: Option<&str> = Some("ms-settings:privacy-microphone");
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const MIC_PRIVACY_SETTINGS_URL: Option<&str> = None;
+
+/// Whether the overlay may use the microphone
+#[derive(Debug, Clone, Serialize)]
+struct MicPermissionChange {
+    granted: bool,
+}
+
+/// Report the audio input devices the overlay can see, and get the device to record
+/// from. Emits `mic-fallback-changed` when a preferred microphone is plugged in or
+/// unplugged and recording switches device because of it.
+#[tauri::command]
+pub async fn report_audio_devices(
+    app: AppHandle,
+    devices: Vec<AudioInputDevice>,
+    monitor: State<'_, MicMonitor>,
+) -> Result<ActiveMic, String> {
+    let preferred = preferred_mics(
+        get_setting_from_store(&app, "preferred_mic_ids", None),
+        get_setting_from_store(&app, "selected_mic_id", None),
+    );
+    let (active, change) = monitor.update(&preferred, &devices);
+
+    if let Some(change) = change {
+        if change.is_fallback {
+            log::warn!(
+                "Preferred microphones such as {} are unavailable, using the system default",
+                change.label.as_deref().unwrap_or("(unknown)")
+            );
+        } else {
+            log::info!(
+                "Switched to preferred microphone {}",
+                change.label.as_deref().unwrap_or("(unknown)")
+            );
+        }
+        let _ = app.emit("mic-fallback-changed", change);
+    }
+
+    Ok(active)
+}
+
+/// Report whether the overlay could open the microphone. When access is denied the
+/// current recording is cancelled rather than streaming silence, and if the OS
+/// prompt was just answered (`prompted`), its privacy settings are opened since the
+/// prompt won't show again. Emits `mic-permission-changed`.
+#[tauri::command]
+pub fn report_mic_permission(app: AppHandle, granted: bool, prompted: bool) -> Result<(), String> {
+    app.state::<MicMonitor>().set_permission(granted);
+    let _ = app.emit("mic-permission-changed", MicPermissionChange { granted });
+    if granted {
+        log::info!("Microphone access allowed");
+        return Ok(());
+    }
+
+    log::warn!("Microphone access denied");
+    #[cfg(desktop)]
+    crate::cancel_recording(&app, "MicPermission");
+    if !prompted {
+        return Ok(());
+    }
+    match MIC_PRIVACY_SETTINGS_URL {
+        Some(url) => app
+            .opener()
+            .open_url(url, None::<&str>)
+            .map_err(|e| format!("Failed to open privacy settings: {}", e)),
+        None => Err("Allow microphone access in your system's privacy settings".to_string()),
+    }
+}
+
+/// Ask for microphone access again. The overlay, which owns the microphone, shows
+/// the OS prompt if access is undecided and reports back with `report_mic_permission`.
+#[tauri::command]
+pub fn request_mic_permission(app: AppHandle) -> Result<(), String> {
+    app.emit_to("overlay", "mic-permission-prompt", ())
+        .map_err(|e| e.to_string())
+}
+
+/// Sound volume and on/off settings, carrying over the old `sound_enabled` flag
+pub fn sound_settings(app: &AppHandle) -> SoundSettings {
+    SoundSettings::from_stored(
+        get_setting_from_store(app, "sound_settings", None),
+        get_setting_from_store(app, "sound_enabled", None),
+    )
+}
+
+fn sound_theme(app: &AppHandle) -> SoundTheme {
+    get_setting_from_store(app, "sound_theme", SoundTheme::default())
+}
+
+/// Play the sound chosen in settings for an event, if the event's sound is on.
+/// Returns whether a sound plays.
+pub fn play_feedback_sound(app: &AppHandle, sounds: &SoundSettings, sound_type: SoundType) -> bool {
+    match sounds.volume_for(sound_type) {
+        Some(volume) => audio::play_sound(&sound_theme(app), sound_type, volume),
+        None => false,
+    }
+}
+
+/// Play the completion and error sounds when turns finish
+pub fn spawn_sound_subscriber(app: &AppHandle) {
+    events::spawn_subscriber(app, "sounds", |app, event| {
+        let sound_type = match event {
+            AppEvent::TurnCompleted { .. } => SoundType::Complete,
+            AppEvent::TurnFailed { .. } => SoundType::Error,
+            _ => return,
+        };
+        play_feedback_sound(app, &sound_settings(app), sound_type);
+    });
+}
+
+/// Check that a file can be used as a feedback sound
+#[tauri::command]
+pub fn validate_sound_file(path: String) -> Result<(), String> {
+    sound_theme::validate_sound_file(Path::new(path.trim()))
+}
+
+/// Play the sound chosen in settings for an event at its volume, even if the
+/// event's sound is turned off
+#[tauri::command]
+pub fn preview_sound(app: AppHandle, sound_type: SoundType) {
+    let mut sounds = sound_settings(&app);
+    sounds.event_mut(sound_type).enabled = true;
+    play_feedback_sound(&app, &sounds, sound_type);
+}
@@ -0,0 +1,169 @@
+use crate::auth::{self, AuthRefresh, AuthStatus, AuthToken};
+use crate::commands::connection::proxy_from_settings;
+use crate::settings::{get_setting_from_store, DEFAULT_SERVER_URL};
+use chrono::Utc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// Settings key older versions kept the token under, in plain text
+const LEGACY_SETTINGS_KEY: &str = "auth_token";
+
+fn status(app: &AppHandle) -> Result<AuthStatus, String> {
+    Ok(AuthStatus::new(
+        auth::load()?.as_ref(),
+        app.state::<AuthRefresh>().error(),
+        Utc::now(),
+    ))
+}
+
+fn emit_status(app: &AppHandle) {
+    match status(app) {
+        Ok(status) => {
+            let _ = app.emit("auth-status-changed", status);
+        }
+        Err(e) => log::error!("{}", e),
+    }
+}
+
+/// Refresh the access token shortly before it expires, replacing any refresh
+/// scheduled for an earlier token
+fn schedule_refresh(app: &AppHandle, token: &AuthToken) {
+    let generation = app.state::<AuthRefresh>().next_generation();
+    let Some(delay) = token.refresh_in(Utc::now()) else {
+        return;
+    };
+    log::info!("Refreshing auth token in {:?}", delay);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        if app.state::<AuthRefresh>().is_current(generation) {
+            refresh_now(&app);
+        }
+    });
+}
+
+fn refresh_now(app: &AppHandle) {
+    let token = match auth::load() {
+        Ok(Some(token)) => token,
+        Ok(None) => return,
+        Err(e) => {
+            log::error!("{}", e);
+            return;
+        }
+    };
+    let server_url: String =
+        get_setting_from_store(app, "server_url", DEFAULT_SERVER_URL.to_string());
+    let refresh = app.state::<AuthRefresh>();
+    match proxy_from_settings(app)
+        .and_then(|proxy| auth::refresh(&server_url, &token, proxy.as_ref()))
+        .and_then(|refreshed| {
+            auth::save(&refreshed)?;
+            Ok(refreshed)
+        }) {
+        Ok(refreshed) => {
+            log::info!("Refreshed auth token");
+            refresh.set_error(None);
+            schedule_refresh(app, &refreshed);
+        }
+        Err(e) => {
+            log::error!("Failed to refresh auth token: {}", e);
+            refresh.set_error(Some(e));
+            // Keep trying while the token is still valid
+            if !token.is_expired(Utc::now()) {
+                let generation = refresh.next_generation();
+                let app = app.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(auth::REFRESH_RETRY_DELAY);
+                    if app.state::<AuthRefresh>().is_current(generation) {
+                        refresh_now(&app);
+                    }
+                });
+            }
+        }
+    }
+    emit_status(app);
+}
+
+/// Move a token left in `settings.json` by older versions into the keyring, then
+/// schedule the stored token's refresh
+pub fn init(app: &AppHandle) {
+    if let Ok(store) = app.store("settings.json") {
+        let legacy = store
+            .get(LEGACY_SETTINGS_KEY)
+            .and_then(|value| value.as_str().map(String::from));
+        if let Some(legacy) = legacy {
+            match AuthToken::new(&legacy, None, None, Utc::now())
+                .and_then(|token| auth::save(&token))
+            {
+                Ok(()) => {
+                    store.delete(LEGACY_SETTINGS_KEY);
+                    let _ = store.save();
+                    log::info!("Moved auth token from settings to the keyring");
+                }
+                Err(e) => log::error!("Failed to move auth token to the keyring: {}", e),
+            }
+        }
+    }
+
+    match auth::load() {
+        Ok(Some(token)) => {
+            if token.is_expired(Utc::now()) && token.refresh_token.is_none() {
+                log::warn!("Stored auth token has expired");
+            }
+            schedule_refresh(app, &token);
+        }
+        Ok(None) => {}
+        Err(e) => log::error!("{}", e),
+    }
+}
+
+/// Store a server auth token in the OS keyring and reconnect with it. The expiry
+/// is read from a JWT when `expires_in_seconds` isn't given.
+#[tauri::command]
+pub async fn set_auth_token(
+    app: AppHandle,
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in_seconds: Option<u64>,
+) -> Result<AuthStatus, String> {
+    let token = AuthToken::new(
+        &access_token,
+        refresh_token.as_deref(),
+        expires_in_seconds.map(Duration::from_secs),
+        Utc::now(),
+    )?;
+    auth::save(&token)?;
+    log::info!("Stored server auth token");
+    app.state::<AuthRefresh>().set_error(None);
+    schedule_refresh(&app, &token);
+    emit_status(&app);
+    let _ = crate::commands::connection::reconnect_server(app.clone());
+    status(&app)
+}
+
+/// Whether a token is stored and when it expires (never returns the token itself)
+#[tauri::command]
+pub async fn get_auth_status(app: AppHandle) -> Result<AuthStatus, String> {
+    status(&app)
+}
+
+/// Delete the stored auth token and reconnect without it
+#[tauri::command]
+pub async fn clear_auth_token(app: AppHandle) -> Result<bool, String> {
+    let deleted = auth::delete()?;
+    let refresh = app.state::<AuthRefresh>();
+    refresh.next_generation();
+    refresh.set_error(None);
+    emit_status(&app);
+    let _ = crate::commands::connection::reconnect_server(app.clone());
+    Ok(deleted)
+}
+
+/// Access token for the overlay to send when connecting, unless it has expired
+#[tauri::command]
+pub async fn get_auth_header() -> Result<Option<String>, String> {
+    Ok(auth::load()?
+        .filter(|token| !token.is_expired(Utc::now()))
+        .map(|token| format!("Bearer {}", token.access_token)))
+}
@@ -0,0 +1,141 @@
+use crate::benchmark::{
+    as_millis, synthetic_audio, BenchmarkSource, LatencyBenchmark, StageTimings, BENCHMARK_TEXT,
+    SYNTHETIC_AUDIO_DURATION,
+};
+use crate::clipboard::ClipboardSnapshot;
+use crate::commands::connection::{proxy_from_settings, tls_options_for};
+use crate::commands::pending_transcriptions::transcribe_queued;
+use crate::formatter::preview_transcript;
+use crate::recordings::{to_pcm16, RecordingArchive};
+use crate::server;
+use crate::settings::{get_setting_from_store, DEFAULT_SERVER_URL};
+use crate::state::AppState;
+use crate::stt::{provider_from_settings, AudioFormat, SttManager};
+use crate::turns::TurnQueue;
+use arboard::Clipboard;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Audio for a benchmark: the last dictation or newest archived recording for
+/// [`BenchmarkSource::Recorded`]
+fn benchmark_audio(
+    app: &AppHandle,
+    source: BenchmarkSource,
+) -> Result<(AudioFormat, Vec<f32>), String> {
+    match source {
+        BenchmarkSource::Synthetic => Ok((
+            AudioFormat::WHISPER,
+            synthetic_audio(AudioFormat::WHISPER, SYNTHETIC_AUDIO_DURATION),
+        )),
+        BenchmarkSource::Recorded => {
+            if let Some(audio) = app.state::<SttManager>().last_audio() {
+                return Ok(audio);
+            }
+            let archive = app
+                .try_state::<RecordingArchive>()
+                .ok_or("There is no recording to benchmark with")?;
+            let newest = archive
+                .list()?
+                .into_iter()
+                .next()
+                .ok_or("There is no recording to benchmark with")?;
+            let (format, samples) = archive.load(&newest.id)?;
+            let samples = samples
+                .iter()
+                .map(|sample| f32::from(*sample) / f32::from(i16::MAX))
+                .collect();
+            Ok((format, samples))
+        }
+    }
+}
+
+/// Put `text` on the clipboard and restore what was there, as paste insertion does
+fn time_clipboard_insertion(text: &str) -> Result<Duration, String> {
+    let started = Instant::now();
+    let snapshot = ClipboardSnapshot::capture();
+    Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("Failed to use the clipboard: {}", e))?;
+    snapshot.restore_after(Duration::ZERO, text);
+    Ok(started.elapsed())
+}
+
+fn run_benchmark(app: &AppHandle, source: BenchmarkSource) -> Result<LatencyBenchmark, String> {
+    let state = app.state::<AppState>();
+    // The server handles one turn at a time, so dictation goes first
+    if state.is_recording.load(Ordering::SeqCst) || app.state::<TurnQueue>().in_flight() > 0 {
+        return Err("Can't run a benchmark while dictating".to_string());
+    }
+
+    let mut timings = StageTimings::default();
+    let started = Instant::now();
+    let (format, samples) = benchmark_audio(app, source)?;
+    let mut provider = provider_from_settings(app);
+    let stt_includes_formatting = !provider.transcribes_in_app();
+    // The overlay sends the server 16-bit samples
+    let pcm16 = stt_includes_formatting.then(|| to_pcm16(&samples));
+    timings.capture_ms = as_millis(started.elapsed());
+
+    let url: String = get_setting_from_store(app, "server_url", DEFAULT_SERVER_URL.to_string());
+    let connection = server::test_connection(
+        &url,
+        &tls_options_for(app, &url)?,
+        proxy_from_settings(app)?.as_ref(),
+    );
+    timings.network_ms = connection.latency_ms.filter(|_| connection.reachable);
+
+    let started = Instant::now();
+    let transcript = match pcm16 {
+        Some(pcm16) => {
+            if !state.server_connected.load(Ordering::SeqCst) {
+                return Err("Not connected to the server".to_string());
+            }
+            transcribe_queued(app, format, pcm16, None)?
+        }
+        // Synthetic audio usually transcribes to nothing, which is fine for timing
+        None => provider
+            .start_stream(format)
+            .and_then(|()| provider.send_audio(&samples))
+            .and_then(|()| provider.finalize())?
+            .unwrap_or_default(),
+    };
+    timings.stt_ms = as_millis(started.elapsed());
+
+    let raw = if transcript.trim().is_empty() {
+        BENCHMARK_TEXT
+    } else {
+        transcript.as_str()
+    };
+    let started = Instant::now();
+    let formatted = preview_transcript(app, raw, None)?;
+    timings.formatting_ms = as_millis(started.elapsed());
+
+    timings.insertion_ms = as_millis(time_clipboard_insertion(&formatted)?);
+
+    Ok(LatencyBenchmark {
+        source,
+        audio_ms: format.duration_ms(samples.len()),
+        stt_provider: provider.name().to_string(),
+        llm_provider: get_setting_from_store(app, "llm_provider", None),
+        stt_includes_formatting,
+        total_ms: timings.total_ms(stt_includes_formatting),
+        timings,
+        transcript,
+        formatted,
+    })
+}
+
+/// Replay synthetic or recorded audio through the configured STT and formatting
+/// providers and time each stage. Nothing is typed into other apps.
+#[tauri::command]
+pub async fn run_latency_benchmark(
+    app: AppHandle,
+    source: Option<BenchmarkSource>,
+) -> Result<LatencyBenchmark, String> {
+    let source = source.unwrap_or_default();
+    log::info!("Running latency benchmark with {:?} audio", source);
+    tauri::async_runtime::spawn_blocking(move || run_benchmark(&app, source))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,211 @@
+use crate::audio_processing::ProcessingOptions;
+use crate::capture::{
+    apply_gain, describe_format, device_formats, pcm16_bytes, peak_level, pick_format, rms_level,
+    FormatRange, LocalCaptureFlush, NativeCapture, StreamFormat, CAPTURE_FORMAT,
+};
+use crate::commands::stt::feed_stt_audio;
+use crate::events::{self, AppEvent};
+use crate::settings::get_setting_from_store;
+use serde::Serialize;
+use tauri::ipc::{Channel, Response};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// Whether the microphone is captured by the backend rather than the webview
+pub(crate) fn native_capture_enabled(app: &AppHandle) -> bool {
+    get_setting_from_store(app, "native_audio_capture", true)
+}
+
+fn mic_gain(app: &AppHandle) -> f32 {
+    get_setting_from_store(app, "mic_gain", 1.0)
+}
+
+fn processing_options(app: &AppHandle) -> ProcessingOptions {
+    ProcessingOptions {
+        noise_suppression: get_setting_from_store(app, "noise_suppression_enabled", false),
+        echo_cancellation: get_setting_from_store(app, "echo_cancellation_enabled", false),
+        auto_gain_control: get_setting_from_store(app, "agc_enabled", true),
+    }
+}
+
+fn stream_format(app: &AppHandle) -> StreamFormat {
+    StreamFormat {
+        sample_rate: get_setting_from_store(app, "sample_rate", None),
+        channels: get_setting_from_store(app, "channels", None),
+    }
+}
+
+/// Pass a captured frame on: its level to the UI as `mic-level`, the audio to
+/// transcription, and as 16-bit PCM bytes to `frames`, which the overlay plays into
+/// the track its Pipecat transport sends to the server. The server connection lives
+/// in the overlay, so frames go through it rather than from here.
+fn handle_frame(app: &AppHandle, frames: &Channel<Response>, mut samples: Vec<f32>) {
+    apply_gain(&mut samples, app.state::<NativeCapture>().gain());
+    let _ = app.emit("mic-level", rms_level(&samples));
+    if let Err(e) = frames.send(Response::new(pcm16_bytes(&samples))) {
+        log::warn!("Failed to send captured audio to the overlay: {}", e);
+    }
+    if let Err(e) = feed_stt_audio(app, &samples) {
+        log::warn!("Failed to transcribe captured audio: {}", e);
+    }
+}
+
+/// Open the microphone the overlay chose, by its webview label, falling back to
+/// the system default, in the format and with the noise suppression, echo
+/// cancellation and gain control settings, sending its audio to `frames`.
+/// Returns the name of the device opened.
+#[tauri::command]
+pub async fn start_native_capture(
+    app: AppHandle,
+    device_label: Option<String>,
+    frames: Channel<Response>,
+) -> Result<String, String> {
+    if !native_capture_enabled(&app) {
+        return Err("Capturing in the app is turned off".to_string());
+    }
+    let capture = app.state::<NativeCapture>();
+    capture.set_gain(mic_gain(&app));
+    let frame_app = app.clone();
+    capture.start(
+        device_label.as_deref(),
+        stream_format(&app),
+        processing_options(&app),
+        move |samples| handle_frame(&frame_app, &frames, samples),
+    )
+}
+
+#[tauri::command]
+pub async fn stop_native_capture(app: AppHandle) {
+    app.state::<NativeCapture>().stop();
+}
+
+/// The microphone opened from settings to test it, apart from dictation
+#[derive(Default)]
+pub struct MicTest(NativeCapture);
+
+/// Levels of a frame of the microphone test, emitted as `mic-test-level`
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MicTestLevel {
+    pub rms: f32,
+    pub peak: f32,
+}
+
+/// Open the microphone with the webview label `device_label`, or the system
+/// default, as dictation would and emit its levels as `mic-test-level` until
+/// [`stop_mic_test`]. Nothing is transcribed. Returns the name of the device opened.
+#[tauri::command]
+pub async fn start_mic_test(
+    app: AppHandle,
+    device_label: Option<String>,
+) -> Result<String, String> {
+    let test = app.state::<MicTest>();
+    test.0.set_gain(mic_gain(&app));
+    let level_app = app.clone();
+    test.0.start(
+        device_label.as_deref(),
+        stream_format(&app),
+        processing_options(&app),
+        move |mut samples| {
+            apply_gain(&mut samples, level_app.state::<MicTest>().0.gain());
+            let level = MicTestLevel {
+                rms: rms_level(&samples),
+                peak: peak_level(&samples),
+            };
+            let _ = level_app.emit("mic-test-level", level);
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn stop_mic_test(app: AppHandle) {
+    app.state::<MicTest>().0.stop();
+}
+
+/// A microphone and the formats it can be captured in
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureFormats {
+    pub device: String,
+    pub formats: Vec<FormatRange>,
+}
+
+/// The formats the microphone with the webview label `device_label`, or the
+/// system default, can be captured in
+#[tauri::command]
+pub async fn list_capture_formats(device_label: Option<String>) -> Result<CaptureFormats, String> {
+    let (device, formats) = device_formats(device_label.as_deref())?;
+    Ok(CaptureFormats { device, formats })
+}
+
+/// Save the format to capture in after checking the microphone with the webview
+/// label `device_label` supports it. `None` keeps the device's default.
+#[tauri::command]
+pub async fn update_capture_format(
+    app: AppHandle,
+    device_label: Option<String>,
+    sample_rate: Option<u32>,
+    channels: Option<u16>,
+) -> Result<(), String> {
+    let format = StreamFormat {
+        sample_rate,
+        channels,
+    };
+    if !format.is_default() {
+        let (device, formats) = device_formats(device_label.as_deref())?;
+        if pick_format(&formats, format, CAPTURE_FORMAT.sample_rate).is_none() {
+            return Err(format!(
+                "{} can't capture {}",
+                device,
+                describe_format(format)
+            ));
+        }
+    }
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.set("sample_rate", serde_json::json!(sample_rate));
+    store.set("channels", serde_json::json!(channels));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
+/// Capture a turn's audio for in-app transcription. Audio captured by the backend
+/// already reaches transcription, so the overlay is only asked to capture when it
+/// owns the microphone.
+pub(crate) fn start_local_capture(app: &AppHandle) {
+    if !app.state::<NativeCapture>().is_running() {
+        let _ = app.emit("local-capture-start", ());
+    }
+}
+
+/// Ask the overlay to stop capturing, if it is. Returns the ID of the stop request,
+/// which the overlay confirms with [`local_capture_flushed`] once it has sent the
+/// last of its audio.
+pub(crate) fn stop_local_capture(app: &AppHandle) -> Option<u64> {
+    if app.state::<NativeCapture>().is_running() {
+        return None;
+    }
+    let id = app.state::<LocalCaptureFlush>().request();
+    let _ = app.emit("local-capture-stop", id);
+    Some(id)
+}
+
+/// The overlay has sent all the audio it captured before stop request `id`
+#[tauri::command]
+pub fn local_capture_flushed(app: AppHandle, id: u64) {
+    app.state::<LocalCaptureFlush>().confirm(id);
+}
+
+/// Follow input gain changes while capturing or testing the microphone
+pub fn spawn_gain_subscriber(app: &AppHandle) {
+    events::spawn_subscriber(app, "capture-gain", |app, event| {
+        if event == AppEvent::SettingsChanged {
+            let gain = mic_gain(app);
+            app.state::<NativeCapture>().set_gain(gain);
+            app.state::<MicTest>().0.set_gain(gain);
+        }
+    });
+}
@@ -0,0 +1,213 @@
+use crate::connection::{self, ConnectionManager, ConnectionStatus};
+use crate::events::{self, AppEvent};
+use crate::proxy::{ProxyAuth, ProxyConfig};
+use crate::server::{self, ConnectionTest, TlsOptions};
+use crate::settings::{get_setting_from_store, DEFAULT_SERVER_URL};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+/// Request for the overlay to connect to the server again
+#[derive(Debug, Clone, Serialize)]
+struct ReconnectRequest {
+    /// Reconnect attempts since the connection was last up (0 for a manual reconnect)
+    attempt: u32,
+    /// Server to connect to
+    url: String,
+}
+
+/// Servers to connect to in priority order, from settings
+fn endpoints_from_settings(app: &AppHandle) -> Vec<String> {
+    let primary: String = get_setting_from_store(app, "server_url", DEFAULT_SERVER_URL.to_string());
+    let fallbacks: Vec<String> = get_setting_from_store(app, "fallback_server_urls", Vec::new());
+    connection::server_endpoints(&primary, &fallbacks)
+}
+
+/// How to trust the certificate of `url`. The pinned fingerprint only applies to
+/// the configured server URL, since fallbacks are usually hosted elsewhere.
+pub(crate) fn tls_options_for(app: &AppHandle, url: &str) -> Result<TlsOptions, String> {
+    let allow_self_signed: bool = get_setting_from_store(app, "allow_self_signed_certs", false);
+    let primary: String = get_setting_from_store(app, "server_url", DEFAULT_SERVER_URL.to_string());
+    let pin: Option<String> = get_setting_from_store(app, "pinned_cert_sha256", None);
+    let is_primary = primary.trim().trim_end_matches('/') == url.trim().trim_end_matches('/');
+    TlsOptions::new(allow_self_signed, pin.as_deref().filter(|_| is_primary))
+}
+
+/// The proxy to reach servers through, from settings
+pub(crate) fn proxy_from_settings(app: &AppHandle) -> Result<Option<ProxyConfig>, String> {
+    let url: Option<String> = get_setting_from_store(app, "proxy_url", None);
+    let auth: Option<ProxyAuth> = get_setting_from_store(app, "proxy_auth", None);
+    ProxyConfig::new(url.as_deref(), auth)
+}
+
+/// Save the proxy settings after checking the URL. A blank URL turns the proxy
+/// off. The overlay picks up a new proxy when the app is restarted.
+#[tauri::command]
+pub async fn update_proxy_settings(
+    app: AppHandle,
+    proxy_url: Option<String>,
+    proxy_auth: Option<ProxyAuth>,
+) -> Result<(), String> {
+    let proxy = ProxyConfig::new(proxy_url.as_deref(), proxy_auth)?;
+    let (url, auth) = match proxy {
+        Some(proxy) => (Some(proxy.url), proxy.auth),
+        None => (None, None),
+    };
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.set("proxy_url", serde_json::json!(url));
+    store.set("proxy_auth", serde_json::json!(auth));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
+/// Load the servers from settings, and reconnect from the first one whenever they
+/// change
+pub fn spawn_endpoint_subscriber(app: &AppHandle) {
+    app.state::<ConnectionManager>()
+        .set_endpoints(endpoints_from_settings(app));
+    events::spawn_subscriber(app, "endpoints", |app, event| {
+        if event != AppEvent::SettingsChanged {
+            return;
+        }
+        let manager = app.state::<ConnectionManager>();
+        if manager.set_endpoints(endpoints_from_settings(app)) {
+            log::info!("Server URLs changed, reconnecting");
+            if let Some(url) = manager.reconnect_now() {
+                request_reconnect(app, 0, url);
+            }
+        }
+    });
+}
+
+/// Record a connection state change reported by the overlay, share it with the
+/// frontend and schedule a reconnect if the connection is down
+#[tauri::command]
+pub fn report_connection_state(app: AppHandle, state: String) {
+    let manager = app.state::<ConnectionManager>();
+    let outcome = manager.report(&state);
+    let _ = app.emit("connection-state-changed", manager.status());
+
+    if let Some(endpoint) = &outcome.active_endpoint {
+        log::info!("Connected to server {}", endpoint.url);
+        let _ = app.emit("server-endpoint-changed", endpoint);
+    }
+
+    if outcome.connected_changed {
+        events::publish(
+            app,
+            AppEvent::ConnectionChanged {
+                connected: events::is_connected_state(&state),
+            },
+        );
+    }
+
+    if let Some(scheduled) = outcome.reconnect {
+        log::info!(
+            "Server connection down, reconnecting in {:?}",
+            scheduled.delay
+        );
+        let app = app.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(scheduled.delay);
+            let manager = app.state::<ConnectionManager>();
+            if let Some(url) = manager.take_due_reconnect(scheduled.generation) {
+                request_reconnect(&app, manager.status().attempt, url);
+            }
+        });
+    }
+}
+
+fn request_reconnect(app: &AppHandle, attempt: u32, url: String) {
+    let options = match tls_options_for(app, &url) {
+        Ok(options) => options,
+        Err(e) => {
+            log::warn!("Ignoring invalid pinned certificate: {}", e);
+            TlsOptions::default()
+        }
+    };
+    let proxy = match proxy_from_settings(app) {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            log::warn!("Ignoring invalid proxy: {}", e);
+            None
+        }
+    };
+    if options.pinned_sha256.is_none() {
+        emit_reconnect(app, attempt, url);
+        return;
+    }
+
+    // Check the certificate first, treating a mismatch like a failed attempt
+    let app = app.clone();
+    std::thread::spawn(move || {
+        match server::verify_pinned_certificate(&url, &options, proxy.as_ref()) {
+            Ok(()) => emit_reconnect(&app, attempt, url),
+            Err(e) => {
+                log::error!("Not connecting to {}: {}", url, e);
+                if let Some(window) = app.get_webview_window("overlay") {
+                    let _ = window.emit("request-disconnect", ());
+                }
+                report_connection_state(app.clone(), "connecting".to_string());
+                report_connection_state(app, "disconnected".to_string());
+            }
+        }
+    });
+}
+
+fn emit_reconnect(app: &AppHandle, attempt: u32, url: String) {
+    log::info!("Connecting to server {} (attempt {})", url, attempt);
+    let _ = app.emit("connection-reconnect", ReconnectRequest { attempt, url });
+    let _ = app.emit(
+        "connection-state-changed",
+        app.state::<ConnectionManager>().status(),
+    );
+}
+
+/// Stop reconnecting and ask the overlay to close the session (on quit)
+pub fn shut_down(app: &AppHandle) {
+    app.state::<ConnectionManager>().shut_down();
+    if let Some(window) = app.get_webview_window("overlay") {
+        let _ = window.emit("request-disconnect", ());
+    }
+}
+
+#[tauri::command]
+pub fn get_connection_status(manager: State<'_, ConnectionManager>) -> ConnectionStatus {
+    manager.status()
+}
+
+/// Connect to the first server right away, resetting the backoff
+#[tauri::command]
+pub fn reconnect_server(app: AppHandle) -> Result<(), String> {
+    let url = app
+        .state::<ConnectionManager>()
+        .reconnect_now()
+        .ok_or_else(|| "No server URL configured".to_string())?;
+    request_reconnect(&app, 0, url);
+    Ok(())
+}
+
+/// Check a server's health and capabilities with the TLS options from settings.
+/// Tests the configured server URL when `url` is `None`.
+#[tauri::command]
+pub async fn test_server_connection(
+    app: AppHandle,
+    url: Option<String>,
+) -> Result<ConnectionTest, String> {
+    let url = url.unwrap_or_else(|| {
+        get_setting_from_store(&app, "server_url", DEFAULT_SERVER_URL.to_string())
+    });
+    let options = tls_options_for(&app, &url)?;
+    let proxy = proxy_from_settings(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        server::test_connection(&url, &options, proxy.as_ref())
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
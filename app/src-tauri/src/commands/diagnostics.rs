@@ -0,0 +1,48 @@
+use crate::commands::connection::{proxy_from_settings, tls_options_for};
+use crate::commands::text::get_insertion_diagnostics;
+use crate::diagnostics::{
+    check_hotkeys, check_insertion, check_microphone_access, check_microphone_device, check_server,
+    DiagnosticsReport,
+};
+use crate::mic_monitor::MicMonitor;
+use crate::server;
+use crate::settings::{get_setting_from_store, DEFAULT_SERVER_URL};
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+
+/// Check microphone access, the selected microphone, the server, hotkey
+/// registration and insertion permissions, with a fix for each problem found
+#[tauri::command]
+pub async fn run_diagnostics(app: AppHandle) -> Result<DiagnosticsReport, String> {
+    let mic = app.state::<MicMonitor>().status();
+
+    let url: String = get_setting_from_store(&app, "server_url", DEFAULT_SERVER_URL.to_string());
+    let options = tls_options_for(&app, &url)?;
+    let proxy = proxy_from_settings(&app)?;
+    let server_url = url.clone();
+    let connection = tauri::async_runtime::spawn_blocking(move || {
+        server::test_connection(&server_url, &options, proxy.as_ref())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    #[cfg(desktop)]
+    let shortcut_failures = app
+        .state::<crate::commands::settings::ShortcutFailures>()
+        .get();
+    #[cfg(not(desktop))]
+    let shortcut_failures = Vec::new();
+
+    let insertion = get_insertion_diagnostics();
+
+    Ok(DiagnosticsReport {
+        checks: vec![
+            check_microphone_access(&mic),
+            check_microphone_device(&mic),
+            check_server(&url, &connection),
+            check_hotkeys(&shortcut_failures),
+            check_insertion(crate::accessibility::is_trusted(), &insertion.warnings),
+        ],
+        generated_at: Utc::now(),
+    })
+}
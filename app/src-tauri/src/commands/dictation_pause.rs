@@ -0,0 +1,54 @@
+use crate::events::{self, AppEvent};
+use crate::state::AppState;
+use std::sync::atomic::Ordering;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Whether dictation is paused (do not disturb)
+pub(crate) fn is_dictation_paused(app: &AppHandle) -> bool {
+    app.state::<AppState>()
+        .dictation_paused
+        .load(Ordering::SeqCst)
+}
+
+/// Pause or resume dictation. While paused no hotkey, mouse button, pedal or remote
+/// client can start a recording, so a screen share or pairing session can't set it
+/// off; a recording already running can still be stopped. Only lasts until the app
+/// quits. Emits `dictation-paused-changed` with whether dictation is now paused.
+pub(crate) fn set_dictation_paused(app: &AppHandle, paused: bool) {
+    let was_paused = app
+        .state::<AppState>()
+        .dictation_paused
+        .swap(paused, Ordering::SeqCst);
+    if was_paused == paused {
+        return;
+    }
+
+    log::info!(
+        "Dictation {}",
+        if paused {
+            "paused, recording hotkeys are off"
+        } else {
+            "resumed"
+        }
+    );
+    let _ = app.emit("dictation-paused-changed", paused);
+    events::publish(app, AppEvent::DictationPausedChanged { paused });
+}
+
+/// Pause dictation if it's running, and resume it if paused
+pub(crate) fn toggle_dictation_paused(app: &AppHandle) {
+    set_dictation_paused(app, !is_dictation_paused(app));
+}
+
+#[tauri::command]
+pub async fn get_dictation_paused(app: AppHandle) -> bool {
+    is_dictation_paused(&app)
+}
+
+/// Pause dictation if it's running, and resume it if paused, returning whether it
+/// is now paused
+#[tauri::command]
+pub async fn switch_dictation_paused(app: AppHandle) -> bool {
+    toggle_dictation_paused(&app);
+    is_dictation_paused(&app)
+}
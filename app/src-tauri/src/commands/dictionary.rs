@@ -0,0 +1,160 @@
+use crate::dictionary::{
+    dictionary_prompt, parse_legacy_dictionary, DictionaryEntry, DictionaryStore,
+    DEFAULT_DICTIONARY_ENTRIES,
+};
+use crate::dictionary_import::{
+    parse_import, plan_import, DictionaryImport, DictionaryImportFormat,
+};
+use crate::settings::{get_setting_from_store, CleanupPromptSections};
+use crate::text_diff::WordCorrection;
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+/// Render the dictionary into the formatting prompt's dictionary section and tell
+/// the windows, so the overlay sends it to the server. `enable` turns the section
+/// on; an empty dictionary turns it off, since the server would otherwise fall back
+/// to its sample entries.
+fn sync_dictionary_prompt(app: &AppHandle, enable: bool) -> Result<(), String> {
+    let prompt = dictionary_prompt(&app.state::<DictionaryStore>().list()?);
+    let saved: Option<CleanupPromptSections> =
+        get_setting_from_store(app, "cleanup_prompt_sections", None);
+    let mut sections = saved.unwrap_or_else(CleanupPromptSections::server_defaults);
+    sections.dictionary.enabled = prompt.is_some() && (enable || sections.dictionary.enabled);
+    sections.dictionary.content = prompt;
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    let value = serde_json::to_value(&sections)
+        .map_err(|e| format!("Failed to serialize prompt sections: {}", e))?;
+    store.set("cleanup_prompt_sections", value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    let _ = app.emit("dictionary-changed", ());
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
+/// Fill a new dictionary from the old free-text prompt section, or with the server's
+/// sample entries if that section was never customized
+pub fn import_prompt_dictionary(app: &AppHandle) {
+    let sections: Option<CleanupPromptSections> =
+        get_setting_from_store(app, "cleanup_prompt_sections", None);
+    let entries = match sections.and_then(|sections| sections.dictionary.content) {
+        Some(content) => parse_legacy_dictionary(&content),
+        None => DEFAULT_DICTIONARY_ENTRIES
+            .iter()
+            .map(|(spoken, written)| (spoken.to_string(), written.to_string(), false))
+            .collect(),
+    };
+
+    let dictionary = app.state::<DictionaryStore>();
+    for (spoken, written, case_sensitive) in &entries {
+        if let Err(e) = dictionary.add(spoken, written, *case_sensitive) {
+            log::warn!("Skipped dictionary entry \"{}\": {}", spoken, e);
+        }
+    }
+    log::info!("Imported {} personal dictionary entries", entries.len());
+    if let Err(e) = sync_dictionary_prompt(app, false) {
+        log::warn!("Failed to update the dictionary prompt: {}", e);
+    }
+}
+
+/// Add corrected words to the dictionary and turn the dictionary prompt on
+pub fn add_corrections(app: &AppHandle, corrections: &[WordCorrection]) -> Result<(), String> {
+    let dictionary = app.state::<DictionaryStore>();
+    for correction in corrections {
+        if let Err(e) = dictionary.add(&correction.heard, &correction.corrected, false) {
+            log::warn!("Skipped dictionary entry \"{}\": {}", correction.heard, e);
+        }
+    }
+    sync_dictionary_prompt(app, true)
+}
+
+/// Dictionary entries, in the order they were added
+#[tauri::command]
+pub async fn list_dictionary_entries(
+    dictionary: State<'_, DictionaryStore>,
+) -> Result<Vec<DictionaryEntry>, String> {
+    dictionary.list()
+}
+
+/// Add a mapping from a spoken form to its written form. An empty written form adds
+/// the spoken form as a term to recognize.
+#[tauri::command]
+pub async fn add_dictionary_entry(
+    app: AppHandle,
+    spoken: String,
+    written: String,
+    case_sensitive: bool,
+) -> Result<DictionaryEntry, String> {
+    let entry = app
+        .state::<DictionaryStore>()
+        .add(&spoken, &written, case_sensitive)?;
+    sync_dictionary_prompt(&app, false)?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn update_dictionary_entry(
+    app: AppHandle,
+    id: String,
+    spoken: String,
+    written: String,
+    case_sensitive: bool,
+) -> Result<DictionaryEntry, String> {
+    let entry = app
+        .state::<DictionaryStore>()
+        .update(&id, &spoken, &written, case_sensitive)?;
+    sync_dictionary_prompt(&app, false)?;
+    Ok(entry)
+}
+
+/// Remove an entry. Returns false if it was already gone.
+#[tauri::command]
+pub async fn remove_dictionary_entry(app: AppHandle, id: String) -> Result<bool, String> {
+    let removed = app.state::<DictionaryStore>().remove(&id)?;
+    if removed {
+        sync_dictionary_prompt(&app, false)?;
+    }
+    Ok(removed)
+}
+
+/// Import entries from a CSV or "spoken = written" text file. Entries already in the
+/// dictionary or repeated in the file are skipped, and spoken forms the dictionary
+/// writes differently are reported as conflicts. A dry run reports the same result
+/// without adding anything, to preview the import.
+#[tauri::command]
+pub async fn import_dictionary(
+    app: AppHandle,
+    path: String,
+    format: DictionaryImportFormat,
+    dry_run: bool,
+) -> Result<DictionaryImport, String> {
+    let path = Path::new(path.trim());
+    if !path.is_absolute() {
+        return Err(format!("Import path must be absolute: {}", path.display()));
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let (entries, skipped) = parse_import(&content, format);
+
+    let dictionary = app.state::<DictionaryStore>();
+    let import = plan_import(&dictionary.list()?, entries, skipped, dry_run);
+    if dry_run || import.added.is_empty() {
+        return Ok(import);
+    }
+
+    dictionary.add_all(&import.new_entries())?;
+    log::info!(
+        "Imported {} dictionary entries from {}",
+        import.added.len(),
+        path.display()
+    );
+    sync_dictionary_prompt(&app, false)?;
+    Ok(import)
+}
@@ -0,0 +1,57 @@
+use crate::export::{default_file_name, ExportData, ExportFormat};
+use crate::history::HistoryStorage;
+use crate::usage::{UsagePeriod, UsageStore};
+use chrono::Utc;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+/// Export history and usage statistics for `range` (all time by default) as CSV
+/// or JSON, returning the path written. Without a `path` the file goes to the
+/// downloads directory, falling back to the app data directory.
+#[tauri::command]
+pub async fn export_data(
+    app: AppHandle,
+    history: State<'_, HistoryStorage>,
+    usage: State<'_, UsageStore>,
+    format: ExportFormat,
+    path: Option<String>,
+    range: Option<UsagePeriod>,
+) -> Result<String, String> {
+    let period = range.unwrap_or(UsagePeriod::All);
+    let now = Utc::now();
+    let data = ExportData {
+        exported_at: now,
+        stats: usage.stats(period, now)?,
+        history: history.entries_since(period.since(now))?,
+    };
+
+    let path = match path.map(|path| path.trim().to_string()) {
+        Some(path) if !path.is_empty() => {
+            let path = PathBuf::from(path);
+            if !path.is_absolute() {
+                return Err(format!("Export path must be absolute: {}", path.display()));
+            }
+            path
+        }
+        _ => {
+            let dir = app
+                .path()
+                .download_dir()
+                .or_else(|_| app.path().app_data_dir())
+                .map_err(|e| format!("Failed to resolve export directory: {}", e))?;
+            dir.join(default_file_name(format, period, now))
+        }
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    fs::write(&path, data.render(format)?).map_err(|e| format!("Failed to write export: {}", e))?;
+
+    log::info!(
+        "Exported {} history entries to {}",
+        data.history.len(),
+        path.display()
+    );
+    Ok(path.to_string_lossy().into_owned())
+}
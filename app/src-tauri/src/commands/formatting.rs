@@ -0,0 +1,44 @@
+use crate::formatter::formatting_enabled;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+/// Turn LLM formatting on or off. Saves the choice and tells the windows, so the
+/// overlay passes it to the server. Emits `formatting-changed` with whether
+/// formatting is now on.
+pub(crate) fn set_formatting_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.set("formatting_enabled", enabled);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    log::info!(
+        "LLM formatting {}",
+        if enabled {
+            "on"
+        } else {
+            "off, inserting raw transcripts"
+        }
+    );
+    let _ = app.emit("settings-changed", ());
+    let _ = app.emit("formatting-changed", enabled);
+    Ok(())
+}
+
+/// Turn LLM formatting off if it's on, and on if it's off, returning whether it is
+/// now on
+#[tauri::command]
+pub async fn switch_formatting(app: AppHandle) -> Result<bool, String> {
+    let enabled = !formatting_enabled(&app);
+    set_formatting_enabled(&app, enabled)?;
+    Ok(enabled)
+}
+
+/// Turn LLM formatting off if it's on, and on if it's off
+pub(crate) fn toggle_formatting(app: &AppHandle) {
+    if let Err(e) = set_formatting_enabled(app, !formatting_enabled(app)) {
+        log::error!("Failed to switch formatting: {}", e);
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(desktop)]
+use crate::hid_input::{self, HidDeviceInfo, HidTriggers};
+#[cfg(desktop)]
+use std::time::Duration;
+use tauri::AppHandle;
+#[cfg(desktop)]
+use tauri::Manager;
+
+/// How long to wait for a button when binding one
+#[cfg(desktop)]
+const DETECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HID devices that can be bound, such as foot pedals
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn list_hid_devices() -> Result<Vec<HidDeviceInfo>, String> {
+    tauri::async_runtime::spawn_blocking(hid_input::list_devices)
+        .await
+        .map_err(|e| format!("Failed to list HID devices: {}", e))?
+}
+
+/// Wait for a button of the HID device interface to be pressed and return its bit
+/// for the `hid_triggers` setting. Bound devices stop triggering meanwhile.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn detect_hid_button(
+    app: AppHandle,
+    vendor_id: u16,
+    product_id: u16,
+    usage_page: u16,
+    usage: u16,
+) -> Result<u32, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let triggers = app.state::<HidTriggers>();
+        triggers.stop();
+        let button =
+            hid_input::detect_button((vendor_id, product_id, usage_page, usage), DETECT_TIMEOUT);
+        triggers.refresh(&app);
+        button
+    })
+    .await
+    .map_err(|e| format!("Failed to read the HID device: {}", e))?
+}
+
+// Stubs for non-desktop platforms
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn list_hid_devices() -> Result<Vec<serde_json::Value>, String> {
+    Ok(Vec::new())
+}
+
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn detect_hid_button(
+    _app: AppHandle,
+    _vendor_id: u16,
+    _product_id: u16,
+    _usage_page: u16,
+    _usage: u16,
+) -> Result<u32, String> {
+    Err("HID devices aren't supported on this platform".to_string())
+}
@@ -1,22 +1,193 @@
-use crate::history::{HistoryEntry, HistoryStorage};
-use tauri::State;
+use crate::active_window::get_active_app;
+use crate::commands::active_window::{ensure_not_blocked, ensure_not_secure_input};
+use crate::commands::dictionary::add_corrections;
+use crate::commands::text::{
+    clipboard_restore_delay, delete_chars_blocking, insert_text_blocking, insertion_method,
+    run_on_main_thread_blocking,
+};
+use crate::commands::usage::record_usage;
+use crate::dictionary_suggestions::{DictionarySuggestion, DictionarySuggestions};
+use crate::events::{self, AppEvent};
+use crate::formatter::{self, FormatPreviews};
+use crate::history::{
+    settings_hash, HistoryContext, HistoryEntry, HistoryPage, HistoryStorage, TurnSnapshot,
+    DEFAULT_HISTORY_PAGE_SIZE,
+};
+use crate::paste_cycle::PasteCycle;
+use crate::settings::{get_setting_from_store, CleanupPromptSections};
+use crate::telemetry::FailureCategory;
+use crate::text_diff::{diff_words, word_corrections, DiffSegment, WordCorrection};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
 
-/// Add a new entry to the dictation history
+/// Time for focus to return to the target app after the overlay's edit box closes
+const FOCUS_RETURN_DELAY_MS: u64 = 150;
+
+/// Settings that change how a transcript is formatted, fingerprinted in each snapshot
+const FORMATTING_SETTING_KEYS: &[&str] = &[
+    "llm_provider",
+    "cleanup_prompt_sections",
+    "short_utterance_max_words",
+    "formatter_order",
+    "ollama_url",
+    "ollama_model",
+    "max_prompt_tokens",
+    "prompt_truncation",
+];
+
+/// A history entry's transcript formatted again with the current settings
+#[derive(Debug, Clone, Serialize)]
+pub struct ReRunResult {
+    /// The entry as originally recorded
+    pub entry: HistoryEntry,
+    /// The transcript formatted with the current settings
+    pub text: String,
+    /// The settings the re-run used
+    pub snapshot: TurnSnapshot,
+    /// Whether formatting settings differ from when the entry was recorded
+    /// (`None` for entries recorded without a snapshot)
+    pub settings_changed: Option<bool>,
+}
+
+/// Add a new entry to the dictation history and the current session transcript
 #[tauri::command]
 pub async fn add_history_entry(
+    app: AppHandle,
     text: String,
-    history: State<'_, HistoryStorage>,
+    duration_ms: Option<u64>,
+    raw_text: Option<String>,
 ) -> Result<HistoryEntry, String> {
-    history.add_entry(text)
+    record_history_entry(&app, text, duration_ms, raw_text, None)
+}
+
+/// Announce a turn the overlay gave up on, e.g. when the server stopped answering
+#[tauri::command]
+pub fn report_turn_failed(app: AppHandle, error: String, category: Option<FailureCategory>) {
+    log::warn!("Turn failed: {}", error);
+    events::publish(
+        &app,
+        AppEvent::TurnFailed {
+            error,
+            category: category.unwrap_or(FailureCategory::Server),
+        },
+    );
+}
+
+/// Record dictated text in history and announce the completed turn.
+/// Shared by the command and dictations completed entirely in Rust.
+/// `provider` is the STT provider that served the turn, defaulting to the selected one.
+pub fn record_history_entry(
+    app: &AppHandle,
+    text: String,
+    duration_ms: Option<u64>,
+    raw_text: Option<String>,
+    provider: Option<&str>,
+) -> Result<HistoryEntry, String> {
+    let context = HistoryContext {
+        app_name: get_active_app()
+            .map(|active| active.app_name)
+            .filter(|name| !name.is_empty()),
+        provider: provider
+            .map(String::from)
+            .or_else(|| get_setting_from_store::<Option<String>>(app, "stt_provider", None)),
+        duration_ms,
+        raw_text: raw_text.filter(|raw| !raw.trim().is_empty()),
+        snapshot: Some(current_turn_snapshot(app)),
+    };
+    let history = app.state::<HistoryStorage>();
+    let entry = history.add_entry(text.clone(), context)?;
+    record_usage(app, &entry);
+    events::publish(app, AppEvent::TurnCompleted { text });
+    Ok(entry)
+}
+
+/// Tell every window to reload history when a turn completes
+pub fn spawn_history_subscriber(app: &AppHandle) {
+    events::spawn_subscriber(app, "history", |app, event| {
+        if let AppEvent::TurnCompleted { .. } = event {
+            // New text was inserted after the last paste, so it must not be replaced
+            app.state::<PasteCycle>().reset();
+            let _ = app.emit("history-changed", ());
+        }
+    });
+}
+
+/// Paste the transcription `index` entries back (0 is the most recent) into the focused app
+#[tauri::command]
+pub async fn paste_history_entry(app: AppHandle, index: usize) -> Result<(), String> {
+    let paste_app = app.clone();
+    run_on_main_thread_blocking(&app, move || {
+        if paste_history_entry_blocking(&paste_app, index, 0)? {
+            Ok(())
+        } else {
+            Err(format!("No history entry at index {}", index))
+        }
+    })
 }
 
-/// Get dictation history entries
+/// Paste history entry `index`, first deleting `replace_chars` characters of an earlier
+/// paste. Returns false if history has no entry that far back.
+pub fn paste_history_entry_blocking(
+    app: &AppHandle,
+    index: usize,
+    replace_chars: usize,
+) -> Result<bool, String> {
+    let Some(entry) = app.state::<HistoryStorage>().nth_latest(index)? else {
+        return Ok(false);
+    };
+    let inserted_chars = paste_text_blocking(app, &entry.text, replace_chars)?;
+    app.state::<PasteCycle>()
+        .record_paste(index, inserted_chars, Instant::now());
+    Ok(true)
+}
+
+/// Paste `text` into the focused app, first deleting `replace_chars` characters.
+/// Returns how many characters were typed into the app.
+fn paste_text_blocking(app: &AppHandle, text: &str, replace_chars: usize) -> Result<usize, String> {
+    ensure_not_blocked(app)?;
+    ensure_not_secure_input(app)?;
+
+    // Clipboard-only insertion never put the earlier entry into the app
+    let method = insertion_method(app);
+    let inserted_chars = if method.inserts_text() {
+        delete_chars_blocking(replace_chars)?;
+        text.chars().count()
+    } else {
+        0
+    };
+    insert_text_blocking(text, method, clipboard_restore_delay(app))?;
+    Ok(inserted_chars)
+}
+
+/// Paste the history entry with `id` into the focused app, e.g. a pinned entry
+pub(crate) fn paste_entry_by_id(app: &AppHandle, id: &str) -> Result<(), String> {
+    let entry = app
+        .state::<HistoryStorage>()
+        .get(id)?
+        .ok_or_else(|| format!("No history entry with ID {}", id))?;
+    let paste_app = app.clone();
+    run_on_main_thread_blocking(app, move || {
+        paste_text_blocking(&paste_app, &entry.text, 0)?;
+        // A pinned entry isn't part of the paste-last cycle
+        paste_app.state::<PasteCycle>().reset();
+        Ok(())
+    })
+}
+
+/// Get a page of dictation history, newest first, optionally filtered by a search query
 #[tauri::command]
 pub async fn get_history(
-    limit: Option<usize>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+    query: Option<String>,
     history: State<'_, HistoryStorage>,
-) -> Result<Vec<HistoryEntry>, String> {
-    history.get_all(limit)
+) -> Result<HistoryPage, String> {
+    history.get_page(
+        page.unwrap_or(0),
+        page_size.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE),
+        query.as_deref(),
+    )
 }
 
 /// Delete a history entry by ID
@@ -28,8 +199,195 @@ pub async fn delete_history_entry(
     history.delete(&id)
 }
 
-/// Clear all history entries
+/// Clear all history entries except pinned ones
 #[tauri::command]
 pub async fn clear_history(history: State<'_, HistoryStorage>) -> Result<(), String> {
     history.clear()
 }
+
+/// Pin or unpin a history entry. Pinned entries are kept when the history is
+/// cleared and listed first in the command palette. Returns false if there is no
+/// entry with that ID.
+#[tauri::command]
+pub async fn pin_history_entry(
+    app: AppHandle,
+    id: String,
+    pinned: bool,
+    history: State<'_, HistoryStorage>,
+) -> Result<bool, String> {
+    let updated = history.set_pinned(&id, pinned)?;
+    if updated {
+        let _ = app.emit("history-changed", ());
+    }
+    Ok(updated)
+}
+
+/// Every pinned history entry, newest first
+#[tauri::command]
+pub async fn list_pinned(history: State<'_, HistoryStorage>) -> Result<Vec<HistoryEntry>, String> {
+    history.pinned()
+}
+
+/// The formatting settings in effect right now
+pub fn current_turn_snapshot(app: &AppHandle) -> TurnSnapshot {
+    // An array keeps the fingerprint independent of JSON object key order
+    let formatting_settings = FORMATTING_SETTING_KEYS
+        .iter()
+        .map(|key| get_setting_from_store(app, key, serde_json::Value::Null))
+        .collect();
+    TurnSnapshot {
+        llm_provider: get_setting_from_store(app, "llm_provider", None),
+        prompt_sections: get_setting_from_store::<Option<CleanupPromptSections>>(
+            app,
+            "cleanup_prompt_sections",
+            None,
+        ),
+        settings_hash: settings_hash(&serde_json::Value::Array(formatting_settings)),
+    }
+}
+
+/// Format a history entry's raw transcript again with the current settings, without
+/// inserting the result or changing history
+#[tauri::command]
+pub async fn re_run_with_current_settings(
+    app: AppHandle,
+    id: String,
+) -> Result<ReRunResult, String> {
+    let entry = app
+        .state::<HistoryStorage>()
+        .get(&id)?
+        .ok_or_else(|| format!("No history entry with ID {}", id))?;
+    let raw = entry
+        .raw_text
+        .clone()
+        .ok_or_else(|| "This entry was recorded without its raw transcript".to_string())?;
+
+    let snapshot = current_turn_snapshot(&app);
+    let text = formatter::preview_transcript(&app, &raw, entry.app_name.clone())?;
+    let settings_changed = entry
+        .snapshot
+        .as_ref()
+        .map(|recorded| recorded.settings_hash != snapshot.settings_hash);
+
+    Ok(ReRunResult {
+        entry,
+        text,
+        snapshot,
+        settings_changed,
+    })
+}
+
+/// Word-level diff between a history entry's raw transcript and its formatted text
+#[tauri::command]
+pub async fn get_turn_diff(
+    id: String,
+    history: State<'_, HistoryStorage>,
+) -> Result<Vec<DiffSegment>, String> {
+    let entry = history
+        .get(&id)?
+        .ok_or_else(|| format!("No history entry with ID {}", id))?;
+    let raw = entry
+        .raw_text
+        .ok_or_else(|| "This entry was recorded without its raw transcript".to_string())?;
+    Ok(diff_words(&raw, &entry.text))
+}
+
+/// Replace the most recent dictation with the user's correction, both in the focused
+/// app (by deleting the inserted text and inserting the correction) and in history.
+/// Only meant for right after the dictation, before anything else was typed.
+/// Returns the corrected words. They are added to the personal dictionary when
+/// `add_to_dictionary` is set, and offered as dictionary suggestions otherwise.
+#[tauri::command]
+pub async fn correct_last_entry(
+    app: AppHandle,
+    text: String,
+    add_to_dictionary: bool,
+) -> Result<Vec<WordCorrection>, String> {
+    let history = app.state::<HistoryStorage>();
+    let entry = history
+        .latest()?
+        .ok_or_else(|| "There is no dictation to correct".to_string())?;
+    if entry.text == text {
+        return Ok(Vec::new());
+    }
+
+    std::thread::sleep(Duration::from_millis(FOCUS_RETURN_DELAY_MS));
+    // Clipboard-only insertion never put the dictation into the app
+    let method = insertion_method(&app);
+    let replace_chars = if method.inserts_text() {
+        entry.text.chars().count()
+    } else {
+        0
+    };
+    let restore_delay = clipboard_restore_delay(&app);
+    let correction = text.clone();
+    run_on_main_thread_blocking(&app, move || {
+        delete_chars_blocking(replace_chars)?;
+        insert_text_blocking(&correction, method, restore_delay)
+    })?;
+
+    history.update_text(&entry.id, &text)?;
+    // The text in the app changed, so a cycle press must not replace it by length
+    app.state::<PasteCycle>().reset();
+    let _ = app.emit("history-changed", ());
+
+    let corrections = word_corrections(&entry.text, &text);
+    if add_to_dictionary {
+        if !corrections.is_empty() {
+            add_corrections(&app, &corrections)?;
+        }
+    } else {
+        let suggested = app.state::<DictionarySuggestions>().record(&corrections);
+        if !suggested.is_empty() {
+            let _ = app.emit("dictionary-suggested", &suggested);
+        }
+    }
+    Ok(corrections)
+}
+
+/// Remove the latest dictation from the focused app and from history, for the
+/// "scratch that" voice command
+pub fn delete_last_dictation(app: &AppHandle) -> Result<(), String> {
+    let history = app.state::<HistoryStorage>();
+    let Some(entry) = history.latest()? else {
+        return Ok(());
+    };
+    // Clipboard-only insertion never put the dictation into the app
+    if insertion_method(app).inserts_text() {
+        let count = entry.text.chars().count();
+        run_on_main_thread_blocking(app, move || delete_chars_blocking(count))?;
+    }
+
+    history.delete(&entry.id)?;
+    app.state::<PasteCycle>().reset();
+    let _ = app.emit("history-changed", ());
+    Ok(())
+}
+
+/// Dictionary entries suggested from earlier corrections, most often corrected first
+#[tauri::command]
+pub async fn get_dictionary_suggestions(
+    suggestions: State<'_, DictionarySuggestions>,
+) -> Result<Vec<DictionarySuggestion>, String> {
+    Ok(suggestions.list())
+}
+
+/// Stop offering a suggested dictionary entry, after it was added or dismissed
+#[tauri::command]
+pub async fn resolve_dictionary_suggestion(
+    suggestions: State<'_, DictionarySuggestions>,
+    heard: String,
+    corrected: String,
+) -> Result<bool, String> {
+    Ok(suggestions.resolve(&heard, &corrected))
+}
+
+/// Hand a server formatting preview back to the Rust caller waiting for it
+#[tauri::command]
+pub fn complete_format_preview(
+    preview_id: u64,
+    text: String,
+    previews: State<'_, FormatPreviews>,
+) -> bool {
+    previews.complete(preview_id, text)
+}
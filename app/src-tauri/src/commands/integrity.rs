@@ -0,0 +1,31 @@
+use crate::integrity::{self, DataFileIssue, DataFileIssues};
+use crate::stt::models::WhisperModelStore;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Verify downloaded Whisper models in the background, reporting corrupt ones
+pub fn spawn_model_check(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let issues = integrity::check_models(&app.state::<WhisperModelStore>());
+        if issues.is_empty() {
+            return;
+        }
+        let data_file_issues = app.state::<DataFileIssues>();
+        for issue in issues {
+            data_file_issues.add(issue);
+        }
+        let _ = app.emit("data-file-issues-changed", ());
+    });
+}
+
+/// Data files found corrupt at startup and moved aside
+#[tauri::command]
+pub fn get_data_file_issues(issues: State<'_, DataFileIssues>) -> Vec<DataFileIssue> {
+    issues.list()
+}
+
+#[tauri::command]
+pub fn dismiss_data_file_issues(app: AppHandle) {
+    app.state::<DataFileIssues>().clear();
+    let _ = app.emit("data-file-issues-changed", ());
+}
@@ -0,0 +1,74 @@
+use crate::language::{language_hint, language_name, next_language, normalize_language};
+use crate::settings::get_setting_from_store;
+use crate::stt::SttManager;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// Language hint for in-app transcription, `None` to detect the spoken language
+pub(crate) fn dictation_language(app: &AppHandle) -> Option<String> {
+    let language: Option<String> = get_setting_from_store(app, "language", None);
+    let auto_detect: bool = get_setting_from_store(app, "auto_detect_language", false);
+    language_hint(language.as_deref(), auto_detect)
+}
+
+/// Languages the hotkey and tray switch between
+pub(crate) fn dictation_languages(app: &AppHandle) -> Vec<String> {
+    let languages: Vec<String> = get_setting_from_store(app, "dictation_languages", Vec::new());
+    languages
+        .iter()
+        .filter_map(|code| normalize_language(code))
+        .collect()
+}
+
+/// Dictate in `language`, or detect the spoken language when `None`. Saves the
+/// choice, rebuilds in-app providers and tells the windows so the overlay passes
+/// the new hint to the server.
+pub(crate) fn set_dictation_language(
+    app: &AppHandle,
+    language: Option<&str>,
+) -> Result<(), String> {
+    let language = match language {
+        Some(code) => {
+            Some(normalize_language(code).ok_or_else(|| format!("Unknown language: {}", code))?)
+        }
+        None => None,
+    };
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    match &language {
+        Some(code) => {
+            store.set("language", code.clone());
+            store.set("auto_detect_language", false);
+        }
+        None => store.set("auto_detect_language", true),
+    }
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    super::stt::rebuild_providers(app, &app.state::<SttManager>())?;
+
+    log::info!(
+        "Dictation language: {}",
+        language
+            .as_deref()
+            .and_then(language_name)
+            .unwrap_or("auto-detect")
+    );
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
+/// Switch to the next of the user's dictation languages
+pub(crate) fn cycle_language(app: &AppHandle) {
+    let languages = dictation_languages(app);
+    let Some(next) = next_language(dictation_language(app).as_deref(), &languages) else {
+        log::info!("CycleLanguage: no dictation languages configured");
+        return;
+    };
+    if let Err(e) = set_dictation_language(app, Some(&next)) {
+        log::error!("Failed to switch dictation language: {}", e);
+    }
+}
@@ -1,4 +1,34 @@
+pub mod active_window;
+pub mod audio;
+pub mod auth;
+pub mod benchmark;
+pub mod capture;
+pub mod connection;
+pub mod diagnostics;
+pub mod dictation_pause;
+pub mod dictionary;
+pub mod export;
+pub mod formatting;
+pub mod hid;
 pub mod history;
+pub mod integrity;
+pub mod language;
+pub mod notes;
 pub mod overlay;
+pub mod palette;
+pub mod pending_transcriptions;
+pub mod prompt_presets;
+pub mod recordings;
+pub mod remote_control;
+pub mod secrets;
+pub mod session;
 pub mod settings;
+pub mod snippets;
+pub mod stt;
+pub mod support;
+pub mod telemetry;
 pub mod text;
+pub mod text_transforms;
+pub mod updater;
+pub mod usage;
+pub mod window_state;
@@ -0,0 +1,58 @@
+use crate::commands::active_window::recording_app;
+use crate::notes::{append_note, format_note, is_markdown, DEFAULT_NOTES_FILE_NAME};
+use crate::settings::get_setting_from_store;
+use chrono::Local;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// The notes file from settings, or the default one in the documents directory
+fn notes_file_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let path: Option<String> = get_setting_from_store(app, "notes_file_path", None);
+    if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+        return Ok(PathBuf::from(path.trim()));
+    }
+    app.path()
+        .document_dir()
+        .or_else(|_| app.path().app_data_dir())
+        .map(|dir| dir.join(DEFAULT_NOTES_FILE_NAME))
+        .map_err(|e| format!("Failed to resolve notes directory: {}", e))
+}
+
+/// Path dictations are appended to in notes mode
+#[tauri::command]
+pub fn get_notes_file_path(app: AppHandle) -> Result<String, String> {
+    notes_file_path(&app).map(|path| path.to_string_lossy().into_owned())
+}
+
+/// Append a dictation to the notes file, with the app it was dictated in when
+/// `notes_include_app` is on
+pub(crate) fn append_dictation_to_notes(app: &AppHandle, text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    let path = notes_file_path(app)?;
+    let app_name = get_setting_from_store(app, "notes_include_app", true)
+        .then(|| recording_app(app))
+        .flatten()
+        .map(|target| target.app_name);
+    let note = format_note(Local::now(), text, app_name.as_deref(), is_markdown(&path));
+    append_note(&path, &note)?;
+    log::info!("Appended a note to {}", path.display());
+    Ok(())
+}
+
+/// Turn notes mode on or off. Used by the tray menu.
+pub(crate) fn set_notes_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.set("notes_enabled", enabled);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    log::info!("Notes mode {}", if enabled { "on" } else { "off" });
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
@@ -1,13 +1,171 @@
-use tauri::{AppHandle, Manager};
+use crate::events::{self, AppEvent};
+use crate::overlay::{
+    clamp_overlay_scale, locate, restore_center, MonitorArea, OverlayPosition,
+    DEFAULT_OVERLAY_SCALE,
+};
+use crate::settings::get_setting_from_store;
+use tauri::{AppHandle, Emitter, Manager, Monitor, WebviewWindow};
+use tauri_plugin_store::StoreExt;
 
+/// Label of the recording overlay window
+const OVERLAY_WINDOW_LABEL: &str = "overlay";
+
+/// Zoom of the overlay's contents from settings
+pub fn overlay_scale(app: &AppHandle) -> f64 {
+    clamp_overlay_scale(get_setting_from_store(
+        app,
+        "overlay_scale",
+        DEFAULT_OVERLAY_SCALE,
+    ))
+}
+
+pub(crate) fn monitor_area(monitor: &Monitor) -> MonitorArea {
+    MonitorArea {
+        name: monitor.name().cloned(),
+        x: f64::from(monitor.position().x),
+        y: f64::from(monitor.position().y),
+        width: f64::from(monitor.size().width),
+        height: f64::from(monitor.size().height),
+        scale_factor: monitor.scale_factor(),
+    }
+}
+
+/// Apply the saved zoom and move the overlay to its saved position, or the default
+/// corner if none is saved
+pub fn restore_overlay_geometry(window: &WebviewWindow) -> Result<(), String> {
+    let app = window.app_handle();
+    window
+        .set_zoom(overlay_scale(app))
+        .map_err(|e| e.to_string())?;
+
+    let saved: Option<OverlayPosition> = get_setting_from_store(app, "overlay_position", None);
+    let saved_monitor: Option<String> = get_setting_from_store(app, "overlay_monitor", None);
+    let monitors: Vec<MonitorArea> = window
+        .available_monitors()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(monitor_area)
+        .collect();
+    let primary = window
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| monitor_area(&monitor));
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let Some((x, y)) = restore_center(
+        saved,
+        saved_monitor.as_deref(),
+        &monitors,
+        primary.as_ref(),
+        (f64::from(size.width), f64::from(size.height)),
+    ) else {
+        return Ok(());
+    };
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: (x - f64::from(size.width) / 2.0).round() as i32,
+            y: (y - f64::from(size.height) / 2.0).round() as i32,
+        }))
+        .map_err(|e| e.to_string())
+}
+
+/// Apply the overlay zoom whenever it changes in settings, and ask the overlay to
+/// resize to its zoomed contents. Also tells it when secure input blocked insertion.
+pub fn spawn_overlay_subscriber(app: &AppHandle) {
+    let mut applied_scale = overlay_scale(app);
+    events::spawn_subscriber(app, "overlay", move |app, event| {
+        if event == AppEvent::SecureInputBlocked {
+            let _ = app.emit("secure-input-blocked", ());
+            return;
+        }
+        if event != AppEvent::SettingsChanged {
+            return;
+        }
+        let scale = overlay_scale(app);
+        if scale == applied_scale {
+            return;
+        }
+        applied_scale = scale;
+        let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+            return;
+        };
+        if let Err(e) = window.set_zoom(scale) {
+            log::warn!("Failed to zoom overlay: {}", e);
+            return;
+        }
+        let _ = window.emit("overlay-scale-changed", scale);
+    });
+}
+
+/// Move the overlay so its top-left corner is at the given physical screen
+/// position, e.g. where it was dragged to, and remember it for the next launch
+#[tauri::command]
+pub async fn set_overlay_position(app: AppHandle, x: i32, y: i32) -> Result<(), String> {
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| e.to_string())?;
+
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let center = (
+        f64::from(x) + f64::from(size.width) / 2.0,
+        f64::from(y) + f64::from(size.height) / 2.0,
+    );
+    let monitors: Vec<MonitorArea> = window
+        .available_monitors()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(monitor_area)
+        .collect();
+    let Some((monitor, position)) = locate(center, &monitors) else {
+        return Ok(());
+    };
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.set("overlay_position", serde_json::json!(position));
+    store.set("overlay_monitor", serde_json::json!(monitor));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+/// Forget the saved overlay position and move the overlay back to the default corner
+#[tauri::command]
+pub async fn reset_overlay_position(app: AppHandle) -> Result<(), String> {
+    forget_overlay_position(&app)
+}
+
+/// Forget the saved overlay position and move the overlay back to the default corner
+pub fn forget_overlay_position(app: &AppHandle) -> Result<(), String> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.delete("overlay_position");
+    store.delete("overlay_monitor");
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    match app.get_webview_window(OVERLAY_WINDOW_LABEL) {
+        Some(window) => restore_overlay_geometry(&window),
+        None => Ok(()),
+    }
+}
+
+/// Resize the overlay to fit its contents, given their unzoomed size
 #[tauri::command]
 pub async fn resize_overlay(app: AppHandle, width: f64, height: f64) -> Result<(), String> {
     // Enforce minimum dimensions to prevent invisible window
     let min_size = 48.0;
-    let width = width.max(min_size);
-    let height = height.max(min_size);
+    let scale = overlay_scale(&app);
+    let width = width.max(min_size) * scale;
+    let height = height.max(min_size) * scale;
 
-    if let Some(window) = app.get_webview_window("overlay") {
+    if let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) {
         // Get current center point from current position and size
         // This allows the overlay to be dragged and maintain its new position
         let center = if let (Ok(pos), Ok(size)) = (window.outer_position(), window.outer_size()) {
@@ -37,3 +195,17 @@ pub async fn resize_overlay(app: AppHandle, width: f64, height: f64) -> Result<(
     }
     Ok(())
 }
+
+/// Let the overlay take keyboard focus while its correction box is open, and stop
+/// it taking focus again once the box closes
+#[tauri::command]
+pub async fn set_overlay_editing(app: AppHandle, editing: bool) -> Result<(), String> {
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    window.set_focusable(editing).map_err(|e| e.to_string())?;
+    if editing {
+        window.set_focus().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
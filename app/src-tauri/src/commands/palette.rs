@@ -0,0 +1,172 @@
+use crate::commands::history::{paste_entry_by_id, paste_history_entry};
+use crate::commands::prompt_presets::{active_preset_id, stored_presets};
+use crate::commands::stt::ServerSttProviders;
+use crate::formatter::formatting_enabled;
+use crate::history::HistoryStorage;
+use crate::palette::{filter_items, one_line_title, PaletteAction, PaletteItem, MAX_TITLE_CHARS};
+use crate::settings::get_setting_from_store;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Label of the command palette window
+pub const PALETTE_WINDOW_LABEL: &str = "palette";
+
+/// Inner size of the command palette window, in logical pixels
+pub const PALETTE_WINDOW_SIZE: (f64, f64) = (560.0, 380.0);
+
+/// Recent dictations listed in the palette
+const PALETTE_HISTORY_ENTRIES: usize = 20;
+
+/// Time for focus to return to the app the palette was opened over before pasting
+const FOCUS_RETURN_DELAY: Duration = Duration::from_millis(150);
+
+/// Show the command palette, creating it if needed, and tell it to start afresh.
+/// Emits `palette-opened` to the palette.
+pub fn show_palette_window(app: &AppHandle) -> tauri::Result<()> {
+    let window = match app.get_webview_window(PALETTE_WINDOW_LABEL) {
+        Some(window) => window,
+        None => tauri::WebviewWindowBuilder::new(
+            app,
+            PALETTE_WINDOW_LABEL,
+            tauri::WebviewUrl::App("palette.html".into()),
+        )
+        .title("Quick Actions")
+        .inner_size(PALETTE_WINDOW_SIZE.0, PALETTE_WINDOW_SIZE.1)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .center()
+        .visible(false)
+        .build()?,
+    };
+    window.show()?;
+    window.set_focus()?;
+    let _ = app.emit_to(PALETTE_WINDOW_LABEL, "palette-opened", ());
+    Ok(())
+}
+
+/// Close the command palette if it is open, and open it otherwise. Used by the
+/// palette hotkey.
+pub(crate) fn toggle_palette_window(app: &AppHandle) {
+    let visible = app
+        .get_webview_window(PALETTE_WINDOW_LABEL)
+        .is_some_and(|window| window.is_visible().unwrap_or(false));
+    let result = if visible {
+        hide_palette(app)
+    } else {
+        show_palette_window(app)
+    };
+    if let Err(e) = result {
+        log::error!("Failed to toggle command palette: {}", e);
+    }
+}
+
+fn hide_palette(app: &AppHandle) -> tauri::Result<()> {
+    match app.get_webview_window(PALETTE_WINDOW_LABEL) {
+        Some(window) => window.hide(),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub async fn open_palette_window(app: AppHandle) -> Result<(), String> {
+    show_palette_window(&app).map_err(|e| e.to_string())
+}
+
+/// Hide the command palette, returning focus to the app it was opened over
+#[tauri::command]
+pub async fn hide_palette_window(app: AppHandle) -> Result<(), String> {
+    hide_palette(&app).map_err(|e| e.to_string())
+}
+
+/// Every palette item: pinned and recent dictations, then the formatting switch,
+/// prompt presets and STT providers
+fn palette_items(app: &AppHandle) -> Result<Vec<PaletteItem>, String> {
+    let mut items = Vec::new();
+
+    let history = app.state::<HistoryStorage>();
+    for entry in history.pinned()? {
+        items.push(PaletteItem {
+            title: one_line_title(&entry.text, MAX_TITLE_CHARS),
+            action: PaletteAction::PastePinned { id: entry.id },
+            detail: "Pinned".to_string(),
+            active: false,
+        });
+    }
+
+    let history = history.get_page(0, PALETTE_HISTORY_ENTRIES, None)?;
+    for (index, entry) in history.entries.iter().enumerate() {
+        items.push(PaletteItem {
+            action: PaletteAction::PasteHistory { index },
+            title: one_line_title(&entry.text, MAX_TITLE_CHARS),
+            detail: match &entry.app_name {
+                Some(app_name) => format!("Paste again · {}", app_name),
+                None => "Paste again".to_string(),
+            },
+            active: false,
+        });
+    }
+
+    let formatting = formatting_enabled(app);
+    items.push(PaletteItem {
+        action: PaletteAction::ToggleFormatting,
+        title: if formatting {
+            "Turn LLM formatting off".to_string()
+        } else {
+            "Turn LLM formatting on".to_string()
+        },
+        detail: "Formatting".to_string(),
+        active: false,
+    });
+
+    let active_preset = active_preset_id(app);
+    for preset in stored_presets(app) {
+        items.push(PaletteItem {
+            active: active_preset.as_deref() == Some(preset.id.as_str()),
+            action: PaletteAction::ApplyPromptPreset { id: preset.id },
+            title: preset.name,
+            detail: "Prompt preset".to_string(),
+        });
+    }
+
+    let current_provider: Option<String> = get_setting_from_store(app, "stt_provider", None);
+    for provider in app.state::<ServerSttProviders>().get() {
+        items.push(PaletteItem {
+            active: current_provider.as_deref() == Some(provider.value.as_str()),
+            action: PaletteAction::SwitchSttProvider {
+                value: provider.value,
+            },
+            title: provider.label,
+            detail: "STT provider".to_string(),
+        });
+    }
+
+    Ok(items)
+}
+
+/// Palette items matching `query`, all of them when it is empty
+#[tauri::command]
+pub async fn list_palette_items(
+    app: AppHandle,
+    query: Option<String>,
+) -> Result<Vec<PaletteItem>, String> {
+    let items = palette_items(&app)?;
+    Ok(filter_items(&items, query.as_deref().unwrap_or_default()))
+}
+
+/// Hide the palette and paste history entry `index` into the app it was opened over
+#[tauri::command]
+pub async fn paste_from_palette(app: AppHandle, index: usize) -> Result<(), String> {
+    hide_palette(&app).map_err(|e| e.to_string())?;
+    std::thread::sleep(FOCUS_RETURN_DELAY);
+    paste_history_entry(app, index).await
+}
+
+/// Hide the palette and paste the pinned entry `id` into the app it was opened over
+#[tauri::command]
+pub async fn paste_pinned_from_palette(app: AppHandle, id: String) -> Result<(), String> {
+    hide_palette(&app).map_err(|e| e.to_string())?;
+    std::thread::sleep(FOCUS_RETURN_DELAY);
+    paste_entry_by_id(&app, &id)
+}
@@ -0,0 +1,199 @@
+use crate::audio_upload::{
+    encode_opus, negotiate_encoding, ServerAudioEncodings, UploadEncoding,
+    DEFAULT_OPUS_BITRATE_KBPS,
+};
+use crate::commands::history::record_history_entry;
+use crate::events::{self, AppEvent};
+use crate::formatter::FormatPreviews;
+use crate::pending_transcriptions::PendingTranscriptions;
+use crate::recordings::RecordingInfo;
+use crate::settings::get_setting_from_store;
+use crate::state::AppState;
+use crate::stt::{AudioFormat, SttManager};
+use crate::turns::TurnQueue;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Time allowed for the server to transcribe and format one queued turn
+const QUEUED_TURN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Wait after reconnecting before retrying, so the overlay can sync settings first
+const RECONNECT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Request for the overlay to replay a queued turn's audio to the server.
+/// The result comes back through `complete_format_preview` like a preview.
+#[derive(Debug, Clone, Serialize)]
+struct QueuedAudioRequest {
+    preview_id: u64,
+    sample_rate: u32,
+    #[serde(flatten)]
+    audio: QueuedAudio,
+    /// Server STT provider to transcribe with instead of the selected one
+    stt_provider: Option<String>,
+}
+
+/// A queued turn's audio, encoded as the server negotiated
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "encoding", rename_all = "lowercase")]
+enum QueuedAudio {
+    /// 16-bit mono samples
+    Pcm16 { samples: Vec<i16> },
+    /// Base64 Opus packets
+    Opus { packets: Vec<String> },
+}
+
+/// Encode a queued turn's audio to Opus if the server decodes it, falling back
+/// to PCM
+fn encode_queued_audio(app: &AppHandle, format: AudioFormat, samples: Vec<i16>) -> QueuedAudio {
+    let server_encodings = app.state::<ServerAudioEncodings>().get();
+    if negotiate_encoding(&server_encodings, format.sample_rate) == UploadEncoding::Pcm16 {
+        return QueuedAudio::Pcm16 { samples };
+    }
+    let bitrate = get_setting_from_store(app, "opus_bitrate_kbps", DEFAULT_OPUS_BITRATE_KBPS);
+    match encode_opus(format.sample_rate, &samples, bitrate) {
+        Ok(packets) => QueuedAudio::Opus {
+            packets: packets
+                .iter()
+                .map(|packet| STANDARD.encode(packet))
+                .collect(),
+        },
+        Err(e) => {
+            log::warn!("Sending queued audio as PCM: {}", e);
+            QueuedAudio::Pcm16 { samples }
+        }
+    }
+}
+
+/// Queue the audio of the offline turn that was just finalized, if nothing transcribed it
+pub fn queue_unsent_turn(app: &AppHandle) {
+    let Some((format, samples)) = app.state::<SttManager>().take_unsent_audio() else {
+        return;
+    };
+    match app.state::<PendingTranscriptions>().queue(format, &samples) {
+        Ok(queued) => {
+            log::info!("Server unreachable, queued turn {} for later", queued.id);
+            let _ = app.emit("pending-transcriptions-changed", ());
+        }
+        Err(e) => log::error!("Failed to queue turn for later: {}", e),
+    }
+}
+
+/// Retry queued turns whenever the server connection comes back
+pub fn spawn_pending_subscriber(app: &AppHandle) {
+    events::spawn_subscriber(app, "pending", |app, event| {
+        if event != (AppEvent::ConnectionChanged { connected: true }) {
+            return;
+        }
+        let app = app.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(RECONNECT_RETRY_DELAY);
+            let has_queued = app
+                .state::<PendingTranscriptions>()
+                .list()
+                .is_ok_and(|queued| !queued.is_empty());
+            if has_queued {
+                match retry_pending(&app) {
+                    Ok(count) => log::info!("Transcribed {} queued turns", count),
+                    Err(e) => log::warn!("Failed to retry queued turns: {}", e),
+                }
+            }
+        });
+    });
+}
+
+/// Replay queued turns through the server, oldest first, adding their text to
+/// history. Returns how many produced text. Stops at the first failure, leaving the
+/// rest queued.
+fn retry_pending(app: &AppHandle) -> Result<usize, String> {
+    let pending = app.state::<PendingTranscriptions>();
+    if !pending.begin_retry() {
+        return Err("Queued dictations are already being retried".to_string());
+    }
+    let result = replay_queue(app, &pending);
+    pending.end_retry();
+    result
+}
+
+fn replay_queue(app: &AppHandle, pending: &PendingTranscriptions) -> Result<usize, String> {
+    let state = app.state::<AppState>();
+    let mut transcribed = 0;
+    for queued in pending.list()? {
+        if !state.server_connected.load(Ordering::SeqCst) {
+            return Err("Not connected to the server".to_string());
+        }
+        // The server handles one turn at a time, so live dictation goes first
+        if state.is_recording.load(Ordering::SeqCst) || app.state::<TurnQueue>().in_flight() > 0 {
+            log::info!("Dictation in progress, leaving the rest of the queue for later");
+            break;
+        }
+
+        let (format, samples) = pending.load(&queued.id)?;
+        let text = transcribe_queued(app, format, samples, None)?;
+        if !text.is_empty() {
+            // Not typed, since the app it was meant for may no longer be focused
+            record_history_entry(app, text, Some(queued.duration_ms), None, Some("server"))?;
+            transcribed += 1;
+        }
+        pending.remove(&queued.id)?;
+        let _ = app.emit("pending-transcriptions-changed", ());
+    }
+    Ok(transcribed)
+}
+
+/// Send queued audio to the server through the overlay and wait for the formatted text.
+/// `stt_provider` picks a server STT provider other than the selected one.
+pub(crate) fn transcribe_queued(
+    app: &AppHandle,
+    format: AudioFormat,
+    samples: Vec<i16>,
+    stt_provider: Option<&str>,
+) -> Result<String, String> {
+    if format.channels != 1 {
+        return Err(format!(
+            "Queued audio has {} channels, only mono can be replayed",
+            format.channels
+        ));
+    }
+    let audio = encode_queued_audio(app, format, samples);
+    let previews = app.state::<FormatPreviews>();
+    let (preview_id, receiver) = previews.register();
+    let result = app
+        .emit(
+            "queued-audio-request",
+            QueuedAudioRequest {
+                preview_id,
+                sample_rate: format.sample_rate,
+                audio,
+                stt_provider: stt_provider.map(String::from),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .and_then(|()| {
+            receiver
+                .recv_timeout(QUEUED_TURN_TIMEOUT)
+                .map_err(|_| "Server did not transcribe the queued dictation in time".to_string())
+        });
+    previews.cancel(preview_id);
+    result
+}
+
+/// List turns waiting for the server, oldest first
+#[tauri::command]
+pub async fn list_pending_transcriptions(
+    pending: State<'_, PendingTranscriptions>,
+) -> Result<Vec<RecordingInfo>, String> {
+    pending.list()
+}
+
+/// Transcribe queued turns now. Returns how many produced text.
+#[tauri::command]
+pub async fn retry_pending_transcriptions(app: AppHandle) -> Result<usize, String> {
+    let retry_app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || retry_pending(&retry_app))
+        .await
+        .map_err(|e| e.to_string())?
+}
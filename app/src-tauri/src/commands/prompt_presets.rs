@@ -0,0 +1,236 @@
+use crate::prompt_presets::{
+    active_preset, merge_presets, next_preset, parse_preset_file, render_preset_file,
+    validate_preset_name, PresetImport, PromptPreset,
+};
+use crate::settings::{get_setting_from_store, CleanupPromptSections};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// File name for exported presets when no path is given
+const DEFAULT_EXPORT_FILE_NAME: &str = "tambourine-prompt-presets.json";
+
+/// Saved presets and the one matching the current prompt
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptPresets {
+    pub presets: Vec<PromptPreset>,
+    pub active_id: Option<String>,
+}
+
+/// Saved prompt presets, in the order they were saved
+pub(crate) fn stored_presets(app: &AppHandle) -> Vec<PromptPreset> {
+    get_setting_from_store(app, "prompt_presets", Vec::new())
+}
+
+/// The current formatting prompt sections
+fn current_sections(app: &AppHandle) -> CleanupPromptSections {
+    get_setting_from_store(app, "cleanup_prompt_sections", None)
+        .unwrap_or_else(CleanupPromptSections::server_defaults)
+}
+
+/// ID of the preset matching the current prompt
+pub(crate) fn active_preset_id(app: &AppHandle) -> Option<String> {
+    active_preset(&stored_presets(app), &current_sections(app)).map(|preset| preset.id.clone())
+}
+
+fn save_presets(app: &AppHandle, presets: &[PromptPreset]) -> Result<(), String> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    let value =
+        serde_json::to_value(presets).map_err(|e| format!("Failed to serialize presets: {}", e))?;
+    store.set("prompt_presets", value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
+fn absolute_path(path: &str, action: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path.trim());
+    if !path.is_absolute() {
+        return Err(format!(
+            "{} path must be absolute: {}",
+            action,
+            path.display()
+        ));
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn list_prompt_presets(app: AppHandle) -> Result<PromptPresets, String> {
+    Ok(PromptPresets {
+        presets: stored_presets(&app),
+        active_id: active_preset_id(&app),
+    })
+}
+
+/// Save the current prompt as a new preset named `name`, or over the preset with
+/// `id`, renaming it to `name`
+#[tauri::command]
+pub async fn save_prompt_preset(
+    app: AppHandle,
+    name: String,
+    id: Option<String>,
+) -> Result<PromptPreset, String> {
+    let mut presets = stored_presets(&app);
+    let name = validate_preset_name(&name, &presets, id.as_deref())?;
+    let sections = current_sections(&app);
+    let preset = match id {
+        Some(id) => {
+            let preset = presets
+                .iter_mut()
+                .find(|preset| preset.id == id)
+                .ok_or_else(|| format!("Unknown prompt preset: {}", id))?;
+            preset.name = name;
+            preset.main = sections.main;
+            preset.advanced = sections.advanced;
+            preset.clone()
+        }
+        None => {
+            let preset = PromptPreset::new(name, &sections);
+            presets.push(preset.clone());
+            preset
+        }
+    };
+    save_presets(&app, &presets)?;
+    log::info!("Saved prompt preset \"{}\"", preset.name);
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn rename_prompt_preset(
+    app: AppHandle,
+    id: String,
+    name: String,
+) -> Result<PromptPreset, String> {
+    let mut presets = stored_presets(&app);
+    let name = validate_preset_name(&name, &presets, Some(&id))?;
+    let preset = presets
+        .iter_mut()
+        .find(|preset| preset.id == id)
+        .ok_or_else(|| format!("Unknown prompt preset: {}", id))?;
+    preset.name = name;
+    let preset = preset.clone();
+    save_presets(&app, &presets)?;
+    Ok(preset)
+}
+
+/// Delete a preset, leaving the current prompt as it is. Returns whether it existed.
+#[tauri::command]
+pub async fn delete_prompt_preset(app: AppHandle, id: String) -> Result<bool, String> {
+    let mut presets = stored_presets(&app);
+    let count = presets.len();
+    presets.retain(|preset| preset.id != id);
+    if presets.len() == count {
+        return Ok(false);
+    }
+    save_presets(&app, &presets)?;
+    Ok(true)
+}
+
+/// Make the preset with `id` the formatting prompt. Tells the windows, so the
+/// overlay sends the prompt to the server, and emits `prompt-preset-changed` with
+/// the preset.
+pub(crate) fn apply_preset(app: &AppHandle, id: &str) -> Result<PromptPreset, String> {
+    let preset = stored_presets(app)
+        .into_iter()
+        .find(|preset| preset.id == id)
+        .ok_or_else(|| format!("Unknown prompt preset: {}", id))?;
+    let sections = preset.apply_to(current_sections(app));
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    let value = serde_json::to_value(&sections)
+        .map_err(|e| format!("Failed to serialize prompt sections: {}", e))?;
+    store.set("cleanup_prompt_sections", value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    log::info!("Formatting with prompt preset \"{}\"", preset.name);
+    let _ = app.emit("settings-changed", ());
+    let _ = app.emit("prompt-preset-changed", &preset);
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn apply_prompt_preset(app: AppHandle, id: String) -> Result<PromptPreset, String> {
+    apply_preset(&app, &id)
+}
+
+/// Switch to the next preset
+pub(crate) fn cycle_prompt_preset(app: &AppHandle) {
+    let presets = stored_presets(app);
+    let Some(next) = next_preset(active_preset_id(app).as_deref(), &presets) else {
+        log::info!("CyclePromptPreset: no prompt presets saved");
+        return;
+    };
+    if let Err(e) = apply_preset(app, &next.id) {
+        log::error!("Failed to switch prompt preset: {}", e);
+    }
+}
+
+/// Write presets to a JSON file, all of them unless `ids` are given, returning the
+/// path written. Without a `path` the file goes to the downloads directory.
+#[tauri::command]
+pub async fn export_prompt_presets(
+    app: AppHandle,
+    path: Option<String>,
+    ids: Option<Vec<String>>,
+) -> Result<String, String> {
+    let presets: Vec<PromptPreset> = stored_presets(&app)
+        .into_iter()
+        .filter(|preset| ids.as_ref().is_none_or(|ids| ids.contains(&preset.id)))
+        .collect();
+    if presets.is_empty() {
+        return Err("There are no prompt presets to export".to_string());
+    }
+
+    let path = match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => absolute_path(&path, "Export")?,
+        None => app
+            .path()
+            .download_dir()
+            .or_else(|_| app.path().app_data_dir())
+            .map_err(|e| format!("Failed to resolve export directory: {}", e))?
+            .join(DEFAULT_EXPORT_FILE_NAME),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    fs::write(&path, render_preset_file(&presets)?)
+        .map_err(|e| format!("Failed to write presets: {}", e))?;
+
+    log::info!(
+        "Exported {} prompt presets to {}",
+        presets.len(),
+        path.display()
+    );
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Add the presets in a file. Presets named like an existing one replace it.
+#[tauri::command]
+pub async fn import_prompt_presets(app: AppHandle, path: String) -> Result<PresetImport, String> {
+    let path = absolute_path(&path, "Import")?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let imported = parse_preset_file(&content)?;
+
+    let mut presets = stored_presets(&app);
+    let import = merge_presets(&mut presets, imported);
+    save_presets(&app, &presets)?;
+    log::info!(
+        "Imported prompt presets from {}: {} added, {} replaced",
+        path.display(),
+        import.added.len(),
+        import.replaced.len()
+    );
+    Ok(import)
+}
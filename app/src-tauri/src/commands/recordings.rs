@@ -0,0 +1,49 @@
+use crate::recordings::{RecordingArchive, RecordingInfo, RetentionPolicy};
+use crate::settings::{
+    get_setting_from_store, DEFAULT_RECORDING_MAX_COUNT, DEFAULT_RECORDING_MAX_DAYS,
+};
+use crate::stt::SttManager;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Save the audio of the turn that was just finalized, if archiving is on
+pub fn archive_finished_turn(app: &AppHandle) {
+    let Some((format, samples)) = app.state::<SttManager>().take_finished_audio() else {
+        return;
+    };
+    let policy = RetentionPolicy {
+        max_count: get_setting_from_store(app, "recording_max_count", DEFAULT_RECORDING_MAX_COUNT),
+        max_days: get_setting_from_store(app, "recording_max_days", DEFAULT_RECORDING_MAX_DAYS),
+    };
+    match app
+        .state::<RecordingArchive>()
+        .save(format, &samples, policy)
+    {
+        Ok(recording) => {
+            log::info!("Saved recording {}", recording.id);
+            let _ = app.emit("recordings-changed", ());
+        }
+        Err(e) => log::error!("Failed to archive recording: {}", e),
+    }
+}
+
+/// List archived recordings, newest first
+#[tauri::command]
+pub async fn list_recordings(
+    archive: State<'_, RecordingArchive>,
+) -> Result<Vec<RecordingInfo>, String> {
+    archive.list()
+}
+
+/// Delete an archived recording
+#[tauri::command]
+pub async fn delete_recording(
+    app: AppHandle,
+    archive: State<'_, RecordingArchive>,
+    id: String,
+) -> Result<bool, String> {
+    let deleted = archive.delete(&id)?;
+    if deleted {
+        let _ = app.emit("recordings-changed", ());
+    }
+    Ok(deleted)
+}
@@ -0,0 +1,29 @@
+#[cfg(desktop)]
+use crate::remote_control;
+
+/// The token remote control clients must send, generated on first use
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn get_remote_control_token() -> Result<String, String> {
+    remote_control::control_token()
+}
+
+/// Replace the remote control token, so clients using the old one are refused
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn regenerate_remote_control_token() -> Result<String, String> {
+    remote_control::regenerate_control_token()
+}
+
+// Stubs for non-desktop platforms
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn get_remote_control_token() -> Result<String, String> {
+    Err("Remote control isn't supported on this platform".to_string())
+}
+
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn regenerate_remote_control_token() -> Result<String, String> {
+    Err("Remote control isn't supported on this platform".to_string())
+}
@@ -0,0 +1,19 @@
+use crate::secrets::{self, ApiKeyStatus};
+
+/// Store a provider API key in the OS keyring
+#[tauri::command]
+pub async fn set_api_key(provider: String, api_key: String) -> Result<(), String> {
+    secrets::set_api_key(&provider, &api_key)
+}
+
+/// Check whether a provider API key is stored (never returns the key itself)
+#[tauri::command]
+pub async fn get_api_key_status(provider: String) -> Result<ApiKeyStatus, String> {
+    secrets::get_api_key_status(&provider)
+}
+
+/// Delete a provider API key from the OS keyring
+#[tauri::command]
+pub async fn delete_api_key(provider: String) -> Result<bool, String> {
+    secrets::delete_api_key(&provider)
+}
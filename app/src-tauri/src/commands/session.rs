@@ -0,0 +1,100 @@
+use crate::commands::window_state::restore_window_state;
+use crate::events::{self, AppEvent};
+use crate::session::{SessionStore, SessionTranscript};
+use std::fs;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Label of the session transcript window
+pub const SESSION_WINDOW_LABEL: &str = "session";
+
+/// Default inner size of the session transcript window, in logical pixels
+pub const SESSION_WINDOW_SIZE: (f64, f64) = (480.0, 640.0);
+
+/// Append each completed turn to the session transcript
+pub fn spawn_session_subscriber(app: &AppHandle) {
+    events::spawn_subscriber(app, "session", |app, event| {
+        let AppEvent::TurnCompleted { text } = event else {
+            return;
+        };
+        match app.state::<SessionStore>().append(text) {
+            Ok(session_entry) => {
+                let _ = app.emit("session-entry-added", &session_entry);
+            }
+            Err(e) => log::warn!("Failed to update session transcript: {}", e),
+        }
+    });
+}
+
+/// Get the transcript of the current session
+#[tauri::command]
+pub async fn get_session_transcript(
+    session: State<'_, SessionStore>,
+) -> Result<SessionTranscript, String> {
+    session.snapshot()
+}
+
+/// Clear the session transcript and start a new session
+#[tauri::command]
+pub async fn reset_session_transcript(
+    app: AppHandle,
+    session: State<'_, SessionStore>,
+) -> Result<SessionTranscript, String> {
+    let transcript = session.reset()?;
+    let _ = app.emit("session-transcript-updated", &transcript);
+    Ok(transcript)
+}
+
+/// Export the session transcript as a text file and return its path.
+/// Written to the downloads directory, falling back to the app data directory.
+#[tauri::command]
+pub async fn export_session_transcript(
+    app: AppHandle,
+    session: State<'_, SessionStore>,
+) -> Result<String, String> {
+    let transcript = session.snapshot()?;
+
+    let dir = app
+        .path()
+        .download_dir()
+        .or_else(|_| app.path().app_data_dir())
+        .map_err(|e| format!("Failed to resolve export directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create export directory: {}", e))?;
+
+    let file_name = format!(
+        "tambourine-session-{}.txt",
+        transcript.started_at.format("%Y%m%d-%H%M%S")
+    );
+    let path = dir.join(file_name);
+    fs::write(&path, transcript.to_text())
+        .map_err(|e| format!("Failed to write session transcript: {}", e))?;
+
+    log::info!("Exported session transcript to {}", path.display());
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Show the session transcript window, creating it if needed
+#[tauri::command]
+pub async fn open_session_window(app: AppHandle) -> Result<(), String> {
+    show_session_window(&app).map_err(|e| e.to_string())
+}
+
+/// Show (or create) the session transcript window. Also used by the tray menu.
+pub fn show_session_window(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(SESSION_WINDOW_LABEL) {
+        window.show()?;
+        return window.set_focus();
+    }
+
+    let window = tauri::WebviewWindowBuilder::new(
+        app,
+        SESSION_WINDOW_LABEL,
+        tauri::WebviewUrl::App("session.html".into()),
+    )
+    .title("Session Transcript")
+    .inner_size(SESSION_WINDOW_SIZE.0, SESSION_WINDOW_SIZE.1)
+    .visible(false)
+    .build()?;
+    restore_window_state(&window);
+    window.show()?;
+    window.set_focus()
+}
@@ -1,101 +1,351 @@
-use crate::settings::HotkeyConfig;
+use crate::error::AppError;
+use crate::settings::{
+    current_default_hotkeys, describe_feature_flags, store_feature_flag, stored_feature_flags,
+    DefaultHotkeys, FeatureFlag, FeatureFlagState, HotkeyAvailability, HotkeyConfig,
+};
 use tauri::AppHandle;
 
 #[cfg(desktop)]
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+use crate::double_tap::DoubleTapTrigger;
+#[cfg(desktop)]
+use crate::media_keys::MediaKeyTrigger;
+#[cfg(desktop)]
+use crate::mouse_trigger::MouseTrigger;
+#[cfg(desktop)]
+use crate::sequence::SequenceState;
 
 #[cfg(desktop)]
-use tauri_plugin_store::StoreExt;
+use crate::settings::{HotkeyAction, HotkeyTrigger, ShortcutRegistrationFailure};
+
+#[cfg(desktop)]
+use std::sync::Mutex;
+#[cfg(desktop)]
+use tauri::{Emitter, Manager};
+
+#[cfg(desktop)]
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
 /// Temporarily unregister all global shortcuts.
 /// Call this before capturing a new hotkey to prevent the shortcuts from intercepting key presses.
 #[cfg(desktop)]
 #[tauri::command]
-pub async fn unregister_shortcuts(app: AppHandle) -> Result<(), String> {
+pub async fn unregister_shortcuts(app: AppHandle) -> Result<(), AppError> {
     log::info!("Temporarily unregistering all shortcuts for hotkey capture");
+    #[cfg(target_os = "linux")]
+    app.state::<crate::wayland_shortcuts::WaylandShortcuts>()
+        .unbind();
     let shortcut_manager = app.global_shortcut();
     shortcut_manager
         .unregister_all()
-        .map_err(|e| format!("Failed to unregister shortcuts: {}", e))?;
+        .map_err(|e| AppError::shortcut_manager("unregister shortcuts", e))?;
     Ok(())
 }
 
 // Stub for non-desktop platforms
 #[cfg(not(desktop))]
 #[tauri::command]
-pub async fn unregister_shortcuts(_app: AppHandle) -> Result<(), String> {
+pub async fn unregister_shortcuts(_app: AppHandle) -> Result<(), AppError> {
     Ok(())
 }
 
-/// Helper to read a setting from the store with a default fallback
-#[cfg(desktop)]
-fn get_setting_from_store<T: serde::de::DeserializeOwned>(
-    app: &AppHandle,
-    key: &str,
-    default: T,
-) -> T {
-    app.store("settings.json")
-        .ok()
-        .and_then(|store| store.get(key))
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or(default)
-}
-
 /// Re-register global shortcuts with the current settings from the store.
 /// Called from frontend after hotkey settings are changed.
 /// Falls back to defaults if stored values are invalid.
+/// Returns an error naming any shortcut that another application already owns.
 #[cfg(desktop)]
 #[tauri::command]
-pub async fn register_shortcuts(app: AppHandle) -> Result<(), String> {
-    // Read hotkeys from store with defaults
-    let toggle_hotkey: HotkeyConfig =
-        get_setting_from_store(&app, "toggle_hotkey", HotkeyConfig::default_toggle());
-    let hold_hotkey: HotkeyConfig =
-        get_setting_from_store(&app, "hold_hotkey", HotkeyConfig::default_hold());
-    let paste_last_hotkey: HotkeyConfig = get_setting_from_store(
-        &app,
-        "paste_last_hotkey",
-        HotkeyConfig::default_paste_last(),
-    );
-
-    // Convert to shortcuts with validation (fall back to defaults if invalid)
-    let toggle_shortcut = toggle_hotkey.to_shortcut_or_default(HotkeyConfig::default_toggle);
-    let hold_shortcut = hold_hotkey.to_shortcut_or_default(HotkeyConfig::default_hold);
-    let paste_last_shortcut =
-        paste_last_hotkey.to_shortcut_or_default(HotkeyConfig::default_paste_last);
-
-    log::info!(
-        "Re-registering shortcuts - Toggle: {}, Hold: {}, PasteLast: {}",
-        toggle_hotkey.to_shortcut_string(),
-        hold_hotkey.to_shortcut_string(),
-        paste_last_hotkey.to_shortcut_string()
-    );
-
-    // Get the global shortcut manager
-    let shortcut_manager = app.global_shortcut();
-
+pub async fn register_shortcuts(app: AppHandle) -> Result<(), AppError> {
     // Unregister all existing shortcuts
-    shortcut_manager
+    app.global_shortcut()
         .unregister_all()
-        .map_err(|e| format!("Failed to unregister shortcuts: {}", e))?;
-
-    // Collect shortcuts to register
-    let shortcuts: Vec<Shortcut> = vec![toggle_shortcut, hold_shortcut, paste_last_shortcut];
+        .map_err(|e| AppError::shortcut_manager("unregister shortcuts", e))?;
 
-    // Register new shortcuts with handler
-    shortcut_manager
-        .on_shortcuts(shortcuts, |app, shortcut, event| {
-            crate::handle_shortcut_event(app, shortcut, &event);
-        })
-        .map_err(|e| format!("Failed to register shortcuts: {}", e))?;
+    let failures = register_shortcuts_from_store(&app);
+    if !failures.is_empty() {
+        return Err(AppError::ShortcutsInUse { failures });
+    }
 
     log::info!("Shortcuts re-registered successfully");
     Ok(())
 }
 
+/// Shortcuts that failed to register the last time shortcuts were registered
+#[cfg(desktop)]
+#[derive(Default)]
+pub struct ShortcutFailures(Mutex<Vec<ShortcutRegistrationFailure>>);
+
+#[cfg(desktop)]
+impl ShortcutFailures {
+    pub fn get(&self) -> Vec<ShortcutRegistrationFailure> {
+        self.0
+            .lock()
+            .map(|failures| failures.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, failures: &[ShortcutRegistrationFailure]) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = failures.to_vec();
+        }
+    }
+}
+
+/// Register the configured shortcuts one at a time so a conflict on one does not
+/// prevent the others from working. Emits `shortcut-registration-failed` and
+/// returns the failures when any shortcut could not be registered.
+#[cfg(desktop)]
+pub(crate) fn register_shortcuts_from_store(app: &AppHandle) -> Vec<ShortcutRegistrationFailure> {
+    let failures = register_configured_shortcuts(app);
+    app.state::<ShortcutFailures>().set(&failures);
+    if !failures.is_empty() {
+        let _ = app.emit("shortcut-registration-failed", &failures);
+    }
+    failures
+}
+
+#[cfg(desktop)]
+fn register_configured_shortcuts(app: &AppHandle) -> Vec<ShortcutRegistrationFailure> {
+    let hotkeys = crate::sequence::configured_hotkeys(app);
+    let described: Vec<String> = HotkeyAction::ALL
+        .into_iter()
+        .map(|action| {
+            let description = hotkeys
+                .iter()
+                .find(|(bound, _)| *bound == action)
+                .map_or_else(|| "off".to_string(), |(_, hotkey)| hotkey.describe());
+            format!("{}: {}", action.id(), description)
+        })
+        .collect();
+    log::info!("Registering shortcuts - {}", described.join(", "));
+
+    // Mouse buttons, double taps and media keys are handled by the input listener
+    // rather than the OS shortcut API
+    app.state::<MouseTrigger>().refresh(app);
+    app.state::<DoubleTapTrigger>().refresh(app);
+    app.state::<MediaKeyTrigger>().refresh(app);
+
+    // Any pending sequence steps were unregistered along with everything else
+    app.state::<SequenceState>().clear();
+
+    // Compositors don't deliver OS shortcuts on Wayland, so bind through the portal
+    #[cfg(target_os = "linux")]
+    if crate::wayland_shortcuts::is_wayland_session() {
+        return crate::wayland_shortcuts::register_from_store(app);
+    }
+
+    // Convert to shortcuts with validation (fall back to defaults if invalid).
+    // Sequences register only their first step, shared between sequences that start alike.
+    let mut shortcuts = Vec::new();
+    let mut leaders = Vec::new();
+    for (action, hotkey) in &hotkeys {
+        if hotkey.is_plain_keyboard() {
+            let shortcut = hotkey.to_shortcut_or_default(|| action.default_hotkey());
+            shortcuts.push((action.id(), shortcut, false));
+        } else if hotkey.is_sequence() {
+            let leader = hotkey.to_shortcut_or_default(|| action.default_hotkey());
+            if !leaders.contains(&leader) {
+                leaders.push(leader);
+                shortcuts.push((action.id(), leader, true));
+            }
+        }
+    }
+
+    let shortcut_manager = app.global_shortcut();
+    let mut failures = Vec::new();
+    for (action, shortcut, is_leader) in shortcuts {
+        let result = if is_leader {
+            shortcut_manager.on_shortcut(shortcut, |app, shortcut, event| {
+                crate::sequence::handle_leader_event(app, shortcut, &event);
+            })
+        } else {
+            shortcut_manager.on_shortcut(shortcut, |app, shortcut, event| {
+                crate::handle_shortcut_event(app, shortcut, &event);
+            })
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to register {} shortcut {}: {}", action, shortcut, e);
+            failures.push(ShortcutRegistrationFailure {
+                action: action.to_string(),
+                shortcut: shortcut.to_string(),
+                error: e.to_string(),
+            });
+        }
+    }
+
+    failures
+}
+
+/// How often to check whether the keyboard layout changed
+#[cfg(target_os = "windows")]
+const LAYOUT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Re-register shortcuts when the user switches keyboard layout, so hotkeys bound
+/// by position move to the keys the new layout puts there
+#[cfg(target_os = "windows")]
+pub(crate) fn watch_keyboard_layout(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut layout = crate::keyboard_layout::current_layout();
+        loop {
+            std::thread::sleep(LAYOUT_POLL_INTERVAL);
+            let current = crate::keyboard_layout::current_layout();
+            if current == layout {
+                continue;
+            }
+            layout = current;
+            let bound_by_position = crate::sequence::configured_hotkeys(&app)
+                .iter()
+                .any(|(_, hotkey)| hotkey.physical);
+            if !bound_by_position {
+                continue;
+            }
+            log::info!("Keyboard layout changed, re-registering hotkeys bound by position");
+            if let Err(e) = app.global_shortcut().unregister_all() {
+                log::warn!("Failed to unregister shortcuts: {}", e);
+                continue;
+            }
+            register_shortcuts_from_store(&app);
+        }
+    });
+}
+
 // Stub for non-desktop platforms
 #[cfg(not(desktop))]
 #[tauri::command]
-pub async fn register_shortcuts(_app: AppHandle) -> Result<(), String> {
+pub async fn register_shortcuts(_app: AppHandle) -> Result<(), AppError> {
     Ok(())
 }
+
+/// Check whether a hotkey can be registered, i.e. no other application owns it.
+/// Called from the settings UI before saving a new hotkey.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn test_hotkey_availability(
+    app: AppHandle,
+    hotkey: HotkeyConfig,
+) -> Result<HotkeyAvailability, AppError> {
+    // Mouse buttons are read by a low-level listener and can't conflict with shortcuts
+    if hotkey.trigger == HotkeyTrigger::Mouse {
+        return Ok(match hotkey.mouse_button() {
+            Some(_) => HotkeyAvailability {
+                available: true,
+                reason: None,
+            },
+            None => HotkeyAvailability {
+                available: false,
+                reason: Some(format!("Unsupported mouse button: {}", hotkey.key)),
+            },
+        });
+    }
+
+    // Double taps are read the same way and only need a supported modifier
+    if hotkey.trigger == HotkeyTrigger::DoubleTap {
+        return Ok(match hotkey.tap_modifier() {
+            Some(_) => HotkeyAvailability {
+                available: true,
+                reason: None,
+            },
+            None => HotkeyAvailability {
+                available: false,
+                reason: Some(format!("Unsupported double-tap key: {}", hotkey.key)),
+            },
+        });
+    }
+
+    // So are media keys, which only need to be one the listener recognizes
+    if hotkey.trigger == HotkeyTrigger::MediaKey {
+        return Ok(match hotkey.media_key() {
+            Some(_) => HotkeyAvailability {
+                available: true,
+                reason: None,
+            },
+            None => HotkeyAvailability {
+                available: false,
+                reason: Some(format!("Unsupported media key: {}", hotkey.key)),
+            },
+        });
+    }
+
+    // The second step of a sequence is only registered while the sequence is pending,
+    // so just make sure it parses
+    if let Some(step) = &hotkey.then {
+        step.to_shortcut()
+            .map_err(|reason| AppError::invalid_hotkey(hotkey.describe(), reason))?;
+    }
+
+    let shortcut = hotkey
+        .to_shortcut()
+        .map_err(|reason| AppError::invalid_hotkey(hotkey.describe(), reason))?;
+    let shortcut_manager = app.global_shortcut();
+
+    // Our own registration of this shortcut is not a conflict with another app
+    if shortcut_manager.is_registered(shortcut) {
+        return Ok(HotkeyAvailability {
+            available: true,
+            reason: None,
+        });
+    }
+
+    // Probe by registering and immediately releasing the shortcut
+    match shortcut_manager.register(shortcut) {
+        Ok(()) => {
+            shortcut_manager
+                .unregister(shortcut)
+                .map_err(|e| AppError::shortcut_manager("release test shortcut", e))?;
+            Ok(HotkeyAvailability {
+                available: true,
+                reason: None,
+            })
+        }
+        Err(e) => {
+            log::info!(
+                "Hotkey {} is unavailable: {}",
+                hotkey.to_shortcut_string(),
+                e
+            );
+            Ok(HotkeyAvailability {
+                available: false,
+                reason: Some(format!(
+                    "{} is already in use by another application",
+                    hotkey.to_shortcut_string()
+                )),
+            })
+        }
+    }
+}
+
+// Stub for non-desktop platforms
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn test_hotkey_availability(
+    _app: AppHandle,
+    _hotkey: HotkeyConfig,
+) -> Result<HotkeyAvailability, AppError> {
+    Ok(HotkeyAvailability {
+        available: true,
+        reason: None,
+    })
+}
+
+/// Default hotkeys for this machine's platform and keyboard layout
+#[tauri::command]
+pub fn get_default_hotkeys() -> DefaultHotkeys {
+    current_default_hotkeys()
+}
+
+/// List the experimental feature flags and whether each is on
+#[tauri::command]
+pub fn list_feature_flags(app: AppHandle) -> Vec<FeatureFlagState> {
+    describe_feature_flags(&stored_feature_flags(&app))
+}
+
+/// Turn an experimental feature flag on or off, returning the updated list
+#[tauri::command]
+pub fn set_feature_flag(
+    app: AppHandle,
+    flag: FeatureFlag,
+    enabled: bool,
+) -> Result<Vec<FeatureFlagState>, AppError> {
+    let flags = store_feature_flag(&app, flag, enabled)?;
+    Ok(describe_feature_flags(&flags))
+}
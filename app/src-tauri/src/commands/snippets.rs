@@ -0,0 +1,73 @@
+use crate::snippets::{expand_snippets, uses_clipboard, Snippet, SnippetStore, TemplateValues};
+use arboard::Clipboard;
+use chrono::Local;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Expand snippet triggers in dictated text before it is inserted. Falls back to the
+/// text as it is if the snippets can't be read.
+pub fn apply_snippets(app: &AppHandle, text: &str) -> String {
+    let snippets = match app.state::<SnippetStore>().list() {
+        Ok(snippets) => snippets,
+        Err(e) => {
+            log::warn!("Failed to read snippets: {}", e);
+            return text.to_string();
+        }
+    };
+    if snippets.is_empty() {
+        return text.to_string();
+    }
+
+    let clipboard = uses_clipboard(&snippets)
+        .then(|| Clipboard::new().and_then(|mut clipboard| clipboard.get_text()))
+        .and_then(|result| {
+            result
+                .inspect_err(|e| log::warn!("Failed to read the clipboard for a snippet: {}", e))
+                .ok()
+        });
+    let values = TemplateValues {
+        now: Local::now(),
+        clipboard,
+    };
+    expand_snippets(text, &snippets, &values)
+}
+
+/// Snippets, in the order they were added
+#[tauri::command]
+pub async fn list_snippets(snippets: State<'_, SnippetStore>) -> Result<Vec<Snippet>, String> {
+    snippets.list()
+}
+
+#[tauri::command]
+pub async fn add_snippet(
+    app: AppHandle,
+    trigger: String,
+    template: String,
+) -> Result<Snippet, String> {
+    let snippet = app.state::<SnippetStore>().add(&trigger, &template)?;
+    let _ = app.emit("snippets-changed", ());
+    Ok(snippet)
+}
+
+#[tauri::command]
+pub async fn update_snippet(
+    app: AppHandle,
+    id: String,
+    trigger: String,
+    template: String,
+) -> Result<Snippet, String> {
+    let snippet = app
+        .state::<SnippetStore>()
+        .update(&id, &trigger, &template)?;
+    let _ = app.emit("snippets-changed", ());
+    Ok(snippet)
+}
+
+/// Remove a snippet. Returns false if it was already gone.
+#[tauri::command]
+pub async fn remove_snippet(app: AppHandle, id: String) -> Result<bool, String> {
+    let removed = app.state::<SnippetStore>().remove(&id)?;
+    if removed {
+        let _ = app.emit("snippets-changed", ());
+    }
+    Ok(removed)
+}
@@ -0,0 +1,295 @@
+use crate::audio_upload::ServerAudioEncodings;
+use crate::commands::history::record_history_entry;
+use crate::commands::pending_transcriptions::transcribe_queued;
+use crate::commands::text::insert_dictation_text;
+use crate::recordings::to_pcm16;
+use crate::settings::{get_setting_from_store, save_setting_to_store};
+use crate::state::AppState;
+use crate::stt::models::{WhisperModel, WhisperModelStatus, WhisperModelStore};
+use crate::stt::{
+    fallback_provider_from_settings, next_provider, provider_from_settings, provider_named,
+    transcribe_audio, ProviderInfo, SttManager, LOCAL_WHISPER_PROVIDER,
+};
+use crate::turns::TurnQueue;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+
+/// Progress of a Whisper model download, emitted as `whisper-model-download-progress`
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDownloadProgress {
+    pub model: WhisperModel,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+}
+
+/// Interim transcript of the turn being recorded, emitted as `transcription-partial`
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionPartial {
+    pub text: String,
+}
+
+/// Rebuild the active and fallback STT providers from settings.
+/// Called from frontend after the STT provider settings change.
+#[tauri::command]
+pub async fn refresh_stt_provider(
+    app: AppHandle,
+    stt: State<'_, SttManager>,
+) -> Result<String, String> {
+    rebuild_providers(&app, &stt)?;
+    stt.provider_name().map(String::from)
+}
+
+pub(crate) fn rebuild_providers(app: &AppHandle, stt: &SttManager) -> Result<(), String> {
+    stt.set_provider(provider_from_settings(app))?;
+    stt.set_fallback(fallback_provider_from_settings(app))
+}
+
+/// STT providers the server offers, as last relayed by the overlay
+#[derive(Default)]
+pub struct ServerSttProviders(Mutex<Vec<ProviderInfo>>);
+
+impl ServerSttProviders {
+    pub fn get(&self) -> Vec<ProviderInfo> {
+        self.0
+            .lock()
+            .map(|providers| providers.clone())
+            .unwrap_or_default()
+    }
+
+    fn set(&self, providers: Vec<ProviderInfo>) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = providers;
+        }
+    }
+}
+
+/// Payload of the overlay's `available-providers` event
+#[derive(Deserialize)]
+struct AvailableProviders {
+    stt: Vec<ProviderInfo>,
+    /// Missing from servers that only decode PCM
+    #[serde(default)]
+    audio_encodings: Vec<String>,
+}
+
+/// Keep [`ServerSttProviders`] up to date with the providers the server lists to
+/// the overlay after connecting, and the tray's provider menu with them. The
+/// audio encodings it decodes are kept in [`ServerAudioEncodings`].
+pub(crate) fn listen_for_server_providers(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen(
+        "available-providers",
+        move |event| match serde_json::from_str::<AvailableProviders>(event.payload()) {
+            Ok(available) => {
+                handle.state::<ServerSttProviders>().set(available.stt);
+                handle
+                    .state::<ServerAudioEncodings>()
+                    .set(available.audio_encodings);
+                crate::refresh_tray_menu(&handle);
+            }
+            Err(e) => log::warn!("Ignoring unreadable provider list: {}", e),
+        },
+    );
+}
+
+/// Transcribe with the server STT provider `value`. Saves the choice, rebuilds
+/// in-app providers and tells the windows, so the overlay switches the server to
+/// it and shows its name.
+pub(crate) fn set_stt_provider(app: &AppHandle, value: &str) -> Result<ProviderInfo, String> {
+    let provider = app
+        .state::<ServerSttProviders>()
+        .get()
+        .into_iter()
+        .find(|provider| provider.value == value)
+        .ok_or_else(|| format!("The server doesn't offer STT provider {}", value))?;
+
+    save_setting_to_store(app, "stt_provider", &provider.value)?;
+    rebuild_providers(app, &app.state::<SttManager>())?;
+
+    log::info!("STT provider: {}", provider.label);
+    let _ = app.emit("stt-provider-changed", &provider);
+    Ok(provider)
+}
+
+/// Transcribe with the server STT provider `value`, returning it
+#[tauri::command]
+pub async fn select_stt_provider(app: AppHandle, value: String) -> Result<ProviderInfo, String> {
+    set_stt_provider(&app, &value)
+}
+
+/// Switch to the STT provider after the selected one among those the server offers,
+/// returning it
+#[tauri::command]
+pub fn cycle_stt_provider(app: AppHandle) -> Result<ProviderInfo, String> {
+    let providers = app.state::<ServerSttProviders>().get();
+    let current: Option<String> = get_setting_from_store(&app, "stt_provider", None);
+    let next = next_provider(current.as_deref(), &providers)
+        .ok_or("The server hasn't listed its speech-to-text providers yet")?;
+    set_stt_provider(&app, &next.value)
+}
+
+/// Switch to the next STT provider from the hotkey
+pub(crate) fn cycle_provider_from_hotkey(app: &AppHandle) {
+    if let Err(e) = cycle_stt_provider(app.clone()) {
+        log::warn!("CycleSttProvider: {}", e);
+    }
+}
+
+/// Feed captured audio to providers that transcribe in-app, and to the silence
+/// detector that auto-stops toggle-mode recordings. Audio is dropped while the
+/// recording is paused.
+pub(crate) fn feed_stt_audio(app: &AppHandle, samples: &[f32]) -> Result<(), String> {
+    #[cfg(desktop)]
+    if crate::is_recording_paused(app) {
+        return Ok(());
+    }
+    let stt = app.state::<SttManager>();
+    stt.send_audio(samples)?;
+    if let Some(text) = stt.partials()?.pop() {
+        emit_transcription_partial(app, text);
+    }
+    #[cfg(desktop)]
+    crate::auto_stop_on_silence(app, samples);
+    Ok(())
+}
+
+/// Audio captured by the overlay, for [`feed_stt_audio`]
+#[tauri::command]
+pub async fn send_stt_audio(app: AppHandle, samples: Vec<f32>) -> Result<(), String> {
+    feed_stt_audio(&app, &samples)
+}
+
+/// Share an interim transcript from the server with the overlay
+#[tauri::command]
+pub fn report_transcription_partial(app: AppHandle, text: String) {
+    emit_transcription_partial(&app, text);
+}
+
+/// The overlay got the server's response for, or gave up on, its `count` oldest turns
+#[tauri::command]
+pub fn report_server_turns_finished(app: AppHandle, count: usize) {
+    app.state::<TurnQueue>().finish_server_turns(count);
+}
+
+/// Emit `transcription-partial` while recording, unless partial transcripts are
+/// turned off
+fn emit_transcription_partial(app: &AppHandle, text: String) {
+    let recording = app.state::<AppState>().is_recording.load(Ordering::SeqCst);
+    if !recording || !get_setting_from_store(app, "show_partial_transcripts", true) {
+        return;
+    }
+    let _ = app.emit("transcription-partial", TranscriptionPartial { text });
+}
+
+/// Transcribe the last recording again and insert the result into the focused app,
+/// e.g. when the provider returned nothing. `provider` is an `stt_provider` value to
+/// use instead of the one chosen for retries.
+#[tauri::command]
+pub async fn retry_last_transcription(
+    app: AppHandle,
+    provider: Option<String>,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || retry_last(&app, provider.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Transcribe the audio kept from the last turn with `provider`, or else the
+/// `retry_stt_provider` setting or the active provider, and deliver it like a new
+/// dictation
+pub(crate) fn retry_last(app: &AppHandle, provider: Option<&str>) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if state.is_recording.load(Ordering::SeqCst) {
+        return Err("Can't retry while recording".to_string());
+    }
+    let (format, samples) = app
+        .state::<SttManager>()
+        .last_audio()
+        .ok_or("There is no recording to retry")?;
+    let duration_ms = Some(format.duration_ms(samples.len()));
+
+    let retry_provider: Option<String> = get_setting_from_store(app, "retry_stt_provider", None);
+    let provider = provider.or(retry_provider.as_deref());
+    let mut stt_provider = match provider {
+        Some(name) => provider_named(app, Some(name)),
+        None => provider_from_settings(app),
+    };
+    log::info!(
+        "Retrying the last recording with {}",
+        provider.unwrap_or(stt_provider.name())
+    );
+    if stt_provider.transcribes_in_app() {
+        let transcript = transcribe_audio(stt_provider.as_mut(), format, &samples)?;
+        crate::deliver_local_transcript(app, &transcript, duration_ms);
+        return Ok(());
+    }
+
+    if provider == Some(LOCAL_WHISPER_PROVIDER) {
+        return Err("Local Whisper has no model to transcribe with".to_string());
+    }
+    // The server transcribes and formats the audio replayed through the overlay
+    if !state.server_connected.load(Ordering::SeqCst) {
+        return Err("Not connected to the server".to_string());
+    }
+    let text = transcribe_queued(app, format, to_pcm16(&samples), provider)?;
+    let text = insert_dictation_text(app, &text);
+    if text.is_empty() {
+        return Ok(());
+    }
+    record_history_entry(app, text, duration_ms, None, Some("server")).map(|_| ())
+}
+
+/// Drain interim transcripts from the active provider
+#[tauri::command]
+pub async fn get_stt_partials(stt: State<'_, SttManager>) -> Result<Vec<String>, String> {
+    stt.partials()
+}
+
+/// List the Whisper models that can be used for local transcription
+#[tauri::command]
+pub async fn list_whisper_models(
+    models: State<'_, WhisperModelStore>,
+) -> Result<Vec<WhisperModelStatus>, String> {
+    Ok(models.list())
+}
+
+/// Download a Whisper model, reporting progress with `whisper-model-download-progress`
+/// events. The STT providers are rebuilt afterwards so a selected model is picked up.
+#[tauri::command]
+pub async fn download_whisper_model(app: AppHandle, model: WhisperModel) -> Result<(), String> {
+    let download_app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let models = download_app.state::<WhisperModelStore>();
+        models.download(model, &mut |downloaded_bytes, total_bytes| {
+            let _ = download_app.emit(
+                "whisper-model-download-progress",
+                ModelDownloadProgress {
+                    model,
+                    downloaded_bytes,
+                    total_bytes,
+                },
+            );
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    rebuild_providers(&app, &app.state::<SttManager>())
+}
+
+/// Delete a downloaded Whisper model
+#[tauri::command]
+pub async fn delete_whisper_model(
+    app: AppHandle,
+    models: State<'_, WhisperModelStore>,
+    model: WhisperModel,
+) -> Result<bool, String> {
+    let deleted = models.delete(model)?;
+    if deleted {
+        // Providers that were using the model fall back to the server
+        rebuild_providers(&app, &app.state::<SttManager>())?;
+    }
+    Ok(deleted)
+}
@@ -0,0 +1,85 @@
+use crate::logging::{self, LOG_DIR};
+use crate::support_bundle::{default_file_name, redact_value, write_bundle, EnvironmentInfo};
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+fn environment_info(app: &AppHandle) -> EnvironmentInfo {
+    #[cfg(target_os = "linux")]
+    let display_server = Some(crate::linux_input::display_server().name().to_string());
+    #[cfg(not(target_os = "linux"))]
+    let display_server = None;
+
+    EnvironmentInfo {
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_family: std::env::consts::FAMILY.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        display_server,
+        generated_at: Utc::now(),
+    }
+}
+
+/// Zip the recent logs, redacted settings and environment details for a bug
+/// report, returning the path written. Without a `path` the bundle goes to the
+/// downloads directory, falling back to the app data directory.
+#[tauri::command]
+pub async fn generate_support_bundle(
+    app: AppHandle,
+    path: Option<String>,
+) -> Result<String, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    let path = match path.map(|path| path.trim().to_string()) {
+        Some(path) if !path.is_empty() => {
+            let path = PathBuf::from(path);
+            if !path.is_absolute() {
+                return Err(format!(
+                    "Support bundle path must be absolute: {}",
+                    path.display()
+                ));
+            }
+            path
+        }
+        _ => {
+            let dir = app
+                .path()
+                .download_dir()
+                .unwrap_or_else(|_| app_data_dir.clone());
+            dir.join(default_file_name(Utc::now()))
+        }
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create support bundle directory: {}", e))?;
+    }
+
+    let settings = app.store("settings.json").ok().map(|store| {
+        let settings: Map<String, Value> = store.entries().into_iter().collect();
+        let home = app.path().home_dir().ok();
+        redact_value(
+            &Value::Object(settings),
+            home.as_deref().and_then(|home| home.to_str()),
+        )
+    });
+    let log_files = logging::log_files(&app_data_dir.join(LOG_DIR));
+    write_bundle(
+        &path,
+        &log_files,
+        settings.as_ref(),
+        &environment_info(&app),
+    )?;
+
+    log::info!(
+        "Wrote support bundle with {} log files to {}",
+        log_files.len(),
+        path.display()
+    );
+    Ok(path.to_string_lossy().into_owned())
+}
@@ -0,0 +1,90 @@
+use crate::events::{self, AppEvent};
+use crate::settings::get_setting_from_store;
+use crate::telemetry::{FailureCategory, TelemetryReport, TelemetryStore};
+use chrono::Utc;
+use std::collections::VecDeque;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Whether the user opted in to local telemetry
+fn telemetry_enabled(app: &AppHandle) -> bool {
+    get_setting_from_store(app, "telemetry_enabled", false)
+}
+
+/// Count this launch as a session
+pub fn record_session(app: &AppHandle) {
+    if !telemetry_enabled(app) {
+        return;
+    }
+    if let Err(e) = app
+        .state::<TelemetryStore>()
+        .update(Utc::now(), |counters| counters.record_session())
+    {
+        log::warn!("{}", e);
+    }
+}
+
+/// Count completed and failed turns, and how long turns took once recording stopped
+pub fn spawn_telemetry_subscriber(app: &AppHandle) {
+    // When each turn still waiting for its text stopped recording, oldest first
+    let mut stopped_at: VecDeque<Instant> = VecDeque::new();
+    let mut is_connected = false;
+    events::spawn_subscriber(app, "telemetry", move |app, event| {
+        let was_connected = is_connected;
+        if let AppEvent::ConnectionChanged { connected } = event {
+            is_connected = connected;
+        }
+        if !telemetry_enabled(app) {
+            stopped_at.clear();
+            return;
+        }
+
+        let telemetry = app.state::<TelemetryStore>();
+        let result = match event {
+            AppEvent::RecordingStopped => {
+                stopped_at.push_back(Instant::now());
+                return;
+            }
+            AppEvent::TurnCompleted { .. } => {
+                let latency = stopped_at.pop_front().map(|stopped| stopped.elapsed());
+                telemetry.update(Utc::now(), |counters| counters.record_dictation(latency))
+            }
+            AppEvent::TurnFailed { category, .. } => {
+                stopped_at.pop_front();
+                telemetry.update(Utc::now(), |counters| counters.record_failure(category))
+            }
+            AppEvent::ConnectionChanged { connected: false } if was_connected => {
+                // Turns in flight are lost with the connection
+                stopped_at.clear();
+                telemetry.update(Utc::now(), |counters| {
+                    counters.record_failure(FailureCategory::Connection)
+                })
+            }
+            _ => return,
+        };
+        match result {
+            Ok(()) => {
+                let _ = app.emit("telemetry-changed", ());
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+    });
+}
+
+/// Sessions, dictations, failures by category and latency buckets recorded so far
+#[tauri::command]
+pub async fn get_telemetry(
+    app: AppHandle,
+    telemetry: State<'_, TelemetryStore>,
+) -> Result<TelemetryReport, String> {
+    Ok(telemetry.snapshot()?.report(telemetry_enabled(&app)))
+}
+
+/// Delete every telemetry counter (recording continues if telemetry stays enabled)
+#[tauri::command]
+pub async fn purge_telemetry(app: AppHandle) -> Result<(), String> {
+    app.state::<TelemetryStore>().purge()?;
+    log::info!("Telemetry purged");
+    let _ = app.emit("telemetry-changed", ());
+    Ok(())
+}
@@ -1,10 +1,35 @@
+use crate::accessibility;
+use crate::active_window::get_active_app;
+use crate::clipboard::ClipboardSnapshot;
+use crate::commands::active_window::{ensure_not_blocked, ensure_not_secure_input};
+use crate::commands::history::delete_last_dictation;
+use crate::commands::notes::append_dictation_to_notes;
+use crate::commands::snippets::apply_snippets;
+use crate::commands::text_transforms::transform_dictation;
+use crate::connection::ConnectionManager;
+use crate::events::{self, AppEvent};
+use crate::formatter::apply_output_style;
+#[cfg(target_os = "linux")]
+use crate::linux_input::{self, KeyModifier, KeyName};
+use crate::secure_input;
+use crate::settings::{
+    get_setting_from_store, resolve_insertion_method, resolve_newline_mode, InsertionMethod,
+    InsertionRule, NewlineMode, NewlineRule, OutputMode,
+};
+use crate::state::AppState;
+use crate::telemetry::FailureCategory;
+use crate::voice_commands::{
+    default_voice_commands, inserted_text, interpret_voice_commands, CommandKey, DictationEdit,
+    VoiceCommand,
+};
 use arboard::Clipboard;
 use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use serde::Serialize;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
-use tauri::AppHandle;
-use tauri_plugin_store::StoreExt;
+use tauri::{AppHandle, Emitter, Manager};
 
 /// Delay after clipboard operations to ensure system stability
 const CLIPBOARD_STABILIZATION_DELAY_MS: u64 = 50;
@@ -12,31 +37,460 @@ const CLIPBOARD_STABILIZATION_DELAY_MS: u64 = 50;
 /// Delay between keyboard key press and release events
 const KEY_EVENT_DELAY_MS: u64 = 50;
 
-/// Delay before restoring previous clipboard content
-const CLIPBOARD_RESTORE_DELAY_MS: u64 = 100;
+/// Default delay before restoring the previous clipboard content after a paste
+pub const DEFAULT_CLIPBOARD_RESTORE_DELAY_MS: u64 = 100;
 
-/// Default server URL when not configured
-const DEFAULT_SERVER_URL: &str = "http://127.0.0.1:8765";
+/// Maximum number of characters pasted in a single chunk
+const INSERTION_CHUNK_CHARS: usize = 1000;
 
+/// Delay between chunk pastes so the target editor can keep up
+const INSERTION_CHUNK_DELAY_MS: u64 = 150;
+
+/// Number of attempts to get the clipboard to hold the expected chunk
+const CLIPBOARD_VERIFY_ATTEMPTS: u32 = 3;
+
+/// Default hard cap on inserted text length; longer text goes to the clipboard only
+pub const DEFAULT_MAX_INSERTION_CHARS: usize = 20_000;
+
+/// Server the overlay is connected to, or the configured one before connecting
 #[tauri::command]
 pub async fn get_server_url(app: AppHandle) -> Result<String, String> {
-    let store = app.store("settings.json").map_err(|e| e.to_string())?;
-    let url = store
-        .get("server_url")
-        .and_then(|v| v.as_str().map(String::from))
-        .unwrap_or_else(|| DEFAULT_SERVER_URL.to_string());
-    Ok(url)
+    app.state::<ConnectionManager>()
+        .status()
+        .endpoint
+        .ok_or_else(|| "No server URL configured".to_string())
+}
+
+/// Progress of a chunked insertion, emitted as `insertion-progress`
+#[derive(Debug, Clone, Serialize)]
+pub struct InsertionProgress {
+    pub chunk: usize,
+    pub total_chunks: usize,
+}
+
+/// Emitted as `insertion-clipboard-fallback` when text exceeds the insertion cap
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardFallback {
+    pub char_count: usize,
+    pub max_chars: usize,
 }
 
+/// Insert text into the focused app. Chunked insertion waits between chunks, so it
+/// runs on a blocking thread.
 #[tauri::command]
 pub async fn type_text(app: AppHandle, text: String) -> Result<(), String> {
-    // macOS HIToolbox APIs (used by enigo) must run on the main thread
-    // Use a channel to get the result back from the main thread
+    tauri::async_runtime::spawn_blocking(move || insert_text(&app, &text))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// How text gets into other apps on this system, for troubleshooting insertion
+#[derive(Debug, Clone, Serialize)]
+pub struct InsertionDiagnostics {
+    /// "x11", "wayland" or "unknown" on Linux, `None` on other platforms
+    pub display_server: Option<String>,
+    /// What simulates key presses: "enigo", "wtype" or "ydotool"
+    pub key_backend: String,
+    pub clipboard_available: bool,
+    /// Problems that will likely stop insertion from working
+    pub warnings: Vec<String>,
+}
+
+#[tauri::command]
+pub fn get_insertion_diagnostics() -> InsertionDiagnostics {
+    let mut warnings = Vec::new();
+    let clipboard_available = match Clipboard::new() {
+        Ok(_) => true,
+        Err(e) => {
+            warnings.push(format!("The clipboard can't be opened: {}", e));
+            false
+        }
+    };
+
+    if secure_input::is_enabled() {
+        warnings.push(
+            "Secure input is on, so macOS drops simulated keys until the app holding it \
+             (a password field or terminal) lets go"
+                .to_string(),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    let (display_server, key_backend) = {
+        let server = linux_input::display_server();
+        let tool = linux_input::wayland_tool();
+        if server == linux_input::DisplayServer::Wayland && tool.is_none() {
+            warnings.push(
+                "Wayland session without wtype or ydotool installed, so text only reaches \
+                 XWayland apps"
+                    .to_string(),
+            );
+        }
+        (
+            Some(server.name().to_string()),
+            tool.map_or("enigo", linux_input::WaylandTool::program),
+        )
+    };
+    #[cfg(not(target_os = "linux"))]
+    let (display_server, key_backend) = (None, "enigo");
+
+    InsertionDiagnostics {
+        display_server,
+        key_backend: key_backend.to_string(),
+        clipboard_available,
+        warnings,
+    }
+}
+
+/// Insert a finished dictation and return the text that ended up in the focused app,
+/// for history
+#[tauri::command]
+pub async fn insert_dictation(app: AppHandle, text: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || insert_dictation_text(&app, &text))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Apply the output style and text transforms to a dictation, expand its snippets,
+/// carry out its voice commands and insert the rest, or append it to the notes file
+/// in notes mode. Returns the inserted text; insertion failures are reported as a
+/// failed turn.
+pub fn insert_dictation_text(app: &AppHandle, text: &str) -> String {
+    let styled = apply_output_style(app, text);
+    let text = apply_snippets(app, &transform_dictation(app, &styled));
+    let edits = if get_setting_from_store(app, "voice_commands_enabled", true) {
+        let commands: Vec<VoiceCommand> =
+            get_setting_from_store(app, "voice_commands", default_voice_commands());
+        interpret_voice_commands(&text, &commands)
+    } else {
+        vec![DictationEdit::Insert(text)]
+    };
+
+    let inserted = inserted_text(&edits);
+    if get_setting_from_store(app, "notes_enabled", false) {
+        if let Err(e) = append_dictation_to_notes(app, &inserted) {
+            log::error!("Failed to append dictation to notes: {}", e);
+            events::publish(
+                app,
+                AppEvent::TurnFailed {
+                    error: e,
+                    category: FailureCategory::Notes,
+                },
+            );
+        }
+        if !get_setting_from_store(app, "notes_also_insert", false) {
+            return inserted;
+        }
+    }
+    let overridden = app
+        .state::<AppState>()
+        .recording_clipboard_only
+        .load(Ordering::SeqCst);
+    let result = match output_mode(app, overridden) {
+        OutputMode::ClipboardOnly => {
+            log::info!(
+                "Copying {} characters to the clipboard only",
+                inserted.chars().count()
+            );
+            copy_to_clipboard(&inserted)
+        }
+        OutputMode::Insert => run_dictation_edits(app, edits),
+        // Voice commands can split the dictation into several insertions, so the
+        // whole of it is copied once they are done, even if one failed
+        OutputMode::Both => {
+            let result = run_dictation_edits(app, edits);
+            if let Err(e) = copy_to_clipboard(&inserted) {
+                log::warn!("Failed to copy dictation to the clipboard: {}", e);
+            }
+            result
+        }
+    };
+    if let Err(e) = result {
+        log::error!("Failed to insert dictation: {}", e);
+        let category = if secure_input::is_enabled() {
+            FailureCategory::SecureInput
+        } else {
+            FailureCategory::Insertion
+        };
+        events::publish(app, AppEvent::TurnFailed { error: e, category });
+    }
+    inserted
+}
+
+/// Where a dictation goes: the `output_mode` setting, or the other way if the
+/// clipboard-only hotkey `overridden` it for this dictation
+pub(crate) fn output_mode(app: &AppHandle, overridden: bool) -> OutputMode {
+    let mode: OutputMode = get_setting_from_store(app, "output_mode", OutputMode::default());
+    if overridden {
+        mode.overridden()
+    } else {
+        mode
+    }
+}
+
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| e.to_string())
+}
+
+fn run_dictation_edits(app: &AppHandle, edits: Vec<DictationEdit>) -> Result<(), String> {
+    ensure_not_blocked(app)?;
+    ensure_not_secure_input(app)?;
+    for edit in edits {
+        match edit {
+            DictationEdit::Insert(text) => insert_text(app, &text)?,
+            DictationEdit::Press(key) => {
+                run_on_main_thread_blocking(app, move || press_command_key_blocking(key))?
+            }
+            DictationEdit::DeletePrevious => delete_last_dictation(app)?,
+        }
+    }
+    Ok(())
+}
+
+/// Insert text into the focused app, applying the length cap, newline rules and chunking.
+/// Blocks until insertion finishes.
+pub fn insert_text(app: &AppHandle, text: &str) -> Result<(), String> {
+    // Output to both the app and the clipboard leaves the text there too
+    let copy_after_insert = get_setting_from_store(app, "copy_after_insert", false)
+        || get_setting_from_store(app, "output_mode", OutputMode::default()) == OutputMode::Both;
+    let result = insert_into_focused_app(app, text, !copy_after_insert);
+
+    // Leave the whole text on the clipboard so a paste that landed in the wrong place
+    // can be redone by hand, even if insertion failed
+    if copy_after_insert {
+        if let Err(e) = Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+            log::warn!("Failed to copy inserted text to the clipboard: {}", e);
+        }
+    }
+
+    result
+}
+
+/// Insert text without the copy-after-insert handling. `restore_clipboard` is whether
+/// a paste should put the previous clipboard contents back afterwards.
+fn insert_into_focused_app(
+    app: &AppHandle,
+    text: &str,
+    restore_clipboard: bool,
+) -> Result<(), String> {
+    let max_chars: usize =
+        get_setting_from_store(app, "max_insertion_chars", DEFAULT_MAX_INSERTION_CHARS);
+    let char_count = text.chars().count();
+
+    if char_count > max_chars {
+        log::warn!(
+            "Text length {} exceeds insertion cap {}, copying to clipboard instead",
+            char_count,
+            max_chars
+        );
+        let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+        clipboard.set_text(text).map_err(|e| e.to_string())?;
+        let _ = app.emit(
+            "insertion-clipboard-fallback",
+            ClipboardFallback {
+                char_count,
+                max_chars,
+            },
+        );
+        return Ok(());
+    }
+
+    let newline_rules: Vec<NewlineRule> = get_setting_from_store(app, "newline_rules", Vec::new());
+    let insertion_rules: Vec<InsertionRule> =
+        get_setting_from_store(app, "insertion_rules", Vec::new());
+    let active_app = if newline_rules.is_empty() && insertion_rules.is_empty() {
+        None
+    } else {
+        get_active_app()
+    };
+
+    let mut method = resolve_insertion_method(
+        &insertion_rules,
+        active_app.as_ref(),
+        get_setting_from_store(app, "insertion_method", InsertionMethod::default()),
+    );
+    // The accessibility API takes the whole text at once. Elements that don't support
+    // it, and other platforms, get the text through the fallback method instead.
+    if method == InsertionMethod::Accessibility {
+        let owned = text.to_string();
+        match run_on_main_thread_blocking(app, move || accessibility::insert_text(&owned)) {
+            Ok(()) => {
+                log::info!(
+                    "Inserted {} characters through the accessibility API",
+                    char_count
+                );
+                return Ok(());
+            }
+            Err(e) => log::warn!(
+                "Accessibility insertion failed, using {:?} instead: {}",
+                accessibility::fallback_method(),
+                e
+            ),
+        }
+        method = accessibility::fallback_method();
+    }
+
+    // Clipboard-only insertion takes the whole text at once
+    if method == InsertionMethod::ClipboardOnly {
+        log::info!("Inserting {} characters ({:?})", char_count, method);
+        let text = text.to_string();
+        return run_on_main_thread_blocking(app, move || {
+            insert_text_blocking(&text, method, Duration::ZERO)
+        });
+    }
+
+    let newline_mode =
+        method.newline_mode(resolve_newline_mode(&newline_rules, active_app.as_ref()));
+    let steps = build_insertion_steps(text, newline_mode, INSERTION_CHUNK_CHARS);
+
+    // Typing leaves the clipboard alone, so there is nothing to restore. The snapshot is
+    // taken and restored off the main thread so the restore delay doesn't block it.
+    let snapshot =
+        (method == InsertionMethod::Paste && restore_clipboard).then(ClipboardSnapshot::capture);
+    let last_pasted = steps
+        .iter()
+        .rev()
+        .find_map(|step| match step {
+            InsertionStep::Paste(chunk) => Some(chunk.clone()),
+            InsertionStep::Newline(_) => None,
+        })
+        .unwrap_or_default();
+
+    let result = if let [InsertionStep::Paste(single)] = steps.as_slice() {
+        let single = single.clone();
+        run_on_main_thread_blocking(app, move || insert_chunk_blocking(&single, method))
+    } else {
+        log::info!(
+            "Inserting {} characters in {} steps ({:?}, newline mode: {:?})",
+            char_count,
+            steps.len(),
+            method,
+            newline_mode
+        );
+        run_insertion_steps(app, steps, method)
+    };
+
+    // Restore previous clipboard once all steps are done (or insertion failed)
+    if let Some(snapshot) = snapshot {
+        snapshot.restore_after(clipboard_restore_delay(app), &last_pasted);
+    }
+
+    result
+}
+
+/// Run planned insertion steps one by one, emitting progress after each
+fn run_insertion_steps(
+    app: &AppHandle,
+    steps: Vec<InsertionStep>,
+    method: InsertionMethod,
+) -> Result<(), String> {
+    let total_chunks = steps.len();
+    let mut result = Ok(());
+    for (index, step) in steps.into_iter().enumerate() {
+        result = match step {
+            InsertionStep::Paste(chunk) => {
+                run_on_main_thread_blocking(app, move || insert_chunk_blocking(&chunk, method))
+            }
+            InsertionStep::Newline(mode) => {
+                run_on_main_thread_blocking(app, move || press_newline_blocking(mode))
+            }
+        };
+        if result.is_err() {
+            break;
+        }
+
+        let _ = app.emit(
+            "insertion-progress",
+            InsertionProgress {
+                chunk: index + 1,
+                total_chunks,
+            },
+        );
+
+        if index + 1 < total_chunks {
+            thread::sleep(Duration::from_millis(INSERTION_CHUNK_DELAY_MS));
+        }
+    }
+
+    result
+}
+
+/// How long to wait after a paste before restoring the previous clipboard contents
+pub fn clipboard_restore_delay(app: &AppHandle) -> Duration {
+    Duration::from_millis(get_setting_from_store(
+        app,
+        "clipboard_restore_delay_ms",
+        DEFAULT_CLIPBOARD_RESTORE_DELAY_MS,
+    ))
+}
+
+/// The way of inserting text into the focused app: its per-app rule if one matches,
+/// otherwise the global setting
+pub fn insertion_method(app: &AppHandle) -> InsertionMethod {
+    let default = get_setting_from_store(app, "insertion_method", InsertionMethod::default());
+    let rules: Vec<InsertionRule> = get_setting_from_store(app, "insertion_rules", Vec::new());
+    if rules.is_empty() {
+        return default;
+    }
+    resolve_insertion_method(&rules, get_active_app().as_ref(), default)
+}
+
+/// Check if accessibility insertion is supported on this platform
+#[tauri::command]
+pub fn is_accessibility_insertion_supported() -> bool {
+    accessibility::is_supported()
+}
+
+/// A single action performed while inserting text
+#[derive(Debug, Clone, PartialEq)]
+pub enum InsertionStep {
+    /// Paste (or type, depending on the insertion method) this text
+    Paste(String),
+    /// Press the key combination for a newline
+    Newline(NewlineMode),
+}
+
+/// Plan how to insert text: chunked pastes, with newlines turned into key presses
+/// unless the newline mode is literal paste.
+pub fn build_insertion_steps(
+    text: &str,
+    newline_mode: NewlineMode,
+    max_chunk_chars: usize,
+) -> Vec<InsertionStep> {
+    let paste_chunks = |segment: &str| {
+        split_into_chunks(segment, max_chunk_chars)
+            .into_iter()
+            .map(|chunk| InsertionStep::Paste(chunk.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    if newline_mode == NewlineMode::Paste {
+        return paste_chunks(text);
+    }
+
+    let mut steps = Vec::new();
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            steps.push(InsertionStep::Newline(newline_mode));
+        }
+        // Drop carriage returns from CRLF line endings; the key press handles the break
+        steps.extend(paste_chunks(line.strip_suffix('\r').unwrap_or(line)));
+    }
+    steps
+}
+
+/// Run a closure on the main thread and wait for its result.
+/// macOS HIToolbox APIs (used by enigo) must run on the main thread.
+pub(crate) fn run_on_main_thread_blocking<F>(app: &AppHandle, f: F) -> Result<(), String>
+where
+    F: FnOnce() -> Result<(), String> + Send + 'static,
+{
     let (tx, rx) = mpsc::channel::<Result<(), String>>();
 
     app.run_on_main_thread(move || {
-        let result = type_text_blocking(&text);
-        let _ = tx.send(result);
+        let _ = tx.send(f());
     })
     .map_err(|e| e.to_string())?;
 
@@ -44,20 +498,120 @@ pub async fn type_text(app: AppHandle, text: String) -> Result<(), String> {
     rx.recv().map_err(|e| e.to_string())?
 }
 
-/// Type text using clipboard and paste. Used internally by shortcut handlers.
-pub fn type_text_blocking(text: &str) -> Result<(), String> {
-    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+/// Split text into chunks of at most `max_chars` characters.
+///
+/// Chunks break after the last newline or whitespace within the limit where possible,
+/// falling back to a hard split for long unbroken runs. Concatenating the chunks
+/// always reproduces the original text exactly. A `max_chars` of zero means no limit.
+pub fn split_into_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    if max_chars == 0 {
+        return if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![text]
+        };
+    }
 
-    // Save previous clipboard content
-    let previous = clipboard.get_text().unwrap_or_default();
+    let mut chunks = Vec::new();
+    let mut rest = text;
 
-    // Set new text
-    clipboard.set_text(text).map_err(|e| e.to_string())?;
+    while !rest.is_empty() {
+        // Byte offset just past the first `max_chars` characters
+        let limit = match rest.char_indices().nth(max_chars) {
+            Some((offset, _)) => offset,
+            None => {
+                chunks.push(rest);
+                break;
+            }
+        };
 
-    // Small delay for clipboard to stabilize
-    thread::sleep(Duration::from_millis(CLIPBOARD_STABILIZATION_DELAY_MS));
+        let window = &rest[..limit];
+        let split_at = window
+            .rfind('\n')
+            .or_else(|| window.rfind(char::is_whitespace))
+            .map(|offset| offset + window[offset..].chars().next().map_or(1, char::len_utf8))
+            .filter(|&offset| offset > 0)
+            .unwrap_or(limit);
+
+        let (chunk, remainder) = rest.split_at(split_at);
+        chunks.push(chunk);
+        rest = remainder;
+    }
+
+    chunks
+}
+
+/// Insert text in one go with the given method. Pasting restores the previous clipboard
+/// contents after `restore_delay`. Used internally by shortcut handlers.
+pub fn insert_text_blocking(
+    text: &str,
+    method: InsertionMethod,
+    restore_delay: Duration,
+) -> Result<(), String> {
+    match method {
+        InsertionMethod::Paste => type_text_blocking(text, restore_delay),
+        InsertionMethod::Type => {
+            let newline_mode = method.newline_mode(NewlineMode::Paste);
+            for step in build_insertion_steps(text, newline_mode, INSERTION_CHUNK_CHARS) {
+                match step {
+                    InsertionStep::Paste(line) => type_keystrokes_blocking(&line)?,
+                    InsertionStep::Newline(mode) => press_newline_blocking(mode)?,
+                }
+            }
+            Ok(())
+        }
+        InsertionMethod::Accessibility => accessibility::insert_text(text).or_else(|e| {
+            let fallback = accessibility::fallback_method();
+            log::warn!(
+                "Accessibility insertion failed, using {:?} instead: {}",
+                fallback,
+                e
+            );
+            insert_text_blocking(text, fallback, restore_delay)
+        }),
+        InsertionMethod::ClipboardOnly => {
+            let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+            clipboard.set_text(text).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Type text using clipboard and paste, restoring the previous clipboard contents after
+/// `restore_delay`. Used internally by shortcut handlers.
+pub fn type_text_blocking(text: &str, restore_delay: Duration) -> Result<(), String> {
+    let snapshot = ClipboardSnapshot::capture();
+    let result = paste_text_blocking(text);
+    snapshot.restore_after(restore_delay, text);
+    result
+}
+
+/// Paste or type a single chunk, depending on the insertion method
+fn insert_chunk_blocking(chunk: &str, method: InsertionMethod) -> Result<(), String> {
+    if method == InsertionMethod::Paste {
+        paste_text_blocking(chunk)
+    } else {
+        type_keystrokes_blocking(chunk)
+    }
+}
+
+/// Put text on the clipboard, verify it landed, and simulate Ctrl+V / Cmd+V.
+/// Does not restore the previous clipboard contents.
+fn paste_text_blocking(text: &str) -> Result<(), String> {
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+
+    set_clipboard_verified(&mut clipboard, text)?;
 
     // Simulate Ctrl+V / Cmd+V
+    press_shortcut_blocking('v')
+}
+
+/// Press Ctrl (Cmd on macOS) with a letter key
+fn press_shortcut_blocking(letter: char) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if let Some(tool) = linux_input::wayland_tool() {
+        return tool.press(Some(KeyModifier::Ctrl), KeyName::Letter(letter));
+    }
+
     let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
 
     #[cfg(target_os = "macos")]
@@ -70,16 +624,120 @@ pub fn type_text_blocking(text: &str) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
     thread::sleep(Duration::from_millis(KEY_EVENT_DELAY_MS));
     enigo
-        .key(Key::Unicode('v'), Direction::Click)
+        .key(Key::Unicode(letter), Direction::Click)
         .map_err(|e| e.to_string())?;
     thread::sleep(Duration::from_millis(KEY_EVENT_DELAY_MS));
     enigo
         .key(modifier, Direction::Release)
         .map_err(|e| e.to_string())?;
 
-    // Restore previous clipboard after a delay
-    thread::sleep(Duration::from_millis(CLIPBOARD_RESTORE_DELAY_MS));
-    let _ = clipboard.set_text(&previous);
+    Ok(())
+}
+
+/// Press the key a voice command asks for
+fn press_command_key_blocking(key: CommandKey) -> Result<(), String> {
+    match key {
+        CommandKey::Enter => press_newline_blocking(NewlineMode::Enter),
+        CommandKey::Tab => {
+            #[cfg(target_os = "linux")]
+            if let Some(tool) = linux_input::wayland_tool() {
+                return tool.press(None, KeyName::Tab);
+            }
+
+            let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+            enigo
+                .key(Key::Tab, Direction::Click)
+                .map_err(|e| e.to_string())
+        }
+        CommandKey::Undo => press_shortcut_blocking('z'),
+    }
+}
 
+/// Simulate typing text character by character, without touching the clipboard
+fn type_keystrokes_blocking(text: &str) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if let Some(tool) = linux_input::wayland_tool() {
+        return tool.type_text(text);
+    }
+
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+    enigo.text(text).map_err(|e| e.to_string())
+}
+
+/// Press Enter or Shift+Enter to insert a newline
+fn press_newline_blocking(mode: NewlineMode) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if let Some(tool) = linux_input::wayland_tool() {
+        return match mode {
+            NewlineMode::Paste => paste_text_blocking("\n"),
+            NewlineMode::Enter => tool.press(None, KeyName::Return),
+            NewlineMode::ShiftEnter => tool.press(Some(KeyModifier::Shift), KeyName::Return),
+        };
+    }
+
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+
+    match mode {
+        NewlineMode::Paste => return paste_text_blocking("\n"),
+        NewlineMode::Enter => {
+            enigo
+                .key(Key::Return, Direction::Click)
+                .map_err(|e| e.to_string())?;
+        }
+        NewlineMode::ShiftEnter => {
+            enigo
+                .key(Key::Shift, Direction::Press)
+                .map_err(|e| e.to_string())?;
+            thread::sleep(Duration::from_millis(KEY_EVENT_DELAY_MS));
+            enigo
+                .key(Key::Return, Direction::Click)
+                .map_err(|e| e.to_string())?;
+            thread::sleep(Duration::from_millis(KEY_EVENT_DELAY_MS));
+            enigo
+                .key(Key::Shift, Direction::Release)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Press Backspace `count` times, e.g. to remove previously pasted text
+pub fn delete_chars_blocking(count: usize) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if let Some(tool) = linux_input::wayland_tool() {
+        for _ in 0..count {
+            tool.press(None, KeyName::BackSpace)?;
+        }
+        return Ok(());
+    }
+
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+    for _ in 0..count {
+        enigo
+            .key(Key::Backspace, Direction::Click)
+            .map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
+
+/// Set the clipboard text and read it back, retrying until it matches
+fn set_clipboard_verified(clipboard: &mut Clipboard, text: &str) -> Result<(), String> {
+    for attempt in 1..=CLIPBOARD_VERIFY_ATTEMPTS {
+        clipboard.set_text(text).map_err(|e| e.to_string())?;
+
+        // Small delay for clipboard to stabilize
+        thread::sleep(Duration::from_millis(CLIPBOARD_STABILIZATION_DELAY_MS));
+
+        if clipboard.get_text().is_ok_and(|current| current == text) {
+            return Ok(());
+        }
+        log::warn!(
+            "Clipboard verification failed (attempt {}/{})",
+            attempt,
+            CLIPBOARD_VERIFY_ATTEMPTS
+        );
+    }
+
+    Err("Clipboard contents did not match text to insert".to_string())
+}
@@ -0,0 +1,33 @@
+use crate::settings::get_setting_from_store;
+use crate::text_transforms::{apply_transforms, TransformRule, TransformTest};
+use tauri::AppHandle;
+
+/// Run the `text_transforms` rules over dictated text before it is inserted
+pub fn transform_dictation(app: &AppHandle, text: &str) -> String {
+    let rules: Vec<TransformRule> = get_setting_from_store(app, "text_transforms", Vec::new());
+    if rules.is_empty() {
+        return text.to_string();
+    }
+    let (output, errors) = apply_transforms(text, &rules);
+    for (index, error) in errors {
+        log::warn!("Skipped text transform {}: {}", index + 1, error);
+    }
+    output
+}
+
+/// Try one rule on sample text, to check it before saving
+#[tauri::command]
+pub fn test_text_transform(rule: TransformRule, sample: String) -> Result<TransformTest, String> {
+    rule.test(&sample)
+}
+
+/// Run a list of rules over sample text as a dictation would be, failing on the
+/// first enabled rule with an invalid pattern
+#[tauri::command]
+pub fn test_text_transforms(rules: Vec<TransformRule>, sample: String) -> Result<String, String> {
+    let (output, errors) = apply_transforms(&sample, &rules);
+    match errors.into_iter().next() {
+        Some((index, error)) => Err(format!("Rule {}: {}", index + 1, error)),
+        None => Ok(output),
+    }
+}
@@ -0,0 +1,132 @@
+use crate::updater::{UpdateChannel, UPDATER_PUBKEY};
+use serde::Serialize;
+use tauri::AppHandle;
+
+#[cfg(desktop)]
+use crate::settings::get_setting_from_store;
+#[cfg(desktop)]
+use std::sync::Mutex;
+#[cfg(desktop)]
+use tauri::{Emitter, Manager, Url};
+#[cfg(desktop)]
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+/// The update found by the last check, waiting for `install_update`
+#[cfg(desktop)]
+#[derive(Default)]
+pub struct PendingUpdate(Mutex<Option<Update>>);
+
+/// A newer release on the selected channel
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub channel: UpdateChannel,
+    /// Release notes
+    pub notes: Option<String>,
+    pub date: Option<String>,
+}
+
+/// Download progress of an update, emitted as `update-download-progress`
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    /// Size of the download, if the server reported it
+    pub total: Option<u64>,
+}
+
+/// The updater plugin, built with the release signing key
+#[cfg(desktop)]
+pub fn build_updater_plugin() -> Option<tauri::plugin::TauriPlugin<tauri::Wry>> {
+    UPDATER_PUBKEY.map(|pubkey| tauri_plugin_updater::Builder::new().pubkey(pubkey).build())
+}
+
+/// Whether this build can update itself
+#[tauri::command]
+pub fn is_updater_available() -> bool {
+    cfg!(desktop) && UPDATER_PUBKEY.is_some()
+}
+
+/// Check the selected update channel for a newer release. A found update is kept for
+/// `install_update`.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    if UPDATER_PUBKEY.is_none() {
+        return Err("This build can't update itself".to_string());
+    }
+    let channel: UpdateChannel =
+        get_setting_from_store(&app, "update_channel", UpdateChannel::default());
+    let endpoint = Url::parse(channel.endpoint()).map_err(|e| e.to_string())?;
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .and_then(|builder| builder.build())
+        .map_err(|e| e.to_string())?;
+    let update = updater
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    let info = update.as_ref().map(|update| UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        channel,
+        notes: update.body.clone(),
+        date: update.date.map(|date| date.to_string()),
+    });
+    match &info {
+        Some(info) => log::info!("Update {} available on {:?}", info.version, channel),
+        None => log::info!("No update available on {:?}", channel),
+    }
+    if let Ok(mut pending) = app.state::<PendingUpdate>().0.lock() {
+        *pending = update;
+    }
+    Ok(info)
+}
+
+/// Download and install the update found by the last check, emitting
+/// `update-download-progress`, then restart into the new version
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    let update = app
+        .state::<PendingUpdate>()
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or_else(|| "No update to install, check for updates first".to_string())?;
+    log::info!("Installing update {}", update.version);
+
+    let progress_app = app.clone();
+    let mut downloaded = 0u64;
+    update
+        .download_and_install(
+            move |chunk_length, total| {
+                downloaded += chunk_length as u64;
+                let _ = progress_app.emit(
+                    "update-download-progress",
+                    UpdateProgress { downloaded, total },
+                );
+            },
+            || log::info!("Update downloaded, installing"),
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    app.restart()
+}
+
+// Stubs for non-desktop platforms, which update through their app stores
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn check_for_updates(_app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    Err("Updates aren't available on this platform".to_string())
+}
+
+#[cfg(not(desktop))]
+#[tauri::command]
+pub async fn install_update(_app: AppHandle) -> Result<(), String> {
+    Err("Updates aren't available on this platform".to_string())
+}
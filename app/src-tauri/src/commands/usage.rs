@@ -0,0 +1,34 @@
+use crate::history::HistoryEntry;
+use crate::usage::{UsagePeriod, UsageStats, UsageStore};
+use chrono::Utc;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Count a dictation just added to history towards usage statistics
+pub fn record_usage(app: &AppHandle, entry: &HistoryEntry) {
+    if let Err(e) = app.state::<UsageStore>().record(
+        entry.timestamp,
+        &entry.text,
+        entry.duration_ms,
+        entry.provider.as_deref(),
+    ) {
+        log::warn!("{}", e);
+    }
+}
+
+/// Words dictated, speaking time, estimated time saved and per-provider usage
+/// over the last day, week, month or all time
+#[tauri::command]
+pub async fn get_usage_stats(
+    usage: State<'_, UsageStore>,
+    period: Option<UsagePeriod>,
+) -> Result<UsageStats, String> {
+    usage.stats(period.unwrap_or_default(), Utc::now())
+}
+
+/// Delete all usage statistics (history is kept)
+#[tauri::command]
+pub async fn reset_usage_stats(app: AppHandle) -> Result<(), String> {
+    app.state::<UsageStore>().reset()?;
+    let _ = app.emit("usage-stats-changed", ());
+    Ok(())
+}
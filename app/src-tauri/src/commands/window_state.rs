@@ -0,0 +1,120 @@
+use crate::commands::overlay::{forget_overlay_position, monitor_area};
+use crate::commands::session::{SESSION_WINDOW_LABEL, SESSION_WINDOW_SIZE};
+use crate::overlay::MonitorArea;
+use crate::window_state::{is_on_screen, WindowGeometry, WindowStateStore};
+use crate::MAIN_WINDOW_LABEL;
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow, Window, WindowEvent};
+
+/// Windows whose size and position are restored on launch
+const TRACKED_WINDOWS: &[&str] = &[MAIN_WINDOW_LABEL, SESSION_WINDOW_LABEL];
+
+/// Resize and move a window to where it was last time, and maximize it if it was.
+/// Windows last seen on a monitor that is no longer connected keep their default
+/// position.
+pub fn restore_window_state(window: &WebviewWindow) {
+    let Some(state) = window
+        .try_state::<WindowStateStore>()
+        .and_then(|store| store.get(window.label()))
+    else {
+        return;
+    };
+    let geometry = state.geometry;
+    let _ = window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
+        width: geometry.width,
+        height: geometry.height,
+    }));
+
+    let monitors: Vec<MonitorArea> = window
+        .available_monitors()
+        .map(|monitors| monitors.iter().map(monitor_area).collect())
+        .unwrap_or_default();
+    if is_on_screen(&geometry, &monitors) {
+        let _ = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: geometry.x,
+            y: geometry.y,
+        }));
+    } else {
+        log::info!(
+            "Saved position of {} is off screen, not restoring it",
+            window.label()
+        );
+    }
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Where a window is, or `None` while it is maximized
+fn current_geometry<R: Runtime>(window: &Window<R>) -> Option<WindowGeometry> {
+    if window.is_maximized().unwrap_or(false) {
+        return None;
+    }
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Keep track of tracked windows as they are moved and resized, and save their
+/// state when they lose focus or close
+pub fn track_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if !TRACKED_WINDOWS.contains(&window.label()) {
+        return;
+    }
+    let Some(store) = window.try_state::<WindowStateStore>() else {
+        return;
+    };
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            // Minimized windows report a position far off screen
+            if window.is_minimized().unwrap_or(false) {
+                return;
+            }
+            let maximized = window.is_maximized().unwrap_or(false);
+            store.update(window.label(), current_geometry(window), maximized);
+        }
+        WindowEvent::Focused(false) | WindowEvent::CloseRequested { .. } => {
+            if let Err(e) = store.save() {
+                log::warn!("{}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Move an open window back to the middle of its monitor at `size` (logical pixels)
+fn reset_window(window: &WebviewWindow, size: (f64, f64)) -> tauri::Result<()> {
+    window.unmaximize()?;
+    window.set_size(tauri::Size::Logical(tauri::LogicalSize {
+        width: size.0,
+        height: size.1,
+    }))?;
+    window.center()
+}
+
+/// Forget the saved size and position of every window and put open windows back
+/// where they start by default
+#[tauri::command]
+pub async fn reset_window_state(app: AppHandle) -> Result<(), String> {
+    app.state::<WindowStateStore>().reset()?;
+
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        let size = app
+            .config()
+            .app
+            .windows
+            .iter()
+            .find(|config| config.label == MAIN_WINDOW_LABEL)
+            .map(|config| (config.width, config.height))
+            .ok_or("Main window is missing from the app config")?;
+        reset_window(&window, size).map_err(|e| e.to_string())?;
+    }
+    if let Some(window) = app.get_webview_window(SESSION_WINDOW_LABEL) {
+        reset_window(&window, SESSION_WINDOW_SIZE).map_err(|e| e.to_string())?;
+    }
+    forget_overlay_position(&app)
+}
@@ -0,0 +1,287 @@
+//! Lifecycle of the server session.
+//!
+//! The overlay webview owns the WebRTC connection and reports every state change.
+//! When the connection drops or fails to open, the manager schedules a reconnect
+//! and asks the overlay to connect again once it is due. The backoff resets after a
+//! successful connection or a manual reconnect.
+//!
+//! Servers are tried in priority order: the configured server URL, then each
+//! fallback. A failed attempt moves on to the next server after a short delay, and
+//! only a full round of failures waits out the exponential backoff. After a drop the
+//! next attempt starts from the top again, so the app returns to the preferred server
+//! once it is reachable.
+
+use crate::events;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Delay before the first reconnect attempt
+pub const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between reconnect attempts
+pub const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Delay before trying the next server after one couldn't be reached
+pub const FAILOVER_DELAY: Duration = Duration::from_millis(500);
+
+/// Servers to connect to in priority order: `primary`, then each fallback.
+/// Blank and repeated URLs are skipped.
+pub fn server_endpoints(primary: &str, fallbacks: &[String]) -> Vec<String> {
+    let mut endpoints: Vec<String> = Vec::new();
+    for url in std::iter::once(primary).chain(fallbacks.iter().map(String::as_str)) {
+        let url = url.trim().trim_end_matches('/');
+        if !url.is_empty() && !endpoints.iter().any(|existing| existing == url) {
+            endpoints.push(url.to_string());
+        }
+    }
+    endpoints
+}
+
+/// Exponential delays between reconnect attempts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(INITIAL_RECONNECT_DELAY, MAX_RECONNECT_DELAY)
+    }
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next attempt, doubling each time up to the maximum
+    pub fn next_delay(&mut self) -> Duration {
+        let factor = 2u32.saturating_pow(self.attempt);
+        self.attempt = self.attempt.saturating_add(1);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+
+    /// Attempts made since the last reset
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// Connection state shown to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectionStatus {
+    /// State reported by the overlay (`disconnected`, `connecting`, `idle`, ...)
+    pub state: String,
+    /// Reconnect attempts since the connection was last up
+    pub attempt: u32,
+    /// Delay of the scheduled reconnect attempt, if there is one
+    pub retry_in_ms: Option<u64>,
+    /// Server in use, or being tried while disconnected
+    pub endpoint: Option<String>,
+}
+
+/// The server a connection was made to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActiveEndpoint {
+    pub url: String,
+    /// Position in the priority order (0 for the configured server URL)
+    pub priority: usize,
+}
+
+/// A reconnect attempt to run after `delay`, unless something newer supersedes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledReconnect {
+    pub generation: u64,
+    pub delay: Duration,
+}
+
+/// What a reported state changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportOutcome {
+    /// Whether the server became reachable or unreachable
+    pub connected_changed: bool,
+    /// Reconnect to schedule, if the connection is down
+    pub reconnect: Option<ScheduledReconnect>,
+    /// Server connected to, if it differs from the last one
+    pub active_endpoint: Option<ActiveEndpoint>,
+}
+
+struct Inner {
+    state: String,
+    backoff: Backoff,
+    endpoints: Vec<String>,
+    /// Index of the server in use or being tried
+    endpoint: usize,
+    /// Index of the server last connected to
+    active: Option<usize>,
+    /// Incremented whenever a scheduled reconnect is superseded
+    generation: u64,
+    /// Generation and delay of the reconnect waiting to run
+    scheduled: Option<(u64, Duration)>,
+    shutting_down: bool,
+}
+
+/// Tracks the server session and decides when to reconnect
+pub struct ConnectionManager {
+    inner: Mutex<Inner>,
+}
+
+impl Default for ConnectionManager {
+    fn default() -> Self {
+        Self::new(Backoff::default())
+    }
+}
+
+impl ConnectionManager {
+    pub fn new(backoff: Backoff) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                state: "disconnected".to_string(),
+                backoff,
+                endpoints: Vec::new(),
+                endpoint: 0,
+                active: None,
+                generation: 0,
+                scheduled: None,
+                shutting_down: false,
+            }),
+        }
+    }
+
+    pub fn status(&self) -> ConnectionStatus {
+        let Ok(inner) = self.inner.lock() else {
+            return ConnectionStatus {
+                state: "disconnected".to_string(),
+                attempt: 0,
+                retry_in_ms: None,
+                endpoint: None,
+            };
+        };
+        ConnectionStatus {
+            state: inner.state.clone(),
+            attempt: inner.backoff.attempt(),
+            retry_in_ms: inner
+                .scheduled
+                .map(|(_, delay)| u64::try_from(delay.as_millis()).unwrap_or(u64::MAX)),
+            endpoint: inner.endpoints.get(inner.endpoint).cloned(),
+        }
+    }
+
+    /// Replace the servers to connect to. Returns whether they changed, in which
+    /// case the next attempt starts from the first.
+    pub fn set_endpoints(&self, endpoints: Vec<String>) -> bool {
+        let Ok(mut inner) = self.inner.lock() else {
+            return false;
+        };
+        if inner.endpoints == endpoints {
+            return false;
+        }
+        inner.endpoints = endpoints;
+        inner.endpoint = 0;
+        inner.active = None;
+        true
+    }
+
+    /// Record a state reported by the overlay. When the connection drops or an
+    /// attempt fails, a reconnect is scheduled unless one is already waiting; a
+    /// successful connection resets the backoff.
+    pub fn report(&self, state: &str) -> ReportOutcome {
+        let Ok(mut inner) = self.inner.lock() else {
+            return ReportOutcome::default();
+        };
+        let was_connected = events::is_connected_state(&inner.state);
+        let was_disconnected = inner.state == "disconnected";
+        let connected = events::is_connected_state(state);
+        inner.state = state.to_string();
+
+        let mut outcome = ReportOutcome {
+            connected_changed: was_connected != connected,
+            ..ReportOutcome::default()
+        };
+        if connected {
+            inner.backoff.reset();
+            inner.scheduled = None;
+            if inner.active != Some(inner.endpoint) {
+                inner.active = Some(inner.endpoint);
+                outcome.active_endpoint =
+                    inner
+                        .endpoints
+                        .get(inner.endpoint)
+                        .map(|url| ActiveEndpoint {
+                            url: url.clone(),
+                            priority: inner.endpoint,
+                        });
+            }
+        } else if state == "disconnected"
+            && !was_disconnected
+            && inner.scheduled.is_none()
+            && !inner.shutting_down
+        {
+            let delay = if was_connected {
+                // Start from the preferred server again
+                inner.endpoint = 0;
+                inner.backoff.next_delay()
+            } else {
+                inner.endpoint = (inner.endpoint + 1) % inner.endpoints.len().max(1);
+                if inner.endpoint == 0 {
+                    inner.backoff.next_delay()
+                } else {
+                    FAILOVER_DELAY
+                }
+            };
+            inner.generation += 1;
+            inner.scheduled = Some((inner.generation, delay));
+            outcome.reconnect = Some(ScheduledReconnect {
+                generation: inner.generation,
+                delay,
+            });
+        }
+        outcome
+    }
+
+    /// Claim a scheduled reconnect once its delay has passed, returning the server
+    /// to connect to. Returns `None` if it was superseded or the connection is no
+    /// longer down.
+    pub fn take_due_reconnect(&self, generation: u64) -> Option<String> {
+        let mut inner = self.inner.lock().ok()?;
+        if inner.scheduled.map(|(scheduled, _)| scheduled) != Some(generation) {
+            return None;
+        }
+        inner.scheduled = None;
+        if inner.state != "disconnected" || inner.shutting_down {
+            return None;
+        }
+        inner.endpoints.get(inner.endpoint).cloned()
+    }
+
+    /// Start over from the first server with a fresh backoff, dropping any scheduled
+    /// reconnect. Returns the server to connect to.
+    pub fn reconnect_now(&self) -> Option<String> {
+        let mut inner = self.inner.lock().ok()?;
+        inner.backoff.reset();
+        inner.generation += 1;
+        inner.scheduled = None;
+        inner.endpoint = 0;
+        inner.endpoints.first().cloned()
+    }
+
+    /// Stop reconnecting, e.g. when the app is quitting
+    pub fn shut_down(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.shutting_down = true;
+            inner.generation += 1;
+            inner.scheduled = None;
+        }
+    }
+}
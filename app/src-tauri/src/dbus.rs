@@ -0,0 +1,123 @@
+//! D-Bus service for Linux desktops, so GNOME extensions, Waybar modules and
+//! scripts can show and control dictation without a token.
+//!
+//! The app owns `org.tambourine.Voice` on the session bus and serves the
+//! `org.tambourine.Voice` interface at `/org/tambourine/Voice`:
+//!
+//! - `Start`, `Stop` and `Toggle` control recording like the remote control
+//!   commands in [`crate::remote_control`]
+//! - `Status` returns `(recording, paused, connected)`
+//! - the `StateChanged(recording, paused, connected)` signal fires when recording
+//!   starts or stops and when the server connection changes
+//!
+//! For example `busctl --user call org.tambourine.Voice /org/tambourine/Voice
+//! org.tambourine.Voice Toggle`. The session bus only accepts the user's own
+//! processes, so unlike the WebSocket and HTTP servers this is always on.
+
+use crate::events::{self, AppEvent};
+use crate::remote_control::{self, RemoteCommand, RemoteStatus};
+use tauri::AppHandle;
+use zbus::object_server::SignalEmitter;
+use zbus::{fdo, interface, Connection};
+
+/// Well-known name requested on the session bus
+pub const BUS_NAME: &str = "org.tambourine.Voice";
+
+/// Object path the interface is served at
+pub const OBJECT_PATH: &str = "/org/tambourine/Voice";
+
+/// Status as sent over D-Bus: recording, paused, connected
+pub type StatusTuple = (bool, bool, bool);
+
+/// Flatten a status for D-Bus, which has no use for field names here
+pub fn status_tuple(status: RemoteStatus) -> StatusTuple {
+    (status.recording, status.paused, status.connected)
+}
+
+struct VoiceInterface {
+    app: AppHandle,
+}
+
+impl VoiceInterface {
+    /// Run a command off the bus's executor, since starting and stopping recording block
+    async fn run(&self, command: RemoteCommand) -> fdo::Result<StatusTuple> {
+        let app = self.app.clone();
+        tauri::async_runtime::spawn_blocking(move || remote_control::execute(&app, command))
+            .await
+            .map(status_tuple)
+            .map_err(|e| fdo::Error::Failed(e.to_string()))
+    }
+}
+
+#[interface(name = "org.tambourine.Voice")]
+impl VoiceInterface {
+    /// Start recording unless already recording
+    async fn start(&self) -> fdo::Result<StatusTuple> {
+        self.run(RemoteCommand::Start).await
+    }
+
+    /// Stop recording and transcribe unless already stopped
+    async fn stop(&self) -> fdo::Result<StatusTuple> {
+        self.run(RemoteCommand::Stop).await
+    }
+
+    /// Start recording if idle, otherwise stop it
+    async fn toggle(&self) -> fdo::Result<StatusTuple> {
+        self.run(RemoteCommand::Toggle).await
+    }
+
+    /// Whether the app is recording, paused and connected to the server
+    async fn status(&self) -> StatusTuple {
+        status_tuple(remote_control::status(&self.app))
+    }
+
+    #[zbus(signal)]
+    async fn state_changed(
+        emitter: &SignalEmitter<'_>,
+        recording: bool,
+        paused: bool,
+        connected: bool,
+    ) -> zbus::Result<()>;
+}
+
+async fn connect(app: &AppHandle) -> zbus::Result<Connection> {
+    zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, VoiceInterface { app: app.clone() })?
+        .build()
+        .await
+}
+
+/// Claim the bus name and signal state changes for as long as the app runs
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let connection = match connect(&app).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                log::warn!("Failed to register D-Bus service {}: {}", BUS_NAME, e);
+                return;
+            }
+        };
+        log::info!("D-Bus service {} registered", BUS_NAME);
+
+        events::spawn_subscriber(&app, "dbus", move |app, event| {
+            if !matches!(
+                event,
+                AppEvent::RecordingStarted
+                    | AppEvent::RecordingStopped
+                    | AppEvent::ConnectionChanged { .. }
+            ) {
+                return;
+            }
+            let (recording, paused, connected) = status_tuple(remote_control::status(app));
+            let result = tauri::async_runtime::block_on(async {
+                let emitter = SignalEmitter::new(&connection, OBJECT_PATH)?;
+                VoiceInterface::state_changed(&emitter, recording, paused, connected).await
+            });
+            if let Err(e) = result {
+                log::warn!("Failed to send D-Bus StateChanged signal: {}", e);
+            }
+        });
+    });
+}
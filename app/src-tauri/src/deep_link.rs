@@ -0,0 +1,151 @@
+//! `tambourine://` links for launchers such as Raycast, Alfred and PowerToys Run.
+//!
+//! - `tambourine://start`, optionally `?profile=<prompt preset>` to format with a
+//!   preset, chosen by name or ID
+//! - `tambourine://stop` and `tambourine://toggle`
+//! - `tambourine://paste-last` to paste the last transcription
+//!
+//! macOS hands links to the running app. Windows and Linux start a second
+//! instance with the link as its argument, which the single-instance plugin
+//! forwards to the first. Any web page can open a link, so they are ignored
+//! unless `deep_links_enabled` is on, and `paste-last` only pastes into an app
+//! known not to be a web browser: otherwise a page could open it while it has
+//! focus and read the last dictation.
+
+use crate::active_window::get_active_app;
+use crate::commands::prompt_presets::{apply_preset, stored_presets};
+use crate::remote_control::{self, RemoteCommand};
+use crate::settings::get_setting_from_store;
+use tauri::{AppHandle, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+/// The scheme registered in tauri.conf.json
+pub const SCHEME: &str = "tambourine";
+
+/// What a link asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkAction {
+    /// Start recording, first switching to the prompt preset `profile`
+    Start {
+        profile: Option<String>,
+    },
+    Stop,
+    Toggle,
+    PasteLast,
+}
+
+/// Parse a `tambourine://` link
+pub fn parse_deep_link(link: &str) -> Result<DeepLinkAction, String> {
+    let url = Url::parse(link).map_err(|e| format!("Invalid link {}: {}", link, e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {}:// link: {}", SCHEME, link));
+    }
+    // `tambourine://start` puts the action in the host, `tambourine:start` in the path
+    let action = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/')
+        .to_ascii_lowercase();
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    match action.as_str() {
+        "start" => Ok(DeepLinkAction::Start {
+            profile: query("profile"),
+        }),
+        "stop" => Ok(DeepLinkAction::Stop),
+        "toggle" => Ok(DeepLinkAction::Toggle),
+        "paste-last" => Ok(DeepLinkAction::PasteLast),
+        _ => Err(format!("Unknown link action: {}", action)),
+    }
+}
+
+/// Switch to the prompt preset named or identified by `profile`
+fn apply_profile(app: &AppHandle, profile: &str) -> Result<(), String> {
+    let preset = stored_presets(app)
+        .into_iter()
+        .find(|preset| preset.id == profile || preset.name.eq_ignore_ascii_case(profile))
+        .ok_or_else(|| format!("No prompt preset named {}", profile))?;
+    apply_preset(app, &preset.id).map(|_| ())
+}
+
+/// Run the action of a link if links are allowed
+pub fn handle_link(app: &AppHandle, link: &str) {
+    if !get_setting_from_store(app, "deep_links_enabled", false) {
+        log::info!("Ignoring link {}: links are turned off", link);
+        return;
+    }
+    let action = match parse_deep_link(link) {
+        Ok(action) => action,
+        Err(e) => {
+            log::warn!("{}", e);
+            return;
+        }
+    };
+    log::info!("DeepLink: {:?}", action);
+
+    match action {
+        DeepLinkAction::Start { profile } => {
+            if let Some(profile) = profile {
+                // Start anyway, with the current prompt, so the launcher still does something
+                if let Err(e) = apply_profile(app, &profile) {
+                    log::warn!("{}", e);
+                }
+            }
+            remote_control::execute(app, RemoteCommand::Start);
+        }
+        DeepLinkAction::Stop => {
+            remote_control::execute(app, RemoteCommand::Stop);
+        }
+        DeepLinkAction::Toggle => {
+            remote_control::execute(app, RemoteCommand::Toggle);
+        }
+        DeepLinkAction::PasteLast => match get_active_app() {
+            Some(target) if !target.is_web_browser() => crate::paste_last_transcription(app),
+            Some(target) => log::warn!(
+                "Not pasting from a link into {}: a web page could read it",
+                target.app_name
+            ),
+            None => log::warn!("Not pasting from a link into an app that can't be identified"),
+        },
+    }
+}
+
+/// Whether process arguments are a link, which the single-instance plugin hands
+/// to the deep link plugin rather than treating as a plain relaunch
+pub fn is_link_launch(args: &[String]) -> bool {
+    args.iter()
+        .skip(1)
+        .any(|arg| arg.starts_with(&format!("{}:", SCHEME)))
+}
+
+/// Handle links opened while the app runs, and the one it was started with
+pub fn init(app: &AppHandle) {
+    let deep_link = app.deep_link();
+    // Installs that skipped the bundle's registration (e.g. an AppImage) still get the scheme
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    if let Err(e) = deep_link.register_all() {
+        log::warn!("Failed to register the {}:// scheme: {}", SCHEME, e);
+    }
+
+    let handle = app.clone();
+    deep_link.on_open_url(move |event| {
+        for url in event.urls() {
+            // Recording setup blocks, so keep it off the event loop
+            let app = handle.clone();
+            std::thread::spawn(move || handle_link(&app, url.as_str()));
+        }
+    });
+
+    if let Ok(Some(urls)) = deep_link.get_current() {
+        for url in urls {
+            let app = app.clone();
+            std::thread::spawn(move || handle_link(&app, url.as_str()));
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Checks of the environment dictation depends on.
+//!
+//! Most problems users report are outside the app: microphone access denied, the
+//! selected microphone unplugged, the server not running, a hotkey owned by another
+//! app, or macOS not trusting the app to type. Each check here turns what the app
+//! already knows into a status with a suggested fix, so the settings UI can list
+//! them in one place.
+
+use crate::mic_monitor::MicStatus;
+use crate::server::ConnectionTest;
+use crate::settings::ShortcutRegistrationFailure;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but not as configured or not everywhere
+    Warn,
+    /// Stops dictation from working
+    Fail,
+    /// Couldn't be checked yet
+    Unknown,
+}
+
+/// Result of one check, with what to do about it when it didn't pass
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagnosticCheck {
+    /// Stable identifier, e.g. "microphone_access"
+    pub id: &'static str,
+    pub label: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+/// Every check, in the order dictation depends on them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagnosticsReport {
+    pub checks: Vec<DiagnosticCheck>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl DiagnosticCheck {
+    fn new(id: &'static str, label: &'static str, status: CheckStatus, detail: String) -> Self {
+        Self {
+            id,
+            label,
+            status,
+            detail,
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: &str) -> Self {
+        self.fix = Some(fix.to_string());
+        self
+    }
+}
+
+/// Whether the overlay could open the microphone
+pub fn check_microphone_access(mic: &MicStatus) -> DiagnosticCheck {
+    let check = |status, detail: &str| {
+        DiagnosticCheck::new(
+            "microphone_access",
+            "Microphone access",
+            status,
+            detail.to_string(),
+        )
+    };
+    match mic.permission {
+        Some(true) => check(CheckStatus::Pass, "Tambourine can use the microphone"),
+        Some(false) => check(CheckStatus::Fail, "Microphone access is denied").with_fix(
+            "Allow microphone access for Tambourine in your system's privacy settings, \
+             then start a new recording",
+        ),
+        None => check(
+            CheckStatus::Unknown,
+            "The microphone hasn't been opened since Tambourine started",
+        )
+        .with_fix("Record a short dictation, then run diagnostics again"),
+    }
+}
+
+/// Whether the selected microphone is plugged in
+pub fn check_microphone_device(mic: &MicStatus) -> DiagnosticCheck {
+    let check = |status, detail: String| {
+        DiagnosticCheck::new("microphone_device", "Microphone", status, detail)
+    };
+    match mic.device_count {
+        None => check(
+            CheckStatus::Unknown,
+            "The recording overlay hasn't listed microphones yet".to_string(),
+        )
+        .with_fix("Make sure the overlay is enabled, then run diagnostics again"),
+        Some(0) => check(CheckStatus::Fail, "No microphones were found".to_string())
+            .with_fix("Connect a microphone, or check that your system detects it"),
+        Some(_) if mic.is_fallback => check(
+            CheckStatus::Warn,
+            format!(
+                "{} isn't connected, so the system default microphone is used",
+                mic.selected_label
+                    .as_deref()
+                    .unwrap_or("Your preferred microphone")
+            ),
+        )
+        .with_fix("Reconnect the microphone, or add another preferred one in Settings → Audio"),
+        Some(count) => check(
+            CheckStatus::Pass,
+            format!(
+                "Recording from {} ({} found)",
+                mic.selected_label
+                    .as_deref()
+                    .unwrap_or("the system default microphone"),
+                count
+            ),
+        ),
+    }
+}
+
+/// Whether the server answered a health check
+pub fn check_server(url: &str, test: &ConnectionTest) -> DiagnosticCheck {
+    let check = |status, detail: String| DiagnosticCheck::new("server", "Server", status, detail);
+    if test.reachable {
+        return check(
+            CheckStatus::Pass,
+            format!(
+                "{} answered in {} ms",
+                url,
+                test.latency_ms.unwrap_or_default()
+            ),
+        );
+    }
+    check(
+        CheckStatus::Fail,
+        test.error
+            .clone()
+            .unwrap_or_else(|| format!("{} is unreachable", url)),
+    )
+    .with_fix(
+        "Start the server (uv run python main.py in the server folder), or check the \
+         server URL in Settings → Connection",
+    )
+}
+
+/// Whether every configured hotkey is registered
+pub fn check_hotkeys(failures: &[ShortcutRegistrationFailure]) -> DiagnosticCheck {
+    if failures.is_empty() {
+        return DiagnosticCheck::new(
+            "hotkeys",
+            "Hotkeys",
+            CheckStatus::Pass,
+            "All hotkeys are registered".to_string(),
+        );
+    }
+    let shortcuts: Vec<String> = failures
+        .iter()
+        .map(|failure| {
+            format!(
+                "{} ({})",
+                failure.action.replace('_', " "),
+                failure.shortcut
+            )
+        })
+        .collect();
+    DiagnosticCheck::new(
+        "hotkeys",
+        "Hotkeys",
+        CheckStatus::Fail,
+        format!("Not registered: {}", shortcuts.join(", ")),
+    )
+    .with_fix(
+        "Another app probably uses these shortcuts. Pick different ones in Settings → \
+         Hotkeys",
+    )
+}
+
+/// Whether text can be inserted into other apps. `accessibility_trusted` is `None`
+/// on platforms without an accessibility permission; `warnings` are the insertion
+/// problems already detected (secure input, missing Wayland tools, no clipboard).
+pub fn check_insertion(
+    accessibility_trusted: Option<bool>,
+    warnings: &[String],
+) -> DiagnosticCheck {
+    let check = |status, detail: String| {
+        DiagnosticCheck::new("insertion", "Text insertion", status, detail)
+    };
+    if accessibility_trusted == Some(false) {
+        return check(
+            CheckStatus::Fail,
+            "macOS doesn't allow Tambourine to type into other apps".to_string(),
+        )
+        .with_fix(
+            "Turn on Tambourine in System Settings → Privacy & Security → Accessibility, \
+             then restart it",
+        );
+    }
+    if !warnings.is_empty() {
+        return check(CheckStatus::Warn, warnings.join(". "))
+            .with_fix("See Settings → Text Insertion for details");
+    }
+    check(
+        CheckStatus::Pass,
+        "Text can be inserted into other apps".to_string(),
+    )
+}
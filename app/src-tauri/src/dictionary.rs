@@ -0,0 +1,342 @@
+//! Personal dictionary of spoken forms and how to write them.
+//!
+//! Entries are stored in `dictionary.db` in the app data directory. Each maps what
+//! the speech-to-text provider hears ("ant row pick") to how it should be written
+//! ("Anthropic"); an entry whose forms are the same is a term to recognize. The
+//! formatting prompt's dictionary section is rendered from the entries, so it can't
+//! drift from what the dictionary manager shows. Entries from the old free-text
+//! prompt section are imported once.
+
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Dictionary database file in the app data directory
+pub const DICTIONARY_DB_FILE: &str = "dictionary.db";
+
+/// Instructions above the entries in the formatting prompt
+pub const DICTIONARY_PROMPT_HEADER: &str = "## Personal Dictionary
+Apply these corrections for technical terms, proper nouns, and custom words.
+
+Each entry is either a mapping (\"spoken form = written form\") or a single term to \
+recognize. When you hear something that sounds like an entry, use its written form. \
+Entries marked (match case) only apply when the spoken form matches exactly.
+
+### Entries:";
+
+/// Entries the server's dictionary section starts with, seeded when the prompt
+/// section was never customized
+pub const DEFAULT_DICTIONARY_ENTRIES: &[(&str, &str)] = &[
+    ("Tambourine", "Tambourine"),
+    ("LLM", "LLM"),
+    ("ant row pick", "Anthropic"),
+    ("Claude", "Claude"),
+    ("Pipecat", "Pipecat"),
+    ("Tauri", "Tauri"),
+];
+
+/// Legacy lines with more words than this are prose rather than terms
+const MAX_TERM_WORDS: usize = 4;
+
+/// Marks a case-sensitive entry in the rendered prompt
+const MATCH_CASE_MARKER: &str = "(match case)";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS dictionary (
+        id TEXT PRIMARY KEY,
+        spoken TEXT NOT NULL,
+        written TEXT NOT NULL,
+        case_sensitive INTEGER NOT NULL DEFAULT 0
+    );
+";
+
+const ENTRY_COLUMNS: &str = "id, spoken, written, case_sensitive";
+
+/// A personal dictionary entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DictionaryEntry {
+    pub id: String,
+    /// What the speech-to-text provider hears
+    pub spoken: String,
+    /// How it should be written
+    pub written: String,
+    /// Only match the spoken form with the same capitalization
+    pub case_sensitive: bool,
+}
+
+impl DictionaryEntry {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            spoken: row.get(1)?,
+            written: row.get(2)?,
+            case_sensitive: row.get(3)?,
+        })
+    }
+
+    /// Whether `spoken` is the same spoken form as this entry's
+    pub fn has_spoken(&self, spoken: &str, case_sensitive: bool) -> bool {
+        same_spoken(&self.spoken, self.case_sensitive, spoken, case_sensitive)
+    }
+}
+
+/// Whether two spoken forms are the same. Capitalization only counts when either
+/// entry is case-sensitive.
+pub fn same_spoken(a: &str, a_case_sensitive: bool, b: &str, b_case_sensitive: bool) -> bool {
+    if a_case_sensitive || b_case_sensitive {
+        a == b
+    } else {
+        a.to_lowercase() == b.to_lowercase()
+    }
+}
+
+/// Trim both forms, using the spoken form as the written one when it is empty
+pub fn normalize_entry(spoken: &str, written: &str) -> Result<(String, String), String> {
+    let spoken = spoken.split_whitespace().collect::<Vec<_>>().join(" ");
+    if spoken.is_empty() {
+        return Err("The spoken form can't be empty".to_string());
+    }
+    let written = written.trim();
+    let written = if written.is_empty() {
+        spoken.clone()
+    } else {
+        written.to_string()
+    };
+    Ok((spoken, written))
+}
+
+/// Parse a "spoken = written" entry, or a single term, as (spoken, written, case
+/// sensitive). Returns `None` if there is no spoken form.
+pub fn parse_entry(text: &str) -> Option<(String, String, bool)> {
+    let text = text.trim();
+    let (text, case_sensitive) = match text.strip_suffix(MATCH_CASE_MARKER) {
+        Some(text) => (text.trim_end(), true),
+        None => (text, false),
+    };
+    let (spoken, written) = text.split_once('=').unwrap_or((text, ""));
+    let (spoken, written) = normalize_entry(spoken, written).ok()?;
+    Some((spoken, written, case_sensitive))
+}
+
+/// Parse a line of the old prompt section. Returns `None` for blank lines, headings,
+/// list items and sentences.
+pub fn parse_dictionary_line(line: &str) -> Option<(String, String, bool)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with(['#', '-', '*']) {
+        return None;
+    }
+    if !line.contains('=') && line.split_whitespace().count() > MAX_TERM_WORDS {
+        return None;
+    }
+    parse_entry(line)
+}
+
+/// Entries in the old free-text dictionary section. Only lines under an
+/// "### Entries:" heading are read when the section has one.
+pub fn parse_legacy_dictionary(content: &str) -> Vec<(String, String, bool)> {
+    let entries = content
+        .split_once("### Entries:")
+        .map_or(content, |(_, entries)| entries);
+    entries.lines().filter_map(parse_dictionary_line).collect()
+}
+
+/// The dictionary section of the formatting prompt, or `None` without entries
+pub fn dictionary_prompt(entries: &[DictionaryEntry]) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+    let mut prompt = DICTIONARY_PROMPT_HEADER.to_string();
+    for entry in entries {
+        prompt.push('\n');
+        if entry.spoken == entry.written {
+            prompt.push_str(&entry.written);
+        } else {
+            prompt.push_str(&format!("{} = {}", entry.spoken, entry.written));
+        }
+        if entry.case_sensitive {
+            prompt.push(' ');
+            prompt.push_str(MATCH_CASE_MARKER);
+        }
+    }
+    Some(prompt)
+}
+
+/// Persistent store of dictionary entries
+pub struct DictionaryStore {
+    connection: Mutex<Connection>,
+}
+
+impl DictionaryStore {
+    /// Open (or create) the dictionary database in the app data directory
+    pub fn new(app_data_dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+        let connection = Connection::open(app_data_dir.join(DICTIONARY_DB_FILE))
+            .map_err(|e| format!("Failed to open dictionary database: {}", e))?;
+        Self::with_connection(connection)
+    }
+
+    /// Entries kept in memory only, discarded when dropped
+    pub fn in_memory() -> Result<Self, String> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open dictionary database: {}", e))?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| format!("Failed to create dictionary table: {}", e))?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.connection
+            .lock()
+            .map_err(|e| format!("Failed to access the dictionary: {}", e))
+    }
+
+    /// All entries, in the order they were added
+    pub fn list(&self) -> Result<Vec<DictionaryEntry>, String> {
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {} FROM dictionary ORDER BY rowid",
+                ENTRY_COLUMNS
+            ))
+            .map_err(|e| format!("Failed to read the dictionary: {}", e))?;
+        statement
+            .query_map([], DictionaryEntry::from_row)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to read the dictionary: {}", e))
+    }
+
+    /// Add an entry. Adding a spoken form that is already in the dictionary with the
+    /// same written form returns the existing entry; a different written form is an error.
+    pub fn add(
+        &self,
+        spoken: &str,
+        written: &str,
+        case_sensitive: bool,
+    ) -> Result<DictionaryEntry, String> {
+        let (spoken, written) = normalize_entry(spoken, written)?;
+        if let Some(existing) = self.find_spoken(&spoken, case_sensitive, None)? {
+            if existing.written == written && existing.case_sensitive == case_sensitive {
+                return Ok(existing);
+            }
+            return Err(already_listed(&existing));
+        }
+
+        let entry = DictionaryEntry {
+            id: Uuid::new_v4().to_string(),
+            spoken,
+            written,
+            case_sensitive,
+        };
+        let connection = self.lock()?;
+        insert_entry(&connection, &entry)?;
+        Ok(entry)
+    }
+
+    /// Add entries in one transaction, without checking for duplicates
+    pub fn add_all(
+        &self,
+        entries: &[(String, String, bool)],
+    ) -> Result<Vec<DictionaryEntry>, String> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction()
+            .map_err(|e| format!("Failed to add dictionary entries: {}", e))?;
+        let mut added = Vec::with_capacity(entries.len());
+        for (spoken, written, case_sensitive) in entries {
+            let entry = DictionaryEntry {
+                id: Uuid::new_v4().to_string(),
+                spoken: spoken.clone(),
+                written: written.clone(),
+                case_sensitive: *case_sensitive,
+            };
+            insert_entry(&transaction, &entry)?;
+            added.push(entry);
+        }
+        transaction
+            .commit()
+            .map_err(|e| format!("Failed to add dictionary entries: {}", e))?;
+        Ok(added)
+    }
+
+    /// Change an entry, keeping its place in the dictionary
+    pub fn update(
+        &self,
+        id: &str,
+        spoken: &str,
+        written: &str,
+        case_sensitive: bool,
+    ) -> Result<DictionaryEntry, String> {
+        let (spoken, written) = normalize_entry(spoken, written)?;
+        if let Some(existing) = self.find_spoken(&spoken, case_sensitive, Some(id))? {
+            return Err(already_listed(&existing));
+        }
+
+        let updated = self
+            .lock()?
+            .execute(
+                "UPDATE dictionary SET spoken = ?2, written = ?3, case_sensitive = ?4
+                 WHERE id = ?1",
+                params![id, spoken, written, case_sensitive],
+            )
+            .map_err(|e| format!("Failed to update dictionary entry: {}", e))?;
+        if updated == 0 {
+            return Err("Dictionary entry not found".to_string());
+        }
+        Ok(DictionaryEntry {
+            id: id.to_string(),
+            spoken,
+            written,
+            case_sensitive,
+        })
+    }
+
+    /// Remove an entry. Returns false if it didn't exist.
+    pub fn remove(&self, id: &str) -> Result<bool, String> {
+        let removed = self
+            .lock()?
+            .execute("DELETE FROM dictionary WHERE id = ?1", [id])
+            .map_err(|e| format!("Failed to remove dictionary entry: {}", e))?;
+        Ok(removed > 0)
+    }
+
+    /// The entry with the same spoken form, other than `except_id`
+    fn find_spoken(
+        &self,
+        spoken: &str,
+        case_sensitive: bool,
+        except_id: Option<&str>,
+    ) -> Result<Option<DictionaryEntry>, String> {
+        Ok(self.list()?.into_iter().find(|entry| {
+            Some(entry.id.as_str()) != except_id && entry.has_spoken(spoken, case_sensitive)
+        }))
+    }
+}
+
+fn insert_entry(connection: &Connection, entry: &DictionaryEntry) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO dictionary (id, spoken, written, case_sensitive)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![entry.id, entry.spoken, entry.written, entry.case_sensitive],
+        )
+        .map_err(|e| format!("Failed to add dictionary entry: {}", e))?;
+    Ok(())
+}
+
+fn already_listed(entry: &DictionaryEntry) -> String {
+    format!(
+        "\"{}\" is already in the dictionary as \"{}\"",
+        entry.spoken, entry.written
+    )
+}
@@ -0,0 +1,247 @@
+//! Import of personal dictionary entries from CSV and text files.
+//!
+//! CSV files have the spoken form in the first column, an optional written form in
+//! the second and an optional case-sensitivity flag in the third, so a spreadsheet
+//! of terms can be saved and loaded as is; a header row is skipped. Text files have
+//! one "spoken = written" entry or term per line, with `#` starting a comment.
+//! Every import can be previewed as a dry run, which reports the same result
+//! without adding anything.
+
+use crate::dictionary::{normalize_entry, parse_entry, same_spoken, DictionaryEntry};
+use serde::{Deserialize, Serialize};
+
+/// First cells that mark a CSV header row rather than an entry
+const CSV_HEADER_CELLS: &[&str] = &["spoken", "spoken form", "heard", "term", "word"];
+
+/// Case-sensitivity cells read as true
+const TRUE_CELLS: &[&str] = &["true", "yes", "y", "1", "x"];
+
+/// File format of a dictionary import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DictionaryImportFormat {
+    Csv,
+    Text,
+}
+
+/// An entry read from an import file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportedEntry {
+    /// Line of the file the entry starts on, from 1
+    pub line: usize,
+    pub spoken: String,
+    pub written: String,
+    pub case_sensitive: bool,
+}
+
+/// A spoken form the dictionary already writes differently. The existing entry is kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportConflict {
+    pub entry: ImportedEntry,
+    pub existing: DictionaryEntry,
+}
+
+/// A line of an import file that holds no entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedLine {
+    pub line: usize,
+    pub text: String,
+}
+
+/// What an import added, or would add in a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DictionaryImport {
+    pub dry_run: bool,
+    pub added: Vec<ImportedEntry>,
+    /// Entries already in the dictionary, or earlier in the file
+    pub duplicates: Vec<ImportedEntry>,
+    pub conflicts: Vec<ImportConflict>,
+    pub skipped: Vec<SkippedLine>,
+}
+
+impl DictionaryImport {
+    /// The entries to add, as (spoken, written, case sensitive)
+    pub fn new_entries(&self) -> Vec<(String, String, bool)> {
+        self.added
+            .iter()
+            .map(|entry| {
+                (
+                    entry.spoken.clone(),
+                    entry.written.clone(),
+                    entry.case_sensitive,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Read the entries of an import file, with the lines that hold none
+pub fn parse_import(
+    content: &str,
+    format: DictionaryImportFormat,
+) -> (Vec<ImportedEntry>, Vec<SkippedLine>) {
+    // Spreadsheets often save UTF-8 with a byte order mark
+    let content = content.trim_start_matches('\u{feff}');
+    match format {
+        DictionaryImportFormat::Csv => parse_csv_import(content),
+        DictionaryImportFormat::Text => parse_text_import(content),
+    }
+}
+
+fn parse_text_import(content: &str) -> (Vec<ImportedEntry>, Vec<SkippedLine>) {
+    let mut entries = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, text) in content.lines().enumerate() {
+        let line = index + 1;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        match parse_entry(text) {
+            Some((spoken, written, case_sensitive)) => entries.push(ImportedEntry {
+                line,
+                spoken,
+                written,
+                case_sensitive,
+            }),
+            None => skipped.push(SkippedLine {
+                line,
+                text: text.to_string(),
+            }),
+        }
+    }
+    (entries, skipped)
+}
+
+fn parse_csv_import(content: &str) -> (Vec<ImportedEntry>, Vec<SkippedLine>) {
+    let mut entries = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, (line, cells)) in parse_csv(content, csv_delimiter(content))
+        .into_iter()
+        .enumerate()
+    {
+        let cell = |column: usize| cells.get(column).map_or("", |cell| cell.trim());
+        if cells.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        if index == 0 && CSV_HEADER_CELLS.contains(&cell(0).to_lowercase().as_str()) {
+            continue;
+        }
+        match normalize_entry(cell(0), cell(1)) {
+            Ok((spoken, written)) => entries.push(ImportedEntry {
+                line,
+                spoken,
+                written,
+                case_sensitive: TRUE_CELLS.contains(&cell(2).to_lowercase().as_str()),
+            }),
+            Err(_) => skipped.push(SkippedLine {
+                line,
+                text: cells.join(","),
+            }),
+        }
+    }
+    (entries, skipped)
+}
+
+/// Semicolons separate cells in spreadsheets saved with a decimal comma
+fn csv_delimiter(content: &str) -> char {
+    let first_line = content.lines().next().unwrap_or_default();
+    if first_line.contains(';') && !first_line.contains(',') {
+        ';'
+    } else {
+        ','
+    }
+}
+
+/// Split CSV into records of cells, each with the line it starts on. Quoted cells
+/// may hold delimiters, newlines and doubled quotes.
+fn parse_csv(content: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    cell.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                in_quotes = true;
+            }
+            '\n' if in_quotes => {
+                line += 1;
+                cell.push(c);
+            }
+            '\r' if !in_quotes => {}
+            '\n' => {
+                cells.push(std::mem::take(&mut cell));
+                records.push((record_line, std::mem::take(&mut cells)));
+                line += 1;
+                record_line = line;
+            }
+            c if c == delimiter && !in_quotes => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !cells.is_empty() {
+        cells.push(cell);
+        records.push((record_line, cells));
+    }
+    records
+}
+
+/// Sort parsed entries into those to add, duplicates and conflicts with `existing`
+pub fn plan_import(
+    existing: &[DictionaryEntry],
+    entries: Vec<ImportedEntry>,
+    skipped: Vec<SkippedLine>,
+    dry_run: bool,
+) -> DictionaryImport {
+    let mut import = DictionaryImport {
+        dry_run,
+        skipped,
+        ..DictionaryImport::default()
+    };
+    for entry in entries {
+        let listed = existing
+            .iter()
+            .find(|existing| existing.has_spoken(&entry.spoken, entry.case_sensitive));
+        if let Some(listed) = listed {
+            if listed.written == entry.written && listed.case_sensitive == entry.case_sensitive {
+                import.duplicates.push(entry);
+            } else {
+                import.conflicts.push(ImportConflict {
+                    entry,
+                    existing: listed.clone(),
+                });
+            }
+            continue;
+        }
+
+        // Repeated in the file: the first occurrence wins
+        let repeated = import.added.iter().any(|added| {
+            same_spoken(
+                &added.spoken,
+                added.case_sensitive,
+                &entry.spoken,
+                entry.case_sensitive,
+            )
+        });
+        if repeated {
+            import.duplicates.push(entry);
+        } else {
+            import.added.push(entry);
+        }
+    }
+    import
+}
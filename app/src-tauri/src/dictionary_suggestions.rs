@@ -0,0 +1,92 @@
+//! Personal dictionary entries suggested from corrected dictations.
+//!
+//! When a dictation is corrected without adding the fixes to the dictionary, each
+//! replaced word is remembered as a suggested "heard = corrected" entry, so the
+//! dictionary can be built from real mistakes. A suggestion is offered until it is
+//! added or dismissed, and words corrected more often are listed first.
+
+use crate::text_diff::WordCorrection;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// A correction that could become a dictionary entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DictionarySuggestion {
+    pub heard: String,
+    pub corrected: String,
+    /// How many times the word has been corrected this way
+    pub count: u32,
+}
+
+#[derive(Default)]
+struct SuggestionState {
+    pending: Vec<DictionarySuggestion>,
+    /// Suggestions that were added or dismissed, as (heard, corrected)
+    resolved: HashSet<(String, String)>,
+}
+
+#[derive(Default)]
+pub struct DictionarySuggestions {
+    state: Mutex<SuggestionState>,
+}
+
+impl DictionarySuggestions {
+    /// Remember corrections. Returns the ones that weren't already suggested.
+    pub fn record(&self, corrections: &[WordCorrection]) -> Vec<DictionarySuggestion> {
+        let Ok(mut state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut added = Vec::new();
+        for correction in corrections {
+            let key = (correction.heard.clone(), correction.corrected.clone());
+            if state.resolved.contains(&key) {
+                continue;
+            }
+            match state
+                .pending
+                .iter_mut()
+                .find(|s| s.heard == correction.heard && s.corrected == correction.corrected)
+            {
+                Some(existing) => existing.count += 1,
+                None => {
+                    let suggestion = DictionarySuggestion {
+                        heard: correction.heard.clone(),
+                        corrected: correction.corrected.clone(),
+                        count: 1,
+                    };
+                    state.pending.push(suggestion.clone());
+                    added.push(suggestion);
+                }
+            }
+        }
+        added
+    }
+
+    /// Pending suggestions, most often corrected first
+    pub fn list(&self) -> Vec<DictionarySuggestion> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut suggestions = state.pending.clone();
+        // Stable, so equally common suggestions stay in the order they were made
+        suggestions.sort_by_key(|s| std::cmp::Reverse(s.count));
+        suggestions
+    }
+
+    /// Stop offering a suggestion because it was added or dismissed.
+    /// Returns false if it wasn't pending.
+    pub fn resolve(&self, heard: &str, corrected: &str) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        state
+            .resolved
+            .insert((heard.to_string(), corrected.to_string()));
+        let before = state.pending.len();
+        state
+            .pending
+            .retain(|s| !(s.heard == heard && s.corrected == corrected));
+        state.pending.len() != before
+    }
+}
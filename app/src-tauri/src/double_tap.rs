@@ -0,0 +1,152 @@
+use crate::input_listener::InputListener;
+use crate::settings::{get_setting_from_store, HotkeyAction, HotkeyConfig, TapModifier};
+use crate::trigger_queue::{Trigger, TriggerQueue};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Toggle recording bound to a double-tapped modifier (e.g. Ctrl, Ctrl).
+///
+/// A lone modifier can't be registered as a global shortcut, so key events come from
+/// the shared low-level input listener, started the first time the toggle hotkey is
+/// bound to a double tap.
+#[derive(Default)]
+pub struct DoubleTapTrigger {
+    /// Modifier currently bound to toggle recording and its tap state, if any
+    binding: Mutex<Option<(TapModifier, DoubleTapDetector)>>,
+}
+
+impl DoubleTapTrigger {
+    /// Re-read the toggle hotkey and start listening if it is bound to a double tap
+    pub fn refresh(&self, app: &AppHandle) {
+        let toggle_hotkey: HotkeyConfig =
+            get_setting_from_store(app, "toggle_hotkey", HotkeyAction::Toggle.default_hotkey());
+        let binding = toggle_hotkey.tap_modifier().map(|modifier| {
+            (
+                modifier,
+                DoubleTapDetector::new(toggle_hotkey.tap_interval()),
+            )
+        });
+        let is_bound = binding.is_some();
+
+        if let Ok(mut current) = self.binding.lock() {
+            *current = binding;
+        }
+
+        if is_bound {
+            app.state::<InputListener>().ensure_started(app);
+        }
+    }
+}
+
+/// Toggle recording when the bound modifier completes a double tap
+pub fn handle_input_event(app: &AppHandle, event: &rdev::Event) {
+    let trigger = app.state::<DoubleTapTrigger>();
+    let Ok(mut binding) = trigger.binding.lock() else {
+        return;
+    };
+    let Some((modifier, detector)) = binding.as_mut() else {
+        return;
+    };
+
+    let now = Instant::now();
+    let completed = match event.event_type {
+        rdev::EventType::KeyPress(key) if tap_modifier_from_rdev(key) == Some(*modifier) => {
+            detector.press(now);
+            false
+        }
+        rdev::EventType::KeyRelease(key) if tap_modifier_from_rdev(key) == Some(*modifier) => {
+            detector.release(now)
+        }
+        // Any other key or button means the modifier is part of a chord (e.g. Ctrl+C)
+        rdev::EventType::KeyPress(_) | rdev::EventType::ButtonPress(_) => {
+            detector.interrupt();
+            false
+        }
+        _ => false,
+    };
+    drop(binding);
+
+    if completed {
+        // Handle off the hook thread so slow recording setup doesn't stall the keyboard
+        app.state::<TriggerQueue>().send(Trigger::Toggle {
+            source: "DoubleTap",
+        });
+    }
+}
+
+/// Map an rdev key to a tappable modifier, treating left and right variants alike
+fn tap_modifier_from_rdev(key: rdev::Key) -> Option<TapModifier> {
+    match key {
+        rdev::Key::ControlLeft | rdev::Key::ControlRight => Some(TapModifier::Ctrl),
+        rdev::Key::Alt | rdev::Key::AltGr => Some(TapModifier::Alt),
+        rdev::Key::ShiftLeft | rdev::Key::ShiftRight => Some(TapModifier::Shift),
+        rdev::Key::MetaLeft | rdev::Key::MetaRight => Some(TapModifier::Meta),
+        _ => None,
+    }
+}
+
+/// Detects two quick taps of a modifier.
+///
+/// A tap is a press and release no longer than the interval with no other key in
+/// between, and the second press must follow the first release within the interval.
+#[derive(Debug, Clone)]
+pub struct DoubleTapDetector {
+    interval: Duration,
+    pressed_at: Option<Instant>,
+    last_tap: Option<Instant>,
+    is_second_tap: bool,
+}
+
+impl DoubleTapDetector {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            pressed_at: None,
+            last_tap: None,
+            is_second_tap: false,
+        }
+    }
+
+    /// The modifier went down. OS key repeats while it is held are ignored.
+    pub fn press(&mut self, now: Instant) {
+        if self.pressed_at.is_some() {
+            return;
+        }
+        self.is_second_tap = self
+            .last_tap
+            .is_some_and(|last_tap| now.duration_since(last_tap) <= self.interval);
+        self.pressed_at = Some(now);
+    }
+
+    /// The modifier was released. Returns true when this completes a double tap.
+    pub fn release(&mut self, now: Instant) -> bool {
+        let Some(pressed_at) = self.pressed_at.take() else {
+            return false;
+        };
+
+        // Held too long to count as a tap
+        if now.duration_since(pressed_at) > self.interval {
+            self.reset();
+            return false;
+        }
+
+        if self.is_second_tap {
+            self.reset();
+            return true;
+        }
+        self.last_tap = Some(now);
+        false
+    }
+
+    /// Another key was pressed, so the modifier is being used in a chord
+    pub fn interrupt(&mut self) {
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.pressed_at = None;
+        self.last_tap = None;
+        self.is_second_tap = false;
+    }
+}
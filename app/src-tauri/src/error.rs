@@ -0,0 +1,102 @@
+//! Structured errors for Tauri commands.
+//!
+//! Commands used to fail with a bare message, which the frontend could only show
+//! as is. An `AppError` serializes to `{ code, kind, message, context }` so the UI
+//! can word (and later translate) its own message from the code and context, and
+//! tell a bad value the user can fix apart from a failure to read or write.
+
+use crate::settings::{describe_registration_failures, ShortcutRegistrationFailure};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+/// Error returned by a Tauri command
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AppError {
+    /// A hotkey that doesn't parse into a shortcut
+    #[error("{reason}")]
+    InvalidHotkey { hotkey: String, reason: String },
+    /// Shortcuts that another application already owns
+    #[error("{}", describe_registration_failures(failures))]
+    ShortcutsInUse {
+        failures: Vec<ShortcutRegistrationFailure>,
+    },
+    /// The OS refused to change the registered shortcuts
+    #[error("Failed to {action}: {reason}")]
+    ShortcutManager { action: String, reason: String },
+    /// The settings file couldn't be opened, serialized or saved
+    #[error("Failed to {action}: {reason}")]
+    SettingsIo { action: String, reason: String },
+}
+
+impl AppError {
+    pub fn invalid_hotkey(hotkey: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidHotkey {
+            hotkey: hotkey.into(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn shortcut_manager(action: &str, error: impl ToString) -> Self {
+        Self::ShortcutManager {
+            action: action.to_string(),
+            reason: error.to_string(),
+        }
+    }
+
+    pub fn settings_io(action: &str, error: impl ToString) -> Self {
+        Self::SettingsIo {
+            action: action.to_string(),
+            reason: error.to_string(),
+        }
+    }
+
+    /// Stable identifier of the error, for the frontend to pick a message by
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidHotkey { .. } => "invalid_hotkey",
+            Self::ShortcutsInUse { .. } => "shortcuts_in_use",
+            Self::ShortcutManager { .. } => "shortcut_manager",
+            Self::SettingsIo { .. } => "settings_io",
+        }
+    }
+
+    /// Broad class of the error: "validation" for input the user can correct,
+    /// "conflict" for something another application holds, "io" for a failure to
+    /// read or write
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::InvalidHotkey { .. } => "validation",
+            Self::ShortcutsInUse { .. } => "conflict",
+            Self::ShortcutManager { .. } | Self::SettingsIo { .. } => "io",
+        }
+    }
+
+    /// Details the frontend needs to build its own message
+    pub fn context(&self) -> Value {
+        match self {
+            Self::InvalidHotkey { hotkey, reason } => json!({
+                "hotkey": hotkey,
+                "reason": reason,
+            }),
+            Self::ShortcutsInUse { failures } => json!({ "failures": failures }),
+            Self::ShortcutManager { action, reason } | Self::SettingsIo { action, reason } => {
+                json!({
+                    "action": action,
+                    "reason": reason,
+                })
+            }
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("AppError", 4)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.serialize_field("context", &self.context())?;
+        error.end()
+    }
+}
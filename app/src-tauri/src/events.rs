@@ -0,0 +1,97 @@
+//! Internal event bus for state shared across subsystems.
+//!
+//! Publishers announce what happened (a turn completed, the server connection
+//! dropped) without knowing who cares, and each subsystem subscribes on its own
+//! thread. Frontend-facing Tauri events are emitted by subscribers, not publishers.
+
+use crate::telemetry::FailureCategory;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Number of events buffered per subscriber before the slowest one starts missing events
+const EVENT_BUS_CAPACITY: usize = 64;
+
+/// Something that happened in the app that other subsystems may react to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AppEvent {
+    /// Recording started for a new dictation turn
+    RecordingStarted,
+    /// Recording stopped and the turn is being transcribed and formatted
+    RecordingStopped,
+    /// A turn finished and its text was inserted into the focused app
+    TurnCompleted { text: String },
+    /// A turn couldn't be transcribed or its text couldn't be inserted
+    TurnFailed {
+        error: String,
+        category: FailureCategory,
+    },
+    /// Insertion was refused because macOS secure input was on
+    SecureInputBlocked,
+    /// The overlay's connection to the server changed
+    ConnectionChanged { connected: bool },
+    /// The most preferred microphone changed (`None` means the system default)
+    DeviceChanged { device_id: Option<String> },
+    /// Settings changed, whether saved from a window, by Rust or in the settings file
+    SettingsChanged,
+    /// Dictation was paused (do not disturb) or resumed
+    DictationPausedChanged { paused: bool },
+}
+
+/// Broadcasts [`AppEvent`]s to every subscriber
+pub struct EventBus {
+    sender: broadcast::Sender<AppEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(EVENT_BUS_CAPACITY)
+    }
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Send an event to all current subscribers. Events with no subscribers are dropped.
+    pub fn publish(&self, event: AppEvent) {
+        log::debug!("Event: {:?}", event);
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// Publish an event on the app's bus
+pub fn publish(app: &AppHandle, event: AppEvent) {
+    app.state::<EventBus>().publish(event);
+}
+
+/// Run `handler` for every event on a dedicated thread until the bus is dropped.
+/// Handlers may block (e.g. on disk or clipboard access) without stalling publishers.
+pub fn spawn_subscriber<F>(app: &AppHandle, name: &'static str, mut handler: F)
+where
+    F: FnMut(&AppHandle, AppEvent) + Send + 'static,
+{
+    let mut receiver = app.state::<EventBus>().subscribe();
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        match receiver.blocking_recv() {
+            Ok(event) => handler(&app, event),
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("Event subscriber {} missed {} events", name, skipped);
+            }
+            Err(RecvError::Closed) => break,
+        }
+    });
+}
+
+/// Whether a connection state reported by the overlay means the server is reachable
+pub fn is_connected_state(state: &str) -> bool {
+    matches!(state, "idle" | "recording" | "processing")
+}
@@ -0,0 +1,109 @@
+//! Export of dictation history and usage statistics.
+//!
+//! JSON exports hold the usage totals for the chosen period followed by every
+//! history entry in it. CSV exports have one row per history entry with its word
+//! count and estimated time saved, so the totals can be worked out in a spreadsheet.
+
+use crate::history::HistoryEntry;
+use crate::usage::{self, UsagePeriod, UsageStats};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Columns of a CSV export, in order
+pub const CSV_HEADER: &[&str] = &[
+    "timestamp",
+    "app_name",
+    "provider",
+    "duration_ms",
+    "words",
+    "time_saved_ms",
+    "text",
+    "raw_text",
+];
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Everything written to a JSON export
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportData {
+    pub exported_at: DateTime<Utc>,
+    pub stats: UsageStats,
+    /// Oldest first
+    pub history: Vec<HistoryEntry>,
+}
+
+impl ExportData {
+    pub fn render(&self, format: ExportFormat) -> Result<String, String> {
+        match format {
+            ExportFormat::Csv => Ok(history_csv(&self.history)),
+            ExportFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Failed to serialize export: {}", e)),
+        }
+    }
+}
+
+/// File name for an export made at `now` when no path is given
+pub fn default_file_name(format: ExportFormat, period: UsagePeriod, now: DateTime<Utc>) -> String {
+    let period = match period {
+        UsagePeriod::Day => "day",
+        UsagePeriod::Week => "week",
+        UsagePeriod::Month => "month",
+        UsagePeriod::All => "all",
+    };
+    format!(
+        "tambourine-{}-{}.{}",
+        period,
+        now.format("%Y%m%d-%H%M%S"),
+        format.extension()
+    )
+}
+
+/// History entries as CSV, one row per entry
+pub fn history_csv(entries: &[HistoryEntry]) -> String {
+    let mut csv = CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+    for entry in entries {
+        let words = usage::count_words(&entry.text);
+        let fields = [
+            entry.timestamp.to_rfc3339(),
+            entry.app_name.clone().unwrap_or_default(),
+            entry.provider.clone().unwrap_or_default(),
+            entry
+                .duration_ms
+                .map(|ms| ms.to_string())
+                .unwrap_or_default(),
+            words.to_string(),
+            usage::time_saved_ms(words, entry.duration_ms.unwrap_or_default()).to_string(),
+            entry.text.clone(),
+            entry.raw_text.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
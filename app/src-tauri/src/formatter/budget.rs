@@ -0,0 +1,144 @@
+//! Keeping in-app formatting prompts within a token budget.
+//!
+//! Tokens are estimated from the character count rather than counted with a model's
+//! tokenizer, which is close enough to keep a prompt from overflowing a context
+//! window. When the system prompt and transcript don't fit, context sections are cut
+//! in [`TRUNCATION_ORDER`]. The core instructions and the transcript are never cut.
+
+use serde::{Deserialize, Serialize};
+
+/// Rough number of characters per token for English text
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// What a part of the system prompt holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptPartKind {
+    /// The main formatting instructions, always kept
+    Instructions,
+    /// Advanced features such as backtrack corrections and lists
+    Advanced,
+    /// Personal dictionary entries
+    Dictionary,
+}
+
+/// Order parts are cut in when a prompt is over budget, least important first
+pub const TRUNCATION_ORDER: [PromptPartKind; 2] =
+    [PromptPartKind::Advanced, PromptPartKind::Dictionary];
+
+/// How an over-budget section is cut
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptTruncation {
+    /// Keep as many leading lines of the section as fit
+    #[default]
+    Trim,
+    /// Leave the whole section out
+    Drop,
+}
+
+/// One section of a system prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptPart {
+    pub kind: PromptPartKind,
+    pub text: String,
+}
+
+impl PromptPart {
+    pub fn new(kind: PromptPartKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// Estimated number of tokens in `text`
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Join prompt parts into a system prompt
+pub fn join_prompt_parts(parts: &[PromptPart]) -> String {
+    parts
+        .iter()
+        .map(|part| part.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Largest prompt size allowed and how to cut sections to reach it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptBudget {
+    /// Most tokens the system prompt and transcript may use together (0 for no limit)
+    pub max_tokens: usize,
+    pub truncation: PromptTruncation,
+}
+
+impl PromptBudget {
+    /// Build the system prompt from `parts`, cutting sections until the prompt plus
+    /// `reserved_tokens` (the transcript) fit. If they still don't fit once every
+    /// section has been cut, the prompt is returned over budget.
+    pub fn fit(&self, mut parts: Vec<PromptPart>, reserved_tokens: usize) -> String {
+        if self.max_tokens == 0 {
+            return join_prompt_parts(&parts);
+        }
+
+        for kind in TRUNCATION_ORDER {
+            let used = estimate_tokens(&join_prompt_parts(&parts)) + reserved_tokens;
+            if used <= self.max_tokens {
+                break;
+            }
+            let Some(index) = parts.iter().position(|part| part.kind == kind) else {
+                continue;
+            };
+            let over = used - self.max_tokens;
+            let kept = match self.truncation {
+                PromptTruncation::Trim => {
+                    let max_chars = parts[index]
+                        .text
+                        .chars()
+                        .count()
+                        .saturating_sub(over * CHARS_PER_TOKEN);
+                    leading_lines(&parts[index].text, max_chars)
+                }
+                PromptTruncation::Drop => None,
+            };
+            match kept {
+                Some(text) => {
+                    log::info!("Trimmed {:?} prompt section to fit the token budget", kind);
+                    parts[index].text = text;
+                }
+                None => {
+                    log::info!("Dropped {:?} prompt section to fit the token budget", kind);
+                    parts.remove(index);
+                }
+            }
+        }
+
+        let prompt = join_prompt_parts(&parts);
+        if estimate_tokens(&prompt) + reserved_tokens > self.max_tokens {
+            log::warn!(
+                "Formatting prompt is still over the {} token budget",
+                self.max_tokens
+            );
+        }
+        prompt
+    }
+}
+
+/// The leading whole lines of `text` that fit in `max_chars`, or `None` if no line fits
+fn leading_lines(text: &str, max_chars: usize) -> Option<String> {
+    let mut kept = Vec::new();
+    let mut chars = 0;
+    for line in text.lines() {
+        // Every line after the first adds a newline
+        let line_chars = line.chars().count() + usize::from(!kept.is_empty());
+        if chars + line_chars > max_chars {
+            break;
+        }
+        chars += line_chars;
+        kept.push(line);
+    }
+    let kept = kept.join("\n");
+    (!kept.trim().is_empty()).then_some(kept)
+}
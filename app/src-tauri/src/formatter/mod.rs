@@ -0,0 +1,294 @@
+mod budget;
+mod ollama;
+mod preview;
+mod rules;
+mod server;
+mod style;
+
+use crate::active_window::get_active_app;
+use crate::commands::active_window::recording_app;
+use crate::settings::{get_setting_from_store, CleanupPromptSections, DEFAULT_MAX_PROMPT_TOKENS};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+pub use budget::{
+    estimate_tokens, join_prompt_parts, PromptBudget, PromptPart, PromptPartKind, PromptTruncation,
+};
+pub use ollama::{parse_ollama_chunk, OllamaFormatter};
+pub use preview::FormatPreviews;
+pub use rules::{format_with_rules, RulesFormatter};
+pub use server::ServerFormatter;
+pub use style::{resolve_output_style, OutputCasing, OutputStyle, OutputStyleRule, QuoteStyle};
+
+/// Default formatter order when none is configured
+pub const DEFAULT_FORMATTER_ORDER: &[&str] = &["server", "rules"];
+
+/// Default Ollama endpoint and model for direct local formatting
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+pub const DEFAULT_OLLAMA_MODEL: &str = "llama3.2";
+
+/// Built-in prompt used when formatting outside the server without a custom main section
+pub const DEFAULT_FORMAT_PROMPT: &str = "You are a dictation formatting assistant. \
+Format the transcribed speech: remove filler words (um, uh, err, erm), add punctuation, \
+and capitalize sentences properly. Keep the original meaning, tone and wording intact. \
+Do not summarize, answer questions or respond conversationally. \
+Output only the cleaned text, with no explanations, quotes or prefixes.";
+
+/// Formatted text streamed so far, emitted as `formatting-partial`. Empty once
+/// formatting is done.
+#[derive(Debug, Clone, Serialize)]
+pub struct FormattingPartial {
+    pub text: String,
+}
+
+/// Information about the turn being formatted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormatContext {
+    /// Name of the app the text will be inserted into, if known
+    pub active_app: Option<String>,
+}
+
+/// Turns raw transcribed text into formatted text.
+pub trait Formatter: Send + Sync {
+    /// Short identifier used in settings and logs
+    fn name(&self) -> &'static str;
+
+    /// Whether the formatter can be used right now (e.g. its backend is connected)
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// Format `raw`, passing text to `on_text` as it streams in.
+    /// Returns the full formatted text, or `None` when the formatter delivers it
+    /// asynchronously (the server path sends it back through the overlay).
+    fn format(
+        &self,
+        raw: &str,
+        context: &FormatContext,
+        prompt: &str,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<Option<String>, String>;
+
+    /// Format `raw` and return the result without inserting it anywhere
+    fn preview(&self, raw: &str, context: &FormatContext, prompt: &str) -> Result<String, String> {
+        self.format(raw, context, prompt, &mut |_| {})?
+            .ok_or_else(|| format!("{} cannot preview formatting", self.name()))
+    }
+}
+
+/// Tries formatters in order, falling through to the next on failure or unavailability
+pub struct FormatterChain {
+    formatters: Vec<Box<dyn Formatter>>,
+}
+
+impl FormatterChain {
+    pub fn new(formatters: Vec<Box<dyn Formatter>>) -> Self {
+        Self { formatters }
+    }
+
+    pub fn format(
+        &self,
+        raw: &str,
+        context: &FormatContext,
+        prompt: &str,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<Option<String>, String> {
+        let mut last_error = None;
+        for formatter in &self.formatters {
+            if !formatter.is_available() {
+                log::debug!("Formatter {} unavailable, skipping", formatter.name());
+                continue;
+            }
+            match formatter.format(raw, context, prompt, on_text) {
+                Ok(result) => {
+                    log::info!("Formatted with {}", formatter.name());
+                    return Ok(result);
+                }
+                Err(e) => {
+                    log::warn!("Formatter {} failed: {}", formatter.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| "No formatter available".to_string()))
+    }
+
+    /// Like [`FormatterChain::format`], but always returns the text instead of inserting it
+    pub fn preview(
+        &self,
+        raw: &str,
+        context: &FormatContext,
+        prompt: &str,
+    ) -> Result<String, String> {
+        let mut last_error = None;
+        for formatter in &self.formatters {
+            if !formatter.is_available() {
+                continue;
+            }
+            match formatter.preview(raw, context, prompt) {
+                Ok(text) => {
+                    log::info!("Previewed formatting with {}", formatter.name());
+                    return Ok(text);
+                }
+                Err(e) => {
+                    log::warn!("Formatter {} preview failed: {}", formatter.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| "No formatter available".to_string()))
+    }
+}
+
+/// Combine the custom prompt sections into a system prompt for in-app formatters.
+/// Default section text lives on the server, so only the main section has a local fallback.
+pub fn build_format_prompt(sections: Option<&CleanupPromptSections>) -> String {
+    join_prompt_parts(&format_prompt_parts(sections))
+}
+
+/// The enabled prompt sections, in the order they appear in the system prompt
+pub fn format_prompt_parts(sections: Option<&CleanupPromptSections>) -> Vec<PromptPart> {
+    let custom = |content: &Option<String>| {
+        content
+            .as_deref()
+            .map(str::trim)
+            .filter(|content| !content.is_empty())
+            .map(String::from)
+    };
+
+    let Some(sections) = sections else {
+        return vec![PromptPart::new(
+            PromptPartKind::Instructions,
+            DEFAULT_FORMAT_PROMPT,
+        )];
+    };
+
+    let mut parts = vec![PromptPart::new(
+        PromptPartKind::Instructions,
+        custom(&sections.main.content).unwrap_or_else(|| DEFAULT_FORMAT_PROMPT.to_string()),
+    )];
+    for (kind, section) in [
+        (PromptPartKind::Advanced, &sections.advanced),
+        (PromptPartKind::Dictionary, &sections.dictionary),
+    ] {
+        if section.enabled {
+            parts.extend(custom(&section.content).map(|text| PromptPart::new(kind, text)));
+        }
+    }
+    parts
+}
+
+/// Read the prompt size limit from the `max_prompt_tokens` and `prompt_truncation` settings
+pub fn prompt_budget_from_settings(app: &AppHandle) -> PromptBudget {
+    PromptBudget {
+        max_tokens: get_setting_from_store(app, "max_prompt_tokens", DEFAULT_MAX_PROMPT_TOKENS),
+        truncation: get_setting_from_store(app, "prompt_truncation", PromptTruncation::default()),
+    }
+}
+
+/// The system prompt for `raw` from the current settings, within the prompt budget
+fn prompt_from_settings(app: &AppHandle, raw: &str) -> String {
+    let sections: Option<CleanupPromptSections> =
+        get_setting_from_store(app, "cleanup_prompt_sections", None);
+    prompt_budget_from_settings(app)
+        .fit(format_prompt_parts(sections.as_ref()), estimate_tokens(raw))
+}
+
+/// Build the formatter chain from the `formatter_order` setting, skipping unknown names
+pub fn formatter_chain_from_settings(app: &AppHandle) -> FormatterChain {
+    let order: Vec<String> = get_setting_from_store(
+        app,
+        "formatter_order",
+        DEFAULT_FORMATTER_ORDER
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+    );
+
+    let formatters = order
+        .iter()
+        .filter_map(|name| -> Option<Box<dyn Formatter>> {
+            match name.as_str() {
+                "server" => Some(Box::new(ServerFormatter::new(app.clone()))),
+                "ollama" => Some(Box::new(OllamaFormatter::new(
+                    get_setting_from_store(app, "ollama_url", DEFAULT_OLLAMA_URL.to_string()),
+                    get_setting_from_store(app, "ollama_model", DEFAULT_OLLAMA_MODEL.to_string()),
+                ))),
+                "rules" => Some(Box::new(RulesFormatter)),
+                other => {
+                    log::warn!("Unknown formatter in settings: {}", other);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    FormatterChain::new(formatters)
+}
+
+/// Apply the `output_style` setting, or the `output_style_rules` entry for the
+/// focused app, to formatted text
+pub fn apply_output_style(app: &AppHandle, text: &str) -> String {
+    let default: OutputStyle = get_setting_from_store(app, "output_style", OutputStyle::default());
+    let rules: Vec<OutputStyleRule> = get_setting_from_store(app, "output_style_rules", Vec::new());
+    let style = if rules.is_empty() {
+        default
+    } else {
+        resolve_output_style(&rules, get_active_app().as_ref(), default)
+    };
+    style.apply(text)
+}
+
+/// Whether transcripts are formatted, from the `formatting_enabled` setting. Turned
+/// off when speed matters more than polish.
+pub fn formatting_enabled(app: &AppHandle) -> bool {
+    get_setting_from_store(app, "formatting_enabled", true)
+}
+
+/// Format a transcript produced in-app using the configured formatter chain, or
+/// return it as transcribed when formatting is off
+pub fn format_transcript(app: &AppHandle, raw: &str) -> Result<Option<String>, String> {
+    if !formatting_enabled(app) {
+        log::info!("Formatting is off, skipping the formatter chain");
+        return Ok(Some(raw.to_string()));
+    }
+    let chain = formatter_chain_from_settings(app);
+    let context = FormatContext {
+        active_app: recording_app(app).map(|target| target.app_name),
+    };
+    let prompt = prompt_from_settings(app, raw);
+
+    // Insertion happens once the full text is available, but the overlay shows it
+    // as it streams in
+    let mut streamed = String::new();
+    let result = chain.format(raw, &context, &prompt, &mut |text| {
+        streamed.push_str(text);
+        let _ = app.emit(
+            "formatting-partial",
+            FormattingPartial {
+                text: streamed.clone(),
+            },
+        );
+    });
+    if !streamed.is_empty() {
+        let _ = app.emit(
+            "formatting-partial",
+            FormattingPartial {
+                text: String::new(),
+            },
+        );
+    }
+    result
+}
+
+/// Format a transcript with the current settings and return the result without
+/// inserting it, e.g. to compare against an earlier result
+pub fn preview_transcript(
+    app: &AppHandle,
+    raw: &str,
+    active_app: Option<String>,
+) -> Result<String, String> {
+    let chain = formatter_chain_from_settings(app);
+    let prompt = prompt_from_settings(app, raw);
+    chain.preview(raw, &FormatContext { active_app }, &prompt)
+}
@@ -0,0 +1,109 @@
+use super::{FormatContext, Formatter};
+use serde::Deserialize;
+use serde_json::json;
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+/// Timeout for a complete Ollama formatting request
+const OLLAMA_TIMEOUT_SECS: u64 = 60;
+
+/// Formatting with a local Ollama model, talking to its HTTP API directly
+pub struct OllamaFormatter {
+    base_url: String,
+    model: String,
+}
+
+impl OllamaFormatter {
+    pub fn new(base_url: String, model: String) -> Self {
+        Self { base_url, model }
+    }
+}
+
+impl Formatter for OllamaFormatter {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn format(
+        &self,
+        raw: &str,
+        context: &FormatContext,
+        prompt: &str,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<Option<String>, String> {
+        let system_prompt = match &context.active_app {
+            Some(app_name) => format!("{}\n\nThe text will be typed into {}.", prompt, app_name),
+            None => prompt.to_string(),
+        };
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(OLLAMA_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let url = format!("{}/api/chat", self.base_url.trim_end_matches('/'));
+        let response = client
+            .post(&url)
+            .json(&json!({
+                "model": self.model,
+                "stream": true,
+                "messages": [
+                    { "role": "system", "content": system_prompt },
+                    { "role": "user", "content": raw },
+                ],
+            }))
+            .send()
+            .map_err(|e| format!("Ollama request failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("Ollama returned an error: {}", e))?;
+
+        // Ollama streams newline-delimited JSON objects
+        let mut formatted = String::new();
+        for line in BufReader::new(response).lines() {
+            let line = line.map_err(|e| format!("Failed to read Ollama response: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (content, done) = parse_ollama_chunk(&line)?;
+            if !content.is_empty() {
+                on_text(&content);
+                formatted.push_str(&content);
+            }
+            if done {
+                break;
+            }
+        }
+
+        let formatted = formatted.trim().to_string();
+        if formatted.is_empty() {
+            return Err("Ollama returned an empty response".to_string());
+        }
+        Ok(Some(formatted))
+    }
+}
+
+#[derive(Deserialize)]
+struct OllamaChunk {
+    #[serde(default)]
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaMessage {
+    #[serde(default)]
+    content: String,
+}
+
+/// Parse one line of Ollama's streaming chat response into (content, done)
+pub fn parse_ollama_chunk(line: &str) -> Result<(String, bool), String> {
+    let chunk: OllamaChunk =
+        serde_json::from_str(line).map_err(|e| format!("Invalid Ollama response: {}", e))?;
+    if let Some(error) = chunk.error {
+        return Err(format!("Ollama error: {}", error));
+    }
+    let content = chunk.message.map(|m| m.content).unwrap_or_default();
+    Ok((content, chunk.done))
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex};
+
+/// Formatting previews waiting for a result from the overlay.
+///
+/// Server formatting results normally come back through the overlay and get
+/// inserted. A preview registers here first, so the overlay can hand its result
+/// back to the waiting caller instead.
+#[derive(Default)]
+pub struct FormatPreviews {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, mpsc::Sender<String>>>,
+}
+
+impl FormatPreviews {
+    /// Start waiting for a preview, returning its ID and where the result will arrive
+    pub fn register(&self) -> (u64, mpsc::Receiver<String>) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = mpsc::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id, sender);
+        }
+        (id, receiver)
+    }
+
+    /// Deliver the result for a preview. Returns false if nobody is waiting for it.
+    pub fn complete(&self, id: u64, text: String) -> bool {
+        let sender = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(&id));
+        sender.is_some_and(|sender| sender.send(text).is_ok())
+    }
+
+    /// Stop waiting for a preview, e.g. after a timeout
+    pub fn cancel(&self, id: u64) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&id);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use super::{FormatContext, Formatter};
+
+/// Filler words dropped by the rule-based formatter
+const FILLER_WORDS: &[&str] = &["um", "uh", "erm", "err", "hmm"];
+
+/// Characters that already end a sentence, so no period needs to be appended
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?'];
+
+/// Offline fallback that applies simple cleanup rules without an LLM
+pub struct RulesFormatter;
+
+impl Formatter for RulesFormatter {
+    fn name(&self) -> &'static str {
+        "rules"
+    }
+
+    fn format(
+        &self,
+        raw: &str,
+        _context: &FormatContext,
+        _prompt: &str,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<Option<String>, String> {
+        let formatted = format_with_rules(raw);
+        on_text(&formatted);
+        Ok(Some(formatted))
+    }
+}
+
+/// Remove filler words, collapse whitespace, capitalize the first letter and end
+/// with a period if there is no terminal punctuation. Mirrors the server's
+/// short-utterance cleanup.
+pub fn format_with_rules(raw: &str) -> String {
+    let words: Vec<&str> = raw
+        .split_whitespace()
+        .filter(|word| !is_filler_word(word))
+        .collect();
+    let cleaned = words.join(" ");
+    let cleaned = cleaned.trim_matches(|c: char| c == ' ' || c == ',');
+    if cleaned.is_empty() {
+        return String::new();
+    }
+
+    let mut chars = cleaned.chars();
+    let mut formatted: String = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default();
+    if !formatted.ends_with(SENTENCE_TERMINATORS) {
+        formatted.push('.');
+    }
+    formatted
+}
+
+/// Match "um", "ummm", "uh," etc. (repeated final letter, optional trailing comma/period)
+fn is_filler_word(word: &str) -> bool {
+    let word = word.trim_end_matches([',', '.']).to_lowercase();
+    FILLER_WORDS.iter().any(|filler| {
+        word.strip_prefix(filler).is_some_and(|rest| {
+            let last = filler.chars().last();
+            rest.chars().all(|c| Some(c) == last)
+        })
+    })
+}
@@ -0,0 +1,93 @@
+use super::{FormatContext, FormatPreviews, Formatter};
+use crate::state::AppState;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How long to wait for the server to return a formatting preview
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Request for the overlay to send raw text to the server for formatting
+#[derive(Debug, Clone, Serialize)]
+struct FormatTextRequest<'a> {
+    text: &'a str,
+    /// Set when the result should be returned to Rust instead of inserted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview_id: Option<u64>,
+}
+
+/// Formatting with the server's LLM pipeline.
+///
+/// The overlay owns the server connection, so the raw text is handed to it and the
+/// formatted result comes back through the usual bot-llm-text flow, which inserts it.
+pub struct ServerFormatter {
+    app: AppHandle,
+}
+
+impl ServerFormatter {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl Formatter for ServerFormatter {
+    fn name(&self) -> &'static str {
+        "server"
+    }
+
+    fn is_available(&self) -> bool {
+        self.app
+            .state::<AppState>()
+            .server_connected
+            .load(Ordering::SeqCst)
+    }
+
+    fn format(
+        &self,
+        raw: &str,
+        _context: &FormatContext,
+        _prompt: &str,
+        _on_text: &mut dyn FnMut(&str),
+    ) -> Result<Option<String>, String> {
+        // The server applies its own prompt, synced from settings by the overlay
+        self.app
+            .emit(
+                "format-text-request",
+                FormatTextRequest {
+                    text: raw,
+                    preview_id: None,
+                },
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(None)
+    }
+
+    fn preview(
+        &self,
+        raw: &str,
+        _context: &FormatContext,
+        _prompt: &str,
+    ) -> Result<String, String> {
+        // The overlay hands the result back through `complete_format_preview`
+        let previews = self.app.state::<FormatPreviews>();
+        let (preview_id, receiver) = previews.register();
+        let result = self
+            .app
+            .emit(
+                "format-text-request",
+                FormatTextRequest {
+                    text: raw,
+                    preview_id: Some(preview_id),
+                },
+            )
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                receiver
+                    .recv_timeout(PREVIEW_TIMEOUT)
+                    .map_err(|_| "Server did not return a formatting preview in time".to_string())
+            });
+        previews.cancel(preview_id);
+        result
+    }
+}
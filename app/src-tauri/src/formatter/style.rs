@@ -0,0 +1,169 @@
+use crate::active_window::ActiveApp;
+use serde::{Deserialize, Serialize};
+
+/// Punctuation removed from the end of a dictation when trailing punctuation is off
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?'];
+
+/// Characters that end a sentence, after which sentence case capitalizes
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?'];
+
+/// Characters after which a quote opens rather than closes
+const QUOTE_OPENERS: &[char] = &['(', '[', '{', '\u{2014}', '\u{2013}', '-', '/'];
+
+/// Letter case applied to formatted text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputCasing {
+    /// Keep the casing the formatter produced
+    #[default]
+    AsFormatted,
+    /// Lowercase everything, as in chat
+    Lowercase,
+    /// Capitalize the first letter of each sentence. Other letters are left alone
+    /// so names keep their capitals.
+    SentenceCase,
+}
+
+/// How quotation marks and apostrophes are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    /// Keep the quotes the formatter produced
+    #[default]
+    Unchanged,
+    /// Curly quotes and apostrophes, as in documents
+    Smart,
+    /// Straight quotes, as in code
+    Straight,
+}
+
+/// Deterministic formatting applied to every dictation after the formatter, so it
+/// holds whether or not an LLM was available
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputStyle {
+    pub casing: OutputCasing,
+    /// Remove punctuation from the end of the dictation
+    pub strip_trailing_punctuation: bool,
+    pub quotes: QuoteStyle,
+}
+
+/// Per-app output style, e.g. lowercase without trailing periods in chat apps
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputStyleRule {
+    /// App name or executable pattern (case-insensitive substring match)
+    pub app: String,
+    pub style: OutputStyle,
+}
+
+/// Find the output style for the given app, falling back to `default`
+pub fn resolve_output_style(
+    rules: &[OutputStyleRule],
+    app: Option<&ActiveApp>,
+    default: OutputStyle,
+) -> OutputStyle {
+    app.and_then(|app| rules.iter().find(|rule| app.matches(&rule.app)))
+        .map(|rule| rule.style)
+        .unwrap_or(default)
+}
+
+impl OutputStyle {
+    /// Apply the style to formatted text
+    pub fn apply(&self, text: &str) -> String {
+        let mut styled = match self.casing {
+            OutputCasing::AsFormatted => text.to_string(),
+            OutputCasing::Lowercase => text.to_lowercase(),
+            OutputCasing::SentenceCase => sentence_case(text),
+        };
+        styled = match self.quotes {
+            QuoteStyle::Unchanged => styled,
+            QuoteStyle::Smart => smart_quotes(&styled),
+            QuoteStyle::Straight => straight_quotes(&styled),
+        };
+        if self.strip_trailing_punctuation {
+            let trimmed = styled.trim_end().trim_end_matches(TRAILING_PUNCTUATION);
+            styled.truncate(trimmed.len());
+        }
+        styled
+    }
+}
+
+/// Capitalize the first letter of the text, of each line and of each sentence. A
+/// period only ends a sentence when whitespace follows and the word isn't an
+/// abbreviation, so "3.5" and "e.g." don't.
+fn sentence_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut capitalize_next = true;
+    let mut word_start = 0;
+    for c in text.chars() {
+        if c == '\n' {
+            capitalize_next = true;
+        } else if c.is_whitespace() {
+            if ends_sentence(&result[word_start..]) {
+                capitalize_next = true;
+            }
+        } else if capitalize_next && c.is_alphanumeric() {
+            result.extend(c.to_uppercase());
+            capitalize_next = false;
+            continue;
+        }
+        result.push(c);
+        if c.is_whitespace() {
+            word_start = result.len();
+        }
+    }
+    result
+}
+
+/// Dotted letters such as "e.g" or "i.e", before their final period
+fn is_abbreviation(word: &str) -> bool {
+    word.contains('.') && word.chars().all(|c| c.is_alphabetic() || c == '.')
+}
+
+/// Whether a word ends its sentence, ignoring closing quotes and brackets
+fn ends_sentence(word: &str) -> bool {
+    let word = word.trim_end_matches(|c: char| is_quote(c) || c == ')');
+    match word.strip_suffix(SENTENCE_TERMINATORS) {
+        Some(rest) if word.ends_with('.') => !is_abbreviation(rest),
+        Some(_) => true,
+        None => false,
+    }
+}
+
+fn is_quote(c: char) -> bool {
+    matches!(
+        c,
+        '"' | '\'' | '\u{201C}' | '\u{201D}' | '\u{2018}' | '\u{2019}'
+    )
+}
+
+/// Turn straight quotes curly: opening at the start of a word, closing elsewhere.
+/// A single quote inside a word is an apostrophe.
+fn smart_quotes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut previous: Option<char> = None;
+    for c in text.chars() {
+        let opens = previous.is_none_or(|p| p.is_whitespace() || QUOTE_OPENERS.contains(&p));
+        let curly = match c {
+            '"' if opens => '\u{201C}',
+            '"' => '\u{201D}',
+            '\'' if opens => '\u{2018}',
+            '\'' => '\u{2019}',
+            other => other,
+        };
+        result.push(curly);
+        previous = Some(c);
+    }
+    result
+}
+
+/// Turn curly quotes and apostrophes straight
+fn straight_quotes(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{201C}' | '\u{201D}' | '\u{201E}' => '"',
+            '\u{2018}' | '\u{2019}' | '\u{201A}' => '\'',
+            other => other,
+        })
+        .collect()
+}
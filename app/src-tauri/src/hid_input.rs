@@ -0,0 +1,307 @@
+//! Hold-to-record and toggle recording from HID devices such as foot pedals.
+//!
+//! Transcription foot pedals are USB HID devices that report their pedals as bits
+//! of an input report rather than as keys, so they can't be bound as shortcuts.
+//! Each binding names a device interface by vendor, product and HID usage, and a
+//! button by its bit in that interface's input reports. Every bound device is read
+//! on its own thread, which waits for the device while it is unplugged. Pedals
+//! that type keys instead already work as ordinary hotkeys.
+
+use crate::events::{self, AppEvent};
+use crate::settings::get_setting_from_store;
+use crate::trigger_queue::{Trigger, TriggerQueue};
+use hidapi::{DeviceInfo, HidApi, HidDevice};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// How long each read waits for a report before checking for new bindings
+const READ_TIMEOUT_MS: i32 = 250;
+
+/// How often to look for a bound device that isn't connected
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Largest input report read from a device
+const MAX_REPORT_BYTES: usize = 64;
+
+/// HID usage page of generic desktop controls, and the keyboard and mouse usages in it
+const GENERIC_DESKTOP_PAGE: u16 = 0x01;
+const MOUSE_USAGE: u16 = 0x02;
+const KEYBOARD_USAGE: u16 = 0x06;
+
+/// A connected HID device interface that can be bound
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HidDeviceInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub usage_page: u16,
+    pub usage: u16,
+    pub name: String,
+}
+
+/// What pressing a bound button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HidAction {
+    /// Record while the button is held down
+    Hold,
+    /// Start recording on one press and stop on the next
+    Toggle,
+}
+
+/// A button of a HID device bound to a recording action, from the `hid_triggers`
+/// setting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HidBinding {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub usage_page: u16,
+    pub usage: u16,
+    /// Name of the device when it was bound, for display while it is unplugged
+    #[serde(default)]
+    pub name: String,
+    /// Bit in the device's input reports, counting from the first byte's lowest bit
+    pub button: u32,
+    pub action: HidAction,
+}
+
+impl HidBinding {
+    /// The device interface this binding reads from
+    pub fn interface(&self) -> (u16, u16, u16, u16) {
+        (self.vendor_id, self.product_id, self.usage_page, self.usage)
+    }
+}
+
+/// Whether an interface is a keyboard or mouse, which the OS reads already
+pub fn is_keyboard_or_mouse(usage_page: u16, usage: u16) -> bool {
+    usage_page == GENERIC_DESKTOP_PAGE && matches!(usage, MOUSE_USAGE | KEYBOARD_USAGE)
+}
+
+/// Buttons whose bit differs between two input reports, with whether each is now
+/// pressed. A longer report than the previous one counts its new bytes as changed
+/// from all released.
+pub fn changed_buttons(previous: &[u8], report: &[u8]) -> Vec<(u32, bool)> {
+    let mut changes = Vec::new();
+    for (index, byte) in report.iter().enumerate() {
+        let changed = byte ^ previous.get(index).copied().unwrap_or(0);
+        for bit in 0..8 {
+            if changed & (1 << bit) != 0 {
+                changes.push(((index * 8 + bit) as u32, byte & (1 << bit) != 0));
+            }
+        }
+    }
+    changes
+}
+
+fn device_name(info: &DeviceInfo) -> String {
+    match (info.manufacturer_string(), info.product_string()) {
+        (_, Some(product)) if !product.trim().is_empty() => product.trim().to_string(),
+        (Some(manufacturer), _) if !manufacturer.trim().is_empty() => {
+            format!("{} device", manufacturer.trim())
+        }
+        _ => format!("{:04x}:{:04x}", info.vendor_id(), info.product_id()),
+    }
+}
+
+/// Connected HID device interfaces other than keyboards and mice, by name
+pub fn list_devices() -> Result<Vec<HidDeviceInfo>, String> {
+    let api = HidApi::new().map_err(|e| format!("Failed to list HID devices: {}", e))?;
+    let mut devices: Vec<HidDeviceInfo> = Vec::new();
+    for info in api.device_list() {
+        if is_keyboard_or_mouse(info.usage_page(), info.usage()) {
+            continue;
+        }
+        let device = HidDeviceInfo {
+            vendor_id: info.vendor_id(),
+            product_id: info.product_id(),
+            usage_page: info.usage_page(),
+            usage: info.usage(),
+            name: device_name(info),
+        };
+        // Some systems list an interface once per report it has
+        if !devices.contains(&device) {
+            devices.push(device);
+        }
+    }
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(devices)
+}
+
+/// Open the device interface `(vendor, product, usage page, usage)`, if connected
+fn open_interface(interface: (u16, u16, u16, u16)) -> Result<Option<HidDevice>, String> {
+    let api = HidApi::new().map_err(|e| format!("Failed to list HID devices: {}", e))?;
+    let Some(info) = api.device_list().find(|info| {
+        (
+            info.vendor_id(),
+            info.product_id(),
+            info.usage_page(),
+            info.usage(),
+        ) == interface
+    }) else {
+        return Ok(None);
+    };
+    info.open_device(&api)
+        .map(Some)
+        .map_err(|e| format!("Failed to open {}: {}", device_name(info), e))
+}
+
+/// Wait up to `timeout` for a button of the device interface to be pressed and
+/// return its bit, for binding it
+pub fn detect_button(interface: (u16, u16, u16, u16), timeout: Duration) -> Result<u32, String> {
+    let device =
+        open_interface(interface)?.ok_or_else(|| "The device isn't connected".to_string())?;
+    let deadline = Instant::now() + timeout;
+    let mut previous = Vec::new();
+    let mut report = [0u8; MAX_REPORT_BYTES];
+    while Instant::now() < deadline {
+        let len = device
+            .read_timeout(&mut report, READ_TIMEOUT_MS)
+            .map_err(|e| format!("Failed to read the device: {}", e))?;
+        if len == 0 {
+            continue;
+        }
+        let pressed = changed_buttons(&previous, &report[..len])
+            .into_iter()
+            .find(|(_, pressed)| *pressed);
+        if let Some((button, _)) = pressed {
+            return Ok(button);
+        }
+        previous = report[..len].to_vec();
+    }
+    Err("No button was pressed".to_string())
+}
+
+/// Reads bound HID devices and runs their actions
+#[derive(Default)]
+pub struct HidTriggers {
+    bindings: Mutex<Vec<HidBinding>>,
+    /// Bumped whenever the bindings change, telling readers of the old ones to stop
+    generation: AtomicU64,
+}
+
+impl HidTriggers {
+    /// Re-read the bindings and restart the device readers if they changed
+    pub fn refresh(&self, app: &AppHandle) {
+        let bindings: Vec<HidBinding> = get_setting_from_store(app, "hid_triggers", Vec::new());
+        let Ok(mut current) = self.bindings.lock() else {
+            return;
+        };
+        if *current == bindings {
+            return;
+        }
+        *current = bindings.clone();
+        drop(current);
+        self.start(app, bindings);
+    }
+
+    /// Stop reading every device, e.g. while a button is being detected, returning
+    /// once the readers have let go of their devices. Readers start again on the
+    /// next [`HidTriggers::refresh`].
+    pub fn stop(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut current) = self.bindings.lock() {
+            current.clear();
+        }
+        std::thread::sleep(Duration::from_millis(READ_TIMEOUT_MS as u64));
+    }
+
+    fn start(&self, app: &AppHandle, bindings: Vec<HidBinding>) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut interfaces: Vec<(u16, u16, u16, u16)> =
+            bindings.iter().map(HidBinding::interface).collect();
+        interfaces.sort_unstable();
+        interfaces.dedup();
+        for interface in interfaces {
+            let device_bindings: Vec<HidBinding> = bindings
+                .iter()
+                .filter(|binding| binding.interface() == interface)
+                .cloned()
+                .collect();
+            let app = app.clone();
+            std::thread::spawn(move || read_device(&app, generation, interface, device_bindings));
+        }
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+}
+
+/// Read a device interface until the bindings change, reconnecting whenever it
+/// is unplugged
+fn read_device(
+    app: &AppHandle,
+    generation: u64,
+    interface: (u16, u16, u16, u16),
+    bindings: Vec<HidBinding>,
+) {
+    let triggers = app.state::<HidTriggers>();
+    let name = &bindings[0].name;
+    let mut was_connected = true;
+    while triggers.is_current(generation) {
+        let device = match open_interface(interface) {
+            Ok(Some(device)) => device,
+            Ok(None) => {
+                if was_connected {
+                    log::info!("Waiting for HID device {} to be connected", name);
+                    was_connected = false;
+                }
+                std::thread::sleep(RECONNECT_INTERVAL);
+                continue;
+            }
+            Err(e) => {
+                log::warn!("{}", e);
+                std::thread::sleep(RECONNECT_INTERVAL);
+                continue;
+            }
+        };
+        log::info!("Reading HID device {}", name);
+        was_connected = true;
+
+        let mut previous = Vec::new();
+        let mut report = [0u8; MAX_REPORT_BYTES];
+        while triggers.is_current(generation) {
+            let len = match device.read_timeout(&mut report, READ_TIMEOUT_MS) {
+                Ok(len) => len,
+                Err(e) => {
+                    log::info!("HID device {} disconnected: {}", name, e);
+                    break;
+                }
+            };
+            if len == 0 {
+                continue;
+            }
+            for (button, pressed) in changed_buttons(&previous, &report[..len]) {
+                for binding in bindings.iter().filter(|binding| binding.button == button) {
+                    run_action(app, binding.action, pressed);
+                }
+            }
+            previous = report[..len].to_vec();
+        }
+    }
+}
+
+fn run_action(app: &AppHandle, action: HidAction, pressed: bool) {
+    // Handle off the reader thread so slow recording setup doesn't delay the release
+    let trigger = match action {
+        HidAction::Hold => Trigger::Hold {
+            pressed,
+            source: "Hid",
+        },
+        HidAction::Toggle if pressed => Trigger::Toggle { source: "Hid" },
+        HidAction::Toggle => return,
+    };
+    app.state::<TriggerQueue>().send(trigger);
+}
+
+/// Start reading the bound devices and follow changes to the bindings
+pub fn init(app: &AppHandle) {
+    app.state::<HidTriggers>().refresh(app);
+    events::spawn_subscriber(app, "hid-triggers", |app, event| {
+        if event == AppEvent::SettingsChanged {
+            app.state::<HidTriggers>().refresh(app);
+        }
+    });
+}
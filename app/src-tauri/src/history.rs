@@ -1,146 +1,454 @@
+//! Persistent dictation history.
+//!
+//! Every finalized transcription is stored in a SQLite database in the app data
+//! directory along with the app it was dictated into, the STT provider, the
+//! recording length, the raw transcript and a snapshot of the formatting settings
+//! in effect. Entries from the old `history.json` file are imported once.
+//!
+//! Entries can be pinned to keep dictations that are used again and again
+//! (addresses, boilerplate replies) at hand. Clearing the history keeps them.
+
+use crate::settings::CleanupPromptSections;
 use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::RwLock;
+use std::path::Path;
+use std::sync::Mutex;
 use uuid::Uuid;
 
+/// Default number of entries returned per history page
+pub const DEFAULT_HISTORY_PAGE_SIZE: usize = 50;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS history (
+        id TEXT PRIMARY KEY,
+        timestamp_ms INTEGER NOT NULL,
+        text TEXT NOT NULL,
+        app_name TEXT,
+        provider TEXT,
+        duration_ms INTEGER,
+        raw_text TEXT,
+        snapshot TEXT,
+        pinned INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS history_timestamp ON history (timestamp_ms);
+";
+
+/// Columns added after the first release, with their types, for older databases
+const ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("raw_text", "TEXT"),
+    ("snapshot", "TEXT"),
+    ("pinned", "INTEGER NOT NULL DEFAULT 0"),
+];
+
+const ENTRY_COLUMNS: &str =
+    "id, timestamp_ms, text, app_name, provider, duration_ms, raw_text, snapshot, pinned";
+
+const INSERT_ENTRY: &str = "
+    INSERT OR IGNORE INTO history
+        (id, timestamp_ms, text, app_name, provider, duration_ms, raw_text, snapshot, pinned)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+";
+
+/// Formatting settings in effect when a turn was dictated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnSnapshot {
+    /// LLM provider that formatted the text
+    pub llm_provider: Option<String>,
+    /// Custom formatting prompt sections (`None` means the server defaults)
+    pub prompt_sections: Option<CleanupPromptSections>,
+    /// Fingerprint of every setting that affects formatting, to tell whether a
+    /// re-run uses different settings
+    pub settings_hash: String,
+}
+
 /// A single dictation history entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub id: String,
     pub timestamp: DateTime<Utc>,
     pub text: String,
+    /// Name of the app the text was dictated into
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// STT provider that transcribed the recording
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Length of the recording in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Transcript before formatting, when known
+    #[serde(default)]
+    pub raw_text: Option<String>,
+    /// Formatting settings in effect when the entry was recorded
+    #[serde(default)]
+    pub snapshot: Option<TurnSnapshot>,
+    /// Pinned entries are kept when the history is cleared
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Context recorded alongside a transcription
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryContext {
+    pub app_name: Option<String>,
+    pub provider: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub raw_text: Option<String>,
+    pub snapshot: Option<TurnSnapshot>,
 }
 
 impl HistoryEntry {
-    pub fn new(text: String) -> Self {
+    pub fn new(text: String, context: HistoryContext) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             timestamp: Utc::now(),
             text,
+            app_name: context.app_name,
+            provider: context.provider,
+            duration_ms: context.duration_ms,
+            raw_text: context.raw_text,
+            snapshot: context.snapshot,
+            pinned: false,
         }
     }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let timestamp_ms: i64 = row.get(1)?;
+        let duration_ms: Option<i64> = row.get(5)?;
+        let snapshot: Option<String> = row.get(7)?;
+        Ok(Self {
+            id: row.get(0)?,
+            timestamp: DateTime::from_timestamp_millis(timestamp_ms).unwrap_or_default(),
+            text: row.get(2)?,
+            app_name: row.get(3)?,
+            provider: row.get(4)?,
+            duration_ms: duration_ms.and_then(|ms| u64::try_from(ms).ok()),
+            raw_text: row.get(6)?,
+            // An unreadable snapshot only loses the comparison, not the entry
+            snapshot: snapshot.and_then(|json| serde_json::from_str(&json).ok()),
+            pinned: row.get(8)?,
+        })
+    }
 }
 
-/// Storage for dictation history entries
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct HistoryData {
+/// One page of history entries matching a search, newest first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Number of entries matching the search across all pages
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// Format of the legacy `history.json` file
+#[derive(Debug, Deserialize, Default)]
+struct LegacyHistoryData {
     entries: Vec<HistoryEntry>,
 }
 
-/// Manages loading and saving of dictation history
+/// Manages the dictation history database
 pub struct HistoryStorage {
-    data: RwLock<HistoryData>,
-    file_path: PathBuf,
+    connection: Mutex<Connection>,
 }
 
 impl HistoryStorage {
-    /// Create a new history storage with the given app data directory
-    pub fn new(app_data_dir: PathBuf) -> Self {
-        let file_path = app_data_dir.join("history.json");
+    /// Open (or create) the history database in the app data directory,
+    /// importing any entries left in the legacy JSON file
+    pub fn new(app_data_dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
 
-        // Ensure the directory exists
-        if let Some(parent) = file_path.parent() {
-            let _ = fs::create_dir_all(parent);
+        let connection = Connection::open(app_data_dir.join("history.db"))
+            .map_err(|e| format!("Failed to open history database: {}", e))?;
+        let storage = Self::with_connection(connection)?;
+
+        let legacy_path = app_data_dir.join("history.json");
+        if legacy_path.exists() {
+            storage.import_legacy_file(&legacy_path);
         }
 
-        // Load existing history or use empty
-        let data = Self::load_from_file(&file_path).unwrap_or_default();
+        Ok(storage)
+    }
 
-        Self {
-            data: RwLock::new(data),
-            file_path,
-        }
+    /// History kept in memory only, discarded when dropped
+    pub fn in_memory() -> Result<Self, String> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open history database: {}", e))?;
+        Self::with_connection(connection)
     }
 
-    /// Load history from the JSON file
-    fn load_from_file(file_path: &PathBuf) -> Option<HistoryData> {
-        let content = fs::read_to_string(file_path).ok()?;
-        serde_json::from_str(&content).ok()
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| format!("Failed to create history table: {}", e))?;
+        add_missing_columns(&connection)
+            .map_err(|e| format!("Failed to upgrade history table: {}", e))?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
     }
 
-    /// Save current history to disk
-    fn save(&self) -> Result<(), String> {
-        let data = self
-            .data
-            .read()
-            .map_err(|e| format!("Failed to read history: {}", e))?;
+    /// Import entries from the legacy JSON file, then rename it so it is only imported once
+    fn import_legacy_file(&self, path: &Path) {
+        let entries = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<LegacyHistoryData>(&content).ok())
+            .map(|data| data.entries)
+            .unwrap_or_default();
 
-        let content = serde_json::to_string_pretty(&*data)
-            .map_err(|e| format!("Failed to serialize history: {}", e))?;
+        match self.import_entries(&entries) {
+            Ok(()) => {
+                log::info!("Imported {} entries from {}", entries.len(), path.display());
+                if let Err(e) = fs::rename(path, path.with_extension("json.imported")) {
+                    log::warn!("Failed to rename legacy history file: {}", e);
+                }
+            }
+            Err(e) => log::error!("Failed to import legacy history: {}", e),
+        }
+    }
 
-        fs::write(&self.file_path, content)
-            .map_err(|e| format!("Failed to write history file: {}", e))?;
+    /// Insert existing entries, skipping any whose ID is already stored
+    pub fn import_entries(&self, entries: &[HistoryEntry]) -> Result<(), String> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction()
+            .map_err(|e| format!("Failed to start import: {}", e))?;
+        for entry in entries {
+            insert_entry(&transaction, entry)
+                .map_err(|e| format!("Failed to import history entry: {}", e))?;
+        }
+        transaction
+            .commit()
+            .map_err(|e| format!("Failed to finish import: {}", e))
+    }
 
-        Ok(())
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.connection
+            .lock()
+            .map_err(|e| format!("Failed to access history: {}", e))
     }
 
     /// Add a new entry to the history
-    pub fn add_entry(&self, text: String) -> Result<HistoryEntry, String> {
-        let entry = HistoryEntry::new(text);
-        {
-            let mut data = self
-                .data
-                .write()
-                .map_err(|e| format!("Failed to write history: {}", e))?;
-
-            // Add to the beginning (newest first)
-            data.entries.insert(0, entry.clone());
-
-            // Limit to 500 entries
-            if data.entries.len() > 500 {
-                data.entries.truncate(500);
-            }
-        }
-        self.save()?;
+    pub fn add_entry(&self, text: String, context: HistoryContext) -> Result<HistoryEntry, String> {
+        let entry = HistoryEntry::new(text, context);
+        insert_entry(&*self.lock()?, &entry)
+            .map_err(|e| format!("Failed to write history: {}", e))?;
         Ok(entry)
     }
 
-    /// Get all history entries (newest first), optionally limited
-    pub fn get_all(&self, limit: Option<usize>) -> Result<Vec<HistoryEntry>, String> {
-        let data = self
-            .data
-            .read()
+    /// The entry with the given ID, if any
+    pub fn get(&self, id: &str) -> Result<Option<HistoryEntry>, String> {
+        self.lock()?
+            .query_row(
+                &format!("SELECT {} FROM history WHERE id = ?1", ENTRY_COLUMNS),
+                [id],
+                HistoryEntry::from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read history: {}", e))
+    }
+
+    /// The most recent entry, if any
+    pub fn latest(&self) -> Result<Option<HistoryEntry>, String> {
+        self.nth_latest(0)
+    }
+
+    /// The entry `index` entries back from the most recent (0 is the most recent), if any
+    pub fn nth_latest(&self, index: usize) -> Result<Option<HistoryEntry>, String> {
+        self.lock()?
+            .query_row(
+                &format!(
+                    "SELECT {} FROM history ORDER BY timestamp_ms DESC, rowid DESC LIMIT 1 OFFSET ?1",
+                    ENTRY_COLUMNS
+                ),
+                [to_sql_int(index)],
+                HistoryEntry::from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read history: {}", e))
+    }
+
+    /// Get a page of entries (newest first) whose text or app name contains `query`,
+    /// ignoring case. Pages are numbered from 0.
+    pub fn get_page(
+        &self,
+        page: usize,
+        page_size: usize,
+        query: Option<&str>,
+    ) -> Result<HistoryPage, String> {
+        let pattern = like_pattern(query.unwrap_or_default().trim());
+        let filter = "text LIKE ?1 ESCAPE '\\' OR app_name LIKE ?1 ESCAPE '\\'";
+        let connection = self.lock()?;
+
+        let total: i64 = connection
+            .query_row(
+                &format!("SELECT COUNT(*) FROM history WHERE {}", filter),
+                [&pattern],
+                |row| row.get(0),
+            )
             .map_err(|e| format!("Failed to read history: {}", e))?;
 
-        let entries = match limit {
-            Some(n) => data.entries.iter().take(n).cloned().collect(),
-            None => data.entries.clone(),
-        };
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE {}
+                 ORDER BY timestamp_ms DESC, rowid DESC LIMIT ?2 OFFSET ?3",
+                ENTRY_COLUMNS, filter
+            ))
+            .map_err(|e| format!("Failed to read history: {}", e))?;
+        let entries = statement
+            .query_map(
+                params![
+                    pattern,
+                    to_sql_int(page_size),
+                    to_sql_int(page.saturating_mul(page_size))
+                ],
+                HistoryEntry::from_row,
+            )
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to read history: {}", e))?;
 
-        Ok(entries)
+        Ok(HistoryPage {
+            entries,
+            total: usize::try_from(total).unwrap_or_default(),
+            page,
+            page_size,
+        })
     }
 
-    /// Delete an entry by ID
-    pub fn delete(&self, id: &str) -> Result<bool, String> {
-        let deleted = {
-            let mut data = self
-                .data
-                .write()
-                .map_err(|e| format!("Failed to write history: {}", e))?;
-
-            let initial_len = data.entries.len();
-            data.entries.retain(|e| e.id != id);
-            data.entries.len() < initial_len
-        };
-
-        if deleted {
-            self.save()?;
-        }
+    /// Every entry recorded at or after `since` (all entries for `None`), oldest first
+    pub fn entries_since(&self, since: Option<DateTime<Utc>>) -> Result<Vec<HistoryEntry>, String> {
+        let since_ms = since.map_or(i64::MIN, |since| since.timestamp_millis());
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE timestamp_ms >= ?1
+                 ORDER BY timestamp_ms, rowid",
+                ENTRY_COLUMNS
+            ))
+            .map_err(|e| format!("Failed to read history: {}", e))?;
+        statement
+            .query_map([since_ms], HistoryEntry::from_row)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to read history: {}", e))
+    }
+
+    /// Replace an entry's text, e.g. after the user corrected it. The raw transcript
+    /// is kept. Returns false if there is no entry with that ID.
+    pub fn update_text(&self, id: &str, text: &str) -> Result<bool, String> {
+        let updated = self
+            .lock()?
+            .execute("UPDATE history SET text = ?2 WHERE id = ?1", [id, text])
+            .map_err(|e| format!("Failed to write history: {}", e))?;
+        Ok(updated > 0)
+    }
+
+    /// Pin or unpin an entry. Returns false if there is no entry with that ID.
+    pub fn set_pinned(&self, id: &str, pinned: bool) -> Result<bool, String> {
+        let updated = self
+            .lock()?
+            .execute(
+                "UPDATE history SET pinned = ?2 WHERE id = ?1",
+                params![id, pinned],
+            )
+            .map_err(|e| format!("Failed to write history: {}", e))?;
+        Ok(updated > 0)
+    }
+
+    /// Every pinned entry, newest first
+    pub fn pinned(&self) -> Result<Vec<HistoryEntry>, String> {
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE pinned != 0
+                 ORDER BY timestamp_ms DESC, rowid DESC",
+                ENTRY_COLUMNS
+            ))
+            .map_err(|e| format!("Failed to read history: {}", e))?;
+        statement
+            .query_map([], HistoryEntry::from_row)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to read history: {}", e))
+    }
 
-        Ok(deleted)
+    /// Delete an entry by ID, pinned or not
+    pub fn delete(&self, id: &str) -> Result<bool, String> {
+        let deleted = self
+            .lock()?
+            .execute("DELETE FROM history WHERE id = ?1", [id])
+            .map_err(|e| format!("Failed to write history: {}", e))?;
+        Ok(deleted > 0)
     }
 
-    /// Clear all history
+    /// Clear the history, keeping pinned entries
     pub fn clear(&self) -> Result<(), String> {
-        {
-            let mut data = self
-                .data
-                .write()
-                .map_err(|e| format!("Failed to write history: {}", e))?;
-            data.entries.clear();
+        self.lock()?
+            .execute("DELETE FROM history WHERE pinned = 0", [])
+            .map_err(|e| format!("Failed to write history: {}", e))?;
+        Ok(())
+    }
+}
+
+fn insert_entry(connection: &Connection, entry: &HistoryEntry) -> rusqlite::Result<usize> {
+    connection.execute(
+        INSERT_ENTRY,
+        params![
+            entry.id,
+            entry.timestamp.timestamp_millis(),
+            entry.text,
+            entry.app_name,
+            entry.provider,
+            entry.duration_ms.map(to_sql_int),
+            entry.raw_text,
+            entry
+                .snapshot
+                .as_ref()
+                .and_then(|snapshot| serde_json::to_string(snapshot).ok()),
+            entry.pinned,
+        ],
+    )
+}
+
+/// Add columns introduced after a database was created
+fn add_missing_columns(connection: &Connection) -> rusqlite::Result<()> {
+    let existing = connection
+        .prepare("SELECT name FROM pragma_table_info('history')")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (name, column_type) in ADDED_COLUMNS {
+        if !existing.iter().any(|column| column == name) {
+            connection.execute_batch(&format!(
+                "ALTER TABLE history ADD COLUMN {} {}",
+                name, column_type
+            ))?;
         }
-        self.save()
     }
+    Ok(())
+}
+
+/// Stable fingerprint of a settings value (FNV-1a over its JSON), as 16 hex digits.
+/// Build the value with a fixed key order so equal settings always match.
+pub fn settings_hash(settings: &serde_json::Value) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in settings.to_string().bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// SQLite integers are signed, so clamp sizes that don't fit
+fn to_sql_int(value: impl TryInto<i64>) -> i64 {
+    value.try_into().unwrap_or(i64::MAX)
+}
+
+/// Build a LIKE pattern matching `query` anywhere, with wildcards in it taken literally
+fn like_pattern(query: &str) -> String {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
 }
@@ -0,0 +1,98 @@
+//! Hold-to-record with tap-to-latch.
+//!
+//! When enabled, a quick tap of the hold hotkey keeps recording hands-free until
+//! the hotkey is tapped again or the latch times out, while holding the hotkey
+//! still records only for as long as it is held.
+
+use std::time::{Duration, Instant};
+
+/// Presses shorter than this are taps that latch recording instead of holds
+pub const HOLD_LATCH_TAP_THRESHOLD: Duration = Duration::from_millis(300);
+
+/// What the hold hotkey should do after a press or release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatchAction {
+    /// Start recording
+    Start,
+    /// Stop recording
+    Stop,
+    /// Keep recording after the tap; it stops when [`HoldLatch::expire`] is called with
+    /// this latch ID, unless the hotkey is tapped again first
+    Latch(u64),
+    /// Nothing to do (a key repeat, or the release of the tap that ended a latch)
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum LatchPhase {
+    #[default]
+    Idle,
+    /// The hotkey is down and recording started at this time
+    Held { since: Instant },
+    /// Recording continues hands-free after a tap
+    Latched { id: u64 },
+    /// The hotkey was pressed to end a latch and hasn't been released yet
+    Unlatching,
+}
+
+/// Decides between hold-to-record and tap-to-latch from how long the hotkey is held
+#[derive(Debug, Default)]
+pub struct HoldLatch {
+    phase: LatchPhase,
+    next_id: u64,
+}
+
+impl HoldLatch {
+    /// The hotkey went down. OS key repeats while it is held are ignored.
+    pub fn press(&mut self, now: Instant) -> LatchAction {
+        match self.phase {
+            LatchPhase::Idle => {
+                self.phase = LatchPhase::Held { since: now };
+                LatchAction::Start
+            }
+            LatchPhase::Latched { .. } => {
+                self.phase = LatchPhase::Unlatching;
+                LatchAction::Stop
+            }
+            LatchPhase::Held { .. } | LatchPhase::Unlatching => LatchAction::Ignore,
+        }
+    }
+
+    /// The hotkey was released. A release within `tap_threshold` of the press latches
+    /// recording, a later one stops it.
+    pub fn release(&mut self, now: Instant, tap_threshold: Duration) -> LatchAction {
+        match self.phase {
+            LatchPhase::Held { since } if now.duration_since(since) < tap_threshold => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.phase = LatchPhase::Latched { id };
+                LatchAction::Latch(id)
+            }
+            LatchPhase::Held { .. } => {
+                self.phase = LatchPhase::Idle;
+                LatchAction::Stop
+            }
+            LatchPhase::Unlatching => {
+                self.phase = LatchPhase::Idle;
+                LatchAction::Ignore
+            }
+            LatchPhase::Idle | LatchPhase::Latched { .. } => LatchAction::Ignore,
+        }
+    }
+
+    /// The time limit for latch `id` ran out. Returns true if that latch is still
+    /// recording and should be stopped.
+    pub fn expire(&mut self, id: u64) -> bool {
+        if self.phase == (LatchPhase::Latched { id }) {
+            self.phase = LatchPhase::Idle;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forget any latch, e.g. because recording was stopped some other way
+    pub fn reset(&mut self) {
+        self.phase = LatchPhase::Idle;
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+
+/// Process-wide low-level input listener shared by the mouse, double-tap and media key
+/// triggers.
+///
+/// rdev supports a single listener per process, so it is started the first time a
+/// trigger needs it and hands every event to each trigger.
+#[derive(Default)]
+pub struct InputListener {
+    started: AtomicBool,
+}
+
+impl InputListener {
+    /// Start the listener thread if it is not already running
+    pub fn ensure_started(&self, app: &AppHandle) {
+        if !self.started.swap(true, Ordering::SeqCst) {
+            start_listener(app.clone());
+        }
+    }
+}
+
+fn start_listener(app: AppHandle) {
+    std::thread::spawn(move || {
+        log::info!("Starting global input listener");
+        let listener_app = app.clone();
+        let result = rdev::listen(move |event| {
+            crate::mouse_trigger::handle_input_event(&listener_app, &event);
+            crate::double_tap::handle_input_event(&listener_app, &event);
+            crate::media_keys::handle_input_event(&listener_app, &event);
+        });
+
+        if let Err(e) = result {
+            log::error!("Global input listener failed: {:?}", e);
+            app.state::<InputListener>()
+                .started
+                .store(false, Ordering::SeqCst);
+        }
+    });
+}
@@ -0,0 +1,316 @@
+//! Startup checks of the app's data files.
+//!
+//! Before the settings store and databases are opened, each is checked for
+//! corruption: settings must be a JSON object, and the history, usage,
+//! dictionary and snippet databases must pass SQLite's integrity check, with
+//! history holding the expected table. Downloaded Whisper models are checked
+//! against the checksum recorded at download, on a background thread since they
+//! are large. A corrupt file is renamed aside with a timestamp (e.g.
+//! `history.db.corrupt-20250101-120000`) so the app starts from defaults, and the
+//! issue is kept until the user dismisses it.
+//!
+//! Settings are backed up to `settings.json.bak` whenever they are found healthy, at
+//! startup and after each save, so corrupt settings (e.g. from a crash while they
+//! were being written) are restored from the last good copy instead of reset.
+
+use crate::dictionary::DICTIONARY_DB_FILE;
+use crate::snippets::SNIPPETS_DB_FILE;
+use crate::stt::models::{WhisperModel, WhisperModelStore};
+use crate::usage::USAGE_DB_FILE;
+use chrono::{DateTime, Local};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Settings store file in the app data directory
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Last known good copy of the settings store
+pub const SETTINGS_BACKUP_FILE: &str = "settings.json.bak";
+
+/// History database file in the app data directory
+pub const HISTORY_DB_FILE: &str = "history.db";
+
+/// Files SQLite keeps next to a database, moved along with it
+const SQLITE_SIDECAR_SUFFIXES: [&str; 3] = ["-journal", "-wal", "-shm"];
+
+/// Columns the history table has had since the first release
+const HISTORY_COLUMNS: [&str; 6] = [
+    "id",
+    "timestamp_ms",
+    "text",
+    "app_name",
+    "provider",
+    "duration_ms",
+];
+
+/// A data file found corrupt at startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DataFileIssue {
+    /// File name within the app data directory
+    pub file: String,
+    /// What was wrong with it
+    pub problem: String,
+    /// Name the file was renamed to, or `None` if renaming failed
+    pub quarantined_as: Option<String>,
+    /// Whether the last good copy of the file was put back in its place
+    pub restored_from_backup: bool,
+}
+
+/// Issues found by the startup checks, until dismissed
+#[derive(Default)]
+pub struct DataFileIssues {
+    issues: Mutex<Vec<DataFileIssue>>,
+}
+
+impl DataFileIssues {
+    pub fn new(issues: Vec<DataFileIssue>) -> Self {
+        Self {
+            issues: Mutex::new(issues),
+        }
+    }
+
+    pub fn add(&self, issue: DataFileIssue) {
+        if let Ok(mut issues) = self.issues.lock() {
+            issues.push(issue);
+        }
+    }
+
+    pub fn list(&self) -> Vec<DataFileIssue> {
+        self.issues
+            .lock()
+            .map(|issues| issues.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut issues) = self.issues.lock() {
+            issues.clear();
+        }
+    }
+}
+
+/// Check the settings store and databases, quarantining corrupt files.
+/// Must run before either is opened.
+pub fn check_data_files(app_data_dir: &Path) -> Vec<DataFileIssue> {
+    let now = Local::now();
+    [
+        check_settings(app_data_dir, now),
+        check_file(&app_data_dir.join(HISTORY_DB_FILE), check_history_db, now),
+        check_file(&app_data_dir.join(USAGE_DB_FILE), check_usage_db, now),
+        check_file(
+            &app_data_dir.join(DICTIONARY_DB_FILE),
+            check_dictionary_db,
+            now,
+        ),
+        check_file(&app_data_dir.join(SNIPPETS_DB_FILE), check_snippets_db, now),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Check every downloaded Whisper model, quarantining corrupt ones along with
+/// their recorded checksum
+pub fn check_models(models: &WhisperModelStore) -> Vec<DataFileIssue> {
+    let now = Local::now();
+    WhisperModel::ALL
+        .into_iter()
+        .filter(|&model| models.downloaded_path(model).is_some())
+        .filter_map(|model| {
+            let issue = check_file(&models.path(model), |_| models.verify(model), now)?;
+            let _ = quarantine(&models.checksum_path(model), now);
+            Some(issue)
+        })
+        .collect()
+}
+
+/// Run `check` on a file that exists, quarantining it if the check fails
+fn check_file(
+    path: &Path,
+    check: impl FnOnce(&Path) -> Result<(), String>,
+    now: DateTime<Local>,
+) -> Option<DataFileIssue> {
+    if !path.exists() {
+        return None;
+    }
+    let problem = check(path).err()?;
+    let file = file_name(path);
+    log::error!("{} is corrupt: {}", file, problem);
+    let quarantined_as = match quarantine(path, now) {
+        Ok(quarantined) => {
+            log::warn!("Moved corrupt {} to {}", file, quarantined.display());
+            Some(file_name(&quarantined))
+        }
+        Err(e) => {
+            log::error!("{}", e);
+            None
+        }
+    };
+    Some(DataFileIssue {
+        file,
+        problem,
+        quarantined_as,
+        restored_from_backup: false,
+    })
+}
+
+/// Check the settings store, restoring corrupt settings from the backup if it is
+/// healthy, and back up healthy settings
+fn check_settings(app_data_dir: &Path, now: DateTime<Local>) -> Option<DataFileIssue> {
+    let Some(mut issue) = check_file(&app_data_dir.join(SETTINGS_FILE), check_settings_file, now)
+    else {
+        if let Err(e) = back_up_settings(app_data_dir) {
+            log::warn!("{}", e);
+        }
+        return None;
+    };
+    if issue.quarantined_as.is_some() {
+        match restore_settings_backup(app_data_dir) {
+            Ok(()) => {
+                log::warn!("Restored settings from {}", SETTINGS_BACKUP_FILE);
+                issue.restored_from_backup = true;
+            }
+            Err(e) => log::error!("{}", e),
+        }
+    }
+    Some(issue)
+}
+
+/// Copy healthy settings over the backup. Does nothing if there are no settings yet,
+/// and never replaces the backup with corrupt settings.
+pub fn back_up_settings(app_data_dir: &Path) -> Result<(), String> {
+    let settings = app_data_dir.join(SETTINGS_FILE);
+    if !settings.exists() {
+        return Ok(());
+    }
+    check_settings_file(&settings)?;
+    let content = fs::read(&settings).map_err(|e| format!("Failed to read settings: {}", e))?;
+    write_atomic(&app_data_dir.join(SETTINGS_BACKUP_FILE), &content)
+        .map_err(|e| format!("Failed to back up settings: {}", e))
+}
+
+/// Put the settings backup in place of the settings store, if the backup is healthy
+fn restore_settings_backup(app_data_dir: &Path) -> Result<(), String> {
+    let backup = app_data_dir.join(SETTINGS_BACKUP_FILE);
+    if !backup.exists() {
+        return Err("No settings backup to restore".to_string());
+    }
+    check_settings_file(&backup).map_err(|e| format!("Settings backup is corrupt too: {}", e))?;
+    let content =
+        fs::read(&backup).map_err(|e| format!("Failed to read settings backup: {}", e))?;
+    write_atomic(&app_data_dir.join(SETTINGS_FILE), &content)
+        .map_err(|e| format!("Failed to restore settings: {}", e))
+}
+
+/// Write a file so that it holds either its old or its new contents, never part of
+/// them: the contents go to a temporary file next to it, which is flushed to disk
+/// and renamed over it
+pub fn write_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let temp = with_suffix(path, ".tmp");
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+/// The settings store must be a JSON object of setting names to values
+pub fn check_settings_file(path: &Path) -> Result<(), String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read settings: {}", e))?;
+    serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content)
+        .map(|_| ())
+        .map_err(|e| format!("Settings are not valid JSON: {}", e))
+}
+
+/// The history database must pass SQLite's integrity check and, if its table has
+/// been created, hold the original columns
+pub fn check_history_db(path: &Path) -> Result<(), String> {
+    let connection = open_checked_db(path, "History")?;
+
+    let mut statement = connection
+        .prepare("SELECT name FROM pragma_table_info('history')")
+        .map_err(|e| format!("History database is unreadable: {}", e))?;
+    let columns = statement
+        .query_map([], |row| row.get::<_, String>(0))
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("History database is unreadable: {}", e))?;
+    if columns.is_empty() {
+        return Ok(());
+    }
+    match HISTORY_COLUMNS
+        .iter()
+        .find(|column| !columns.iter().any(|existing| existing == *column))
+    {
+        Some(missing) => Err(format!("History table is missing the {} column", missing)),
+        None => Ok(()),
+    }
+}
+
+/// The usage database must pass SQLite's integrity check
+pub fn check_usage_db(path: &Path) -> Result<(), String> {
+    open_checked_db(path, "Usage").map(|_| ())
+}
+
+/// The dictionary database must pass SQLite's integrity check
+pub fn check_dictionary_db(path: &Path) -> Result<(), String> {
+    open_checked_db(path, "Dictionary").map(|_| ())
+}
+
+/// The snippet database must pass SQLite's integrity check
+pub fn check_snippets_db(path: &Path) -> Result<(), String> {
+    open_checked_db(path, "Snippet").map(|_| ())
+}
+
+/// Open a database and run SQLite's integrity check on it. `name` starts errors.
+fn open_checked_db(path: &Path, name: &str) -> Result<Connection, String> {
+    let connection = Connection::open(path)
+        .map_err(|e| format!("Failed to open {} database: {}", name.to_lowercase(), e))?;
+    let result: String = connection
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| format!("{} database is unreadable: {}", name, e))?;
+    if result != "ok" {
+        return Err(format!(
+            "{} database failed its integrity check: {}",
+            name, result
+        ));
+    }
+    Ok(connection)
+}
+
+/// Where a corrupt file is moved: its name with a `.corrupt-<timestamp>` suffix
+pub fn quarantine_path(path: &Path, now: DateTime<Local>) -> PathBuf {
+    with_suffix(path, &format!(".corrupt-{}", now.format("%Y%m%d-%H%M%S")))
+}
+
+/// Rename a corrupt file aside, along with any SQLite files kept next to it.
+/// Returns the new path.
+pub fn quarantine(path: &Path, now: DateTime<Local>) -> Result<PathBuf, String> {
+    let quarantined = quarantine_path(path, now);
+    fs::rename(path, &quarantined)
+        .map_err(|e| format!("Failed to move corrupt {}: {}", file_name(path), e))?;
+    for suffix in SQLITE_SIDECAR_SUFFIXES {
+        let sidecar = with_suffix(path, suffix);
+        if sidecar.exists() {
+            let _ = fs::rename(&sidecar, with_suffix(&quarantined, suffix));
+        }
+    }
+    Ok(quarantined)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,156 @@
+//! Binding hotkeys by key position rather than by the character a key types.
+//!
+//! Hotkeys are recorded by position and named as on a US QWERTY keyboard, so "Q" is
+//! the key right of Tab. Windows registers shortcuts by virtual key, which follows
+//! the active layout: on AZERTY a shortcut saved as "Q" fires on the key labelled Q,
+//! where QWERTY has A. Hotkeys bound by position are translated to the virtual key
+//! the current layout puts at their position before they are registered, and again
+//! whenever the layout changes. macOS already registers shortcuts by position, and
+//! on Linux keys are registered as named.
+
+/// Set-1 scancodes of the key positions whose character depends on the layout,
+/// named as on a US keyboard
+const POSITIONS: &[(&str, u16)] = &[
+    ("1", 0x02),
+    ("2", 0x03),
+    ("3", 0x04),
+    ("4", 0x05),
+    ("5", 0x06),
+    ("6", 0x07),
+    ("7", 0x08),
+    ("8", 0x09),
+    ("9", 0x0A),
+    ("0", 0x0B),
+    ("Minus", 0x0C),
+    ("Equal", 0x0D),
+    ("Q", 0x10),
+    ("W", 0x11),
+    ("E", 0x12),
+    ("R", 0x13),
+    ("T", 0x14),
+    ("Y", 0x15),
+    ("U", 0x16),
+    ("I", 0x17),
+    ("O", 0x18),
+    ("P", 0x19),
+    ("BracketLeft", 0x1A),
+    ("BracketRight", 0x1B),
+    ("A", 0x1E),
+    ("S", 0x1F),
+    ("D", 0x20),
+    ("F", 0x21),
+    ("G", 0x22),
+    ("H", 0x23),
+    ("J", 0x24),
+    ("K", 0x25),
+    ("L", 0x26),
+    ("Semicolon", 0x27),
+    ("Quote", 0x28),
+    ("Backquote", 0x29),
+    ("Backslash", 0x2B),
+    ("Z", 0x2C),
+    ("X", 0x2D),
+    ("C", 0x2E),
+    ("V", 0x2F),
+    ("B", 0x30),
+    ("N", 0x31),
+    ("M", 0x32),
+    ("Comma", 0x33),
+    ("Period", 0x34),
+    ("Slash", 0x35),
+    ("IntlBackslash", 0x56),
+];
+
+/// Windows virtual keys of punctuation (`VK_OEM_*`), named as the key that types
+/// them on a US keyboard
+const OEM_VIRTUAL_KEYS: &[(u16, &str)] = &[
+    (0xBA, "Semicolon"),
+    (0xBB, "Equal"),
+    (0xBC, "Comma"),
+    (0xBD, "Minus"),
+    (0xBE, "Period"),
+    (0xBF, "Slash"),
+    (0xC0, "Backquote"),
+    (0xDB, "BracketLeft"),
+    (0xDC, "Backslash"),
+    (0xDD, "BracketRight"),
+    (0xDE, "Quote"),
+    (0xE2, "IntlBackslash"),
+];
+
+/// Scancode of the key at `key`'s position, `None` for keys that are the same on
+/// every layout (Space, F1, arrows...)
+pub fn scancode(key: &str) -> Option<u16> {
+    POSITIONS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, scancode)| *scancode)
+}
+
+/// Key name of a Windows virtual key, for the letters, digits and punctuation a
+/// layout can put at a position
+pub fn key_for_virtual_key(virtual_key: u16) -> Option<String> {
+    match virtual_key {
+        0x30..=0x39 | 0x41..=0x5A => char::from_u32(u32::from(virtual_key)).map(String::from),
+        _ => OEM_VIRTUAL_KEYS
+            .iter()
+            .find(|(vk, _)| *vk == virtual_key)
+            .map(|(_, name)| name.to_string()),
+    }
+}
+
+/// The key to register for the position `key` names, given the virtual key the
+/// current layout puts at each scancode. Keys that don't depend on the layout, or
+/// positions the layout leaves without a letter, digit or punctuation key, are
+/// registered as named.
+pub fn resolve_position(key: &str, virtual_key_at: impl Fn(u16) -> Option<u16>) -> String {
+    scancode(key)
+        .and_then(virtual_key_at)
+        .and_then(key_for_virtual_key)
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// The key to register for the position `key` names on the current layout
+#[cfg(target_os = "windows")]
+pub fn key_at_position(key: &str) -> String {
+    resolve_position(key, windows_layout::virtual_key)
+}
+
+/// The key to register for the position `key` names on the current layout
+#[cfg(not(target_os = "windows"))]
+pub fn key_at_position(key: &str) -> String {
+    key.to_string()
+}
+
+/// Identifier of the active keyboard layout, to notice when the user switches
+#[cfg(target_os = "windows")]
+pub fn current_layout() -> isize {
+    windows_layout::current().0 as isize
+}
+
+#[cfg(target_os = "windows")]
+mod windows_layout {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetKeyboardLayout, MapVirtualKeyExW, HKL, MAPVK_VSC_TO_VK_EX,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    /// Layout of the window being typed into. Layouts are per thread, and switching
+    /// layout changes the foreground window's.
+    pub fn current() -> HKL {
+        // SAFETY: these calls only read state; a missing foreground window yields
+        // thread 0, which is this thread's layout
+        unsafe {
+            let thread = GetWindowThreadProcessId(GetForegroundWindow(), None);
+            GetKeyboardLayout(thread)
+        }
+    }
+
+    /// Virtual key the current layout puts at `scancode`
+    pub fn virtual_key(scancode: u16) -> Option<u16> {
+        // SAFETY: MapVirtualKeyExW only reads the layout tables
+        let virtual_key =
+            unsafe { MapVirtualKeyExW(u32::from(scancode), MAPVK_VSC_TO_VK_EX, Some(current())) };
+        u16::try_from(virtual_key).ok().filter(|vk| *vk != 0)
+    }
+}
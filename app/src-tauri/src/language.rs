@@ -0,0 +1,73 @@
+//! Dictation language hint for speech-to-text providers.
+//!
+//! The hint is an ISO 639-1 code such as `en`. With auto-detect on, or no
+//! language chosen, providers detect the spoken language themselves. A hotkey and
+//! the tray switch between the languages the user dictates in.
+
+/// Languages offered for dictation, by ISO 639-1 code
+pub const LANGUAGES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("zh", "Chinese"),
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("nl", "Dutch"),
+    ("en", "English"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("hu", "Hungarian"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("no", "Norwegian"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("es", "Spanish"),
+    ("sv", "Swedish"),
+    ("th", "Thai"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("vi", "Vietnamese"),
+];
+
+/// Display name of a language code, if it is one of `LANGUAGES`
+pub fn language_name(code: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code.trim()))
+        .map(|(_, name)| *name)
+}
+
+/// The code of a known language in canonical (lowercase) form
+pub fn normalize_language(code: &str) -> Option<String> {
+    language_name(code).map(|_| code.trim().to_lowercase())
+}
+
+/// The hint to send providers, or `None` to let them detect the language
+pub fn language_hint(language: Option<&str>, auto_detect: bool) -> Option<String> {
+    if auto_detect {
+        return None;
+    }
+    language.and_then(normalize_language)
+}
+
+/// The language after `current` in `languages`, wrapping around, or the first one
+/// when `current` isn't among them. Unknown codes are skipped.
+pub fn next_language(current: Option<&str>, languages: &[String]) -> Option<String> {
+    let languages: Vec<String> = languages
+        .iter()
+        .filter_map(|code| normalize_language(code))
+        .collect();
+    let current = current.and_then(normalize_language);
+    let next = match current.and_then(|code| languages.iter().position(|known| *known == code)) {
+        Some(index) => (index + 1) % languages.len(),
+        None => 0,
+    };
+    languages.into_iter().nth(next)
+}
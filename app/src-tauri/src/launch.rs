@@ -0,0 +1,48 @@
+//! Command-line flags read at startup.
+//!
+//! `--headless` skips opening the settings window at launch; it is created on demand
+//! from the tray. `--no-overlay` also skips the recording overlay, leaving only the
+//! tray and hotkeys. Server dictation streams audio through the overlay, so without
+//! it only in-app STT providers can transcribe.
+
+/// How the app was asked to start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaunchOptions {
+    /// Open the settings window at launch
+    pub show_main_window: bool,
+    /// Create the recording overlay
+    pub show_overlay: bool,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            show_main_window: true,
+            show_overlay: true,
+        }
+    }
+}
+
+impl LaunchOptions {
+    /// Parse launch flags, ignoring anything unrecognized (e.g. flags added by the OS)
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = Self::default();
+        for arg in args {
+            match arg.as_ref() {
+                "--headless" => options.show_main_window = false,
+                "--no-overlay" => options.show_overlay = false,
+                _ => {}
+            }
+        }
+        options
+    }
+
+    /// Read launch flags from the process arguments
+    pub fn from_env() -> Self {
+        Self::from_args(std::env::args().skip(1))
+    }
+}
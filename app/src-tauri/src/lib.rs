@@ -1,31 +1,139 @@
 use std::sync::atomic::Ordering;
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager,
 };
 use tauri_utils::config::BackgroundThrottlingPolicy;
 
+mod accessibility;
+mod active_window;
+#[cfg(desktop)]
+mod api;
 mod audio;
 mod audio_mute;
+mod audio_processing;
+mod audio_upload;
+mod auth;
+mod benchmark;
+mod capture;
+#[cfg(desktop)]
+mod clipboard;
 mod commands;
+mod connection;
+#[cfg(target_os = "linux")]
+mod dbus;
+#[cfg(desktop)]
+mod deep_link;
+mod diagnostics;
+mod dictionary;
+mod dictionary_import;
+mod dictionary_suggestions;
+#[cfg(desktop)]
+mod double_tap;
+mod error;
+mod events;
+mod export;
+mod formatter;
+#[cfg(desktop)]
+mod hid_input;
 mod history;
+mod hold_latch;
+#[cfg(desktop)]
+mod input_listener;
+mod integrity;
+mod keyboard_layout;
+mod language;
+mod launch;
+#[cfg(target_os = "linux")]
+mod linux_input;
+#[cfg(desktop)]
+mod local_server;
+mod logging;
+#[cfg(desktop)]
+mod media_keys;
+mod mic_monitor;
+#[cfg(desktop)]
+mod mouse_trigger;
+mod notes;
+mod overlay;
+mod palette;
+mod paste_cycle;
+mod pending_transcriptions;
+mod phrases;
+mod pre_roll;
+mod prompt_presets;
+mod proxy;
+mod recording_limit;
+mod recordings;
+#[cfg(desktop)]
+mod remote_control;
+mod secrets;
+mod secure_input;
+#[cfg(desktop)]
+mod sequence;
+mod server;
+mod session;
 mod settings;
+mod settings_broadcast;
+#[cfg(desktop)]
+mod settings_watcher;
+mod snippets;
+mod sound_theme;
 mod state;
+mod stt;
+mod support_bundle;
+mod telemetry;
+mod text_diff;
+mod text_transforms;
+#[cfg(desktop)]
+mod trigger_queue;
+mod turns;
+mod updater;
+mod usage;
+mod vad;
+mod voice_commands;
+#[cfg(target_os = "linux")]
+mod wayland_shortcuts;
+mod window_state;
 
 #[cfg(test)]
 mod tests;
 
-use audio_mute::AudioMuteManager;
+use audio_mute::{AudioMuteManager, DEFAULT_DUCK_PERCENT};
+use dictionary::{DictionaryStore, DICTIONARY_DB_FILE};
+use events::{AppEvent, EventBus};
 use history::HistoryStorage;
-use settings::HotkeyConfig;
+use launch::LaunchOptions;
+use paste_cycle::PasteCycle;
+use pending_transcriptions::PendingTranscriptions;
+use recording_limit::RecordingLimit;
+use recordings::RecordingArchive;
+use session::SessionStore;
+use settings::{get_setting_from_store, DEFAULT_MAX_CONCURRENT_TURNS};
+#[cfg(desktop)]
+use settings::{is_blocked_app, OutputMode, DEFAULT_PRE_ROLL_MS};
+use snippets::SnippetStore;
 use state::AppState;
+use stt::models::WhisperModelStore;
+use stt::{AudioFormat, SttManager, Transcript};
+use telemetry::{FailureCategory, TelemetryStore};
+use turns::TurnQueue;
+use usage::UsageStore;
+use window_state::WindowStateStore;
 
 #[cfg(desktop)]
-use tauri_plugin_store::StoreExt;
-
+use active_window::get_active_app;
+#[cfg(desktop)]
+use hold_latch::LatchAction;
+#[cfg(desktop)]
+use settings::{HotkeyAction, DEFAULT_HOLD_LATCH_MAX_SECONDS, DEFAULT_MAX_RECORDING_SECONDS};
+#[cfg(desktop)]
+use sound_theme::SoundSettings;
 #[cfg(desktop)]
 use tauri_plugin_global_shortcut::{Shortcut, ShortcutEvent, ShortcutState};
+#[cfg(desktop)]
+use vad::SilenceDetector;
 
 // Define NSPanel type for overlay on macOS
 #[cfg(target_os = "macos")]
@@ -38,6 +146,17 @@ tauri_nspanel::tauri_panel! {
     })
 }
 
+/// ID of the system tray icon
+const TRAY_ID: &str = "main";
+
+/// Label of the main (settings) window, as defined in tauri.conf.json
+pub(crate) const MAIN_WINDOW_LABEL: &str = "main";
+
+/// Longest wait for the overlay to send its last captured audio before an in-app
+/// turn is transcribed without it
+#[cfg(desktop)]
+const LOCAL_CAPTURE_FLUSH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Normalize a shortcut string for comparison (handles "ctrl" vs "control" differences)
 #[cfg(desktop)]
 pub(crate) fn normalize_shortcut_string(s: &str) -> String {
@@ -48,47 +167,159 @@ pub(crate) fn normalize_shortcut_string(s: &str) -> String {
         .replace("win", "super")
 }
 
-/// Helper to read a setting from the store with a default fallback
-#[cfg(desktop)]
-fn get_setting_from_store<T: serde::de::DeserializeOwned>(
-    app: &AppHandle,
-    key: &str,
-    default: T,
-) -> T {
-    app.store("settings.json")
-        .ok()
-        .and_then(|store| store.get(key))
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or(default)
-}
-
 /// Start recording with sound and audio mute handling
 #[cfg(desktop)]
 fn start_recording(
     app: &AppHandle,
     state: &AppState,
-    sound_enabled: bool,
+    sounds: &SoundSettings,
     audio_mute_manager: &Option<tauri::State<'_, AudioMuteManager>>,
     auto_mute_audio: bool,
     source: &str,
 ) {
-    state.is_recording.store(true, Ordering::SeqCst);
-    log::info!("{}: starting recording", source);
+    if commands::dictation_pause::is_dictation_paused(app) {
+        log::info!("{}: dictation is paused, not starting a recording", source);
+        return;
+    }
+
+    let max_turns: usize =
+        get_setting_from_store(app, "max_concurrent_turns", DEFAULT_MAX_CONCURRENT_TURNS);
+    let turns = app.state::<TurnQueue>();
+    if !turns.has_capacity(max_turns) {
+        log::warn!(
+            "{}: {} turns still processing, not starting a new recording",
+            source,
+            turns.in_flight()
+        );
+        return;
+    }
+
+    // Capture the target app before the overlay or a sound can change focus
+    let target_app = get_active_app();
+    let blocked_apps: Vec<String> = get_setting_from_store(app, "blocked_apps", Vec::new());
+    if is_blocked_app(&blocked_apps, target_app.as_ref()) {
+        log::info!(
+            "{}: {} is a blocked app, not starting a recording",
+            source,
+            target_app.map(|target| target.app_name).unwrap_or_default()
+        );
+        return;
+    }
+
+    // Claim the recording, so triggers arriving together from different threads
+    // (a hotkey and a remote client, say) can't both start one
+    if state
+        .is_recording
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        log::info!("{}: already recording", source);
+        return;
+    }
+    let recording_id = state.recording_id.fetch_add(1, Ordering::SeqCst) + 1;
+    if let Ok(mut started_at) = state.recording_started_at.lock() {
+        *started_at = Some(std::time::Instant::now());
+    }
+    if let Ok(mut paused_at) = state.paused_at.lock() {
+        *paused_at = None;
+    }
+    log::info!(
+        "{}: starting recording in {}",
+        source,
+        target_app
+            .as_ref()
+            .map_or("an unknown app", |target| &target.app_name)
+    );
+    if let Ok(mut recording_app) = state.recording_app.lock() {
+        *recording_app = target_app;
+    }
+    let overridden = state.clipboard_only_next.swap(false, Ordering::SeqCst);
+    state
+        .recording_clipboard_only
+        .store(overridden, Ordering::SeqCst);
+    let _ = app.emit(
+        "clipboard-only-changed",
+        commands::text::output_mode(app, overridden) == OutputMode::ClipboardOnly,
+    );
+    events::publish(app, AppEvent::RecordingStarted);
     // Play sound BEFORE muting so it's audible
-    if sound_enabled {
-        audio::play_sound(audio::SoundType::RecordingStart);
+    if commands::audio::play_feedback_sound(app, sounds, audio::SoundType::RecordingStart) {
         // Brief delay to let sound play before muting
         std::thread::sleep(std::time::Duration::from_millis(150));
     }
-    // Mute system audio if enabled
+    // Mute or duck system audio if enabled
     if auto_mute_audio {
         if let Some(manager) = audio_mute_manager {
-            if let Err(e) = manager.mute() {
+            let duck_percent: u8 =
+                get_setting_from_store(app, "audio_duck_percent", DEFAULT_DUCK_PERCENT);
+            if let Err(e) = manager.mute(duck_percent) {
                 log::warn!("Failed to mute audio: {}", e);
             }
         }
     }
-    let _ = app.emit("recording-start", ());
+    let pre_roll_ms: u64 = get_setting_from_store(app, "pre_roll_ms", DEFAULT_PRE_ROLL_MS);
+    let pre_roll = std::time::Duration::from_millis(pre_roll_ms).min(pre_roll::MAX_PRE_ROLL);
+    let stt = app.state::<SttManager>();
+    stt.set_archive_audio(get_setting_from_store(app, "archive_recordings", false));
+    stt.set_keep_last_audio(get_setting_from_store(app, "keep_last_recording", true));
+    // Without a server connection, capture the audio in-app so the turn can be queued
+    stt.set_offline(!stt.transcribes_in_app() && !state.server_connected.load(Ordering::SeqCst));
+    if let Err(e) = stt.start_stream(AudioFormat::default(), pre_roll) {
+        log::error!("Failed to start transcription stream: {}", e);
+    }
+    // In-app providers transcribe audio captured by the backend, or by the overlay
+    // when it owns the microphone
+    if stt.captures_in_app() {
+        commands::capture::start_local_capture(app);
+    }
+    arm_max_duration_stop(app, recording_id);
+}
+
+/// Arm the stop of a recording that runs for `max_recording_seconds`, so a forgotten
+/// toggle recording doesn't run forever
+#[cfg(desktop)]
+fn arm_max_duration_stop(app: &AppHandle, recording_id: u64) {
+    let max_seconds: u64 =
+        get_setting_from_store(app, "max_recording_seconds", DEFAULT_MAX_RECORDING_SECONDS);
+    if max_seconds == 0 {
+        return;
+    }
+    app.state::<RecordingLimit>()
+        .arm(recording_id, std::time::Duration::from_secs(max_seconds));
+}
+
+/// Stop each recording that reaches its limit and transcribe what was captured.
+/// Emits `recording-limit-reached` with the limit when it stops a recording.
+#[cfg(desktop)]
+fn watch_max_duration(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let limit = app.state::<RecordingLimit>();
+        while let Some(recording_id) = limit.wait_for_expiry() {
+            let state = app.state::<AppState>();
+            // The recording stopped, or another one started, just as the limit passed
+            if state.recording_id.load(Ordering::SeqCst) != recording_id
+                || !state.is_recording.load(Ordering::SeqCst)
+            {
+                continue;
+            }
+            let max_seconds: u64 = get_setting_from_store(
+                &app,
+                "max_recording_seconds",
+                DEFAULT_MAX_RECORDING_SECONDS,
+            );
+            log::warn!("MaxDuration: recording limit of {}s reached", max_seconds);
+            let _ = app.emit("recording-limit-reached", max_seconds);
+            stop_recording(
+                &app,
+                &state,
+                &commands::audio::sound_settings(&app),
+                &app.try_state::<AudioMuteManager>(),
+                get_setting_from_store(&app, "auto_mute_audio", false),
+                "MaxDuration",
+            );
+        }
+    });
 }
 
 /// Stop recording with sound and audio unmute handling
@@ -96,13 +327,18 @@ fn start_recording(
 fn stop_recording(
     app: &AppHandle,
     state: &AppState,
-    sound_enabled: bool,
+    sounds: &SoundSettings,
     audio_mute_manager: &Option<tauri::State<'_, AudioMuteManager>>,
     auto_mute_audio: bool,
     source: &str,
 ) {
-    state.is_recording.store(false, Ordering::SeqCst);
+    // Recording may have been refused because too many turns were processing
+    if !state.is_recording.swap(false, Ordering::SeqCst) {
+        return;
+    }
     log::info!("{}: stopping recording", source);
+    app.state::<RecordingLimit>().disarm();
+    events::publish(app, AppEvent::RecordingStopped);
     // Unmute system audio if it was muted
     if auto_mute_audio {
         if let Some(manager) = audio_mute_manager {
@@ -111,10 +347,146 @@ fn stop_recording(
             }
         }
     }
-    if sound_enabled {
-        audio::play_sound(audio::SoundType::RecordingStop);
+    commands::audio::play_feedback_sound(app, sounds, audio::SoundType::RecordingStop);
+    if let Ok(mut detector) = state.silence_detector.lock() {
+        *detector = None;
+    }
+    let capture_flush = if app.state::<SttManager>().captures_in_app() {
+        commands::capture::stop_local_capture(app)
+    } else {
+        None
+    };
+    // Stopping while paused doesn't count the pause towards the duration
+    resume_recording_clock(state);
+    let duration_ms = state
+        .recording_started_at
+        .lock()
+        .ok()
+        .and_then(|mut started_at| started_at.take())
+        .map(|started_at| started_at.elapsed().as_millis() as u64);
+    finalize_transcription(app, duration_ms, capture_flush);
+}
+
+/// Abandon the current recording without transcribing it, e.g. when the overlay
+/// can't open the microphone
+#[cfg(desktop)]
+pub(crate) fn cancel_recording(app: &AppHandle, source: &str) {
+    let state = app.state::<AppState>();
+    if !state.is_recording.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    log::info!("{}: cancelling recording", source);
+    app.state::<RecordingLimit>().disarm();
+    events::publish(app, AppEvent::RecordingStopped);
+    if get_setting_from_store(app, "auto_mute_audio", false) {
+        if let Some(manager) = app.try_state::<AudioMuteManager>() {
+            if let Err(e) = manager.unmute() {
+                log::warn!("Failed to unmute audio: {}", e);
+            }
+        }
+    }
+    if let Ok(mut detector) = state.silence_detector.lock() {
+        *detector = None;
+    }
+    if let Ok(mut started_at) = state.recording_started_at.lock() {
+        *started_at = None;
+    }
+    if let Ok(mut paused_at) = state.paused_at.lock() {
+        *paused_at = None;
+    }
+    let stt = app.state::<SttManager>();
+    if stt.captures_in_app() {
+        commands::capture::stop_local_capture(app);
+    }
+    if let Err(e) = stt.cancel() {
+        log::error!("Failed to cancel transcription stream: {}", e);
+    }
+}
+
+/// Finalize the STT turn off the shortcut thread, since local providers transcribe here.
+/// `capture_flush` is the stop request to wait on when the overlay captured the audio.
+#[cfg(desktop)]
+fn finalize_transcription(app: &AppHandle, duration_ms: Option<u64>, capture_flush: Option<u64>) {
+    let turn_id = app.state::<TurnQueue>().begin();
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let stt = app.state::<SttManager>();
+        let turns = app.state::<TurnQueue>();
+        if let Some(id) = capture_flush {
+            let flush = app.state::<capture::LocalCaptureFlush>();
+            if !flush.wait(id, LOCAL_CAPTURE_FLUSH_TIMEOUT) {
+                log::warn!("Overlay didn't send the last captured audio in time");
+            }
+        }
+        // Only the server answers through the overlay, and only while connected
+        let connected = app
+            .state::<AppState>()
+            .server_connected
+            .load(Ordering::SeqCst);
+        let server_turn = connected && !stt.is_offline() && !stt.transcribes_in_app();
+        let mut awaits_server = false;
+        match stt.finalize() {
+            Ok(Some(transcript)) => {
+                // Insert after earlier turns so text lands in the order it was spoken
+                turns.wait_for_turn(turn_id);
+                deliver_local_transcript(&app, &transcript, duration_ms);
+            }
+            // The server's transcript comes back through the overlay, which reports
+            // when the turn is done
+            Ok(None) => awaits_server = server_turn,
+            Err(e) => {
+                log::error!("Failed to finalize transcription: {}", e);
+                events::publish(
+                    &app,
+                    AppEvent::TurnFailed {
+                        error: e,
+                        category: FailureCategory::Transcription,
+                    },
+                );
+            }
+        }
+        commands::recordings::archive_finished_turn(&app);
+        commands::pending_transcriptions::queue_unsent_turn(&app);
+        if awaits_server {
+            turns.await_server(turn_id);
+        } else {
+            turns.finish(turn_id);
+        }
+    });
+}
+
+/// Format a transcript produced in-app, insert it and record it in history.
+/// Falls back to inserting the raw transcript if every formatter fails.
+pub(crate) fn deliver_local_transcript(
+    app: &AppHandle,
+    transcript: &Transcript,
+    duration_ms: Option<u64>,
+) {
+    let raw = transcript.text.as_str();
+    let text = match formatter::format_transcript(app, raw) {
+        Ok(Some(text)) => text,
+        // The server formatter delivers its result through the overlay
+        Ok(None) => return,
+        Err(e) => {
+            log::error!("Failed to format transcript, inserting raw text: {}", e);
+            raw.to_string()
+        }
+    };
+
+    // A dictation of only voice commands leaves nothing to record
+    let text = commands::text::insert_dictation_text(app, &text);
+    if text.is_empty() {
+        return;
+    }
+    if let Err(e) = commands::history::record_history_entry(
+        app,
+        text,
+        duration_ms,
+        Some(raw.to_string()),
+        Some(transcript.provider),
+    ) {
+        log::error!("Failed to record history entry: {}", e);
     }
-    let _ = app.emit("recording-stop", ());
 }
 
 /// Handle a shortcut event - public so it can be called from commands/settings.rs
@@ -122,134 +494,371 @@ fn stop_recording(
 pub fn handle_shortcut_event(app: &AppHandle, shortcut: &Shortcut, event: &ShortcutEvent) {
     let state = app.state::<AppState>();
 
-    // Get current settings from store
-    let sound_enabled: bool = get_setting_from_store(app, "sound_enabled", true);
-    let auto_mute_audio: bool = get_setting_from_store(app, "auto_mute_audio", false);
-
     // Get shortcut string for comparison (normalized to handle "ctrl" vs "control" differences)
     let shortcut_str = normalize_shortcut_string(&shortcut.to_string());
 
-    // Get configured shortcut strings from store (normalized), with validation fallback
-    let toggle_hotkey: HotkeyConfig =
-        get_setting_from_store(app, "toggle_hotkey", HotkeyConfig::default_toggle());
-    let hold_hotkey: HotkeyConfig =
-        get_setting_from_store(app, "hold_hotkey", HotkeyConfig::default_hold());
-    let paste_last_hotkey: HotkeyConfig =
-        get_setting_from_store(app, "paste_last_hotkey", HotkeyConfig::default_paste_last());
-
-    // Validate hotkeys - if they can't be parsed as shortcuts, use defaults
-    let toggle_shortcut_str = normalize_shortcut_string(
-        &toggle_hotkey
-            .to_shortcut()
-            .map(|_| toggle_hotkey.to_shortcut_string())
-            .unwrap_or_else(|_| HotkeyConfig::default_toggle().to_shortcut_string()),
-    );
-    let hold_shortcut_str = normalize_shortcut_string(
-        &hold_hotkey
-            .to_shortcut()
-            .map(|_| hold_hotkey.to_shortcut_string())
-            .unwrap_or_else(|_| HotkeyConfig::default_hold().to_shortcut_string()),
+    // Compare with the configured hotkeys, falling back to an action's default if its
+    // hotkey can't be parsed, as when it was registered.
+    // Double taps, mouse buttons and sequences are handled by their own listeners instead
+    let action = sequence::configured_hotkeys(app)
+        .into_iter()
+        .find(|(action, hotkey)| {
+            let hotkey = match hotkey.to_shortcut() {
+                Ok(_) => hotkey.clone(),
+                Err(_) => action.default_hotkey(),
+            };
+            hotkey.is_plain_keyboard()
+                && shortcut_str == normalize_shortcut_string(&hotkey.to_shortcut_string())
+        })
+        .map(|(action, _)| action);
+    let Some(action) = action else {
+        log::warn!("Unknown shortcut: {}", shortcut_str);
+        return;
+    };
+
+    if action == HotkeyAction::Hold {
+        // Hold-to-Record: start on press, stop on release
+        handle_hold_trigger(app, matches!(event.state, ShortcutState::Pressed), "Hold");
+        return;
+    }
+    // Everything else happens once on key release, ignoring OS key repeat
+    let held = &state.hotkeys_held[action.index()];
+    match event.state {
+        ShortcutState::Pressed => held.store(true, Ordering::SeqCst),
+        ShortcutState::Released => {
+            if held.swap(false, Ordering::SeqCst) {
+                run_hotkey_action(app, action, false, "Shortcut");
+            }
+        }
+    }
+}
+
+/// Run a hotkey action for a press or release reported by a listener that tracks
+/// each key itself (sequences and Wayland shortcuts). Hold acts on both; everything
+/// else acts on release.
+#[cfg(desktop)]
+pub(crate) fn run_hotkey_action(
+    app: &AppHandle,
+    action: HotkeyAction,
+    pressed: bool,
+    source: &str,
+) {
+    match (action, pressed) {
+        (HotkeyAction::Hold, _) => handle_hold_trigger(app, pressed, source),
+        (HotkeyAction::Toggle, false) => handle_toggle_trigger(app, source),
+        (HotkeyAction::PasteLast, false) => paste_last_transcription(app),
+        (HotkeyAction::CycleHistory, false) => cycle_history_paste(app),
+        (HotkeyAction::CycleLanguage, false) => commands::language::cycle_language(app),
+        (HotkeyAction::PauseResume, false) => toggle_recording_pause(app, source),
+        (HotkeyAction::RetryLast, false) => retry_last_transcription(app),
+        (HotkeyAction::ClipboardOnly, false) => toggle_clipboard_only(app, source),
+        (HotkeyAction::CycleSttProvider, false) => commands::stt::cycle_provider_from_hotkey(app),
+        (HotkeyAction::ToggleFormatting, false) => commands::formatting::toggle_formatting(app),
+        (HotkeyAction::CyclePromptPreset, false) => {
+            commands::prompt_presets::cycle_prompt_preset(app)
+        }
+        (HotkeyAction::OpenPalette, false) => commands::palette::toggle_palette_window(app),
+        (HotkeyAction::DoNotDisturb, false) => {
+            commands::dictation_pause::toggle_dictation_paused(app)
+        }
+        _ => {}
+    }
+}
+
+/// Start recording if idle, otherwise stop it.
+/// Shared by the keyboard shortcut, double-tap, media key, tray and remote control triggers.
+#[cfg(desktop)]
+pub(crate) fn handle_toggle_trigger(app: &AppHandle, source: &str) {
+    let state = app.state::<AppState>();
+    let sounds = commands::audio::sound_settings(app);
+    let auto_mute_audio: bool = get_setting_from_store(app, "auto_mute_audio", false);
+    let audio_mute_manager = app.try_state::<AudioMuteManager>();
+
+    if state.is_recording.load(Ordering::SeqCst) {
+        stop_recording(
+            app,
+            &state,
+            &sounds,
+            &audio_mute_manager,
+            auto_mute_audio,
+            source,
+        );
+    } else {
+        start_recording(
+            app,
+            &state,
+            &sounds,
+            &audio_mute_manager,
+            auto_mute_audio,
+            source,
+        );
+        if state.is_recording.load(Ordering::SeqCst) {
+            arm_silence_auto_stop(app, &state);
+        }
+    }
+}
+
+/// Pause the current recording, or resume it if paused. Audio captured while paused
+/// is dropped, so the segments on either side are transcribed as one dictation.
+/// Emits `recording-paused` with whether the recording is now paused.
+#[cfg(desktop)]
+pub(crate) fn toggle_recording_pause(app: &AppHandle, source: &str) {
+    let state = app.state::<AppState>();
+    if !state.is_recording.load(Ordering::SeqCst) {
+        log::info!("{}: not recording, nothing to pause", source);
+        return;
+    }
+
+    let Ok(paused) = state.paused_at.lock().map(|mut paused_at| {
+        let pausing = paused_at.is_none();
+        if pausing {
+            *paused_at = Some(std::time::Instant::now());
+        }
+        pausing
+    }) else {
+        return;
+    };
+    if !paused {
+        resume_recording_clock(&state);
+    }
+    log::info!(
+        "{}: {} recording",
+        source,
+        if paused { "pausing" } else { "resuming" }
     );
-    let paste_last_shortcut_str = normalize_shortcut_string(
-        &paste_last_hotkey
-            .to_shortcut()
-            .map(|_| paste_last_hotkey.to_shortcut_string())
-            .unwrap_or_else(|_| HotkeyConfig::default_paste_last().to_shortcut_string()),
+    let _ = app.emit("recording-paused", paused);
+}
+
+/// Switch the current recording, or the next one when idle, between the usual output
+/// and the clipboard only (or inserting, when the clipboard only is the usual output),
+/// for when the wrong window is focused. Emits `clipboard-only-changed` with whether
+/// the dictation now goes to the clipboard only.
+#[cfg(desktop)]
+pub(crate) fn toggle_clipboard_only(app: &AppHandle, source: &str) {
+    let state = app.state::<AppState>();
+    let recording = state.is_recording.load(Ordering::SeqCst);
+    let flag = if recording {
+        &state.recording_clipboard_only
+    } else {
+        &state.clipboard_only_next
+    };
+    let overridden = !flag.fetch_xor(true, Ordering::SeqCst);
+    let clipboard_only = commands::text::output_mode(app, overridden) == OutputMode::ClipboardOnly;
+    log::info!(
+        "{}: {} dictation goes to the {}",
+        source,
+        if recording { "current" } else { "next" },
+        if clipboard_only {
+            "clipboard only"
+        } else {
+            "focused app"
+        }
     );
+    let _ = app.emit("clipboard-only-changed", clipboard_only);
+}
+
+/// Transcribe the last recording again off the shortcut thread, since in-app
+/// providers transcribe there
+#[cfg(desktop)]
+pub(crate) fn retry_last_transcription(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = commands::stt::retry_last(&app, None) {
+            log::warn!("Failed to retry the last transcription: {}", e);
+        }
+    });
+}
+
+/// Clear the pause, moving the recording's start forward so the paused time isn't
+/// counted in its duration
+#[cfg(desktop)]
+fn resume_recording_clock(state: &AppState) {
+    let Some(paused_for) = state
+        .paused_at
+        .lock()
+        .ok()
+        .and_then(|mut paused_at| paused_at.take())
+        .map(|paused_at| paused_at.elapsed())
+    else {
+        return;
+    };
+    if let Ok(mut started_at) = state.recording_started_at.lock() {
+        if let Some(started_at) = started_at.as_mut() {
+            *started_at += paused_for;
+        }
+    }
+}
+
+/// Whether the current recording is paused, so its audio should be dropped
+#[cfg(desktop)]
+pub(crate) fn is_recording_paused(app: &AppHandle) -> bool {
+    app.state::<AppState>()
+        .paused_at
+        .lock()
+        .is_ok_and(|paused_at| paused_at.is_some())
+}
+
+/// Watch a toggle-mode recording for silence if `auto_stop_silence_seconds` is set
+#[cfg(desktop)]
+fn arm_silence_auto_stop(app: &AppHandle, state: &AppState) {
+    let seconds: Option<f64> = get_setting_from_store(app, "auto_stop_silence_seconds", None);
+    let detector = seconds
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map(|seconds| {
+            SilenceDetector::new(
+                AudioFormat::default(),
+                std::time::Duration::from_secs_f64(seconds),
+            )
+        });
+    if let Ok(mut current) = state.silence_detector.lock() {
+        *current = detector;
+    }
+}
+
+/// Stop a toggle-mode recording once its audio has been silent for the auto-stop
+/// duration. Called with each chunk of audio fed to the STT provider.
+#[cfg(desktop)]
+pub(crate) fn auto_stop_on_silence(app: &AppHandle, samples: &[f32]) {
+    let state = app.state::<AppState>();
+    let silent = state.silence_detector.lock().is_ok_and(|mut detector| {
+        let silent = detector
+            .as_mut()
+            .is_some_and(|detector| detector.push(samples));
+        if silent {
+            // Only stop once, even if more audio arrives before recording ends
+            *detector = None;
+        }
+        silent
+    });
+    if !silent {
+        return;
+    }
+
+    log::info!("AutoStop: silence limit reached");
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        stop_recording(
+            &app,
+            &state,
+            &commands::audio::sound_settings(&app),
+            &app.try_state::<AudioMuteManager>(),
+            get_setting_from_store(&app, "auto_mute_audio", false),
+            "AutoStop",
+        );
+    });
+}
+
+/// Start recording on press and stop on release of the hold-to-record trigger.
+/// Shared by the keyboard shortcut and mouse button listeners.
+#[cfg(desktop)]
+pub(crate) fn handle_hold_trigger(app: &AppHandle, pressed: bool, source: &str) {
+    if get_setting_from_store(app, "hold_latch_enabled", false) {
+        handle_latching_hold_trigger(app, pressed, source);
+        return;
+    }
 
-    // Get audio mute manager if available
+    let state = app.state::<AppState>();
+    let sounds = commands::audio::sound_settings(app);
+    let auto_mute_audio: bool = get_setting_from_store(app, "auto_mute_audio", false);
     let audio_mute_manager = app.try_state::<AudioMuteManager>();
 
-    // Compare normalized strings directly
-    let is_toggle = shortcut_str == toggle_shortcut_str;
-    let is_hold = shortcut_str == hold_shortcut_str;
-    let is_paste_last = shortcut_str == paste_last_shortcut_str;
-
-    if is_toggle {
-        // Toggle mode: action happens on key release (debounced)
-        match event.state {
-            ShortcutState::Pressed => {
-                state.toggle_key_held.swap(true, Ordering::SeqCst);
-            }
-            ShortcutState::Released => {
-                if state.toggle_key_held.swap(false, Ordering::SeqCst) {
-                    if state.is_recording.load(Ordering::SeqCst) {
-                        stop_recording(
-                            app,
-                            &state,
-                            sound_enabled,
-                            &audio_mute_manager,
-                            auto_mute_audio,
-                            "Toggle",
-                        );
-                    } else {
-                        start_recording(
-                            app,
-                            &state,
-                            sound_enabled,
-                            &audio_mute_manager,
-                            auto_mute_audio,
-                            "Toggle",
-                        );
-                    }
-                }
-            }
+    if pressed {
+        if !state.ptt_key_held.swap(true, Ordering::SeqCst) {
+            start_recording(
+                app,
+                &state,
+                &sounds,
+                &audio_mute_manager,
+                auto_mute_audio,
+                source,
+            );
         }
-    } else if is_hold {
-        // Hold-to-Record: start on press, stop on release
-        match event.state {
-            ShortcutState::Pressed => {
-                if !state.ptt_key_held.swap(true, Ordering::SeqCst) {
-                    start_recording(
-                        app,
-                        &state,
-                        sound_enabled,
-                        &audio_mute_manager,
-                        auto_mute_audio,
-                        "Hold",
-                    );
-                }
+    } else if state.ptt_key_held.swap(false, Ordering::SeqCst) {
+        stop_recording(
+            app,
+            &state,
+            &sounds,
+            &audio_mute_manager,
+            auto_mute_audio,
+            source,
+        );
+    }
+}
+
+/// Hold trigger in tap-to-latch mode: holding records until release, while a quick tap
+/// keeps recording until the next tap or the latch time limit
+#[cfg(desktop)]
+fn handle_latching_hold_trigger(app: &AppHandle, pressed: bool, source: &str) {
+    let state = app.state::<AppState>();
+    let now = std::time::Instant::now();
+    let action = {
+        let Ok(mut latch) = state.hold_latch.lock() else {
+            return;
+        };
+        if pressed {
+            // Recording was stopped some other way (e.g. the toggle hotkey) while latched
+            if !state.is_recording.load(Ordering::SeqCst) {
+                latch.reset();
             }
-            ShortcutState::Released => {
-                if state.ptt_key_held.swap(false, Ordering::SeqCst) {
+            latch.press(now)
+        } else {
+            latch.release(now, hold_latch::HOLD_LATCH_TAP_THRESHOLD)
+        }
+    };
+
+    let sounds = commands::audio::sound_settings(app);
+    let auto_mute_audio: bool = get_setting_from_store(app, "auto_mute_audio", false);
+    let audio_mute_manager = app.try_state::<AudioMuteManager>();
+
+    match action {
+        LatchAction::Start => start_recording(
+            app,
+            &state,
+            &sounds,
+            &audio_mute_manager,
+            auto_mute_audio,
+            source,
+        ),
+        LatchAction::Stop => stop_recording(
+            app,
+            &state,
+            &sounds,
+            &audio_mute_manager,
+            auto_mute_audio,
+            source,
+        ),
+        LatchAction::Latch(id) => {
+            let max_seconds: u64 = get_setting_from_store(
+                app,
+                "hold_latch_max_seconds",
+                DEFAULT_HOLD_LATCH_MAX_SECONDS,
+            );
+            log::info!("{}: recording latched for up to {}s", source, max_seconds);
+
+            let app = app.clone();
+            let source = source.to_string();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_secs(max_seconds));
+                let state = app.state::<AppState>();
+                let expired = state
+                    .hold_latch
+                    .lock()
+                    .is_ok_and(|mut latch| latch.expire(id));
+                if expired {
+                    log::info!("{}: latch time limit reached", source);
                     stop_recording(
-                        app,
+                        &app,
                         &state,
-                        sound_enabled,
-                        &audio_mute_manager,
-                        auto_mute_audio,
-                        "Hold",
+                        &commands::audio::sound_settings(&app),
+                        &app.try_state::<AudioMuteManager>(),
+                        get_setting_from_store(&app, "auto_mute_audio", false),
+                        &source,
                     );
                 }
-            }
-        }
-    } else if is_paste_last {
-        // Paste last transcription: hold-to-paste (paste happens on release)
-        match event.state {
-            ShortcutState::Pressed => {
-                // Mark key as held (ignore OS key repeat)
-                state.paste_key_held.swap(true, Ordering::SeqCst);
-            }
-            ShortcutState::Released => {
-                if state.paste_key_held.swap(false, Ordering::SeqCst) {
-                    // Key released - do the paste
-                    log::info!("PasteLast: pasting last transcription");
-                    let history_storage = app.state::<HistoryStorage>();
-
-                    if let Ok(entries) = history_storage.get_all(Some(1)) {
-                        if let Some(entry) = entries.first() {
-                            if let Err(e) = commands::text::type_text_blocking(&entry.text) {
-                                log::error!("Failed to paste last transcription: {}", e);
-                            }
-                        } else {
-                            log::info!("PasteLast: no history entries available");
-                        }
-                    }
-                }
-            }
+            });
         }
-    } else {
-        log::warn!("Unknown shortcut: {}", shortcut_str);
+        LatchAction::Ignore => {}
     }
 }
 
@@ -261,11 +870,27 @@ fn is_audio_mute_supported() -> bool {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Initialize logger
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    // Log to the console until setup knows where the log files go
+    logging::init();
 
     let mut builder = tauri::Builder::default();
 
+    // Must be the first plugin so a second launch exits before doing anything else
+    #[cfg(desktop)]
+    {
+        builder = builder
+            .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+                // Links are forwarded to the deep link plugin; a plain relaunch opens settings
+                if deep_link::is_link_launch(&args) {
+                    return;
+                }
+                if let Err(e) = show_main_window(app) {
+                    log::warn!("Failed to show main window: {}", e);
+                }
+            }))
+            .plugin(tauri_plugin_deep_link::init());
+    }
+
     #[cfg(desktop)]
     {
         builder = builder.plugin(build_global_shortcut_plugin());
@@ -276,31 +901,205 @@ pub fn run() {
         builder = builder.plugin(tauri_nspanel::init());
     }
 
+    #[cfg(desktop)]
+    {
+        builder = builder.manage(commands::updater::PendingUpdate::default());
+        if let Some(updater) = commands::updater::build_updater_plugin() {
+            builder = builder.plugin(updater);
+        }
+    }
+
     builder
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .manage(AppState::default())
+        .manage(connection::ConnectionManager::default())
+        .manage(capture::NativeCapture::default())
+        .manage(capture::LocalCaptureFlush::default())
+        .manage(commands::capture::MicTest::default())
+        .manage(auth::AuthRefresh::default())
+        .manage(TurnQueue::default())
+        .manage(RecordingLimit::default())
+        .manage(PasteCycle::default())
+        .manage(mic_monitor::MicMonitor::default())
+        .manage(formatter::FormatPreviews::default())
+        .manage(dictionary_suggestions::DictionarySuggestions::default())
+        .manage(SessionStore::default())
+        .manage(LaunchOptions::from_env())
+        .on_window_event(commands::window_state::track_window_event)
         .invoke_handler(tauri::generate_handler![
             commands::text::type_text,
+            commands::text::insert_dictation,
+            commands::text::is_accessibility_insertion_supported,
             commands::text::get_server_url,
+            commands::text::get_insertion_diagnostics,
+            commands::updater::is_updater_available,
+            commands::updater::check_for_updates,
+            commands::updater::install_update,
+            commands::active_window::get_recording_app,
+            commands::text_transforms::test_text_transform,
+            commands::text_transforms::test_text_transforms,
             commands::settings::register_shortcuts,
             commands::settings::unregister_shortcuts,
+            commands::settings::test_hotkey_availability,
+            commands::settings::get_default_hotkeys,
+            commands::settings::list_feature_flags,
+            commands::settings::set_feature_flag,
             is_audio_mute_supported,
+            commands::audio::report_audio_devices,
+            commands::audio::report_mic_permission,
+            commands::audio::request_mic_permission,
+            commands::audio::validate_sound_file,
+            commands::capture::start_native_capture,
+            commands::capture::list_capture_formats,
+            commands::capture::update_capture_format,
+            commands::capture::start_mic_test,
+            commands::capture::stop_mic_test,
+            commands::capture::stop_native_capture,
+            commands::capture::local_capture_flushed,
+            commands::hid::list_hid_devices,
+            commands::hid::detect_hid_button,
+            commands::remote_control::get_remote_control_token,
+            commands::remote_control::regenerate_remote_control_token,
+            commands::audio::preview_sound,
+            commands::connection::report_connection_state,
+            commands::connection::get_connection_status,
+            commands::connection::reconnect_server,
+            commands::connection::test_server_connection,
+            commands::connection::update_proxy_settings,
             commands::history::add_history_entry,
+            commands::history::report_turn_failed,
             commands::history::get_history,
             commands::history::delete_history_entry,
             commands::history::clear_history,
+            commands::history::pin_history_entry,
+            commands::history::list_pinned,
+            commands::history::paste_history_entry,
+            commands::history::re_run_with_current_settings,
+            commands::history::get_turn_diff,
+            commands::history::correct_last_entry,
+            commands::dictionary::list_dictionary_entries,
+            commands::dictionary::add_dictionary_entry,
+            commands::dictionary::update_dictionary_entry,
+            commands::dictionary::remove_dictionary_entry,
+            commands::dictionary::import_dictionary,
+            commands::snippets::list_snippets,
+            commands::snippets::add_snippet,
+            commands::snippets::update_snippet,
+            commands::snippets::remove_snippet,
+            commands::history::get_dictionary_suggestions,
+            commands::history::resolve_dictionary_suggestion,
+            commands::history::complete_format_preview,
+            commands::usage::get_usage_stats,
+            commands::usage::reset_usage_stats,
+            commands::telemetry::get_telemetry,
+            commands::telemetry::purge_telemetry,
+            commands::support::generate_support_bundle,
+            commands::diagnostics::run_diagnostics,
+            commands::benchmark::run_latency_benchmark,
+            commands::window_state::reset_window_state,
+            commands::stt::cycle_stt_provider,
+            commands::prompt_presets::list_prompt_presets,
+            commands::prompt_presets::save_prompt_preset,
+            commands::prompt_presets::rename_prompt_preset,
+            commands::prompt_presets::delete_prompt_preset,
+            commands::prompt_presets::apply_prompt_preset,
+            commands::prompt_presets::export_prompt_presets,
+            commands::prompt_presets::import_prompt_presets,
+            commands::palette::open_palette_window,
+            commands::palette::hide_palette_window,
+            commands::palette::list_palette_items,
+            commands::palette::paste_from_palette,
+            commands::palette::paste_pinned_from_palette,
+            commands::stt::select_stt_provider,
+            commands::formatting::switch_formatting,
+            commands::dictation_pause::get_dictation_paused,
+            commands::dictation_pause::switch_dictation_paused,
+            commands::export::export_data,
+            commands::integrity::get_data_file_issues,
+            commands::integrity::dismiss_data_file_issues,
             commands::overlay::resize_overlay,
+            commands::overlay::set_overlay_editing,
+            commands::overlay::set_overlay_position,
+            commands::overlay::reset_overlay_position,
+            commands::recordings::list_recordings,
+            commands::recordings::delete_recording,
+            commands::pending_transcriptions::list_pending_transcriptions,
+            commands::pending_transcriptions::retry_pending_transcriptions,
+            commands::secrets::set_api_key,
+            commands::secrets::get_api_key_status,
+            commands::secrets::delete_api_key,
+            commands::auth::set_auth_token,
+            commands::auth::get_auth_status,
+            commands::auth::clear_auth_token,
+            commands::auth::get_auth_header,
+            commands::notes::get_notes_file_path,
+            commands::session::get_session_transcript,
+            commands::session::reset_session_transcript,
+            commands::session::export_session_transcript,
+            commands::session::open_session_window,
+            commands::stt::refresh_stt_provider,
+            commands::stt::send_stt_audio,
+            commands::stt::retry_last_transcription,
+            commands::stt::report_transcription_partial,
+            commands::stt::report_server_turns_finished,
+            commands::stt::get_stt_partials,
+            commands::stt::list_whisper_models,
+            commands::stt::download_whisper_model,
+            commands::stt::delete_whisper_model,
         ])
         .setup(|app| {
-            // Initialize history storage
             let app_data_dir = app
                 .path()
                 .app_data_dir()
                 .expect("Failed to get app data directory");
+            if let Err(e) = logging::start_file_logging(&app_data_dir) {
+                log::warn!("{}", e);
+            }
 
-            let history_storage = HistoryStorage::new(app_data_dir);
+            // Check data files before the settings store or history database opens them,
+            // so a corrupt file is moved aside instead of silently ignored
+            let data_file_issues = integrity::check_data_files(&app_data_dir);
+            app.manage(integrity::DataFileIssues::new(data_file_issues));
+
+            // Event bus first so every subsystem can subscribe during setup
+            app.manage(EventBus::default());
+            spawn_core_subscribers(app.handle());
+            settings_broadcast::spawn(app.handle());
+
+            // Initialize history storage
+            let history_storage = HistoryStorage::new(&app_data_dir)?;
             app.manage(history_storage);
+            app.manage(UsageStore::new(&app_data_dir)?);
+            app.manage(TelemetryStore::new(&app_data_dir));
+            app.manage(WindowStateStore::new(&app_data_dir));
+            commands::telemetry::record_session(app.handle());
+
+            // The dictionary used to be a free-text prompt section; import it once
+            let new_dictionary = !app_data_dir.join(DICTIONARY_DB_FILE).exists();
+            app.manage(DictionaryStore::new(&app_data_dir)?);
+            if new_dictionary {
+                commands::dictionary::import_prompt_dictionary(app.handle());
+            }
+            app.manage(SnippetStore::new(&app_data_dir)?);
+            app.manage(RecordingArchive::new(&app_data_dir.join("recordings")));
+            app.manage(PendingTranscriptions::new(
+                &app_data_dir.join("pending-transcriptions"),
+            ));
+
+            // Initialize the STT providers selected in settings, after the model store
+            // that local Whisper loads downloaded models from
+            app.manage(WhisperModelStore::new(&app_data_dir.join("models")));
+            commands::integrity::spawn_model_check(app.handle());
+            let stt_manager = SttManager::new(stt::provider_from_settings(app.handle()));
+            stt_manager.set_fallback(stt::fallback_provider_from_settings(app.handle()))?;
+            app.manage(stt_manager);
+            app.manage(commands::stt::ServerSttProviders::default());
+            app.manage(audio_upload::ServerAudioEncodings::default());
+            commands::stt::listen_for_server_providers(app.handle());
+
+            // Move a plain-text auth token into the keyring and keep it refreshed
+            commands::auth::init(app.handle());
 
             // Initialize audio mute manager (may be None on unsupported platforms)
             if let Some(audio_mute_manager) = AudioMuteManager::new() {
@@ -310,69 +1109,47 @@ pub fn run() {
             // Register shortcuts from store (now that store plugin is available)
             #[cfg(desktop)]
             {
+                app.manage(trigger_queue::TriggerQueue::start(app.handle()));
+                watch_max_duration(app.handle());
+                app.manage(input_listener::InputListener::default());
+                app.manage(mouse_trigger::MouseTrigger::default());
+                app.manage(double_tap::DoubleTapTrigger::default());
+                app.manage(media_keys::MediaKeyTrigger::default());
+                app.manage(hid_input::HidTriggers::default());
+                hid_input::init(app.handle());
+                app.manage(remote_control::ControlServer::default());
+                remote_control::init(app.handle());
+                app.manage(api::ApiServer::default());
+                api::init(app.handle());
+                deep_link::init(app.handle());
+                #[cfg(target_os = "linux")]
+                dbus::init(app.handle());
+                app.manage(sequence::SequenceState::default());
+                app.manage(commands::settings::ShortcutFailures::default());
+                #[cfg(target_os = "linux")]
+                app.manage(wayland_shortcuts::WaylandShortcuts::default());
+                if let Err(e) = settings::store_default_hotkeys(app.handle()) {
+                    log::warn!("Failed to store default hotkeys: {}", e);
+                }
                 register_initial_shortcuts(app.handle())?;
-            }
+                #[cfg(target_os = "windows")]
+                commands::settings::watch_keyboard_layout(app.handle());
 
-            // Create overlay window
-            let overlay = tauri::WebviewWindowBuilder::new(
-                app,
-                "overlay",
-                tauri::WebviewUrl::App("overlay.html".into()),
-            )
-            .title("Voice Overlay")
-            .inner_size(48.0, 48.0)
-            .decorations(false)
-            .transparent(true)
-            .shadow(false)
-            .always_on_top(true)
-            .skip_taskbar(true)
-            .resizable(false)
-            .focused(false)
-            .focusable(false)
-            .accept_first_mouse(true)
-            .visible(true)
-            .visible_on_all_workspaces(true)
-            .background_throttling(BackgroundThrottlingPolicy::Disabled)
-            .build()?;
-
-            // On macOS, convert to NSPanel for better fullscreen app behavior
-            #[cfg(target_os = "macos")]
-            {
-                use tauri_nspanel::{CollectionBehavior, PanelLevel, WebviewWindowExt};
-                match overlay.to_panel::<OverlayPanel>() {
-                    Ok(panel) => {
-                        // Configure panel to float above fullscreen apps
-                        panel.set_level(PanelLevel::ScreenSaver.value());
-                        panel.set_floating_panel(true);
-
-                        // Set collection behavior to appear on all spaces including fullscreen
-                        let behavior = CollectionBehavior::new()
-                            .can_join_all_spaces()
-                            .full_screen_auxiliary();
-                        panel.set_collection_behavior(behavior.value());
-
-                        // Set style mask to non-activating panel
-                        let style = tauri_nspanel::StyleMask::empty().nonactivating_panel();
-                        panel.set_style_mask(style.value());
-
-                        log::info!("[NSPanel] Successfully converted overlay to NSPanel");
-                    }
-                    Err(e) => {
-                        log::error!("[NSPanel] Failed to convert overlay to NSPanel: {:?}", e);
-                    }
+                // Pick up settings edited by hand while the app runs
+                if let Err(e) = settings_watcher::watch(app.handle(), &app_data_dir) {
+                    log::warn!("{}", e);
                 }
             }
 
-            // Position bottom-right
-            if let Ok(Some(monitor)) = overlay.current_monitor() {
-                let size = monitor.size();
-                let scale = monitor.scale_factor();
-                let x = (size.width as f64 / scale) as i32 - 150;
-                let y = (size.height as f64 / scale) as i32 - 100;
-                let _ = overlay.set_position(tauri::Position::Logical(tauri::LogicalPosition {
-                    x: x as f64,
-                    y: y as f64,
-                }));
+            // Create windows requested at launch; the tray can open the main window later
+            let launch_options = app.state::<LaunchOptions>();
+            if launch_options.show_main_window {
+                show_main_window(app.handle())?;
+            }
+            if launch_options.show_overlay {
+                create_overlay_window(app.handle())?;
+            } else {
+                log::warn!("Overlay disabled at launch; server dictation is unavailable");
             }
 
             // Setup system tray
@@ -380,37 +1157,508 @@ pub fn run() {
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            // Keep running in the tray when the last window closes (e.g. headless mode)
+            if let tauri::RunEvent::ExitRequested {
+                code: None, api, ..
+            } = event
+            {
+                api.prevent_exit();
+            }
+        });
 }
 
-fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+/// Subscribers for state owned by the core app (connection flag, tray, history, session)
+fn spawn_core_subscribers(app: &AppHandle) {
+    events::spawn_subscriber(app, "state", |app, event| match event {
+        AppEvent::ConnectionChanged { connected } => {
+            app.state::<AppState>()
+                .server_connected
+                .store(connected, Ordering::SeqCst);
+            // Responses for turns the server was handling will never arrive
+            if !connected {
+                app.state::<TurnQueue>().finish_server_turns(usize::MAX);
+            }
+        }
+        AppEvent::DeviceChanged { device_id } => {
+            log::info!(
+                "Input device changed to {}",
+                device_id.as_deref().unwrap_or("system default")
+            );
+        }
+        _ => {}
+    });
+
+    // Tray tooltip reflects recording, connection, secure input and do-not-disturb state,
+    // the menu the language
+    let mut is_recording = false;
+    let mut is_connected = false;
+    let mut secure_input_blocked = false;
+    let mut dictation_paused = false;
+    events::spawn_subscriber(app, "tray", move |app, event| {
+        let Some(tray) = app.tray_by_id(TRAY_ID) else {
+            return;
+        };
+        match event {
+            AppEvent::RecordingStarted => {
+                is_recording = true;
+                secure_input_blocked = false;
+            }
+            AppEvent::RecordingStopped => is_recording = false,
+            AppEvent::SecureInputBlocked => secure_input_blocked = true,
+            AppEvent::ConnectionChanged { connected } => is_connected = connected,
+            AppEvent::DictationPausedChanged { paused } => {
+                dictation_paused = paused;
+                refresh_tray_menu(app);
+            }
+            AppEvent::SettingsChanged => {
+                refresh_tray_menu(app);
+                return;
+            }
+            _ => return,
+        }
+        let _ = tray.set_tooltip(Some(tray_tooltip(
+            is_recording,
+            is_connected,
+            secure_input_blocked,
+            dictation_paused,
+        )));
+    });
+
+    commands::history::spawn_history_subscriber(app);
+    commands::session::spawn_session_subscriber(app);
+    commands::telemetry::spawn_telemetry_subscriber(app);
+    commands::pending_transcriptions::spawn_pending_subscriber(app);
+    commands::connection::spawn_endpoint_subscriber(app);
+    commands::overlay::spawn_overlay_subscriber(app);
+    commands::audio::spawn_sound_subscriber(app);
+    commands::capture::spawn_gain_subscriber(app);
+}
+
+/// Tray tooltip text for the current recording, connection and do-not-disturb state
+fn tray_tooltip(
+    is_recording: bool,
+    is_connected: bool,
+    secure_input_blocked: bool,
+    dictation_paused: bool,
+) -> &'static str {
+    match (
+        is_recording,
+        dictation_paused,
+        is_connected,
+        secure_input_blocked,
+    ) {
+        (true, _, _, _) => "Tambourine - Recording",
+        (false, true, _, _) => "Tambourine - Dictation paused",
+        (false, false, _, true) => "Tambourine - Secure input blocked insertion",
+        (false, false, true, false) => "Tambourine",
+        (false, false, false, false) => "Tambourine - Disconnected",
+    }
+}
+
+/// Create the recording overlay where it was last placed, by default in the
+/// bottom-right corner of the screen. Its connection to the server goes through
+/// the proxy from settings when the webview supports it.
+fn create_overlay_window(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let overlay_scale = commands::overlay::overlay_scale(app);
+    let proxy_url = match commands::connection::proxy_from_settings(app) {
+        Ok(Some(proxy)) => {
+            let url = proxy.webview_url();
+            if url.is_none() {
+                log::warn!(
+                    "Overlay connecting without proxy {}: needs HTTP or SOCKS5 without credentials",
+                    proxy.url
+                );
+            }
+            url
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("Ignoring invalid proxy: {}", e);
+            None
+        }
+    };
+    let mut overlay = tauri::WebviewWindowBuilder::new(
+        app,
+        "overlay",
+        tauri::WebviewUrl::App("overlay.html".into()),
+    )
+    .title("Voice Overlay")
+    .inner_size(48.0 * overlay_scale, 48.0 * overlay_scale)
+    .decorations(false)
+    .transparent(true)
+    .shadow(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .focused(false)
+    .focusable(false)
+    .accept_first_mouse(true)
+    .visible(true)
+    .visible_on_all_workspaces(true)
+    .background_throttling(BackgroundThrottlingPolicy::Disabled);
+    if let Some(proxy_url) = proxy_url {
+        overlay = overlay.proxy_url(tauri::Url::parse(&proxy_url)?);
+    }
+    let overlay = overlay.build()?;
+
+    // On macOS, convert to NSPanel for better fullscreen app behavior
+    #[cfg(target_os = "macos")]
+    {
+        use tauri_nspanel::{CollectionBehavior, PanelLevel, WebviewWindowExt};
+        match overlay.to_panel::<OverlayPanel>() {
+            Ok(panel) => {
+                // Configure panel to float above fullscreen apps
+                panel.set_level(PanelLevel::ScreenSaver.value());
+                panel.set_floating_panel(true);
+
+                // Set collection behavior to appear on all spaces including fullscreen
+                let behavior = CollectionBehavior::new()
+                    .can_join_all_spaces()
+                    .full_screen_auxiliary();
+                panel.set_collection_behavior(behavior.value());
+
+                // Set style mask to non-activating panel
+                let style = tauri_nspanel::StyleMask::empty().nonactivating_panel();
+                panel.set_style_mask(style.value());
+
+                log::info!("[NSPanel] Successfully converted overlay to NSPanel");
+            }
+            Err(e) => {
+                log::error!("[NSPanel] Failed to convert overlay to NSPanel: {:?}", e);
+            }
+        }
+    }
+
+    // Restore the zoom and position from the last launch
+    if let Err(e) = commands::overlay::restore_overlay_geometry(&overlay) {
+        log::warn!("Failed to restore overlay position: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Show the main window, creating it from the app config if it was never opened or was closed
+fn show_main_window(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let window = match app.get_webview_window(MAIN_WINDOW_LABEL) {
+        Some(window) => window,
+        None => {
+            let config = app
+                .config()
+                .app
+                .windows
+                .iter()
+                .find(|config| config.label == MAIN_WINDOW_LABEL)
+                .ok_or("Main window is missing from the app config")?;
+            let window = tauri::WebviewWindowBuilder::from_config(app, config)?
+                .visible(false)
+                .build()?;
+            commands::window_state::restore_window_state(&window);
+            window
+        }
+    };
+    window.show()?;
+    window.set_focus()?;
+    Ok(())
+}
+
+/// Paste the most recent history entry into the focused app
+#[cfg(desktop)]
+pub(crate) fn paste_last_transcription(app: &AppHandle) {
+    log::info!("PasteLast: pasting last transcription");
+    match commands::history::paste_history_entry_blocking(app, 0, 0) {
+        Ok(true) => {}
+        Ok(false) => log::info!("PasteLast: no history entries available"),
+        Err(e) => log::error!("Failed to paste last transcription: {}", e),
+    }
+}
+
+/// Paste the transcription before the one pasted last, replacing it, or the most
+/// recent transcription if nothing was pasted just before
+#[cfg(desktop)]
+pub(crate) fn cycle_history_paste(app: &AppHandle) {
+    let step = app
+        .state::<PasteCycle>()
+        .next_step(std::time::Instant::now(), paste_cycle::PASTE_CYCLE_WINDOW);
+    log::info!("CycleHistory: pasting history entry {}", step.index);
+
+    let result = match commands::history::paste_history_entry_blocking(
+        app,
+        step.index,
+        step.replace_chars,
+    ) {
+        // Past the oldest entry, so wrap around to the most recent one
+        Ok(false) if step.index > 0 => {
+            commands::history::paste_history_entry_blocking(app, 0, step.replace_chars)
+        }
+        result => result,
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => log::info!("CycleHistory: no history entries available"),
+        Err(e) => log::error!("Failed to paste history entry: {}", e),
+    }
+}
+
+/// Rebuild the tray menu, e.g. after a setting it shows changed
+pub(crate) fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => log::warn!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
+/// Prefix of the tray menu IDs that choose a dictation language
+const TRAY_LANGUAGE_PREFIX: &str = "language:";
+
+/// Tray menu ID that turns on language detection
+const TRAY_LANGUAGE_AUTO: &str = "language:auto";
+
+/// Tray submenu for choosing the dictation language among the user's languages,
+/// with the current choice checked
+fn build_language_submenu(app: &AppHandle) -> tauri::Result<Submenu<tauri::Wry>> {
+    let current = commands::language::dictation_language(app);
+    let mut languages = commands::language::dictation_languages(app);
+    if let Some(code) = &current {
+        if !languages.contains(code) {
+            languages.push(code.clone());
+        }
+    }
+
+    let submenu = Submenu::with_id(app, "language", "Dictation Language", true)?;
+    submenu.append(&CheckMenuItem::with_id(
+        app,
+        TRAY_LANGUAGE_AUTO,
+        "Auto-detect",
+        true,
+        current.is_none(),
+        None::<&str>,
+    )?)?;
+    for code in languages {
+        let name = language::language_name(&code).unwrap_or(&code);
+        submenu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{}{}", TRAY_LANGUAGE_PREFIX, code),
+            name,
+            true,
+            current.as_deref() == Some(code.as_str()),
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+/// Prefix of the tray menu IDs that choose an STT provider
+const TRAY_STT_PROVIDER_PREFIX: &str = "stt_provider:";
+
+/// Tray submenu for choosing among the STT providers the server offers, with the
+/// current choice checked. Disabled until the server has listed them.
+fn build_stt_provider_submenu(app: &AppHandle) -> tauri::Result<Submenu<tauri::Wry>> {
+    let current: Option<String> = get_setting_from_store(app, "stt_provider", None);
+    let providers = app.state::<commands::stt::ServerSttProviders>().get();
+    let submenu = Submenu::with_id(
+        app,
+        "stt_provider",
+        "Speech-to-Text Provider",
+        !providers.is_empty(),
+    )?;
+    for provider in providers {
+        submenu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{}{}", TRAY_STT_PROVIDER_PREFIX, provider.value),
+            &provider.label,
+            true,
+            current.as_deref() == Some(provider.value.as_str()),
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+/// Prefix of the tray menu IDs that choose a prompt preset
+const TRAY_PROMPT_PRESET_PREFIX: &str = "prompt_preset:";
+
+/// Tray submenu for choosing among the saved prompt presets, with the active one
+/// checked. Disabled until a preset is saved.
+fn build_prompt_preset_submenu(app: &AppHandle) -> tauri::Result<Submenu<tauri::Wry>> {
+    let presets = commands::prompt_presets::stored_presets(app);
+    let active = commands::prompt_presets::active_preset_id(app);
+    let submenu = Submenu::with_id(
+        app,
+        "prompt_preset",
+        "Formatting Prompt",
+        !presets.is_empty(),
+    )?;
+    for preset in presets {
+        submenu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{}{}", TRAY_PROMPT_PRESET_PREFIX, preset.id),
+            &preset.name,
+            true,
+            active.as_deref() == Some(preset.id.as_str()),
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
     let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
+    let toggle_item = MenuItem::with_id(
+        app,
+        "toggle_recording",
+        "Start/Stop Recording",
+        true,
+        None::<&str>,
+    )?;
+    let paste_last_item = MenuItem::with_id(
+        app,
+        "paste_last",
+        "Paste Last Transcription",
+        true,
+        None::<&str>,
+    )?;
+    let retry_last_item = MenuItem::with_id(
+        app,
+        "retry_last",
+        "Retry Last Transcription",
+        true,
+        None::<&str>,
+    )?;
+    let notes_item = CheckMenuItem::with_id(
+        app,
+        "notes",
+        "Dictate to Notes",
+        true,
+        get_setting_from_store(app, "notes_enabled", false),
+        None::<&str>,
+    )?;
+    let formatting_item = CheckMenuItem::with_id(
+        app,
+        "formatting",
+        "Format with LLM",
+        true,
+        formatter::formatting_enabled(app),
+        None::<&str>,
+    )?;
+    let prompt_preset_menu = build_prompt_preset_submenu(app)?;
+    let language_menu = build_language_submenu(app)?;
+    let stt_provider_menu = build_stt_provider_submenu(app)?;
+    let session_item = MenuItem::with_id(app, "session", "Session Transcript", true, None::<&str>)?;
+    let palette_item = MenuItem::with_id(app, "palette", "Quick Actions", true, None::<&str>)?;
+    let dictation_paused_item = CheckMenuItem::with_id(
+        app,
+        "dictation_paused",
+        "Pause Dictation",
+        true,
+        commands::dictation_pause::is_dictation_paused(app),
+        None::<&str>,
+    )?;
     let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show_item, &quit_item])?;
+    Menu::with_items(
+        app,
+        &[
+            &show_item,
+            &toggle_item,
+            &paste_last_item,
+            &retry_last_item,
+            &notes_item,
+            &formatting_item,
+            &prompt_preset_menu,
+            &language_menu,
+            &stt_provider_menu,
+            &session_item,
+            &palette_item,
+            &dictation_paused_item,
+            &quit_item,
+        ],
+    )
+}
+
+fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app)?;
 
     // Load the template icon for macOS menu bar
     // The @2x version is automatically used for retina displays
     let icon_bytes = include_bytes!("../icons/tray-iconTemplate@2x.png");
     let icon = tauri::image::Image::from_bytes(icon_bytes)?;
 
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .icon_as_template(true)
+        .tooltip(tray_tooltip(false, false, false, false))
         .menu(&menu)
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| match event.id.as_ref() {
             "show" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
+                if let Err(e) = show_main_window(app) {
+                    log::error!("Failed to open main window: {}", e);
                 }
             }
-            "quit" => {
-                // Emit disconnect request to frontend before exiting
-                if let Some(window) = app.get_webview_window("overlay") {
-                    let _ = window.emit("request-disconnect", ());
+            // Queued behind other triggers so a tray click and a hotkey can't race
+            #[cfg(desktop)]
+            "toggle_recording" => app
+                .state::<trigger_queue::TriggerQueue>()
+                .send(trigger_queue::Trigger::Toggle { source: "Tray" }),
+            // Run off the main thread, pasting blocks
+            #[cfg(desktop)]
+            "paste_last" => {
+                let app = app.clone();
+                std::thread::spawn(move || paste_last_transcription(&app));
+            }
+            #[cfg(desktop)]
+            "retry_last" => retry_last_transcription(app),
+            "notes" => {
+                let enabled = !get_setting_from_store(app, "notes_enabled", false);
+                if let Err(e) = commands::notes::set_notes_enabled(app, enabled) {
+                    log::error!("Failed to switch notes mode: {}", e);
+                }
+            }
+            "formatting" => commands::formatting::toggle_formatting(app),
+            "dictation_paused" => commands::dictation_pause::toggle_dictation_paused(app),
+            TRAY_LANGUAGE_AUTO => {
+                if let Err(e) = commands::language::set_dictation_language(app, None) {
+                    log::error!("Failed to switch dictation language: {}", e);
+                }
+            }
+            id if id.starts_with(TRAY_LANGUAGE_PREFIX) => {
+                let code = &id[TRAY_LANGUAGE_PREFIX.len()..];
+                if let Err(e) = commands::language::set_dictation_language(app, Some(code)) {
+                    log::error!("Failed to switch dictation language: {}", e);
                 }
+            }
+            id if id.starts_with(TRAY_PROMPT_PRESET_PREFIX) => {
+                let preset_id = &id[TRAY_PROMPT_PRESET_PREFIX.len()..];
+                if let Err(e) = commands::prompt_presets::apply_preset(app, preset_id) {
+                    log::error!("Failed to switch prompt preset: {}", e);
+                }
+            }
+            id if id.starts_with(TRAY_STT_PROVIDER_PREFIX) => {
+                let value = &id[TRAY_STT_PROVIDER_PREFIX.len()..];
+                if let Err(e) = commands::stt::set_stt_provider(app, value) {
+                    log::error!("Failed to switch STT provider: {}", e);
+                }
+            }
+            "session" => {
+                if let Err(e) = commands::session::show_session_window(app) {
+                    log::error!("Failed to open session transcript window: {}", e);
+                }
+            }
+            "palette" => {
+                if let Err(e) = commands::palette::show_palette_window(app) {
+                    log::error!("Failed to open command palette: {}", e);
+                }
+            }
+            "quit" => {
+                // Stop reconnecting and ask the overlay to disconnect before exiting
+                commands::connection::shut_down(app);
                 // Give frontend time to disconnect gracefully
                 std::thread::sleep(std::time::Duration::from_millis(500));
                 app.exit(0);
@@ -425,12 +1673,14 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
             } = event
             {
                 let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    if window.is_visible().unwrap_or(false) {
+                match app.get_webview_window(MAIN_WINDOW_LABEL) {
+                    Some(window) if window.is_visible().unwrap_or(false) => {
                         let _ = window.hide();
-                    } else {
-                        let _ = window.show();
-                        let _ = window.set_focus();
+                    }
+                    _ => {
+                        if let Err(e) = show_main_window(app) {
+                            log::error!("Failed to open main window: {}", e);
+                        }
                     }
                 }
             }
@@ -446,39 +1696,16 @@ fn build_global_shortcut_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
     tauri_plugin_global_shortcut::Builder::new().build()
 }
 
-/// Register shortcuts from store settings (called from setup() after store plugin is available)
+/// Register shortcuts from store settings (called from setup() after store plugin is available).
+/// Conflicts are reported to the frontend via `shortcut-registration-failed` rather than
+/// aborting startup, so the remaining shortcuts and the UI still work.
 #[cfg(desktop)]
 fn register_initial_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri_plugin_global_shortcut::GlobalShortcutExt;
-
-    // Read hotkeys from store with defaults
-    let toggle_hotkey: HotkeyConfig =
-        get_setting_from_store(app, "toggle_hotkey", HotkeyConfig::default_toggle());
-    let hold_hotkey: HotkeyConfig =
-        get_setting_from_store(app, "hold_hotkey", HotkeyConfig::default_hold());
-    let paste_last_hotkey: HotkeyConfig =
-        get_setting_from_store(app, "paste_last_hotkey", HotkeyConfig::default_paste_last());
-
-    // Convert to shortcuts with validation (fall back to defaults if invalid)
-    let toggle_shortcut = toggle_hotkey.to_shortcut_or_default(HotkeyConfig::default_toggle);
-    let hold_shortcut = hold_hotkey.to_shortcut_or_default(HotkeyConfig::default_hold);
-    let paste_last_shortcut =
-        paste_last_hotkey.to_shortcut_or_default(HotkeyConfig::default_paste_last);
-
-    log::info!(
-        "Registering shortcuts - Toggle: {}, Hold: {}, PasteLast: {}",
-        toggle_hotkey.to_shortcut_string(),
-        hold_hotkey.to_shortcut_string(),
-        paste_last_hotkey.to_shortcut_string()
-    );
-
-    let shortcuts: Vec<Shortcut> = vec![toggle_shortcut, hold_shortcut, paste_last_shortcut];
-
-    app.global_shortcut()
-        .on_shortcuts(shortcuts, |app, shortcut, event| {
-            handle_shortcut_event(app, shortcut, &event);
-        })?;
-
-    log::info!("Shortcuts registered successfully");
+    let failures = commands::settings::register_shortcuts_from_store(app);
+    if failures.is_empty() {
+        log::info!("Shortcuts registered successfully");
+    } else {
+        log::warn!("{}", settings::describe_registration_failures(&failures));
+    }
     Ok(())
 }
@@ -0,0 +1,233 @@
+//! Simulated key presses on Linux for whichever display server is running.
+//!
+//! enigo sends X11 (XTest) events, which only reach XWayland windows in a Wayland
+//! session. There, keys go through wtype (virtual-keyboard protocol, wlroots
+//! compositors) or ydotool (uinput, any compositor while its daemon runs) instead.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Display server of the current session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayServer {
+    X11,
+    Wayland,
+    Unknown,
+}
+
+impl DisplayServer {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::X11 => "x11",
+            Self::Wayland => "wayland",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Work out the display server from `XDG_SESSION_TYPE`, `WAYLAND_DISPLAY` and
+/// `DISPLAY`
+pub fn detect_display_server(
+    session_type: Option<&str>,
+    wayland_display: Option<&str>,
+    display: Option<&str>,
+) -> DisplayServer {
+    let is_set = |value: Option<&str>| value.is_some_and(|value| !value.trim().is_empty());
+    match session_type.map(str::to_lowercase).as_deref() {
+        Some("wayland") => DisplayServer::Wayland,
+        Some("x11") => DisplayServer::X11,
+        _ if is_set(wayland_display) => DisplayServer::Wayland,
+        _ if is_set(display) => DisplayServer::X11,
+        _ => DisplayServer::Unknown,
+    }
+}
+
+/// Display server of this session, detected once
+pub fn display_server() -> DisplayServer {
+    static DISPLAY_SERVER: OnceLock<DisplayServer> = OnceLock::new();
+    *DISPLAY_SERVER.get_or_init(|| {
+        let var = |name: &str| std::env::var(name).ok();
+        detect_display_server(
+            var("XDG_SESSION_TYPE").as_deref(),
+            var("WAYLAND_DISPLAY").as_deref(),
+            var("DISPLAY").as_deref(),
+        )
+    })
+}
+
+/// Command-line tool that simulates keys on Wayland
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaylandTool {
+    Wtype,
+    Ydotool,
+}
+
+/// Modifier held while pressing a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyModifier {
+    Ctrl,
+    Shift,
+}
+
+/// Key pressed by a shortcut, voice command or newline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyName {
+    Letter(char),
+    Return,
+    Tab,
+    BackSpace,
+}
+
+/// XKB name of a key, for wtype
+fn key_name(key: KeyName) -> String {
+    match key {
+        KeyName::Letter(letter) => letter.to_ascii_lowercase().to_string(),
+        KeyName::Return => "Return".to_string(),
+        KeyName::Tab => "Tab".to_string(),
+        KeyName::BackSpace => "BackSpace".to_string(),
+    }
+}
+
+/// Linux input event code of a modifier, for ydotool
+fn modifier_code(modifier: KeyModifier) -> u16 {
+    match modifier {
+        KeyModifier::Ctrl => 29,
+        KeyModifier::Shift => 42,
+    }
+}
+
+/// Linux input event code of a key, for ydotool
+fn key_code(key: KeyName) -> Option<u16> {
+    const LETTERS: &str = "qwertyuiopasdfghjklzxcvbnm";
+    const ROW_STARTS: [(usize, u16); 3] = [(0, 16), (10, 30), (19, 44)];
+    match key {
+        KeyName::Return => Some(28),
+        KeyName::Tab => Some(15),
+        KeyName::BackSpace => Some(14),
+        KeyName::Letter(letter) => {
+            let index = LETTERS.find(letter.to_ascii_lowercase())?;
+            let (row_start, first_code) = ROW_STARTS
+                .iter()
+                .rev()
+                .find(|(row_start, _)| index >= *row_start)?;
+            Some(first_code + (index - row_start) as u16)
+        }
+    }
+}
+
+impl WaylandTool {
+    /// Name of the tool's executable
+    pub fn program(self) -> &'static str {
+        match self {
+            Self::Wtype => "wtype",
+            Self::Ydotool => "ydotool",
+        }
+    }
+
+    /// Arguments that press `key` while holding `modifier`
+    pub fn press_args(self, modifier: Option<KeyModifier>, key: KeyName) -> Vec<String> {
+        match self {
+            Self::Wtype => {
+                let modifier = modifier.map(|modifier| match modifier {
+                    KeyModifier::Ctrl => "ctrl",
+                    KeyModifier::Shift => "shift",
+                });
+                let mut args = Vec::new();
+                if let Some(modifier) = modifier {
+                    args.extend(["-M", modifier].map(String::from));
+                }
+                args.extend(["-k".to_string(), key_name(key)]);
+                if let Some(modifier) = modifier {
+                    args.extend(["-m", modifier].map(String::from));
+                }
+                args
+            }
+            Self::Ydotool => {
+                let mut codes = Vec::new();
+                let modifier = modifier.map(modifier_code);
+                codes.extend(modifier.map(|code| format!("{}:1", code)));
+                if let Some(code) = key_code(key) {
+                    codes.push(format!("{}:1", code));
+                    codes.push(format!("{}:0", code));
+                }
+                codes.extend(modifier.map(|code| format!("{}:0", code)));
+                std::iter::once("key".to_string()).chain(codes).collect()
+            }
+        }
+    }
+
+    /// Arguments that type `text`
+    pub fn type_args(self, text: &str) -> Vec<String> {
+        match self {
+            Self::Wtype => vec!["--".to_string(), text.to_string()],
+            Self::Ydotool => vec!["type".to_string(), "--".to_string(), text.to_string()],
+        }
+    }
+
+    fn run(self, args: &[String]) -> Result<(), String> {
+        let output = Command::new(self.program())
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", self.program(), e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} failed: {}",
+                self.program(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    /// Press `key` while holding `modifier`
+    pub fn press(self, modifier: Option<KeyModifier>, key: KeyName) -> Result<(), String> {
+        self.run(&self.press_args(modifier, key))
+    }
+
+    /// Type `text` into the focused window
+    pub fn type_text(self, text: &str) -> Result<(), String> {
+        self.run(&self.type_args(text))
+    }
+}
+
+/// Tools to try in order. GNOME and KDE don't offer the virtual-keyboard protocol
+/// wtype needs, so ydotool comes first there.
+pub fn preferred_tools(current_desktop: Option<&str>) -> [WaylandTool; 2] {
+    let desktop = current_desktop.unwrap_or_default().to_lowercase();
+    if desktop.contains("gnome") || desktop.contains("kde") {
+        [WaylandTool::Ydotool, WaylandTool::Wtype]
+    } else {
+        [WaylandTool::Wtype, WaylandTool::Ydotool]
+    }
+}
+
+/// Whether `program` is an executable in `PATH`
+pub fn is_installed(program: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| Path::new(&dir).join(program).is_file())
+    })
+}
+
+/// The tool to send keys with in a Wayland session, or `None` to use enigo (X11,
+/// or Wayland without either tool installed, which only reaches XWayland apps)
+pub fn wayland_tool() -> Option<WaylandTool> {
+    static TOOL: OnceLock<Option<WaylandTool>> = OnceLock::new();
+    *TOOL.get_or_init(|| {
+        if display_server() != DisplayServer::Wayland {
+            return None;
+        }
+        let desktop = std::env::var("XDG_CURRENT_DESKTOP").ok();
+        let tool = preferred_tools(desktop.as_deref())
+            .into_iter()
+            .find(|tool| is_installed(tool.program()));
+        match tool {
+            Some(tool) => log::info!("Wayland session, simulating keys with {:?}", tool),
+            None => log::warn!(
+                "Wayland session without wtype or ydotool, keys only reach XWayland apps"
+            ),
+        }
+        tool
+    })
+}
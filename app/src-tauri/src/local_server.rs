@@ -0,0 +1,133 @@
+//! Servers on localhost that follow their settings, shared by the remote control
+//! WebSocket server in [`crate::remote_control`] and the HTTP API in [`crate::api`].
+//!
+//! Each server has a setting that turns it on and one for its port. When they
+//! change, the running listener is stopped and joined before the new one binds, so
+//! turning a server off and on again gets its port back. Every connection is handed
+//! to the server's handler on its own thread.
+
+use crate::events::{self, AppEvent};
+use crate::settings::get_setting_from_store;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// How long the listener waits between checks for new connections and for being stopped
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Tells a server's threads that it was stopped or restarted
+#[derive(Debug, Clone, Default)]
+pub struct StopSignal(Arc<AtomicBool>);
+
+impl StopSignal {
+    pub fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    fn stop(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// A localhost server kept in Tauri state
+pub trait LocalService: Send + Sync + 'static {
+    /// Shown in logs
+    const NAME: &'static str;
+    /// Setting that turns the server on
+    const ENABLED_SETTING: &'static str;
+    /// Setting holding the port
+    const PORT_SETTING: &'static str;
+    /// Port listened on unless the port setting is set
+    const DEFAULT_PORT: u16;
+
+    fn server(&self) -> &LocalServer;
+
+    /// Handle one connection. Long-lived connections should end once `stop` is signalled.
+    fn serve(app: &AppHandle, stream: TcpStream, stop: StopSignal);
+}
+
+struct Listener {
+    port: u16,
+    stop: StopSignal,
+    thread: JoinHandle<()>,
+}
+
+/// The running listener of a [`LocalService`], if any
+#[derive(Default)]
+pub struct LocalServer {
+    listener: Mutex<Option<Listener>>,
+}
+
+impl LocalServer {
+    /// Re-read the settings and restart the server if they changed
+    fn refresh<S: LocalService>(&self, app: &AppHandle) {
+        let enabled: bool = get_setting_from_store(app, S::ENABLED_SETTING, false);
+        let port = enabled.then(|| get_setting_from_store(app, S::PORT_SETTING, S::DEFAULT_PORT));
+        let Ok(mut listener) = self.listener.lock() else {
+            return;
+        };
+        if listener.as_ref().map(|listener| listener.port) == port {
+            return;
+        }
+
+        // The old listener frees its port once its thread exits
+        if let Some(old) = listener.take() {
+            old.stop.stop();
+            if old.thread.join().is_err() {
+                log::warn!("{} listener on port {} panicked", S::NAME, old.port);
+            }
+        }
+        let Some(port) = port else {
+            return;
+        };
+        match start::<S>(app, port) {
+            Ok(started) => *listener = Some(started),
+            Err(e) => log::warn!("Failed to start {} on port {}: {}", S::NAME, port, e),
+        }
+    }
+}
+
+fn start<S: LocalService>(app: &AppHandle, port: u16) -> std::io::Result<Listener> {
+    let tcp_listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    tcp_listener.set_nonblocking(true)?;
+    log::info!("{} listening on 127.0.0.1:{}", S::NAME, port);
+
+    let stop = StopSignal::default();
+    let app = app.clone();
+    let thread_stop = stop.clone();
+    let thread = std::thread::spawn(move || accept::<S>(&app, tcp_listener, thread_stop, port));
+    Ok(Listener { port, stop, thread })
+}
+
+/// Accept connections until the server is restarted or turned off
+fn accept<S: LocalService>(app: &AppHandle, listener: TcpListener, stop: StopSignal, port: u16) {
+    while !stop.is_stopped() {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let app = app.clone();
+                let stop = stop.clone();
+                std::thread::spawn(move || S::serve(&app, stream, stop));
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                log::warn!("{} failed to accept a connection: {}", S::NAME, e);
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+    log::info!("{} stopped listening on port {}", S::NAME, port);
+}
+
+/// Start the server if enabled and follow changes to its settings
+pub fn init<S: LocalService>(app: &AppHandle) {
+    app.state::<S>().server().refresh::<S>(app);
+    events::spawn_subscriber(app, S::NAME, |app, event| {
+        if event == AppEvent::SettingsChanged {
+            app.state::<S>().server().refresh::<S>(app);
+        }
+    });
+}
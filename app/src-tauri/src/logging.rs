@@ -0,0 +1,187 @@
+//! Logging to the console and to rotating files in the app data directory.
+//!
+//! Records go to stderr as before (filtered by `RUST_LOG`, info by default) and,
+//! once the app data directory is known, as JSON lines to `logs/tambourine.log`.
+//! When that file grows past [`MAX_LOG_BYTES`] it is renamed to `tambourine.1.log`,
+//! older files shift up and the oldest beyond [`MAX_ROTATED_LOGS`] is deleted, so
+//! logs for bug reports are always in one place and never grow without bound.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Logs directory in the app data directory
+pub const LOG_DIR: &str = "logs";
+
+/// Name of the current log file
+pub const LOG_FILE: &str = "tambourine.log";
+
+/// Size at which the current log file is rotated
+pub const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated log files kept besides the current one
+pub const MAX_ROTATED_LOGS: usize = 3;
+
+/// One log record as written to the log file
+#[derive(Debug, Serialize)]
+struct LogLine<'a> {
+    timestamp: String,
+    level: &'a str,
+    target: &'a str,
+    message: String,
+}
+
+/// A log record as one line of JSON, without the trailing newline
+pub fn format_record(
+    timestamp: DateTime<Utc>,
+    level: log::Level,
+    target: &str,
+    message: &str,
+) -> String {
+    let line = LogLine {
+        timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+        level: level.as_str(),
+        target,
+        message: message.to_string(),
+    };
+    serde_json::to_string(&line).unwrap_or_default()
+}
+
+/// Path of a log file in `log_dir`: the current file for 0, rotated files after
+pub fn log_file_path(log_dir: &Path, index: usize) -> PathBuf {
+    match index {
+        0 => log_dir.join(LOG_FILE),
+        index => log_dir.join(format!("tambourine.{}.log", index)),
+    }
+}
+
+/// Existing log files in `log_dir`, newest first
+pub fn log_files(log_dir: &Path) -> Vec<PathBuf> {
+    (0..=MAX_ROTATED_LOGS)
+        .map(|index| log_file_path(log_dir, index))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Shift every log file in `log_dir` up by one, deleting the oldest, so the
+/// current file can start over
+pub fn rotate(log_dir: &Path) -> std::io::Result<()> {
+    let oldest = log_file_path(log_dir, MAX_ROTATED_LOGS);
+    if oldest.exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for index in (0..MAX_ROTATED_LOGS).rev() {
+        let path = log_file_path(log_dir, index);
+        if path.exists() {
+            fs::rename(&path, log_file_path(log_dir, index + 1))?;
+        }
+    }
+    Ok(())
+}
+
+/// The current log file, rotated once it reaches `max_bytes`
+pub struct RotatingLog {
+    log_dir: PathBuf,
+    max_bytes: u64,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLog {
+    pub fn open(log_dir: &Path, max_bytes: u64) -> std::io::Result<Self> {
+        fs::create_dir_all(log_dir)?;
+        let file = open_append(log_dir)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            log_dir: log_dir.to_path_buf(),
+            max_bytes,
+            file,
+            size,
+        })
+    }
+
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let length = line.len() as u64 + 1;
+        if self.size > 0 && self.size + length > self.max_bytes {
+            rotate(&self.log_dir)?;
+            self.file = open_append(&self.log_dir)?;
+            self.size = 0;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += length;
+        Ok(())
+    }
+}
+
+fn open_append(log_dir: &Path) -> std::io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_file_path(log_dir, 0))
+}
+
+/// Logger writing to the console and, once started, the log file
+struct AppLogger {
+    console: env_logger::Logger,
+    file: OnceLock<Mutex<RotatingLog>>,
+}
+
+impl log::Log for AppLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.console.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.console.matches(record) {
+            return;
+        }
+        self.console.log(record);
+        let Some(file) = self.file.get() else {
+            return;
+        };
+        let line = format_record(
+            Utc::now(),
+            record.level(),
+            record.target(),
+            &record.args().to_string(),
+        );
+        if let Ok(mut file) = file.lock() {
+            // Nowhere left to report a failure to write the log
+            let _ = file.write_line(&line);
+        }
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+    }
+}
+
+static LOGGER: OnceLock<AppLogger> = OnceLock::new();
+
+/// Install the logger. Records only reach the console until [`start_file_logging`].
+pub fn init() {
+    let console =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).build();
+    let max_level = console.filter();
+    let logger = LOGGER.get_or_init(|| AppLogger {
+        console,
+        file: OnceLock::new(),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Also write records to rotating files in the app data directory's logs directory
+pub fn start_file_logging(app_data_dir: &Path) -> Result<(), String> {
+    let Some(logger) = LOGGER.get() else {
+        return Ok(());
+    };
+    let log = RotatingLog::open(&app_data_dir.join(LOG_DIR), MAX_LOG_BYTES)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+    let _ = logger.file.set(Mutex::new(log));
+    Ok(())
+}
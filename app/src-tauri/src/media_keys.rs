@@ -0,0 +1,92 @@
+use crate::input_listener::InputListener;
+use crate::settings::{get_setting_from_store, HotkeyAction, HotkeyConfig, MediaKey};
+use crate::trigger_queue::{Trigger, TriggerQueue};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Toggle recording bound to a media key, including Bluetooth headset buttons
+/// (e.g. tapping an earbud sends play/pause).
+///
+/// Media keys can't be registered as global shortcuts, so presses come from the
+/// shared low-level input listener, started the first time the toggle hotkey is
+/// bound to one. The key still reaches media players as well.
+/// Note: rdev does not report media keys on macOS.
+#[derive(Default)]
+pub struct MediaKeyTrigger {
+    /// Key currently bound to toggle recording and whether it is held down, if any
+    binding: Mutex<Option<(MediaKey, bool)>>,
+}
+
+impl MediaKeyTrigger {
+    /// Re-read the toggle hotkey and start listening if it is bound to a media key
+    pub fn refresh(&self, app: &AppHandle) {
+        let toggle_hotkey: HotkeyConfig =
+            get_setting_from_store(app, "toggle_hotkey", HotkeyAction::Toggle.default_hotkey());
+        let key = toggle_hotkey.media_key();
+
+        if let Ok(mut binding) = self.binding.lock() {
+            *binding = key.map(|key| (key, false));
+        }
+
+        if key.is_some() {
+            app.state::<InputListener>().ensure_started(app);
+        }
+    }
+}
+
+/// Toggle recording when the bound media key is pressed
+pub fn handle_input_event(app: &AppHandle, event: &rdev::Event) {
+    let (code, pressed) = match event.event_type {
+        rdev::EventType::KeyPress(rdev::Key::Unknown(code)) => (code, true),
+        rdev::EventType::KeyRelease(rdev::Key::Unknown(code)) => (code, false),
+        _ => return,
+    };
+    let Some(key) = media_key_from_code(code) else {
+        return;
+    };
+
+    let trigger = app.state::<MediaKeyTrigger>();
+    let Ok(mut binding) = trigger.binding.lock() else {
+        return;
+    };
+    let Some((bound, held)) = binding.as_mut() else {
+        return;
+    };
+    // OS key repeats while the key is held only toggle once
+    let toggled = *bound == key && pressed && !*held;
+    if *bound == key {
+        *held = pressed;
+    }
+    drop(binding);
+
+    if toggled {
+        // Handle off the hook thread so slow recording setup doesn't stall the keyboard
+        app.state::<TriggerQueue>()
+            .send(Trigger::Toggle { source: "MediaKey" });
+    }
+}
+
+/// Windows reports media keys by virtual-key code
+#[cfg(target_os = "windows")]
+fn media_key_from_code(code: u32) -> Option<MediaKey> {
+    match code {
+        0xB0 => Some(MediaKey::NextTrack),
+        0xB1 => Some(MediaKey::PreviousTrack),
+        0xB2 => Some(MediaKey::Stop),
+        0xB3 => Some(MediaKey::PlayPause),
+        _ => None,
+    }
+}
+
+/// X11 reports media keys by keycode. BlueZ sends the AVRCP play and pause of
+/// headsets as separate keys, both treated as play/pause.
+#[cfg(not(target_os = "windows"))]
+fn media_key_from_code(code: u32) -> Option<MediaKey> {
+    match code {
+        171 => Some(MediaKey::NextTrack),
+        172 | 208 | 209 => Some(MediaKey::PlayPause),
+        173 => Some(MediaKey::PreviousTrack),
+        174 => Some(MediaKey::Stop),
+        _ => None,
+    }
+}
@@ -0,0 +1,168 @@
+//! Switching between the preferred microphones as they are plugged in and out.
+//!
+//! Microphones are listed by the overlay webview, so device IDs are the webview's
+//! and the overlay reports the current input devices whenever they change. This
+//! decides which device to record from: the first of the user's preferred devices
+//! that is plugged in, such as a headset ahead of a desk microphone, otherwise the
+//! system default until one of them comes back.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// An audio input device as enumerated by the overlay webview
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioInputDevice {
+    pub device_id: String,
+    pub label: String,
+}
+
+/// The microphone to record from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActiveMic {
+    /// Device to record from (`None` means the system default)
+    pub device_id: Option<String>,
+    /// Whether none of the preferred devices is present and the system default is
+    /// used instead
+    pub is_fallback: bool,
+}
+
+/// A switch of microphone because a preferred one was plugged in or unplugged,
+/// emitted as `mic-fallback-changed`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MicFallbackChange {
+    /// Whether recording fell back to the system default (false when switching to a
+    /// preferred device)
+    pub is_fallback: bool,
+    /// Label of the device now recorded from, or when falling back of the most
+    /// preferred device, if it was seen while plugged in
+    pub label: Option<String>,
+}
+
+/// The preferred microphones from settings, most preferred first. Before the
+/// list existed a single `selected_mic_id` was saved, which stands in for it.
+pub fn preferred_mics(preferred: Option<Vec<String>>, selected: Option<String>) -> Vec<String> {
+    preferred.unwrap_or_else(|| selected.into_iter().collect())
+}
+
+/// Pick the device to record from given the preferred devices, most preferred
+/// first, and the devices present
+pub fn resolve_active_mic(preferred: &[String], devices: &[AudioInputDevice]) -> ActiveMic {
+    let present = preferred
+        .iter()
+        .find(|id| devices.iter().any(|device| &device.device_id == *id));
+    ActiveMic {
+        device_id: present.cloned(),
+        is_fallback: present.is_none() && !preferred.is_empty(),
+    }
+}
+
+/// What the overlay last reported about the microphone, for diagnostics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MicStatus {
+    /// Whether the overlay could open the microphone, `None` until it tried
+    pub permission: Option<bool>,
+    /// Number of input devices present, `None` until the overlay listed them
+    pub device_count: Option<usize>,
+    /// Label of the device recorded from, or when falling back of the most preferred
+    /// device, if it was seen
+    pub selected_label: Option<String>,
+    /// Whether none of the preferred devices is present and the system default is
+    /// used instead
+    pub is_fallback: bool,
+}
+
+#[derive(Default)]
+struct MonitorState {
+    preferred: Vec<String>,
+    active: Option<ActiveMic>,
+    /// Labels of devices seen so far, so a missing device can still be named
+    labels: HashMap<String, String>,
+    permission: Option<bool>,
+    device_count: Option<usize>,
+}
+
+impl MonitorState {
+    /// Label of the device recorded from, or of the most preferred one when none
+    /// of them is present
+    fn active_label(&self, active: &ActiveMic) -> Option<String> {
+        let id = match active.device_id.as_ref() {
+            Some(id) => Some(id),
+            None if active.is_fallback => self.preferred.first(),
+            None => None,
+        };
+        id.and_then(|id| self.labels.get(id).cloned())
+    }
+}
+
+/// Tracks the active microphone across device list reports
+#[derive(Default)]
+pub struct MicMonitor {
+    state: Mutex<MonitorState>,
+}
+
+impl MicMonitor {
+    /// Record the devices currently present and pick the device to record from. Also
+    /// returns a change to announce when recording falls back to the system default,
+    /// or switches device because a preferred one was plugged in or unplugged.
+    /// Switches made by changing the preferred devices aren't announced.
+    pub fn update(
+        &self,
+        preferred: &[String],
+        devices: &[AudioInputDevice],
+    ) -> (ActiveMic, Option<MicFallbackChange>) {
+        let active = resolve_active_mic(preferred, devices);
+        let Ok(mut state) = self.state.lock() else {
+            return (active, None);
+        };
+
+        for device in devices.iter().filter(|device| !device.label.is_empty()) {
+            state
+                .labels
+                .insert(device.device_id.clone(), device.label.clone());
+        }
+
+        let was_fallback = state.active.as_ref().is_some_and(|mic| mic.is_fallback);
+        let same_preferences = state.preferred == preferred;
+        let device_changed = state
+            .active
+            .as_ref()
+            .is_some_and(|mic| mic.device_id != active.device_id);
+        let announce = if active.is_fallback {
+            !was_fallback
+        } else {
+            same_preferences && device_changed
+        };
+
+        state.preferred = preferred.to_vec();
+        let change = announce.then(|| MicFallbackChange {
+            is_fallback: active.is_fallback,
+            label: state.active_label(&active),
+        });
+        state.active = Some(active.clone());
+        state.device_count = Some(devices.len());
+        (active, change)
+    }
+
+    /// Record whether the overlay could open the microphone
+    pub fn set_permission(&self, granted: bool) {
+        if let Ok(mut state) = self.state.lock() {
+            state.permission = Some(granted);
+        }
+    }
+
+    pub fn status(&self) -> MicStatus {
+        let Ok(state) = self.state.lock() else {
+            return MicStatus::default();
+        };
+        MicStatus {
+            permission: state.permission,
+            device_count: state.device_count,
+            selected_label: state
+                .active
+                .as_ref()
+                .and_then(|active| state.active_label(active)),
+            is_fallback: state.active.as_ref().is_some_and(|mic| mic.is_fallback),
+        }
+    }
+}
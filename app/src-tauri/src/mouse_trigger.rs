@@ -0,0 +1,89 @@
+use crate::input_listener::InputListener;
+use crate::settings::{get_setting_from_store, HotkeyAction, HotkeyConfig, MouseButton};
+use crate::trigger_queue::{Trigger, TriggerQueue};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Hold-to-record bound to a mouse button.
+///
+/// Mouse buttons can't be registered as global shortcuts, so presses come from the
+/// shared low-level input listener, started the first time the hold hotkey is bound to one.
+/// Note: rdev does not report side buttons on macOS.
+#[derive(Default)]
+pub struct MouseTrigger {
+    /// Button currently bound to hold-to-record, if any
+    button: Mutex<Option<MouseButton>>,
+}
+
+impl MouseTrigger {
+    /// Re-read the hold hotkey and start listening if it is bound to a mouse button
+    pub fn refresh(&self, app: &AppHandle) {
+        let hold_hotkey: HotkeyConfig =
+            get_setting_from_store(app, "hold_hotkey", HotkeyAction::Hold.default_hotkey());
+        let button = hold_hotkey.mouse_button();
+
+        if let Ok(mut current) = self.button.lock() {
+            *current = button;
+        }
+
+        if button.is_some() {
+            app.state::<InputListener>().ensure_started(app);
+        }
+    }
+
+    fn is_bound(&self, button: MouseButton) -> bool {
+        self.button
+            .lock()
+            .map(|current| *current == Some(button))
+            .unwrap_or(false)
+    }
+}
+
+/// Start or stop hold-to-record when the bound mouse button is pressed or released
+pub fn handle_input_event(app: &AppHandle, event: &rdev::Event) {
+    let (button, pressed) = match event.event_type {
+        rdev::EventType::ButtonPress(button) => (button, true),
+        rdev::EventType::ButtonRelease(button) => (button, false),
+        _ => return,
+    };
+    let Some(button) = mouse_button_from_rdev(button) else {
+        return;
+    };
+    if app.state::<MouseTrigger>().is_bound(button) {
+        // Handle off the hook thread so slow recording setup doesn't stall the mouse
+        app.state::<TriggerQueue>().send(Trigger::Hold {
+            pressed,
+            source: "Mouse",
+        });
+    }
+}
+
+/// Map an rdev button to a named mouse button.
+/// Side buttons arrive as raw codes that differ per platform.
+fn mouse_button_from_rdev(button: rdev::Button) -> Option<MouseButton> {
+    match button {
+        rdev::Button::Middle => Some(MouseButton::Middle),
+        rdev::Button::Unknown(code) => side_button_from_code(code),
+        _ => None,
+    }
+}
+
+/// Windows reports XBUTTON1/XBUTTON2 as 1 and 2
+#[cfg(target_os = "windows")]
+fn side_button_from_code(code: u8) -> Option<MouseButton> {
+    match code {
+        1 => Some(MouseButton::Back),
+        2 => Some(MouseButton::Forward),
+        _ => None,
+    }
+}
+
+/// X11 reports the side buttons as buttons 8 and 9
+#[cfg(not(target_os = "windows"))]
+fn side_button_from_code(code: u8) -> Option<MouseButton> {
+    match code {
+        8 => Some(MouseButton::Back),
+        9 => Some(MouseButton::Forward),
+        _ => None,
+    }
+}
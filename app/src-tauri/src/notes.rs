@@ -0,0 +1,55 @@
+//! Appends dictations to a notes file, for capturing thoughts when there is no text
+//! field to dictate into.
+//!
+//! Files ending in `.md` get a heading per note, anything else a plain timestamp line.
+
+use chrono::{DateTime, Local};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Default notes file name, in the documents directory
+pub const DEFAULT_NOTES_FILE_NAME: &str = "Tambourine Notes.md";
+
+/// Whether a notes file is written as markdown
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("md") || extension.eq_ignore_ascii_case("markdown")
+        })
+}
+
+/// Format a dictation as a note, headed by when it was made and, if known, the app
+/// that was focused
+pub fn format_note(
+    timestamp: DateTime<Local>,
+    text: &str,
+    app_name: Option<&str>,
+    markdown: bool,
+) -> String {
+    let time = timestamp.format("%Y-%m-%d %H:%M");
+    let heading = match app_name {
+        Some(app_name) => format!("{} ({})", time, app_name),
+        None => time.to_string(),
+    };
+    if markdown {
+        format!("## {}\n\n{}\n\n", heading, text.trim())
+    } else {
+        format!("[{}]\n{}\n\n", heading, text.trim())
+    }
+}
+
+/// Append a note to the file, creating it and its directory if needed
+pub fn append_note(path: &Path, note: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create notes directory: {}", e))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open notes file: {}", e))?;
+    file.write_all(note.as_bytes())
+        .map_err(|e| format!("Failed to write notes file: {}", e))
+}
@@ -0,0 +1,115 @@
+//! Where the recording overlay is shown and how large it is.
+//!
+//! The overlay's position is saved as the offset of its center from the top-left
+//! corner of the monitor it was dragged to, in logical pixels, along with that
+//! monitor's name. On launch it is restored on the same monitor if it is still
+//! connected, otherwise on the primary monitor, and kept fully on screen. Without
+//! a saved position the overlay starts in the bottom-right corner.
+
+use serde::{Deserialize, Serialize};
+
+/// Default zoom of the overlay's contents
+pub const DEFAULT_OVERLAY_SCALE: f64 = 1.0;
+
+/// Smallest allowed overlay zoom
+pub const MIN_OVERLAY_SCALE: f64 = 0.5;
+
+/// Largest allowed overlay zoom
+pub const MAX_OVERLAY_SCALE: f64 = 2.0;
+
+/// Distance of the default position's center from the monitor's right and bottom edges
+const DEFAULT_CORNER_OFFSET: (f64, f64) = (126.0, 76.0);
+
+/// Offset of the overlay's center from its monitor's top-left corner, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OverlayPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A monitor's bounds in physical pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorArea {
+    pub name: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub scale_factor: f64,
+}
+
+impl MonitorArea {
+    /// Distance from a physical point to the monitor, 0 if it is on the monitor
+    fn distance_to(&self, (x, y): (f64, f64)) -> f64 {
+        let dx = (self.x - x).max(x - (self.x + self.width)).max(0.0);
+        let dy = (self.y - y).max(y - (self.y + self.height)).max(0.0);
+        dx.hypot(dy)
+    }
+}
+
+/// Keep a zoom within the allowed range, using the default for invalid values
+pub fn clamp_overlay_scale(scale: f64) -> f64 {
+    if scale.is_finite() {
+        scale.clamp(MIN_OVERLAY_SCALE, MAX_OVERLAY_SCALE)
+    } else {
+        DEFAULT_OVERLAY_SCALE
+    }
+}
+
+/// Physical position of the overlay's center for a saved position. `size` is the
+/// overlay's physical size, used to keep it on screen. Returns `None` if there
+/// are no monitors.
+pub fn restore_center(
+    saved: Option<OverlayPosition>,
+    saved_monitor: Option<&str>,
+    monitors: &[MonitorArea],
+    primary: Option<&MonitorArea>,
+    size: (f64, f64),
+) -> Option<(f64, f64)> {
+    let monitor = saved_monitor
+        .and_then(|name| {
+            monitors
+                .iter()
+                .find(|monitor| monitor.name.as_deref() == Some(name))
+        })
+        .or(primary)
+        .or_else(|| monitors.first())?;
+    let scale = monitor.scale_factor;
+    let (x, y) = match saved {
+        Some(position) => (position.x * scale, position.y * scale),
+        None => (
+            monitor.width - DEFAULT_CORNER_OFFSET.0 * scale,
+            monitor.height - DEFAULT_CORNER_OFFSET.1 * scale,
+        ),
+    };
+    Some((
+        monitor.x + clamp_on_screen(x, size.0, monitor.width),
+        monitor.y + clamp_on_screen(y, size.1, monitor.height),
+    ))
+}
+
+/// Keep a center coordinate far enough from the edges for the whole overlay to show
+fn clamp_on_screen(center: f64, size: f64, extent: f64) -> f64 {
+    let half = size / 2.0;
+    if extent <= size {
+        extent / 2.0
+    } else {
+        center.clamp(half, extent - half)
+    }
+}
+
+/// Monitor the overlay is on and the position to save, given the physical
+/// position of its center. A center off every monitor counts as on the nearest.
+pub fn locate(
+    center: (f64, f64),
+    monitors: &[MonitorArea],
+) -> Option<(Option<String>, OverlayPosition)> {
+    let monitor = monitors
+        .iter()
+        .min_by(|a, b| a.distance_to(center).total_cmp(&b.distance_to(center)))?;
+    let position = OverlayPosition {
+        x: (center.0 - monitor.x) / monitor.scale_factor,
+        y: (center.1 - monitor.y) / monitor.scale_factor,
+    };
+    Some((monitor.name.clone(), position))
+}
@@ -0,0 +1,69 @@
+//! Actions offered by the quick-action command palette.
+//!
+//! The palette is a small always-on-top window opened by a hotkey. It lists pinned
+//! and recent dictations to paste again, the STT providers and prompt presets to
+//! switch to, and the LLM formatting switch, so these can be reached from the
+//! keyboard without the main window. Items are filtered by what is typed into the
+//! palette: every word typed must appear in the item's title or detail.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest title shown for a history entry, in characters
+pub const MAX_TITLE_CHARS: usize = 80;
+
+/// What choosing a palette item does
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PaletteAction {
+    /// Paste the history entry `index` entries back (0 is the most recent)
+    PasteHistory { index: usize },
+    /// Paste the pinned history entry `id`
+    PastePinned { id: String },
+    /// Transcribe with the server STT provider `value`
+    SwitchSttProvider { value: String },
+    /// Make the prompt preset `id` the formatting prompt
+    ApplyPromptPreset { id: String },
+    /// Turn LLM formatting on or off
+    ToggleFormatting,
+}
+
+/// A palette entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PaletteItem {
+    pub action: PaletteAction,
+    pub title: String,
+    /// Group or extra context shown beside the title ("History", "STT provider")
+    pub detail: String,
+    /// Whether this is the current choice, e.g. the selected provider
+    pub active: bool,
+}
+
+/// `text` on one line, cut to `max_chars` characters with an ellipsis
+pub fn one_line_title(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= max_chars {
+        return line;
+    }
+    let cut: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// The items matching `query`, ignoring case. Items whose title starts with the
+/// query come first; otherwise the order is kept.
+pub fn filter_items(items: &[PaletteItem], query: &str) -> Vec<PaletteItem> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return items.to_vec();
+    }
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let mut matching: Vec<PaletteItem> = items
+        .iter()
+        .filter(|item| {
+            let text = format!("{} {}", item.title, item.detail).to_lowercase();
+            words.iter().all(|word| text.contains(word))
+        })
+        .cloned()
+        .collect();
+    matching.sort_by_key(|item| !item.title.to_lowercase().starts_with(&query));
+    matching
+}
@@ -0,0 +1,71 @@
+//! Cycling back through recent transcriptions with one hotkey.
+//!
+//! Works like yanking from a kill ring: a press pastes the most recent
+//! transcription, and each press soon after a paste replaces the text just pasted
+//! with the transcription before it.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long after a paste the cycle hotkey keeps stepping back instead of starting over
+pub const PASTE_CYCLE_WINDOW: Duration = Duration::from_secs(5);
+
+/// A history entry that was pasted into the focused app
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LastPaste {
+    /// How many entries back the pasted transcription was (0 is the most recent)
+    index: usize,
+    char_count: usize,
+    at: Instant,
+}
+
+/// What the next press of the cycle hotkey should do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleStep {
+    /// History entry to paste (0 is the most recent)
+    pub index: usize,
+    /// Characters of the previous paste to delete first
+    pub replace_chars: usize,
+}
+
+/// Tracks the last pasted history entry so the next cycle press can replace it
+#[derive(Default)]
+pub struct PasteCycle {
+    last: Mutex<Option<LastPaste>>,
+}
+
+impl PasteCycle {
+    /// Remember that history entry `index` was just pasted
+    pub fn record_paste(&self, index: usize, char_count: usize, now: Instant) {
+        if let Ok(mut last) = self.last.lock() {
+            *last = Some(LastPaste {
+                index,
+                char_count,
+                at: now,
+            });
+        }
+    }
+
+    /// Forget the last paste, so the next cycle press starts from the most recent entry
+    pub fn reset(&self) {
+        if let Ok(mut last) = self.last.lock() {
+            *last = None;
+        }
+    }
+
+    /// The step for a cycle press at `now`: the entry before the last one pasted,
+    /// replacing it, if that paste was within `window`; otherwise the most recent entry
+    pub fn next_step(&self, now: Instant, window: Duration) -> CycleStep {
+        let last = self.last.lock().ok().and_then(|last| *last);
+        match last {
+            Some(last) if now.saturating_duration_since(last.at) <= window => CycleStep {
+                index: last.index + 1,
+                replace_chars: last.char_count,
+            },
+            _ => CycleStep {
+                index: 0,
+                replace_chars: 0,
+            },
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Dictations recorded while the server was unreachable.
+//!
+//! When a turn starts without a server connection, the overlay captures its audio
+//! for Rust. If no fallback provider transcribes it, the turn is queued as a WAV file
+//! in the `pending-transcriptions` folder of the app data directory, so it survives
+//! a restart. Queued turns are replayed through the server once it is reachable again.
+
+use crate::recordings::{RecordingArchive, RecordingInfo, RetentionPolicy};
+use crate::stt::AudioFormat;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Queued turns are only removed once they have been transcribed
+const KEEP_ALL: RetentionPolicy = RetentionPolicy {
+    max_count: 0,
+    max_days: 0,
+};
+
+/// The queue of turns waiting to be transcribed
+pub struct PendingTranscriptions {
+    archive: RecordingArchive,
+    retrying: AtomicBool,
+}
+
+impl PendingTranscriptions {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            archive: RecordingArchive::new(dir),
+            retrying: AtomicBool::new(false),
+        }
+    }
+
+    /// Add a turn's audio to the queue
+    pub fn queue(&self, format: AudioFormat, samples: &[f32]) -> Result<RecordingInfo, String> {
+        self.archive.save(format, samples, KEEP_ALL)
+    }
+
+    /// Queued turns, oldest first
+    pub fn list(&self) -> Result<Vec<RecordingInfo>, String> {
+        let mut queued = self.archive.list()?;
+        queued.reverse();
+        Ok(queued)
+    }
+
+    pub fn load(&self, id: &str) -> Result<(AudioFormat, Vec<i16>), String> {
+        self.archive.load(id)
+    }
+
+    /// Remove a turn once it has been transcribed
+    pub fn remove(&self, id: &str) -> Result<bool, String> {
+        self.archive.delete(id)
+    }
+
+    /// Claim the queue for a retry. Returns false if a retry is already running.
+    pub fn begin_retry(&self) -> bool {
+        !self.retrying.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn end_retry(&self) {
+        self.retrying.store(false, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,92 @@
+//! Finding spoken phrases in transcribed text.
+//!
+//! Snippet triggers and voice commands are matched the same way: as whole words,
+//! regardless of case and of the punctuation a formatter puts around them, so
+//! "Scratch that." matches the phrase "scratch that".
+
+/// Punctuation a formatter may put directly after a spoken phrase
+const TRAILING_PUNCTUATION: &[char] = &['.', '!', '?', ',', ';', ':'];
+
+/// Where a phrase was found in a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhraseMatch {
+    /// Index of the matched phrase
+    pub phrase: usize,
+    /// Byte range of the phrase's words, without surrounding punctuation
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A word as phrases compare it: lowercase, without surrounding punctuation
+fn match_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// The words of a phrase as they are matched
+pub fn phrase_words(phrase: &str) -> Vec<String> {
+    phrase
+        .split_whitespace()
+        .map(match_word)
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Find phrases (as returned by [`phrase_words`]) in `text`, left to right and
+/// without overlaps. Where several phrases start at the same word, the longest wins.
+pub fn find_phrases(text: &str, phrases: &[Vec<String>]) -> Vec<PhraseMatch> {
+    let mut by_length: Vec<usize> = (0..phrases.len())
+        .filter(|&index| !phrases[index].is_empty())
+        .collect();
+    by_length.sort_by_key(|&index| std::cmp::Reverse(phrases[index].len()));
+
+    // Words of the text with their byte ranges, without surrounding punctuation
+    let words: Vec<(usize, usize, String)> = text
+        .split_whitespace()
+        .filter_map(|word| {
+            let offset = word.as_ptr() as usize - text.as_ptr() as usize;
+            let start = word.find(char::is_alphanumeric)?;
+            let last = word.rfind(char::is_alphanumeric)?;
+            let end = last + word[last..].chars().next()?.len_utf8();
+            Some((offset + start, offset + end, match_word(word)))
+        })
+        .collect();
+
+    let mut matches = Vec::new();
+    let mut index = 0;
+    while index < words.len() {
+        let remaining = &words[index..];
+        let found = by_length.iter().copied().find(|&phrase| {
+            let phrase_words = &phrases[phrase];
+            remaining.len() >= phrase_words.len()
+                && phrase_words
+                    .iter()
+                    .zip(remaining)
+                    .all(|(expected, (_, _, word))| expected == word)
+        });
+        match found {
+            Some(phrase) => {
+                let length = phrases[phrase].len();
+                matches.push(PhraseMatch {
+                    phrase,
+                    start: words[index].0,
+                    end: words[index + length - 1].1,
+                });
+                index += length;
+            }
+            None => index += 1,
+        }
+    }
+    matches
+}
+
+/// Whether text holds nothing but punctuation and whitespace
+pub fn is_blank(text: &str) -> bool {
+    !text.chars().any(char::is_alphanumeric)
+}
+
+/// Length in bytes of the punctuation at the start of `text`, which a formatter
+/// put after the phrase before it
+pub fn trailing_punctuation_len(text: &str) -> usize {
+    text.len() - text.trim_start_matches(TRAILING_PUNCTUATION).len()
+}
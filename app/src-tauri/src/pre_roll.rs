@@ -0,0 +1,60 @@
+//! Rolling buffer of the audio heard just before recording starts.
+//!
+//! Audio fed while no turn is active is kept for up to [`MAX_PRE_ROLL`] and the
+//! newest part of it is prepended to the next turn, so a word spoken while the
+//! hotkey is being pressed isn't clipped.
+
+use crate::stt::AudioFormat;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Longest pre-roll that can be prepended to a turn
+pub const MAX_PRE_ROLL: Duration = Duration::from_millis(2000);
+
+/// Audio from before the current turn, oldest samples dropped first
+#[derive(Debug)]
+pub struct PreRollBuffer {
+    format: AudioFormat,
+    samples: VecDeque<f32>,
+}
+
+impl PreRollBuffer {
+    pub fn new(format: AudioFormat) -> Self {
+        Self {
+            format,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Format of the audio this buffer expects
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    /// Interleaved samples covering `duration`, rounded down to whole frames
+    fn sample_count(&self, duration: Duration) -> usize {
+        let frames = self.format.sample_rate as u128 * duration.as_millis() / 1000;
+        frames as usize * self.format.channels.max(1) as usize
+    }
+
+    /// Keep interleaved samples, dropping any older than [`MAX_PRE_ROLL`]
+    pub fn push(&mut self, samples: &[f32]) {
+        self.samples.extend(samples);
+        let capacity = self.sample_count(MAX_PRE_ROLL);
+        if self.samples.len() > capacity {
+            let excess = self.samples.len() - capacity;
+            self.samples.drain(..excess);
+        }
+    }
+
+    /// Take the newest `duration` of audio and forget the rest
+    pub fn take(&mut self, duration: Duration) -> Vec<f32> {
+        let channels = self.format.channels.max(1) as usize;
+        // Leave out a trailing partial frame so channels stay interleaved in order
+        let end = self.samples.len() - self.samples.len() % channels;
+        let count = self.sample_count(duration).min(end);
+        let taken = self.samples.range(end - count..end).copied().collect();
+        self.samples.clear();
+        taken
+    }
+}
@@ -0,0 +1,170 @@
+//! Named formatting prompts to switch between.
+//!
+//! A preset keeps the main and advanced prompt sections under a name ("Email",
+//! "Code comments") so the prompt can be switched from the tray or a hotkey rather
+//! than edited each time. Presets are stored under `prompt_presets` in the
+//! settings. Applying one replaces the main and advanced sections of
+//! `cleanup_prompt_sections`; the dictionary section follows the dictionary and is
+//! kept. The active preset is the one matching the current prompt, so editing the
+//! prompt by hand leaves none active. Presets are shared as JSON files.
+
+use crate::settings::{CleanupPromptSections, PromptSection};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Version written to preset files. Files from newer versions are refused.
+pub const PRESET_FILE_VERSION: u32 = 1;
+
+/// Longest preset name, in characters
+pub const MAX_PRESET_NAME_CHARS: usize = 60;
+
+/// Main and advanced prompt sections saved under a name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptPreset {
+    /// Assigned when the preset is saved or imported, so files can leave it out
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub main: PromptSection,
+    pub advanced: PromptSection,
+}
+
+impl PromptPreset {
+    /// A preset named `name` with the main and advanced sections of `sections`
+    pub fn new(name: String, sections: &CleanupPromptSections) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            main: sections.main.clone(),
+            advanced: sections.advanced.clone(),
+        }
+    }
+
+    /// Whether `sections` has this preset's main and advanced sections
+    pub fn matches(&self, sections: &CleanupPromptSections) -> bool {
+        self.main == sections.main && self.advanced == sections.advanced
+    }
+
+    /// `sections` with this preset's main and advanced sections
+    pub fn apply_to(&self, sections: CleanupPromptSections) -> CleanupPromptSections {
+        CleanupPromptSections {
+            main: self.main.clone(),
+            advanced: self.advanced.clone(),
+            ..sections
+        }
+    }
+}
+
+/// The first preset matching `sections`
+pub fn active_preset<'a>(
+    presets: &'a [PromptPreset],
+    sections: &CleanupPromptSections,
+) -> Option<&'a PromptPreset> {
+    presets.iter().find(|preset| preset.matches(sections))
+}
+
+/// The preset after the one with ID `current`, wrapping around. Starts from the
+/// first when no preset is active.
+pub fn next_preset<'a>(
+    current: Option<&str>,
+    presets: &'a [PromptPreset],
+) -> Option<&'a PromptPreset> {
+    let next = current
+        .and_then(|id| presets.iter().position(|preset| preset.id == id))
+        .map_or(0, |index| index + 1);
+    presets.get(next % presets.len().max(1))
+}
+
+/// `name` trimmed, if it is a usable name not taken by another preset than
+/// `except_id`. Names are compared ignoring case.
+pub fn validate_preset_name(
+    name: &str,
+    presets: &[PromptPreset],
+    except_id: Option<&str>,
+) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Preset name can't be empty".to_string());
+    }
+    if name.chars().count() > MAX_PRESET_NAME_CHARS {
+        return Err(format!(
+            "Preset name can't be longer than {} characters",
+            MAX_PRESET_NAME_CHARS
+        ));
+    }
+    let taken = presets.iter().any(|preset| {
+        Some(preset.id.as_str()) != except_id && preset.name.eq_ignore_ascii_case(name)
+    });
+    if taken {
+        return Err(format!("There is already a preset named \"{}\"", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Contents of a preset file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresetFile {
+    pub version: u32,
+    pub presets: Vec<PromptPreset>,
+}
+
+/// A preset file holding `presets`, as pretty-printed JSON
+pub fn render_preset_file(presets: &[PromptPreset]) -> Result<String, String> {
+    let file = PresetFile {
+        version: PRESET_FILE_VERSION,
+        presets: presets.to_vec(),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize presets: {}", e))
+}
+
+/// The presets in a preset file, with names trimmed
+pub fn parse_preset_file(content: &str) -> Result<Vec<PromptPreset>, String> {
+    let file: PresetFile =
+        serde_json::from_str(content).map_err(|e| format!("Not a preset file: {}", e))?;
+    if file.version > PRESET_FILE_VERSION {
+        return Err(format!(
+            "Preset file version {} is newer than this app supports",
+            file.version
+        ));
+    }
+    file.presets
+        .into_iter()
+        .map(|preset| {
+            let name = validate_preset_name(&preset.name, &[], None)?;
+            Ok(PromptPreset { name, ..preset })
+        })
+        .collect()
+}
+
+/// Names of the presets an import added, and of those it replaced
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PresetImport {
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+}
+
+/// Add `imported` to `presets`. An imported preset with the name of an existing
+/// one replaces its sections, keeping its ID; the rest get new IDs.
+pub fn merge_presets(presets: &mut Vec<PromptPreset>, imported: Vec<PromptPreset>) -> PresetImport {
+    let mut import = PresetImport::default();
+    for preset in imported {
+        match presets
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&preset.name))
+        {
+            Some(existing) => {
+                existing.main = preset.main;
+                existing.advanced = preset.advanced;
+                import.replaced.push(existing.name.clone());
+            }
+            None => {
+                import.added.push(preset.name.clone());
+                presets.push(PromptPreset {
+                    id: Uuid::new_v4().to_string(),
+                    ..preset
+                });
+            }
+        }
+    }
+    import
+}
@@ -0,0 +1,96 @@
+//! Proxy for connections to the Tambourine server.
+//!
+//! Networks that only allow traffic through a proxy are set up with `proxy_url`
+//! (`http://`, `https://` or `socks5://`) and, when the proxy asks for them,
+//! credentials in `proxy_auth`. Requests from the backend go through the proxy,
+//! and so does the overlay's connection to the server, which is made from its
+//! webview. Webviews only support HTTP and SOCKS5 proxies without credentials, so
+//! an authenticated proxy is only used by the backend.
+
+use serde::{Deserialize, Serialize};
+
+/// URL schemes of the proxies that can be used
+pub const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+/// Credentials for a proxy that requires them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyAuth {
+    pub username: String,
+    pub password: String,
+}
+
+/// A proxy to send server connections through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// Proxy URL, trimmed and without a trailing `/`
+    pub url: String,
+    pub auth: Option<ProxyAuth>,
+}
+
+impl ProxyConfig {
+    /// The proxy from the settings values, `None` when no URL is set. Credentials
+    /// without a username are ignored. Fails if the URL isn't a supported proxy.
+    pub fn new(url: Option<&str>, auth: Option<ProxyAuth>) -> Result<Option<Self>, String> {
+        let Some(url) = url.map(str::trim).filter(|url| !url.is_empty()) else {
+            return Ok(None);
+        };
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| format!("Proxy URL must start with a scheme: {}", url))?;
+        let scheme = scheme.to_lowercase();
+        if !PROXY_SCHEMES.contains(&scheme.as_str()) {
+            return Err(format!(
+                "Unsupported proxy scheme \"{}\" (use {})",
+                scheme,
+                PROXY_SCHEMES.join(", ")
+            ));
+        }
+        let host = rest.trim_end_matches('/');
+        if host.is_empty() || host.contains(['/', '?', '#']) || host.contains(char::is_whitespace) {
+            return Err(format!("Proxy URL has no valid host: {}", url));
+        }
+        Ok(Some(Self {
+            url: format!("{}://{}", scheme, host),
+            auth: auth.filter(|auth| !auth.username.trim().is_empty()),
+        }))
+    }
+
+    /// Send the requests of a client through this proxy
+    pub fn apply(
+        &self,
+        builder: reqwest::blocking::ClientBuilder,
+    ) -> Result<reqwest::blocking::ClientBuilder, String> {
+        let mut proxy =
+            reqwest::Proxy::all(&self.url).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+        if let Some(auth) = &self.auth {
+            proxy = proxy.basic_auth(auth.username.trim(), &auth.password);
+        }
+        Ok(builder.proxy(proxy))
+    }
+
+    /// The URL a webview can be given, if it supports this proxy. Webviews
+    /// can't send credentials or reach HTTPS proxies.
+    pub fn webview_url(&self) -> Option<String> {
+        let (scheme, host) = self.url.split_once("://")?;
+        let scheme = match scheme {
+            "http" => "http",
+            "socks5" | "socks5h" => "socks5",
+            _ => return None,
+        };
+        if self.auth.is_some() || host.contains('@') {
+            return None;
+        }
+        Some(format!("{}://{}", scheme, host))
+    }
+}
+
+/// Send the requests of a client through `proxy`, if there is one
+pub fn with_proxy(
+    builder: reqwest::blocking::ClientBuilder,
+    proxy: Option<&ProxyConfig>,
+) -> Result<reqwest::blocking::ClientBuilder, String> {
+    match proxy {
+        Some(proxy) => proxy.apply(builder),
+        None => Ok(builder),
+    }
+}
@@ -0,0 +1,54 @@
+//! Maximum duration of a recording.
+//!
+//! One watcher thread waits on the deadline of the recording in progress. Stopping
+//! or cancelling the recording disarms it, so short dictations don't leave a thread
+//! sleeping out the full limit behind them.
+
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// The deadline of the recording in progress, if it has a limit
+#[derive(Default)]
+pub struct RecordingLimit {
+    /// ID of the limited recording and when it must stop
+    armed: Mutex<Option<(u64, Instant)>>,
+    changed: Condvar,
+}
+
+impl RecordingLimit {
+    /// Stop recording `recording_id` once it has run for `limit`, replacing any
+    /// earlier deadline
+    pub fn arm(&self, recording_id: u64, limit: Duration) {
+        if let Ok(mut armed) = self.armed.lock() {
+            *armed = Some((recording_id, Instant::now() + limit));
+        }
+        self.changed.notify_all();
+    }
+
+    /// Forget the deadline, as the recording stopped before it
+    pub fn disarm(&self) {
+        if let Ok(mut armed) = self.armed.lock() {
+            *armed = None;
+        }
+        self.changed.notify_all();
+    }
+
+    /// Block until an armed deadline passes and return the ID of its recording.
+    /// Returns `None` only if the lock is poisoned.
+    pub fn wait_for_expiry(&self) -> Option<u64> {
+        let mut armed = self.armed.lock().ok()?;
+        loop {
+            armed = match *armed {
+                None => self.changed.wait(armed).ok()?,
+                Some((recording_id, deadline)) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        *armed = None;
+                        return Some(recording_id);
+                    }
+                    self.changed.wait_timeout(armed, deadline - now).ok()?.0
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Archive of the raw audio of each dictation.
+//!
+//! When enabled, the audio of every turn that went through the Rust STT path is
+//! saved as a 16-bit PCM WAV file in the `recordings` folder of the app data
+//! directory, named after the time it was recorded. Old recordings are pruned by
+//! count and age after each save.
+
+use crate::stt::AudioFormat;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Size of the header written by [`encode_wav`]
+const WAV_HEADER_LEN: usize = 44;
+
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// A saved recording
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecordingInfo {
+    /// File name without the extension
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub size_bytes: u64,
+}
+
+/// How many recordings to keep and for how long (0 means no limit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_count: usize,
+    pub max_days: u64,
+}
+
+/// Encode interleaved f32 samples as a 16-bit PCM WAV file
+pub fn encode_wav(format: AudioFormat, samples: &[f32]) -> Vec<u8> {
+    let channels = format.channels.max(1);
+    let block_align = channels * 2;
+    let byte_rate = format.sample_rate * u32::from(block_align);
+    let data_len = (samples.len() * 2) as u32;
+
+    let mut wav = Vec::with_capacity(WAV_HEADER_LEN + samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&format.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for value in to_pcm16(samples) {
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    wav
+}
+
+/// Convert f32 samples to 16-bit PCM, clipping anything outside [-1, 1]
+pub fn to_pcm16(samples: &[f32]) -> Vec<i16> {
+    samples
+        .iter()
+        .map(|sample| (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16)
+        .collect()
+}
+
+/// Decode a WAV file written by [`encode_wav`] into its format and 16-bit samples
+pub fn decode_wav(wav: &[u8]) -> Result<(AudioFormat, Vec<i16>), String> {
+    if wav.len() < WAV_HEADER_LEN || &wav[0..4] != b"RIFF" || &wav[36..40] != b"data" {
+        return Err("Not a recording WAV file".to_string());
+    }
+    let format = AudioFormat {
+        sample_rate: u32::from_le_bytes([wav[24], wav[25], wav[26], wav[27]]),
+        channels: u16::from_le_bytes([wav[22], wav[23]]),
+    };
+    let samples = wav[WAV_HEADER_LEN..]
+        .chunks_exact(2)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect();
+    Ok((format, samples))
+}
+
+/// Length of the audio in a WAV file written by [`encode_wav`], from its header
+fn wav_duration_ms(header: &[u8; WAV_HEADER_LEN]) -> u64 {
+    let byte_rate = u32::from_le_bytes([header[28], header[29], header[30], header[31]]);
+    let data_len = u32::from_le_bytes([header[40], header[41], header[42], header[43]]);
+    if byte_rate == 0 {
+        return 0;
+    }
+    u64::from(data_len) * 1000 / u64::from(byte_rate)
+}
+
+/// IDs of the recordings a retention policy removes, given recordings newest first
+pub fn expired_recordings(
+    recordings: &[RecordingInfo],
+    policy: RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let oldest_kept =
+        (policy.max_days > 0).then(|| now.timestamp_millis() - policy.max_days as i64 * MS_PER_DAY);
+    recordings
+        .iter()
+        .enumerate()
+        .filter(|(index, recording)| {
+            (policy.max_count > 0 && *index >= policy.max_count)
+                || oldest_kept.is_some_and(|oldest| recording.timestamp.timestamp_millis() < oldest)
+        })
+        .map(|(_, recording)| recording.id.clone())
+        .collect()
+}
+
+/// Recording IDs are millisecond timestamps, which also keeps paths inside the folder
+fn parse_id(id: &str) -> Option<DateTime<Utc>> {
+    id.parse::<i64>()
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+}
+
+/// The recordings folder
+pub struct RecordingArchive {
+    dir: PathBuf,
+}
+
+impl RecordingArchive {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.wav", id))
+    }
+
+    /// Save a turn's audio, then prune recordings the policy no longer keeps
+    pub fn save(
+        &self,
+        format: AudioFormat,
+        samples: &[f32],
+        policy: RetentionPolicy,
+    ) -> Result<RecordingInfo, String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create recordings folder: {}", e))?;
+
+        let mut millis = Utc::now().timestamp_millis();
+        // Two turns can finish within the same millisecond
+        while self.path(&millis.to_string()).exists() {
+            millis += 1;
+        }
+        let id = millis.to_string();
+        let wav = encode_wav(format, samples);
+        fs::write(self.path(&id), &wav).map_err(|e| format!("Failed to save recording: {}", e))?;
+
+        for expired in expired_recordings(&self.list()?, policy, Utc::now()) {
+            if let Err(e) = self.delete(&expired) {
+                log::warn!("Failed to remove old recording {}: {}", expired, e);
+            }
+        }
+
+        let mut header = [0u8; WAV_HEADER_LEN];
+        header.copy_from_slice(&wav[..WAV_HEADER_LEN]);
+        Ok(RecordingInfo {
+            timestamp: parse_id(&id).unwrap_or_default(),
+            id,
+            duration_ms: wav_duration_ms(&header),
+            size_bytes: wav.len() as u64,
+        })
+    }
+
+    /// Saved recordings, newest first
+    pub fn list(&self) -> Result<Vec<RecordingInfo>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read recordings folder: {}", e)),
+        };
+
+        let mut recordings: Vec<RecordingInfo> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "wav" {
+                    return None;
+                }
+                let id = path.file_stem()?.to_str()?.to_string();
+                let timestamp = parse_id(&id)?;
+                let mut header = [0u8; WAV_HEADER_LEN];
+                fs::File::open(&path)
+                    .and_then(|mut file| file.read_exact(&mut header))
+                    .ok()?;
+                Some(RecordingInfo {
+                    id,
+                    timestamp,
+                    duration_ms: wav_duration_ms(&header),
+                    size_bytes: entry.metadata().ok()?.len(),
+                })
+            })
+            .collect();
+        recordings.sort_by_key(|recording| std::cmp::Reverse(recording.timestamp));
+        Ok(recordings)
+    }
+
+    /// Read a recording's format and 16-bit samples
+    pub fn load(&self, id: &str) -> Result<(AudioFormat, Vec<i16>), String> {
+        if parse_id(id).is_none() {
+            return Err(format!("Invalid recording ID: {}", id));
+        }
+        let wav =
+            fs::read(self.path(id)).map_err(|e| format!("Failed to read recording: {}", e))?;
+        decode_wav(&wav)
+    }
+
+    /// Delete a recording by ID. Returns false if there was no such recording.
+    pub fn delete(&self, id: &str) -> Result<bool, String> {
+        if parse_id(id).is_none() {
+            return Err(format!("Invalid recording ID: {}", id));
+        }
+        match fs::remove_file(self.path(id)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(format!("Failed to delete recording: {}", e)),
+        }
+    }
+}
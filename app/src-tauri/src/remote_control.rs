@@ -0,0 +1,303 @@
+//! Local control surface for Stream Deck plugins and automation tools.
+//!
+//! When `remote_control_enabled` is on, a WebSocket server listens on
+//! `127.0.0.1:<remote_control_port>`. Clients send JSON requests such as
+//! `{"id": 1, "token": "...", "command": "toggle"}` and get back
+//! `{"id": 1, "ok": true, "status": {...}}`. Every request carries the control
+//! token, which is generated on first use, kept in the OS keyring and shared
+//! with the HTTP API in [`crate::api`]. Once a client has sent an authenticated
+//! request it is also sent `{"event": "status", "status": {...}}` whenever
+//! recording starts or stops, so a Stream Deck key can show whether Tambourine
+//! is recording.
+
+use crate::events::{AppEvent, EventBus};
+use crate::local_server::{self, LocalServer, LocalService, StopSignal, POLL_INTERVAL};
+use crate::secrets::KEYRING_SERVICE;
+use crate::state::AppState;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::{error::TryRecvError, Receiver};
+use tungstenite::{Message, WebSocket};
+
+/// Port the control server listens on unless `remote_control_port` is set
+pub const DEFAULT_PORT: u16 = 47653;
+
+/// Keyring account holding the control token, which tambourine-cli also reads
+const KEYRING_ACCOUNT: &str = "control-token";
+
+/// How long a client may take to complete the WebSocket handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A command sent by a remote client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteCommand {
+    /// Start recording unless already recording
+    Start,
+    /// Stop recording and transcribe unless already stopped
+    Stop,
+    /// Start recording if idle, otherwise stop it
+    Toggle,
+    /// Only report the status
+    Status,
+}
+
+/// What remote clients are told about the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RemoteStatus {
+    pub recording: bool,
+    pub paused: bool,
+    /// Whether the overlay is connected to the server
+    pub connected: bool,
+}
+
+/// A request from a remote client
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ControlRequest {
+    /// Echoed back in the response so clients can match them up
+    #[serde(default)]
+    pub id: serde_json::Value,
+    #[serde(default)]
+    pub token: String,
+    pub command: RemoteCommand,
+}
+
+/// The reply to a request, or a status update pushed to the client
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ControlResponse {
+    Success {
+        id: serde_json::Value,
+        ok: bool,
+        status: RemoteStatus,
+    },
+    Failure {
+        id: serde_json::Value,
+        ok: bool,
+        error: String,
+    },
+    Event {
+        event: &'static str,
+        status: RemoteStatus,
+    },
+}
+
+impl ControlResponse {
+    pub fn success(id: serde_json::Value, status: RemoteStatus) -> Self {
+        Self::Success {
+            id,
+            ok: true,
+            status,
+        }
+    }
+
+    pub fn failure(id: serde_json::Value, error: impl Into<String>) -> Self {
+        Self::Failure {
+            id,
+            ok: false,
+            error: error.into(),
+        }
+    }
+
+    pub fn status_event(status: RemoteStatus) -> Self {
+        Self::Event {
+            event: "status",
+            status,
+        }
+    }
+
+    fn to_message(&self) -> Message {
+        Message::text(serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Parse a request and check its token, returning the command to run
+pub fn authorize(text: &str, expected_token: &str) -> Result<ControlRequest, ControlResponse> {
+    let request: ControlRequest = serde_json::from_str(text).map_err(|e| {
+        let id = serde_json::from_str::<serde_json::Value>(text)
+            .ok()
+            .and_then(|value| value.get("id").cloned())
+            .unwrap_or_default();
+        ControlResponse::failure(id, format!("Invalid request: {}", e))
+    })?;
+    if !tokens_match(&request.token, expected_token) {
+        return Err(ControlResponse::failure(request.id, "Invalid token"));
+    }
+    Ok(request)
+}
+
+/// Compare tokens in time that doesn't depend on where they first differ
+pub fn tokens_match(given: &str, expected: &str) -> bool {
+    if expected.is_empty() || given.len() != expected.len() {
+        return false;
+    }
+    given
+        .bytes()
+        .zip(expected.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// The current recording and connection status
+pub fn status(app: &AppHandle) -> RemoteStatus {
+    let state = app.state::<AppState>();
+    RemoteStatus {
+        recording: state.is_recording.load(Ordering::SeqCst),
+        paused: crate::is_recording_paused(app),
+        connected: state.server_connected.load(Ordering::SeqCst),
+    }
+}
+
+/// Run a command and report the status after it
+pub fn execute(app: &AppHandle, command: RemoteCommand) -> RemoteStatus {
+    let recording = app.state::<AppState>().is_recording.load(Ordering::SeqCst);
+    let toggle = match command {
+        RemoteCommand::Start => !recording,
+        RemoteCommand::Stop => recording,
+        RemoteCommand::Toggle => true,
+        RemoteCommand::Status => false,
+    };
+    if toggle {
+        crate::handle_toggle_trigger(app, "Remote");
+    }
+    status(app)
+}
+
+fn token_entry() -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .map_err(|e| format!("Failed to open keyring entry: {}", e))
+}
+
+/// The control token, generated the first time it is asked for
+pub fn control_token() -> Result<String, String> {
+    match token_entry()?.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => regenerate_control_token(),
+        Err(e) => Err(format!("Failed to read control token: {}", e)),
+    }
+}
+
+/// Replace the control token, so clients using the old one are refused
+pub fn regenerate_control_token() -> Result<String, String> {
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    token_entry()?
+        .set_password(&token)
+        .map_err(|e| format!("Failed to store control token: {}", e))?;
+    log::info!("Generated a new remote control token");
+    Ok(token)
+}
+
+/// Runs the control server while it is enabled
+#[derive(Default)]
+pub struct ControlServer {
+    server: LocalServer,
+}
+
+impl LocalService for ControlServer {
+    const NAME: &'static str = "Remote control";
+    const ENABLED_SETTING: &'static str = "remote_control_enabled";
+    const PORT_SETTING: &'static str = "remote_control_port";
+    const DEFAULT_PORT: u16 = DEFAULT_PORT;
+
+    fn server(&self) -> &LocalServer {
+        &self.server
+    }
+
+    fn serve(app: &AppHandle, stream: TcpStream, stop: StopSignal) {
+        serve(app, &stop, stream);
+    }
+}
+
+/// Handle one client's requests until it disconnects or the server stops
+fn serve(app: &AppHandle, stop: &StopSignal, stream: TcpStream) {
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT)).is_err()
+    {
+        return;
+    }
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(e) => {
+            log::debug!("Remote control handshake failed: {}", e);
+            return;
+        }
+    };
+    // Wake up regularly to push status updates and notice the server stopping
+    if socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .is_err()
+    {
+        return;
+    }
+
+    let mut receiver = app.state::<EventBus>().subscribe();
+    let mut authenticated = false;
+    while !stop.is_stopped() {
+        if !push_status_updates(app, &mut socket, &mut receiver, authenticated) {
+            break;
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let response = match control_token() {
+                    Ok(token) => match authorize(&text, &token) {
+                        Ok(request) => {
+                            authenticated = true;
+                            ControlResponse::success(request.id, execute(app, request.command))
+                        }
+                        Err(response) => response,
+                    },
+                    Err(e) => ControlResponse::failure(serde_json::Value::Null, e),
+                };
+                if socket.send(response.to_message()).is_err() {
+                    break;
+                }
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+    }
+    let _ = socket.close(None);
+    let _ = socket.flush();
+}
+
+/// Send the status to an authenticated client after recording starts or stops.
+/// Returns false if the client has gone away.
+fn push_status_updates(
+    app: &AppHandle,
+    socket: &mut WebSocket<TcpStream>,
+    receiver: &mut Receiver<AppEvent>,
+    authenticated: bool,
+) -> bool {
+    let mut changed = false;
+    loop {
+        match receiver.try_recv() {
+            Ok(
+                AppEvent::RecordingStarted
+                | AppEvent::RecordingStopped
+                | AppEvent::ConnectionChanged { .. },
+            ) => changed = true,
+            Ok(_) | Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+        }
+    }
+    if !changed || !authenticated {
+        return true;
+    }
+    socket
+        .send(ControlResponse::status_event(status(app)).to_message())
+        .is_ok()
+}
+
+/// Start the control server if enabled and follow changes to its settings
+pub fn init(app: &AppHandle) {
+    local_server::init::<ControlServer>(app);
+}
@@ -0,0 +1,81 @@
+//! Provider API key storage in the OS keyring.
+//!
+//! Keys are stored in the macOS Keychain, Windows Credential Manager, or the
+//! Secret Service on Linux, so they never end up in `settings.json`.
+
+use keyring::Entry;
+use serde::Serialize;
+
+/// Keyring service name under which all provider keys are stored
+pub(crate) const KEYRING_SERVICE: &str = "com.tambourine-voice.app";
+
+/// Whether an API key is stored for a provider
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyStatus {
+    pub provider: String,
+    pub is_set: bool,
+}
+
+/// Validate a provider ID before using it as a keyring account name
+fn validate_provider(provider: &str) -> Result<(), String> {
+    let is_valid = !provider.is_empty()
+        && provider
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid provider ID: '{}'", provider))
+    }
+}
+
+/// Get the keyring entry for a provider's API key
+fn entry_for(provider: &str) -> Result<Entry, String> {
+    validate_provider(provider)?;
+    Entry::new(KEYRING_SERVICE, &format!("api-key:{}", provider))
+        .map_err(|e| format!("Failed to open keyring entry: {}", e))
+}
+
+/// Store an API key for a provider, replacing any existing key
+pub fn set_api_key(provider: &str, api_key: &str) -> Result<(), String> {
+    let api_key = api_key.trim();
+    if api_key.is_empty() {
+        return Err("API key cannot be empty".to_string());
+    }
+
+    entry_for(provider)?
+        .set_password(api_key)
+        .map_err(|e| format!("Failed to store API key: {}", e))?;
+    log::info!("Stored API key for provider: {}", provider);
+    Ok(())
+}
+
+/// Get the stored API key for a provider, if any
+pub fn get_api_key(provider: &str) -> Result<Option<String>, String> {
+    match entry_for(provider)?.get_password() {
+        Ok(api_key) => Ok(Some(api_key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read API key: {}", e)),
+    }
+}
+
+/// Check whether an API key is stored for a provider without exposing it
+pub fn get_api_key_status(provider: &str) -> Result<ApiKeyStatus, String> {
+    Ok(ApiKeyStatus {
+        provider: provider.to_string(),
+        is_set: get_api_key(provider)?.is_some(),
+    })
+}
+
+/// Delete the stored API key for a provider.
+/// Returns false if no key was stored.
+pub fn delete_api_key(provider: &str) -> Result<bool, String> {
+    match entry_for(provider)?.delete_credential() {
+        Ok(()) => {
+            log::info!("Deleted API key for provider: {}", provider);
+            Ok(true)
+        }
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(format!("Failed to delete API key: {}", e)),
+    }
+}
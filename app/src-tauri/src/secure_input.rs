@@ -0,0 +1,24 @@
+//! Detection of macOS secure input.
+//!
+//! While a password field or a terminal with "Secure Keyboard Entry" has secure
+//! input on, macOS drops simulated key presses from every other app, so a paste
+//! silently does nothing. Insertion checks first and refuses instead, keeping the
+//! dictation in history for paste last. Other platforms have no equivalent.
+
+/// Whether secure input is on, blocking simulated keys
+#[cfg(target_os = "macos")]
+pub fn is_enabled() -> bool {
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn IsSecureEventInputEnabled() -> u8;
+    }
+
+    // SAFETY: takes no arguments and only reads the session's secure input state
+    unsafe { IsSecureEventInputEnabled() != 0 }
+}
+
+/// Whether secure input is on, blocking simulated keys
+#[cfg(not(target_os = "macos"))]
+pub fn is_enabled() -> bool {
+    false
+}
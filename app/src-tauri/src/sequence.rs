@@ -0,0 +1,156 @@
+//! Hotkey sequences ("leader keys") such as Ctrl+Alt+D then T.
+//!
+//! Only the first step is registered with the OS. Pressing it registers the second
+//! step of every sequence that shares it until one is pressed or the timeout passes,
+//! so the second key works normally the rest of the time.
+
+use crate::settings::{
+    get_setting_from_store, sequence_follow_ups, stored_hotkey, HotkeyAction, HotkeyConfig,
+    DEFAULT_SEQUENCE_TIMEOUT_MS,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+/// Second steps waiting to be pressed after a sequence's first step
+#[derive(Default)]
+pub struct SequenceState {
+    /// Second steps currently registered, with the action each one triggers
+    pending: Mutex<Vec<(HotkeyAction, Shortcut)>>,
+    /// Bumped when a sequence starts or a step is pressed, so stale timeouts do nothing
+    generation: AtomicU64,
+}
+
+impl SequenceState {
+    /// Forget pending steps after all shortcuts were unregistered
+    pub fn clear(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.clear();
+        }
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Handle the first step of a sequence being pressed
+pub fn handle_leader_event(app: &AppHandle, shortcut: &Shortcut, event: &ShortcutEvent) {
+    if event.state != ShortcutState::Pressed {
+        return;
+    }
+    // Registering shortcuts from inside a shortcut handler can deadlock, so use another thread
+    let app = app.clone();
+    let leader = *shortcut;
+    std::thread::spawn(move || begin_sequence(&app, leader));
+}
+
+fn begin_sequence(app: &AppHandle, leader: Shortcut) {
+    let hotkeys = configured_hotkeys(app);
+    let Some(leader_step) = hotkeys
+        .iter()
+        .find(|(_, hotkey)| hotkey.is_sequence() && hotkey.to_shortcut().ok() == Some(leader))
+        .map(|(_, hotkey)| hotkey.leader())
+    else {
+        return;
+    };
+
+    // Pressing a first step again restarts the sequence
+    cancel_pending(app);
+
+    let shortcut_manager = app.global_shortcut();
+    let mut pending = Vec::new();
+    for (action, step) in sequence_follow_ups(&hotkeys, &leader_step) {
+        let shortcut = match step.to_shortcut() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                log::warn!("Invalid sequence step for {:?}: {}", action, e);
+                continue;
+            }
+        };
+        let result = shortcut_manager.on_shortcut(shortcut, |app, shortcut, event| {
+            handle_step_event(app, shortcut, &event);
+        });
+        match result {
+            Ok(()) => pending.push((action, shortcut)),
+            Err(e) => log::warn!("Failed to register sequence step {}: {}", shortcut, e),
+        }
+    }
+    if pending.is_empty() {
+        return;
+    }
+
+    log::info!("Hotkey sequence started, waiting for the next step");
+    let state = app.state::<SequenceState>();
+    if let Ok(mut current) = state.pending.lock() {
+        *current = pending;
+    }
+    let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let timeout_ms: u64 =
+        get_setting_from_store(app, "sequence_timeout_ms", DEFAULT_SEQUENCE_TIMEOUT_MS);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(timeout_ms));
+        if app
+            .state::<SequenceState>()
+            .generation
+            .load(Ordering::SeqCst)
+            == generation
+        {
+            log::info!("Hotkey sequence timed out");
+            cancel_pending(&app);
+        }
+    });
+}
+
+/// Handle the second step of a pending sequence
+fn handle_step_event(app: &AppHandle, shortcut: &Shortcut, event: &ShortcutEvent) {
+    let state = app.state::<SequenceState>();
+    let action = state.pending.lock().ok().and_then(|pending| {
+        pending
+            .iter()
+            .find(|(_, step)| step == shortcut)
+            .map(|(action, _)| *action)
+    });
+    let Some(action) = action else {
+        return;
+    };
+
+    let pressed = event.state == ShortcutState::Pressed;
+    if pressed {
+        // Keep the sequence alive until the step is released (hold-to-record needs both)
+        state.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    crate::run_hotkey_action(app, action, pressed, "Sequence");
+
+    if !pressed {
+        let app = app.clone();
+        std::thread::spawn(move || cancel_pending(&app));
+    }
+}
+
+/// Unregister the second steps of the pending sequence
+fn cancel_pending(app: &AppHandle) {
+    let pending = app
+        .state::<SequenceState>()
+        .pending
+        .lock()
+        .map(|mut pending| std::mem::take(&mut *pending))
+        .unwrap_or_default();
+
+    let shortcut_manager = app.global_shortcut();
+    for (_, shortcut) in pending {
+        if let Err(e) = shortcut_manager.unregister(shortcut) {
+            log::warn!("Failed to unregister sequence step {}: {}", shortcut, e);
+        }
+    }
+}
+
+/// Every enabled hotkey with the action it triggers
+pub(crate) fn configured_hotkeys(app: &AppHandle) -> Vec<(HotkeyAction, HotkeyConfig)> {
+    HotkeyAction::ALL
+        .into_iter()
+        .filter_map(|action| Some((action, stored_hotkey(app, action)?)))
+        .collect()
+}
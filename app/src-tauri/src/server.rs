@@ -0,0 +1,196 @@
+//! HTTP requests from the backend to the Tambourine server.
+//!
+//! Self-hosted servers often use a self-signed certificate. When allowed in
+//! settings, certificates that don't chain to a trusted root are accepted, and a
+//! pinned SHA-256 fingerprint of the server's leaf certificate can be required
+//! instead. The pin is checked before the overlay connects, so a server presenting
+//! a different certificate is treated as unreachable. Requests go through the
+//! configured proxy, if any.
+
+use crate::proxy::{self, ProxyConfig};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+/// Timeout for a connection test request
+const TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How certificates presented by the server are trusted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// Accept certificates that don't chain to a trusted root
+    pub allow_self_signed: bool,
+    /// Required SHA-256 fingerprint of the leaf certificate, as lowercase hex
+    pub pinned_sha256: Option<String>,
+}
+
+impl TlsOptions {
+    /// Options from the settings values, ignoring a blank pin. Fails if the pin
+    /// isn't a SHA-256 fingerprint.
+    pub fn new(allow_self_signed: bool, pinned_sha256: Option<&str>) -> Result<Self, String> {
+        let pinned_sha256 = pinned_sha256
+            .filter(|pin| !pin.trim().is_empty())
+            .map(normalize_fingerprint)
+            .transpose()?;
+        Ok(Self {
+            allow_self_signed,
+            pinned_sha256,
+        })
+    }
+}
+
+/// Parse a SHA-256 fingerprint written as hex, with or without `:` separators
+pub fn normalize_fingerprint(fingerprint: &str) -> Result<String, String> {
+    let hex: String = fingerprint
+        .trim()
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "\"{}\" is not a SHA-256 fingerprint (64 hex digits)",
+            fingerprint.trim()
+        ));
+    }
+    Ok(hex)
+}
+
+/// SHA-256 fingerprint of a DER encoded certificate, as lowercase hex
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Providers the server reports from `/api/capabilities`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerCapabilities {
+    pub stt_providers: Vec<String>,
+    pub llm_providers: Vec<String>,
+}
+
+/// Result of testing the connection to a server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConnectionTest {
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    /// `None` if the server is too old to report them
+    pub capabilities: Option<ServerCapabilities>,
+    /// Fingerprint of the certificate presented over HTTPS
+    pub certificate_sha256: Option<String>,
+    pub error: Option<String>,
+}
+
+fn client(
+    options: &TlsOptions,
+    proxy: Option<&ProxyConfig>,
+    timeout: Duration,
+) -> Result<reqwest::blocking::Client, String> {
+    let builder = proxy::with_proxy(reqwest::blocking::Client::builder(), proxy)?;
+    builder
+        .timeout(timeout)
+        // A pinned certificate is trusted on its own, whoever issued it
+        .danger_accept_invalid_certs(options.allow_self_signed || options.pinned_sha256.is_some())
+        .tls_info(true)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Fingerprint of the certificate a response came over, if it used TLS
+fn response_fingerprint(response: &reqwest::blocking::Response) -> Option<String> {
+    response
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|info| info.peer_certificate())
+        .map(certificate_fingerprint)
+}
+
+/// Require the pinned fingerprint, if there is one, to match the certificate presented
+pub fn check_pin(options: &TlsOptions, fingerprint: Option<&str>) -> Result<(), String> {
+    match (&options.pinned_sha256, fingerprint) {
+        (None, _) => Ok(()),
+        (Some(pin), Some(fingerprint)) if pin == fingerprint => Ok(()),
+        (Some(_), Some(fingerprint)) => Err(format!(
+            "Server certificate does not match the pinned fingerprint (got {})",
+            fingerprint
+        )),
+        (Some(_), None) => {
+            Err("A certificate is pinned but the server is not using HTTPS".to_string())
+        }
+    }
+}
+
+/// Check the server's health and capabilities with the given TLS options, through
+/// `proxy` if there is one
+pub fn test_connection(
+    url: &str,
+    options: &TlsOptions,
+    proxy: Option<&ProxyConfig>,
+) -> ConnectionTest {
+    let base_url = url.trim().trim_end_matches('/');
+    let client = match client(options, proxy, TEST_TIMEOUT) {
+        Ok(client) => client,
+        Err(e) => {
+            return ConnectionTest {
+                error: Some(e),
+                ..ConnectionTest::default()
+            }
+        }
+    };
+
+    let started = Instant::now();
+    let response = match client
+        .get(format!("{}/health", base_url))
+        .send()
+        .and_then(|response| response.error_for_status())
+    {
+        Ok(response) => response,
+        Err(e) => {
+            return ConnectionTest {
+                error: Some(format!("Server is unreachable: {}", e)),
+                ..ConnectionTest::default()
+            }
+        }
+    };
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let certificate_sha256 = response_fingerprint(&response);
+    if let Err(e) = check_pin(options, certificate_sha256.as_deref()) {
+        return ConnectionTest {
+            certificate_sha256,
+            error: Some(e),
+            ..ConnectionTest::default()
+        };
+    }
+
+    let capabilities = client
+        .get(format!("{}/api/capabilities", base_url))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json::<ServerCapabilities>())
+        .ok();
+    ConnectionTest {
+        reachable: true,
+        latency_ms: Some(latency_ms),
+        capabilities,
+        certificate_sha256,
+        error: None,
+    }
+}
+
+/// Make sure the server presents the pinned certificate before connecting to it
+pub fn verify_pinned_certificate(
+    url: &str,
+    options: &TlsOptions,
+    proxy: Option<&ProxyConfig>,
+) -> Result<(), String> {
+    if options.pinned_sha256.is_none() {
+        return Ok(());
+    }
+    let response = client(options, proxy, TEST_TIMEOUT)?
+        .get(format!("{}/health", url.trim().trim_end_matches('/')))
+        .send()
+        .map_err(|e| format!("Server is unreachable: {}", e))?;
+    check_pin(options, response_fingerprint(&response).as_deref())
+}
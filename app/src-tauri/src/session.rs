@@ -0,0 +1,87 @@
+//! In-memory transcript of everything dictated in the current session.
+//!
+//! A session starts at app launch and lasts until it is manually reset.
+//! Unlike history, the session transcript is never persisted to disk.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::RwLock;
+
+/// A single dictation within the session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionEntry {
+    pub timestamp: DateTime<Utc>,
+    pub text: String,
+}
+
+/// All dictations since the session started
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionTranscript {
+    pub started_at: DateTime<Utc>,
+    pub entries: Vec<SessionEntry>,
+}
+
+impl SessionTranscript {
+    fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Concatenate all entries into a single block of text, one paragraph per dictation
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Holds the transcript for the current session
+pub struct SessionStore {
+    transcript: RwLock<SessionTranscript>,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self {
+            transcript: RwLock::new(SessionTranscript::new()),
+        }
+    }
+}
+
+impl SessionStore {
+    /// Append a dictation to the session
+    pub fn append(&self, text: String) -> Result<SessionEntry, String> {
+        let entry = SessionEntry {
+            timestamp: Utc::now(),
+            text,
+        };
+        self.transcript
+            .write()
+            .map_err(|e| format!("Failed to write session transcript: {}", e))?
+            .entries
+            .push(entry.clone());
+        Ok(entry)
+    }
+
+    /// Get a copy of the current session transcript
+    pub fn snapshot(&self) -> Result<SessionTranscript, String> {
+        self.transcript
+            .read()
+            .map(|transcript| transcript.clone())
+            .map_err(|e| format!("Failed to read session transcript: {}", e))
+    }
+
+    /// Start a new, empty session
+    pub fn reset(&self) -> Result<SessionTranscript, String> {
+        let mut transcript = self
+            .transcript
+            .write()
+            .map_err(|e| format!("Failed to write session transcript: {}", e))?;
+        *transcript = SessionTranscript::new();
+        Ok(transcript.clone())
+    }
+}
@@ -1,5 +1,12 @@
+use crate::active_window::ActiveApp;
+use crate::error::AppError;
+use crate::stt::models::WhisperModel;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
 
 #[cfg(desktop)]
 use tauri_plugin_global_shortcut::Shortcut;
@@ -20,8 +27,555 @@ pub const DEFAULT_HOLD_KEY: &str = "Backquote";
 /// Default key for paste last transcription (Ctrl+Alt+.)
 pub const DEFAULT_PASTE_LAST_KEY: &str = "Period";
 
+/// Suggested key for cycling through history (Ctrl+Alt+,), used when the hotkey is enabled
+pub const DEFAULT_CYCLE_HISTORY_KEY: &str = "Comma";
+
+/// Suggested key for switching dictation language (Ctrl+Alt+/), used when the hotkey is enabled
+pub const DEFAULT_CYCLE_LANGUAGE_KEY: &str = "Slash";
+
+/// Suggested key for pausing and resuming a recording (Ctrl+Alt+P), used when the hotkey
+/// is enabled
+pub const DEFAULT_PAUSE_RESUME_KEY: &str = "P";
+
+/// Suggested key for retrying the last transcription (Ctrl+Alt+R), used when the hotkey
+/// is enabled
+pub const DEFAULT_RETRY_LAST_KEY: &str = "R";
+
+/// Suggested key for sending a dictation to the clipboard only (Ctrl+Alt+C), used when
+/// the hotkey is enabled
+pub const DEFAULT_CLIPBOARD_ONLY_KEY: &str = "C";
+
+/// Suggested key for switching to the next STT provider (Ctrl+Alt+S), used when the
+/// hotkey is enabled
+pub const DEFAULT_CYCLE_STT_PROVIDER_KEY: &str = "S";
+
+/// Suggested key for turning LLM formatting on and off (Ctrl+Alt+F), used when the
+/// hotkey is enabled
+pub const DEFAULT_TOGGLE_FORMATTING_KEY: &str = "F";
+
+/// Suggested key for switching to the next prompt preset (Ctrl+Alt+M), used when the
+/// hotkey is enabled
+pub const DEFAULT_CYCLE_PROMPT_PRESET_KEY: &str = "M";
+
+/// Suggested key for opening the command palette (Ctrl+Alt+K), used when the hotkey
+/// is enabled
+pub const DEFAULT_OPEN_PALETTE_KEY: &str = "K";
+
+/// Suggested key for pausing and resuming dictation (Ctrl+Alt+D), used when the hotkey
+/// is enabled
+pub const DEFAULT_DO_NOT_DISTURB_KEY: &str = "D";
+
+/// Default maximum gap between the two taps of a double-tap hotkey
+pub const DEFAULT_DOUBLE_TAP_INTERVAL_MS: u64 = 300;
+
+/// Default time allowed between the steps of a hotkey sequence
+pub const DEFAULT_SEQUENCE_TIMEOUT_MS: u64 = 1000;
+
+/// Default longest time a tap of the hold hotkey keeps recording latched
+pub const DEFAULT_HOLD_LATCH_MAX_SECONDS: u64 = 30;
+
+/// Default longest a recording runs before it stops by itself (0 for no limit)
+pub const DEFAULT_MAX_RECORDING_SECONDS: u64 = 600;
+
+/// Default audio from before the hotkey press prepended to a recording (off)
+pub const DEFAULT_PRE_ROLL_MS: u64 = 0;
+
+/// Default server URL when not configured
+pub const DEFAULT_SERVER_URL: &str = "http://127.0.0.1:8765";
+
+/// Default size of the downloaded model used for local Whisper transcription
+pub const DEFAULT_WHISPER_MODEL: WhisperModel = WhisperModel::Base;
+
+/// Default number of archived recordings kept (0 keeps any number)
+pub const DEFAULT_RECORDING_MAX_COUNT: usize = 100;
+
+/// Default number of days archived recordings are kept (0 keeps them forever)
+pub const DEFAULT_RECORDING_MAX_DAYS: u64 = 30;
+
+/// Default largest formatting prompt, in estimated tokens, sent to in-app formatters
+pub const DEFAULT_MAX_PROMPT_TOKENS: usize = 4096;
+
+/// Default number of turns that may be processing at once
+pub const DEFAULT_MAX_CONCURRENT_TURNS: usize = 1;
+
 // ============================================================================
 
+/// Helper to read a setting from the store with a default fallback
+pub fn get_setting_from_store<T: serde::de::DeserializeOwned>(
+    app: &AppHandle,
+    key: &str,
+    default: T,
+) -> T {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(key))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(default)
+}
+
+/// Save a setting to the store and emit `settings-changed`, so the windows reload
+/// their settings and the overlay passes the change on to the server
+pub fn save_setting_to_store<T: Serialize>(
+    app: &AppHandle,
+    key: &str,
+    value: T,
+) -> Result<(), String> {
+    let value =
+        serde_json::to_value(value).map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    let store = app
+        .store("settings.json")
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    store.set(key, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
+/// How newlines in formatted text are inserted into the target app
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NewlineMode {
+    /// Paste newlines literally as part of the text
+    #[default]
+    Paste,
+    /// Press Enter for each newline
+    Enter,
+    /// Press Shift+Enter for each newline (soft line break in chat apps)
+    ShiftEnter,
+}
+
+/// How text is inserted into the focused app
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InsertionMethod {
+    /// Put text on the clipboard and simulate Ctrl+V / Cmd+V
+    #[default]
+    Paste,
+    /// Simulate typing each character, for terminals, remote desktops and apps
+    /// that intercept paste
+    Type,
+    /// Write into the focused element via the accessibility API (macOS and Windows,
+    /// pasted or typed elsewhere and in elements that don't support it)
+    Accessibility,
+    /// Only copy text to the clipboard, leaving the user to paste it
+    ClipboardOnly,
+}
+
+impl InsertionMethod {
+    /// The newline mode to use with this method. Typed text can't contain a literal
+    /// newline, so newlines are typed as Enter instead.
+    pub fn newline_mode(self, mode: NewlineMode) -> NewlineMode {
+        match (self, mode) {
+            (Self::Type, NewlineMode::Paste) => NewlineMode::Enter,
+            _ => mode,
+        }
+    }
+
+    /// Whether this method puts text into the focused app (rather than only the clipboard)
+    pub fn inserts_text(self) -> bool {
+        self != Self::ClipboardOnly
+    }
+}
+
+/// Where a finished dictation goes
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// Insert into the focused app
+    #[default]
+    Insert,
+    /// Only copy to the clipboard, for when the wrong window may be focused
+    ClipboardOnly,
+    /// Insert into the focused app and leave the dictation on the clipboard
+    Both,
+}
+
+impl OutputMode {
+    /// The mode for a dictation made with the clipboard-only hotkey switched on:
+    /// the clipboard only, or inserting if the clipboard only is the usual mode
+    pub fn overridden(self) -> Self {
+        match self {
+            Self::Insert | Self::Both => Self::ClipboardOnly,
+            Self::ClipboardOnly => Self::Insert,
+        }
+    }
+}
+
+/// Per-app rule for how text is inserted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InsertionRule {
+    /// App name or executable pattern (case-insensitive substring match)
+    pub app: String,
+    /// Insertion method to use when this app is focused
+    pub method: InsertionMethod,
+}
+
+/// Find the insertion method for the given app, falling back to `default`
+pub fn resolve_insertion_method(
+    rules: &[InsertionRule],
+    app: Option<&ActiveApp>,
+    default: InsertionMethod,
+) -> InsertionMethod {
+    app.and_then(|app| rules.iter().find(|rule| app.matches(&rule.app)))
+        .map(|rule| rule.method)
+        .unwrap_or(default)
+}
+
+/// Per-app rule for how newlines are inserted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewlineRule {
+    /// App name or executable pattern (case-insensitive substring match)
+    pub app: String,
+    /// Newline mode to use when this app is focused
+    pub mode: NewlineMode,
+}
+
+/// Find the newline mode for the given app, defaulting to literal paste
+pub fn resolve_newline_mode(rules: &[NewlineRule], app: Option<&ActiveApp>) -> NewlineMode {
+    app.and_then(|app| rules.iter().find(|rule| app.matches(&rule.app)))
+        .map(|rule| rule.mode)
+        .unwrap_or_default()
+}
+
+/// Check if the app is in the `blocked_apps` list (same matching as per-app rules),
+/// where hotkeys don't record and nothing is inserted
+pub fn is_blocked_app(blocked_apps: &[String], app: Option<&ActiveApp>) -> bool {
+    app.is_some_and(|app| blocked_apps.iter().any(|pattern| app.matches(pattern)))
+}
+
+/// One section of the formatting prompt, as stored by the prompt settings UI
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PromptSection {
+    pub enabled: bool,
+    /// Custom prompt text, or `None` to use the server default
+    pub content: Option<String>,
+}
+
+/// Formatting prompt sections stored under `cleanup_prompt_sections`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CleanupPromptSections {
+    pub main: PromptSection,
+    pub advanced: PromptSection,
+    pub dictionary: PromptSection,
+}
+
+impl CleanupPromptSections {
+    /// Sections as they are before the prompt is first saved: the main and advanced
+    /// server defaults
+    pub fn server_defaults() -> Self {
+        let mut sections = Self::default();
+        sections.main.enabled = true;
+        sections.advanced.enabled = true;
+        sections
+    }
+}
+
+/// A shortcut that could not be registered, usually because another app already owns it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ShortcutRegistrationFailure {
+    /// Which action the shortcut triggers ("toggle", "hold", "paste_last", "cycle_history",
+    /// "cycle_language", "pause_resume", "retry_last", "clipboard_only",
+    /// "cycle_stt_provider", "toggle_formatting", "cycle_prompt_preset", "open_palette" or
+    /// "do_not_disturb")
+    pub action: String,
+    /// Shortcut string that failed, e.g. "ctrl+alt+Space"
+    pub shortcut: String,
+    /// Error reported by the OS
+    pub error: String,
+}
+
+/// Result of checking whether a hotkey can be registered with the OS
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HotkeyAvailability {
+    pub available: bool,
+    /// Why the hotkey can't be used, when unavailable
+    pub reason: Option<String>,
+}
+
+/// Build a user-facing message describing shortcuts that failed to register
+pub fn describe_registration_failures(failures: &[ShortcutRegistrationFailure]) -> String {
+    let details: Vec<String> = failures
+        .iter()
+        .map(|failure| {
+            format!(
+                "{} ({}): {}",
+                failure.action.replace('_', " "),
+                failure.shortcut,
+                failure.error
+            )
+        })
+        .collect();
+    format!(
+        "Some shortcuts could not be registered and may be in use by another application - {}",
+        details.join("; ")
+    )
+}
+
+/// What kind of input activates a hotkey
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyTrigger {
+    /// Keyboard shortcut registered with the OS
+    #[default]
+    Keyboard,
+    /// Mouse button, with `key` naming the button (e.g. "Mouse4"). Hold-to-record only.
+    Mouse,
+    /// Modifier tapped twice in quick succession, with `key` naming the modifier
+    /// (e.g. "ctrl"). Toggle recording only.
+    DoubleTap,
+    /// Media key or headset button, with `key` naming it (e.g. "MediaPlayPause").
+    /// Toggle recording only.
+    MediaKey,
+}
+
+/// Mouse buttons that can trigger hold-to-record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Middle,
+    /// Side "back" button (Mouse4)
+    Back,
+    /// Side "forward" button (Mouse5)
+    Forward,
+}
+
+impl MouseButton {
+    /// Parse a button name like "Mouse4" (case-insensitive)
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "mouse3" | "middle" => Some(Self::Middle),
+            "mouse4" | "back" => Some(Self::Back),
+            "mouse5" | "forward" => Some(Self::Forward),
+            _ => None,
+        }
+    }
+}
+
+/// Modifier keys that can be double-tapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapModifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Meta,
+}
+
+impl TapModifier {
+    /// Parse a modifier name like "ctrl" (case-insensitive)
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "ctrl" | "control" => Some(Self::Ctrl),
+            "alt" | "option" => Some(Self::Alt),
+            "shift" => Some(Self::Shift),
+            "meta" | "super" | "cmd" | "command" => Some(Self::Meta),
+            _ => None,
+        }
+    }
+}
+
+/// Media keys that can toggle recording. Bluetooth headsets send their buttons
+/// (AVRCP play/pause, next and previous) to the system as these keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKey {
+    PlayPause,
+    NextTrack,
+    PreviousTrack,
+    Stop,
+}
+
+impl MediaKey {
+    /// Parse a key name like "MediaPlayPause", as the webview names it
+    /// (case-insensitive)
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "mediaplaypause" | "playpause" => Some(Self::PlayPause),
+            "mediatracknext" | "next" => Some(Self::NextTrack),
+            "mediatrackprevious" | "previous" => Some(Self::PreviousTrack),
+            "mediastop" | "stop" => Some(Self::Stop),
+            _ => None,
+        }
+    }
+}
+
+/// Actions that can be bound to a hotkey
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    Toggle,
+    Hold,
+    PasteLast,
+    CycleHistory,
+    CycleLanguage,
+    PauseResume,
+    RetryLast,
+    ClipboardOnly,
+    CycleSttProvider,
+    ToggleFormatting,
+    CyclePromptPreset,
+    OpenPalette,
+    DoNotDisturb,
+}
+
+impl HotkeyAction {
+    pub const ALL: [Self; 13] = [
+        Self::Toggle,
+        Self::Hold,
+        Self::PasteLast,
+        Self::CycleHistory,
+        Self::CycleLanguage,
+        Self::PauseResume,
+        Self::RetryLast,
+        Self::ClipboardOnly,
+        Self::CycleSttProvider,
+        Self::ToggleFormatting,
+        Self::CyclePromptPreset,
+        Self::OpenPalette,
+        Self::DoNotDisturb,
+    ];
+
+    /// Name of the action, as reported in `ShortcutRegistrationFailure`
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Hold => "hold",
+            Self::PasteLast => "paste_last",
+            Self::CycleHistory => "cycle_history",
+            Self::CycleLanguage => "cycle_language",
+            Self::PauseResume => "pause_resume",
+            Self::RetryLast => "retry_last",
+            Self::ClipboardOnly => "clipboard_only",
+            Self::CycleSttProvider => "cycle_stt_provider",
+            Self::ToggleFormatting => "toggle_formatting",
+            Self::CyclePromptPreset => "cycle_prompt_preset",
+            Self::OpenPalette => "open_palette",
+            Self::DoNotDisturb => "do_not_disturb",
+        }
+    }
+
+    /// Parse an action name like "paste_last"
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// Position of the action in [`Self::ALL`]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Settings key the action's hotkey is stored under
+    pub fn setting_key(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle_hotkey",
+            Self::Hold => "hold_hotkey",
+            Self::PasteLast => "paste_last_hotkey",
+            Self::CycleHistory => "cycle_history_hotkey",
+            Self::CycleLanguage => "cycle_language_hotkey",
+            Self::PauseResume => "pause_resume_hotkey",
+            Self::RetryLast => "retry_last_hotkey",
+            Self::ClipboardOnly => "clipboard_only_hotkey",
+            Self::CycleSttProvider => "cycle_stt_provider_hotkey",
+            Self::ToggleFormatting => "toggle_formatting_hotkey",
+            Self::CyclePromptPreset => "cycle_prompt_preset_hotkey",
+            Self::OpenPalette => "open_palette_hotkey",
+            Self::DoNotDisturb => "do_not_disturb_hotkey",
+        }
+    }
+
+    /// Hotkey used when the stored one can't be parsed, or when none is stored for
+    /// an action that is bound by default
+    pub fn default_hotkey(self) -> HotkeyConfig {
+        HotkeyConfig::suggested(match self {
+            Self::Toggle => DEFAULT_TOGGLE_KEY,
+            Self::Hold => DEFAULT_HOLD_KEY,
+            Self::PasteLast => DEFAULT_PASTE_LAST_KEY,
+            Self::CycleHistory => DEFAULT_CYCLE_HISTORY_KEY,
+            Self::CycleLanguage => DEFAULT_CYCLE_LANGUAGE_KEY,
+            Self::PauseResume => DEFAULT_PAUSE_RESUME_KEY,
+            Self::RetryLast => DEFAULT_RETRY_LAST_KEY,
+            Self::ClipboardOnly => DEFAULT_CLIPBOARD_ONLY_KEY,
+            Self::CycleSttProvider => DEFAULT_CYCLE_STT_PROVIDER_KEY,
+            Self::ToggleFormatting => DEFAULT_TOGGLE_FORMATTING_KEY,
+            Self::CyclePromptPreset => DEFAULT_CYCLE_PROMPT_PRESET_KEY,
+            Self::OpenPalette => DEFAULT_OPEN_PALETTE_KEY,
+            Self::DoNotDisturb => DEFAULT_DO_NOT_DISTURB_KEY,
+        })
+    }
+
+    /// Whether the action has a hotkey before the user sets one. The others stay off
+    /// until a hotkey is saved for them.
+    pub fn is_bound_by_default(self) -> bool {
+        matches!(self, Self::Toggle | Self::Hold | Self::PasteLast)
+    }
+}
+
+/// The hotkey stored for `action`, its default if it is bound by default and none is
+/// stored, or `None` while it is off
+pub fn stored_hotkey(app: &AppHandle, action: HotkeyAction) -> Option<HotkeyConfig> {
+    let hotkey: Option<HotkeyConfig> = get_setting_from_store(app, action.setting_key(), None);
+    hotkey.or_else(|| {
+        action
+            .is_bound_by_default()
+            .then(|| action.default_hotkey())
+    })
+}
+
+/// The key combination pressed after the first step of a hotkey sequence
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HotkeyStep {
+    /// Modifier keys, may be empty for a single key (e.g. "T")
+    pub modifiers: Vec<String>,
+    pub key: String,
+}
+
+impl HotkeyStep {
+    /// Convert to shortcut string format like "ctrl+T"
+    pub fn to_shortcut_string(&self) -> String {
+        shortcut_string(&self.modifiers, &self.key)
+    }
+
+    /// Convert to a tauri Shortcut using FromStr parsing
+    #[cfg(desktop)]
+    pub fn to_shortcut(&self) -> Result<Shortcut, String> {
+        parse_shortcut(&self.to_shortcut_string())
+    }
+
+    /// Whether both steps are the same combination, ignoring case and modifier order
+    pub fn is_same_as(&self, other: &HotkeyStep) -> bool {
+        let normalized_modifiers = |step: &HotkeyStep| {
+            let mut modifiers: Vec<String> =
+                step.modifiers.iter().map(|m| m.to_lowercase()).collect();
+            modifiers.sort();
+            modifiers
+        };
+        self.key.eq_ignore_ascii_case(&other.key)
+            && normalized_modifiers(self) == normalized_modifiers(other)
+    }
+}
+
+/// Follow-up steps for every sequence that starts with `leader`
+pub fn sequence_follow_ups(
+    hotkeys: &[(HotkeyAction, HotkeyConfig)],
+    leader: &HotkeyStep,
+) -> Vec<(HotkeyAction, HotkeyStep)> {
+    hotkeys
+        .iter()
+        .filter(|(_, hotkey)| hotkey.is_sequence() && hotkey.leader().is_same_as(leader))
+        .filter_map(|(action, hotkey)| hotkey.follow_up().map(|step| (*action, step)))
+        .collect()
+}
+
+fn shortcut_string(modifiers: &[String], key: &str) -> String {
+    let mut parts: Vec<String> = modifiers.iter().map(|m| m.to_lowercase()).collect();
+    parts.push(key.to_string());
+    parts.join("+")
+}
+
+#[cfg(desktop)]
+fn parse_shortcut(shortcut_str: &str) -> Result<Shortcut, String> {
+    Shortcut::from_str(shortcut_str)
+        .map_err(|e| format!("Failed to parse shortcut '{}': {:?}", shortcut_str, e))
+}
+
 /// Configuration for a hotkey combination
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HotkeyConfig {
@@ -29,73 +583,142 @@ pub struct HotkeyConfig {
     pub modifiers: Vec<String>,
     /// The main key (e.g., "Space")
     pub key: String,
+    /// Input that activates the hotkey (missing in older settings, meaning keyboard)
+    #[serde(default)]
+    pub trigger: HotkeyTrigger,
+    /// Maximum milliseconds between taps for a double-tap trigger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tap_interval_ms: Option<u64>,
+    /// Second step of a sequence such as "Ctrl+Alt+D then T", pressed after this combination
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub then: Option<HotkeyStep>,
+    /// Bind the keys by their position, named as on a US keyboard, rather than by
+    /// the character they type, so the shortcut stays on the same keys when the
+    /// keyboard layout changes (missing in older settings, meaning by character)
+    #[serde(default)]
+    pub physical: bool,
 }
 
 impl Default for HotkeyConfig {
     fn default() -> Self {
-        Self {
-            modifiers: DEFAULT_HOTKEY_MODIFIERS
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
-            key: DEFAULT_TOGGLE_KEY.to_string(),
-        }
+        Self::suggested(DEFAULT_TOGGLE_KEY)
     }
 }
 
 impl HotkeyConfig {
-    /// Create default toggle hotkey config
-    pub fn default_toggle() -> Self {
+    /// A keyboard hotkey of the default modifiers and `key`, as suggested for an action
+    pub fn suggested(key: &str) -> Self {
         Self {
             modifiers: DEFAULT_HOTKEY_MODIFIERS
                 .iter()
                 .map(|s| s.to_string())
                 .collect(),
-            key: DEFAULT_TOGGLE_KEY.to_string(),
+            key: key.to_string(),
+            trigger: HotkeyTrigger::Keyboard,
+            tap_interval_ms: None,
+            then: None,
+            physical: false,
         }
     }
 
-    /// Create default hold hotkey config
-    pub fn default_hold() -> Self {
-        Self {
-            modifiers: DEFAULT_HOTKEY_MODIFIERS
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
-            key: DEFAULT_HOLD_KEY.to_string(),
+    /// The mouse button this hotkey is bound to, if it is a mouse trigger
+    pub fn mouse_button(&self) -> Option<MouseButton> {
+        match self.trigger {
+            HotkeyTrigger::Mouse => MouseButton::from_key(&self.key),
+            HotkeyTrigger::Keyboard | HotkeyTrigger::DoubleTap | HotkeyTrigger::MediaKey => None,
         }
     }
 
-    /// Create default paste-last hotkey config
-    pub fn default_paste_last() -> Self {
-        Self {
-            modifiers: DEFAULT_HOTKEY_MODIFIERS
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
-            key: DEFAULT_PASTE_LAST_KEY.to_string(),
+    /// The modifier this hotkey is bound to, if it is a double-tap trigger
+    pub fn tap_modifier(&self) -> Option<TapModifier> {
+        match self.trigger {
+            HotkeyTrigger::DoubleTap => TapModifier::from_key(&self.key),
+            HotkeyTrigger::Keyboard | HotkeyTrigger::Mouse | HotkeyTrigger::MediaKey => None,
+        }
+    }
+
+    /// The media key this hotkey is bound to, if it is a media key trigger
+    pub fn media_key(&self) -> Option<MediaKey> {
+        match self.trigger {
+            HotkeyTrigger::MediaKey => MediaKey::from_key(&self.key),
+            HotkeyTrigger::Keyboard | HotkeyTrigger::Mouse | HotkeyTrigger::DoubleTap => None,
         }
     }
 
-    /// Convert to shortcut string format like "ctrl+alt+Space"
+    /// Maximum gap between the two taps of a double-tap trigger
+    pub fn tap_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.tap_interval_ms
+                .unwrap_or(DEFAULT_DOUBLE_TAP_INTERVAL_MS),
+        )
+    }
+
+    /// Whether this is a keyboard shortcut with a second step
+    pub fn is_sequence(&self) -> bool {
+        self.trigger == HotkeyTrigger::Keyboard && self.then.is_some()
+    }
+
+    /// Whether this is a single keyboard shortcut registered directly with the OS
+    pub fn is_plain_keyboard(&self) -> bool {
+        self.trigger == HotkeyTrigger::Keyboard && self.then.is_none()
+    }
+
+    /// The key to register for `key`: the key at its position on the current layout
+    /// when bound by position, otherwise `key` itself
+    fn bound_key(&self, key: &str) -> String {
+        if self.physical && self.trigger == HotkeyTrigger::Keyboard {
+            crate::keyboard_layout::key_at_position(key)
+        } else {
+            key.to_string()
+        }
+    }
+
+    /// The first (or only) key combination of this hotkey, as registered
+    pub fn leader(&self) -> HotkeyStep {
+        HotkeyStep {
+            modifiers: self.modifiers.clone(),
+            key: self.bound_key(&self.key),
+        }
+    }
+
+    /// The second step of a sequence, as registered
+    pub fn follow_up(&self) -> Option<HotkeyStep> {
+        self.then.as_ref().map(|step| HotkeyStep {
+            modifiers: step.modifiers.clone(),
+            key: self.bound_key(&step.key),
+        })
+    }
+
+    /// Convert to shortcut string format like "ctrl+alt+Space".
+    /// For sequences this is the first step only.
     /// Note: modifiers must be lowercase for the parser to recognize them
     pub fn to_shortcut_string(&self) -> String {
-        let mut parts: Vec<String> = self.modifiers.iter().map(|m| m.to_lowercase()).collect();
-        parts.push(self.key.clone());
-        parts.join("+")
+        shortcut_string(&self.modifiers, &self.bound_key(&self.key))
+    }
+
+    /// Human-readable description including any second step, e.g. "ctrl+alt+D then T"
+    pub fn describe(&self) -> String {
+        match self.follow_up() {
+            Some(step) if self.is_sequence() => {
+                format!(
+                    "{} then {}",
+                    self.to_shortcut_string(),
+                    step.to_shortcut_string()
+                )
+            }
+            _ => self.to_shortcut_string(),
+        }
     }
 
     /// Convert to a tauri Shortcut using FromStr parsing
     #[cfg(desktop)]
     pub fn to_shortcut(&self) -> Result<Shortcut, String> {
-        let shortcut_str = self.to_shortcut_string();
-        Shortcut::from_str(&shortcut_str)
-            .map_err(|e| format!("Failed to parse shortcut '{}': {:?}", shortcut_str, e))
+        parse_shortcut(&self.to_shortcut_string())
     }
 
     /// Convert to a tauri Shortcut, falling back to a default if parsing fails
     #[cfg(desktop)]
-    pub fn to_shortcut_or_default(&self, default_fn: fn() -> Self) -> Shortcut {
+    pub fn to_shortcut_or_default(&self, default_fn: impl FnOnce() -> Self) -> Shortcut {
         self.to_shortcut().unwrap_or_else(|_| {
             default_fn()
                 .to_shortcut()
@@ -103,3 +726,239 @@ impl HotkeyConfig {
         })
     }
 }
+
+// ============================================================================
+// LOCALE-AWARE DEFAULTS - Adjust the defaults above to the user's keyboard
+// ============================================================================
+
+/// Hold-to-record key for layouts where Backquote is a dead key (Ctrl+Alt+/)
+pub const DEAD_BACKQUOTE_HOLD_KEY: &str = "Slash";
+
+/// Toggle key on macOS with non-US layouts, where Ctrl+Option+Space switches
+/// input sources (Ctrl+Alt+Enter)
+pub const MACOS_INPUT_SOURCE_TOGGLE_KEY: &str = "Enter";
+
+/// Languages whose usual keyboard layouts put a dead key or accent on Backquote
+const DEAD_BACKQUOTE_LANGUAGES: &[&str] = &[
+    "cs", "da", "de", "es", "fi", "fr", "hu", "is", "it", "nb", "nl", "nn", "no", "pt", "sk", "sv",
+    "tr",
+];
+
+/// Operating system the default hotkeys are chosen for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyPlatform {
+    MacOs,
+    Windows,
+    Linux,
+}
+
+impl HotkeyPlatform {
+    /// The platform this build runs on
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else if cfg!(target_os = "windows") {
+            Self::Windows
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Keyboard layout family, as far as it matters for the default hotkeys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// US-style layouts where Backquote types ` directly
+    UsCompatible,
+    /// Layouts where the Backquote position is a dead key or accent (e.g. German ^, French ²)
+    DeadBackquote,
+}
+
+impl KeyboardLayout {
+    /// Guess the layout family from a locale such as "de-DE" or "fr_CA.UTF-8"
+    pub fn from_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if DEAD_BACKQUOTE_LANGUAGES.contains(&language.as_str()) {
+            Self::DeadBackquote
+        } else {
+            Self::UsCompatible
+        }
+    }
+
+    /// The layout family for the system locale, assuming US-compatible if unknown
+    pub fn current() -> Self {
+        sys_locale::get_locale()
+            .map(|locale| Self::from_locale(&locale))
+            .unwrap_or(Self::UsCompatible)
+    }
+}
+
+/// Default hotkeys resolved for a platform and keyboard layout
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DefaultHotkeys {
+    pub toggle_hotkey: HotkeyConfig,
+    pub hold_hotkey: HotkeyConfig,
+    pub paste_last_hotkey: HotkeyConfig,
+}
+
+/// Pick default hotkeys that can be typed on the given platform and layout
+pub fn resolve_default_hotkeys(platform: HotkeyPlatform, layout: KeyboardLayout) -> DefaultHotkeys {
+    let mut defaults = DefaultHotkeys {
+        toggle_hotkey: HotkeyAction::Toggle.default_hotkey(),
+        hold_hotkey: HotkeyAction::Hold.default_hotkey(),
+        paste_last_hotkey: HotkeyAction::PasteLast.default_hotkey(),
+    };
+
+    if layout == KeyboardLayout::DeadBackquote {
+        defaults.hold_hotkey.key = DEAD_BACKQUOTE_HOLD_KEY.to_string();
+        if platform == HotkeyPlatform::MacOs {
+            defaults.toggle_hotkey.key = MACOS_INPUT_SOURCE_TOGGLE_KEY.to_string();
+        }
+    }
+
+    defaults
+}
+
+/// Default hotkeys for this machine
+pub fn current_default_hotkeys() -> DefaultHotkeys {
+    resolve_default_hotkeys(HotkeyPlatform::current(), KeyboardLayout::current())
+}
+
+/// Save this machine's default hotkeys for any hotkey that has never been set,
+/// so the first run registers (and the UI shows) keys the user can actually press
+pub fn store_default_hotkeys(app: &AppHandle) -> Result<(), AppError> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::settings_io("open settings", e))?;
+    let defaults = current_default_hotkeys();
+
+    let mut changed = false;
+    for (key, hotkey) in [
+        ("toggle_hotkey", &defaults.toggle_hotkey),
+        ("hold_hotkey", &defaults.hold_hotkey),
+        ("paste_last_hotkey", &defaults.paste_last_hotkey),
+    ] {
+        if store.get(key).is_none() {
+            let value = serde_json::to_value(hotkey)
+                .map_err(|e| AppError::settings_io(&format!("serialize {}", key), e))?;
+            store.set(key, value);
+            log::info!("Default {} set to {}", key, hotkey.describe());
+            changed = true;
+        }
+    }
+
+    if changed {
+        store
+            .save()
+            .map_err(|e| AppError::settings_io("save settings", e))?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// FEATURE FLAGS - Experimental subsystems the user has to opt in to
+// ============================================================================
+
+/// An experimental subsystem that stays off unless explicitly enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureFlag {
+    /// Insert text while the user is still speaking
+    StreamingInsertion,
+    /// Start recording when a wake word is heard
+    WakeWord,
+    /// Keep the microphone open and transcribe continuously
+    AlwaysListening,
+}
+
+impl FeatureFlag {
+    pub const ALL: [FeatureFlag; 3] = [
+        FeatureFlag::StreamingInsertion,
+        FeatureFlag::WakeWord,
+        FeatureFlag::AlwaysListening,
+    ];
+
+    /// Key of this flag in the stored `feature_flags` object
+    pub fn key(self) -> &'static str {
+        match self {
+            FeatureFlag::StreamingInsertion => "streaming_insertion",
+            FeatureFlag::WakeWord => "wake_word",
+            FeatureFlag::AlwaysListening => "always_listening",
+        }
+    }
+
+    /// What the flag turns on, for the settings UI
+    pub fn description(self) -> &'static str {
+        match self {
+            FeatureFlag::StreamingInsertion => "Insert text while you are still speaking",
+            FeatureFlag::WakeWord => "Start recording when a wake word is heard",
+            FeatureFlag::AlwaysListening => "Keep the microphone open and transcribe continuously",
+        }
+    }
+}
+
+/// Stored flag states by key. Keys are strings so flags removed in later versions
+/// don't stop the rest from loading.
+pub type FeatureFlags = BTreeMap<String, bool>;
+
+/// A feature flag and whether it is on, as listed in the settings UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeatureFlagState {
+    pub flag: FeatureFlag,
+    pub enabled: bool,
+    pub description: &'static str,
+}
+
+/// Whether `flag` is on. Flags are off unless explicitly enabled, so subsystems check
+/// `is_flag_enabled(&stored_feature_flags(app), flag)` before starting.
+pub fn is_flag_enabled(flags: &FeatureFlags, flag: FeatureFlag) -> bool {
+    flags.get(flag.key()).copied().unwrap_or(false)
+}
+
+/// Every known flag with its state
+pub fn describe_feature_flags(flags: &FeatureFlags) -> Vec<FeatureFlagState> {
+    FeatureFlag::ALL
+        .into_iter()
+        .map(|flag| FeatureFlagState {
+            flag,
+            enabled: is_flag_enabled(flags, flag),
+            description: flag.description(),
+        })
+        .collect()
+}
+
+/// Read the stored feature flags
+pub fn stored_feature_flags(app: &AppHandle) -> FeatureFlags {
+    get_setting_from_store(app, "feature_flags", FeatureFlags::new())
+}
+
+/// Turn a feature flag on or off and save it, returning the updated flags
+pub fn store_feature_flag(
+    app: &AppHandle,
+    flag: FeatureFlag,
+    enabled: bool,
+) -> Result<FeatureFlags, AppError> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::settings_io("open settings", e))?;
+
+    let mut flags = stored_feature_flags(app);
+    flags.insert(flag.key().to_string(), enabled);
+    let value = serde_json::to_value(&flags)
+        .map_err(|e| AppError::settings_io("serialize feature flags", e))?;
+    store.set("feature_flags", value);
+    store
+        .save()
+        .map_err(|e| AppError::settings_io("save settings", e))?;
+
+    log::info!(
+        "Feature flag {} {}",
+        flag.key(),
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(flags)
+}
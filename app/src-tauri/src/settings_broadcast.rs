@@ -0,0 +1,120 @@
+//! Broadcasting settings changes to every window.
+//!
+//! Settings are written from several places: the settings window, the overlay,
+//! the tray menu and hotkeys in Rust, and by hand in `settings.json`. Whenever the
+//! settings store reports a change, it is compared to the settings last broadcast
+//! and the changed keys are emitted as [`SETTINGS_CHANGED_EVENT`], so each window
+//! can refresh without polling. Changes arriving together (a save touching several
+//! keys) are coalesced into one event.
+
+use crate::events::{self, AppEvent};
+use crate::integrity::SETTINGS_FILE;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Listener};
+use tauri_plugin_store::StoreExt;
+
+/// Event emitted to every window with the [`SettingsChangedPayload`]
+pub const SETTINGS_CHANGED_EVENT: &str = "settings://changed";
+
+/// How long to wait for more changes before broadcasting
+const COALESCE_DELAY: Duration = Duration::from_millis(50);
+
+/// One changed setting. A setting that was added or removed has `null` on the
+/// side it was missing from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingChange {
+    pub key: String,
+    pub old_value: Value,
+    pub new_value: Value,
+}
+
+/// Payload of [`SETTINGS_CHANGED_EVENT`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingsChangedPayload {
+    /// Sorted by key
+    pub changes: Vec<SettingChange>,
+}
+
+impl SettingsChangedPayload {
+    /// The change to `key`, if it changed
+    pub fn change(&self, key: &str) -> Option<&SettingChange> {
+        self.changes.iter().find(|change| change.key == key)
+    }
+}
+
+/// Settings that differ between `old` and `new`, sorted by key
+pub fn diff_settings(old: &Map<String, Value>, new: &Map<String, Value>) -> Vec<SettingChange> {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let old_value = old.get(key).unwrap_or(&Value::Null);
+            let new_value = new.get(key).unwrap_or(&Value::Null);
+            (old_value != new_value).then(|| SettingChange {
+                key: key.clone(),
+                old_value: old_value.clone(),
+                new_value: new_value.clone(),
+            })
+        })
+        .collect()
+}
+
+fn stored_settings(app: &AppHandle) -> Option<Map<String, Value>> {
+    let store = app.store(SETTINGS_FILE).ok()?;
+    Some(store.entries().into_iter().collect())
+}
+
+/// Broadcast settings changes from now on. Checks for changes whenever the
+/// settings store is written, and on `settings-changed`, which is emitted after
+/// the store is reloaded or saved from a window.
+pub fn spawn(app: &AppHandle) {
+    let (tx, rx) = mpsc::channel();
+    for event in ["store://change", "settings-changed"] {
+        let tx = tx.clone();
+        app.listen(event, move |_| {
+            let _ = tx.send(());
+        });
+    }
+
+    let app = app.clone();
+    let mut broadcast = stored_settings(&app).unwrap_or_default();
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            while rx.recv_timeout(COALESCE_DELAY).is_ok() {}
+            let Some(current) = stored_settings(&app) else {
+                continue;
+            };
+            let changes = diff_settings(&broadcast, &current);
+            if changes.is_empty() {
+                continue;
+            }
+            broadcast = current;
+            publish(&app, SettingsChangedPayload { changes });
+        }
+    });
+}
+
+fn publish(app: &AppHandle, payload: SettingsChangedPayload) {
+    log::debug!(
+        "Settings changed: {}",
+        payload
+            .changes
+            .iter()
+            .map(|change| change.key.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if let Some(change) = payload.change("preferred_mic_ids") {
+        let device_id = change
+            .new_value
+            .get(0)
+            .and_then(|id| id.as_str())
+            .map(String::from);
+        events::publish(app, AppEvent::DeviceChanged { device_id });
+    }
+    let _ = app.emit(SETTINGS_CHANGED_EVENT, &payload);
+    events::publish(app, AppEvent::SettingsChanged);
+}
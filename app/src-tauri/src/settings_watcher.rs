@@ -0,0 +1,187 @@
+//! Hot reload of `settings.json` when it is edited outside the app.
+//!
+//! The settings store keeps settings in memory and only ever writes the file, so
+//! edits made by hand or by a sync tool were ignored until the next launch. The
+//! file's directory is watched (editors often replace the file instead of writing
+//! to it), and once writes settle the file is compared to the settings the app has
+//! held recently. The store autosaves, so the file can lag behind it: by the time a
+//! save is read, a slider may have changed the store again. Comparing the file to
+//! the live store would take that save for an outside edit and reload the older
+//! value, so instead a hash of the settings is kept after every change to the store,
+//! and a file matching one of them is the app's own save. Any other file is reloaded
+//! into the store, hotkeys are registered again and `settings-changed` is emitted,
+//! as if the settings had been saved from the UI. Either way, settings that are
+//! valid JSON are backed up for recovery after a crash mid-write.
+
+use crate::integrity::{self, SETTINGS_FILE};
+use notify::{RecursiveMode, Watcher};
+use serde_json::{Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Listener};
+use tauri_plugin_store::StoreExt;
+
+/// How long the file must go without changes before it is read, so a save that
+/// writes in several steps is read once, complete
+const SETTLE_DELAY: Duration = Duration::from_millis(300);
+
+/// How many recent store states count as the app's own saves. Autosave trails the
+/// store by a moment, so only the last few can still be on their way to disk.
+const RECENT_SETTINGS_LIMIT: usize = 32;
+
+/// Hash of the settings, independent of key order
+pub fn settings_hash(settings: &Map<String, Value>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_object(settings, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_object(map: &Map<String, Value>, hasher: &mut DefaultHasher) {
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+    for key in keys {
+        key.hash(hasher);
+        hash_value(&map[key], hasher);
+    }
+    '}'.hash(hasher);
+}
+
+fn hash_value(value: &Value, hasher: &mut DefaultHasher) {
+    match value {
+        Value::Object(map) => hash_object(map, hasher),
+        Value::Array(items) => {
+            for item in items {
+                hash_value(item, hasher);
+            }
+            ']'.hash(hasher);
+        }
+        other => other.to_string().hash(hasher),
+    }
+}
+
+/// Hashes of the settings the store held most recently, i.e. what its autosave may write
+#[derive(Debug, Default)]
+pub struct RecentSettings {
+    hashes: VecDeque<u64>,
+}
+
+impl RecentSettings {
+    /// Remember `settings` as a state the store has held
+    pub fn record(&mut self, settings: &Map<String, Value>) {
+        let hash = settings_hash(settings);
+        if self.hashes.back() == Some(&hash) {
+            return;
+        }
+        if self.hashes.len() == RECENT_SETTINGS_LIMIT {
+            self.hashes.pop_front();
+        }
+        self.hashes.push_back(hash);
+    }
+
+    /// Whether the store has recently held `settings`
+    pub fn contains(&self, settings: &Map<String, Value>) -> bool {
+        self.hashes.contains(&settings_hash(settings))
+    }
+}
+
+/// Whether the settings file was edited outside the app, i.e. holds settings the
+/// store hasn't held recently. `None` if the file isn't a JSON object, e.g. while
+/// half written or after a typo, in which case the store is kept as is.
+pub fn is_outside_edit(contents: &str, recent: &RecentSettings) -> Option<bool> {
+    match serde_json::from_str::<Value>(contents).ok()? {
+        Value::Object(settings) => Some(!recent.contains(&settings)),
+        _ => None,
+    }
+}
+
+fn stored_settings(app: &AppHandle) -> Option<Map<String, Value>> {
+    let store = app.store(SETTINGS_FILE).ok()?;
+    Some(store.entries().into_iter().collect())
+}
+
+/// Watch the settings file in `app_data_dir` and reload settings edited outside the app
+pub fn watch(app: &AppHandle, app_data_dir: &Path) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let touches_settings = event
+            .paths
+            .iter()
+            .any(|path| path.file_name().is_some_and(|name| name == SETTINGS_FILE));
+        if touches_settings && !event.kind.is_access() {
+            let _ = tx.send(());
+        }
+    })
+    .map_err(|e| format!("Failed to create settings watcher: {}", e))?;
+    watcher
+        .watch(app_data_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch settings: {}", e))?;
+
+    let recent = Arc::new(Mutex::new(RecentSettings::default()));
+    if let Some(settings) = stored_settings(app) {
+        recent.lock().unwrap().record(&settings);
+    }
+    let handle = app.clone();
+    let recorded = recent.clone();
+    app.listen("store://change", move |_| {
+        if let Some(settings) = stored_settings(&handle) {
+            recorded.lock().unwrap().record(&settings);
+        }
+    });
+
+    let settings_path = app_data_dir.join(SETTINGS_FILE);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        // Owned by the thread so it keeps watching for the life of the app
+        let _watcher = watcher;
+        while rx.recv().is_ok() {
+            while rx.recv_timeout(SETTLE_DELAY).is_ok() {}
+            reload_if_changed(&app, &settings_path, &recent);
+        }
+    });
+    Ok(())
+}
+
+fn reload_if_changed(app: &AppHandle, settings_path: &Path, recent: &Mutex<RecentSettings>) {
+    let Ok(contents) = std::fs::read_to_string(settings_path) else {
+        return;
+    };
+    let Ok(store) = app.store(SETTINGS_FILE) else {
+        return;
+    };
+    let differs = is_outside_edit(&contents, &recent.lock().unwrap());
+    if differs.is_some() {
+        if let Some(app_data_dir) = settings_path.parent() {
+            if let Err(e) = integrity::back_up_settings(app_data_dir) {
+                log::warn!("{}", e);
+            }
+        }
+    }
+    match differs {
+        Some(true) => {}
+        Some(false) => return,
+        None => {
+            log::warn!("Ignoring settings file edit that isn't a JSON object");
+            return;
+        }
+    }
+
+    if let Err(e) = store.reload() {
+        log::warn!("Failed to reload edited settings: {}", e);
+        return;
+    }
+    log::info!("Reloaded settings edited outside the app");
+    if let Some(settings) = stored_settings(app) {
+        recent.lock().unwrap().record(&settings);
+    }
+
+    #[cfg(desktop)]
+    crate::commands::settings::register_shortcuts_from_store(app);
+    let _ = app.emit("settings-changed", ());
+}
@@ -0,0 +1,252 @@
+//! Voice-triggered text snippets.
+//!
+//! A snippet maps a spoken trigger phrase ("insert my signature") to a stored
+//! template. After a dictation is transcribed and formatted, and before it is
+//! inserted, each trigger found in the text is replaced with its template. Triggers
+//! match whole words regardless of case and punctuation, so the formatter adding a
+//! capital letter or a period doesn't stop them; a dictation that is only a trigger
+//! becomes the template exactly. Templates may use `{date}`, `{time}` and
+//! `{clipboard}`, with `{{` and `}}` for literal braces. Snippets are stored in
+//! `snippets.db` in the app data directory.
+
+use crate::phrases::{find_phrases, is_blank, phrase_words, trailing_punctuation_len};
+use chrono::{DateTime, Local};
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Snippet database file in the app data directory
+pub const SNIPPETS_DB_FILE: &str = "snippets.db";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS snippets (
+        id TEXT PRIMARY KEY,
+        trigger TEXT NOT NULL,
+        template TEXT NOT NULL
+    );
+";
+
+const SNIPPET_COLUMNS: &str = "id, trigger, template";
+
+/// Characters that end a sentence
+const SENTENCE_END: &[char] = &['.', '!', '?'];
+
+/// A spoken trigger phrase and the text it expands to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub id: String,
+    pub trigger: String,
+    pub template: String,
+}
+
+impl Snippet {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            trigger: row.get(1)?,
+            template: row.get(2)?,
+        })
+    }
+}
+
+/// Values filled into template placeholders
+#[derive(Debug, Clone)]
+pub struct TemplateValues {
+    pub now: DateTime<Local>,
+    pub clipboard: Option<String>,
+}
+
+/// Fill `{date}`, `{time}` and `{clipboard}` into a template. Unknown placeholders
+/// are left as they are.
+pub fn render_template(template: &str, values: &TemplateValues) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("{{") {
+            rendered.push('{');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("}}") {
+            rendered.push('}');
+            rest = after;
+        } else if let Some((name, after)) = rest
+            .strip_prefix('{')
+            .and_then(|inner| inner.split_once('}'))
+        {
+            match name {
+                "date" => rendered.push_str(&values.now.format("%Y-%m-%d").to_string()),
+                "time" => rendered.push_str(&values.now.format("%H:%M").to_string()),
+                "clipboard" => rendered.push_str(values.clipboard.as_deref().unwrap_or_default()),
+                _ => {
+                    rendered.push('{');
+                    rendered.push_str(name);
+                    rendered.push('}');
+                }
+            }
+            rest = after;
+        } else {
+            rendered.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Whether templates use `{clipboard}`, so the clipboard is only read when needed
+pub fn uses_clipboard(snippets: &[Snippet]) -> bool {
+    snippets
+        .iter()
+        .any(|snippet| snippet.template.contains("{clipboard}"))
+}
+
+/// Replace every trigger phrase in `text` with its rendered template. Returns the
+/// text unchanged when no trigger is found.
+pub fn expand_snippets(text: &str, snippets: &[Snippet], values: &TemplateValues) -> String {
+    let triggers: Vec<Vec<String>> = snippets
+        .iter()
+        .map(|snippet| phrase_words(&snippet.trigger))
+        .collect();
+    let matches = find_phrases(text, &triggers);
+
+    // Only the trigger, maybe with a capital and a period added by formatting
+    if let [only] = matches.as_slice() {
+        if is_blank(&text[..only.start]) && is_blank(&text[only.end..]) {
+            return render_template(&snippets[only.phrase].template, values);
+        }
+    }
+
+    let mut expanded = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    for found in matches {
+        let rendered = render_template(&snippets[found.phrase].template, values);
+        expanded.push_str(&text[copied_to..found.start]);
+        expanded.push_str(&rendered);
+        copied_to = found.end;
+        // A template ending a sentence replaces the period formatting put after it
+        if rendered.ends_with(SENTENCE_END) {
+            copied_to += trailing_punctuation_len(&text[copied_to..]);
+        }
+    }
+    expanded.push_str(&text[copied_to..]);
+    expanded
+}
+
+/// Persistent store of snippets
+pub struct SnippetStore {
+    connection: Mutex<Connection>,
+}
+
+impl SnippetStore {
+    /// Open (or create) the snippet database in the app data directory
+    pub fn new(app_data_dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+        let connection = Connection::open(app_data_dir.join(SNIPPETS_DB_FILE))
+            .map_err(|e| format!("Failed to open snippet database: {}", e))?;
+        Self::with_connection(connection)
+    }
+
+    /// Snippets kept in memory only, discarded when dropped
+    pub fn in_memory() -> Result<Self, String> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open snippet database: {}", e))?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| format!("Failed to create snippet table: {}", e))?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.connection
+            .lock()
+            .map_err(|e| format!("Failed to access snippets: {}", e))
+    }
+
+    /// All snippets, in the order they were added
+    pub fn list(&self) -> Result<Vec<Snippet>, String> {
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {} FROM snippets ORDER BY rowid",
+                SNIPPET_COLUMNS
+            ))
+            .map_err(|e| format!("Failed to read snippets: {}", e))?;
+        statement
+            .query_map([], Snippet::from_row)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to read snippets: {}", e))
+    }
+
+    pub fn add(&self, trigger: &str, template: &str) -> Result<Snippet, String> {
+        let trigger = self.check_trigger(trigger, None)?;
+        let snippet = Snippet {
+            id: Uuid::new_v4().to_string(),
+            trigger,
+            template: template.to_string(),
+        };
+        self.lock()?
+            .execute(
+                "INSERT INTO snippets (id, trigger, template) VALUES (?1, ?2, ?3)",
+                params![snippet.id, snippet.trigger, snippet.template],
+            )
+            .map_err(|e| format!("Failed to add snippet: {}", e))?;
+        Ok(snippet)
+    }
+
+    pub fn update(&self, id: &str, trigger: &str, template: &str) -> Result<Snippet, String> {
+        let trigger = self.check_trigger(trigger, Some(id))?;
+        let updated = self
+            .lock()?
+            .execute(
+                "UPDATE snippets SET trigger = ?2, template = ?3 WHERE id = ?1",
+                params![id, trigger, template],
+            )
+            .map_err(|e| format!("Failed to update snippet: {}", e))?;
+        if updated == 0 {
+            return Err("Snippet not found".to_string());
+        }
+        Ok(Snippet {
+            id: id.to_string(),
+            trigger,
+            template: template.to_string(),
+        })
+    }
+
+    /// Remove a snippet. Returns false if it didn't exist.
+    pub fn remove(&self, id: &str) -> Result<bool, String> {
+        let removed = self
+            .lock()?
+            .execute("DELETE FROM snippets WHERE id = ?1", [id])
+            .map_err(|e| format!("Failed to remove snippet: {}", e))?;
+        Ok(removed > 0)
+    }
+
+    /// Trim the trigger and make sure it has words and no other snippet (other
+    /// than `except_id`) has the same one
+    fn check_trigger(&self, trigger: &str, except_id: Option<&str>) -> Result<String, String> {
+        let words = phrase_words(trigger);
+        if words.is_empty() {
+            return Err("The trigger phrase needs at least one word".to_string());
+        }
+        if let Some(existing) = self.list()?.into_iter().find(|snippet| {
+            Some(snippet.id.as_str()) != except_id && phrase_words(&snippet.trigger) == words
+        }) {
+            return Err(format!(
+                "\"{}\" is already the trigger of another snippet",
+                existing.trigger
+            ));
+        }
+        Ok(trigger.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+}
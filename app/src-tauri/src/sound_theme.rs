@@ -0,0 +1,257 @@
+//! Which sound plays for each feedback event, and how loud.
+//!
+//! Each event can use the built-in sound, play nothing, or play a WAV file chosen
+//! by the user. A theme directory supplies files for every event left on its
+//! default, named after the event (`start.wav`, `stop.wav`, `error.wav`,
+//! `complete.wav`). Files that are missing or aren't valid WAV files fall back to
+//! the built-in sound. Only recording start and stop have built-in sounds, so
+//! errors and completed turns are silent unless a file is provided.
+//!
+//! Every event can also be turned off or given its own volume, scaled by a master
+//! volume. These replace the single `sound_enabled` flag of earlier versions.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Largest sound file that will be played, to keep feedback short
+pub const MAX_SOUND_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Default master and per-event volume
+pub const DEFAULT_SOUND_VOLUME: f32 = 1.0;
+
+/// Types of sounds that can be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundType {
+    RecordingStart,
+    RecordingStop,
+    /// A turn failed to transcribe or insert
+    Error,
+    /// A turn's text was inserted
+    Complete,
+}
+
+impl SoundType {
+    /// Name of this sound's file in a theme directory
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::RecordingStart => "start.wav",
+            Self::RecordingStop => "stop.wav",
+            Self::Error => "error.wav",
+            Self::Complete => "complete.wav",
+        }
+    }
+}
+
+/// Where an event's sound comes from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "source", content = "path")]
+pub enum SoundSource {
+    /// The theme directory's file if it has one, otherwise the built-in sound
+    #[default]
+    Default,
+    /// No sound
+    Silent,
+    /// A WAV file
+    File(String),
+}
+
+/// Sounds chosen for each feedback event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundTheme {
+    /// Directory of WAV files named after each event
+    pub directory: Option<String>,
+    pub start: SoundSource,
+    pub stop: SoundSource,
+    pub error: SoundSource,
+    pub complete: SoundSource,
+}
+
+/// A sound to play
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sound {
+    /// The sound bundled with the app
+    Builtin(SoundType),
+    /// A validated WAV file
+    File(PathBuf),
+}
+
+impl SoundTheme {
+    pub fn source(&self, sound_type: SoundType) -> &SoundSource {
+        match sound_type {
+            SoundType::RecordingStart => &self.start,
+            SoundType::RecordingStop => &self.stop,
+            SoundType::Error => &self.error,
+            SoundType::Complete => &self.complete,
+        }
+    }
+
+    /// The sound to play for `sound_type`, or `None` for silence
+    pub fn resolve(&self, sound_type: SoundType) -> Option<Sound> {
+        let builtin = has_builtin(sound_type).then_some(Sound::Builtin(sound_type));
+        match self.source(sound_type) {
+            SoundSource::Silent => None,
+            SoundSource::File(path) => match validate_sound_file(Path::new(path)) {
+                Ok(()) => Some(Sound::File(PathBuf::from(path))),
+                Err(e) => {
+                    log::warn!("{}, using the built-in sound", e);
+                    builtin
+                }
+            },
+            SoundSource::Default => {
+                let Some(directory) = self.directory.as_deref().filter(|dir| !dir.is_empty())
+                else {
+                    return builtin;
+                };
+                let path = Path::new(directory).join(sound_type.file_name());
+                if !path.exists() {
+                    return builtin;
+                }
+                match validate_sound_file(&path) {
+                    Ok(()) => Some(Sound::File(path)),
+                    Err(e) => {
+                        log::warn!("{}, using the built-in sound", e);
+                        builtin
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether the app bundles a sound for `sound_type`
+pub fn has_builtin(sound_type: SoundType) -> bool {
+    matches!(
+        sound_type,
+        SoundType::RecordingStart | SoundType::RecordingStop
+    )
+}
+
+/// Check that a file exists, isn't too large and starts with a WAV header
+pub fn validate_sound_file(path: &Path) -> Result<(), String> {
+    let metadata = fs::metadata(path)
+        .map_err(|e| format!("Sound file {} can't be read: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("Sound file {} is not a file", path.display()));
+    }
+    if metadata.len() > MAX_SOUND_FILE_BYTES {
+        return Err(format!(
+            "Sound file {} is larger than {} MB",
+            path.display(),
+            MAX_SOUND_FILE_BYTES / (1024 * 1024)
+        ));
+    }
+
+    let mut header = [0u8; 12];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map_err(|_| format!("Sound file {} is not a WAV file", path.display()))?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return Err(format!("Sound file {} is not a WAV file", path.display()));
+    }
+    Ok(())
+}
+
+/// Whether and how loud one event's sound plays
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventSound {
+    pub enabled: bool,
+    /// From 0 to 1, scaled by the master volume
+    pub volume: f32,
+}
+
+impl Default for EventSound {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            volume: DEFAULT_SOUND_VOLUME,
+        }
+    }
+}
+
+/// Volume and on/off state of the feedback sounds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundSettings {
+    /// Master volume from 0 to 1
+    pub volume: f32,
+    pub start: EventSound,
+    pub stop: EventSound,
+    pub error: EventSound,
+    pub complete: EventSound,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            volume: DEFAULT_SOUND_VOLUME,
+            start: EventSound::default(),
+            stop: EventSound::default(),
+            error: EventSound::default(),
+            complete: EventSound::default(),
+        }
+    }
+}
+
+impl SoundSettings {
+    /// Saved sound settings, or for settings saved before they existed, every
+    /// event on or off as the old `sound_enabled` flag was
+    pub fn from_stored(saved: Option<Self>, legacy_enabled: Option<bool>) -> Self {
+        if let Some(saved) = saved {
+            return saved;
+        }
+        let enabled = legacy_enabled.unwrap_or(true);
+        let event = EventSound {
+            enabled,
+            ..EventSound::default()
+        };
+        Self {
+            volume: DEFAULT_SOUND_VOLUME,
+            start: event,
+            stop: event,
+            error: event,
+            complete: event,
+        }
+    }
+
+    pub fn event(&self, sound_type: SoundType) -> &EventSound {
+        match sound_type {
+            SoundType::RecordingStart => &self.start,
+            SoundType::RecordingStop => &self.stop,
+            SoundType::Error => &self.error,
+            SoundType::Complete => &self.complete,
+        }
+    }
+
+    pub fn event_mut(&mut self, sound_type: SoundType) -> &mut EventSound {
+        match sound_type {
+            SoundType::RecordingStart => &mut self.start,
+            SoundType::RecordingStop => &mut self.stop,
+            SoundType::Error => &mut self.error,
+            SoundType::Complete => &mut self.complete,
+        }
+    }
+
+    /// Volume to play an event's sound at, or `None` if it is off or silent
+    pub fn volume_for(&self, sound_type: SoundType) -> Option<f32> {
+        let event = self.event(sound_type);
+        if !event.enabled {
+            return None;
+        }
+        let volume = clamp_volume(self.volume) * clamp_volume(event.volume);
+        (volume > 0.0).then_some(volume)
+    }
+}
+
+/// Keep a volume between 0 and 1, treating invalid values as the default
+pub fn clamp_volume(volume: f32) -> f32 {
+    if volume.is_finite() {
+        volume.clamp(0.0, 1.0)
+    } else {
+        DEFAULT_SOUND_VOLUME
+    }
+}
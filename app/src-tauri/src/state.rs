@@ -1,4 +1,10 @@
-use std::sync::atomic::AtomicBool;
+use crate::active_window::ActiveApp;
+use crate::hold_latch::HoldLatch;
+use crate::settings::HotkeyAction;
+use crate::vad::SilenceDetector;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::Mutex;
+use std::time::Instant;
 
 #[derive(Default)]
 pub struct AppState {
@@ -6,8 +12,27 @@ pub struct AppState {
     pub is_recording: AtomicBool,
     /// Tracks if PTT key is currently held down (for hold-to-record mode)
     pub ptt_key_held: AtomicBool,
-    /// Tracks if paste-last key is currently held down
-    pub paste_key_held: AtomicBool,
-    /// Tracks if toggle key is currently held down (for debouncing - action happens on release)
-    pub toggle_key_held: AtomicBool,
+    /// Hold hotkey state when a quick tap latches recording (tap-to-latch mode)
+    pub hold_latch: Mutex<HoldLatch>,
+    /// Watches a toggle-mode recording for silence when auto-stop is enabled
+    pub silence_detector: Mutex<Option<SilenceDetector>>,
+    /// Tracks which hotkeys are held down, by [`HotkeyAction::index`], so actions
+    /// that happen on release run once per press
+    pub hotkeys_held: [AtomicBool; HotkeyAction::ALL.len()],
+    /// Tracks if the overlay is connected to the server (for choosing a formatter)
+    pub server_connected: AtomicBool,
+    /// When the current recording started, for the duration stored in history
+    pub recording_started_at: Mutex<Option<Instant>>,
+    /// When the current recording was paused; its audio is dropped until it resumes
+    pub paused_at: Mutex<Option<Instant>>,
+    /// Counts started recordings, so a timer can tell if its recording is still running
+    pub recording_id: AtomicU64,
+    /// App focused when the current (or last) recording started, where its text goes
+    pub recording_app: Mutex<Option<ActiveApp>>,
+    /// Whether the clipboard-only hotkey was pressed for the next recording
+    pub clipboard_only_next: AtomicBool,
+    /// Whether the current (or last) recording's text goes to the clipboard only
+    pub recording_clipboard_only: AtomicBool,
+    /// Do not disturb: nothing may start a recording until dictation is resumed
+    pub dictation_paused: AtomicBool,
 }
@@ -0,0 +1,561 @@
+pub mod models;
+mod server;
+#[cfg(feature = "local-whisper")]
+pub mod whisper;
+
+use crate::pre_roll::PreRollBuffer;
+use crate::settings::get_setting_from_store;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+#[cfg(feature = "local-whisper")]
+use {
+    crate::settings::DEFAULT_WHISPER_MODEL,
+    models::{WhisperModel, WhisperModelStore},
+    std::path::PathBuf,
+    tauri::Manager,
+};
+
+pub use server::ServerSttProvider;
+#[cfg(feature = "local-whisper")]
+pub use whisper::LocalWhisperProvider;
+
+/// `stt_provider` setting value that selects in-app Whisper transcription
+pub const LOCAL_WHISPER_PROVIDER: &str = "local-whisper";
+
+/// Format of the PCM audio passed to [`SttProvider::send_audio`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl AudioFormat {
+    /// 16 kHz mono, the format Whisper models expect
+    pub const WHISPER: Self = Self {
+        sample_rate: 16_000,
+        channels: 1,
+    };
+
+    /// How long `samples` interleaved samples in this format play for
+    pub fn duration_ms(&self, samples: usize) -> u64 {
+        let per_second = u64::from(self.sample_rate) * u64::from(self.channels.max(1));
+        if per_second == 0 {
+            return 0;
+        }
+        samples as u64 * 1000 / per_second
+    }
+}
+
+impl Default for AudioFormat {
+    fn default() -> Self {
+        Self::WHISPER
+    }
+}
+
+/// Work that produces a transcript, returned by [`SttProvider::finish`]
+pub type FinishJob = Box<dyn FnOnce() -> Result<Option<String>, String> + Send>;
+
+/// A speech-to-text backend driven by the recording turn controller.
+///
+/// A turn is `start_stream`, any number of `send_audio` calls, then `finalize`.
+/// Interim results can be drained with `partials` at any point during the turn.
+pub trait SttProvider: Send {
+    /// Short identifier used in logs
+    fn name(&self) -> &'static str;
+
+    /// Begin a new utterance with audio in the given format
+    fn start_stream(&mut self, format: AudioFormat) -> Result<(), String>;
+
+    /// Feed interleaved f32 samples for the current utterance
+    fn send_audio(&mut self, samples: &[f32]) -> Result<(), String>;
+
+    /// End the current utterance.
+    /// Returns the final transcript, or `None` when the provider delivers it asynchronously
+    /// (the server path sends it to the overlay over its own connection).
+    fn finalize(&mut self) -> Result<Option<String>, String>;
+
+    /// End the current utterance like `finalize`, but hand back the work of producing
+    /// the transcript instead of doing it, so it can run once the provider is unlocked
+    /// and the next utterance can start meanwhile. Providers that finish quickly
+    /// don't need to override this.
+    fn finish(&mut self) -> FinishJob {
+        let result = self.finalize();
+        Box::new(move || result)
+    }
+
+    /// Drain interim transcripts produced since the last call
+    fn partials(&mut self) -> Vec<String>;
+
+    /// Whether this provider transcribes audio passed to `send_audio`, so it can retry
+    /// a turn another provider failed on
+    fn transcribes_in_app(&self) -> bool {
+        true
+    }
+}
+
+/// A provider the server offers, as listed in its `available-providers` message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderInfo {
+    /// `stt_provider` setting value that selects it
+    pub value: String,
+    pub label: String,
+    #[serde(default)]
+    pub is_local: bool,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// The provider after `current` in `available`, wrapping around, or the first one
+/// when `current` isn't among them (the server default or in-app Whisper)
+pub fn next_provider<'a>(
+    current: Option<&str>,
+    available: &'a [ProviderInfo],
+) -> Option<&'a ProviderInfo> {
+    let next = match current.and_then(|value| {
+        available
+            .iter()
+            .position(|provider| provider.value == value)
+    }) {
+        Some(index) => (index + 1) % available.len(),
+        None => 0,
+    };
+    available.get(next)
+}
+
+/// A final transcript and the provider that produced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub text: String,
+    pub provider: &'static str,
+}
+
+/// Audio of the current turn, kept so a fallback provider can retry it, so it can be
+/// saved to the recording archive, so it can be queued until the server is reachable
+/// or so the user can retry it
+#[derive(Clone, Default)]
+struct TurnAudio {
+    /// Whether a turn is in progress (audio between turns goes to the pre-roll)
+    active: bool,
+    format: AudioFormat,
+    samples: Vec<f32>,
+}
+
+/// Holds the active STT provider so it can be swapped without touching the turn controller.
+///
+/// When a fallback provider is configured, the turn's audio is buffered and retried
+/// once with the fallback if the active provider fails or returns nothing.
+/// Audio sent between turns is kept as pre-roll for the next turn. A turn recorded
+/// without a server connection is captured in-app and, if nothing transcribes it,
+/// kept for [`Self::take_unsent_audio`]. The last turn's audio is kept in memory for
+/// [`Self::last_audio`] while that is enabled.
+pub struct SttManager {
+    provider: Mutex<Box<dyn SttProvider>>,
+    fallback: Mutex<Option<Box<dyn SttProvider>>>,
+    turn_audio: Mutex<TurnAudio>,
+    pre_roll: Mutex<PreRollBuffer>,
+    /// Whether turn audio is kept for the recording archive
+    archive_audio: AtomicBool,
+    /// Audio of the last finalized turn, while archiving
+    finished_audio: Mutex<Option<TurnAudio>>,
+    /// Whether the current turn was started without a server connection
+    offline_turn: AtomicBool,
+    /// Audio of the last offline turn that couldn't be transcribed
+    unsent_audio: Mutex<Option<TurnAudio>>,
+    /// Whether turn audio is kept so the last turn can be transcribed again
+    keep_last_audio: AtomicBool,
+    /// Audio of the last finalized turn, while keeping it
+    last_audio: Mutex<Option<TurnAudio>>,
+}
+
+impl SttManager {
+    pub fn new(provider: Box<dyn SttProvider>) -> Self {
+        Self {
+            provider: Mutex::new(provider),
+            fallback: Mutex::new(None),
+            turn_audio: Mutex::new(TurnAudio::default()),
+            pre_roll: Mutex::new(PreRollBuffer::new(AudioFormat::default())),
+            archive_audio: AtomicBool::new(false),
+            finished_audio: Mutex::new(None),
+            offline_turn: AtomicBool::new(false),
+            unsent_audio: Mutex::new(None),
+            keep_last_audio: AtomicBool::new(false),
+            last_audio: Mutex::new(None),
+        }
+    }
+
+    /// Replace the provider that retries failed turns (`None` disables retrying)
+    pub fn set_fallback(&self, fallback: Option<Box<dyn SttProvider>>) -> Result<(), String> {
+        let mut current = self.fallback.lock().map_err(|e| e.to_string())?;
+        if let Some(fallback) = &fallback {
+            log::info!("STT fallback provider: {}", fallback.name());
+        }
+        *current = fallback;
+        Ok(())
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.fallback
+            .lock()
+            .is_ok_and(|fallback| fallback.is_some())
+    }
+
+    /// Keep each turn's audio so it can be archived with [`Self::take_finished_audio`]
+    pub fn set_archive_audio(&self, enabled: bool) {
+        self.archive_audio.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Format and samples of the last finalized turn, if archiving was on
+    pub fn take_finished_audio(&self) -> Option<(AudioFormat, Vec<f32>)> {
+        let audio = self.finished_audio.lock().ok()?.take()?;
+        (!audio.samples.is_empty()).then_some((audio.format, audio.samples))
+    }
+
+    /// Mark the next turn as recorded without a server connection, so its audio is
+    /// captured in-app and kept if it can't be transcribed
+    pub fn set_offline(&self, offline: bool) {
+        self.offline_turn.store(offline, Ordering::SeqCst);
+    }
+
+    /// Whether the current turn is being recorded without a server connection
+    pub fn is_offline(&self) -> bool {
+        self.offline_turn.load(Ordering::SeqCst)
+    }
+
+    /// Format and samples of the last offline turn that nothing could transcribe
+    pub fn take_unsent_audio(&self) -> Option<(AudioFormat, Vec<f32>)> {
+        let audio = self.unsent_audio.lock().ok()?.take()?;
+        (!audio.samples.is_empty()).then_some((audio.format, audio.samples))
+    }
+
+    /// Capture each turn's audio and keep the last one for [`Self::last_audio`].
+    /// Turning this off drops the audio already kept.
+    pub fn set_keep_last_audio(&self, enabled: bool) {
+        self.keep_last_audio.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            if let Ok(mut last) = self.last_audio.lock() {
+                *last = None;
+            }
+        }
+    }
+
+    /// Format and samples of the last finalized turn, if it was kept
+    pub fn last_audio(&self) -> Option<(AudioFormat, Vec<f32>)> {
+        let last = self.last_audio.lock().ok()?;
+        let audio = last.as_ref()?;
+        (!audio.samples.is_empty()).then(|| (audio.format, audio.samples.clone()))
+    }
+
+    /// Replace the active provider
+    pub fn set_provider(&self, provider: Box<dyn SttProvider>) -> Result<(), String> {
+        let mut current = self.provider.lock().map_err(|e| e.to_string())?;
+        log::info!(
+            "Switching STT provider: {} -> {}",
+            current.name(),
+            provider.name()
+        );
+        *current = provider;
+        Ok(())
+    }
+
+    pub fn provider_name(&self) -> Result<&'static str, String> {
+        let provider = self.provider.lock().map_err(|e| e.to_string())?;
+        Ok(provider.name())
+    }
+
+    /// Whether the active provider needs the app to capture audio for it
+    pub fn transcribes_in_app(&self) -> bool {
+        self.provider
+            .lock()
+            .is_ok_and(|provider| provider.transcribes_in_app())
+    }
+
+    /// Whether the app needs to capture this turn's audio, either for an in-app
+    /// provider, because the turn is offline or to keep it for a retry
+    pub fn captures_in_app(&self) -> bool {
+        self.transcribes_in_app()
+            || self.offline_turn.load(Ordering::SeqCst)
+            || self.keep_last_audio.load(Ordering::SeqCst)
+    }
+
+    /// Begin a turn, starting with up to `pre_roll` of the audio sent before it
+    pub fn start_stream(&self, format: AudioFormat, pre_roll: Duration) -> Result<(), String> {
+        let preceding = self.take_pre_roll(format, pre_roll);
+        if let Ok(mut audio) = self.turn_audio.lock() {
+            audio.active = true;
+            audio.format = format;
+            audio.samples.clear();
+        }
+        self.provider
+            .lock()
+            .map_err(|e| e.to_string())?
+            .start_stream(format)?;
+        if preceding.is_empty() {
+            return Ok(());
+        }
+        self.send_audio(&preceding)
+    }
+
+    fn take_pre_roll(&self, format: AudioFormat, duration: Duration) -> Vec<f32> {
+        let Ok(mut buffer) = self.pre_roll.lock() else {
+            return Vec::new();
+        };
+        let preceding = buffer.take(duration);
+        // Pre-roll in another format can't be mixed into this turn
+        if buffer.format() == format {
+            preceding
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn send_audio(&self, samples: &[f32]) -> Result<(), String> {
+        {
+            let mut audio = self.turn_audio.lock().map_err(|e| e.to_string())?;
+            if !audio.active {
+                if let Ok(mut pre_roll) = self.pre_roll.lock() {
+                    pre_roll.push(samples);
+                }
+                return Ok(());
+            }
+            if self.has_fallback()
+                || self.archive_audio.load(Ordering::SeqCst)
+                || self.offline_turn.load(Ordering::SeqCst)
+                || self.keep_last_audio.load(Ordering::SeqCst)
+            {
+                audio.samples.extend_from_slice(samples);
+            }
+        }
+        let mut provider = self.provider.lock().map_err(|e| e.to_string())?;
+        provider.send_audio(samples)
+    }
+
+    /// End the turn. If the active provider fails or returns an empty transcript while
+    /// the turn's audio is buffered, the fallback provider gets one retry, as does an
+    /// offline turn. While archiving, the turn's audio is then kept for
+    /// [`Self::take_finished_audio`], and while keeping it, for [`Self::last_audio`].
+    pub fn finalize(&self) -> Result<Option<Transcript>, String> {
+        let offline = self.offline_turn.swap(false, Ordering::SeqCst);
+        let (name, job, audio) = {
+            let mut provider = self.provider.lock().map_err(|e| e.to_string())?;
+            let audio = self
+                .turn_audio
+                .lock()
+                .map(|mut audio| std::mem::take(&mut *audio))
+                .unwrap_or_default();
+            (provider.name(), provider.finish(), audio)
+        };
+        // Transcribe with the provider unlocked, so the next turn can start meanwhile
+        let result = job();
+        let transcript = self.retry_if_needed(name, result, &audio, offline);
+
+        if offline && !matches!(transcript, Ok(Some(_))) {
+            if let Ok(mut unsent) = self.unsent_audio.lock() {
+                *unsent = Some(audio.clone());
+            }
+        }
+        if self.keep_last_audio.load(Ordering::SeqCst) {
+            if let Ok(mut last) = self.last_audio.lock() {
+                *last = Some(audio.clone());
+            }
+        }
+        if self.archive_audio.load(Ordering::SeqCst) {
+            if let Ok(mut finished) = self.finished_audio.lock() {
+                *finished = Some(audio);
+            }
+        }
+        transcript
+    }
+
+    /// Abandon the turn without transcribing it, discarding its audio
+    pub fn cancel(&self) -> Result<(), String> {
+        self.offline_turn.store(false, Ordering::SeqCst);
+        if let Ok(mut audio) = self.turn_audio.lock() {
+            *audio = TurnAudio::default();
+        }
+        // Providers can't abort a stream, so end it without running the transcription
+        drop(self.provider.lock().map_err(|e| e.to_string())?.finish());
+        Ok(())
+    }
+
+    /// Pass through the active provider's result, or the fallback's if it needed a retry
+    fn retry_if_needed(
+        &self,
+        name: &'static str,
+        result: Result<Option<String>, String>,
+        audio: &TurnAudio,
+        offline: bool,
+    ) -> Result<Option<Transcript>, String> {
+        let needs_retry = match &result {
+            Ok(Some(text)) => text.trim().is_empty(),
+            // Delivered asynchronously, unless there was no server to deliver it
+            Ok(None) => offline,
+            Err(_) => true,
+        };
+        if needs_retry {
+            match self.retry_with_fallback(name, audio) {
+                Some(Ok(transcript)) => {
+                    log::info!(
+                        "STT provider {} {}, {} transcribed the turn instead",
+                        name,
+                        describe_failure(&result),
+                        transcript.provider
+                    );
+                    return Ok(Some(transcript));
+                }
+                Some(Err(e)) => log::error!(
+                    "STT fallback failed after {} {}: {}",
+                    name,
+                    describe_failure(&result),
+                    e
+                ),
+                None => {}
+            }
+        }
+
+        result.map(|text| {
+            text.map(|text| Transcript {
+                text,
+                provider: name,
+            })
+        })
+    }
+
+    /// Transcribe buffered audio with the fallback provider. Returns `None` when there is
+    /// no audio or no fallback able to retry it (other than the provider that failed).
+    fn retry_with_fallback(
+        &self,
+        failed: &str,
+        audio: &TurnAudio,
+    ) -> Option<Result<Transcript, String>> {
+        if audio.samples.is_empty() {
+            return None;
+        }
+        let (name, job) = {
+            let mut fallback = self.fallback.lock().ok()?;
+            let fallback = fallback
+                .as_mut()
+                .filter(|fallback| fallback.transcribes_in_app() && fallback.name() != failed)?;
+            (
+                fallback.name(),
+                queue_transcription(fallback.as_mut(), audio.format, &audio.samples),
+            )
+        };
+        // As in `finalize`, the fallback is unlocked while it transcribes
+        Some(job.and_then(|job| non_empty_transcript(name, job())))
+    }
+
+    pub fn partials(&self) -> Result<Vec<String>, String> {
+        let mut provider = self.provider.lock().map_err(|e| e.to_string())?;
+        Ok(provider.partials())
+    }
+}
+
+/// Transcribe recorded audio as one turn with an in-app provider, treating an empty
+/// transcript as a failure
+pub fn transcribe_audio(
+    provider: &mut dyn SttProvider,
+    format: AudioFormat,
+    samples: &[f32],
+) -> Result<Transcript, String> {
+    let job = queue_transcription(provider, format, samples)?;
+    non_empty_transcript(provider.name(), job())
+}
+
+/// Send audio to a provider as one utterance and return the work that transcribes it
+fn queue_transcription(
+    provider: &mut dyn SttProvider,
+    format: AudioFormat,
+    samples: &[f32],
+) -> Result<FinishJob, String> {
+    provider.start_stream(format)?;
+    provider.send_audio(samples)?;
+    Ok(provider.finish())
+}
+
+fn non_empty_transcript(
+    provider: &'static str,
+    text: Result<Option<String>, String>,
+) -> Result<Transcript, String> {
+    match text? {
+        Some(text) if !text.trim().is_empty() => Ok(Transcript { text, provider }),
+        _ => Err("empty transcript".to_string()),
+    }
+}
+
+/// Why a provider's result needs a retry, for logs
+fn describe_failure(failure: &Result<Option<String>, String>) -> String {
+    match failure {
+        Err(e) => format!("failed ({})", e),
+        Ok(Some(_)) => "returned an empty transcript".to_string(),
+        Ok(None) => "couldn't reach the server".to_string(),
+    }
+}
+
+/// Build the provider selected in settings, falling back to the server
+pub fn provider_from_settings(app: &AppHandle) -> Box<dyn SttProvider> {
+    let selected: Option<String> = get_setting_from_store(app, "stt_provider", None);
+    provider_named(app, selected.as_deref())
+}
+
+/// Build the provider for an `stt_provider` setting value, falling back to the server
+pub fn provider_named(app: &AppHandle, selected: Option<&str>) -> Box<dyn SttProvider> {
+    if selected == Some(LOCAL_WHISPER_PROVIDER) {
+        match local_whisper_from_settings(app) {
+            Some(provider) => return provider,
+            None => log::warn!("Local Whisper selected but no model is configured, using server"),
+        }
+    }
+    Box::new(ServerSttProvider::new(app.clone()))
+}
+
+/// Build the provider that retries failed turns from the `stt_fallback_provider` setting.
+/// Only in-app providers can retry buffered audio, so that is currently local Whisper.
+pub fn fallback_provider_from_settings(app: &AppHandle) -> Option<Box<dyn SttProvider>> {
+    let fallback: Option<String> = get_setting_from_store(app, "stt_fallback_provider", None);
+    match fallback.as_deref() {
+        None => None,
+        Some(LOCAL_WHISPER_PROVIDER) => {
+            let provider = local_whisper_from_settings(app);
+            if provider.is_none() {
+                log::warn!("Local Whisper fallback selected but no model is configured");
+            }
+            provider
+        }
+        Some(other) => {
+            log::warn!(
+                "STT provider {} can't retry buffered audio, ignoring it",
+                other
+            );
+            None
+        }
+    }
+}
+
+/// Local Whisper with the model file in `whisper_model_path` if set, otherwise the
+/// downloaded model of the size in `whisper_model`
+#[cfg(feature = "local-whisper")]
+fn local_whisper_from_settings(app: &AppHandle) -> Option<Box<dyn SttProvider>> {
+    let model_path: Option<PathBuf> = get_setting_from_store(app, "whisper_model_path", None);
+    let model_path = model_path.or_else(|| {
+        let model: WhisperModel =
+            get_setting_from_store(app, "whisper_model", DEFAULT_WHISPER_MODEL);
+        let path = app.try_state::<WhisperModelStore>()?.downloaded_path(model);
+        if path.is_none() {
+            log::warn!("Whisper model {} has not been downloaded", model.id());
+        }
+        path
+    })?;
+    Some(Box::new(LocalWhisperProvider::new(
+        model_path,
+        crate::commands::language::dictation_language(app),
+    )))
+}
+
+#[cfg(not(feature = "local-whisper"))]
+fn local_whisper_from_settings(_app: &AppHandle) -> Option<Box<dyn SttProvider>> {
+    log::warn!("This build was made without the local-whisper feature");
+    None
+}
@@ -0,0 +1,288 @@
+//! Whisper models that can be downloaded for local transcription.
+//!
+//! Models are whisper.cpp GGML files fetched from the whisper.cpp model repository
+//! into the `models` folder of the app data directory. A download is written to a
+//! `.part` file first, so an interrupted download never looks like a usable model.
+//! The SHA-256 checksum of each download is saved next to the model, so a file
+//! that is later corrupted on disk can be detected.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Where the GGML model files are downloaded from
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Time allowed to connect to the model host (the download itself is not limited)
+const DOWNLOAD_CONNECT_TIMEOUT_SECS: u64 = 30;
+
+/// Bytes downloaded between progress reports
+const PROGRESS_INTERVAL_BYTES: u64 = 1024 * 1024;
+
+/// First bytes of a GGML model file, checked for models saved without a checksum
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+
+/// Whisper model sizes, from fastest to most accurate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WhisperModel {
+    Tiny,
+    Base,
+    Small,
+    Medium,
+    LargeV3Turbo,
+}
+
+impl WhisperModel {
+    pub const ALL: [Self; 5] = [
+        Self::Tiny,
+        Self::Base,
+        Self::Small,
+        Self::Medium,
+        Self::LargeV3Turbo,
+    ];
+
+    /// Name used in the model's file name
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Tiny => "tiny",
+            Self::Base => "base",
+            Self::Small => "small",
+            Self::Medium => "medium",
+            Self::LargeV3Turbo => "large-v3-turbo",
+        }
+    }
+
+    pub fn file_name(self) -> String {
+        format!("ggml-{}.bin", self.id())
+    }
+
+    pub fn download_url(self) -> String {
+        format!("{}/{}", MODEL_BASE_URL, self.file_name())
+    }
+
+    /// Approximate download size, shown before a model is downloaded
+    pub fn approx_size_mb(self) -> u64 {
+        match self {
+            Self::Tiny => 75,
+            Self::Base => 142,
+            Self::Small => 466,
+            Self::Medium => 1500,
+            Self::LargeV3Turbo => 1600,
+        }
+    }
+}
+
+/// A model and whether it has been downloaded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WhisperModelStatus {
+    pub model: WhisperModel,
+    pub approx_size_mb: u64,
+    pub downloaded: bool,
+    pub downloading: bool,
+}
+
+/// The folder downloaded models are kept in
+pub struct WhisperModelStore {
+    dir: PathBuf,
+    downloading: Mutex<HashSet<WhisperModel>>,
+}
+
+impl WhisperModelStore {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            downloading: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn path(&self, model: WhisperModel) -> PathBuf {
+        self.dir.join(model.file_name())
+    }
+
+    /// Where the checksum recorded when the model was downloaded is kept
+    pub fn checksum_path(&self, model: WhisperModel) -> PathBuf {
+        self.path(model).with_extension("bin.sha256")
+    }
+
+    /// Path of a model if it has been downloaded
+    pub fn downloaded_path(&self, model: WhisperModel) -> Option<PathBuf> {
+        let path = self.path(model);
+        path.is_file().then_some(path)
+    }
+
+    fn is_downloading(&self, model: WhisperModel) -> bool {
+        self.downloading
+            .lock()
+            .is_ok_and(|downloading| downloading.contains(&model))
+    }
+
+    pub fn list(&self) -> Vec<WhisperModelStatus> {
+        WhisperModel::ALL
+            .into_iter()
+            .map(|model| WhisperModelStatus {
+                model,
+                approx_size_mb: model.approx_size_mb(),
+                downloaded: self.downloaded_path(model).is_some(),
+                downloading: self.is_downloading(model),
+            })
+            .collect()
+    }
+
+    /// Delete a downloaded model. Returns false if it wasn't downloaded.
+    pub fn delete(&self, model: WhisperModel) -> Result<bool, String> {
+        if self.is_downloading(model) {
+            return Err(format!("Whisper model {} is downloading", model.id()));
+        }
+        let _ = fs::remove_file(self.checksum_path(model));
+        match fs::remove_file(self.path(model)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(format!("Failed to delete Whisper model: {}", e)),
+        }
+    }
+
+    /// Download a model, blocking until it is saved. `on_progress` is called with the
+    /// bytes downloaded so far and the total size, when the server reports it.
+    pub fn download(
+        &self,
+        model: WhisperModel,
+        on_progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<PathBuf, String> {
+        {
+            let mut downloading = self.downloading.lock().map_err(|e| e.to_string())?;
+            if !downloading.insert(model) {
+                return Err(format!(
+                    "Whisper model {} is already downloading",
+                    model.id()
+                ));
+            }
+        }
+        let result = self.fetch(model, on_progress);
+        if let Ok(mut downloading) = self.downloading.lock() {
+            downloading.remove(&model);
+        }
+        result
+    }
+
+    /// Check a downloaded model against its recorded checksum, or for the GGML
+    /// header if it was downloaded before checksums were recorded
+    pub fn verify(&self, model: WhisperModel) -> Result<(), String> {
+        let path = self.path(model);
+        match fs::read_to_string(self.checksum_path(model)) {
+            Ok(expected) => {
+                if sha256_file(&path)? != expected.trim() {
+                    return Err("Checksum does not match the download".to_string());
+                }
+            }
+            Err(_) => {
+                let mut magic = [0u8; 4];
+                fs::File::open(&path)
+                    .and_then(|mut file| file.read_exact(&mut magic))
+                    .map_err(|e| format!("Failed to read model file: {}", e))?;
+                if magic != GGML_MAGIC {
+                    return Err("Not a GGML model file".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn fetch(
+        &self,
+        model: WhisperModel,
+        on_progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create models folder: {}", e))?;
+
+        let client = reqwest::blocking::Client::builder()
+            .connect_timeout(Duration::from_secs(DOWNLOAD_CONNECT_TIMEOUT_SECS))
+            .timeout(None)
+            .build()
+            .map_err(|e| e.to_string())?;
+        log::info!("Downloading Whisper model {}", model.id());
+        let mut response = client
+            .get(model.download_url())
+            .send()
+            .map_err(|e| format!("Whisper model download failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("Whisper model download failed: {}", e))?;
+        let total = response.content_length();
+
+        let part_path = self.path(model).with_extension("bin.part");
+        let result =
+            write_download(&mut response, &part_path, total, on_progress).and_then(|checksum| {
+                fs::rename(&part_path, self.path(model))
+                    .map_err(|e| format!("Failed to save Whisper model: {}", e))?;
+                fs::write(self.checksum_path(model), checksum)
+                    .map_err(|e| format!("Failed to save Whisper model checksum: {}", e))
+            });
+        if result.is_err() {
+            let _ = fs::remove_file(&part_path);
+        }
+        result.map(|()| self.path(model))
+    }
+}
+
+/// Hex-encoded SHA-256 checksum of a file
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to read model file: {}", e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read model file: {}", e))?;
+    Ok(hex_digest(hasher))
+}
+
+fn hex_digest(hasher: Sha256) -> String {
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Copy a download to `path`, reporting progress every [`PROGRESS_INTERVAL_BYTES`].
+/// Returns the hex-encoded SHA-256 checksum of the download.
+fn write_download(
+    source: &mut dyn Read,
+    path: &Path,
+    total: Option<u64>,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<String, String> {
+    let mut file =
+        fs::File::create(path).map_err(|e| format!("Failed to create model file: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut downloaded = 0u64;
+    let mut reported = 0u64;
+    on_progress(0, total);
+    loop {
+        let read = source
+            .read(&mut buffer)
+            .map_err(|e| format!("Whisper model download failed: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write model file: {}", e))?;
+        hasher.update(&buffer[..read]);
+        downloaded += read as u64;
+        if downloaded - reported >= PROGRESS_INTERVAL_BYTES {
+            reported = downloaded;
+            on_progress(downloaded, total);
+        }
+    }
+    if total.is_some_and(|total| total != downloaded) {
+        return Err("Whisper model download was incomplete".to_string());
+    }
+    on_progress(downloaded, total);
+    file.flush()
+        .map_err(|e| format!("Failed to write model file: {}", e))?;
+    Ok(hex_digest(hasher))
+}
@@ -0,0 +1,50 @@
+use super::{AudioFormat, SttProvider};
+use tauri::{AppHandle, Emitter};
+
+/// Transcription through the Tambourine server.
+///
+/// The overlay window owns the WebRTC connection and captures the microphone itself,
+/// so this provider only signals turn boundaries to it. The transcript comes back
+/// from the server through the overlay rather than from `finalize`.
+pub struct ServerSttProvider {
+    app: AppHandle,
+}
+
+impl ServerSttProvider {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl SttProvider for ServerSttProvider {
+    fn name(&self) -> &'static str {
+        "server"
+    }
+
+    fn start_stream(&mut self, _format: AudioFormat) -> Result<(), String> {
+        self.app
+            .emit("recording-start", ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn send_audio(&mut self, _samples: &[f32]) -> Result<(), String> {
+        // Audio is streamed by the overlay's WebRTC connection
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<Option<String>, String> {
+        self.app
+            .emit("recording-stop", ())
+            .map_err(|e| e.to_string())?;
+        Ok(None)
+    }
+
+    fn partials(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn transcribes_in_app(&self) -> bool {
+        // The server hears the overlay's microphone, not audio passed in here
+        false
+    }
+}
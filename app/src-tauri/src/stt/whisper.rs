@@ -0,0 +1,164 @@
+use super::{AudioFormat, FinishJob, SttProvider};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// Local transcription with a whisper.cpp model, no server required.
+///
+/// Audio is buffered for the whole utterance and transcribed on `finalize`.
+/// The model is loaded on first use and kept for later turns.
+pub struct LocalWhisperProvider {
+    model_path: PathBuf,
+    language: Option<String>,
+    /// Shared with transcriptions still running, which load it if needed
+    context: Arc<Mutex<Option<Arc<WhisperContext>>>>,
+    format: AudioFormat,
+    buffer: Vec<f32>,
+}
+
+impl LocalWhisperProvider {
+    pub fn new(model_path: PathBuf, language: Option<String>) -> Self {
+        Self {
+            model_path,
+            language,
+            context: Arc::new(Mutex::new(None)),
+            format: AudioFormat::WHISPER,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+/// The model at `model_path`, loaded into `cache` the first time
+fn load_context(
+    model_path: &Path,
+    cache: &Mutex<Option<Arc<WhisperContext>>>,
+) -> Result<Arc<WhisperContext>, String> {
+    let mut cache = cache.lock().map_err(|e| e.to_string())?;
+    if let Some(context) = cache.as_ref() {
+        return Ok(context.clone());
+    }
+    log::info!("Loading Whisper model from {}", model_path.display());
+    let context = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+        .map_err(|e| format!("Failed to load Whisper model: {}", e))?;
+    let context = Arc::new(context);
+    *cache = Some(context.clone());
+    Ok(context)
+}
+
+fn transcribe(
+    context: &WhisperContext,
+    language: Option<&str>,
+    audio: &[f32],
+) -> Result<String, String> {
+    let mut state = context
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(language);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+
+    state
+        .full(params, audio)
+        .map_err(|e| format!("Whisper transcription failed: {}", e))?;
+
+    let segments: Vec<String> = state
+        .as_iter()
+        .filter_map(|segment| segment.to_str_lossy().ok().map(|text| text.into_owned()))
+        .collect();
+    Ok(join_segments(&segments))
+}
+
+impl SttProvider for LocalWhisperProvider {
+    fn name(&self) -> &'static str {
+        "local-whisper"
+    }
+
+    fn start_stream(&mut self, format: AudioFormat) -> Result<(), String> {
+        if format.sample_rate == 0 || format.channels == 0 {
+            return Err(format!("Unsupported audio format: {:?}", format));
+        }
+        self.format = format;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn send_audio(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.buffer.extend_from_slice(samples);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<Option<String>, String> {
+        self.finish()()
+    }
+
+    fn finish(&mut self) -> FinishJob {
+        let audio = to_whisper_input(&std::mem::take(&mut self.buffer), self.format);
+        let model_path = self.model_path.clone();
+        let language = self.language.clone();
+        let context = self.context.clone();
+        Box::new(move || {
+            if audio.is_empty() {
+                return Ok(Some(String::new()));
+            }
+            let context = load_context(&model_path, &context)?;
+            transcribe(&context, language.as_deref(), &audio).map(Some)
+        })
+    }
+
+    fn partials(&mut self) -> Vec<String> {
+        // Whisper runs once per utterance, so there are no interim results
+        Vec::new()
+    }
+}
+
+/// Convert interleaved samples in any format to 16 kHz mono, as Whisper expects
+pub fn to_whisper_input(samples: &[f32], format: AudioFormat) -> Vec<f32> {
+    let mono = downmix_to_mono(samples, format.channels);
+    resample_linear(&mono, format.sample_rate, AudioFormat::WHISPER.sample_rate)
+}
+
+/// Average interleaved channels into a single channel
+pub fn downmix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
+    let channels = usize::from(channels.max(1));
+    if channels == 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resample mono audio with linear interpolation
+pub fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = f64::from(from_rate) / f64::from(to_rate);
+    let output_len = (samples.len() as f64 / ratio).round() as usize;
+    (0..output_len)
+        .map(|index| {
+            let position = index as f64 * ratio;
+            let base = position.floor() as usize;
+            let next = (base + 1).min(samples.len() - 1);
+            let fraction = (position - base as f64) as f32;
+            let current = samples[base.min(samples.len() - 1)];
+            current + (samples[next] - current) * fraction
+        })
+        .collect()
+}
+
+/// Join Whisper segments into a single transcript with normalized spacing
+pub fn join_segments(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|segment| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,144 @@
+//! Support bundles for bug reports.
+//!
+//! A bundle is a zip of the recent log files, the settings with anything that
+//! could identify the user or unlock a service redacted, and a description of the
+//! environment. Provider API keys live in the OS keyring and are never read here.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Replacement for redacted setting values
+pub const REDACTED: &str = "[redacted]";
+
+/// Words in setting names whose values are always redacted
+const SENSITIVE_WORDS: &[&str] = &["token", "secret", "password", "auth", "cert", "credentials"];
+
+/// Settings whose values are the user's own words or places rather than configuration
+const PERSONAL_KEYS: &[&str] = &["snippets", "notes_file_path", "blocked_apps"];
+
+/// Where and what the app is running on
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EnvironmentInfo {
+    pub app_version: String,
+    pub os: String,
+    pub os_family: String,
+    pub arch: String,
+    /// Display server of a Linux session
+    pub display_server: Option<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Whether a setting's value must not leave the machine
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    // A hotkey's "key" names a key on the keyboard, so only API keys are matched
+    key.ends_with("api_key")
+        || key.ends_with("apikey")
+        || key.split('_').any(|word| SENSITIVE_WORDS.contains(&word))
+}
+
+/// `text` with the user name and password of a URL such as a proxy or server
+/// address redacted, or `None` if it is not a URL with credentials
+fn redact_url_userinfo(text: &str) -> Option<String> {
+    let (scheme, rest) = text.split_once("://")?;
+    if scheme.is_empty()
+        || !scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    {
+        return None;
+    }
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let userinfo_end = rest[..authority_end].rfind('@')?;
+    Some(format!(
+        "{}://{}{}",
+        scheme,
+        REDACTED,
+        &rest[userinfo_end..]
+    ))
+}
+
+/// Copy of `value` with sensitive and personal settings and credentials in URLs
+/// redacted and `home` replaced by `~` in every string, so paths don't name the user
+pub fn redact_value(value: &Value, home: Option<&str>) -> Value {
+    match value {
+        Value::Object(map) => {
+            let redacted: Map<String, Value> = map
+                .iter()
+                .map(|(key, value)| {
+                    let value = if value.is_null() {
+                        Value::Null
+                    } else if is_sensitive_key(key) || PERSONAL_KEYS.contains(&key.as_str()) {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_value(value, home)
+                    };
+                    (key.clone(), value)
+                })
+                .collect();
+            Value::Object(redacted)
+        }
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|item| redact_value(item, home)).collect())
+        }
+        Value::String(text) => {
+            let text = redact_url_userinfo(text).unwrap_or_else(|| text.clone());
+            match home {
+                Some(home) if !home.is_empty() => Value::String(text.replace(home, "~")),
+                _ => Value::String(text),
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Default file name of a bundle made at `now`
+pub fn default_file_name(now: DateTime<Utc>) -> String {
+    format!("tambourine-support-{}.zip", now.format("%Y%m%d-%H%M%S"))
+}
+
+/// Write a bundle to `path` holding `log_files` under `logs/`, the redacted
+/// `settings` and `environment`
+pub fn write_bundle(
+    path: &Path,
+    log_files: &[PathBuf],
+    settings: Option<&Value>,
+    environment: &EnvironmentInfo,
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create support bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut add = |name: &str, content: &[u8]| -> Result<(), String> {
+        zip.start_file(name, options)
+            .and_then(|()| zip.write_all(content).map_err(Into::into))
+            .map_err(|e| format!("Failed to write {} to support bundle: {}", name, e))
+    };
+
+    for log_file in log_files {
+        let Some(name) = log_file.file_name() else {
+            continue;
+        };
+        match fs::read(log_file) {
+            Ok(content) => add(&format!("logs/{}", name.to_string_lossy()), &content)?,
+            Err(e) => log::warn!("Skipping log {}: {}", log_file.display(), e),
+        }
+    }
+    if let Some(settings) = settings {
+        let content = serde_json::to_vec_pretty(settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        add("settings.json", &content)?;
+    }
+    let content = serde_json::to_vec_pretty(environment)
+        .map_err(|e| format!("Failed to serialize environment: {}", e))?;
+    add("environment.json", &content)?;
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish support bundle: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Opt-in local analytics.
+//!
+//! With `telemetry_enabled` on, a few anonymous counters are kept in
+//! `telemetry.json` in the app data directory: app sessions, completed dictations,
+//! failures by category and how long turns took to arrive once recording stopped.
+//! No text, app names or identifiers are recorded and nothing leaves the machine;
+//! the counters are only there for the user to look at, and can be purged at any time.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Telemetry file in the app data directory
+pub const TELEMETRY_FILE: &str = "telemetry.json";
+
+/// Upper bounds of the latency buckets in milliseconds. Slower turns go in one
+/// more bucket without a bound.
+pub const LATENCY_BUCKET_BOUNDS_MS: [u64; 4] = [500, 1000, 2000, 5000];
+
+/// What went wrong with a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    /// The recording couldn't be transcribed
+    Transcription,
+    /// The server didn't answer in time
+    Server,
+    /// The text couldn't be inserted into the focused app
+    Insertion,
+    /// The text couldn't be appended to the notes file
+    Notes,
+    /// Insertion was refused while macOS secure input was on
+    SecureInput,
+    /// The connection to the server dropped
+    Connection,
+}
+
+/// Counters since telemetry was enabled or last purged
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetryCounters {
+    /// When the first counter was recorded
+    pub since: Option<DateTime<Utc>>,
+    /// App launches
+    pub sessions: u64,
+    /// Turns whose text was delivered
+    pub dictations: u64,
+    pub failures: BTreeMap<FailureCategory, u64>,
+    /// Turns per latency bucket, in the order of [`LATENCY_BUCKET_BOUNDS_MS`]
+    #[serde(default)]
+    pub latency_buckets: Vec<u64>,
+}
+
+/// Number of turns that failed in one way
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FailureCount {
+    pub category: FailureCategory,
+    pub count: u64,
+}
+
+/// Number of turns that took up to `max_ms` (`None` for the slowest bucket)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LatencyBucket {
+    pub max_ms: Option<u64>,
+    pub count: u64,
+}
+
+/// Counters as shown to the user
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TelemetryReport {
+    pub enabled: bool,
+    pub since: Option<DateTime<Utc>>,
+    pub sessions: u64,
+    pub dictations: u64,
+    /// Most frequent first
+    pub failures: Vec<FailureCount>,
+    /// Share of turns that failed, `None` before any turn
+    pub failure_rate: Option<f64>,
+    pub latency: Vec<LatencyBucket>,
+}
+
+/// Index of the bucket a turn that took `latency` falls in
+pub fn latency_bucket(latency: Duration) -> usize {
+    let ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+    LATENCY_BUCKET_BOUNDS_MS
+        .iter()
+        .position(|bound| ms <= *bound)
+        .unwrap_or(LATENCY_BUCKET_BOUNDS_MS.len())
+}
+
+impl TelemetryCounters {
+    pub fn record_session(&mut self) {
+        self.sessions += 1;
+    }
+
+    pub fn record_dictation(&mut self, latency: Option<Duration>) {
+        self.dictations += 1;
+        if let Some(latency) = latency {
+            self.latency_buckets
+                .resize(LATENCY_BUCKET_BOUNDS_MS.len() + 1, 0);
+            self.latency_buckets[latency_bucket(latency)] += 1;
+        }
+    }
+
+    pub fn record_failure(&mut self, category: FailureCategory) {
+        *self.failures.entry(category).or_default() += 1;
+    }
+
+    pub fn report(&self, enabled: bool) -> TelemetryReport {
+        let mut failures: Vec<FailureCount> = self
+            .failures
+            .iter()
+            .map(|(category, count)| FailureCount {
+                category: *category,
+                count: *count,
+            })
+            .collect();
+        failures.sort_by_key(|failure| std::cmp::Reverse(failure.count));
+
+        // Dropped connections aren't turns, so they don't count towards the rate
+        let failed_turns: u64 = failures
+            .iter()
+            .filter(|failure| failure.category != FailureCategory::Connection)
+            .map(|failure| failure.count)
+            .sum();
+        let turns = self.dictations + failed_turns;
+        let failure_rate = (turns > 0).then(|| failed_turns as f64 / turns as f64);
+
+        let latency = (0..=LATENCY_BUCKET_BOUNDS_MS.len())
+            .map(|index| LatencyBucket {
+                max_ms: LATENCY_BUCKET_BOUNDS_MS.get(index).copied(),
+                count: self.latency_buckets.get(index).copied().unwrap_or_default(),
+            })
+            .collect();
+
+        TelemetryReport {
+            enabled,
+            since: self.since,
+            sessions: self.sessions,
+            dictations: self.dictations,
+            failures,
+            failure_rate,
+            latency,
+        }
+    }
+}
+
+/// Telemetry counters, saved to the app data directory after every change
+pub struct TelemetryStore {
+    path: Option<PathBuf>,
+    counters: Mutex<TelemetryCounters>,
+}
+
+impl TelemetryStore {
+    /// Load the counters from the app data directory, starting over if the file
+    /// is missing or unreadable
+    pub fn new(app_data_dir: &Path) -> Self {
+        let path = app_data_dir.join(TELEMETRY_FILE);
+        let counters = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable telemetry file: {}", e);
+                TelemetryCounters::default()
+            }),
+            Err(_) => TelemetryCounters::default(),
+        };
+        Self {
+            path: Some(path),
+            counters: Mutex::new(counters),
+        }
+    }
+
+    /// Counters kept in memory only, discarded when dropped
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            counters: Mutex::new(TelemetryCounters::default()),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, TelemetryCounters>, String> {
+        self.counters
+            .lock()
+            .map_err(|e| format!("Failed to access telemetry: {}", e))
+    }
+
+    /// Change the counters and save them
+    pub fn update(
+        &self,
+        now: DateTime<Utc>,
+        change: impl FnOnce(&mut TelemetryCounters),
+    ) -> Result<(), String> {
+        let mut counters = self.lock()?;
+        counters.since.get_or_insert(now);
+        change(&mut counters);
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let content = serde_json::to_vec_pretty(&*counters)
+            .map_err(|e| format!("Failed to serialize telemetry: {}", e))?;
+        crate::integrity::write_atomic(path, &content)
+            .map_err(|e| format!("Failed to save telemetry: {}", e))
+    }
+
+    pub fn snapshot(&self) -> Result<TelemetryCounters, String> {
+        Ok(self.lock()?.clone())
+    }
+
+    /// Reset every counter and delete the telemetry file
+    pub fn purge(&self) -> Result<(), String> {
+        let mut counters = self.lock()?;
+        *counters = TelemetryCounters::default();
+        if let Some(path) = &self.path {
+            match fs::remove_file(path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to delete telemetry: {}", e)),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::accessibility::{splice_value, text_was_ignored};
+
+#[test]
+fn test_unchanged_value_means_text_was_ignored() {
+    assert!(text_was_ignored(Some("Hello"), Some("Hello"), " world"));
+}
+
+#[test]
+fn test_changed_value_means_text_was_inserted() {
+    assert!(!text_was_ignored(
+        Some("Hello"),
+        Some("Hello world"),
+        " world"
+    ));
+}
+
+#[test]
+fn test_unreadable_value_is_assumed_inserted() {
+    assert!(!text_was_ignored(None, None, "secret"));
+    assert!(!text_was_ignored(Some(""), None, "text"));
+}
+
+#[test]
+fn test_empty_text_is_never_ignored() {
+    assert!(!text_was_ignored(Some("same"), Some("same"), ""));
+}
+
+#[test]
+fn test_splice_inserts_at_caret() {
+    assert_eq!(
+        splice_value("Hello world", 5, 5, ","),
+        Some(("Hello, world".to_string(), 6))
+    );
+}
+
+#[test]
+fn test_splice_replaces_selection() {
+    assert_eq!(
+        splice_value("Hello world", 6, 11, "there"),
+        Some(("Hello there".to_string(), 11))
+    );
+}
+
+#[test]
+fn test_splice_counts_utf16_offsets() {
+    // The emoji is two UTF-16 units but one character
+    assert_eq!(
+        splice_value("a😀b", 3, 3, "c"),
+        Some(("a😀cb".to_string(), 3))
+    );
+    assert_eq!(splice_value("a😀b", 2, 2, "c"), None);
+}
+
+#[test]
+fn test_splice_rejects_offsets_past_the_end() {
+    assert_eq!(
+        splice_value("abc", 3, 3, "d"),
+        Some(("abcd".to_string(), 4))
+    );
+    assert_eq!(splice_value("abc", 4, 4, "d"), None);
+    assert_eq!(splice_value("abc", 2, 1, "d"), None);
+}
@@ -0,0 +1,91 @@
+use crate::api::{
+    bearer_token, parse_head, response_bytes, route, string_field, ApiError, Endpoint,
+};
+use crate::remote_control::RemoteCommand;
+use serde_json::json;
+
+#[test]
+fn test_parse_head_waits_for_the_whole_head() {
+    assert_eq!(parse_head(b"POST /insert HTTP/1.1\r\nHost: loc"), Ok(None));
+
+    let request = b"POST /insert HTTP/1.1\r\nAuthorization: Bearer abc\r\ncontent-length: 15\r\n\r\n{\"text\": \"hi\"}";
+    let head = parse_head(request).unwrap().unwrap();
+    assert_eq!(head.method, "POST");
+    assert_eq!(head.path, "/insert");
+    assert_eq!(head.authorization.as_deref(), Some("Bearer abc"));
+    assert_eq!(head.content_length, 15);
+    assert_eq!(&request[head.head_len..], b"{\"text\": \"hi\"}");
+}
+
+#[test]
+fn test_parse_head_rejects_malformed_requests() {
+    assert_eq!(parse_head(b"NOT HTTP\r\n\r\n").unwrap_err().status, 400);
+    let error = parse_head(b"POST /insert HTTP/1.1\r\nContent-Length: lots\r\n\r\n").unwrap_err();
+    assert_eq!(error.status, 400);
+}
+
+#[test]
+fn test_bearer_token() {
+    assert_eq!(bearer_token(Some("Bearer abc")), Some("abc"));
+    assert_eq!(bearer_token(Some("bearer  abc ")), Some("abc"));
+    assert_eq!(bearer_token(Some("Basic abc")), None);
+    assert_eq!(bearer_token(Some("abc")), None);
+    assert_eq!(bearer_token(None), None);
+}
+
+#[test]
+fn test_route_matches_methods_and_paths() {
+    assert_eq!(
+        route("GET", "/status"),
+        Ok(Endpoint::Command(RemoteCommand::Status))
+    );
+    assert_eq!(
+        route("POST", "/start/"),
+        Ok(Endpoint::Command(RemoteCommand::Start))
+    );
+    assert_eq!(
+        route("POST", "/stop?source=vim"),
+        Ok(Endpoint::Command(RemoteCommand::Stop))
+    );
+    assert_eq!(
+        route("GET", "/last-transcript"),
+        Ok(Endpoint::LastTranscript)
+    );
+    assert_eq!(route("POST", "/insert"), Ok(Endpoint::Insert));
+    assert_eq!(route("GET", "/providers"), Ok(Endpoint::Providers));
+    assert_eq!(route("POST", "/provider"), Ok(Endpoint::SelectProvider));
+    assert_eq!(route("GET", "/diagnostics"), Ok(Endpoint::Diagnostics));
+
+    assert_eq!(route("GET", "/start").unwrap_err().status, 405);
+    assert_eq!(route("GET", "/settings").unwrap_err().status, 404);
+}
+
+#[test]
+fn test_string_field() {
+    assert_eq!(
+        string_field(br#"{"text": "Hello there"}"#, "text"),
+        Ok("Hello there".to_string())
+    );
+    assert_eq!(
+        string_field(br#"{"provider": 3}"#, "provider"),
+        Err(ApiError::new(400, r#"Expected {"provider": "..."}"#))
+    );
+    assert_eq!(
+        string_field(br#"{"text": ""}"#, "text"),
+        Err(ApiError::new(400, "text cannot be empty"))
+    );
+    assert_eq!(
+        string_field(b"Hello there", "text").unwrap_err().status,
+        400
+    );
+}
+
+#[test]
+fn test_response_bytes() {
+    let response =
+        String::from_utf8(response_bytes(401, &json!({"error": "Invalid token"}))).unwrap();
+    assert_eq!(
+        response,
+        "HTTP/1.1 401 Unauthorized\r\nContent-Type: application/json\r\nContent-Length: 25\r\nConnection: close\r\n\r\n{\"error\":\"Invalid token\"}"
+    );
+}
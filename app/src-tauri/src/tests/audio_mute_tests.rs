@@ -0,0 +1,155 @@
+use crate::audio_mute::{
+    ducked_volume, fade_steps, AudioControlError, AudioMuteManager, SystemAudioControl,
+    MAX_DUCK_PERCENT,
+};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Longer than the slowest fade
+const FADE_WAIT: Duration = Duration::from_millis(800);
+
+struct FakeAudio {
+    muted: Mutex<bool>,
+    volume: Mutex<f32>,
+}
+
+impl FakeAudio {
+    fn new(volume: f32) -> Arc<Self> {
+        Arc::new(Self {
+            muted: Mutex::new(false),
+            volume: Mutex::new(volume),
+        })
+    }
+
+    fn level(&self) -> f32 {
+        *self.volume.lock().unwrap()
+    }
+}
+
+impl SystemAudioControl for FakeAudio {
+    fn is_muted(&self) -> Result<bool, AudioControlError> {
+        Ok(*self.muted.lock().unwrap())
+    }
+
+    fn set_muted(&self, muted: bool) -> Result<(), AudioControlError> {
+        *self.muted.lock().unwrap() = muted;
+        Ok(())
+    }
+
+    fn volume(&self) -> Result<f32, AudioControlError> {
+        Ok(self.level())
+    }
+
+    fn set_volume(&self, volume: f32) -> Result<(), AudioControlError> {
+        *self.volume.lock().unwrap() = volume;
+        Ok(())
+    }
+}
+
+fn assert_near(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 0.001,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_ducked_volume_is_a_share_of_the_original() {
+    assert_near(ducked_volume(0.8, 25), 0.2);
+    assert_near(ducked_volume(0.8, 0), 0.0);
+    assert_near(ducked_volume(1.0, 100), f32::from(MAX_DUCK_PERCENT) / 100.0);
+}
+
+#[test]
+fn test_fade_steps_end_at_the_target() {
+    let steps = fade_steps(1.0, 0.0, 4);
+    assert_eq!(steps.len(), 4);
+    assert_near(steps[0], 0.75);
+    assert_near(steps[3], 0.0);
+    assert_eq!(fade_steps(0.2, 0.6, 0).len(), 1);
+}
+
+#[test]
+fn test_zero_percent_mutes_and_unmutes() {
+    let audio = FakeAudio::new(0.7);
+    let manager = AudioMuteManager::with_controller(audio.clone());
+
+    manager.mute(0).unwrap();
+    assert!(audio.is_muted().unwrap());
+    assert_near(audio.level(), 0.7);
+
+    manager.unmute().unwrap();
+    assert!(!audio.is_muted().unwrap());
+}
+
+#[test]
+fn test_audio_muted_before_recording_stays_muted() {
+    let audio = FakeAudio::new(0.7);
+    audio.set_muted(true).unwrap();
+    let manager = AudioMuteManager::with_controller(audio.clone());
+
+    manager.mute(30).unwrap();
+    manager.unmute().unwrap();
+
+    assert!(audio.is_muted().unwrap());
+    assert_near(audio.level(), 0.7);
+}
+
+#[test]
+fn test_ducking_fades_down_and_restores() {
+    let audio = FakeAudio::new(0.8);
+    let manager = AudioMuteManager::with_controller(audio.clone());
+
+    manager.mute(25).unwrap();
+    thread::sleep(FADE_WAIT);
+    assert_near(audio.level(), 0.2);
+    assert!(!audio.is_muted().unwrap());
+
+    manager.unmute().unwrap();
+    thread::sleep(FADE_WAIT);
+    assert_near(audio.level(), 0.8);
+}
+
+#[test]
+fn test_volume_changed_while_ducked_is_left_alone() {
+    let audio = FakeAudio::new(0.8);
+    let manager = AudioMuteManager::with_controller(audio.clone());
+
+    manager.mute(25).unwrap();
+    thread::sleep(FADE_WAIT);
+    audio.set_volume(0.5).unwrap();
+
+    manager.unmute().unwrap();
+    thread::sleep(FADE_WAIT);
+    assert_near(audio.level(), 0.5);
+}
+
+#[test]
+fn test_dropping_the_manager_restores_immediately() {
+    let audio = FakeAudio::new(0.6);
+    let manager = AudioMuteManager::with_controller(audio.clone());
+
+    manager.mute(50).unwrap();
+    thread::sleep(FADE_WAIT);
+    drop(manager);
+
+    assert_near(audio.level(), 0.6);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_parse_pactl_output() {
+    use crate::audio_mute::linux::{parse_mute, parse_volume};
+
+    assert_eq!(parse_mute("Mute: yes\n"), Some(true));
+    assert_eq!(parse_mute("Mute: no\n"), Some(false));
+    assert_eq!(parse_mute("Volume: 50%"), None);
+
+    let output = "Volume: front-left: 32768 /  50% / -18.06 dB,   \
+                  front-right: 19661 /  30% / -31.37 dB\n        balance -0.40\n";
+    assert_near(parse_volume(output).unwrap(), 0.4);
+    assert_eq!(parse_volume("Volume: unknown"), None);
+}
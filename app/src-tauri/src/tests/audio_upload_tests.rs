@@ -0,0 +1,53 @@
+use crate::audio_upload::{
+    clamp_opus_bitrate, encode_opus, negotiate_encoding, UploadEncoding, MAX_OPUS_BITRATE_KBPS,
+    MIN_OPUS_BITRATE_KBPS,
+};
+
+fn encodings(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_opus_is_used_when_the_server_decodes_it() {
+    let both = encodings(&["pcm16", "opus"]);
+    assert_eq!(negotiate_encoding(&both, 16_000), UploadEncoding::Opus);
+    assert_eq!(negotiate_encoding(&both, 48_000), UploadEncoding::Opus);
+}
+
+#[test]
+fn test_pcm_is_used_for_older_servers_and_other_rates() {
+    assert_eq!(negotiate_encoding(&[], 16_000), UploadEncoding::Pcm16);
+    assert_eq!(
+        negotiate_encoding(&encodings(&["pcm16"]), 16_000),
+        UploadEncoding::Pcm16
+    );
+    // Opus doesn't take 44.1 kHz audio
+    assert_eq!(
+        negotiate_encoding(&encodings(&["pcm16", "opus"]), 44_100),
+        UploadEncoding::Pcm16
+    );
+}
+
+#[test]
+fn test_opus_bitrate_is_clamped() {
+    assert_eq!(clamp_opus_bitrate(0), MIN_OPUS_BITRATE_KBPS);
+    assert_eq!(clamp_opus_bitrate(24), 24);
+    assert_eq!(clamp_opus_bitrate(1000), MAX_OPUS_BITRATE_KBPS);
+}
+
+#[test]
+fn test_audio_is_encoded_to_20ms_packets() {
+    // 1.01 s of a 440 Hz tone at 16 kHz, so the last packet is padded
+    let samples: Vec<i16> = (0..16_160)
+        .map(|i| ((i as f32 * 440.0 * std::f32::consts::TAU / 16_000.0).sin() * 8000.0) as i16)
+        .collect();
+    let packets = encode_opus(16_000, &samples, 24).expect("encodes");
+    assert_eq!(packets.len(), 51);
+    assert!(packets.iter().all(|packet| !packet.is_empty()));
+
+    // Far smaller than the 32 000 bytes of PCM
+    let total: usize = packets.iter().map(Vec::len).sum();
+    assert!(total < 8000, "{} bytes", total);
+
+    assert!(encode_opus(44_100, &samples, 24).is_err());
+}
@@ -0,0 +1,130 @@
+use crate::auth::{self, AuthStatus, AuthToken, REFRESH_BEFORE_EXPIRY};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+fn now() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+}
+
+fn jwt(claims: &str) -> String {
+    format!(
+        "{}.{}.signature",
+        URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#),
+        URL_SAFE_NO_PAD.encode(claims)
+    )
+}
+
+#[test]
+fn test_expiry_read_from_jwt() {
+    let token =
+        AuthToken::new(&jwt(r#"{"sub":"me","exp":1700003600}"#), None, None, now()).unwrap();
+    assert_eq!(token.expires_at, DateTime::from_timestamp(1_700_003_600, 0));
+    assert_eq!(auth::jwt_expiry("opaque-token"), None);
+    assert_eq!(auth::jwt_expiry(&jwt(r#"{"sub":"me"}"#)), None);
+}
+
+#[test]
+fn test_explicit_lifetime_overrides_jwt() {
+    let token = AuthToken::new(
+        &jwt(r#"{"exp":1700003600}"#),
+        Some(" refresh "),
+        Some(Duration::from_secs(60)),
+        now(),
+    )
+    .unwrap();
+    assert_eq!(token.expires_at, Some(now() + TimeDelta::seconds(60)));
+    assert_eq!(token.refresh_token.as_deref(), Some("refresh"));
+}
+
+#[test]
+fn test_empty_token_is_rejected() {
+    assert!(AuthToken::new("  ", None, None, now()).is_err());
+    let token = AuthToken::new("abc", Some(""), None, now()).unwrap();
+    assert_eq!(token.refresh_token, None);
+}
+
+#[test]
+fn test_refresh_scheduled_before_expiry() {
+    let hour = Some(Duration::from_secs(3600));
+    let token = AuthToken::new("abc", Some("refresh"), hour, now()).unwrap();
+    assert_eq!(
+        token.refresh_in(now()),
+        Some(Duration::from_secs(3600) - REFRESH_BEFORE_EXPIRY)
+    );
+    // Already inside the refresh window
+    assert_eq!(
+        token.refresh_in(now() + TimeDelta::seconds(3500)),
+        Some(Duration::ZERO)
+    );
+
+    let without_refresh = AuthToken::new("abc", None, hour, now()).unwrap();
+    assert_eq!(without_refresh.refresh_in(now()), None);
+    let without_expiry = AuthToken::new("abc", Some("refresh"), None, now()).unwrap();
+    assert_eq!(without_expiry.refresh_in(now()), None);
+}
+
+#[test]
+fn test_status_never_includes_token() {
+    let token = AuthToken::new("secret", None, Some(Duration::from_secs(60)), now()).unwrap();
+    let status = AuthStatus::new(Some(&token), None, now() + TimeDelta::seconds(61));
+    assert!(status.is_set);
+    assert!(status.is_expired);
+    assert!(!status.can_refresh);
+    assert!(!serde_json::to_string(&status).unwrap().contains("secret"));
+
+    assert_eq!(AuthStatus::new(None, None, now()), AuthStatus::default());
+}
+
+#[test]
+fn test_refresh_keeps_refresh_token_unless_rotated() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header == "\r\n" {
+                break;
+            }
+            if let Some(value) = header.to_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let response = r#"{"access_token": "new-access", "expires_in": 600}"#;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+        (request_line, String::from_utf8(body).unwrap())
+    });
+
+    let token = AuthToken::new("old-access", Some("refresh"), None, now()).unwrap();
+    let refreshed = auth::refresh(&url, &token, None).unwrap();
+    assert_eq!(refreshed.access_token, "new-access");
+    assert_eq!(refreshed.refresh_token.as_deref(), Some("refresh"));
+    assert!(refreshed.expires_at.is_some());
+
+    let (request_line, body) = server.join().unwrap();
+    assert!(request_line.starts_with("POST /api/auth/refresh "));
+    assert_eq!(body, r#"{"refresh_token":"refresh"}"#);
+}
+
+#[test]
+fn test_refresh_requires_refresh_token() {
+    let token = AuthToken::new("abc", None, None, now()).unwrap();
+    assert!(auth::refresh("http://127.0.0.1:1", &token, None).is_err());
+}
@@ -0,0 +1,48 @@
+use crate::benchmark::{synthetic_audio, BenchmarkSource, StageTimings};
+use crate::stt::AudioFormat;
+use std::time::Duration;
+
+#[test]
+fn test_synthetic_audio_matches_format_and_duration() {
+    let samples = synthetic_audio(AudioFormat::WHISPER, Duration::from_secs(2));
+    assert_eq!(samples.len(), 32_000);
+    assert!(samples.iter().all(|sample| sample.abs() <= 1.0));
+
+    let stereo = AudioFormat {
+        sample_rate: 48_000,
+        channels: 2,
+    };
+    let samples = synthetic_audio(stereo, Duration::from_millis(500));
+    assert_eq!(stereo.duration_ms(samples.len()), 500);
+    assert_eq!(samples[100], samples[101]);
+}
+
+#[test]
+fn test_synthetic_audio_has_pauses_between_syllables() {
+    let samples = synthetic_audio(AudioFormat::WHISPER, Duration::from_secs(1));
+    // The envelope is silent for the second half of every 250 ms syllable
+    let quiet = &samples[2_100..3_900];
+    assert!(quiet.iter().all(|sample| *sample == 0.0));
+    let loud = samples[..2_000].iter().map(|sample| sample.abs());
+    assert!(loud.fold(0.0, f32::max) > 0.1);
+}
+
+#[test]
+fn test_total_skips_formatting_already_in_transcription() {
+    let timings = StageTimings {
+        capture_ms: 5,
+        network_ms: Some(40),
+        stt_ms: 900,
+        formatting_ms: 300,
+        insertion_ms: 10,
+    };
+    assert_eq!(timings.total_ms(false), 1_215);
+    assert_eq!(timings.total_ms(true), 915);
+}
+
+#[test]
+fn test_source_deserializes_from_snake_case() {
+    let source: BenchmarkSource = serde_json::from_str("\"recorded\"").unwrap();
+    assert_eq!(source, BenchmarkSource::Recorded);
+    assert_eq!(BenchmarkSource::default(), BenchmarkSource::Synthetic);
+}
@@ -0,0 +1,251 @@
+use crate::audio_processing::PROCESSING_FRAME_LEN;
+use crate::capture::{
+    apply_gain, clamp_mic_gain, describe_format, downmix, pcm16_bytes, peak_level, pick_device,
+    pick_format, rms_level, take_chunks, FormatRange, FrameConverter, FrameProcessor,
+    LocalCaptureFlush, Resampler, StreamFormat, CAPTURE_FORMAT, FRAME_DURATION,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Replaces the audio with a constant, counting the frames it gets
+struct ConstantProcessor {
+    frames: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl FrameProcessor for ConstantProcessor {
+    fn process(&mut self, frame: &mut [f32]) {
+        assert_eq!(frame.len(), PROCESSING_FRAME_LEN);
+        frame.fill(0.5);
+        self.frames
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+fn ramp(len: usize) -> Vec<f32> {
+    (0..len).map(|i| i as f32 / len as f32).collect()
+}
+
+#[test]
+fn test_downmix_averages_channels() {
+    assert_eq!(downmix(&[0.2, 0.4, -1.0, 1.0], 2), vec![0.3, 0.0]);
+    assert_eq!(downmix(&[0.5, 0.25], 1), vec![0.5, 0.25]);
+}
+
+#[test]
+fn test_gain_clips_and_is_clamped() {
+    let mut samples = vec![0.1, -0.4, 0.6];
+    apply_gain(&mut samples, 2.0);
+    assert_eq!(samples, vec![0.2, -0.8, 1.0]);
+
+    assert_eq!(clamp_mic_gain(10.0), 4.0);
+    assert_eq!(clamp_mic_gain(0.1), 0.5);
+    assert_eq!(clamp_mic_gain(f32::NAN), 1.0);
+}
+
+#[test]
+fn test_rms_level() {
+    assert_eq!(rms_level(&[]), 0.0);
+    assert_eq!(rms_level(&[0.5, -0.5]), 0.5);
+    assert_eq!(rms_level(&[0.0; 4]), 0.0);
+}
+
+#[test]
+fn test_peak_level() {
+    assert_eq!(peak_level(&[]), 0.0);
+    assert_eq!(peak_level(&[0.1, -0.6, 0.3]), 0.6);
+    assert_eq!(peak_level(&[1.5]), 1.0);
+}
+
+#[test]
+fn test_resampler_keeps_every_third_sample_from_48khz() {
+    let input = ramp(48);
+    let output = Resampler::new(48_000, 16_000).process(&input);
+    let expected: Vec<f32> = input.iter().step_by(3).copied().collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_resampler_is_unaffected_by_chunking() {
+    let input = ramp(441);
+    let whole = Resampler::new(44_100, 16_000).process(&input);
+
+    let mut resampler = Resampler::new(44_100, 16_000);
+    let mut chunked = Vec::new();
+    for chunk in input.chunks(37) {
+        chunked.extend(resampler.process(chunk));
+    }
+    assert_eq!(chunked.len(), whole.len());
+    for (a, b) in chunked.iter().zip(&whole) {
+        assert!((a - b).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_frames_are_cut_to_the_frame_duration() {
+    let frame_len =
+        (CAPTURE_FORMAT.sample_rate as u128 * FRAME_DURATION.as_millis() / 1000) as usize;
+    // 100 ms of 48 kHz stereo
+    let mut converter = FrameConverter::new(48_000, 2, None);
+    let frames = converter.push(&vec![0.25; 9600]);
+    assert_eq!(frames.len(), 2);
+    assert!(frames.iter().all(|frame| frame.len() == frame_len));
+    assert!(frames[0].iter().all(|sample| *sample == 0.25));
+
+    // The leftover 20 ms completes a frame with the next 20 ms
+    assert_eq!(converter.push(&vec![0.25; 1920]).len(), 1);
+}
+
+#[test]
+fn test_processing_runs_on_10ms_frames_at_48khz() {
+    let frames = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let processor = ConstantProcessor {
+        frames: frames.clone(),
+    };
+    // Just over 100 ms of 44.1 kHz mono
+    let mut converter = FrameConverter::new(44_100, 1, Some(Box::new(processor)));
+    let output = converter.push(&vec![0.1; 4500]);
+
+    assert_eq!(frames.load(std::sync::atomic::Ordering::SeqCst), 10);
+    assert_eq!(output.len(), 2);
+    assert!(output.iter().flatten().all(|sample| *sample == 0.5));
+}
+
+#[test]
+fn test_take_chunks_leaves_the_remainder() {
+    let mut pending = ramp(10);
+    let chunks = take_chunks(&mut pending, 4);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1], ramp(10)[4..8].to_vec());
+    assert_eq!(pending, ramp(10)[8..].to_vec());
+}
+
+/// A USB interface that only captures 48 kHz stereo, and a built-in microphone
+fn usb_ranges() -> Vec<FormatRange> {
+    vec![FormatRange {
+        channels: 2,
+        min_sample_rate: 48_000,
+        max_sample_rate: 48_000,
+    }]
+}
+
+fn builtin_ranges() -> Vec<FormatRange> {
+    vec![
+        FormatRange {
+            channels: 1,
+            min_sample_rate: 8_000,
+            max_sample_rate: 48_000,
+        },
+        FormatRange {
+            channels: 2,
+            min_sample_rate: 44_100,
+            max_sample_rate: 96_000,
+        },
+    ]
+}
+
+fn format(sample_rate: Option<u32>, channels: Option<u16>) -> StreamFormat {
+    StreamFormat {
+        sample_rate,
+        channels,
+    }
+}
+
+#[test]
+fn test_pick_format_finds_a_supported_range() {
+    let builtin = builtin_ranges();
+    assert_eq!(
+        pick_format(&builtin, format(Some(16_000), Some(1)), 48_000),
+        Some((0, 16_000))
+    );
+    assert_eq!(
+        pick_format(&builtin, format(Some(96_000), None), 48_000),
+        Some((1, 96_000))
+    );
+    // Only the channels are asked for, so the default rate is kept
+    assert_eq!(
+        pick_format(&builtin, format(None, Some(2)), 48_000),
+        Some((1, 48_000))
+    );
+}
+
+#[test]
+fn test_pick_format_keeps_a_rate_the_range_supports() {
+    // The default rate isn't supported in stereo, so the nearest one is used
+    assert_eq!(
+        pick_format(&builtin_ranges(), format(None, Some(2)), 16_000),
+        Some((1, 44_100))
+    );
+}
+
+#[test]
+fn test_pick_format_rejects_unsupported_formats() {
+    let usb = usb_ranges();
+    assert_eq!(pick_format(&usb, format(Some(16_000), None), 48_000), None);
+    assert_eq!(pick_format(&usb, format(None, Some(1)), 48_000), None);
+    assert_eq!(
+        pick_format(&usb, format(Some(48_000), Some(2)), 48_000),
+        Some((0, 48_000))
+    );
+}
+
+#[test]
+fn test_describe_format() {
+    assert_eq!(
+        describe_format(format(Some(48_000), Some(2))),
+        "48000 Hz stereo"
+    );
+    assert_eq!(
+        describe_format(format(Some(16_000), Some(1))),
+        "16000 Hz mono"
+    );
+    assert_eq!(
+        describe_format(format(None, Some(4))),
+        "the default rate with 4 channels"
+    );
+}
+
+#[test]
+fn test_frames_from_a_48khz_stereo_interface() {
+    // 40 ms of 48 kHz stereo makes one frame of 16 kHz mono
+    let mut converter = FrameConverter::new(48_000, 2, None);
+    let frames = converter.push(&[0.5, -0.5].repeat(1920));
+    assert_eq!(frames.len(), 1);
+    assert!(frames[0].iter().all(|sample| *sample == 0.0));
+}
+
+#[test]
+fn test_pick_device_matches_webview_labels() {
+    let names = vec![
+        "MacBook Pro Microphone".to_string(),
+        "USB Audio Device".to_string(),
+    ];
+    assert_eq!(pick_device(&names, Some("USB Audio Device")), Some(1));
+    assert_eq!(pick_device(&names, Some("usb audio device")), Some(1));
+    assert_eq!(
+        pick_device(&names, Some("Default - MacBook Pro Microphone (Built-in)")),
+        Some(0)
+    );
+    assert_eq!(pick_device(&names, Some("Headset")), None);
+    assert_eq!(pick_device(&names, None), None);
+    assert_eq!(pick_device(&names, Some(" ")), None);
+}
+
+#[test]
+fn test_local_capture_flush_waits_for_confirmation() {
+    let flush = Arc::new(LocalCaptureFlush::default());
+    let first = flush.request();
+    let second = flush.request();
+    assert!(!flush.wait(first, Duration::from_millis(10)));
+
+    let confirming = flush.clone();
+    let handle = std::thread::spawn(move || confirming.confirm(second));
+    assert!(flush.wait(second, Duration::from_secs(5)));
+    handle.join().unwrap();
+    // Confirming a later stop covers the earlier ones
+    assert!(flush.wait(first, Duration::ZERO));
+}
+
+#[test]
+fn test_pcm16_bytes_are_little_endian() {
+    assert_eq!(pcm16_bytes(&[0.0, 1.0, -1.0]), [0, 0, 255, 127, 1, 128]);
+}
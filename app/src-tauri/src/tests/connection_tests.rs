@@ -0,0 +1,208 @@
+use crate::connection::{self, ActiveEndpoint, Backoff, ConnectionManager, FAILOVER_DELAY};
+use std::time::Duration;
+
+const LOCAL: &str = "http://127.0.0.1:8765";
+const CLOUD: &str = "https://voice.example.com";
+
+fn manager() -> ConnectionManager {
+    let manager =
+        ConnectionManager::new(Backoff::new(Duration::from_secs(1), Duration::from_secs(8)));
+    manager.set_endpoints(vec![LOCAL.to_string()]);
+    manager
+}
+
+fn failover_manager() -> ConnectionManager {
+    let manager = manager();
+    manager.set_endpoints(vec![LOCAL.to_string(), CLOUD.to_string()]);
+    manager
+}
+
+#[test]
+fn test_backoff_doubles_up_to_max_and_resets() {
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(8));
+    let delays: Vec<_> = (0..6).map(|_| backoff.next_delay().as_secs()).collect();
+    assert_eq!(delays, vec![1, 2, 4, 8, 8, 8]);
+    assert_eq!(backoff.attempt(), 6);
+
+    backoff.reset();
+    assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+}
+
+#[test]
+fn test_backoff_saturates_after_many_attempts() {
+    let mut backoff = Backoff::default();
+    for _ in 0..100 {
+        assert!(backoff.next_delay() <= crate::connection::MAX_RECONNECT_DELAY);
+    }
+}
+
+#[test]
+fn test_drop_schedules_one_reconnect_at_a_time() {
+    let manager = manager();
+    assert!(manager.report("idle").connected_changed);
+
+    let outcome = manager.report("disconnected");
+    assert!(outcome.connected_changed);
+    let scheduled = outcome.reconnect.unwrap();
+    assert_eq!(scheduled.delay, Duration::from_secs(1));
+
+    // Repeated reports while a reconnect is waiting don't schedule another
+    assert_eq!(manager.report("disconnected").reconnect, None);
+    assert_eq!(manager.status().retry_in_ms, Some(1000));
+
+    assert_eq!(
+        manager.take_due_reconnect(scheduled.generation).as_deref(),
+        Some(LOCAL)
+    );
+    assert_eq!(manager.take_due_reconnect(scheduled.generation), None);
+    assert_eq!(manager.status().retry_in_ms, None);
+}
+
+#[test]
+fn test_failed_attempts_back_off_until_connected() {
+    let manager = manager();
+    manager.report("connecting");
+    let mut delays = Vec::new();
+    for _ in 0..3 {
+        let scheduled = manager.report("disconnected").reconnect.unwrap();
+        delays.push(scheduled.delay.as_secs());
+        assert!(manager.take_due_reconnect(scheduled.generation).is_some());
+        manager.report("connecting");
+    }
+    assert_eq!(delays, vec![1, 2, 4]);
+    assert_eq!(manager.status().attempt, 3);
+
+    manager.report("idle");
+    assert_eq!(manager.status().attempt, 0);
+    let scheduled = manager.report("disconnected").reconnect.unwrap();
+    assert_eq!(scheduled.delay, Duration::from_secs(1));
+}
+
+#[test]
+fn test_initial_disconnected_report_schedules_nothing() {
+    let manager = manager();
+    assert_eq!(manager.report("disconnected").reconnect, None);
+}
+
+#[test]
+fn test_reconnect_skipped_once_connection_is_back() {
+    let manager = manager();
+    manager.report("idle");
+    let scheduled = manager.report("disconnected").reconnect.unwrap();
+    manager.report("connecting");
+    assert_eq!(manager.take_due_reconnect(scheduled.generation), None);
+}
+
+#[test]
+fn test_manual_reconnect_and_shutdown_cancel_scheduled_reconnect() {
+    let manager = manager();
+    manager.report("idle");
+    let scheduled = manager.report("disconnected").reconnect.unwrap();
+    assert_eq!(manager.reconnect_now().as_deref(), Some(LOCAL));
+    assert_eq!(manager.take_due_reconnect(scheduled.generation), None);
+    assert_eq!(manager.status().attempt, 0);
+
+    manager.report("connecting");
+    manager.shut_down();
+    assert_eq!(manager.report("disconnected").reconnect, None);
+}
+
+#[test]
+fn test_state_changes_while_connected_keep_connection() {
+    let manager = manager();
+    manager.report("idle");
+    assert!(!manager.report("recording").connected_changed);
+    assert!(!manager.report("processing").connected_changed);
+    assert_eq!(manager.status().state, "processing");
+}
+
+#[test]
+fn test_server_endpoints_skip_blank_and_repeated_urls() {
+    let fallbacks = vec![
+        " ".to_string(),
+        format!("{}/", LOCAL),
+        CLOUD.to_string(),
+        CLOUD.to_string(),
+    ];
+    assert_eq!(
+        connection::server_endpoints(LOCAL, &fallbacks),
+        vec![LOCAL.to_string(), CLOUD.to_string()]
+    );
+    assert!(connection::server_endpoints("", &[]).is_empty());
+}
+
+#[test]
+fn test_failed_attempt_fails_over_to_next_server() {
+    let manager = failover_manager();
+    manager.report("connecting");
+
+    // The local server is down, so the cloud server is tried next without backing off
+    let scheduled = manager.report("disconnected").reconnect.unwrap();
+    assert_eq!(scheduled.delay, FAILOVER_DELAY);
+    assert_eq!(
+        manager.take_due_reconnect(scheduled.generation).as_deref(),
+        Some(CLOUD)
+    );
+    manager.report("connecting");
+    assert_eq!(
+        manager.report("idle").active_endpoint,
+        Some(ActiveEndpoint {
+            url: CLOUD.to_string(),
+            priority: 1,
+        })
+    );
+    assert_eq!(manager.status().endpoint.as_deref(), Some(CLOUD));
+}
+
+#[test]
+fn test_full_round_of_failures_backs_off_before_starting_over() {
+    let manager = failover_manager();
+    manager.report("connecting");
+    let mut attempts = Vec::new();
+    for _ in 0..4 {
+        let scheduled = manager.report("disconnected").reconnect.unwrap();
+        let url = manager.take_due_reconnect(scheduled.generation).unwrap();
+        attempts.push((scheduled.delay, url));
+        manager.report("connecting");
+    }
+    assert_eq!(
+        attempts,
+        vec![
+            (FAILOVER_DELAY, CLOUD.to_string()),
+            (Duration::from_secs(1), LOCAL.to_string()),
+            (FAILOVER_DELAY, CLOUD.to_string()),
+            (Duration::from_secs(2), LOCAL.to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_drop_from_fallback_retries_preferred_server_first() {
+    let manager = failover_manager();
+    manager.report("connecting");
+    let scheduled = manager.report("disconnected").reconnect.unwrap();
+    manager.take_due_reconnect(scheduled.generation);
+    manager.report("idle");
+
+    let scheduled = manager.report("disconnected").reconnect.unwrap();
+    assert_eq!(
+        manager.take_due_reconnect(scheduled.generation).as_deref(),
+        Some(LOCAL)
+    );
+    manager.report("connecting");
+    let switched = manager.report("idle").active_endpoint.unwrap();
+    assert_eq!(switched.priority, 0);
+
+    // Reconnecting to the same server isn't a switch
+    manager.report("disconnected");
+    manager.report("connecting");
+    assert_eq!(manager.report("idle").active_endpoint, None);
+}
+
+#[test]
+fn test_changing_endpoints_starts_from_the_first() {
+    let manager = failover_manager();
+    assert!(!manager.set_endpoints(vec![LOCAL.to_string(), CLOUD.to_string()]));
+    assert!(manager.set_endpoints(vec![CLOUD.to_string()]));
+    assert_eq!(manager.reconnect_now().as_deref(), Some(CLOUD));
+}
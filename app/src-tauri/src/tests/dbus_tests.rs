@@ -0,0 +1,21 @@
+use crate::dbus::status_tuple;
+use crate::remote_control::RemoteStatus;
+
+#[test]
+fn test_status_tuple_order() {
+    let status = RemoteStatus {
+        recording: true,
+        paused: false,
+        connected: true,
+    };
+    // Waybar modules and extensions read the fields by position
+    assert_eq!(status_tuple(status), (true, false, true));
+    assert_eq!(
+        status_tuple(RemoteStatus {
+            recording: false,
+            paused: true,
+            connected: false,
+        }),
+        (false, true, false)
+    );
+}
@@ -0,0 +1,84 @@
+use crate::active_window::ActiveApp;
+use crate::deep_link::{is_link_launch, parse_deep_link, DeepLinkAction};
+
+#[test]
+fn test_parse_deep_link_actions() {
+    assert_eq!(
+        parse_deep_link("tambourine://start"),
+        Ok(DeepLinkAction::Start { profile: None })
+    );
+    assert_eq!(
+        parse_deep_link("tambourine://stop"),
+        Ok(DeepLinkAction::Stop)
+    );
+    assert_eq!(
+        parse_deep_link("tambourine://toggle/"),
+        Ok(DeepLinkAction::Toggle)
+    );
+    // Launchers don't always add the slashes
+    assert_eq!(parse_deep_link("tambourine:stop"), Ok(DeepLinkAction::Stop));
+    assert_eq!(
+        parse_deep_link("TAMBOURINE://Toggle"),
+        Ok(DeepLinkAction::Toggle)
+    );
+    assert_eq!(
+        parse_deep_link("tambourine://paste-last"),
+        Ok(DeepLinkAction::PasteLast)
+    );
+}
+
+#[test]
+fn test_parse_deep_link_profile() {
+    assert_eq!(
+        parse_deep_link("tambourine://start?profile=email"),
+        Ok(DeepLinkAction::Start {
+            profile: Some("email".to_string())
+        })
+    );
+    assert_eq!(
+        parse_deep_link("tambourine://start?profile=Formal%20Email&x=1"),
+        Ok(DeepLinkAction::Start {
+            profile: Some("Formal Email".to_string())
+        })
+    );
+    assert_eq!(
+        parse_deep_link("tambourine://start?profile="),
+        Ok(DeepLinkAction::Start { profile: None })
+    );
+}
+
+#[test]
+fn test_parse_deep_link_rejects_other_links() {
+    assert!(parse_deep_link("tambourine://record").is_err());
+    assert!(parse_deep_link("tambourine://").is_err());
+    assert!(parse_deep_link("https://start").is_err());
+    assert!(parse_deep_link("not a link").is_err());
+}
+
+#[test]
+fn test_is_link_launch() {
+    let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    assert!(is_link_launch(&args(&[
+        "tambourine",
+        "tambourine://toggle"
+    ])));
+    assert!(!is_link_launch(&args(&["tambourine", "--headless"])));
+    // The program path is never the link
+    assert!(!is_link_launch(&args(&["tambourine:start"])));
+}
+
+#[test]
+fn test_links_do_not_paste_into_browsers() {
+    let app = |app_name: &str, process_path: &str, bundle_id: Option<&str>| ActiveApp {
+        app_name: app_name.to_string(),
+        title: String::new(),
+        process_path: process_path.to_string(),
+        bundle_id: bundle_id.map(str::to_string),
+    };
+    assert!(app("Google Chrome", "", Some("com.google.Chrome")).is_web_browser());
+    assert!(app("Firefox", "/usr/lib/firefox/firefox", None).is_web_browser());
+    assert!(app("Arc", "", Some("company.thebrowser.Browser")).is_web_browser());
+    assert!(app("msedge", "C:\\Edge\\msedge.exe", None).is_web_browser());
+    assert!(!app("Slack", "", Some("com.tinyspeck.slackmacgap")).is_web_browser());
+    assert!(!app("Archive Utility", "", None).is_web_browser());
+}
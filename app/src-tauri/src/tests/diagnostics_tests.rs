@@ -0,0 +1,101 @@
+use crate::diagnostics::{
+    check_hotkeys, check_insertion, check_microphone_access, check_microphone_device, check_server,
+    CheckStatus,
+};
+use crate::mic_monitor::MicStatus;
+use crate::server::ConnectionTest;
+use crate::settings::ShortcutRegistrationFailure;
+
+#[test]
+fn test_microphone_access_follows_reported_permission() {
+    let mut mic = MicStatus::default();
+    assert_eq!(check_microphone_access(&mic).status, CheckStatus::Unknown);
+
+    mic.permission = Some(false);
+    let check = check_microphone_access(&mic);
+    assert_eq!(check.status, CheckStatus::Fail);
+    assert!(check.fix.is_some());
+
+    mic.permission = Some(true);
+    let check = check_microphone_access(&mic);
+    assert_eq!(check.status, CheckStatus::Pass);
+    assert_eq!(check.fix, None);
+}
+
+#[test]
+fn test_microphone_device_warns_on_fallback() {
+    let mic = MicStatus {
+        permission: Some(true),
+        device_count: Some(2),
+        selected_label: Some("USB Mic".to_string()),
+        is_fallback: true,
+    };
+    let check = check_microphone_device(&mic);
+    assert_eq!(check.status, CheckStatus::Warn);
+    assert!(check.detail.contains("USB Mic"));
+
+    let check = check_microphone_device(&MicStatus {
+        is_fallback: false,
+        ..mic.clone()
+    });
+    assert_eq!(check.status, CheckStatus::Pass);
+
+    let check = check_microphone_device(&MicStatus {
+        device_count: Some(0),
+        ..mic
+    });
+    assert_eq!(check.status, CheckStatus::Fail);
+}
+
+#[test]
+fn test_server_check_reports_connection_error() {
+    let reachable = ConnectionTest {
+        reachable: true,
+        latency_ms: Some(42),
+        ..Default::default()
+    };
+    let check = check_server("http://127.0.0.1:8765", &reachable);
+    assert_eq!(check.status, CheckStatus::Pass);
+    assert!(check.detail.contains("42 ms"));
+
+    let unreachable = ConnectionTest {
+        error: Some("Connection refused".to_string()),
+        ..Default::default()
+    };
+    let check = check_server("http://127.0.0.1:8765", &unreachable);
+    assert_eq!(check.status, CheckStatus::Fail);
+    assert_eq!(check.detail, "Connection refused");
+    assert!(check.fix.is_some());
+}
+
+#[test]
+fn test_hotkeys_check_lists_failed_shortcuts() {
+    assert_eq!(check_hotkeys(&[]).status, CheckStatus::Pass);
+
+    let failures = vec![ShortcutRegistrationFailure {
+        action: "paste_last".to_string(),
+        shortcut: "ctrl+alt+Period".to_string(),
+        error: "already registered".to_string(),
+    }];
+    let check = check_hotkeys(&failures);
+    assert_eq!(check.status, CheckStatus::Fail);
+    assert_eq!(check.detail, "Not registered: paste last (ctrl+alt+Period)");
+}
+
+#[test]
+fn test_insertion_check_prefers_missing_permission_over_warnings() {
+    let warnings = vec!["Secure input is on".to_string()];
+    assert_eq!(
+        check_insertion(Some(false), &warnings).status,
+        CheckStatus::Fail
+    );
+    assert_eq!(check_insertion(None, &warnings).status, CheckStatus::Warn);
+    assert_eq!(check_insertion(Some(true), &[]).status, CheckStatus::Pass);
+}
+
+#[test]
+fn test_check_serializes_status_in_snake_case() {
+    let value = serde_json::to_value(check_hotkeys(&[])).unwrap();
+    assert_eq!(value["status"], "pass");
+    assert_eq!(value["id"], "hotkeys");
+}
@@ -0,0 +1,117 @@
+use crate::dictionary::DictionaryStore;
+use crate::dictionary_import::{
+    parse_import, plan_import, DictionaryImportFormat, ImportedEntry, SkippedLine,
+};
+
+fn imported(line: usize, spoken: &str, written: &str, case_sensitive: bool) -> ImportedEntry {
+    ImportedEntry {
+        line,
+        spoken: spoken.to_string(),
+        written: written.to_string(),
+        case_sensitive,
+    }
+}
+
+#[test]
+fn test_csv_columns_and_header() {
+    let csv = "\u{feff}Spoken,Written,Match case\r\nant row pick,Anthropic,\r\nTauri,,\r\nwill,Will,yes\r\n";
+    let (entries, skipped) = parse_import(csv, DictionaryImportFormat::Csv);
+    assert_eq!(
+        entries,
+        vec![
+            imported(2, "ant row pick", "Anthropic", false),
+            imported(3, "Tauri", "Tauri", false),
+            imported(4, "will", "Will", true),
+        ]
+    );
+    assert!(skipped.is_empty());
+}
+
+#[test]
+fn test_csv_quoted_cells() {
+    let csv = "\"acme, inc\",\"Acme, Inc.\"\n\"say \"\"hi\"\"\",\"multi\nline\"\nnext,Next\n";
+    let (entries, _) = parse_import(csv, DictionaryImportFormat::Csv);
+    assert_eq!(
+        entries,
+        vec![
+            imported(1, "acme, inc", "Acme, Inc.", false),
+            imported(2, "say \"hi\"", "multi\nline", false),
+            imported(4, "next", "Next", false),
+        ]
+    );
+}
+
+#[test]
+fn test_csv_semicolon_delimiter_and_blank_spoken_form() {
+    let csv = "pipe cat;Pipecat\n;Orphan\n\n";
+    let (entries, skipped) = parse_import(csv, DictionaryImportFormat::Csv);
+    assert_eq!(entries, vec![imported(1, "pipe cat", "Pipecat", false)]);
+    assert_eq!(
+        skipped,
+        vec![SkippedLine {
+            line: 2,
+            text: ",Orphan".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_text_lines() {
+    let text = "# Product names\nAcme Widget Pro Max Ultra\nant row pick = Anthropic\n\n= nothing heard\nwill = Will (match case)\n";
+    let (entries, skipped) = parse_import(text, DictionaryImportFormat::Text);
+    assert_eq!(
+        entries,
+        vec![
+            imported(
+                2,
+                "Acme Widget Pro Max Ultra",
+                "Acme Widget Pro Max Ultra",
+                false
+            ),
+            imported(3, "ant row pick", "Anthropic", false),
+            imported(6, "will", "Will", true),
+        ]
+    );
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].line, 5);
+}
+
+#[test]
+fn test_plan_sorts_duplicates_and_conflicts() {
+    let store = DictionaryStore::in_memory().unwrap();
+    store.add("pipe cat", "Pipecat", false).unwrap();
+    store.add("tory", "Tauri", false).unwrap();
+
+    let text = "Pipe Cat = Pipecat\ntory = Tory\nLLM\nllm\nnew = New\n";
+    let (entries, skipped) = parse_import(text, DictionaryImportFormat::Text);
+    let import = plan_import(&store.list().unwrap(), entries, skipped, true);
+
+    assert!(import.dry_run);
+    assert_eq!(
+        import.added,
+        vec![
+            imported(3, "LLM", "LLM", false),
+            imported(5, "new", "New", false)
+        ]
+    );
+    assert_eq!(
+        import.duplicates,
+        vec![
+            imported(1, "Pipe Cat", "Pipecat", false),
+            imported(4, "llm", "llm", false),
+        ]
+    );
+    assert_eq!(import.conflicts.len(), 1);
+    assert_eq!(import.conflicts[0].existing.written, "Tauri");
+
+    // Planning added nothing; adding the planned entries does
+    assert_eq!(store.list().unwrap().len(), 2);
+    store.add_all(&import.new_entries()).unwrap();
+    let written: Vec<String> = store
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.written)
+        .collect();
+    assert_eq!(written, vec!["Pipecat", "Tauri", "LLM", "New"]);
+}
@@ -0,0 +1,60 @@
+use crate::dictionary_suggestions::{DictionarySuggestion, DictionarySuggestions};
+use crate::text_diff::WordCorrection;
+
+fn correction(heard: &str, corrected: &str) -> WordCorrection {
+    WordCorrection {
+        heard: heard.to_string(),
+        corrected: corrected.to_string(),
+    }
+}
+
+fn suggestion(heard: &str, corrected: &str, count: u32) -> DictionarySuggestion {
+    DictionarySuggestion {
+        heard: heard.to_string(),
+        corrected: corrected.to_string(),
+        count,
+    }
+}
+
+#[test]
+fn test_record_returns_only_new_suggestions() {
+    let suggestions = DictionarySuggestions::default();
+    assert_eq!(
+        suggestions.record(&[correction("tambourin", "Tambourine")]),
+        vec![suggestion("tambourin", "Tambourine", 1)]
+    );
+    assert!(suggestions
+        .record(&[correction("tambourin", "Tambourine")])
+        .is_empty());
+    assert_eq!(
+        suggestions.list(),
+        vec![suggestion("tambourin", "Tambourine", 2)]
+    );
+}
+
+#[test]
+fn test_list_puts_most_corrected_first() {
+    let suggestions = DictionarySuggestions::default();
+    suggestions.record(&[correction("pipe cat", "Pipecat")]);
+    suggestions.record(&[correction("tory", "Tauri")]);
+    suggestions.record(&[correction("tory", "Tauri")]);
+
+    let heard: Vec<_> = suggestions.list().into_iter().map(|s| s.heard).collect();
+    assert_eq!(heard, vec!["tory", "pipe cat"]);
+}
+
+#[test]
+fn test_resolved_suggestions_are_not_offered_again() {
+    let suggestions = DictionarySuggestions::default();
+    suggestions.record(&[correction("tory", "Tauri")]);
+    assert!(suggestions.resolve("tory", "Tauri"));
+    assert!(!suggestions.resolve("tory", "Tauri"));
+    assert!(suggestions.list().is_empty());
+
+    assert!(suggestions
+        .record(&[correction("tory", "Tauri")])
+        .is_empty());
+    assert!(suggestions.list().is_empty());
+    // A different correction of the same word is still offered
+    assert_eq!(suggestions.record(&[correction("tory", "story")]).len(), 1);
+}
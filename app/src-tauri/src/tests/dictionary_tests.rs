@@ -0,0 +1,116 @@
+use crate::dictionary::{
+    dictionary_prompt, parse_dictionary_line, parse_legacy_dictionary, DictionaryStore,
+    DICTIONARY_PROMPT_HEADER,
+};
+
+fn entry(spoken: &str, written: &str, case_sensitive: bool) -> (String, String, bool) {
+    (spoken.to_string(), written.to_string(), case_sensitive)
+}
+
+#[test]
+fn test_add_list_update_remove() {
+    let store = DictionaryStore::in_memory().unwrap();
+    let anthropic = store.add("ant row pick", "Anthropic", false).unwrap();
+    let tauri = store.add("  Tauri ", "", true).unwrap();
+    assert_eq!(
+        (tauri.spoken.as_str(), tauri.written.as_str()),
+        ("Tauri", "Tauri")
+    );
+
+    let updated = store
+        .update(&anthropic.id, "ant row pic", "Anthropic", false)
+        .unwrap();
+    assert_eq!(updated.id, anthropic.id);
+
+    let entries = store.list().unwrap();
+    assert_eq!(entries, vec![updated, tauri.clone()]);
+
+    assert!(store.remove(&anthropic.id).unwrap());
+    assert!(!store.remove(&anthropic.id).unwrap());
+    assert_eq!(store.list().unwrap(), vec![tauri]);
+}
+
+#[test]
+fn test_duplicate_spoken_forms() {
+    let store = DictionaryStore::in_memory().unwrap();
+    let first = store.add("pipe cat", "Pipecat", false).unwrap();
+
+    // The same mapping again is the existing entry
+    assert_eq!(store.add("Pipe Cat", "Pipecat", false).unwrap(), first);
+    assert!(store.add("pipe cat", "PipeCat", false).is_err());
+    // Case-sensitive entries only clash with the exact spoken form
+    assert!(store.add("Pipe Cat", "PipeCat", true).is_ok());
+
+    let other = store.add("tory", "Tauri", false).unwrap();
+    assert!(store.update(&other.id, "pipe cat", "Tauri", false).is_err());
+    assert!(store.update("missing", "new", "New", false).is_err());
+}
+
+#[test]
+fn test_empty_spoken_form_is_rejected() {
+    let store = DictionaryStore::in_memory().unwrap();
+    assert!(store.add("   ", "Anything", false).is_err());
+    assert!(store.list().unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_dictionary_lines() {
+    assert_eq!(
+        parse_dictionary_line("ant row pick = Anthropic"),
+        Some(entry("ant row pick", "Anthropic", false))
+    );
+    assert_eq!(
+        parse_dictionary_line("LLM"),
+        Some(entry("LLM", "LLM", false))
+    );
+    assert_eq!(
+        parse_dictionary_line("will = Will (match case)"),
+        Some(entry("will", "Will", true))
+    );
+    assert_eq!(parse_dictionary_line("### Entries:"), None);
+    assert_eq!(parse_dictionary_line("- Single terms to recognize"), None);
+    assert_eq!(
+        parse_dictionary_line("The name Claude should always be capitalized"),
+        None
+    );
+}
+
+#[test]
+fn test_legacy_section_reads_entries_heading() {
+    let content = "## Personal Dictionary\nApply these corrections.\n\n### Entries:\nTambourine\nant row pick = Anthropic\n";
+    assert_eq!(
+        parse_legacy_dictionary(content),
+        vec![
+            entry("Tambourine", "Tambourine", false),
+            entry("ant row pick", "Anthropic", false),
+        ]
+    );
+}
+
+#[test]
+fn test_prompt_renders_entries() {
+    let store = DictionaryStore::in_memory().unwrap();
+    assert_eq!(dictionary_prompt(&store.list().unwrap()), None);
+
+    store.add("ant row pick", "Anthropic", false).unwrap();
+    store.add("LLM", "", false).unwrap();
+    store.add("will", "Will", true).unwrap();
+    let prompt = dictionary_prompt(&store.list().unwrap()).unwrap();
+    assert_eq!(
+        prompt,
+        format!(
+            "{}\nant row pick = Anthropic\nLLM\nwill = Will (match case)",
+            DICTIONARY_PROMPT_HEADER
+        )
+    );
+
+    // The rendered prompt imports back to the same entries
+    assert_eq!(
+        parse_legacy_dictionary(&prompt),
+        vec![
+            entry("ant row pick", "Anthropic", false),
+            entry("LLM", "LLM", false),
+            entry("will", "Will", true),
+        ]
+    );
+}
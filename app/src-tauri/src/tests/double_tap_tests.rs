@@ -0,0 +1,77 @@
+use crate::double_tap::DoubleTapDetector;
+use std::time::{Duration, Instant};
+
+const INTERVAL: Duration = Duration::from_millis(300);
+
+fn ms(value: u64) -> Duration {
+    Duration::from_millis(value)
+}
+
+/// Tap once, starting at `start`, and return whether it completed a double tap
+fn tap(detector: &mut DoubleTapDetector, start: Instant, hold: Duration) -> bool {
+    detector.press(start);
+    detector.release(start + hold)
+}
+
+#[test]
+fn test_two_quick_taps_complete() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    let t0 = Instant::now();
+    assert!(!tap(&mut detector, t0, ms(50)));
+    assert!(tap(&mut detector, t0 + ms(150), ms(50)));
+}
+
+#[test]
+fn test_single_tap_does_not_complete() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    assert!(!tap(&mut detector, Instant::now(), ms(50)));
+}
+
+#[test]
+fn test_slow_second_tap_starts_over() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    let t0 = Instant::now();
+    assert!(!tap(&mut detector, t0, ms(50)));
+    assert!(!tap(&mut detector, t0 + ms(500), ms(50)));
+    // The slow tap counts as the first tap of a new pair
+    assert!(tap(&mut detector, t0 + ms(700), ms(50)));
+}
+
+#[test]
+fn test_long_hold_is_not_a_tap() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    let t0 = Instant::now();
+    assert!(!tap(&mut detector, t0, ms(500)));
+    assert!(!tap(&mut detector, t0 + ms(600), ms(50)));
+}
+
+#[test]
+fn test_key_repeat_is_ignored() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    let t0 = Instant::now();
+    detector.press(t0);
+    detector.press(t0 + ms(30));
+    detector.press(t0 + ms(60));
+    assert!(!detector.release(t0 + ms(80)));
+    assert!(tap(&mut detector, t0 + ms(200), ms(50)));
+}
+
+#[test]
+fn test_chord_interrupts_double_tap() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    let t0 = Instant::now();
+    assert!(!tap(&mut detector, t0, ms(50)));
+    // Ctrl+C on the second press
+    detector.press(t0 + ms(150));
+    detector.interrupt();
+    assert!(!detector.release(t0 + ms(200)));
+}
+
+#[test]
+fn test_triple_tap_completes_once() {
+    let mut detector = DoubleTapDetector::new(INTERVAL);
+    let t0 = Instant::now();
+    assert!(!tap(&mut detector, t0, ms(50)));
+    assert!(tap(&mut detector, t0 + ms(150), ms(50)));
+    assert!(!tap(&mut detector, t0 + ms(300), ms(50)));
+}
@@ -0,0 +1,44 @@
+use crate::error::AppError;
+use crate::settings::ShortcutRegistrationFailure;
+use serde_json::json;
+
+#[test]
+fn test_error_serializes_code_kind_message_and_context() {
+    let error = AppError::settings_io("save settings", "disk full");
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "code": "settings_io",
+            "kind": "io",
+            "message": "Failed to save settings: disk full",
+            "context": { "action": "save settings", "reason": "disk full" },
+        })
+    );
+}
+
+#[test]
+fn test_invalid_hotkey_is_a_validation_error() {
+    let error = AppError::invalid_hotkey("ctrl+alt+Nope", "Failed to parse shortcut");
+    assert_eq!(error.code(), "invalid_hotkey");
+    assert_eq!(error.kind(), "validation");
+    assert_eq!(error.context()["hotkey"], "ctrl+alt+Nope");
+}
+
+#[test]
+fn test_shortcuts_in_use_lists_failures() {
+    let error = AppError::ShortcutsInUse {
+        failures: vec![ShortcutRegistrationFailure {
+            action: "paste_last".to_string(),
+            shortcut: "ctrl+alt+Period".to_string(),
+            error: "already registered".to_string(),
+        }],
+    };
+    assert_eq!(error.kind(), "conflict");
+    assert!(error
+        .to_string()
+        .contains("paste last (ctrl+alt+Period): already registered"));
+    assert_eq!(
+        error.context()["failures"][0]["shortcut"],
+        "ctrl+alt+Period"
+    );
+}
@@ -0,0 +1,76 @@
+use crate::events::{is_connected_state, AppEvent, EventBus};
+use tokio::sync::broadcast::error::TryRecvError;
+
+#[test]
+fn test_subscriber_receives_published_event() {
+    let bus = EventBus::default();
+    let mut receiver = bus.subscribe();
+    bus.publish(AppEvent::RecordingStarted);
+    assert_eq!(receiver.try_recv(), Ok(AppEvent::RecordingStarted));
+}
+
+#[test]
+fn test_every_subscriber_receives_each_event() {
+    let bus = EventBus::default();
+    let mut first = bus.subscribe();
+    let mut second = bus.subscribe();
+    let event = AppEvent::TurnCompleted {
+        text: "Hello.".to_string(),
+    };
+    bus.publish(event.clone());
+    assert_eq!(first.try_recv(), Ok(event.clone()));
+    assert_eq!(second.try_recv(), Ok(event));
+}
+
+#[test]
+fn test_events_arrive_in_order() {
+    let bus = EventBus::default();
+    let mut receiver = bus.subscribe();
+    bus.publish(AppEvent::RecordingStarted);
+    bus.publish(AppEvent::RecordingStopped);
+    assert_eq!(receiver.try_recv(), Ok(AppEvent::RecordingStarted));
+    assert_eq!(receiver.try_recv(), Ok(AppEvent::RecordingStopped));
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn test_publish_without_subscribers_is_ignored() {
+    let bus = EventBus::default();
+    bus.publish(AppEvent::SettingsChanged);
+}
+
+#[test]
+fn test_subscriber_only_sees_events_after_subscribing() {
+    let bus = EventBus::default();
+    bus.publish(AppEvent::RecordingStarted);
+    let mut receiver = bus.subscribe();
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn test_slow_subscriber_reports_lag() {
+    let bus = EventBus::new(2);
+    let mut receiver = bus.subscribe();
+    for _ in 0..3 {
+        bus.publish(AppEvent::SettingsChanged);
+    }
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Lagged(1)));
+}
+
+#[test]
+fn test_event_serializes_with_type_tag() {
+    let event = AppEvent::ConnectionChanged { connected: true };
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!({ "type": "connection_changed", "connected": true })
+    );
+}
+
+#[test]
+fn test_connected_states() {
+    assert!(is_connected_state("idle"));
+    assert!(is_connected_state("recording"));
+    assert!(is_connected_state("processing"));
+    assert!(!is_connected_state("connecting"));
+    assert!(!is_connected_state("disconnected"));
+}
@@ -0,0 +1,71 @@
+use crate::export::{self, ExportData, ExportFormat};
+use crate::history::{HistoryContext, HistoryEntry};
+use crate::usage::{UsagePeriod, UsageStats};
+use chrono::DateTime;
+
+fn entry(text: &str) -> HistoryEntry {
+    HistoryEntry {
+        timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        ..HistoryEntry::new(
+            text.to_string(),
+            HistoryContext {
+                app_name: Some("Notes".to_string()),
+                provider: Some("deepgram".to_string()),
+                duration_ms: Some(1_000),
+                ..Default::default()
+            },
+        )
+    }
+}
+
+#[test]
+fn test_csv_field_quotes_only_when_needed() {
+    assert_eq!(export::csv_field("plain text"), "plain text");
+    assert_eq!(export::csv_field("a, b"), "\"a, b\"");
+    assert_eq!(export::csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(export::csv_field("two\nlines"), "\"two\nlines\"");
+}
+
+#[test]
+fn test_history_csv_has_a_row_per_entry() {
+    let csv = export::history_csv(&[entry("one two three"), entry("Hello, world")]);
+    let rows: Vec<&str> = csv.split("\r\n").collect();
+
+    assert_eq!(rows[0], export::CSV_HEADER.join(","));
+    // 40 words per minute types three words in 4.5s; speaking took 1s
+    assert_eq!(
+        rows[1],
+        "2023-11-14T22:13:20+00:00,Notes,deepgram,1000,3,3500,one two three,"
+    );
+    assert!(rows[2].ends_with(",\"Hello, world\","));
+    assert_eq!(rows.len(), 4);
+}
+
+#[test]
+fn test_json_export_includes_stats_and_history() {
+    let data = ExportData {
+        exported_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        stats: UsageStats {
+            period: UsagePeriod::Month,
+            dictations: 1,
+            words: 2,
+            ..UsageStats::default()
+        },
+        history: vec![entry("hello there")],
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(&data.render(ExportFormat::Json).unwrap()).unwrap();
+
+    assert_eq!(json["stats"]["period"], "month");
+    assert_eq!(json["stats"]["words"], 2);
+    assert_eq!(json["history"][0]["text"], "hello there");
+}
+
+#[test]
+fn test_default_file_name() {
+    let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    assert_eq!(
+        export::default_file_name(ExportFormat::Csv, UsagePeriod::Week, now),
+        "tambourine-week-20231114-221320.csv"
+    );
+}
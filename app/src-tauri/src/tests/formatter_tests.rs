@@ -0,0 +1,307 @@
+use crate::formatter::{
+    build_format_prompt, format_with_rules, parse_ollama_chunk, FormatContext, FormatPreviews,
+    Formatter, FormatterChain, PromptBudget, PromptPart, PromptPartKind, PromptTruncation,
+    DEFAULT_FORMAT_PROMPT,
+};
+use crate::settings::{CleanupPromptSections, PromptSection};
+
+struct MockFormatter {
+    name: &'static str,
+    available: bool,
+    result: Result<Option<String>, String>,
+}
+
+impl Formatter for MockFormatter {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
+    fn format(
+        &self,
+        _raw: &str,
+        _context: &FormatContext,
+        _prompt: &str,
+        _on_text: &mut dyn FnMut(&str),
+    ) -> Result<Option<String>, String> {
+        self.result.clone()
+    }
+}
+
+fn mock(
+    name: &'static str,
+    available: bool,
+    result: Result<Option<String>, String>,
+) -> Box<dyn Formatter> {
+    Box::new(MockFormatter {
+        name,
+        available,
+        result,
+    })
+}
+
+fn run_chain(chain: &FormatterChain) -> Result<Option<String>, String> {
+    chain.format("raw", &FormatContext::default(), "prompt", &mut |_| {})
+}
+
+#[test]
+fn test_rules_removes_fillers_and_punctuates() {
+    assert_eq!(format_with_rules("um hello uh world"), "Hello world.");
+}
+
+#[test]
+fn test_rules_removes_repeated_letter_fillers_with_commas() {
+    assert_eq!(format_with_rules("ummm, so hmmm yes"), "So yes.");
+}
+
+#[test]
+fn test_rules_keeps_words_starting_with_filler() {
+    assert_eq!(format_with_rules("umbrella under"), "Umbrella under.");
+}
+
+#[test]
+fn test_rules_keeps_existing_terminal_punctuation() {
+    assert_eq!(format_with_rules("is it ready?"), "Is it ready?");
+}
+
+#[test]
+fn test_rules_collapses_whitespace() {
+    assert_eq!(format_with_rules("  hello \n  there  "), "Hello there.");
+}
+
+#[test]
+fn test_rules_only_fillers_is_empty() {
+    assert_eq!(format_with_rules("um uh"), "");
+}
+
+#[test]
+fn test_chain_uses_first_successful_formatter() {
+    let chain = FormatterChain::new(vec![
+        mock("first", true, Ok(Some("first".to_string()))),
+        mock("second", true, Ok(Some("second".to_string()))),
+    ]);
+    assert_eq!(run_chain(&chain), Ok(Some("first".to_string())));
+}
+
+#[test]
+fn test_chain_skips_unavailable_formatters() {
+    let chain = FormatterChain::new(vec![
+        mock("offline", false, Ok(Some("offline".to_string()))),
+        mock("fallback", true, Ok(Some("fallback".to_string()))),
+    ]);
+    assert_eq!(run_chain(&chain), Ok(Some("fallback".to_string())));
+}
+
+#[test]
+fn test_chain_falls_through_on_error() {
+    let chain = FormatterChain::new(vec![
+        mock("broken", true, Err("boom".to_string())),
+        mock("fallback", true, Ok(Some("fallback".to_string()))),
+    ]);
+    assert_eq!(run_chain(&chain), Ok(Some("fallback".to_string())));
+}
+
+#[test]
+fn test_chain_passes_through_async_result() {
+    let chain = FormatterChain::new(vec![
+        mock("server", true, Ok(None)),
+        mock("fallback", true, Ok(Some("fallback".to_string()))),
+    ]);
+    assert_eq!(run_chain(&chain), Ok(None));
+}
+
+#[test]
+fn test_chain_returns_last_error_when_all_fail() {
+    let chain = FormatterChain::new(vec![
+        mock("first", true, Err("first failed".to_string())),
+        mock("second", true, Err("second failed".to_string())),
+    ]);
+    assert_eq!(run_chain(&chain), Err("second failed".to_string()));
+}
+
+#[test]
+fn test_empty_chain_errors() {
+    let chain = FormatterChain::new(Vec::new());
+    assert_eq!(run_chain(&chain), Err("No formatter available".to_string()));
+}
+
+fn preview_chain(chain: &FormatterChain) -> Result<String, String> {
+    chain.preview("raw", &FormatContext::default(), "prompt")
+}
+
+#[test]
+fn test_preview_returns_first_synchronous_result() {
+    let chain = FormatterChain::new(vec![
+        mock("offline", false, Ok(Some("offline".to_string()))),
+        mock("first", true, Ok(Some("first".to_string()))),
+        mock("second", true, Ok(Some("second".to_string()))),
+    ]);
+    assert_eq!(preview_chain(&chain), Ok("first".to_string()));
+}
+
+#[test]
+fn test_preview_skips_formatters_that_only_insert() {
+    let chain = FormatterChain::new(vec![
+        mock("async", true, Ok(None)),
+        mock("fallback", true, Ok(Some("fallback".to_string()))),
+    ]);
+    assert_eq!(preview_chain(&chain), Ok("fallback".to_string()));
+}
+
+#[test]
+fn test_preview_errors_when_nothing_can_preview() {
+    let chain = FormatterChain::new(vec![mock("async", true, Ok(None))]);
+    assert_eq!(
+        preview_chain(&chain),
+        Err("async cannot preview formatting".to_string())
+    );
+}
+
+#[test]
+fn test_format_previews_deliver_to_waiting_caller() {
+    let previews = FormatPreviews::default();
+    let (first_id, first) = previews.register();
+    let (second_id, second) = previews.register();
+    assert_ne!(first_id, second_id);
+
+    assert!(previews.complete(second_id, "second".to_string()));
+    assert!(previews.complete(first_id, "first".to_string()));
+    assert_eq!(first.recv().unwrap(), "first");
+    assert_eq!(second.recv().unwrap(), "second");
+
+    // Each preview is delivered once
+    assert!(!previews.complete(first_id, "again".to_string()));
+}
+
+#[test]
+fn test_cancelled_format_preview_is_not_delivered() {
+    let previews = FormatPreviews::default();
+    let (id, _receiver) = previews.register();
+    previews.cancel(id);
+    assert!(!previews.complete(id, "late".to_string()));
+}
+
+#[test]
+fn test_prompt_defaults_without_sections() {
+    assert_eq!(build_format_prompt(None), DEFAULT_FORMAT_PROMPT);
+}
+
+#[test]
+fn test_prompt_uses_custom_main_and_enabled_sections() {
+    let sections = CleanupPromptSections {
+        main: PromptSection {
+            enabled: true,
+            content: Some("Main".to_string()),
+        },
+        advanced: PromptSection {
+            enabled: false,
+            content: Some("Advanced".to_string()),
+        },
+        dictionary: PromptSection {
+            enabled: true,
+            content: Some("Dictionary".to_string()),
+        },
+    };
+    assert_eq!(build_format_prompt(Some(&sections)), "Main\n\nDictionary");
+}
+
+#[test]
+fn test_prompt_falls_back_to_default_main_when_blank() {
+    let sections = CleanupPromptSections {
+        main: PromptSection {
+            enabled: true,
+            content: Some("   ".to_string()),
+        },
+        ..Default::default()
+    };
+    assert_eq!(build_format_prompt(Some(&sections)), DEFAULT_FORMAT_PROMPT);
+}
+
+fn budget(max_tokens: usize, truncation: PromptTruncation) -> PromptBudget {
+    PromptBudget {
+        max_tokens,
+        truncation,
+    }
+}
+
+fn budget_parts() -> Vec<PromptPart> {
+    vec![
+        PromptPart::new(PromptPartKind::Instructions, "Main"),
+        PromptPart::new(PromptPartKind::Advanced, "x".repeat(40)),
+        PromptPart::new(PromptPartKind::Dictionary, "one = 1\ntwo = 2\nthree = 3"),
+    ]
+}
+
+#[test]
+fn test_budget_zero_means_no_limit() {
+    let prompt = budget(0, PromptTruncation::Drop).fit(budget_parts(), 1000);
+    assert_eq!(prompt.len(), 4 + 2 + 40 + 2 + 25);
+}
+
+#[test]
+fn test_budget_keeps_prompt_that_fits() {
+    let parts = vec![
+        PromptPart::new(PromptPartKind::Instructions, "Main"),
+        PromptPart::new(PromptPartKind::Dictionary, "one = 1"),
+    ];
+    let prompt = budget(100, PromptTruncation::Trim).fit(parts, 10);
+    assert_eq!(prompt, "Main\n\none = 1");
+}
+
+#[test]
+fn test_budget_cuts_advanced_before_dictionary() {
+    // Without the advanced section the prompt is 31 chars, about 8 tokens
+    let prompt = budget(8, PromptTruncation::Drop).fit(budget_parts(), 0);
+    assert_eq!(prompt, "Main\n\none = 1\ntwo = 2\nthree = 3");
+}
+
+#[test]
+fn test_budget_trims_dictionary_to_leading_lines() {
+    let parts = vec![
+        PromptPart::new(PromptPartKind::Instructions, "Main"),
+        PromptPart::new(PromptPartKind::Dictionary, "one = 1\ntwo = 2\nthree = 3"),
+    ];
+    let prompt = budget(6, PromptTruncation::Trim).fit(parts, 0);
+    assert_eq!(prompt, "Main\n\none = 1\ntwo = 2");
+}
+
+#[test]
+fn test_budget_drops_whole_sections() {
+    let prompt = budget(6, PromptTruncation::Drop).fit(budget_parts(), 0);
+    assert_eq!(prompt, "Main");
+}
+
+#[test]
+fn test_budget_never_cuts_instructions_or_transcript() {
+    let prompt = budget(1, PromptTruncation::Trim).fit(budget_parts(), 50);
+    assert_eq!(prompt, "Main");
+}
+
+#[test]
+fn test_parse_ollama_chunk_content() {
+    let line = r#"{"message":{"role":"assistant","content":"Hello"},"done":false}"#;
+    assert_eq!(parse_ollama_chunk(line), Ok(("Hello".to_string(), false)));
+}
+
+#[test]
+fn test_parse_ollama_chunk_done() {
+    let line = r#"{"message":{"role":"assistant","content":""},"done":true}"#;
+    assert_eq!(parse_ollama_chunk(line), Ok((String::new(), true)));
+}
+
+#[test]
+fn test_parse_ollama_chunk_error() {
+    let line = r#"{"error":"model not found"}"#;
+    assert_eq!(
+        parse_ollama_chunk(line),
+        Err("Ollama error: model not found".to_string())
+    );
+}
+
+#[test]
+fn test_parse_ollama_chunk_invalid_json() {
+    assert!(parse_ollama_chunk("not json").is_err());
+}
@@ -0,0 +1,40 @@
+use crate::hid_input::{changed_buttons, is_keyboard_or_mouse, HidAction, HidBinding};
+
+#[test]
+fn test_changed_buttons_reports_presses_and_releases() {
+    // A three-pedal foot pedal: left, middle and right are bits 0, 1 and 2
+    assert_eq!(changed_buttons(&[], &[0b010]), vec![(1, true)]);
+    assert_eq!(
+        changed_buttons(&[0b010], &[0b101]),
+        vec![(0, true), (1, false), (2, true)]
+    );
+    assert_eq!(changed_buttons(&[0b101], &[0b101]), vec![]);
+}
+
+#[test]
+fn test_changed_buttons_counts_bits_across_bytes() {
+    assert_eq!(changed_buttons(&[0, 0], &[0, 0b1000]), vec![(11, true)]);
+    // Bytes missing from the previous report count as released
+    assert_eq!(changed_buttons(&[1], &[1, 1]), vec![(8, true)]);
+}
+
+#[test]
+fn test_keyboards_and_mice_are_not_offered() {
+    assert!(is_keyboard_or_mouse(0x01, 0x06));
+    assert!(is_keyboard_or_mouse(0x01, 0x02));
+    // Consumer controls and vendor pages, as foot pedals use
+    assert!(!is_keyboard_or_mouse(0x0C, 0x01));
+    assert!(!is_keyboard_or_mouse(0xFF00, 0x01));
+}
+
+#[test]
+fn test_binding_deserializes_from_settings() {
+    let binding: HidBinding = serde_json::from_str(
+        r#"{"vendor_id": 1523, "product_id": 255, "usage_page": 12, "usage": 1,
+            "button": 1, "action": "hold"}"#,
+    )
+    .unwrap();
+    assert_eq!(binding.action, HidAction::Hold);
+    assert_eq!(binding.name, "");
+    assert_eq!(binding.interface(), (1523, 255, 12, 1));
+}
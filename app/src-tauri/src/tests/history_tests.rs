@@ -0,0 +1,292 @@
+use crate::history::{settings_hash, HistoryContext, HistoryEntry, HistoryStorage, TurnSnapshot};
+use crate::settings::{CleanupPromptSections, PromptSection};
+use chrono::{Duration, Utc};
+
+fn storage_with(texts: &[&str]) -> HistoryStorage {
+    let storage = HistoryStorage::in_memory().unwrap();
+    for text in texts {
+        storage
+            .add_entry(text.to_string(), HistoryContext::default())
+            .unwrap();
+    }
+    storage
+}
+
+#[test]
+fn test_add_entry_keeps_context() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    let context = HistoryContext {
+        app_name: Some("Slack".to_string()),
+        provider: Some("deepgram".to_string()),
+        duration_ms: Some(4200),
+        ..Default::default()
+    };
+    let added = storage.add_entry("Hello".to_string(), context).unwrap();
+
+    let latest = storage.latest().unwrap().unwrap();
+    assert_eq!(latest.id, added.id);
+    assert_eq!(latest.app_name.as_deref(), Some("Slack"));
+    assert_eq!(latest.provider.as_deref(), Some("deepgram"));
+    assert_eq!(latest.duration_ms, Some(4200));
+}
+
+#[test]
+fn test_latest_is_none_when_empty() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    assert_eq!(storage.latest().unwrap(), None);
+}
+
+#[test]
+fn test_nth_latest_counts_back_from_newest() {
+    let storage = storage_with(&["one", "two", "three"]);
+    let text_at = |index| storage.nth_latest(index).unwrap().map(|entry| entry.text);
+
+    assert_eq!(text_at(0).as_deref(), Some("three"));
+    assert_eq!(text_at(2).as_deref(), Some("one"));
+    assert_eq!(text_at(3), None);
+}
+
+#[test]
+fn test_pages_are_newest_first() {
+    let storage = storage_with(&["one", "two", "three"]);
+
+    let first = storage.get_page(0, 2, None).unwrap();
+    let texts: Vec<&str> = first.entries.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(texts, vec!["three", "two"]);
+    assert_eq!(first.total, 3);
+
+    let second = storage.get_page(1, 2, None).unwrap();
+    let texts: Vec<&str> = second.entries.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(texts, vec!["one"]);
+}
+
+#[test]
+fn test_search_ignores_case() {
+    let storage = storage_with(&["Meeting notes", "grocery list", "Team meeting"]);
+    let page = storage.get_page(0, 10, Some("MEETING")).unwrap();
+    assert_eq!(page.total, 2);
+    assert!(page
+        .entries
+        .iter()
+        .all(|e| e.text.to_lowercase().contains("meeting")));
+}
+
+#[test]
+fn test_search_matches_app_name() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    let context = HistoryContext {
+        app_name: Some("Slack".to_string()),
+        ..Default::default()
+    };
+    storage.add_entry("hi there".to_string(), context).unwrap();
+    storage
+        .add_entry("unrelated".to_string(), HistoryContext::default())
+        .unwrap();
+
+    let page = storage.get_page(0, 10, Some("slack")).unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.entries[0].text, "hi there");
+}
+
+#[test]
+fn test_search_treats_wildcards_literally() {
+    let storage = storage_with(&["100% done", "1000 done", "snake_case", "snakecase"]);
+    assert_eq!(storage.get_page(0, 10, Some("0%")).unwrap().total, 1);
+    assert_eq!(storage.get_page(0, 10, Some("e_c")).unwrap().total, 1);
+}
+
+#[test]
+fn test_delete_and_clear() {
+    let storage = storage_with(&["keep", "remove"]);
+    let remove_id = storage.latest().unwrap().unwrap().id;
+
+    assert!(storage.delete(&remove_id).unwrap());
+    assert!(!storage.delete(&remove_id).unwrap());
+    assert_eq!(storage.get_page(0, 10, None).unwrap().total, 1);
+
+    storage.clear().unwrap();
+    assert_eq!(storage.get_page(0, 10, None).unwrap().total, 0);
+}
+
+#[test]
+fn test_update_text_keeps_raw_text() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    let context = HistoryContext {
+        raw_text: Some("send it to john".to_string()),
+        ..Default::default()
+    };
+    let added = storage
+        .add_entry("Send it to john.".to_string(), context)
+        .unwrap();
+
+    assert!(storage.update_text(&added.id, "Send it to Jon.").unwrap());
+    assert!(!storage.update_text("missing", "text").unwrap());
+
+    let stored = storage.get(&added.id).unwrap().unwrap();
+    assert_eq!(stored.text, "Send it to Jon.");
+    assert_eq!(stored.raw_text.as_deref(), Some("send it to john"));
+}
+
+#[test]
+fn test_import_entries_skips_existing_ids() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    let older = HistoryEntry {
+        timestamp: Utc::now() - Duration::days(1),
+        ..HistoryEntry::new("from json".to_string(), HistoryContext::default())
+    };
+
+    storage
+        .import_entries(std::slice::from_ref(&older))
+        .unwrap();
+    storage
+        .import_entries(std::slice::from_ref(&older))
+        .unwrap();
+    storage
+        .add_entry("new".to_string(), HistoryContext::default())
+        .unwrap();
+
+    let page = storage.get_page(0, 10, None).unwrap();
+    assert_eq!(page.total, 2);
+    assert_eq!(page.entries[1].id, older.id);
+    assert_eq!(
+        page.entries[1].timestamp.timestamp_millis(),
+        older.timestamp.timestamp_millis()
+    );
+}
+
+#[test]
+fn test_entries_since_are_oldest_first() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    let old = HistoryEntry {
+        timestamp: Utc::now() - Duration::days(10),
+        ..HistoryEntry::new("old".to_string(), HistoryContext::default())
+    };
+    let recent = HistoryEntry {
+        timestamp: Utc::now() - Duration::days(1),
+        ..HistoryEntry::new("recent".to_string(), HistoryContext::default())
+    };
+    storage.import_entries(&[recent, old]).unwrap();
+    storage
+        .add_entry("new".to_string(), HistoryContext::default())
+        .unwrap();
+
+    let texts = |entries: Vec<HistoryEntry>| -> Vec<String> {
+        entries.into_iter().map(|entry| entry.text).collect()
+    };
+    assert_eq!(
+        texts(storage.entries_since(None).unwrap()),
+        ["old", "recent", "new"]
+    );
+    assert_eq!(
+        texts(
+            storage
+                .entries_since(Some(Utc::now() - Duration::days(7)))
+                .unwrap()
+        ),
+        ["recent", "new"]
+    );
+}
+
+#[test]
+fn test_legacy_entries_deserialize_without_context() {
+    let json = r#"{"id":"a","timestamp":"2024-01-01T00:00:00Z","text":"old"}"#;
+    let entry: HistoryEntry = serde_json::from_str(json).unwrap();
+    assert_eq!(entry.app_name, None);
+    assert_eq!(entry.duration_ms, None);
+}
+
+#[test]
+fn test_entry_keeps_raw_text_and_snapshot() {
+    let storage = HistoryStorage::in_memory().unwrap();
+    let snapshot = TurnSnapshot {
+        llm_provider: Some("openai".to_string()),
+        prompt_sections: Some(CleanupPromptSections {
+            main: PromptSection {
+                enabled: true,
+                content: Some("Be terse".to_string()),
+            },
+            ..Default::default()
+        }),
+        settings_hash: "0123456789abcdef".to_string(),
+    };
+    let context = HistoryContext {
+        raw_text: Some("um be terse".to_string()),
+        snapshot: Some(snapshot.clone()),
+        ..Default::default()
+    };
+    let added = storage.add_entry("Be terse.".to_string(), context).unwrap();
+
+    let stored = storage.get(&added.id).unwrap().unwrap();
+    assert_eq!(stored.raw_text.as_deref(), Some("um be terse"));
+    assert_eq!(stored.snapshot, Some(snapshot));
+    assert_eq!(storage.get("missing").unwrap(), None);
+}
+
+#[test]
+fn test_opening_old_database_adds_new_columns() {
+    let dir = std::env::temp_dir().join(format!("history-upgrade-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let connection = rusqlite::Connection::open(dir.join("history.db")).unwrap();
+    connection
+        .execute_batch(
+            "CREATE TABLE history (
+                id TEXT PRIMARY KEY, timestamp_ms INTEGER NOT NULL, text TEXT NOT NULL,
+                app_name TEXT, provider TEXT, duration_ms INTEGER
+            );
+            INSERT INTO history (id, timestamp_ms, text) VALUES ('old', 0, 'before upgrade');",
+        )
+        .unwrap();
+    drop(connection);
+
+    let storage = HistoryStorage::new(&dir).unwrap();
+    let old = storage.get("old").unwrap().unwrap();
+    assert_eq!(old.text, "before upgrade");
+    assert_eq!(old.raw_text, None);
+    assert_eq!(old.snapshot, None);
+    assert!(!old.pinned);
+    storage
+        .add_entry("after upgrade".to_string(), HistoryContext::default())
+        .unwrap();
+    assert_eq!(storage.get_page(0, 10, None).unwrap().total, 2);
+
+    drop(storage);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_settings_hash_is_stable_and_sensitive() {
+    let settings = serde_json::json!({"llm_provider": "openai", "short_utterance_max_words": 3});
+    assert_eq!(settings_hash(&settings), settings_hash(&settings.clone()));
+    assert_eq!(settings_hash(&settings).len(), 16);
+    let changed = serde_json::json!({"llm_provider": "groq", "short_utterance_max_words": 3});
+    assert_ne!(settings_hash(&settings), settings_hash(&changed));
+}
+
+#[test]
+fn test_pinned_entries_survive_clearing() {
+    let storage = storage_with(&["123 Main Street", "scratch", "Thanks, talk soon!"]);
+    let page = storage.get_page(0, 10, None).unwrap();
+    let reply = &page.entries[0];
+    let address = &page.entries[2];
+
+    assert!(storage.set_pinned(&address.id, true).unwrap());
+    assert!(storage.set_pinned(&reply.id, true).unwrap());
+    assert!(!storage.set_pinned("missing", true).unwrap());
+    let pinned: Vec<String> = storage
+        .pinned()
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.text)
+        .collect();
+    assert_eq!(pinned, ["Thanks, talk soon!", "123 Main Street"]);
+
+    storage.clear().unwrap();
+    assert_eq!(storage.get_page(0, 10, None).unwrap().total, 2);
+    assert!(storage.get(&address.id).unwrap().unwrap().pinned);
+
+    // Unpinned entries go with the next clear
+    assert!(storage.set_pinned(&address.id, false).unwrap());
+    storage.clear().unwrap();
+    assert_eq!(storage.pinned().unwrap().len(), 1);
+    assert_eq!(storage.get_page(0, 10, None).unwrap().total, 1);
+}
@@ -0,0 +1,80 @@
+use crate::hold_latch::{HoldLatch, LatchAction};
+use std::time::{Duration, Instant};
+
+const TAP_THRESHOLD: Duration = Duration::from_millis(300);
+
+fn ms(value: u64) -> Duration {
+    Duration::from_millis(value)
+}
+
+#[test]
+fn test_long_hold_records_while_held() {
+    let mut latch = HoldLatch::default();
+    let t0 = Instant::now();
+    assert_eq!(latch.press(t0), LatchAction::Start);
+    assert_eq!(
+        latch.release(t0 + ms(2000), TAP_THRESHOLD),
+        LatchAction::Stop
+    );
+}
+
+#[test]
+fn test_quick_tap_latches_until_second_tap() {
+    let mut latch = HoldLatch::default();
+    let t0 = Instant::now();
+    assert_eq!(latch.press(t0), LatchAction::Start);
+    assert_eq!(
+        latch.release(t0 + ms(100), TAP_THRESHOLD),
+        LatchAction::Latch(0)
+    );
+
+    assert_eq!(latch.press(t0 + ms(5000)), LatchAction::Stop);
+    assert_eq!(
+        latch.release(t0 + ms(5100), TAP_THRESHOLD),
+        LatchAction::Ignore
+    );
+
+    // Back to idle, so the next press starts a new recording
+    assert_eq!(latch.press(t0 + ms(6000)), LatchAction::Start);
+}
+
+#[test]
+fn test_key_repeat_is_ignored() {
+    let mut latch = HoldLatch::default();
+    let t0 = Instant::now();
+    assert_eq!(latch.press(t0), LatchAction::Start);
+    assert_eq!(latch.press(t0 + ms(50)), LatchAction::Ignore);
+    assert_eq!(latch.press(t0 + ms(100)), LatchAction::Ignore);
+}
+
+#[test]
+fn test_latch_expires_only_while_still_latched() {
+    let mut latch = HoldLatch::default();
+    let t0 = Instant::now();
+    latch.press(t0);
+    let LatchAction::Latch(first) = latch.release(t0 + ms(100), TAP_THRESHOLD) else {
+        panic!("tap should latch");
+    };
+
+    // Stopped by a second tap, then latched again: the old timer must not stop it
+    latch.press(t0 + ms(1000));
+    latch.release(t0 + ms(1100), TAP_THRESHOLD);
+    latch.press(t0 + ms(2000));
+    let LatchAction::Latch(second) = latch.release(t0 + ms(2100), TAP_THRESHOLD) else {
+        panic!("tap should latch");
+    };
+
+    assert!(!latch.expire(first));
+    assert!(latch.expire(second));
+    assert!(!latch.expire(second));
+}
+
+#[test]
+fn test_reset_forgets_latch() {
+    let mut latch = HoldLatch::default();
+    let t0 = Instant::now();
+    latch.press(t0);
+    latch.release(t0 + ms(100), TAP_THRESHOLD);
+    latch.reset();
+    assert_eq!(latch.press(t0 + ms(1000)), LatchAction::Start);
+}
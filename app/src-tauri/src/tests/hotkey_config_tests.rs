@@ -1,4 +1,8 @@
-use crate::settings::HotkeyConfig;
+use crate::settings::{
+    resolve_default_hotkeys, sequence_follow_ups, HotkeyAction, HotkeyConfig, HotkeyPlatform,
+    HotkeyStep, HotkeyTrigger, KeyboardLayout, MediaKey, MouseButton, TapModifier,
+};
+use std::time::Duration;
 
 // Tests for HotkeyConfig::to_shortcut_string()
 #[test]
@@ -6,6 +10,7 @@ fn test_to_shortcut_string_single_modifier() {
     let hotkey = HotkeyConfig {
         key: "Space".to_string(),
         modifiers: vec!["Ctrl".to_string()],
+        ..Default::default()
     };
     assert_eq!(hotkey.to_shortcut_string(), "ctrl+Space");
 }
@@ -15,6 +20,7 @@ fn test_to_shortcut_string_multiple_modifiers() {
     let hotkey = HotkeyConfig {
         key: "Space".to_string(),
         modifiers: vec!["Ctrl".to_string(), "Alt".to_string()],
+        ..Default::default()
     };
     assert_eq!(hotkey.to_shortcut_string(), "ctrl+alt+Space");
 }
@@ -24,7 +30,315 @@ fn test_to_shortcut_string_preserves_key_case() {
     let hotkey = HotkeyConfig {
         key: "Backquote".to_string(),
         modifiers: vec!["CTRL".to_string(), "ALT".to_string()],
+        ..Default::default()
     };
     // Modifiers should be lowercase, key should preserve case
     assert_eq!(hotkey.to_shortcut_string(), "ctrl+alt+Backquote");
 }
+
+// Tests for mouse button triggers
+#[test]
+fn test_missing_trigger_defaults_to_keyboard() {
+    let hotkey: HotkeyConfig =
+        serde_json::from_str(r#"{"modifiers": ["ctrl"], "key": "Space"}"#).unwrap();
+    assert_eq!(hotkey.trigger, HotkeyTrigger::Keyboard);
+    assert_eq!(hotkey.mouse_button(), None);
+}
+
+#[test]
+fn test_mouse_trigger_deserializes() {
+    let hotkey: HotkeyConfig =
+        serde_json::from_str(r#"{"modifiers": [], "key": "Mouse4", "trigger": "mouse"}"#).unwrap();
+    assert_eq!(hotkey.trigger, HotkeyTrigger::Mouse);
+    assert_eq!(hotkey.mouse_button(), Some(MouseButton::Back));
+}
+
+#[test]
+fn test_mouse_button_from_key() {
+    assert_eq!(MouseButton::from_key("Mouse3"), Some(MouseButton::Middle));
+    assert_eq!(MouseButton::from_key("mouse4"), Some(MouseButton::Back));
+    assert_eq!(MouseButton::from_key("MOUSE5"), Some(MouseButton::Forward));
+    assert_eq!(MouseButton::from_key("Space"), None);
+}
+
+#[test]
+fn test_keyboard_trigger_has_no_mouse_button() {
+    let hotkey = HotkeyConfig {
+        key: "Mouse4".to_string(),
+        modifiers: vec![],
+        trigger: HotkeyTrigger::Keyboard,
+        ..Default::default()
+    };
+    assert_eq!(hotkey.mouse_button(), None);
+}
+
+// Tests for double-tap triggers
+#[test]
+fn test_double_tap_trigger_deserializes() {
+    let hotkey: HotkeyConfig = serde_json::from_str(
+        r#"{"modifiers": [], "key": "ctrl", "trigger": "double_tap", "tap_interval_ms": 400}"#,
+    )
+    .unwrap();
+    assert_eq!(hotkey.trigger, HotkeyTrigger::DoubleTap);
+    assert_eq!(hotkey.tap_modifier(), Some(TapModifier::Ctrl));
+    assert_eq!(hotkey.tap_interval(), Duration::from_millis(400));
+}
+
+#[test]
+fn test_tap_interval_defaults_when_missing() {
+    let hotkey: HotkeyConfig =
+        serde_json::from_str(r#"{"modifiers": [], "key": "shift", "trigger": "double_tap"}"#)
+            .unwrap();
+    assert_eq!(hotkey.tap_interval(), Duration::from_millis(300));
+}
+
+#[test]
+fn test_tap_modifier_from_key() {
+    assert_eq!(TapModifier::from_key("Ctrl"), Some(TapModifier::Ctrl));
+    assert_eq!(TapModifier::from_key("option"), Some(TapModifier::Alt));
+    assert_eq!(TapModifier::from_key("SHIFT"), Some(TapModifier::Shift));
+    assert_eq!(TapModifier::from_key("cmd"), Some(TapModifier::Meta));
+    assert_eq!(TapModifier::from_key("Space"), None);
+}
+
+#[test]
+fn test_keyboard_trigger_has_no_tap_modifier() {
+    let hotkey = HotkeyConfig {
+        key: "ctrl".to_string(),
+        modifiers: vec![],
+        ..Default::default()
+    };
+    assert_eq!(hotkey.tap_modifier(), None);
+}
+
+// Tests for media key triggers
+#[test]
+fn test_media_key_trigger_deserializes() {
+    let hotkey: HotkeyConfig = serde_json::from_str(
+        r#"{"modifiers": [], "key": "MediaPlayPause", "trigger": "media_key"}"#,
+    )
+    .unwrap();
+    assert_eq!(hotkey.trigger, HotkeyTrigger::MediaKey);
+    assert_eq!(hotkey.media_key(), Some(MediaKey::PlayPause));
+    assert_eq!(hotkey.mouse_button(), None);
+    assert!(!hotkey.is_plain_keyboard());
+}
+
+#[test]
+fn test_media_key_from_key() {
+    assert_eq!(
+        MediaKey::from_key("MediaTrackNext"),
+        Some(MediaKey::NextTrack)
+    );
+    assert_eq!(
+        MediaKey::from_key("mediatrackprevious"),
+        Some(MediaKey::PreviousTrack)
+    );
+    assert_eq!(MediaKey::from_key("MediaStop"), Some(MediaKey::Stop));
+    assert_eq!(MediaKey::from_key("Space"), None);
+}
+
+#[test]
+fn test_keyboard_trigger_has_no_media_key() {
+    let hotkey = HotkeyConfig {
+        key: "MediaPlayPause".to_string(),
+        modifiers: vec![],
+        ..Default::default()
+    };
+    assert_eq!(hotkey.media_key(), None);
+}
+
+#[test]
+fn test_unset_tap_interval_is_not_serialized() {
+    let json = serde_json::to_string(&HotkeyAction::Toggle.default_hotkey()).unwrap();
+    assert!(!json.contains("tap_interval_ms"));
+}
+
+fn step(modifiers: &[&str], key: &str) -> HotkeyStep {
+    HotkeyStep {
+        modifiers: modifiers.iter().map(|m| m.to_string()).collect(),
+        key: key.to_string(),
+    }
+}
+
+fn sequence(key: &str, then: HotkeyStep) -> HotkeyConfig {
+    HotkeyConfig {
+        modifiers: vec!["ctrl".to_string(), "alt".to_string()],
+        key: key.to_string(),
+        then: Some(then),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_sequence_deserializes_second_step() {
+    let json = r#"{"modifiers":["ctrl","alt"],"key":"D","then":{"modifiers":[],"key":"T"}}"#;
+    let hotkey: HotkeyConfig = serde_json::from_str(json).unwrap();
+    assert!(hotkey.is_sequence());
+    assert!(!hotkey.is_plain_keyboard());
+    assert_eq!(hotkey.then, Some(step(&[], "T")));
+}
+
+#[test]
+fn test_unset_second_step_is_not_serialized() {
+    let json = serde_json::to_string(&HotkeyAction::Toggle.default_hotkey()).unwrap();
+    assert!(!json.contains("then"));
+    assert!(HotkeyAction::Toggle.default_hotkey().is_plain_keyboard());
+}
+
+#[test]
+fn test_second_step_ignored_for_non_keyboard_triggers() {
+    let hotkey = HotkeyConfig {
+        trigger: HotkeyTrigger::DoubleTap,
+        ..sequence("ctrl", step(&[], "T"))
+    };
+    assert!(!hotkey.is_sequence());
+    assert!(!hotkey.is_plain_keyboard());
+    assert_eq!(hotkey.describe(), "ctrl+alt+ctrl");
+}
+
+#[test]
+fn test_bound_by_character_unless_set() {
+    let json = r#"{"modifiers":["ctrl","alt"],"key":"Q"}"#;
+    let hotkey: HotkeyConfig = serde_json::from_str(json).unwrap();
+    assert!(!hotkey.physical);
+    assert_eq!(hotkey.to_shortcut_string(), "ctrl+alt+Q");
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_bound_by_position_keeps_key_outside_windows() {
+    let hotkey = HotkeyConfig {
+        physical: true,
+        ..sequence("Q", step(&[], "W"))
+    };
+    assert_eq!(hotkey.to_shortcut_string(), "ctrl+alt+Q");
+    assert_eq!(hotkey.follow_up(), Some(step(&[], "W")));
+}
+
+#[test]
+fn test_describe_sequence() {
+    let hotkey = sequence("D", step(&["Shift"], "T"));
+    assert_eq!(hotkey.describe(), "ctrl+alt+D then shift+T");
+    assert_eq!(hotkey.to_shortcut_string(), "ctrl+alt+D");
+}
+
+#[test]
+fn test_step_is_same_as_ignores_case_and_order() {
+    assert!(step(&["Ctrl", "Alt"], "d").is_same_as(&step(&["alt", "ctrl"], "D")));
+    assert!(!step(&["ctrl"], "D").is_same_as(&step(&["ctrl", "alt"], "D")));
+    assert!(!step(&["ctrl"], "D").is_same_as(&step(&["ctrl"], "T")));
+}
+
+#[test]
+fn test_sequence_follow_ups_share_leader() {
+    let hotkeys = vec![
+        (HotkeyAction::Toggle, sequence("D", step(&[], "T"))),
+        (HotkeyAction::Hold, HotkeyAction::Hold.default_hotkey()),
+        (HotkeyAction::PasteLast, sequence("D", step(&[], "P"))),
+    ];
+    let follow_ups = sequence_follow_ups(&hotkeys, &step(&["alt", "ctrl"], "D"));
+    assert_eq!(
+        follow_ups,
+        vec![
+            (HotkeyAction::Toggle, step(&[], "T")),
+            (HotkeyAction::PasteLast, step(&[], "P")),
+        ]
+    );
+    assert!(sequence_follow_ups(&hotkeys, &step(&["ctrl"], "D")).is_empty());
+}
+
+// Tests for locale-aware default hotkeys
+#[test]
+fn test_keyboard_layout_from_locale() {
+    assert_eq!(
+        KeyboardLayout::from_locale("en-US"),
+        KeyboardLayout::UsCompatible
+    );
+    assert_eq!(
+        KeyboardLayout::from_locale("ja-JP"),
+        KeyboardLayout::UsCompatible
+    );
+    assert_eq!(
+        KeyboardLayout::from_locale("de-DE"),
+        KeyboardLayout::DeadBackquote
+    );
+    assert_eq!(
+        KeyboardLayout::from_locale("fr_CA.UTF-8"),
+        KeyboardLayout::DeadBackquote
+    );
+    assert_eq!(
+        KeyboardLayout::from_locale("NB"),
+        KeyboardLayout::DeadBackquote
+    );
+    assert_eq!(
+        KeyboardLayout::from_locale(""),
+        KeyboardLayout::UsCompatible
+    );
+}
+
+#[test]
+fn test_us_layout_keeps_defaults() {
+    for platform in [
+        HotkeyPlatform::MacOs,
+        HotkeyPlatform::Windows,
+        HotkeyPlatform::Linux,
+    ] {
+        let defaults = resolve_default_hotkeys(platform, KeyboardLayout::UsCompatible);
+        assert_eq!(
+            defaults.toggle_hotkey,
+            HotkeyAction::Toggle.default_hotkey()
+        );
+        assert_eq!(defaults.hold_hotkey, HotkeyAction::Hold.default_hotkey());
+        assert_eq!(
+            defaults.paste_last_hotkey,
+            HotkeyAction::PasteLast.default_hotkey()
+        );
+    }
+}
+
+#[test]
+fn test_dead_backquote_layout_moves_hold_key() {
+    let defaults = resolve_default_hotkeys(HotkeyPlatform::Windows, KeyboardLayout::DeadBackquote);
+    assert_eq!(defaults.hold_hotkey.to_shortcut_string(), "ctrl+alt+Slash");
+    assert_eq!(
+        defaults.toggle_hotkey,
+        HotkeyAction::Toggle.default_hotkey()
+    );
+    assert_eq!(
+        defaults.paste_last_hotkey,
+        HotkeyAction::PasteLast.default_hotkey()
+    );
+}
+
+#[test]
+fn test_dead_backquote_layout_on_macos_avoids_input_source_shortcut() {
+    let defaults = resolve_default_hotkeys(HotkeyPlatform::MacOs, KeyboardLayout::DeadBackquote);
+    assert_eq!(
+        defaults.toggle_hotkey.to_shortcut_string(),
+        "ctrl+alt+Enter"
+    );
+    assert_eq!(defaults.hold_hotkey.to_shortcut_string(), "ctrl+alt+Slash");
+}
+
+#[test]
+fn test_hotkey_actions_are_listed_in_index_order() {
+    for (index, action) in HotkeyAction::ALL.into_iter().enumerate() {
+        assert_eq!(action.index(), index);
+        assert_eq!(HotkeyAction::from_id(action.id()), Some(action));
+        assert_eq!(action.setting_key(), format!("{}_hotkey", action.id()));
+    }
+}
+
+#[test]
+fn test_hotkey_action_defaults() {
+    assert!(HotkeyAction::Toggle.is_bound_by_default());
+    assert!(!HotkeyAction::CycleHistory.is_bound_by_default());
+    assert_eq!(
+        HotkeyAction::PasteLast.default_hotkey(),
+        HotkeyAction::PasteLast.default_hotkey()
+    );
+    for action in HotkeyAction::ALL {
+        assert!(!action.default_hotkey().to_shortcut_string().is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::history::HistoryStorage;
+use crate::integrity::{
+    back_up_settings, check_data_files, check_history_db, check_models, check_settings_file,
+    quarantine_path, write_atomic, HISTORY_DB_FILE, SETTINGS_BACKUP_FILE, SETTINGS_FILE,
+};
+use crate::stt::models::{WhisperModel, WhisperModelStore};
+use crate::tests::support::TempDir;
+use crate::usage::{UsageStore, USAGE_DB_FILE};
+use chrono::{Local, TimeZone};
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn test_settings_file_must_be_json_object() {
+    let dir = TempDir::new("integrity");
+    let path = dir.join(SETTINGS_FILE);
+
+    fs::write(&path, r#"{"server_url": "http://localhost:8765"}"#).unwrap();
+    assert!(check_settings_file(&path).is_ok());
+
+    fs::write(&path, "[1, 2]").unwrap();
+    assert!(check_settings_file(&path).is_err());
+
+    fs::write(&path, "{\"server_url\": \"http://loc").unwrap();
+    assert!(check_settings_file(&path).is_err());
+}
+
+#[test]
+fn test_history_db_checks_integrity_and_schema() {
+    let dir = TempDir::new("integrity");
+    drop(HistoryStorage::new(&dir).unwrap());
+    assert!(check_history_db(&dir.join(HISTORY_DB_FILE)).is_ok());
+
+    let old_schema = dir.join("old.db");
+    let connection = rusqlite::Connection::open(&old_schema).unwrap();
+    connection
+        .execute_batch("CREATE TABLE history (id TEXT PRIMARY KEY, text TEXT);")
+        .unwrap();
+    drop(connection);
+    let error = check_history_db(&old_schema).unwrap_err();
+    assert!(error.contains("timestamp_ms"), "{}", error);
+
+    let garbage = dir.join("garbage.db");
+    fs::write(&garbage, vec![0xAB; 4096]).unwrap();
+    assert!(check_history_db(&garbage).is_err());
+}
+
+#[test]
+fn test_corrupt_usage_db_is_quarantined() {
+    let dir = TempDir::new("integrity");
+    drop(UsageStore::new(&dir).unwrap());
+    assert!(check_data_files(&dir).is_empty());
+
+    fs::write(dir.join(USAGE_DB_FILE), vec![0xAB; 4096]).unwrap();
+    let issues = check_data_files(&dir);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].file, USAGE_DB_FILE);
+    assert!(!dir.join(USAGE_DB_FILE).exists());
+}
+
+#[test]
+fn test_corrupt_files_are_quarantined_and_healthy_ones_kept() {
+    let dir = TempDir::new("integrity");
+    fs::write(dir.join(SETTINGS_FILE), "{}").unwrap();
+    fs::write(dir.join(HISTORY_DB_FILE), "not a database, just text").unwrap();
+
+    let issues = check_data_files(&dir);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].file, HISTORY_DB_FILE);
+    let quarantined = issues[0].quarantined_as.clone().unwrap();
+    assert!(quarantined.starts_with("history.db.corrupt-"));
+
+    assert!(dir.join(SETTINGS_FILE).exists());
+    assert!(!dir.join(HISTORY_DB_FILE).exists());
+    assert!(dir.join(&quarantined).exists());
+
+    // The app starts over with an empty database
+    drop(HistoryStorage::new(&dir).unwrap());
+    assert!(check_data_files(&dir).is_empty());
+}
+
+#[test]
+fn test_healthy_settings_are_backed_up() {
+    let dir = TempDir::new("integrity");
+    fs::write(dir.join(SETTINGS_FILE), r#"{"language": "en"}"#).unwrap();
+
+    assert!(check_data_files(&dir).is_empty());
+    assert_eq!(
+        fs::read_to_string(dir.join(SETTINGS_BACKUP_FILE)).unwrap(),
+        r#"{"language": "en"}"#
+    );
+}
+
+#[test]
+fn test_corrupt_settings_are_restored_from_backup() {
+    let dir = TempDir::new("integrity");
+    fs::write(dir.join(SETTINGS_BACKUP_FILE), r#"{"language": "de"}"#).unwrap();
+    fs::write(dir.join(SETTINGS_FILE), r#"{"language": "e"#).unwrap();
+
+    let issues = check_data_files(&dir);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].file, SETTINGS_FILE);
+    assert!(issues[0].restored_from_backup);
+    assert!(dir.join(issues[0].quarantined_as.clone().unwrap()).exists());
+    assert_eq!(
+        fs::read_to_string(dir.join(SETTINGS_FILE)).unwrap(),
+        r#"{"language": "de"}"#
+    );
+}
+
+#[test]
+fn test_corrupt_backup_is_not_restored_or_overwritten_by_corrupt_settings() {
+    let dir = TempDir::new("integrity");
+    fs::write(dir.join(SETTINGS_BACKUP_FILE), "not json").unwrap();
+    fs::write(dir.join(SETTINGS_FILE), "[]").unwrap();
+
+    let issues = check_data_files(&dir);
+    assert_eq!(issues.len(), 1);
+    assert!(!issues[0].restored_from_backup);
+    assert!(!dir.join(SETTINGS_FILE).exists());
+
+    fs::write(dir.join(SETTINGS_FILE), "{").unwrap();
+    assert!(back_up_settings(&dir).is_err());
+    assert_eq!(
+        fs::read_to_string(dir.join(SETTINGS_BACKUP_FILE)).unwrap(),
+        "not json"
+    );
+}
+
+#[test]
+fn test_write_atomic_replaces_file_without_leftovers() {
+    let dir = TempDir::new("integrity");
+    let path = dir.join("file.json");
+    fs::write(&path, "old").unwrap();
+
+    write_atomic(&path, b"new").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[test]
+fn test_missing_files_are_not_issues() {
+    let dir = TempDir::new("integrity");
+    assert!(check_data_files(&dir).is_empty());
+}
+
+#[test]
+fn test_quarantine_path_adds_timestamp() {
+    let now = Local.with_ymd_and_hms(2025, 3, 4, 5, 6, 7).unwrap();
+    assert_eq!(
+        quarantine_path(&PathBuf::from("data").join("settings.json"), now),
+        PathBuf::from("data").join("settings.json.corrupt-20250304-050607")
+    );
+}
+
+#[test]
+fn test_models_checked_against_checksum_or_header() {
+    let dir = TempDir::new("integrity");
+    let models = WhisperModelStore::new(&dir);
+
+    // Downloaded before checksums were recorded: only the header is checked
+    fs::write(models.path(WhisperModel::Tiny), b"lmgg model data").unwrap();
+    fs::write(models.path(WhisperModel::Base), b"<html>error page</html>").unwrap();
+    // A checksum is recorded, so a valid header isn't enough
+    fs::write(models.path(WhisperModel::Small), b"lmgg changed on disk").unwrap();
+    fs::write(models.checksum_path(WhisperModel::Small), "0".repeat(64)).unwrap();
+
+    let issues = check_models(&models);
+    let files: Vec<_> = issues.iter().map(|issue| issue.file.as_str()).collect();
+    assert_eq!(files, vec!["ggml-base.bin", "ggml-small.bin"]);
+    assert!(models.downloaded_path(WhisperModel::Tiny).is_some());
+    assert!(models.downloaded_path(WhisperModel::Base).is_none());
+    assert!(!models.checksum_path(WhisperModel::Small).exists());
+}
+
+#[test]
+fn test_model_with_matching_checksum_passes() {
+    let dir = TempDir::new("integrity");
+    let models = WhisperModelStore::new(&dir);
+    let path = models.path(WhisperModel::Tiny);
+    fs::write(&path, b"model data").unwrap();
+    let checksum = crate::stt::models::sha256_file(&path).unwrap();
+    fs::write(models.checksum_path(WhisperModel::Tiny), checksum).unwrap();
+
+    assert!(models.verify(WhisperModel::Tiny).is_ok());
+    assert!(check_models(&models).is_empty());
+}
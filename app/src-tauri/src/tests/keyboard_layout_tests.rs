@@ -0,0 +1,62 @@
+use crate::keyboard_layout::{key_for_virtual_key, resolve_position, scancode};
+
+/// Virtual keys French AZERTY puts at a few positions
+fn azerty(scancode: u16) -> Option<u16> {
+    match scancode {
+        0x10 => Some(u16::from(b'A')),
+        0x1E => Some(u16::from(b'Q')),
+        0x11 => Some(u16::from(b'Z')),
+        0x27 => Some(u16::from(b'M')),
+        0x32 => Some(0xBC),
+        // The digit row types symbols but keeps the digit virtual keys
+        0x02 => Some(u16::from(b'1')),
+        // A dead key without a letter, digit or punctuation virtual key
+        0x1A => Some(0xDD),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_scancode_of_us_positions() {
+    assert_eq!(scancode("Q"), Some(0x10));
+    assert_eq!(scancode("a"), Some(0x1E));
+    assert_eq!(scancode("1"), Some(0x02));
+    assert_eq!(scancode("Backquote"), Some(0x29));
+    assert_eq!(scancode("IntlBackslash"), Some(0x56));
+}
+
+#[test]
+fn test_layout_independent_keys_have_no_scancode() {
+    assert_eq!(scancode("Space"), None);
+    assert_eq!(scancode("F5"), None);
+    assert_eq!(scancode("ArrowUp"), None);
+}
+
+#[test]
+fn test_key_for_virtual_key() {
+    assert_eq!(key_for_virtual_key(u16::from(b'Q')).as_deref(), Some("Q"));
+    assert_eq!(key_for_virtual_key(u16::from(b'7')).as_deref(), Some("7"));
+    assert_eq!(key_for_virtual_key(0xBC).as_deref(), Some("Comma"));
+    assert_eq!(key_for_virtual_key(0xC0).as_deref(), Some("Backquote"));
+    // Modifiers and function keys aren't at layout-dependent positions
+    assert_eq!(key_for_virtual_key(0x10), None);
+    assert_eq!(key_for_virtual_key(0x74), None);
+}
+
+#[test]
+fn test_resolve_position_on_azerty() {
+    assert_eq!(resolve_position("Q", azerty), "A");
+    assert_eq!(resolve_position("A", azerty), "Q");
+    assert_eq!(resolve_position("W", azerty), "Z");
+    assert_eq!(resolve_position("Semicolon", azerty), "M");
+    assert_eq!(resolve_position("M", azerty), "Comma");
+    assert_eq!(resolve_position("1", azerty), "1");
+    assert_eq!(resolve_position("BracketLeft", azerty), "BracketRight");
+}
+
+#[test]
+fn test_resolve_position_keeps_keys_it_cannot_map() {
+    assert_eq!(resolve_position("Space", azerty), "Space");
+    assert_eq!(resolve_position("Backquote", azerty), "Backquote");
+    assert_eq!(resolve_position("Q", |_| None), "Q");
+}
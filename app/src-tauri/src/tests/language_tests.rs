@@ -0,0 +1,53 @@
+use crate::language::{language_hint, language_name, next_language, normalize_language};
+
+fn codes(codes: &[&str]) -> Vec<String> {
+    codes.iter().map(|code| code.to_string()).collect()
+}
+
+#[test]
+fn test_known_languages_are_normalized() {
+    assert_eq!(language_name("en"), Some("English"));
+    assert_eq!(normalize_language(" DE "), Some("de".to_string()));
+    assert_eq!(normalize_language("xx"), None);
+}
+
+#[test]
+fn test_auto_detect_sends_no_hint() {
+    assert_eq!(language_hint(Some("fr"), false), Some("fr".to_string()));
+    assert_eq!(language_hint(Some("fr"), true), None);
+    assert_eq!(language_hint(None, false), None);
+    assert_eq!(language_hint(Some("klingon"), false), None);
+}
+
+#[test]
+fn test_next_language_wraps_around() {
+    let languages = codes(&["en", "es", "de"]);
+    assert_eq!(
+        next_language(Some("en"), &languages),
+        Some("es".to_string())
+    );
+    assert_eq!(
+        next_language(Some("de"), &languages),
+        Some("en".to_string())
+    );
+}
+
+#[test]
+fn test_next_language_starts_from_the_first() {
+    let languages = codes(&["en", "es"]);
+    assert_eq!(next_language(None, &languages), Some("en".to_string()));
+    assert_eq!(
+        next_language(Some("fr"), &languages),
+        Some("en".to_string())
+    );
+    assert_eq!(next_language(Some("en"), &[]), None);
+}
+
+#[test]
+fn test_next_language_skips_unknown_codes() {
+    let languages = codes(&["en", "nope", "ES"]);
+    assert_eq!(
+        next_language(Some("en"), &languages),
+        Some("es".to_string())
+    );
+}
@@ -0,0 +1,36 @@
+use crate::launch::LaunchOptions;
+
+#[test]
+fn test_no_flags_shows_everything() {
+    let options = LaunchOptions::from_args(Vec::<String>::new());
+    assert_eq!(options, LaunchOptions::default());
+    assert!(options.show_main_window);
+    assert!(options.show_overlay);
+}
+
+#[test]
+fn test_headless_skips_main_window() {
+    let options = LaunchOptions::from_args(["--headless"]);
+    assert!(!options.show_main_window);
+    assert!(options.show_overlay);
+}
+
+#[test]
+fn test_no_overlay_skips_overlay() {
+    let options = LaunchOptions::from_args(["--no-overlay"]);
+    assert!(options.show_main_window);
+    assert!(!options.show_overlay);
+}
+
+#[test]
+fn test_flags_combine() {
+    let options = LaunchOptions::from_args(["--no-overlay", "--headless"]);
+    assert!(!options.show_main_window);
+    assert!(!options.show_overlay);
+}
+
+#[test]
+fn test_unknown_flags_are_ignored() {
+    let options = LaunchOptions::from_args(["-psn_0_12345", "--verbose"]);
+    assert_eq!(options, LaunchOptions::default());
+}
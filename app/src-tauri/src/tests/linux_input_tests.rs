@@ -0,0 +1,85 @@
+use crate::linux_input::{
+    detect_display_server, preferred_tools, DisplayServer, KeyModifier, KeyName, WaylandTool,
+};
+
+#[test]
+fn test_display_server_prefers_session_type() {
+    assert_eq!(
+        detect_display_server(Some("wayland"), None, Some(":0")),
+        DisplayServer::Wayland
+    );
+    assert_eq!(
+        detect_display_server(Some("x11"), Some("wayland-0"), None),
+        DisplayServer::X11
+    );
+}
+
+#[test]
+fn test_display_server_falls_back_to_display_vars() {
+    assert_eq!(
+        detect_display_server(Some("tty"), Some("wayland-0"), Some(":0")),
+        DisplayServer::Wayland
+    );
+    assert_eq!(
+        detect_display_server(None, Some(""), Some(":0")),
+        DisplayServer::X11
+    );
+    assert_eq!(
+        detect_display_server(None, None, None),
+        DisplayServer::Unknown
+    );
+}
+
+#[test]
+fn test_wtype_press_args() {
+    assert_eq!(
+        WaylandTool::Wtype.press_args(Some(KeyModifier::Ctrl), KeyName::Letter('V')),
+        ["-M", "ctrl", "-k", "v", "-m", "ctrl"]
+    );
+    assert_eq!(
+        WaylandTool::Wtype.press_args(None, KeyName::BackSpace),
+        ["-k", "BackSpace"]
+    );
+}
+
+#[test]
+fn test_ydotool_press_args_use_event_codes() {
+    assert_eq!(
+        WaylandTool::Ydotool.press_args(Some(KeyModifier::Ctrl), KeyName::Letter('v')),
+        ["key", "29:1", "47:1", "47:0", "29:0"]
+    );
+    assert_eq!(
+        WaylandTool::Ydotool.press_args(Some(KeyModifier::Shift), KeyName::Return),
+        ["key", "42:1", "28:1", "28:0", "42:0"]
+    );
+    assert_eq!(
+        WaylandTool::Ydotool.press_args(None, KeyName::Letter('q')),
+        ["key", "16:1", "16:0"]
+    );
+}
+
+#[test]
+fn test_type_args_end_options() {
+    assert_eq!(WaylandTool::Wtype.type_args("-hi"), ["--", "-hi"]);
+    assert_eq!(WaylandTool::Ydotool.type_args("-hi"), ["type", "--", "-hi"]);
+}
+
+#[test]
+fn test_ydotool_preferred_on_gnome_and_kde() {
+    assert_eq!(
+        preferred_tools(Some("ubuntu:GNOME")),
+        [WaylandTool::Ydotool, WaylandTool::Wtype]
+    );
+    assert_eq!(
+        preferred_tools(Some("KDE")),
+        [WaylandTool::Ydotool, WaylandTool::Wtype]
+    );
+    assert_eq!(
+        preferred_tools(Some("sway")),
+        [WaylandTool::Wtype, WaylandTool::Ydotool]
+    );
+    assert_eq!(
+        preferred_tools(None),
+        [WaylandTool::Wtype, WaylandTool::Ydotool]
+    );
+}
@@ -0,0 +1,53 @@
+use crate::logging::{
+    format_record, log_file_path, log_files, rotate, RotatingLog, MAX_ROTATED_LOGS,
+};
+use crate::tests::support::TempDir;
+use chrono::{DateTime, Utc};
+use std::fs;
+
+#[test]
+fn test_record_is_one_json_line() {
+    let timestamp = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+    let line = format_record(timestamp, log::Level::Warn, "tambourine", "two\nlines");
+    assert!(!line.contains('\n'));
+    let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(value["timestamp"], "2023-11-14T22:13:20.000Z");
+    assert_eq!(value["level"], "WARN");
+    assert_eq!(value["target"], "tambourine");
+    assert_eq!(value["message"], "two\nlines");
+}
+
+#[test]
+fn test_rotation_keeps_a_limited_number_of_files() {
+    let dir = TempDir::new("logging");
+    for round in 0..MAX_ROTATED_LOGS + 2 {
+        fs::write(log_file_path(&dir, 0), round.to_string()).unwrap();
+        rotate(&dir).unwrap();
+    }
+
+    let files = log_files(&dir);
+    assert_eq!(files.len(), MAX_ROTATED_LOGS);
+    assert!(!log_file_path(&dir, 0).exists());
+    // The newest rotated file holds the last round
+    assert_eq!(
+        fs::read_to_string(log_file_path(&dir, 1)).unwrap(),
+        (MAX_ROTATED_LOGS + 1).to_string()
+    );
+}
+
+#[test]
+fn test_log_rotates_when_full() {
+    let dir = TempDir::new("logging");
+    let mut log = RotatingLog::open(&dir, 16).unwrap();
+    log.write_line("first line").unwrap();
+    log.write_line("second line").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(log_file_path(&dir, 0)).unwrap(),
+        "second line\n"
+    );
+    assert_eq!(
+        fs::read_to_string(log_file_path(&dir, 1)).unwrap(),
+        "first line\n"
+    );
+}
@@ -0,0 +1,157 @@
+use crate::mic_monitor::{
+    preferred_mics, resolve_active_mic, ActiveMic, AudioInputDevice, MicFallbackChange, MicMonitor,
+};
+
+fn device(id: &str, label: &str) -> AudioInputDevice {
+    AudioInputDevice {
+        device_id: id.to_string(),
+        label: label.to_string(),
+    }
+}
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+#[test]
+fn test_most_preferred_device_present_is_used() {
+    let devices = [device("built-in", "MacBook Mic"), device("usb", "USB Mic")];
+    assert_eq!(
+        resolve_active_mic(&ids(&["headset", "usb", "built-in"]), &devices),
+        ActiveMic {
+            device_id: Some("usb".to_string()),
+            is_fallback: false
+        }
+    );
+}
+
+#[test]
+fn test_missing_preferred_devices_fall_back_to_default() {
+    let devices = [device("built-in", "MacBook Mic")];
+    assert_eq!(
+        resolve_active_mic(&ids(&["headset", "usb"]), &devices),
+        ActiveMic {
+            device_id: None,
+            is_fallback: true
+        }
+    );
+}
+
+#[test]
+fn test_no_preference_uses_default_without_fallback() {
+    assert_eq!(
+        resolve_active_mic(&[], &[]),
+        ActiveMic {
+            device_id: None,
+            is_fallback: false
+        }
+    );
+}
+
+#[test]
+fn test_legacy_selected_mic_becomes_the_only_preference() {
+    assert_eq!(preferred_mics(None, Some("usb".to_string())), ids(&["usb"]));
+    assert_eq!(preferred_mics(None, None), ids(&[]));
+    assert_eq!(
+        preferred_mics(Some(ids(&["headset"])), Some("usb".to_string())),
+        ids(&["headset"])
+    );
+}
+
+#[test]
+fn test_connecting_a_headset_switches_to_it() {
+    let monitor = MicMonitor::default();
+    let preferred = ids(&["headset", "usb"]);
+    let desk = [device("built-in", "MacBook Mic"), device("usb", "USB Mic")];
+    let with_headset = [
+        device("built-in", "MacBook Mic"),
+        device("usb", "USB Mic"),
+        device("headset", "AirPods"),
+    ];
+
+    let (active, change) = monitor.update(&preferred, &desk);
+    assert_eq!(active.device_id.as_deref(), Some("usb"));
+    assert_eq!(change, None);
+
+    let (active, change) = monitor.update(&preferred, &with_headset);
+    assert_eq!(active.device_id.as_deref(), Some("headset"));
+    assert_eq!(
+        change,
+        Some(MicFallbackChange {
+            is_fallback: false,
+            label: Some("AirPods".to_string())
+        })
+    );
+
+    // Disconnecting it falls back down the list
+    let (active, change) = monitor.update(&preferred, &desk);
+    assert_eq!(active.device_id.as_deref(), Some("usb"));
+    assert_eq!(
+        change,
+        Some(MicFallbackChange {
+            is_fallback: false,
+            label: Some("USB Mic".to_string())
+        })
+    );
+}
+
+#[test]
+fn test_unplug_and_replug_announce_changes_once() {
+    let monitor = MicMonitor::default();
+    let preferred = ids(&["usb"]);
+    let plugged = [device("built-in", "MacBook Mic"), device("usb", "USB Mic")];
+    let unplugged = [device("built-in", "MacBook Mic")];
+
+    let (_, change) = monitor.update(&preferred, &plugged);
+    assert_eq!(change, None);
+
+    let (active, change) = monitor.update(&preferred, &unplugged);
+    assert!(active.is_fallback);
+    assert_eq!(
+        change,
+        Some(MicFallbackChange {
+            is_fallback: true,
+            label: Some("USB Mic".to_string())
+        })
+    );
+
+    // Still missing, nothing new to announce
+    let (_, change) = monitor.update(&preferred, &unplugged);
+    assert_eq!(change, None);
+
+    let (active, change) = monitor.update(&preferred, &plugged);
+    assert_eq!(active.device_id.as_deref(), Some("usb"));
+    assert_eq!(
+        change,
+        Some(MicFallbackChange {
+            is_fallback: false,
+            label: Some("USB Mic".to_string())
+        })
+    );
+}
+
+#[test]
+fn test_changing_preferences_is_not_announced() {
+    let monitor = MicMonitor::default();
+    let devices = [device("built-in", "MacBook Mic"), device("usb", "USB Mic")];
+
+    let (_, change) = monitor.update(&ids(&["usb"]), &devices);
+    assert_eq!(change, None);
+
+    let (active, change) = monitor.update(&ids(&["built-in", "usb"]), &devices);
+    assert_eq!(active.device_id.as_deref(), Some("built-in"));
+    assert_eq!(change, None);
+}
+
+#[test]
+fn test_choosing_default_while_fallen_back_is_not_a_switch_back() {
+    let monitor = MicMonitor::default();
+    let unplugged = [device("built-in", "MacBook Mic")];
+
+    let (_, change) = monitor.update(&ids(&["usb"]), &unplugged);
+    assert_eq!(change.map(|c| c.is_fallback), Some(true));
+
+    let (active, change) = monitor.update(&[], &unplugged);
+    assert!(!active.is_fallback);
+    assert_eq!(change, None);
+}
@@ -1,3 +1,76 @@
+mod accessibility_tests;
+#[cfg(desktop)]
+mod api_tests;
+mod audio_mute_tests;
+mod audio_upload_tests;
+mod auth_tests;
+mod benchmark_tests;
+mod capture_tests;
+mod connection_tests;
+#[cfg(target_os = "linux")]
+mod dbus_tests;
+#[cfg(desktop)]
+mod deep_link_tests;
+mod diagnostics_tests;
+mod dictionary_import_tests;
+mod dictionary_suggestions_tests;
+mod dictionary_tests;
+#[cfg(desktop)]
+mod double_tap_tests;
+mod error_tests;
+mod events_tests;
+mod export_tests;
+mod formatter_tests;
+#[cfg(desktop)]
+mod hid_input_tests;
+mod history_tests;
+mod hold_latch_tests;
 mod hotkey_config_tests;
+mod integrity_tests;
+mod keyboard_layout_tests;
+mod language_tests;
+mod launch_tests;
+#[cfg(target_os = "linux")]
+mod linux_input_tests;
+mod logging_tests;
+mod mic_monitor_tests;
+mod notes_tests;
+mod output_style_tests;
+mod overlay_tests;
+mod palette_tests;
+mod paste_cycle_tests;
+mod pending_transcriptions_tests;
+mod pre_roll_tests;
+mod prompt_presets_tests;
+mod proxy_tests;
+mod recording_limit_tests;
+mod recordings_tests;
+#[cfg(desktop)]
+mod remote_control_tests;
+mod secrets_tests;
+mod server_tests;
+mod session_tests;
+mod settings_broadcast_tests;
 mod settings_commands_tests;
+#[cfg(desktop)]
+mod settings_watcher_tests;
 mod shortcut_tests;
+mod snippets_tests;
+mod sound_theme_tests;
+mod stt_tests;
+mod support;
+mod support_bundle_tests;
+mod telemetry_tests;
+mod text_diff_tests;
+mod text_tests;
+mod text_transforms_tests;
+mod turns_tests;
+mod updater_tests;
+mod usage_tests;
+mod vad_tests;
+mod voice_commands_tests;
+#[cfg(target_os = "linux")]
+mod wayland_shortcuts_tests;
+#[cfg(feature = "local-whisper")]
+mod whisper_tests;
+mod window_state_tests;
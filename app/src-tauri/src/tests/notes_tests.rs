@@ -0,0 +1,41 @@
+use crate::notes::{append_note, format_note, is_markdown};
+use chrono::{Local, TimeZone};
+use std::path::{Path, PathBuf};
+
+fn temp_notes_path() -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("notes-{}", uuid::Uuid::new_v4()))
+        .join("notes.md")
+}
+
+#[test]
+fn test_markdown_note_has_heading_with_app() {
+    let timestamp = Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 0).unwrap();
+    let note = format_note(timestamp, " Buy milk. ", Some("Slack"), true);
+    assert_eq!(note, "## 2026-03-14 09:05 (Slack)\n\nBuy milk.\n\n");
+}
+
+#[test]
+fn test_text_note_has_timestamp_line() {
+    let timestamp = Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 0).unwrap();
+    let note = format_note(timestamp, "Buy milk.", None, false);
+    assert_eq!(note, "[2026-03-14 09:05]\nBuy milk.\n\n");
+}
+
+#[test]
+fn test_markdown_detected_from_extension() {
+    assert!(is_markdown(Path::new("notes.md")));
+    assert!(is_markdown(Path::new("Notes.MARKDOWN")));
+    assert!(!is_markdown(Path::new("notes.txt")));
+    assert!(!is_markdown(Path::new("notes")));
+}
+
+#[test]
+fn test_append_note_creates_file_and_appends() {
+    let path = temp_notes_path();
+    append_note(&path, "first\n").unwrap();
+    append_note(&path, "second\n").unwrap();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
@@ -0,0 +1,89 @@
+use crate::active_window::ActiveApp;
+use crate::formatter::{
+    resolve_output_style, OutputCasing, OutputStyle, OutputStyleRule, QuoteStyle,
+};
+
+fn style(
+    casing: OutputCasing,
+    strip_trailing_punctuation: bool,
+    quotes: QuoteStyle,
+) -> OutputStyle {
+    OutputStyle {
+        casing,
+        strip_trailing_punctuation,
+        quotes,
+    }
+}
+
+#[test]
+fn test_default_style_leaves_text_unchanged() {
+    let text = "He said \"Hi.\" Then left.";
+    assert_eq!(OutputStyle::default().apply(text), text);
+}
+
+#[test]
+fn test_lowercase_without_trailing_punctuation() {
+    let chat = style(OutputCasing::Lowercase, true, QuoteStyle::Unchanged);
+    assert_eq!(
+        chat.apply("Sounds good, See you at 5!  "),
+        "sounds good, see you at 5"
+    );
+}
+
+#[test]
+fn test_sentence_case_skips_abbreviations_and_decimals() {
+    let sentences = style(OutputCasing::SentenceCase, false, QuoteStyle::Unchanged);
+    assert_eq!(
+        sentences.apply("use e.g. version 3.5 here. it works! ask Alice?\nyes"),
+        "Use e.g. version 3.5 here. It works! Ask Alice?\nYes"
+    );
+    assert_eq!(
+        sentences.apply("she said \"stop.\" then left"),
+        "She said \"stop.\" Then left"
+    );
+    assert_eq!(sentences.apply("it costs 3.5. done"), "It costs 3.5. Done");
+}
+
+#[test]
+fn test_quote_styles() {
+    let smart = style(OutputCasing::AsFormatted, false, QuoteStyle::Smart);
+    assert_eq!(
+        smart.apply("He said \"don't\" ('quietly')"),
+        "He said \u{201C}don\u{2019}t\u{201D} (\u{2018}quietly\u{2019})"
+    );
+
+    let straight = style(OutputCasing::AsFormatted, false, QuoteStyle::Straight);
+    assert_eq!(straight.apply("\u{201C}it\u{2019}s\u{201D}"), "\"it's\"");
+}
+
+#[test]
+fn test_resolve_output_style_by_app() {
+    let chat = style(OutputCasing::Lowercase, true, QuoteStyle::Unchanged);
+    let rules = vec![OutputStyleRule {
+        app: "slack".to_string(),
+        style: chat,
+    }];
+    let slack = ActiveApp {
+        app_name: "Slack".to_string(),
+        title: "general".to_string(),
+        process_path: "/Applications/Slack.app/Contents/MacOS/Slack".to_string(),
+        bundle_id: Some("com.tinyspeck.slackmacgap".to_string()),
+    };
+    let editor = ActiveApp {
+        app_name: "TextEdit".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        resolve_output_style(&rules, Some(&slack), OutputStyle::default()),
+        chat
+    );
+    assert_eq!(
+        resolve_output_style(&rules, Some(&editor), OutputStyle::default()),
+        OutputStyle::default()
+    );
+    assert_eq!(
+        resolve_output_style(&rules, None, OutputStyle::default()),
+        OutputStyle::default()
+    );
+}
@@ -0,0 +1,84 @@
+use crate::overlay::{self, MonitorArea, OverlayPosition};
+
+fn monitor(name: &str, x: f64, scale_factor: f64) -> MonitorArea {
+    MonitorArea {
+        name: Some(name.to_string()),
+        x,
+        y: 0.0,
+        width: 1920.0 * scale_factor,
+        height: 1080.0 * scale_factor,
+        scale_factor,
+    }
+}
+
+#[test]
+fn test_scale_is_clamped() {
+    assert_eq!(overlay::clamp_overlay_scale(1.5), 1.5);
+    assert_eq!(
+        overlay::clamp_overlay_scale(0.1),
+        overlay::MIN_OVERLAY_SCALE
+    );
+    assert_eq!(
+        overlay::clamp_overlay_scale(9.0),
+        overlay::MAX_OVERLAY_SCALE
+    );
+    assert_eq!(
+        overlay::clamp_overlay_scale(f64::NAN),
+        overlay::DEFAULT_OVERLAY_SCALE
+    );
+}
+
+#[test]
+fn test_default_position_is_bottom_right_of_primary() {
+    let monitors = [monitor("left", 0.0, 1.0), monitor("right", 1920.0, 1.0)];
+    let center = overlay::restore_center(None, None, &monitors, Some(&monitors[1]), (48.0, 48.0));
+    assert_eq!(center, Some((1920.0 + 1920.0 - 126.0, 1080.0 - 76.0)));
+}
+
+#[test]
+fn test_saved_position_is_restored_on_its_monitor() {
+    let monitors = [monitor("left", 0.0, 1.0), monitor("right", 1920.0, 2.0)];
+    let saved = OverlayPosition { x: 100.0, y: 200.0 };
+    let center = overlay::restore_center(
+        Some(saved),
+        Some("right"),
+        &monitors,
+        Some(&monitors[0]),
+        (96.0, 96.0),
+    );
+    // Logical offsets are scaled to the monitor's physical pixels
+    assert_eq!(center, Some((1920.0 + 200.0, 400.0)));
+}
+
+#[test]
+fn test_missing_monitor_falls_back_to_primary_on_screen() {
+    let monitors = [monitor("laptop", 0.0, 1.0)];
+    let saved = OverlayPosition {
+        x: 3000.0,
+        y: -50.0,
+    };
+    let center = overlay::restore_center(
+        Some(saved),
+        Some("external"),
+        &monitors,
+        Some(&monitors[0]),
+        (48.0, 48.0),
+    );
+    assert_eq!(center, Some((1920.0 - 24.0, 24.0)));
+    assert_eq!(
+        overlay::restore_center(None, None, &[], None, (48.0, 48.0)),
+        None
+    );
+}
+
+#[test]
+fn test_locate_finds_monitor_and_logical_offset() {
+    let monitors = [monitor("left", 0.0, 1.0), monitor("right", 1920.0, 2.0)];
+    let (name, position) = overlay::locate((1920.0 + 300.0, 500.0), &monitors).unwrap();
+    assert_eq!(name.as_deref(), Some("right"));
+    assert_eq!(position, OverlayPosition { x: 150.0, y: 250.0 });
+
+    // Dragged partly off screen, the nearest monitor is used
+    let (name, _) = overlay::locate((-20.0, 100.0), &monitors).unwrap();
+    assert_eq!(name.as_deref(), Some("left"));
+}
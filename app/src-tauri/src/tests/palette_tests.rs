@@ -0,0 +1,91 @@
+use crate::palette::{filter_items, one_line_title, PaletteAction, PaletteItem};
+
+fn item(title: &str, detail: &str, action: PaletteAction) -> PaletteItem {
+    PaletteItem {
+        action,
+        title: title.to_string(),
+        detail: detail.to_string(),
+        active: false,
+    }
+}
+
+fn items() -> Vec<PaletteItem> {
+    vec![
+        item(
+            "Meeting moved to Thursday",
+            "Paste again · Slack",
+            PaletteAction::PasteHistory { index: 0 },
+        ),
+        item(
+            "Turn LLM formatting off",
+            "Formatting",
+            PaletteAction::ToggleFormatting,
+        ),
+        item(
+            "Email",
+            "Prompt preset",
+            PaletteAction::ApplyPromptPreset {
+                id: "1".to_string(),
+            },
+        ),
+        item(
+            "Deepgram",
+            "STT provider",
+            PaletteAction::SwitchSttProvider {
+                value: "deepgram".to_string(),
+            },
+        ),
+    ]
+}
+
+fn titles(items: &[PaletteItem]) -> Vec<&str> {
+    items.iter().map(|item| item.title.as_str()).collect()
+}
+
+#[test]
+fn test_empty_query_lists_everything() {
+    assert_eq!(filter_items(&items(), "  "), items());
+}
+
+#[test]
+fn test_every_query_word_must_match_title_or_detail() {
+    assert_eq!(
+        titles(&filter_items(&items(), "slack THURSDAY")),
+        vec!["Meeting moved to Thursday"]
+    );
+    assert_eq!(
+        titles(&filter_items(&items(), "provider deep")),
+        vec!["Deepgram"]
+    );
+    assert!(filter_items(&items(), "slack deepgram").is_empty());
+}
+
+#[test]
+fn test_title_prefix_matches_come_first() {
+    // "Email" is listed after the dictation but its title starts with "e"
+    assert_eq!(
+        titles(&filter_items(&items(), "e")),
+        vec!["Email", "Meeting moved to Thursday", "Deepgram"]
+    );
+}
+
+#[test]
+fn test_titles_are_one_line_and_shortened() {
+    assert_eq!(
+        one_line_title("Dear Sam,\n\n  Thanks", 80),
+        "Dear Sam, Thanks"
+    );
+    assert_eq!(one_line_title("abcdef", 6), "abcdef");
+    assert_eq!(one_line_title("abcd efgh", 6), "abcd…");
+}
+
+#[test]
+fn test_actions_are_tagged_by_kind() {
+    let action: PaletteAction =
+        serde_json::from_str(r#"{ "kind": "paste_history", "index": 2 }"#).unwrap();
+    assert_eq!(action, PaletteAction::PasteHistory { index: 2 });
+    assert_eq!(
+        serde_json::to_value(PaletteAction::ToggleFormatting).unwrap(),
+        serde_json::json!({ "kind": "toggle_formatting" })
+    );
+}
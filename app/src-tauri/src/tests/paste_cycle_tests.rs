@@ -0,0 +1,61 @@
+use crate::paste_cycle::{CycleStep, PasteCycle};
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(5);
+
+#[test]
+fn test_first_press_pastes_most_recent() {
+    let cycle = PasteCycle::default();
+    assert_eq!(
+        cycle.next_step(Instant::now(), WINDOW),
+        CycleStep {
+            index: 0,
+            replace_chars: 0
+        }
+    );
+}
+
+#[test]
+fn test_press_after_paste_replaces_with_previous_entry() {
+    let cycle = PasteCycle::default();
+    let start = Instant::now();
+    cycle.record_paste(0, 12, start);
+
+    assert_eq!(
+        cycle.next_step(start + Duration::from_secs(1), WINDOW),
+        CycleStep {
+            index: 1,
+            replace_chars: 12
+        }
+    );
+
+    cycle.record_paste(1, 7, start + Duration::from_secs(1));
+    assert_eq!(
+        cycle.next_step(start + Duration::from_secs(2), WINDOW),
+        CycleStep {
+            index: 2,
+            replace_chars: 7
+        }
+    );
+}
+
+#[test]
+fn test_press_after_window_starts_over() {
+    let cycle = PasteCycle::default();
+    let start = Instant::now();
+    cycle.record_paste(3, 20, start);
+
+    let step = cycle.next_step(start + WINDOW + Duration::from_millis(1), WINDOW);
+    assert_eq!(step.index, 0);
+    assert_eq!(step.replace_chars, 0);
+}
+
+#[test]
+fn test_reset_starts_over() {
+    let cycle = PasteCycle::default();
+    let start = Instant::now();
+    cycle.record_paste(0, 5, start);
+    cycle.reset();
+
+    assert_eq!(cycle.next_step(start, WINDOW).index, 0);
+}
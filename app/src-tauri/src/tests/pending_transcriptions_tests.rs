@@ -0,0 +1,35 @@
+use crate::pending_transcriptions::PendingTranscriptions;
+use crate::stt::AudioFormat;
+
+fn temp_queue() -> (PendingTranscriptions, std::path::PathBuf) {
+    let dir = std::env::temp_dir().join(format!("pending-{}", uuid::Uuid::new_v4()));
+    (PendingTranscriptions::new(&dir), dir)
+}
+
+#[test]
+fn test_queue_lists_oldest_first_and_removes() {
+    let (queue, dir) = temp_queue();
+    assert!(queue.list().unwrap().is_empty());
+
+    let first = queue.queue(AudioFormat::WHISPER, &[0.5; 16]).unwrap();
+    let second = queue.queue(AudioFormat::WHISPER, &[0.5; 32]).unwrap();
+    assert_eq!(queue.list().unwrap(), vec![first.clone(), second.clone()]);
+
+    let (format, samples) = queue.load(&first.id).unwrap();
+    assert_eq!(format, AudioFormat::WHISPER);
+    assert_eq!(samples.len(), 16);
+
+    assert!(queue.remove(&first.id).unwrap());
+    assert_eq!(queue.list().unwrap(), vec![second]);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_only_one_retry_runs_at_a_time() {
+    let (queue, _) = temp_queue();
+    assert!(queue.begin_retry());
+    assert!(!queue.begin_retry());
+    queue.end_retry();
+    assert!(queue.begin_retry());
+}
@@ -0,0 +1,51 @@
+use crate::pre_roll::{PreRollBuffer, MAX_PRE_ROLL};
+use crate::stt::AudioFormat;
+use std::time::Duration;
+
+const FORMAT: AudioFormat = AudioFormat {
+    sample_rate: 1_000,
+    channels: 1,
+};
+
+fn ramp(len: usize) -> Vec<f32> {
+    (0..len).map(|i| i as f32).collect()
+}
+
+#[test]
+fn test_take_returns_newest_audio() {
+    let mut buffer = PreRollBuffer::new(FORMAT);
+    buffer.push(&ramp(1000));
+    let taken = buffer.take(Duration::from_millis(250));
+    assert_eq!(taken.len(), 250);
+    assert_eq!(taken.first(), Some(&750.0));
+    assert_eq!(taken.last(), Some(&999.0));
+}
+
+#[test]
+fn test_take_clears_buffer() {
+    let mut buffer = PreRollBuffer::new(FORMAT);
+    buffer.push(&ramp(100));
+    assert_eq!(buffer.take(Duration::from_millis(50)).len(), 50);
+    assert!(buffer.take(Duration::from_millis(50)).is_empty());
+}
+
+#[test]
+fn test_buffer_is_capped_at_max_pre_roll() {
+    let mut buffer = PreRollBuffer::new(FORMAT);
+    for _ in 0..5 {
+        buffer.push(&ramp(1000));
+    }
+    let taken = buffer.take(Duration::from_secs(10));
+    assert_eq!(taken.len() as u128, MAX_PRE_ROLL.as_millis());
+}
+
+#[test]
+fn test_take_keeps_stereo_frames_whole() {
+    let mut buffer = PreRollBuffer::new(AudioFormat {
+        sample_rate: 1_000,
+        channels: 2,
+    });
+    // Five samples: two whole frames and half of a third
+    buffer.push(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(buffer.take(Duration::from_millis(1)), vec![3.0, 4.0]);
+}
@@ -0,0 +1,142 @@
+use crate::prompt_presets::{
+    active_preset, merge_presets, next_preset, parse_preset_file, render_preset_file,
+    validate_preset_name, PromptPreset, PRESET_FILE_VERSION,
+};
+use crate::settings::{CleanupPromptSections, PromptSection};
+
+fn custom(content: &str) -> PromptSection {
+    PromptSection {
+        enabled: true,
+        content: Some(content.to_string()),
+    }
+}
+
+fn preset(id: &str, name: &str, main: &str) -> PromptPreset {
+    PromptPreset {
+        id: id.to_string(),
+        name: name.to_string(),
+        main: custom(main),
+        advanced: PromptSection::default(),
+    }
+}
+
+#[test]
+fn test_applying_a_preset_keeps_the_dictionary() {
+    let mut sections = CleanupPromptSections::server_defaults();
+    sections.dictionary = custom("Tambourine");
+    let email = preset("1", "Email", "Write like an email");
+
+    let applied = email.apply_to(sections);
+    assert_eq!(applied.main, custom("Write like an email"));
+    assert_eq!(applied.advanced, PromptSection::default());
+    assert_eq!(applied.dictionary, custom("Tambourine"));
+    assert!(email.matches(&applied));
+}
+
+#[test]
+fn test_active_preset_matches_the_current_prompt() {
+    let presets = vec![
+        preset("1", "Email", "Write like an email"),
+        preset("2", "Chat", "Keep it casual"),
+    ];
+    let chat = presets[1].apply_to(CleanupPromptSections::server_defaults());
+    assert_eq!(
+        active_preset(&presets, &chat).map(|p| p.name.as_str()),
+        Some("Chat")
+    );
+
+    // Editing the prompt by hand leaves no preset active
+    let mut edited = chat;
+    edited.main = custom("Keep it casual, with emoji");
+    assert_eq!(active_preset(&presets, &edited), None);
+}
+
+#[test]
+fn test_next_preset_wraps_around() {
+    let presets = vec![
+        preset("1", "Email", "a"),
+        preset("2", "Chat", "b"),
+        preset("3", "Code", "c"),
+    ];
+    let next = |current| next_preset(current, &presets).map(|p| p.id.as_str());
+    assert_eq!(next(Some("1")), Some("2"));
+    assert_eq!(next(Some("3")), Some("1"));
+    assert_eq!(next(None), Some("1"));
+    assert_eq!(next(Some("deleted")), Some("1"));
+    assert_eq!(next_preset(None, &[]), None);
+}
+
+#[test]
+fn test_preset_names_are_trimmed_and_unique() {
+    let presets = vec![preset("1", "Email", "a")];
+    assert_eq!(
+        validate_preset_name("  Chat ", &presets, None),
+        Ok("Chat".to_string())
+    );
+    assert!(validate_preset_name("email", &presets, None).is_err());
+    assert!(validate_preset_name("   ", &presets, None).is_err());
+    assert!(validate_preset_name(&"x".repeat(61), &presets, None).is_err());
+    // Renaming a preset may keep its own name
+    assert_eq!(
+        validate_preset_name("EMAIL", &presets, Some("1")),
+        Ok("EMAIL".to_string())
+    );
+}
+
+#[test]
+fn test_preset_files_round_trip() {
+    let presets = vec![preset("1", "Email", "a"), preset("2", "Chat", "b")];
+    let file = render_preset_file(&presets).unwrap();
+    assert_eq!(parse_preset_file(&file).unwrap(), presets);
+}
+
+#[test]
+fn test_preset_files_may_leave_out_ids() {
+    let file = r#"{
+        "version": 1,
+        "presets": [
+            {
+                "name": " Code comments ",
+                "main": { "enabled": true, "content": "Terse" },
+                "advanced": { "enabled": false, "content": null }
+            }
+        ]
+    }"#;
+    let presets = parse_preset_file(file).unwrap();
+    assert_eq!(presets.len(), 1);
+    assert_eq!(presets[0].name, "Code comments");
+    assert_eq!(presets[0].id, "");
+}
+
+#[test]
+fn test_unreadable_preset_files_are_refused() {
+    assert!(parse_preset_file("not json").is_err());
+    assert!(parse_preset_file(&format!(
+        r#"{{ "version": {}, "presets": [] }}"#,
+        PRESET_FILE_VERSION + 1
+    ))
+    .is_err());
+    assert!(parse_preset_file(
+        r#"{ "version": 1, "presets": [{
+            "name": " ",
+            "main": { "enabled": true, "content": null },
+            "advanced": { "enabled": true, "content": null }
+        }] }"#
+    )
+    .is_err());
+}
+
+#[test]
+fn test_import_replaces_presets_with_the_same_name() {
+    let mut presets = vec![preset("1", "Email", "old")];
+    let imported = vec![preset("", "EMAIL", "new"), preset("", "Chat", "b")];
+
+    let import = merge_presets(&mut presets, imported);
+    assert_eq!(import.replaced, vec!["Email".to_string()]);
+    assert_eq!(import.added, vec!["Chat".to_string()]);
+    assert_eq!(presets.len(), 2);
+    assert_eq!(presets[0].id, "1");
+    assert_eq!(presets[0].name, "Email");
+    assert_eq!(presets[0].main, custom("new"));
+    assert!(!presets[1].id.is_empty());
+}
@@ -0,0 +1,27 @@
+use crate::recording_limit::RecordingLimit;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_expiry_reports_the_armed_recording() {
+    let limit = RecordingLimit::default();
+    limit.arm(1, Duration::from_millis(20));
+    limit.arm(2, Duration::from_millis(20));
+    assert_eq!(limit.wait_for_expiry(), Some(2));
+}
+
+#[test]
+fn test_disarming_wakes_the_watcher_for_the_next_recording() {
+    let limit = Arc::new(RecordingLimit::default());
+    limit.arm(1, Duration::from_secs(600));
+    let watcher = {
+        let limit = Arc::clone(&limit);
+        std::thread::spawn(move || limit.wait_for_expiry())
+    };
+
+    let started = Instant::now();
+    limit.disarm();
+    limit.arm(2, Duration::from_millis(20));
+    assert_eq!(watcher.join().unwrap(), Some(2));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
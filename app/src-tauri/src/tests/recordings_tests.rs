@@ -0,0 +1,133 @@
+use crate::recordings::{
+    decode_wav, encode_wav, expired_recordings, RecordingArchive, RecordingInfo, RetentionPolicy,
+};
+use crate::stt::AudioFormat;
+use chrono::{DateTime, Duration, Utc};
+
+fn recording(timestamp: DateTime<Utc>) -> RecordingInfo {
+    RecordingInfo {
+        id: timestamp.timestamp_millis().to_string(),
+        timestamp,
+        duration_ms: 1000,
+        size_bytes: 32_044,
+    }
+}
+
+fn temp_archive() -> (RecordingArchive, std::path::PathBuf) {
+    let dir = std::env::temp_dir().join(format!("recordings-{}", uuid::Uuid::new_v4()));
+    (RecordingArchive::new(&dir), dir)
+}
+
+const KEEP_ALL: RetentionPolicy = RetentionPolicy {
+    max_count: 0,
+    max_days: 0,
+};
+
+#[test]
+fn test_encode_wav_writes_pcm_header_and_samples() {
+    let wav = encode_wav(AudioFormat::WHISPER, &[0.0, 1.0, -1.0, 2.0]);
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(&wav[8..16], b"WAVEfmt ");
+    assert_eq!(&wav[36..40], b"data");
+    assert_eq!(wav.len(), 44 + 8);
+    assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+    assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+
+    let samples: Vec<i16> = wav[44..]
+        .chunks(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    // Out of range samples are clipped
+    assert_eq!(samples, vec![0, i16::MAX, -i16::MAX, i16::MAX]);
+}
+
+#[test]
+fn test_decode_wav_reads_back_encoded_audio() {
+    let wav = encode_wav(AudioFormat::WHISPER, &[0.0, 1.0, -1.0]);
+    assert_eq!(
+        decode_wav(&wav).unwrap(),
+        (AudioFormat::WHISPER, vec![0, i16::MAX, -i16::MAX])
+    );
+    assert!(decode_wav(b"not a wav").is_err());
+}
+
+#[test]
+fn test_expired_recordings_by_count() {
+    let now = Utc::now();
+    let recordings: Vec<_> = (0..4)
+        .map(|i| recording(now - Duration::minutes(i)))
+        .collect();
+    let policy = RetentionPolicy {
+        max_count: 2,
+        max_days: 0,
+    };
+    assert_eq!(
+        expired_recordings(&recordings, policy, now),
+        vec![recordings[2].id.clone(), recordings[3].id.clone()]
+    );
+    assert!(expired_recordings(&recordings, KEEP_ALL, now).is_empty());
+}
+
+#[test]
+fn test_expired_recordings_by_age() {
+    let now = Utc::now();
+    let recent = recording(now - Duration::days(2));
+    let old = recording(now - Duration::days(8));
+    let policy = RetentionPolicy {
+        max_count: 0,
+        max_days: 7,
+    };
+    assert_eq!(
+        expired_recordings(&[recent, old.clone()], policy, now),
+        vec![old.id]
+    );
+}
+
+#[test]
+fn test_archive_saves_lists_and_deletes() {
+    let (archive, dir) = temp_archive();
+    assert!(archive.list().unwrap().is_empty());
+
+    let saved = archive
+        .save(AudioFormat::WHISPER, &[0.1; 8000], KEEP_ALL)
+        .unwrap();
+    assert_eq!(saved.duration_ms, 500);
+    let second = archive
+        .save(AudioFormat::WHISPER, &[0.1; 16], KEEP_ALL)
+        .unwrap();
+    assert_ne!(saved.id, second.id);
+
+    let listed = archive.list().unwrap();
+    assert_eq!(listed, vec![second.clone(), saved.clone()]);
+
+    assert!(archive.delete(&saved.id).unwrap());
+    assert!(!archive.delete(&saved.id).unwrap());
+    assert_eq!(archive.list().unwrap(), vec![second]);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_archive_applies_retention_on_save() {
+    let (archive, dir) = temp_archive();
+    let policy = RetentionPolicy {
+        max_count: 1,
+        max_days: 0,
+    };
+    archive
+        .save(AudioFormat::WHISPER, &[0.1; 16], policy)
+        .unwrap();
+    let newest = archive
+        .save(AudioFormat::WHISPER, &[0.1; 16], policy)
+        .unwrap();
+    assert_eq!(archive.list().unwrap(), vec![newest]);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_delete_rejects_paths() {
+    let (archive, _) = temp_archive();
+    assert!(archive.delete("../history").is_err());
+    assert!(archive.delete("").is_err());
+}
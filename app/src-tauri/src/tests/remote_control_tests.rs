@@ -0,0 +1,91 @@
+use crate::remote_control::{
+    authorize, tokens_match, ControlResponse, RemoteCommand, RemoteStatus,
+};
+use serde_json::json;
+
+const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+fn idle() -> RemoteStatus {
+    RemoteStatus {
+        recording: false,
+        paused: false,
+        connected: true,
+    }
+}
+
+#[test]
+fn test_authorize_accepts_commands_with_the_token() {
+    let request = authorize(
+        &json!({"id": 7, "token": TOKEN, "command": "toggle"}).to_string(),
+        TOKEN,
+    )
+    .unwrap();
+    assert_eq!(request.command, RemoteCommand::Toggle);
+    assert_eq!(request.id, json!(7));
+
+    // The id is optional
+    let request = authorize(
+        &json!({"token": TOKEN, "command": "status"}).to_string(),
+        TOKEN,
+    )
+    .unwrap();
+    assert_eq!(request.command, RemoteCommand::Status);
+}
+
+#[test]
+fn test_authorize_refuses_a_wrong_or_missing_token() {
+    let wrong = authorize(
+        &json!({"id": "a", "token": "guess", "command": "start"}).to_string(),
+        TOKEN,
+    );
+    assert_eq!(
+        wrong,
+        Err(ControlResponse::failure(json!("a"), "Invalid token"))
+    );
+    assert!(authorize(&json!({"command": "stop"}).to_string(), TOKEN).is_err());
+}
+
+#[test]
+fn test_authorize_reports_invalid_requests_with_their_id() {
+    let Err(ControlResponse::Failure { id, error, .. }) = authorize(
+        &json!({"id": 3, "token": TOKEN, "command": "explode"}).to_string(),
+        TOKEN,
+    ) else {
+        panic!("expected a failure");
+    };
+    assert_eq!(id, json!(3));
+    assert!(error.starts_with("Invalid request"));
+    assert!(authorize("not json", TOKEN).is_err());
+}
+
+#[test]
+fn test_tokens_match() {
+    assert!(tokens_match(TOKEN, TOKEN));
+    assert!(!tokens_match(&TOKEN[1..], TOKEN));
+    assert!(!tokens_match("0123456789abcdef0123456789abcdee", TOKEN));
+    // No token set never matches
+    assert!(!tokens_match("", ""));
+}
+
+#[test]
+fn test_responses_serialize_for_clients() {
+    assert_eq!(
+        serde_json::to_value(ControlResponse::success(json!(1), idle())).unwrap(),
+        json!({
+            "id": 1,
+            "ok": true,
+            "status": {"recording": false, "paused": false, "connected": true}
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(ControlResponse::failure(json!(null), "Invalid token")).unwrap(),
+        json!({"id": null, "ok": false, "error": "Invalid token"})
+    );
+    assert_eq!(
+        serde_json::to_value(ControlResponse::status_event(idle())).unwrap(),
+        json!({
+            "event": "status",
+            "status": {"recording": false, "paused": false, "connected": true}
+        })
+    );
+}
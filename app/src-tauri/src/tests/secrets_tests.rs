@@ -0,0 +1,19 @@
+use crate::secrets::{delete_api_key, set_api_key};
+
+#[test]
+fn test_set_api_key_rejects_invalid_provider() {
+    assert!(set_api_key("", "sk-test").is_err());
+    assert!(set_api_key("open ai", "sk-test").is_err());
+    assert!(set_api_key("../openai", "sk-test").is_err());
+}
+
+#[test]
+fn test_set_api_key_rejects_empty_key() {
+    let result = set_api_key("openai", "   ");
+    assert_eq!(result, Err("API key cannot be empty".to_string()));
+}
+
+#[test]
+fn test_delete_api_key_rejects_invalid_provider() {
+    assert!(delete_api_key("bad/provider").is_err());
+}
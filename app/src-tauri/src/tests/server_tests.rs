@@ -0,0 +1,112 @@
+use crate::server::{self, ServerCapabilities, TlsOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+const FINGERPRINT: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+/// Serve `requests` HTTP requests, answering `/health` and optionally
+/// `/api/capabilities`. Returns the server's URL.
+fn serve(requests: usize, capabilities: Option<&'static str>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(requests) {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(&stream);
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).unwrap() == 0 || header == "\r\n" {
+                    break;
+                }
+            }
+            let (status, body) = match (request_line.split(' ').nth(1), capabilities) {
+                (Some("/health"), _) => ("200 OK", r#"{"status": "ok"}"#),
+                (Some("/api/capabilities"), Some(body)) => ("200 OK", body),
+                _ => ("404 Not Found", r#"{"detail": "Not Found"}"#),
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+        }
+    });
+    url
+}
+
+#[test]
+fn test_fingerprints_accept_colons_and_any_case() {
+    let colons = FINGERPRINT
+        .to_uppercase()
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| std::str::from_utf8(pair).unwrap())
+        .collect::<Vec<_>>()
+        .join(":");
+    assert_eq!(server::normalize_fingerprint(&colons).unwrap(), FINGERPRINT);
+    assert!(server::normalize_fingerprint("abc123").is_err());
+    assert!(server::normalize_fingerprint(&FINGERPRINT.replace('a', "z")).is_err());
+}
+
+#[test]
+fn test_certificate_fingerprint_is_sha256_hex() {
+    assert_eq!(server::certificate_fingerprint(b"test"), FINGERPRINT);
+}
+
+#[test]
+fn test_blank_pin_is_ignored() {
+    let options = TlsOptions::new(true, Some("  ")).unwrap();
+    assert_eq!(options.pinned_sha256, None);
+    assert!(TlsOptions::new(false, Some("not a fingerprint")).is_err());
+}
+
+#[test]
+fn test_pin_must_match_certificate() {
+    let options = TlsOptions::new(false, Some(FINGERPRINT)).unwrap();
+    assert!(server::check_pin(&options, Some(FINGERPRINT)).is_ok());
+    assert!(server::check_pin(&options, Some(&"0".repeat(64))).is_err());
+    // Plain HTTP can't satisfy a pin
+    assert!(server::check_pin(&options, None).is_err());
+    assert!(server::check_pin(&TlsOptions::default(), None).is_ok());
+}
+
+#[test]
+fn test_connection_reports_capabilities() {
+    let url = serve(
+        2,
+        Some(r#"{"stt_providers": ["deepgram"], "llm_providers": ["openai", "ollama"]}"#),
+    );
+    let result = server::test_connection(&format!("{}/", url), &TlsOptions::default(), None);
+    assert!(result.reachable, "{:?}", result.error);
+    assert!(result.latency_ms.is_some());
+    assert_eq!(result.certificate_sha256, None);
+    assert_eq!(
+        result.capabilities,
+        Some(ServerCapabilities {
+            stt_providers: vec!["deepgram".to_string()],
+            llm_providers: vec!["openai".to_string(), "ollama".to_string()],
+        })
+    );
+}
+
+#[test]
+fn test_connection_to_older_server_has_no_capabilities() {
+    let url = serve(2, None);
+    let result = server::test_connection(&url, &TlsOptions::default(), None);
+    assert!(result.reachable);
+    assert_eq!(result.capabilities, None);
+}
+
+#[test]
+fn test_connection_to_closed_port_is_unreachable() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let result = server::test_connection(&url, &TlsOptions::default(), None);
+    assert!(!result.reachable);
+    assert!(result.error.is_some());
+}
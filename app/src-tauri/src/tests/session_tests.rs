@@ -0,0 +1,22 @@
+use crate::session::SessionStore;
+
+#[test]
+fn test_session_transcript_joins_entries_as_paragraphs() {
+    let session = SessionStore::default();
+    session.append("First thought.".to_string()).unwrap();
+    session.append("Second thought.".to_string()).unwrap();
+
+    let transcript = session.snapshot().unwrap();
+    assert_eq!(transcript.entries.len(), 2);
+    assert_eq!(transcript.to_text(), "First thought.\n\nSecond thought.");
+}
+
+#[test]
+fn test_session_reset_clears_entries() {
+    let session = SessionStore::default();
+    session.append("Something".to_string()).unwrap();
+
+    let reset = session.reset().unwrap();
+    assert!(reset.entries.is_empty());
+    assert!(session.snapshot().unwrap().entries.is_empty());
+}
@@ -0,0 +1,74 @@
+use crate::settings_broadcast::{diff_settings, SettingChange, SettingsChangedPayload};
+use serde_json::{json, Map, Value};
+
+fn settings(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => panic!("settings must be an object"),
+    }
+}
+
+#[test]
+fn test_diff_lists_changed_added_and_removed_keys_in_order() {
+    let old = settings(json!({
+        "sound_enabled": true,
+        "server_url": "http://127.0.0.1:8765",
+        "selected_mic_id": "usb",
+    }));
+    let new = settings(json!({
+        "sound_enabled": false,
+        "server_url": "http://127.0.0.1:8765",
+        "auto_mute_audio": true,
+    }));
+
+    assert_eq!(
+        diff_settings(&old, &new),
+        vec![
+            SettingChange {
+                key: "auto_mute_audio".to_string(),
+                old_value: Value::Null,
+                new_value: json!(true),
+            },
+            SettingChange {
+                key: "selected_mic_id".to_string(),
+                old_value: json!("usb"),
+                new_value: Value::Null,
+            },
+            SettingChange {
+                key: "sound_enabled".to_string(),
+                old_value: json!(true),
+                new_value: json!(false),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_compares_nested_values() {
+    let old = settings(json!({ "toggle_hotkey": { "modifiers": ["ctrl"], "key": "Space" } }));
+    let same = old.clone();
+    assert!(diff_settings(&old, &same).is_empty());
+
+    let new = settings(json!({ "toggle_hotkey": { "modifiers": ["alt"], "key": "Space" } }));
+    let changes = diff_settings(&old, &new);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].key, "toggle_hotkey");
+}
+
+#[test]
+fn test_payload_serializes_changes() {
+    let payload = SettingsChangedPayload {
+        changes: diff_settings(
+            &settings(json!({ "overlay_scale": 1.0 })),
+            &settings(json!({ "overlay_scale": 1.5 })),
+        ),
+    };
+    assert!(payload.change("overlay_scale").is_some());
+    assert!(payload.change("server_url").is_none());
+    assert_eq!(
+        serde_json::to_value(&payload).unwrap(),
+        json!({
+            "changes": [{ "key": "overlay_scale", "old_value": 1.0, "new_value": 1.5 }]
+        })
+    );
+}
@@ -1,9 +1,12 @@
-use crate::settings::HotkeyConfig;
+use crate::settings::{
+    describe_feature_flags, describe_registration_failures, is_flag_enabled, FeatureFlag,
+    FeatureFlags, HotkeyAction, HotkeyConfig, ShortcutRegistrationFailure,
+};
 
 // Tests for HotkeyConfig
 #[test]
 fn test_default_toggle_hotkey() {
-    let hotkey = HotkeyConfig::default_toggle();
+    let hotkey = HotkeyAction::Toggle.default_hotkey();
     assert_eq!(hotkey.key, "Space");
     assert!(hotkey.modifiers.contains(&"ctrl".to_string()));
     assert!(hotkey.modifiers.contains(&"alt".to_string()));
@@ -11,7 +14,7 @@ fn test_default_toggle_hotkey() {
 
 #[test]
 fn test_default_hold_hotkey() {
-    let hotkey = HotkeyConfig::default_hold();
+    let hotkey = HotkeyAction::Hold.default_hotkey();
     assert_eq!(hotkey.key, "Backquote");
     assert!(hotkey.modifiers.contains(&"ctrl".to_string()));
     assert!(hotkey.modifiers.contains(&"alt".to_string()));
@@ -19,7 +22,7 @@ fn test_default_hold_hotkey() {
 
 #[test]
 fn test_default_paste_last_hotkey() {
-    let hotkey = HotkeyConfig::default_paste_last();
+    let hotkey = HotkeyAction::PasteLast.default_hotkey();
     assert_eq!(hotkey.key, "Period");
     assert!(hotkey.modifiers.contains(&"ctrl".to_string()));
     assert!(hotkey.modifiers.contains(&"alt".to_string()));
@@ -30,6 +33,7 @@ fn test_to_shortcut_string() {
     let hotkey = HotkeyConfig {
         modifiers: vec!["Ctrl".to_string(), "Alt".to_string()],
         key: "Space".to_string(),
+        ..Default::default()
     };
     // Modifiers should be lowercased
     let result = hotkey.to_shortcut_string();
@@ -37,3 +41,56 @@ fn test_to_shortcut_string() {
     assert!(result.contains("alt"));
     assert!(result.contains("Space"));
 }
+
+// Tests for shortcut registration failure reporting
+#[test]
+fn test_describe_registration_failures_names_each_shortcut() {
+    let failures = vec![
+        ShortcutRegistrationFailure {
+            action: "toggle".to_string(),
+            shortcut: "ctrl+alt+Space".to_string(),
+            error: "already registered".to_string(),
+        },
+        ShortcutRegistrationFailure {
+            action: "paste_last".to_string(),
+            shortcut: "ctrl+alt+Period".to_string(),
+            error: "already registered".to_string(),
+        },
+    ];
+
+    let message = describe_registration_failures(&failures);
+    assert!(message.contains("another application"));
+    assert!(message.contains("toggle (ctrl+alt+Space): already registered"));
+    assert!(message.contains("paste last (ctrl+alt+Period): already registered"));
+}
+
+// Tests for feature flags
+#[test]
+fn test_feature_flags_default_to_off() {
+    let flags = FeatureFlags::new();
+    for flag in FeatureFlag::ALL {
+        assert!(!is_flag_enabled(&flags, flag));
+    }
+}
+
+#[test]
+fn test_feature_flag_keys_match_serialized_names() {
+    for flag in FeatureFlag::ALL {
+        assert_eq!(serde_json::to_value(flag).unwrap(), flag.key());
+    }
+}
+
+#[test]
+fn test_stored_feature_flags_ignore_unknown_keys() {
+    let flags: FeatureFlags = serde_json::from_value(serde_json::json!({
+        "wake_word": true,
+        "retired_experiment": true,
+    }))
+    .unwrap();
+
+    let states = describe_feature_flags(&flags);
+    assert_eq!(states.len(), FeatureFlag::ALL.len());
+    assert!(states
+        .iter()
+        .all(|state| state.enabled == (state.flag == FeatureFlag::WakeWord)));
+}
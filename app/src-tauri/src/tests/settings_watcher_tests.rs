@@ -0,0 +1,75 @@
+use crate::settings_watcher::{is_outside_edit, settings_hash, RecentSettings};
+use serde_json::{json, Map, Value};
+
+fn stored(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_own_save_is_not_an_outside_edit() {
+    let mut recent = RecentSettings::default();
+    recent.record(&stored(json!({ "sound_enabled": true, "language": "en" })));
+    let file = "{\n  \"language\": \"en\",\n  \"sound_enabled\": true\n}";
+    assert_eq!(is_outside_edit(file, &recent), Some(false));
+}
+
+#[test]
+fn test_save_lagging_behind_the_store_is_not_an_outside_edit() {
+    // A slider moved again before the autosave of the previous value was read
+    let mut recent = RecentSettings::default();
+    recent.record(&stored(json!({ "volume": 0.4 })));
+    recent.record(&stored(json!({ "volume": 0.5 })));
+    recent.record(&stored(json!({ "volume": 0.6 })));
+    assert_eq!(
+        is_outside_edit(r#"{ "volume": 0.5 }"#, &recent),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_hand_edit_is_an_outside_edit() {
+    let mut recent = RecentSettings::default();
+    recent.record(&stored(json!({ "sound_enabled": true })));
+    assert_eq!(
+        is_outside_edit(r#"{ "sound_enabled": false }"#, &recent),
+        Some(true)
+    );
+    assert_eq!(
+        is_outside_edit(r#"{ "sound_enabled": true, "language": "de" }"#, &recent),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_invalid_file_is_ignored() {
+    let recent = RecentSettings::default();
+    assert_eq!(is_outside_edit(r#"{ "sound_enabled": tr"#, &recent), None);
+    assert_eq!(is_outside_edit("[]", &recent), None);
+    assert_eq!(is_outside_edit("", &recent), None);
+}
+
+#[test]
+fn test_settings_hash_ignores_key_order() {
+    let a = stored(json!({ "a": 1, "b": { "x": [1, 2], "y": "z" } }));
+    let b = stored(json!({ "b": { "y": "z", "x": [1, 2] }, "a": 1 }));
+    assert_eq!(settings_hash(&a), settings_hash(&b));
+    assert_ne!(
+        settings_hash(&a),
+        settings_hash(&stored(json!({ "a": 1, "b": { "x": [2, 1], "y": "z" } })))
+    );
+}
+
+#[test]
+fn test_recent_settings_forget_old_states() {
+    let mut recent = RecentSettings::default();
+    let first = stored(json!({ "n": 0 }));
+    recent.record(&first);
+    for n in 1..=32 {
+        recent.record(&stored(json!({ "n": n })));
+    }
+    assert!(!recent.contains(&first));
+    assert!(recent.contains(&stored(json!({ "n": 32 }))));
+}
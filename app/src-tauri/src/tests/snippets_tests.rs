@@ -0,0 +1,81 @@
+use crate::snippets::{expand_snippets, render_template, Snippet, SnippetStore, TemplateValues};
+use chrono::{Local, TimeZone};
+
+fn snippet(trigger: &str, template: &str) -> Snippet {
+    Snippet {
+        id: trigger.to_string(),
+        trigger: trigger.to_string(),
+        template: template.to_string(),
+    }
+}
+
+fn values() -> TemplateValues {
+    TemplateValues {
+        now: Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap(),
+        clipboard: Some("https://example.com".to_string()),
+    }
+}
+
+#[test]
+fn test_whole_dictation_becomes_template() {
+    let snippets = vec![snippet("insert my signature", "Best,\nSam")];
+    assert_eq!(
+        expand_snippets("Insert my signature.", &snippets, &values()),
+        "Best,\nSam"
+    );
+    assert_eq!(
+        expand_snippets("insert my signature", &snippets, &values()),
+        "Best,\nSam"
+    );
+}
+
+#[test]
+fn test_trigger_inside_text_keeps_surrounding_text() {
+    let snippets = vec![
+        snippet("email closer", "Let me know if you have any questions."),
+        snippet("closer", "unused"),
+    ];
+    assert_eq!(
+        expand_snippets("Thanks for the update. Email closer.", &snippets, &values()),
+        "Thanks for the update. Let me know if you have any questions."
+    );
+    assert_eq!(
+        expand_snippets("Call me, closer to noon", &snippets, &values()),
+        "Call me, unused to noon"
+    );
+    // Partial words don't match
+    assert_eq!(
+        expand_snippets("The emails closed early.", &snippets, &values()),
+        "The emails closed early."
+    );
+}
+
+#[test]
+fn test_template_placeholders() {
+    assert_eq!(
+        render_template("Sent {date} at {time}: {clipboard}", &values()),
+        "Sent 2025-03-07 at 09:05: https://example.com"
+    );
+    assert_eq!(
+        render_template("{{date}} {unknown} {", &values()),
+        "{date} {unknown} {"
+    );
+}
+
+#[test]
+fn test_store_rejects_duplicate_and_empty_triggers() {
+    let store = SnippetStore::in_memory().unwrap();
+    let first = store.add("  email   closer ", "Thanks!").unwrap();
+    assert_eq!(first.trigger, "email closer");
+    assert!(store.add("Email closer.", "Other").is_err());
+    assert!(store.add("...", "Nothing").is_err());
+
+    let second = store.add("my address", "1 Main St").unwrap();
+    assert!(store.update(&second.id, "email closer", "x").is_err());
+    let updated = store.update(&first.id, "email closer", "Cheers!").unwrap();
+    assert_eq!(updated.template, "Cheers!");
+
+    assert!(store.remove(&first.id).unwrap());
+    assert!(!store.remove(&first.id).unwrap());
+    assert_eq!(store.list().unwrap(), vec![second]);
+}
@@ -0,0 +1,149 @@
+use crate::sound_theme::{
+    validate_sound_file, EventSound, Sound, SoundSettings, SoundSource, SoundTheme, SoundType,
+};
+use crate::tests::support::TempDir;
+use std::fs;
+use std::path::Path;
+
+/// Smallest WAV file: a RIFF header with an empty data chunk
+fn write_wav(path: &Path) {
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&36u32.to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&[1, 0, 1, 0]);
+    wav.extend_from_slice(&16_000u32.to_le_bytes());
+    wav.extend_from_slice(&32_000u32.to_le_bytes());
+    wav.extend_from_slice(&[2, 0, 16, 0]);
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&0u32.to_le_bytes());
+    fs::write(path, wav).unwrap();
+}
+
+#[test]
+fn test_default_theme_uses_builtins_where_they_exist() {
+    let theme = SoundTheme::default();
+    assert_eq!(
+        theme.resolve(SoundType::RecordingStart),
+        Some(Sound::Builtin(SoundType::RecordingStart))
+    );
+    assert_eq!(theme.resolve(SoundType::Complete), None);
+    assert_eq!(theme.resolve(SoundType::Error), None);
+}
+
+#[test]
+fn test_silent_event_plays_nothing() {
+    let theme = SoundTheme {
+        start: SoundSource::Silent,
+        ..SoundTheme::default()
+    };
+    assert_eq!(theme.resolve(SoundType::RecordingStart), None);
+    assert!(theme.resolve(SoundType::RecordingStop).is_some());
+}
+
+#[test]
+fn test_directory_supplies_files_named_after_events() {
+    let dir = TempDir::new("sound-theme");
+    write_wav(&dir.join("complete.wav"));
+    let theme = SoundTheme {
+        directory: Some(dir.to_string_lossy().into_owned()),
+        ..SoundTheme::default()
+    };
+
+    assert_eq!(
+        theme.resolve(SoundType::Complete),
+        Some(Sound::File(dir.join("complete.wav")))
+    );
+    // No start.wav in the directory
+    assert_eq!(
+        theme.resolve(SoundType::RecordingStart),
+        Some(Sound::Builtin(SoundType::RecordingStart))
+    );
+}
+
+#[test]
+fn test_invalid_file_falls_back_to_builtin() {
+    let dir = TempDir::new("sound-theme");
+    let path = dir.join("stop.wav");
+    fs::write(&path, "not audio").unwrap();
+    let theme = SoundTheme {
+        stop: SoundSource::File(path.to_string_lossy().into_owned()),
+        ..SoundTheme::default()
+    };
+    assert_eq!(
+        theme.resolve(SoundType::RecordingStop),
+        Some(Sound::Builtin(SoundType::RecordingStop))
+    );
+}
+
+#[test]
+fn test_validate_sound_file() {
+    let dir = TempDir::new("sound-theme");
+    let wav = dir.join("ok.wav");
+    write_wav(&wav);
+    assert!(validate_sound_file(&wav).is_ok());
+
+    let mp3 = dir.join("chime.mp3");
+    fs::write(&mp3, b"ID3\x03\x00\x00\x00\x00\x00\x00\x00\x00").unwrap();
+    assert!(validate_sound_file(&mp3).is_err());
+    assert!(validate_sound_file(&dir.join("missing.wav")).is_err());
+    assert!(validate_sound_file(&dir).is_err());
+}
+
+#[test]
+fn test_theme_deserializes_with_missing_fields() {
+    let theme: SoundTheme = serde_json::from_str(
+        r#"{"start": {"source": "silent"}, "complete": {"source": "file", "path": "/a.wav"}}"#,
+    )
+    .unwrap();
+    assert_eq!(theme.start, SoundSource::Silent);
+    assert_eq!(theme.complete, SoundSource::File("/a.wav".to_string()));
+    assert_eq!(theme.stop, SoundSource::Default);
+}
+
+#[test]
+fn test_sound_settings_carry_over_legacy_flag() {
+    let muted = SoundSettings::from_stored(None, Some(false));
+    assert_eq!(muted.volume_for(SoundType::RecordingStart), None);
+    assert_eq!(muted.volume_for(SoundType::Complete), None);
+
+    assert_eq!(
+        SoundSettings::from_stored(None, None),
+        SoundSettings::default()
+    );
+    // Saved settings win over the old flag
+    let saved = SoundSettings {
+        volume: 0.5,
+        ..SoundSettings::default()
+    };
+    assert_eq!(SoundSettings::from_stored(Some(saved), Some(false)), saved);
+}
+
+#[test]
+fn test_event_volume_scales_with_master_volume() {
+    let sounds = SoundSettings {
+        volume: 0.5,
+        stop: EventSound {
+            enabled: true,
+            volume: 0.2,
+        },
+        start: EventSound {
+            enabled: false,
+            volume: 1.0,
+        },
+        error: EventSound {
+            enabled: true,
+            volume: 0.0,
+        },
+        complete: EventSound {
+            enabled: true,
+            volume: 3.0,
+        },
+    };
+    assert_eq!(sounds.volume_for(SoundType::RecordingStop), Some(0.1));
+    assert_eq!(sounds.volume_for(SoundType::RecordingStart), None);
+    assert_eq!(sounds.volume_for(SoundType::Error), None);
+    // Out of range volumes are clamped
+    assert_eq!(sounds.volume_for(SoundType::Complete), Some(0.5));
+}
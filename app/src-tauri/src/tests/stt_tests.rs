@@ -0,0 +1,459 @@
+use crate::stt::models::{WhisperModel, WhisperModelStore};
+use crate::stt::{
+    next_provider, transcribe_audio, AudioFormat, FinishJob, ProviderInfo, SttManager, SttProvider,
+    Transcript,
+};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+/// Provider that returns a fixed result and records the audio it was given
+struct FakeProvider {
+    name: &'static str,
+    result: Result<Option<String>, String>,
+    received: std::sync::Arc<std::sync::Mutex<Vec<f32>>>,
+}
+
+impl FakeProvider {
+    fn new(name: &'static str, result: Result<Option<String>, String>) -> Self {
+        Self {
+            name,
+            result,
+            received: Default::default(),
+        }
+    }
+}
+
+impl SttProvider for FakeProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn start_stream(&mut self, _format: AudioFormat) -> Result<(), String> {
+        self.received.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn send_audio(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.received.lock().unwrap().extend_from_slice(samples);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<Option<String>, String> {
+        self.result.clone()
+    }
+
+    fn partials(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Transcribes slowly: reports when its transcription starts and finishes it when told
+struct SlowProvider {
+    started: mpsc::Sender<()>,
+    release: Arc<Mutex<mpsc::Receiver<()>>>,
+}
+
+impl SttProvider for SlowProvider {
+    fn name(&self) -> &'static str {
+        "slow"
+    }
+
+    fn start_stream(&mut self, _format: AudioFormat) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn send_audio(&mut self, _samples: &[f32]) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<Option<String>, String> {
+        self.finish()()
+    }
+
+    fn finish(&mut self) -> FinishJob {
+        let started = self.started.clone();
+        let release = self.release.clone();
+        Box::new(move || {
+            let _ = started.send(());
+            let _ = release.lock().unwrap().recv();
+            Ok(Some("done".to_string()))
+        })
+    }
+
+    fn partials(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+fn run_turn(manager: &SttManager) -> Result<Option<Transcript>, String> {
+    manager.start_stream(AudioFormat::WHISPER, Duration::ZERO)?;
+    manager.send_audio(&[0.1, 0.2])?;
+    manager.send_audio(&[0.3])?;
+    manager.finalize()
+}
+
+#[test]
+fn test_successful_turn_is_served_by_primary() {
+    let manager = SttManager::new(Box::new(FakeProvider::new(
+        "cloud",
+        Ok(Some("hello".to_string())),
+    )));
+    manager
+        .set_fallback(Some(Box::new(FakeProvider::new(
+            "local",
+            Ok(Some("fallback".to_string())),
+        ))))
+        .unwrap();
+
+    assert_eq!(
+        run_turn(&manager).unwrap(),
+        Some(Transcript {
+            text: "hello".to_string(),
+            provider: "cloud",
+        })
+    );
+}
+
+#[test]
+fn test_failed_turn_is_retried_with_buffered_audio() {
+    let manager = SttManager::new(Box::new(FakeProvider::new(
+        "cloud",
+        Err("timed out".to_string()),
+    )));
+    let fallback = FakeProvider::new("local", Ok(Some("retried".to_string())));
+    let received = fallback.received.clone();
+    manager.set_fallback(Some(Box::new(fallback))).unwrap();
+
+    assert_eq!(
+        run_turn(&manager).unwrap(),
+        Some(Transcript {
+            text: "retried".to_string(),
+            provider: "local",
+        })
+    );
+    assert_eq!(*received.lock().unwrap(), vec![0.1, 0.2, 0.3]);
+}
+
+#[test]
+fn test_empty_transcript_is_retried() {
+    let manager = SttManager::new(Box::new(FakeProvider::new(
+        "local",
+        Ok(Some("  ".to_string())),
+    )));
+    manager
+        .set_fallback(Some(Box::new(FakeProvider::new(
+            "cloud",
+            Ok(Some("retried".to_string())),
+        ))))
+        .unwrap();
+
+    assert_eq!(
+        run_turn(&manager).unwrap().map(|t| t.provider),
+        Some("cloud")
+    );
+}
+
+#[test]
+fn test_failed_fallback_reports_original_error() {
+    let manager = SttManager::new(Box::new(FakeProvider::new(
+        "cloud",
+        Err("timed out".to_string()),
+    )));
+    manager
+        .set_fallback(Some(Box::new(FakeProvider::new(
+            "local",
+            Err("no model".to_string()),
+        ))))
+        .unwrap();
+
+    assert_eq!(run_turn(&manager), Err("timed out".to_string()));
+}
+
+#[test]
+fn test_no_retry_without_fallback() {
+    let manager = SttManager::new(Box::new(FakeProvider::new(
+        "cloud",
+        Err("timed out".to_string()),
+    )));
+    assert_eq!(run_turn(&manager), Err("timed out".to_string()));
+}
+
+#[test]
+fn test_pre_roll_is_sent_before_turn_audio() {
+    let provider = FakeProvider::new("local", Ok(Some("hello".to_string())));
+    let received = provider.received.clone();
+    let manager = SttManager::new(Box::new(provider));
+
+    // 16 samples is 1 ms of Whisper-format audio
+    manager.send_audio(&[0.5; 32]).unwrap();
+    manager
+        .start_stream(AudioFormat::WHISPER, Duration::from_millis(1))
+        .unwrap();
+    manager.send_audio(&[0.1]).unwrap();
+
+    let mut expected = vec![0.5; 16];
+    expected.push(0.1);
+    assert_eq!(*received.lock().unwrap(), expected);
+}
+
+#[test]
+fn test_audio_between_turns_is_not_transcribed() {
+    let provider = FakeProvider::new("local", Ok(Some("hello".to_string())));
+    let received = provider.received.clone();
+    let manager = SttManager::new(Box::new(provider));
+
+    run_turn(&manager).unwrap();
+    manager.send_audio(&[0.9; 8]).unwrap();
+    assert_eq!(*received.lock().unwrap(), vec![0.1, 0.2, 0.3]);
+}
+
+#[test]
+fn test_archived_turn_audio_is_kept_after_finalize() {
+    let manager = SttManager::new(Box::new(FakeProvider::new(
+        "cloud",
+        Ok(Some("hello".to_string())),
+    )));
+    assert_eq!(manager.take_finished_audio(), None);
+    run_turn(&manager).unwrap();
+    assert_eq!(manager.take_finished_audio(), None);
+
+    manager.set_archive_audio(true);
+    run_turn(&manager).unwrap();
+    assert_eq!(
+        manager.take_finished_audio(),
+        Some((AudioFormat::WHISPER, vec![0.1, 0.2, 0.3]))
+    );
+    assert_eq!(manager.take_finished_audio(), None);
+}
+
+#[test]
+fn test_last_turn_audio_is_kept_for_retry() {
+    let manager = SttManager::new(Box::new(FakeProvider::new("server", Ok(None))));
+    run_turn(&manager).unwrap();
+    assert_eq!(manager.last_audio(), None);
+
+    manager.set_keep_last_audio(true);
+    assert!(manager.captures_in_app());
+    run_turn(&manager).unwrap();
+    // Retrying doesn't use the audio up
+    for _ in 0..2 {
+        assert_eq!(
+            manager.last_audio(),
+            Some((AudioFormat::WHISPER, vec![0.1, 0.2, 0.3]))
+        );
+    }
+
+    manager.set_keep_last_audio(false);
+    assert_eq!(manager.last_audio(), None);
+}
+
+#[test]
+fn test_transcribe_audio_sends_all_samples() {
+    let mut provider = FakeProvider::new("local", Ok(Some("again".to_string())));
+    let received = provider.received.clone();
+    let transcript = transcribe_audio(&mut provider, AudioFormat::WHISPER, &[0.1, 0.2]);
+    assert_eq!(
+        transcript,
+        Ok(Transcript {
+            text: "again".to_string(),
+            provider: "local",
+        })
+    );
+    assert_eq!(*received.lock().unwrap(), vec![0.1, 0.2]);
+
+    let mut provider = FakeProvider::new("local", Ok(Some("  ".to_string())));
+    assert!(transcribe_audio(&mut provider, AudioFormat::WHISPER, &[0.1]).is_err());
+}
+
+#[test]
+fn test_audio_duration_ms() {
+    assert_eq!(AudioFormat::WHISPER.duration_ms(8_000), 500);
+    let stereo = AudioFormat {
+        sample_rate: 48_000,
+        channels: 2,
+    };
+    assert_eq!(stereo.duration_ms(96_000), 1_000);
+}
+
+#[test]
+fn test_offline_turn_is_kept_when_nothing_transcribes_it() {
+    // The server provider delivers asynchronously, which can't happen offline
+    let manager = SttManager::new(Box::new(FakeProvider::new("server", Ok(None))));
+    run_turn(&manager).unwrap();
+    assert_eq!(manager.take_unsent_audio(), None);
+
+    manager.set_offline(true);
+    assert_eq!(run_turn(&manager), Ok(None));
+    assert_eq!(
+        manager.take_unsent_audio(),
+        Some((AudioFormat::WHISPER, vec![0.1, 0.2, 0.3]))
+    );
+
+    // Only the turn that was started offline is kept
+    run_turn(&manager).unwrap();
+    assert_eq!(manager.take_unsent_audio(), None);
+}
+
+#[test]
+fn test_offline_turn_is_transcribed_by_fallback() {
+    let manager = SttManager::new(Box::new(FakeProvider::new("server", Ok(None))));
+    manager
+        .set_fallback(Some(Box::new(FakeProvider::new(
+            "local",
+            Ok(Some("offline".to_string())),
+        ))))
+        .unwrap();
+    manager.set_offline(true);
+
+    assert_eq!(
+        run_turn(&manager).unwrap().map(|t| t.provider),
+        Some("local")
+    );
+    assert_eq!(manager.take_unsent_audio(), None);
+}
+
+#[test]
+fn test_cancelled_turn_keeps_no_audio() {
+    let manager = SttManager::new(Box::new(FakeProvider::new("server", Ok(None))));
+    manager.set_archive_audio(true);
+    manager.set_offline(true);
+    manager
+        .start_stream(AudioFormat::WHISPER, Duration::ZERO)
+        .unwrap();
+    manager.send_audio(&[0.1, 0.2]).unwrap();
+    manager.cancel().unwrap();
+
+    assert_eq!(manager.take_unsent_audio(), None);
+    assert_eq!(manager.take_finished_audio(), None);
+
+    // The next turn starts fresh and online
+    assert_eq!(run_turn(&manager), Ok(None));
+    assert_eq!(manager.take_unsent_audio(), None);
+    assert_eq!(
+        manager.take_finished_audio(),
+        Some((AudioFormat::WHISPER, vec![0.1, 0.2, 0.3]))
+    );
+}
+
+#[test]
+fn test_whisper_model_names() {
+    assert_eq!(
+        serde_json::to_value(WhisperModel::LargeV3Turbo).unwrap(),
+        "large-v3-turbo"
+    );
+    assert_eq!(
+        serde_json::from_value::<WhisperModel>("base".into()).unwrap(),
+        WhisperModel::Base
+    );
+    assert_eq!(WhisperModel::Tiny.file_name(), "ggml-tiny.bin");
+    assert!(WhisperModel::Small
+        .download_url()
+        .ends_with("/ggml-small.bin"));
+}
+
+#[test]
+fn test_model_store_lists_and_deletes_downloaded_models() {
+    let dir = std::env::temp_dir().join(format!("whisper-models-{}", uuid::Uuid::new_v4()));
+    let models = WhisperModelStore::new(&dir);
+    assert!(models.list().iter().all(|status| !status.downloaded));
+    assert_eq!(models.downloaded_path(WhisperModel::Base), None);
+
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("ggml-base.bin"), b"model").unwrap();
+    // An unfinished download is not a usable model
+    std::fs::write(dir.join("ggml-tiny.bin.part"), b"mod").unwrap();
+
+    let downloaded: Vec<_> = models
+        .list()
+        .into_iter()
+        .filter(|status| status.downloaded)
+        .map(|status| status.model)
+        .collect();
+    assert_eq!(downloaded, vec![WhisperModel::Base]);
+    assert_eq!(
+        models.downloaded_path(WhisperModel::Base),
+        Some(dir.join("ggml-base.bin"))
+    );
+
+    assert!(models.delete(WhisperModel::Base).unwrap());
+    assert!(!models.delete(WhisperModel::Base).unwrap());
+    assert_eq!(models.downloaded_path(WhisperModel::Base), None);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+fn providers(values: &[&str]) -> Vec<ProviderInfo> {
+    values
+        .iter()
+        .map(|value| ProviderInfo {
+            value: value.to_string(),
+            label: value.to_uppercase(),
+            is_local: false,
+            model: None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_next_provider_wraps_around() {
+    let available = providers(&["deepgram", "openai", "speechmatics"]);
+    let next = |current| next_provider(Some(current), &available).map(|p| p.value.as_str());
+    assert_eq!(next("deepgram"), Some("openai"));
+    assert_eq!(next("speechmatics"), Some("deepgram"));
+}
+
+#[test]
+fn test_next_provider_starts_from_the_first() {
+    let available = providers(&["deepgram", "openai"]);
+    let value = |current| next_provider(current, &available).map(|p| p.value.clone());
+    // The server default and in-app Whisper aren't among the server's providers
+    assert_eq!(value(None), Some("deepgram".to_string()));
+    assert_eq!(value(Some("local-whisper")), Some("deepgram".to_string()));
+    assert_eq!(next_provider(Some("deepgram"), &[]), None);
+}
+
+#[test]
+fn test_provider_info_deserializes_server_listing() {
+    let json = r#"{"value":"deepgram","label":"Deepgram","is_local":false,"model":"nova-3"}"#;
+    let provider: ProviderInfo = serde_json::from_str(json).unwrap();
+    assert_eq!(provider.model.as_deref(), Some("nova-3"));
+    let minimal: ProviderInfo =
+        serde_json::from_str(r#"{"value":"whisper","label":"Whisper"}"#).unwrap();
+    assert!(!minimal.is_local);
+}
+
+#[test]
+fn test_next_turn_starts_while_the_last_is_transcribed() {
+    let (started, transcribing) = mpsc::channel();
+    let (release, released) = mpsc::channel();
+    let manager = Arc::new(SttManager::new(Box::new(SlowProvider {
+        started,
+        release: Arc::new(Mutex::new(released)),
+    })));
+    manager
+        .start_stream(AudioFormat::WHISPER, Duration::ZERO)
+        .unwrap();
+    let finishing = {
+        let manager = manager.clone();
+        std::thread::spawn(move || manager.finalize())
+    };
+    transcribing.recv().unwrap();
+
+    let (next_started, next_turn) = mpsc::channel();
+    {
+        let manager = manager.clone();
+        std::thread::spawn(move || {
+            let _ = next_started.send(manager.start_stream(AudioFormat::WHISPER, Duration::ZERO));
+        });
+    }
+    let next = next_turn.recv_timeout(Duration::from_secs(5));
+    release.send(()).unwrap();
+    assert_eq!(next, Ok(Ok(())));
+    assert_eq!(
+        finishing.join().unwrap(),
+        Ok(Some(Transcript {
+            text: "done".to_string(),
+            provider: "slow",
+        }))
+    );
+}
@@ -0,0 +1,87 @@
+use crate::telemetry::{
+    latency_bucket, FailureCategory, TelemetryCounters, TelemetryStore, LATENCY_BUCKET_BOUNDS_MS,
+};
+use crate::tests::support::TempDir;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+fn now() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+}
+
+#[test]
+fn test_latency_buckets_include_their_bound() {
+    assert_eq!(latency_bucket(Duration::from_millis(0)), 0);
+    assert_eq!(latency_bucket(Duration::from_millis(500)), 0);
+    assert_eq!(latency_bucket(Duration::from_millis(501)), 1);
+    assert_eq!(latency_bucket(Duration::from_secs(3)), 3);
+    assert_eq!(
+        latency_bucket(Duration::from_secs(60)),
+        LATENCY_BUCKET_BOUNDS_MS.len()
+    );
+}
+
+#[test]
+fn test_report_failure_rate_ignores_dropped_connections() {
+    let mut counters = TelemetryCounters::default();
+    for _ in 0..3 {
+        counters.record_dictation(Some(Duration::from_millis(800)));
+    }
+    counters.record_failure(FailureCategory::Insertion);
+    counters.record_failure(FailureCategory::Connection);
+    counters.record_failure(FailureCategory::Connection);
+
+    let report = counters.report(true);
+    assert_eq!(report.failure_rate, Some(0.25));
+    assert_eq!(report.failures[0].category, FailureCategory::Connection);
+    assert_eq!(report.failures[0].count, 2);
+    assert_eq!(report.latency.len(), LATENCY_BUCKET_BOUNDS_MS.len() + 1);
+    assert_eq!(report.latency[1].max_ms, Some(1000));
+    assert_eq!(report.latency[1].count, 3);
+    assert_eq!(report.latency.last().unwrap().max_ms, None);
+}
+
+#[test]
+fn test_report_without_turns_has_no_failure_rate() {
+    let report = TelemetryCounters::default().report(false);
+    assert!(!report.enabled);
+    assert_eq!(report.failure_rate, None);
+    assert!(report.latency.iter().all(|bucket| bucket.count == 0));
+}
+
+#[test]
+fn test_counters_persist_until_purged() {
+    let dir = TempDir::new("telemetry");
+    let store = TelemetryStore::new(&dir);
+    store
+        .update(now(), |counters| counters.record_session())
+        .unwrap();
+    store
+        .update(now(), |counters| {
+            counters.record_failure(FailureCategory::Server)
+        })
+        .unwrap();
+
+    let reloaded = TelemetryStore::new(&dir).snapshot().unwrap();
+    assert_eq!(reloaded.since, Some(now()));
+    assert_eq!(reloaded.sessions, 1);
+    assert_eq!(reloaded.failures.get(&FailureCategory::Server), Some(&1));
+
+    store.purge().unwrap();
+    assert_eq!(store.snapshot().unwrap(), TelemetryCounters::default());
+    assert_eq!(
+        TelemetryStore::new(&dir).snapshot().unwrap(),
+        TelemetryCounters::default()
+    );
+}
+
+#[test]
+fn test_in_memory_counters_start_empty() {
+    let store = TelemetryStore::in_memory();
+    store
+        .update(now(), |counters| counters.record_dictation(None))
+        .unwrap();
+    let counters = store.snapshot().unwrap();
+    assert_eq!(counters.dictations, 1);
+    assert!(counters.latency_buckets.is_empty());
+}
@@ -0,0 +1,97 @@
+use crate::text_diff::{diff_words, word_corrections, DiffKind, DiffSegment, WordCorrection};
+
+fn segment(kind: DiffKind, text: &str) -> DiffSegment {
+    DiffSegment {
+        kind,
+        text: text.to_string(),
+    }
+}
+
+fn rebuild(segments: &[DiffSegment], skip: DiffKind) -> String {
+    segments
+        .iter()
+        .filter(|s| s.kind != skip)
+        .map(|s| s.text.as_str())
+        .collect()
+}
+
+#[test]
+fn test_identical_text_is_one_equal_segment() {
+    assert_eq!(
+        diff_words("hello world", "hello world"),
+        vec![segment(DiffKind::Equal, "hello world")]
+    );
+}
+
+#[test]
+fn test_changed_word_is_deleted_then_inserted() {
+    assert_eq!(
+        diff_words("send it to john tomorrow", "send it to John tomorrow"),
+        vec![
+            segment(DiffKind::Equal, "send it to "),
+            segment(DiffKind::Delete, "john"),
+            segment(DiffKind::Insert, "John"),
+            segment(DiffKind::Equal, " tomorrow"),
+        ]
+    );
+}
+
+#[test]
+fn test_removed_filler_words() {
+    assert_eq!(
+        diff_words("so um I think we should go", "I think we should go"),
+        vec![
+            segment(DiffKind::Delete, "so um "),
+            segment(DiffKind::Equal, "I think we should go"),
+        ]
+    );
+}
+
+#[test]
+fn test_segments_rebuild_both_texts() {
+    let raw = "the patient um reports\nno pain at all";
+    let formatted = "The patient reports no pain.\n\nFollow up in two weeks.";
+    let segments = diff_words(raw, formatted);
+
+    assert_eq!(rebuild(&segments, DiffKind::Insert), raw);
+    assert_eq!(rebuild(&segments, DiffKind::Delete), formatted);
+}
+
+#[test]
+fn test_empty_inputs() {
+    assert!(diff_words("", "").is_empty());
+    assert_eq!(
+        diff_words("", "added"),
+        vec![segment(DiffKind::Insert, "added")]
+    );
+    assert_eq!(
+        diff_words("removed", ""),
+        vec![segment(DiffKind::Delete, "removed")]
+    );
+}
+
+#[test]
+fn test_word_corrections_pair_replacements() {
+    assert_eq!(
+        word_corrections(
+            "ask ant row pick about pipe cat today",
+            "ask Anthropic about Pipecat today"
+        ),
+        vec![
+            WordCorrection {
+                heard: "ant row pick".to_string(),
+                corrected: "Anthropic".to_string(),
+            },
+            WordCorrection {
+                heard: "pipe cat".to_string(),
+                corrected: "Pipecat".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_word_corrections_skip_additions_and_removals() {
+    assert!(word_corrections("so um send it", "send it now").is_empty());
+    assert!(word_corrections("same text", "same text").is_empty());
+}
@@ -0,0 +1,230 @@
+use crate::active_window::ActiveApp;
+use crate::commands::text::{build_insertion_steps, split_into_chunks, InsertionStep};
+use crate::settings::{
+    is_blocked_app, resolve_insertion_method, resolve_newline_mode, InsertionMethod, InsertionRule,
+    NewlineMode, NewlineRule, OutputMode,
+};
+
+#[test]
+fn test_split_short_text_is_single_chunk() {
+    assert_eq!(split_into_chunks("hello world", 100), vec!["hello world"]);
+}
+
+#[test]
+fn test_split_empty_text_has_no_chunks() {
+    assert!(split_into_chunks("", 10).is_empty());
+}
+
+#[test]
+fn test_split_without_limit_is_single_chunk() {
+    assert_eq!(split_into_chunks("hello world", 0), vec!["hello world"]);
+    assert!(split_into_chunks("", 0).is_empty());
+}
+
+#[test]
+fn test_split_prefers_whitespace_boundaries() {
+    let chunks = split_into_chunks("one two three four", 9);
+    assert_eq!(chunks, vec!["one two ", "three ", "four"]);
+}
+
+#[test]
+fn test_split_prefers_newline_boundaries() {
+    let chunks = split_into_chunks("first line\nsecond line", 15);
+    assert_eq!(chunks[0], "first line\n");
+}
+
+#[test]
+fn test_split_hard_splits_long_words() {
+    let chunks = split_into_chunks("abcdefghij", 4);
+    assert_eq!(chunks, vec!["abcd", "efgh", "ij"]);
+}
+
+#[test]
+fn test_split_respects_multibyte_characters() {
+    let text = "héllo wörld ünïcödé";
+    let chunks = split_into_chunks(text, 5);
+    assert!(chunks.iter().all(|c| c.chars().count() <= 5));
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn test_insertion_steps_paste_mode_keeps_newlines() {
+    let steps = build_insertion_steps("hello\nworld", NewlineMode::Paste, 100);
+    assert_eq!(
+        steps,
+        vec![InsertionStep::Paste("hello\nworld".to_string())]
+    );
+}
+
+#[test]
+fn test_insertion_steps_shift_enter_between_lines() {
+    let steps = build_insertion_steps("hello\n\nworld", NewlineMode::ShiftEnter, 100);
+    assert_eq!(
+        steps,
+        vec![
+            InsertionStep::Paste("hello".to_string()),
+            InsertionStep::Newline(NewlineMode::ShiftEnter),
+            InsertionStep::Newline(NewlineMode::ShiftEnter),
+            InsertionStep::Paste("world".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_insertion_steps_strip_carriage_returns() {
+    let steps = build_insertion_steps("a\r\nb", NewlineMode::Enter, 100);
+    assert_eq!(
+        steps,
+        vec![
+            InsertionStep::Paste("a".to_string()),
+            InsertionStep::Newline(NewlineMode::Enter),
+            InsertionStep::Paste("b".to_string()),
+        ]
+    );
+}
+
+fn slack_app() -> ActiveApp {
+    ActiveApp {
+        app_name: "Slack".to_string(),
+        title: "general".to_string(),
+        process_path: "/Applications/Slack.app/Contents/MacOS/Slack".to_string(),
+        bundle_id: Some("com.tinyspeck.slackmacgap".to_string()),
+    }
+}
+
+#[test]
+fn test_resolve_newline_mode_matches_app_case_insensitively() {
+    let rules = vec![NewlineRule {
+        app: "slack".to_string(),
+        mode: NewlineMode::ShiftEnter,
+    }];
+    assert_eq!(
+        resolve_newline_mode(&rules, Some(&slack_app())),
+        NewlineMode::ShiftEnter
+    );
+}
+
+#[test]
+fn test_resolve_newline_mode_defaults_to_paste() {
+    let rules = vec![NewlineRule {
+        app: "discord".to_string(),
+        mode: NewlineMode::ShiftEnter,
+    }];
+    assert_eq!(
+        resolve_newline_mode(&rules, Some(&slack_app())),
+        NewlineMode::Paste
+    );
+    assert_eq!(resolve_newline_mode(&rules, None), NewlineMode::Paste);
+}
+
+#[test]
+fn test_active_app_matches_executable_name() {
+    let app = ActiveApp {
+        app_name: "Visual Studio Code".to_string(),
+        title: String::new(),
+        process_path: "C:\\Program Files\\Microsoft VS Code\\Code.exe".to_string(),
+        bundle_id: None,
+    };
+    assert!(app.matches("code.exe"));
+    assert!(!app.matches(""));
+}
+
+#[test]
+fn test_is_blocked_app_matches_any_pattern() {
+    let blocked = vec!["1password".to_string(), "com.tinyspeck".to_string()];
+    assert!(is_blocked_app(&blocked, Some(&slack_app())));
+    assert!(!is_blocked_app(&blocked[..1], Some(&slack_app())));
+    // An unknown app can't be matched, so it isn't blocked
+    assert!(!is_blocked_app(&blocked, None));
+}
+
+#[test]
+fn test_active_app_matches_bundle_id() {
+    assert!(slack_app().matches("com.tinyspeck"));
+    assert!(!slack_app().matches("com.microsoft"));
+}
+
+#[test]
+fn test_insertion_method_defaults_to_paste() {
+    assert_eq!(InsertionMethod::default(), InsertionMethod::Paste);
+    let method: InsertionMethod = serde_json::from_value(serde_json::json!("type")).unwrap();
+    assert_eq!(method, InsertionMethod::Type);
+}
+
+#[test]
+fn test_output_mode_defaults_to_insert() {
+    assert_eq!(OutputMode::default(), OutputMode::Insert);
+    let mode: OutputMode = serde_json::from_value(serde_json::json!("clipboard_only")).unwrap();
+    assert_eq!(mode, OutputMode::ClipboardOnly);
+}
+
+#[test]
+fn test_clipboard_only_hotkey_overrides_output_mode() {
+    assert_eq!(OutputMode::Insert.overridden(), OutputMode::ClipboardOnly);
+    assert_eq!(OutputMode::Both.overridden(), OutputMode::ClipboardOnly);
+    assert_eq!(OutputMode::ClipboardOnly.overridden(), OutputMode::Insert);
+}
+
+#[test]
+fn test_typing_turns_literal_newlines_into_enter() {
+    assert_eq!(
+        InsertionMethod::Type.newline_mode(NewlineMode::Paste),
+        NewlineMode::Enter
+    );
+    assert_eq!(
+        InsertionMethod::Type.newline_mode(NewlineMode::ShiftEnter),
+        NewlineMode::ShiftEnter
+    );
+    assert_eq!(
+        InsertionMethod::Paste.newline_mode(NewlineMode::Paste),
+        NewlineMode::Paste
+    );
+}
+
+#[test]
+fn test_resolve_insertion_method_uses_first_matching_rule() {
+    let rules = vec![
+        InsertionRule {
+            app: "discord".to_string(),
+            method: InsertionMethod::ClipboardOnly,
+        },
+        InsertionRule {
+            app: "SLACK".to_string(),
+            method: InsertionMethod::Type,
+        },
+        InsertionRule {
+            app: "slack".to_string(),
+            method: InsertionMethod::Accessibility,
+        },
+    ];
+    assert_eq!(
+        resolve_insertion_method(&rules, Some(&slack_app()), InsertionMethod::Paste),
+        InsertionMethod::Type
+    );
+}
+
+#[test]
+fn test_resolve_insertion_method_falls_back_to_default() {
+    let rules = vec![InsertionRule {
+        app: "terminal".to_string(),
+        method: InsertionMethod::Type,
+    }];
+    assert_eq!(
+        resolve_insertion_method(&rules, Some(&slack_app()), InsertionMethod::Paste),
+        InsertionMethod::Paste
+    );
+    assert_eq!(
+        resolve_insertion_method(&rules, None, InsertionMethod::Accessibility),
+        InsertionMethod::Accessibility
+    );
+}
+
+#[test]
+fn test_insertion_rule_round_trips_snake_case_method() {
+    let rule: InsertionRule =
+        serde_json::from_value(serde_json::json!({"app": "Code", "method": "clipboard_only"}))
+            .unwrap();
+    assert_eq!(rule.method, InsertionMethod::ClipboardOnly);
+    assert!(!rule.method.inserts_text());
+    assert!(InsertionMethod::Accessibility.inserts_text());
+}
@@ -0,0 +1,49 @@
+use crate::text_transforms::{apply_transforms, TransformRule};
+
+fn rule(pattern: &str, replacement: &str) -> TransformRule {
+    TransformRule {
+        name: String::new(),
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+        enabled: true,
+    }
+}
+
+#[test]
+fn test_rules_run_in_order() {
+    let rules = vec![
+        rule(r"(?i)\btodo:", "TODO:"),
+        rule(r"\.$", ""),
+        rule(r"TODO: (\w+)", "TODO($1):"),
+    ];
+    let (output, errors) = apply_transforms("todo: alex check the build.", &rules);
+    assert_eq!(output, "TODO(alex): check the build");
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn test_disabled_and_invalid_rules_are_skipped() {
+    let mut disabled = rule("build", "deploy");
+    disabled.enabled = false;
+    let rules = vec![disabled, rule("(unclosed", "x"), rule("build", "release")];
+    let (output, errors) = apply_transforms("Check the build", &rules);
+    assert_eq!(output, "Check the release");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 1);
+}
+
+#[test]
+fn test_single_rule_reports_matches() {
+    let test = rule(r"(?m)\.$", "").test("One.\nTwo.\nThree").unwrap();
+    assert_eq!(test.output, "One\nTwo\nThree");
+    assert_eq!(test.matches, 2);
+    assert!(rule("", "x").test("text").is_err());
+}
+
+#[test]
+fn test_rule_settings_default_to_enabled() {
+    let parsed: TransformRule =
+        serde_json::from_str(r#"{"pattern": "a", "replacement": "b"}"#).unwrap();
+    assert!(parsed.enabled);
+    assert_eq!(parsed.name, "");
+}
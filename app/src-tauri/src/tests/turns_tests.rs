@@ -0,0 +1,100 @@
+use crate::turns::TurnQueue;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_begin_assigns_increasing_ids() {
+    let queue = TurnQueue::default();
+    assert_eq!(queue.begin(), 1);
+    assert_eq!(queue.begin(), 2);
+    assert_eq!(queue.in_flight(), 2);
+}
+
+#[test]
+fn test_finish_frees_capacity() {
+    let queue = TurnQueue::default();
+    assert!(queue.has_capacity(1));
+    let id = queue.begin();
+    assert!(!queue.has_capacity(1));
+    assert!(queue.has_capacity(2));
+    queue.finish(id);
+    assert!(queue.has_capacity(1));
+}
+
+#[test]
+fn test_zero_limit_still_allows_one_turn() {
+    let queue = TurnQueue::default();
+    assert!(queue.has_capacity(0));
+    queue.begin();
+    assert!(!queue.has_capacity(0));
+}
+
+#[test]
+fn test_oldest_turn_does_not_wait() {
+    let queue = TurnQueue::default();
+    let first = queue.begin();
+    queue.begin();
+    // Returns immediately since nothing is ahead of it
+    queue.wait_for_turn(first);
+}
+
+#[test]
+fn test_later_turn_delivers_after_earlier_one() {
+    let queue = Arc::new(TurnQueue::default());
+    let delivered = Arc::new(Mutex::new(Vec::new()));
+    let first = queue.begin();
+    let second = queue.begin();
+
+    // The second turn is ready first but must wait for the first
+    let waiter = {
+        let queue = Arc::clone(&queue);
+        let delivered = Arc::clone(&delivered);
+        thread::spawn(move || {
+            queue.wait_for_turn(second);
+            delivered.lock().unwrap().push(second);
+            queue.finish(second);
+        })
+    };
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(delivered.lock().unwrap().is_empty());
+
+    delivered.lock().unwrap().push(first);
+    queue.finish(first);
+    waiter.join().unwrap();
+
+    assert_eq!(*delivered.lock().unwrap(), vec![first, second]);
+    assert_eq!(queue.in_flight(), 0);
+}
+
+#[test]
+fn test_server_turn_stays_in_flight_until_reported() {
+    let queue = TurnQueue::default();
+    let first = queue.begin();
+    queue.await_server(first);
+    let second = queue.begin();
+    queue.await_server(second);
+    assert!(!queue.has_capacity(2));
+
+    // The server answers in order
+    queue.finish_server_turns(1);
+    assert_eq!(queue.in_flight(), 1);
+    queue.wait_for_turn(second);
+
+    // Reporting more turns than are waiting finishes only those
+    queue.finish_server_turns(usize::MAX);
+    assert_eq!(queue.in_flight(), 0);
+}
+
+#[test]
+fn test_finished_turn_is_not_awaited() {
+    let queue = TurnQueue::default();
+    let id = queue.begin();
+    queue.finish(id);
+    queue.await_server(id);
+    let next = queue.begin();
+    queue.await_server(next);
+    queue.finish_server_turns(1);
+    assert_eq!(queue.in_flight(), 0);
+}
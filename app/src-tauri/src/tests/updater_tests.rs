@@ -0,0 +1,28 @@
+use crate::updater::UpdateChannel;
+
+#[test]
+fn test_update_channel_defaults_to_stable() {
+    assert_eq!(UpdateChannel::default(), UpdateChannel::Stable);
+}
+
+#[test]
+fn test_update_channel_serializes_snake_case() {
+    assert_eq!(
+        serde_json::to_string(&UpdateChannel::Beta).unwrap(),
+        "\"beta\""
+    );
+    assert_eq!(
+        serde_json::from_str::<UpdateChannel>("\"stable\"").unwrap(),
+        UpdateChannel::Stable
+    );
+}
+
+#[test]
+fn test_each_channel_has_its_own_manifest() {
+    assert_ne!(
+        UpdateChannel::Stable.endpoint(),
+        UpdateChannel::Beta.endpoint()
+    );
+    assert!(UpdateChannel::Stable.endpoint().ends_with("/latest.json"));
+    assert!(UpdateChannel::Beta.endpoint().ends_with("/latest.json"));
+}
@@ -0,0 +1,109 @@
+use crate::usage::{self, UsagePeriod, UsageStore};
+use chrono::{DateTime, TimeDelta, Utc};
+
+fn now() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+}
+
+#[test]
+fn test_word_count_ignores_extra_whitespace() {
+    assert_eq!(usage::count_words("  hello   world\nagain "), 3);
+    assert_eq!(usage::count_words(""), 0);
+}
+
+#[test]
+fn test_time_saved_against_typing_speed() {
+    // 40 words take a minute to type; speaking them took 15 seconds
+    assert_eq!(usage::time_saved_ms(40, 15_000), 45_000);
+    // Slow dictation saves nothing rather than going negative
+    assert_eq!(usage::time_saved_ms(10, 60_000), 0);
+}
+
+#[test]
+fn test_stats_total_dictations_in_period() {
+    let store = UsageStore::in_memory().unwrap();
+    store
+        .record(
+            now() - TimeDelta::hours(1),
+            "one two three",
+            Some(2_000),
+            Some("deepgram"),
+        )
+        .unwrap();
+    store
+        .record(
+            now() - TimeDelta::days(3),
+            "four five",
+            Some(1_000),
+            Some("whisper"),
+        )
+        .unwrap();
+    store
+        .record(now() - TimeDelta::days(10), "six", None, None)
+        .unwrap();
+
+    let day = store.stats(UsagePeriod::Day, now()).unwrap();
+    assert_eq!((day.dictations, day.words, day.speaking_ms), (1, 3, 2_000));
+
+    let week = store.stats(UsagePeriod::Week, now()).unwrap();
+    assert_eq!(week.period, UsagePeriod::Week);
+    assert_eq!(
+        (week.dictations, week.words, week.speaking_ms),
+        (2, 5, 3_000)
+    );
+    assert_eq!(week.time_saved_ms, usage::time_saved_ms(5, 3_000));
+
+    let all = store.stats(UsagePeriod::All, now()).unwrap();
+    assert_eq!(all.dictations, 3);
+    assert_eq!(all.by_provider.len(), 3);
+    assert!(all.by_provider.iter().any(|usage| usage.provider.is_none()));
+}
+
+#[test]
+fn test_stats_group_by_provider_most_used_first() {
+    let store = UsageStore::in_memory().unwrap();
+    for text in ["a", "b c"] {
+        store
+            .record(now(), text, Some(500), Some("whisper"))
+            .unwrap();
+    }
+    store
+        .record(now(), "d", Some(500), Some("deepgram"))
+        .unwrap();
+
+    let stats = store.stats(UsagePeriod::Day, now()).unwrap();
+    let providers: Vec<_> = stats
+        .by_provider
+        .iter()
+        .map(|usage| (usage.provider.as_deref(), usage.dictations, usage.words))
+        .collect();
+    assert_eq!(
+        providers,
+        vec![(Some("whisper"), 2, 3), (Some("deepgram"), 1, 1)]
+    );
+}
+
+#[test]
+fn test_reset_clears_all_usage() {
+    let store = UsageStore::in_memory().unwrap();
+    store.record(now(), "hello", Some(500), None).unwrap();
+    store.reset().unwrap();
+    let stats = store.stats(UsagePeriod::All, now()).unwrap();
+    assert_eq!(stats.dictations, 0);
+    assert!(stats.by_provider.is_empty());
+}
+
+#[test]
+fn test_usage_persists_in_app_data_dir() {
+    let dir = std::env::temp_dir().join(format!("tambourine-usage-{}", uuid::Uuid::new_v4()));
+    UsageStore::new(&dir)
+        .unwrap()
+        .record(now(), "kept", Some(100), None)
+        .unwrap();
+    let stats = UsageStore::new(&dir)
+        .unwrap()
+        .stats(UsagePeriod::All, now())
+        .unwrap();
+    assert_eq!(stats.words, 1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,59 @@
+use crate::stt::AudioFormat;
+use crate::vad::SilenceDetector;
+use std::time::Duration;
+
+const FORMAT: AudioFormat = AudioFormat::WHISPER;
+
+fn samples_per_ms(ms: u64) -> usize {
+    (FORMAT.sample_rate as u64 * ms / 1000) as usize
+}
+
+fn silence(ms: u64) -> Vec<f32> {
+    vec![0.001; samples_per_ms(ms)]
+}
+
+/// A 200 Hz tone loud enough to count as speech
+fn speech(ms: u64) -> Vec<f32> {
+    (0..samples_per_ms(ms))
+        .map(|i| 0.2 * (i as f32 * 200.0 * std::f32::consts::TAU / 16_000.0).sin())
+        .collect()
+}
+
+#[test]
+fn test_silence_stops_after_limit() {
+    let mut detector = SilenceDetector::new(FORMAT, Duration::from_secs(2));
+    assert!(!detector.push(&silence(1500)));
+    assert!(detector.push(&silence(600)));
+}
+
+#[test]
+fn test_speech_resets_silence() {
+    let mut detector = SilenceDetector::new(FORMAT, Duration::from_secs(2));
+    assert!(!detector.push(&silence(1500)));
+    assert!(!detector.push(&speech(500)));
+    assert!(!detector.push(&silence(1500)));
+    assert!(detector.push(&silence(600)));
+}
+
+#[test]
+fn test_short_clicks_are_not_speech() {
+    let mut detector = SilenceDetector::new(FORMAT, Duration::from_secs(1));
+    let mut stopped = false;
+    for _ in 0..20 {
+        stopped |= detector.push(&speech(30));
+        stopped |= detector.push(&silence(200));
+    }
+    assert!(stopped);
+}
+
+#[test]
+fn test_stereo_frames_cover_both_channels() {
+    let stereo = AudioFormat {
+        sample_rate: 16_000,
+        channels: 2,
+    };
+    let mut detector = SilenceDetector::new(stereo, Duration::from_secs(1));
+    // Each push is half as long in stereo as the same number of mono samples
+    assert!(!detector.push(&silence(1200)));
+    assert!(detector.push(&silence(1200)));
+}
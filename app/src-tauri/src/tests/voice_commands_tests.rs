@@ -0,0 +1,78 @@
+use crate::voice_commands::{
+    default_voice_commands, inserted_text, interpret_voice_commands, CommandKey, DictationEdit,
+    VoiceCommand, VoiceCommandAction,
+};
+
+fn insert(text: &str) -> DictationEdit {
+    DictationEdit::Insert(text.to_string())
+}
+
+#[test]
+fn test_text_without_commands_is_one_insert() {
+    let edits = interpret_voice_commands("Lines and paragraphs.", &default_voice_commands());
+    assert_eq!(edits, vec![insert("Lines and paragraphs.")]);
+}
+
+#[test]
+fn test_line_breaks_replace_command_words() {
+    let edits = interpret_voice_commands(
+        "Dear Sam, new line. Thanks for the notes. New paragraph. Best, new line Alex",
+        &default_voice_commands(),
+    );
+    assert_eq!(
+        edits,
+        vec![insert("Dear Sam,\nThanks for the notes.\n\nBest,\nAlex")]
+    );
+}
+
+#[test]
+fn test_key_presses_split_the_text() {
+    let edits = interpret_voice_commands(
+        "Looks good to me. Press enter. Undo that.",
+        &default_voice_commands(),
+    );
+    assert_eq!(
+        edits,
+        vec![
+            insert("Looks good to me."),
+            DictationEdit::Press(CommandKey::Enter),
+            DictationEdit::Press(CommandKey::Undo),
+        ]
+    );
+    assert_eq!(inserted_text(&edits), "Looks good to me.");
+}
+
+#[test]
+fn test_scratch_that() {
+    let commands = default_voice_commands();
+    // At the start, it removes the previous dictation
+    assert_eq!(
+        interpret_voice_commands("Scratch that.", &commands),
+        vec![DictationEdit::DeletePrevious]
+    );
+    assert_eq!(
+        interpret_voice_commands("Scratch that. Meet at noon.", &commands),
+        vec![DictationEdit::DeletePrevious, insert("Meet at noon.")]
+    );
+    // Later, it drops what was said before it
+    assert_eq!(
+        interpret_voice_commands("Meet at ten. Delete that. Meet at noon.", &commands),
+        vec![insert("Meet at noon.")]
+    );
+}
+
+#[test]
+fn test_custom_commands() {
+    let commands = vec![VoiceCommand {
+        phrase: "next field".to_string(),
+        action: VoiceCommandAction::PressTab,
+    }];
+    assert_eq!(
+        interpret_voice_commands("Alex next field Smith new line", &commands),
+        vec![
+            insert("Alex"),
+            DictationEdit::Press(CommandKey::Tab),
+            insert("Smith new line"),
+        ]
+    );
+}
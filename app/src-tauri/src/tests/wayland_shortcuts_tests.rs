@@ -0,0 +1,87 @@
+use crate::settings::{HotkeyAction, HotkeyConfig, HotkeyStep, HotkeyTrigger};
+use crate::wayland_shortcuts::{is_wayland, portal_shortcuts, portal_trigger};
+
+fn hotkey(modifiers: &[&str], key: &str) -> HotkeyConfig {
+    HotkeyConfig {
+        modifiers: modifiers.iter().map(|m| m.to_string()).collect(),
+        key: key.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_wayland_detected_from_display() {
+    assert!(is_wayland(Some("wayland-0")));
+    assert!(!is_wayland(Some("")));
+    assert!(!is_wayland(None));
+}
+
+#[test]
+fn test_portal_trigger_uses_xkb_names() {
+    assert_eq!(
+        portal_trigger(&hotkey(&["ctrl", "alt"], "Space")).as_deref(),
+        Some("CTRL+ALT+space")
+    );
+    assert_eq!(
+        portal_trigger(&hotkey(&["Ctrl", "Alt"], "Backquote")).as_deref(),
+        Some("CTRL+ALT+grave")
+    );
+    assert_eq!(
+        portal_trigger(&hotkey(&["meta", "shift"], "P")).as_deref(),
+        Some("LOGO+SHIFT+p")
+    );
+    assert_eq!(
+        portal_trigger(&hotkey(&["ctrl"], "KeyR")).as_deref(),
+        Some("CTRL+r")
+    );
+    assert_eq!(
+        portal_trigger(&hotkey(&["alt"], "Digit1")).as_deref(),
+        Some("ALT+1")
+    );
+    assert_eq!(portal_trigger(&hotkey(&[], "F13")).as_deref(), Some("F13"));
+    assert_eq!(
+        portal_trigger(&hotkey(&["ctrl"], "Enter")).as_deref(),
+        Some("CTRL+Return")
+    );
+}
+
+#[test]
+fn test_portal_trigger_rejects_unknown_keys() {
+    assert_eq!(portal_trigger(&hotkey(&["ctrl"], "Fn")), None);
+    assert_eq!(portal_trigger(&hotkey(&["hyper"], "Space")), None);
+}
+
+#[test]
+fn test_portal_shortcuts_skip_listener_only_hotkeys() {
+    let sequence = HotkeyConfig {
+        then: Some(HotkeyStep {
+            modifiers: Vec::new(),
+            key: "T".to_string(),
+        }),
+        ..hotkey(&["ctrl", "alt"], "D")
+    };
+    let mouse = HotkeyConfig {
+        trigger: HotkeyTrigger::Mouse,
+        ..hotkey(&[], "Mouse4")
+    };
+    let (shortcuts, failures) = portal_shortcuts(&[
+        (HotkeyAction::Toggle, hotkey(&["ctrl", "alt"], "Space")),
+        (HotkeyAction::Hold, mouse),
+        (HotkeyAction::PasteLast, sequence),
+    ]);
+
+    assert_eq!(
+        shortcuts,
+        vec![(HotkeyAction::Toggle, "CTRL+ALT+space".to_string())]
+    );
+    let failed: Vec<&str> = failures.iter().map(|f| f.action.as_str()).collect();
+    assert_eq!(failed, vec!["hold", "paste_last"]);
+}
+
+#[test]
+fn test_hotkey_action_ids_round_trip() {
+    for action in HotkeyAction::ALL {
+        assert_eq!(HotkeyAction::from_id(action.id()), Some(action));
+    }
+    assert_eq!(HotkeyAction::from_id("unknown"), None);
+}
@@ -0,0 +1,60 @@
+use crate::stt::whisper::{downmix_to_mono, join_segments, resample_linear, to_whisper_input};
+use crate::stt::AudioFormat;
+
+#[test]
+fn test_downmix_mono_is_unchanged() {
+    let samples = vec![0.1, -0.2, 0.3];
+    assert_eq!(downmix_to_mono(&samples, 1), samples);
+}
+
+#[test]
+fn test_downmix_stereo_averages_channels() {
+    let samples = vec![1.0, 0.0, 0.5, 0.5, -1.0, 1.0];
+    assert_eq!(downmix_to_mono(&samples, 2), vec![0.5, 0.5, 0.0]);
+}
+
+#[test]
+fn test_resample_same_rate_is_unchanged() {
+    let samples = vec![0.1, 0.2, 0.3];
+    assert_eq!(resample_linear(&samples, 16_000, 16_000), samples);
+}
+
+#[test]
+fn test_resample_downsamples_to_expected_length() {
+    let samples = vec![0.0; 48_000];
+    assert_eq!(resample_linear(&samples, 48_000, 16_000).len(), 16_000);
+}
+
+#[test]
+fn test_resample_interpolates_between_samples() {
+    let samples = vec![0.0, 1.0];
+    let resampled = resample_linear(&samples, 8_000, 16_000);
+    assert_eq!(resampled.len(), 4);
+    assert_eq!(resampled[0], 0.0);
+    assert_eq!(resampled[1], 0.5);
+    assert_eq!(resampled[2], 1.0);
+}
+
+#[test]
+fn test_to_whisper_input_converts_stereo_48k() {
+    let format = AudioFormat {
+        sample_rate: 48_000,
+        channels: 2,
+    };
+    let samples = vec![0.25; 96_000];
+    let converted = to_whisper_input(&samples, format);
+    assert_eq!(converted.len(), 16_000);
+    assert!(converted
+        .iter()
+        .all(|sample| (*sample - 0.25).abs() < f32::EPSILON));
+}
+
+#[test]
+fn test_join_segments_normalizes_whitespace() {
+    let segments = vec![
+        " Hello there.".to_string(),
+        "   ".to_string(),
+        " How are you?".to_string(),
+    ];
+    assert_eq!(join_segments(&segments), "Hello there. How are you?");
+}
@@ -0,0 +1,80 @@
+use crate::overlay::MonitorArea;
+use crate::tests::support::TempDir;
+use crate::window_state::{
+    is_on_screen, WindowGeometry, WindowState, WindowStateStore, WINDOW_STATE_FILE,
+};
+
+fn monitor(x: f64) -> MonitorArea {
+    MonitorArea {
+        name: None,
+        x,
+        y: 0.0,
+        width: 1920.0,
+        height: 1080.0,
+        scale_factor: 1.0,
+    }
+}
+
+fn geometry(x: i32, y: i32) -> WindowGeometry {
+    WindowGeometry {
+        x,
+        y,
+        width: 1280,
+        height: 720,
+    }
+}
+
+#[test]
+fn test_window_on_second_monitor_is_on_screen_while_connected() {
+    let window = geometry(2200, 100);
+    assert!(is_on_screen(&window, &[monitor(0.0), monitor(1920.0)]));
+    assert!(!is_on_screen(&window, &[monitor(0.0)]));
+}
+
+#[test]
+fn test_window_with_title_bar_above_screen_is_off_screen() {
+    assert!(!is_on_screen(&geometry(100, -200), &[monitor(0.0)]));
+    // Partly off the left edge, but the title bar can still be grabbed
+    assert!(is_on_screen(&geometry(-400, 0), &[monitor(0.0)]));
+}
+
+#[test]
+fn test_maximizing_keeps_unmaximized_geometry() {
+    let store = WindowStateStore::in_memory();
+    store.update("main", None, true);
+    assert_eq!(store.get("main"), None);
+
+    store.update("main", Some(geometry(10, 20)), false);
+    store.update("main", None, true);
+    assert_eq!(
+        store.get("main"),
+        Some(WindowState {
+            geometry: geometry(10, 20),
+            maximized: true,
+        })
+    );
+}
+
+#[test]
+fn test_states_are_saved_per_label_and_reset() {
+    let dir = TempDir::new("window-state");
+    let store = WindowStateStore::new(&dir);
+    store.update("main", Some(geometry(10, 20)), false);
+    store.update("session", Some(geometry(30, 40)), false);
+    store.save().unwrap();
+
+    let reloaded = WindowStateStore::new(&dir);
+    assert_eq!(reloaded.get("main").unwrap().geometry, geometry(10, 20));
+    assert_eq!(reloaded.get("session").unwrap().geometry, geometry(30, 40));
+
+    reloaded.reset().unwrap();
+    assert_eq!(reloaded.get("main"), None);
+    assert!(!dir.join(WINDOW_STATE_FILE).exists());
+}
+
+#[test]
+fn test_unreadable_file_starts_over() {
+    let dir = TempDir::new("window-state");
+    std::fs::write(dir.join(WINDOW_STATE_FILE), "not json").unwrap();
+    assert_eq!(WindowStateStore::new(&dir).get("main"), None);
+}
@@ -0,0 +1,159 @@
+//! Word-level diff between a raw transcript and its formatted text.
+//!
+//! Text is split into alternating runs of words and whitespace, and the longest
+//! common subsequence of those tokens decides what was kept, removed or added.
+//! Concatenating the `Equal` and `Delete` segments reproduces the raw text, and the
+//! `Equal` and `Insert` segments reproduce the formatted text.
+
+use serde::Serialize;
+
+/// Token pairs compared before giving up and replacing the whole changed span
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Whether a diff segment was kept, removed from the raw text or added by formatting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A run of text with the same diff kind
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffSegment {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// A word or phrase replaced between two versions of a text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WordCorrection {
+    /// What the earlier version said
+    pub heard: String,
+    /// What replaced it
+    pub corrected: String,
+}
+
+/// Split text into runs of non-whitespace and whitespace characters
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_whitespace = None;
+
+    for (offset, c) in text.char_indices() {
+        let is_whitespace = c.is_whitespace();
+        if in_whitespace.is_some_and(|current| current != is_whitespace) {
+            tokens.push(&text[start..offset]);
+            start = offset;
+        }
+        in_whitespace = Some(is_whitespace);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Diff `raw` against `formatted` word by word
+pub fn diff_words(raw: &str, formatted: &str) -> Vec<DiffSegment> {
+    let old = tokenize(raw);
+    let new = tokenize(formatted);
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut segments = Vec::new();
+    let mut push = |kind: DiffKind, token: &str| match segments.last_mut() {
+        Some(DiffSegment { kind: last, text }) if *last == kind => text.push_str(token),
+        _ => segments.push(DiffSegment {
+            kind,
+            text: token.to_string(),
+        }),
+    };
+
+    for token in &old[..prefix] {
+        push(DiffKind::Equal, token);
+    }
+    for (kind, token) in diff_tokens(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ) {
+        push(kind, token);
+    }
+    for token in &old[old.len() - suffix..] {
+        push(DiffKind::Equal, token);
+    }
+
+    segments
+}
+
+/// Diff two token lists with a longest common subsequence table. Spans too large to
+/// compare are reported as deleted then inserted wholesale.
+fn diff_tokens<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffKind, &'a str)> {
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|token| (DiffKind::Delete, *token))
+            .chain(new.iter().map(|token| (DiffKind::Insert, *token)))
+            .collect();
+    }
+
+    // lcs[i][j] is the LCS length of old[i..] and new[j..]
+    let width = new.len() + 1;
+    let mut lcs = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push((DiffKind::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            ops.push((DiffKind::Delete, old[i]));
+            i += 1;
+        } else {
+            ops.push((DiffKind::Insert, new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|token| (DiffKind::Delete, *token)));
+    ops.extend(new[j..].iter().map(|token| (DiffKind::Insert, *token)));
+    ops
+}
+
+/// Replacements made to `original` to get `corrected`: each deletion directly followed
+/// by an insertion. Pure additions and removals aren't corrections and are skipped.
+pub fn word_corrections(original: &str, corrected: &str) -> Vec<WordCorrection> {
+    diff_words(original, corrected)
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [deleted, inserted]
+                if deleted.kind == DiffKind::Delete && inserted.kind == DiffKind::Insert =>
+            {
+                let heard = deleted.text.trim();
+                let corrected = inserted.text.trim();
+                (!heard.is_empty() && !corrected.is_empty()).then(|| WordCorrection {
+                    heard: heard.to_string(),
+                    corrected: corrected.to_string(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+//! Regex find/replace rules applied to each dictation before insertion.
+//!
+//! Rules in the `text_transforms` setting run in order on the formatted transcript,
+//! after the output style and before snippets and voice commands, so fixed
+//! conventions ("TODO:" in capitals, no trailing period) hold without asking the
+//! LLM. Replacements use the regex
+//! crate's syntax: `$1` or `${name}` for groups, `$$` for a dollar sign. Inline
+//! flags such as `(?i)` and `(?m)` set case-insensitive and multi-line matching.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A regex find/replace rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransformRule {
+    /// Label shown in settings
+    #[serde(default)]
+    pub name: String,
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Result of trying a rule on sample text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransformTest {
+    pub output: String,
+    /// Number of places the pattern matched
+    pub matches: usize,
+}
+
+impl TransformRule {
+    fn regex(&self) -> Result<Regex, String> {
+        if self.pattern.is_empty() {
+            return Err("The pattern is empty".to_string());
+        }
+        Regex::new(&self.pattern).map_err(|e| format!("Invalid pattern: {}", e))
+    }
+
+    /// Apply the rule to sample text, whether or not it is enabled
+    pub fn test(&self, sample: &str) -> Result<TransformTest, String> {
+        let regex = self.regex()?;
+        Ok(TransformTest {
+            output: regex
+                .replace_all(sample, self.replacement.as_str())
+                .into_owned(),
+            matches: regex.find_iter(sample).count(),
+        })
+    }
+}
+
+/// Run the enabled rules over `text` in order. Rules with an invalid pattern are
+/// skipped and returned with their error, so one bad rule doesn't stop the others.
+pub fn apply_transforms(text: &str, rules: &[TransformRule]) -> (String, Vec<(usize, String)>) {
+    let mut output = text.to_string();
+    let mut errors = Vec::new();
+    for (index, rule) in rules.iter().enumerate().filter(|(_, rule)| rule.enabled) {
+        match rule.regex() {
+            Ok(regex) => {
+                output = regex
+                    .replace_all(&output, rule.replacement.as_str())
+                    .into_owned();
+            }
+            Err(e) => errors.push((index, e)),
+        }
+    }
+    (output, errors)
+}
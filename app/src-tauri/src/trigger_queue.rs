@@ -0,0 +1,48 @@
+use std::sync::mpsc;
+use tauri::AppHandle;
+
+/// A recording trigger from a low-level input source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// Hold-to-record pressed or released
+    Hold { pressed: bool, source: &'static str },
+    /// Toggle recording
+    Toggle { source: &'static str },
+}
+
+/// Runs triggers from input hooks on one worker thread, in the order they arrive.
+///
+/// Hooks must return quickly, so triggers can't run on the hook thread, but a thread
+/// per event lets a release overtake its press and leave recording on, or two
+/// quick toggles run out of order.
+pub struct TriggerQueue {
+    sender: mpsc::Sender<Trigger>,
+}
+
+impl TriggerQueue {
+    /// Start the worker thread
+    pub fn start(app: &AppHandle) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let app = app.clone();
+        std::thread::spawn(move || {
+            for trigger in receiver {
+                run(&app, trigger);
+            }
+        });
+        Self { sender }
+    }
+
+    /// Queue a trigger behind any still running
+    pub fn send(&self, trigger: Trigger) {
+        if self.sender.send(trigger).is_err() {
+            log::warn!("Trigger worker stopped, dropping {:?}", trigger);
+        }
+    }
+}
+
+fn run(app: &AppHandle, trigger: Trigger) {
+    match trigger {
+        Trigger::Hold { pressed, source } => crate::handle_hold_trigger(app, pressed, source),
+        Trigger::Toggle { source } => crate::handle_toggle_trigger(app, source),
+    }
+}
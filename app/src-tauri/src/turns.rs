@@ -0,0 +1,94 @@
+//! Ordering and limits for dictation turns that overlap.
+//!
+//! A new recording may start while earlier turns are still being transcribed and
+//! formatted. Each turn gets an increasing ID when its recording stops, and results
+//! are delivered strictly in ID order so a short later turn can't jump ahead.
+//!
+//! This is the one limit on concurrent turns. Turns the server transcribes stay in
+//! flight after their recording is handed off, until the overlay reports their
+//! response or the connection drops.
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+/// Tracks turns between the end of recording and the insertion of their text
+#[derive(Default)]
+pub struct TurnQueue {
+    state: Mutex<TurnQueueState>,
+    turn_finished: Condvar,
+}
+
+#[derive(Default)]
+struct TurnQueueState {
+    last_id: u64,
+    /// Turns that stopped recording but haven't finished, oldest first
+    in_flight: VecDeque<u64>,
+    /// Turns among them waiting for the server's response, oldest first
+    server_turns: VecDeque<u64>,
+}
+
+impl TurnQueue {
+    /// Register a turn whose recording just stopped and return its ID
+    pub fn begin(&self) -> u64 {
+        let Ok(mut state) = self.state.lock() else {
+            return 0;
+        };
+        state.last_id += 1;
+        let id = state.last_id;
+        state.in_flight.push_back(id);
+        id
+    }
+
+    /// Number of turns still being processed
+    pub fn in_flight(&self) -> usize {
+        self.state.lock().map_or(0, |state| state.in_flight.len())
+    }
+
+    /// Whether a new turn may start without exceeding `max_in_flight` (at least 1)
+    pub fn has_capacity(&self, max_in_flight: usize) -> bool {
+        self.in_flight() < max_in_flight.max(1)
+    }
+
+    /// Block until every turn that started before `id` has finished
+    pub fn wait_for_turn(&self, id: u64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        while state.in_flight.front().is_some_and(|&oldest| oldest < id) {
+            state = match self.turn_finished.wait(state) {
+                Ok(state) => state,
+                Err(_) => return,
+            };
+        }
+    }
+
+    /// Mark a turn as finished, letting the next one deliver
+    pub fn finish(&self, id: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.in_flight.retain(|&turn| turn != id);
+            state.server_turns.retain(|&turn| turn != id);
+        }
+        self.turn_finished.notify_all();
+    }
+
+    /// Keep a turn in flight until the server's response for it is reported with
+    /// [`Self::finish_server_turns`]
+    pub fn await_server(&self, id: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.in_flight.contains(&id) {
+                state.server_turns.push_back(id);
+            }
+        }
+    }
+
+    /// Finish up to `count` of the oldest turns waiting for the server, since it
+    /// answers in order
+    pub fn finish_server_turns(&self, count: usize) {
+        if let Ok(mut state) = self.state.lock() {
+            let count = count.min(state.server_turns.len());
+            let finished: Vec<u64> = state.server_turns.drain(..count).collect();
+            state.in_flight.retain(|turn| !finished.contains(turn));
+        }
+        self.turn_finished.notify_all();
+    }
+}
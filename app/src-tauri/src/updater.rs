@@ -0,0 +1,35 @@
+//! Update channels for the in-app updater.
+//!
+//! Each channel has its own update manifest published with the GitHub releases. The
+//! beta manifest is moved to every release, stable ones included, so beta users never
+//! fall behind stable.
+
+use serde::{Deserialize, Serialize};
+
+/// Public key release builds verify updates with, set by the release workflow.
+/// Builds without it (such as local ones) can't verify updates, so can't update.
+pub const UPDATER_PUBKEY: Option<&str> = option_env!("TAMBOURINE_UPDATER_PUBKEY");
+
+/// Which releases the app updates to
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// Pre-releases as well as stable releases
+    Beta,
+}
+
+impl UpdateChannel {
+    /// URL of the channel's update manifest
+    pub fn endpoint(self) -> &'static str {
+        match self {
+            Self::Stable => {
+                "https://github.com/kstonekuan/tambourine-voice/releases/latest/download/latest.json"
+            }
+            Self::Beta => {
+                "https://github.com/kstonekuan/tambourine-voice/releases/download/beta/latest.json"
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Local usage statistics.
+//!
+//! Every completed dictation adds a row to `usage.db` in the app data directory
+//! with its word count, speaking time and STT provider. Statistics are kept apart
+//! from history so clearing or deleting history entries doesn't reset them, and
+//! nothing leaves the machine.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Usage database file in the app data directory
+pub const USAGE_DB_FILE: &str = "usage.db";
+
+/// Typing speed the time saved is estimated against, in words per minute
+pub const TYPING_WORDS_PER_MINUTE: u64 = 40;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS usage (
+        timestamp_ms INTEGER NOT NULL,
+        words INTEGER NOT NULL,
+        speaking_ms INTEGER NOT NULL,
+        provider TEXT
+    );
+    CREATE INDEX IF NOT EXISTS usage_timestamp ON usage (timestamp_ms);
+";
+
+/// Time span statistics are totalled over, ending now
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsagePeriod {
+    /// The last 24 hours
+    Day,
+    /// The last 7 days
+    #[default]
+    Week,
+    /// The last 30 days
+    Month,
+    All,
+}
+
+impl UsagePeriod {
+    /// Start of the period ending at `now`, or `None` for all time
+    pub fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let days = match self {
+            Self::Day => 1,
+            Self::Week => 7,
+            Self::Month => 30,
+            Self::All => return None,
+        };
+        Some(now - TimeDelta::days(days))
+    }
+}
+
+/// Usage of one STT provider
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProviderUsage {
+    /// `None` for dictations recorded without a known provider
+    pub provider: Option<String>,
+    pub dictations: u64,
+    pub words: u64,
+    pub speaking_ms: u64,
+}
+
+/// Totals over a period
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UsageStats {
+    pub period: UsagePeriod,
+    pub dictations: u64,
+    pub words: u64,
+    pub speaking_ms: u64,
+    /// Time typing the same words would have taken, minus the time spent speaking
+    pub time_saved_ms: u64,
+    /// Most used first
+    pub by_provider: Vec<ProviderUsage>,
+}
+
+/// Number of words in dictated text
+pub fn count_words(text: &str) -> u64 {
+    u64::try_from(text.split_whitespace().count()).unwrap_or(u64::MAX)
+}
+
+/// Estimated time saved by dictating `words` in `speaking_ms` instead of typing them
+pub fn time_saved_ms(words: u64, speaking_ms: u64) -> u64 {
+    let typing_ms = words.saturating_mul(60_000) / TYPING_WORDS_PER_MINUTE;
+    typing_ms.saturating_sub(speaking_ms)
+}
+
+/// Persistent store of per-dictation usage
+pub struct UsageStore {
+    connection: Mutex<Connection>,
+}
+
+impl UsageStore {
+    /// Open (or create) the usage database in the app data directory
+    pub fn new(app_data_dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+        let connection = Connection::open(app_data_dir.join(USAGE_DB_FILE))
+            .map_err(|e| format!("Failed to open usage database: {}", e))?;
+        Self::with_connection(connection)
+    }
+
+    /// Usage kept in memory only, discarded when dropped
+    pub fn in_memory() -> Result<Self, String> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open usage database: {}", e))?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| format!("Failed to create usage table: {}", e))?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.connection
+            .lock()
+            .map_err(|e| format!("Failed to access usage statistics: {}", e))
+    }
+
+    /// Record a completed dictation
+    pub fn record(
+        &self,
+        timestamp: DateTime<Utc>,
+        text: &str,
+        speaking_ms: Option<u64>,
+        provider: Option<&str>,
+    ) -> Result<(), String> {
+        self.lock()?
+            .execute(
+                "INSERT INTO usage (timestamp_ms, words, speaking_ms, provider)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    timestamp.timestamp_millis(),
+                    to_sql_int(count_words(text)),
+                    to_sql_int(speaking_ms.unwrap_or_default()),
+                    provider,
+                ],
+            )
+            .map_err(|e| format!("Failed to record usage: {}", e))?;
+        Ok(())
+    }
+
+    /// Totals over `period`, ending at `now`
+    pub fn stats(&self, period: UsagePeriod, now: DateTime<Utc>) -> Result<UsageStats, String> {
+        let since_ms = period
+            .since(now)
+            .map_or(i64::MIN, |since| since.timestamp_millis());
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT provider, COUNT(*), SUM(words), SUM(speaking_ms) FROM usage
+                 WHERE timestamp_ms >= ?1
+                 GROUP BY provider ORDER BY COUNT(*) DESC, provider",
+            )
+            .map_err(|e| format!("Failed to read usage statistics: {}", e))?;
+        let by_provider = statement
+            .query_map([since_ms], |row| {
+                Ok(ProviderUsage {
+                    provider: row.get(0)?,
+                    dictations: from_sql_int(row.get(1)?),
+                    words: from_sql_int(row.get(2)?),
+                    speaking_ms: from_sql_int(row.get(3)?),
+                })
+            })
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to read usage statistics: {}", e))?;
+
+        let mut stats = UsageStats {
+            period,
+            ..UsageStats::default()
+        };
+        for usage in &by_provider {
+            stats.dictations += usage.dictations;
+            stats.words += usage.words;
+            stats.speaking_ms += usage.speaking_ms;
+        }
+        stats.time_saved_ms = time_saved_ms(stats.words, stats.speaking_ms);
+        stats.by_provider = by_provider;
+        Ok(stats)
+    }
+
+    /// Delete all recorded usage
+    pub fn reset(&self) -> Result<(), String> {
+        self.lock()?
+            .execute("DELETE FROM usage", [])
+            .map_err(|e| format!("Failed to reset usage statistics: {}", e))?;
+        Ok(())
+    }
+}
+
+/// SQLite integers are signed, so clamp sizes that don't fit
+fn to_sql_int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn from_sql_int(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or_default()
+}
@@ -0,0 +1,90 @@
+//! Silence detection for stopping toggle-mode recordings automatically.
+//!
+//! Audio is split into short frames and a frame counts as speech when its RMS energy
+//! clears both a fixed minimum and a multiple of the running noise floor. Keystrokes
+//! and other short clicks don't count, because several speech frames in a row are
+//! needed before the silence timer resets.
+
+use crate::stt::AudioFormat;
+use std::time::Duration;
+
+/// Length of each analysed frame
+const FRAME_DURATION: Duration = Duration::from_millis(30);
+
+/// Frames quieter than this are never speech, however low the noise floor is
+const MIN_SPEECH_RMS: f32 = 0.02;
+
+/// How far above the noise floor a frame must be to count as speech
+const NOISE_FLOOR_MULTIPLIER: f32 = 3.0;
+
+/// How quickly the noise floor follows quieter and louder frames
+const NOISE_FLOOR_FALL: f32 = 0.5;
+const NOISE_FLOOR_RISE: f32 = 0.01;
+
+/// Consecutive speech frames (90 ms) needed before the silence timer resets
+const MIN_SPEECH_FRAMES: u32 = 3;
+
+/// Tracks how long the audio of the current recording has been silent
+#[derive(Debug)]
+pub struct SilenceDetector {
+    /// Interleaved samples per frame
+    frame_len: usize,
+    frame: Vec<f32>,
+    silence_limit: Duration,
+    silent_for: Duration,
+    speech_run: u32,
+    noise_floor: f32,
+}
+
+impl SilenceDetector {
+    pub fn new(format: AudioFormat, silence_limit: Duration) -> Self {
+        let frames_per_second = 1000 / FRAME_DURATION.as_millis() as usize;
+        let frame_len = (format.sample_rate as usize * format.channels.max(1) as usize
+            / frames_per_second)
+            .max(1);
+        Self {
+            frame_len,
+            frame: Vec::with_capacity(frame_len),
+            silence_limit,
+            silent_for: Duration::ZERO,
+            speech_run: 0,
+            noise_floor: MIN_SPEECH_RMS / NOISE_FLOOR_MULTIPLIER,
+        }
+    }
+
+    /// Feed interleaved samples. Returns true once the audio has been silent for the
+    /// silence limit, counting from the start of the recording if nothing was said.
+    pub fn push(&mut self, samples: &[f32]) -> bool {
+        for &sample in samples {
+            self.frame.push(sample);
+            if self.frame.len() == self.frame_len {
+                self.process_frame();
+                self.frame.clear();
+            }
+        }
+        self.silent_for >= self.silence_limit
+    }
+
+    fn process_frame(&mut self) {
+        let rms = (self.frame.iter().map(|s| s * s).sum::<f32>() / self.frame.len() as f32).sqrt();
+        let is_speech = rms >= MIN_SPEECH_RMS && rms >= self.noise_floor * NOISE_FLOOR_MULTIPLIER;
+
+        let rate = if rms < self.noise_floor {
+            NOISE_FLOOR_FALL
+        } else {
+            NOISE_FLOOR_RISE
+        };
+        self.noise_floor += (rms - self.noise_floor) * rate;
+
+        if is_speech {
+            self.speech_run += 1;
+            if self.speech_run >= MIN_SPEECH_FRAMES {
+                self.silent_for = Duration::ZERO;
+                return;
+            }
+        } else {
+            self.speech_run = 0;
+        }
+        self.silent_for += FRAME_DURATION;
+    }
+}
@@ -0,0 +1,147 @@
+//! Voice commands spoken while dictating.
+//!
+//! After snippets are expanded, phrases like "new paragraph", "press enter" or
+//! "scratch that" are turned into edits instead of being typed: line breaks in the
+//! text, key presses between parts of it, or deleting the previous dictation. The
+//! phrases and what they do are configurable; text around a command loses the
+//! spaces and punctuation a formatter put next to it.
+
+use crate::phrases::{find_phrases, phrase_words, trailing_punctuation_len};
+use serde::{Deserialize, Serialize};
+
+/// What a voice command does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceCommandAction {
+    NewLine,
+    NewParagraph,
+    PressEnter,
+    PressTab,
+    /// Drop the text dictated before the command, or the previous dictation if
+    /// the command starts this one
+    DeleteThat,
+    /// Press the undo shortcut
+    Undo,
+}
+
+/// A spoken phrase and the action it triggers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceCommand {
+    pub phrase: String,
+    pub action: VoiceCommandAction,
+}
+
+/// Commands used until the list is customized
+pub fn default_voice_commands() -> Vec<VoiceCommand> {
+    [
+        ("new line", VoiceCommandAction::NewLine),
+        ("new paragraph", VoiceCommandAction::NewParagraph),
+        ("press enter", VoiceCommandAction::PressEnter),
+        ("press tab", VoiceCommandAction::PressTab),
+        ("scratch that", VoiceCommandAction::DeleteThat),
+        ("delete that", VoiceCommandAction::DeleteThat),
+        ("undo that", VoiceCommandAction::Undo),
+    ]
+    .into_iter()
+    .map(|(phrase, action)| VoiceCommand {
+        phrase: phrase.to_string(),
+        action,
+    })
+    .collect()
+}
+
+/// A key pressed by a voice command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandKey {
+    Enter,
+    Tab,
+    Undo,
+}
+
+/// One step of inserting a dictation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictationEdit {
+    Insert(String),
+    Press(CommandKey),
+    /// Remove the previous dictation from the focused app
+    DeletePrevious,
+}
+
+/// Turn a dictation into the edits that insert it, with voice commands carried out
+/// rather than typed. Without commands in it, the dictation is one insert.
+pub fn interpret_voice_commands(text: &str, commands: &[VoiceCommand]) -> Vec<DictationEdit> {
+    let phrases: Vec<Vec<String>> = commands
+        .iter()
+        .map(|command| phrase_words(&command.phrase))
+        .collect();
+
+    let mut edits = Vec::new();
+    let mut pending = String::new();
+    let mut copied_to = 0;
+    for found in find_phrases(text, &phrases) {
+        push_dictated(&mut pending, &text[copied_to..found.start]);
+        copied_to = found.end + trailing_punctuation_len(&text[found.end..]);
+
+        let key = match commands[found.phrase].action {
+            VoiceCommandAction::NewLine => {
+                trim_spaces_end(&mut pending);
+                pending.push('\n');
+                continue;
+            }
+            VoiceCommandAction::NewParagraph => {
+                trim_spaces_end(&mut pending);
+                pending.push_str("\n\n");
+                continue;
+            }
+            VoiceCommandAction::DeleteThat => {
+                if pending.trim().is_empty() && edits.is_empty() {
+                    edits.push(DictationEdit::DeletePrevious);
+                }
+                pending.clear();
+                continue;
+            }
+            VoiceCommandAction::PressEnter => CommandKey::Enter,
+            VoiceCommandAction::PressTab => CommandKey::Tab,
+            VoiceCommandAction::Undo => CommandKey::Undo,
+        };
+        flush_insert(&mut edits, &mut pending);
+        edits.push(DictationEdit::Press(key));
+    }
+    push_dictated(&mut pending, &text[copied_to..]);
+    flush_insert(&mut edits, &mut pending);
+    edits
+}
+
+/// Text that ends up in the focused app from these edits, for history
+pub fn inserted_text(edits: &[DictationEdit]) -> String {
+    edits
+        .iter()
+        .filter_map(|edit| match edit {
+            DictationEdit::Insert(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Add dictated text to what is waiting to be inserted, without the spaces left
+/// where a command was removed
+fn push_dictated(pending: &mut String, text: &str) {
+    if pending.is_empty() || pending.ends_with('\n') {
+        pending.push_str(text.trim_start());
+    } else {
+        pending.push_str(text);
+    }
+}
+
+/// Drop spaces, but not line breaks, from the end of the text
+fn trim_spaces_end(text: &mut String) {
+    let trimmed = text.trim_end_matches(|c: char| c.is_whitespace() && c != '\n');
+    text.truncate(trimmed.len());
+}
+
+fn flush_insert(edits: &mut Vec<DictationEdit>, pending: &mut String) {
+    trim_spaces_end(pending);
+    if !pending.is_empty() {
+        edits.push(DictationEdit::Insert(std::mem::take(pending)));
+    }
+}
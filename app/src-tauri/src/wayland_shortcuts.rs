@@ -0,0 +1,252 @@
+//! Global hotkeys on Wayland through the xdg-desktop-portal GlobalShortcuts interface.
+//!
+//! Wayland compositors don't let apps grab keys, so the OS shortcut API registers
+//! nothing there. Instead the hotkeys are bound in a portal session, which asks the
+//! user to confirm them the first time, and the compositor reports each press and
+//! release. Binding again closes the previous session.
+
+use crate::commands::settings::ShortcutFailures;
+use crate::settings::{HotkeyAction, HotkeyConfig, ShortcutRegistrationFailure};
+use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+use futures_util::future::{self, Either};
+use futures_util::{stream, StreamExt};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::oneshot;
+
+/// Whether the app runs in a Wayland session, given `WAYLAND_DISPLAY`
+pub fn is_wayland(wayland_display: Option<&str>) -> bool {
+    wayland_display.is_some_and(|display| !display.trim().is_empty())
+}
+
+/// Whether hotkeys should go through the portal instead of the OS shortcut API
+pub fn is_wayland_session() -> bool {
+    is_wayland(std::env::var("WAYLAND_DISPLAY").ok().as_deref())
+}
+
+/// Convert a hotkey to a portal trigger like "CTRL+ALT+space" (modifiers followed
+/// by an XKB key name). `None` for keys with no known XKB name.
+pub fn portal_trigger(hotkey: &HotkeyConfig) -> Option<String> {
+    let mut parts = Vec::new();
+    for modifier in &hotkey.modifiers {
+        parts.push(match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => "CTRL",
+            "alt" | "option" => "ALT",
+            "shift" => "SHIFT",
+            "meta" | "super" | "cmd" | "command" => "LOGO",
+            _ => return None,
+        });
+    }
+    let key = xkb_key_name(&hotkey.key)?;
+    Some(
+        parts
+            .into_iter()
+            .map(str::to_string)
+            .chain(std::iter::once(key))
+            .collect::<Vec<_>>()
+            .join("+"),
+    )
+}
+
+/// XKB name of a key stored in the hotkey format ("Space", "P", "Digit1", "F5", ...)
+fn xkb_key_name(key: &str) -> Option<String> {
+    let key = key
+        .strip_prefix("Key")
+        .or_else(|| key.strip_prefix("Digit"))
+        .filter(|rest| rest.len() == 1)
+        .unwrap_or(key);
+    if key.len() == 1 && key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(key.to_lowercase());
+    }
+    if key.len() > 1
+        && key.starts_with(['F', 'f'])
+        && key[1..].parse::<u8>().is_ok_and(|n| (1..=24).contains(&n))
+    {
+        return Some(key.to_uppercase());
+    }
+    let name = match key.to_lowercase().as_str() {
+        "space" => "space",
+        "backquote" => "grave",
+        "minus" => "minus",
+        "equal" => "equal",
+        "bracketleft" => "bracketleft",
+        "bracketright" => "bracketright",
+        "backslash" => "backslash",
+        "semicolon" => "semicolon",
+        "quote" => "apostrophe",
+        "comma" => "comma",
+        "period" => "period",
+        "slash" => "slash",
+        "enter" => "Return",
+        "tab" => "Tab",
+        "escape" => "Escape",
+        "backspace" => "BackSpace",
+        "delete" => "Delete",
+        "insert" => "Insert",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" => "Prior",
+        "pagedown" => "Next",
+        "arrowup" => "Up",
+        "arrowdown" => "Down",
+        "arrowleft" => "Left",
+        "arrowright" => "Right",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+/// What the hotkey does, shown by the compositor when asking to confirm it
+fn description(action: HotkeyAction) -> &'static str {
+    match action {
+        HotkeyAction::Toggle => "Start or stop recording",
+        HotkeyAction::Hold => "Record while held",
+        HotkeyAction::PasteLast => "Paste the last transcription",
+        HotkeyAction::CycleHistory => "Paste older transcriptions",
+        HotkeyAction::CycleLanguage => "Switch dictation language",
+        HotkeyAction::PauseResume => "Pause or resume recording",
+        HotkeyAction::RetryLast => "Retry the last transcription",
+        HotkeyAction::ClipboardOnly => "Send the dictation to the clipboard only",
+        HotkeyAction::CycleSttProvider => "Switch speech-to-text provider",
+        HotkeyAction::ToggleFormatting => "Turn LLM formatting on or off",
+        HotkeyAction::CyclePromptPreset => "Switch formatting prompt preset",
+        HotkeyAction::OpenPalette => "Open the quick actions palette",
+        HotkeyAction::DoNotDisturb => "Pause or resume dictation",
+    }
+}
+
+/// The hotkeys the portal can bind, and a failure for each one it can't. Sequences,
+/// mouse buttons and double taps are keyboard listener features the portal lacks.
+pub fn portal_shortcuts(
+    hotkeys: &[(HotkeyAction, HotkeyConfig)],
+) -> (
+    Vec<(HotkeyAction, String)>,
+    Vec<ShortcutRegistrationFailure>,
+) {
+    let mut shortcuts = Vec::new();
+    let mut failures = Vec::new();
+    for (action, hotkey) in hotkeys {
+        let trigger = hotkey
+            .is_plain_keyboard()
+            .then(|| portal_trigger(hotkey))
+            .flatten();
+        match trigger {
+            Some(trigger) => shortcuts.push((*action, trigger)),
+            None => failures.push(ShortcutRegistrationFailure {
+                action: action.id().to_string(),
+                shortcut: hotkey.describe(),
+                error: "Not supported by Wayland global shortcuts".to_string(),
+            }),
+        }
+    }
+    (shortcuts, failures)
+}
+
+/// The portal session holding the bound hotkeys
+#[derive(Default)]
+pub struct WaylandShortcuts {
+    /// Ends the running session when sent or dropped
+    close_session: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl WaylandShortcuts {
+    /// Bind the hotkeys in a new portal session, replacing any previous one. Failures
+    /// are logged and emitted as `shortcut-registration-failed`.
+    pub fn bind(&self, app: &AppHandle, hotkeys: Vec<(HotkeyAction, String)>) {
+        let (close_tx, close_rx) = oneshot::channel();
+        if let Ok(mut close_session) = self.close_session.lock() {
+            *close_session = Some(close_tx);
+        }
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = run_session(&app, &hotkeys, close_rx).await {
+                log::error!("Wayland global shortcuts failed: {}", e);
+                let failures: Vec<ShortcutRegistrationFailure> = hotkeys
+                    .iter()
+                    .map(|(action, trigger)| ShortcutRegistrationFailure {
+                        action: action.id().to_string(),
+                        shortcut: trigger.clone(),
+                        error: e.clone(),
+                    })
+                    .collect();
+                app.state::<ShortcutFailures>().set(&failures);
+                let _ = app.emit("shortcut-registration-failed", &failures);
+            }
+        });
+    }
+
+    /// Close the portal session, e.g. while a new hotkey is being captured
+    pub fn unbind(&self) {
+        if let Ok(mut close_session) = self.close_session.lock() {
+            close_session.take();
+        }
+    }
+}
+
+async fn run_session(
+    app: &AppHandle,
+    hotkeys: &[(HotkeyAction, String)],
+    mut close_rx: oneshot::Receiver<()>,
+) -> Result<(), String> {
+    let portal = GlobalShortcuts::new()
+        .await
+        .map_err(|e| format!("GlobalShortcuts portal unavailable: {}", e))?;
+    let session = portal
+        .create_session()
+        .await
+        .map_err(|e| format!("Failed to create shortcuts session: {}", e))?;
+
+    let shortcuts: Vec<NewShortcut> = hotkeys
+        .iter()
+        .map(|(action, trigger)| {
+            NewShortcut::new(action.id(), description(*action))
+                .preferred_trigger(Some(trigger.as_str()))
+        })
+        .collect();
+    portal
+        .bind_shortcuts(&session, &shortcuts, None)
+        .await
+        .and_then(|request| request.response())
+        .map_err(|e| format!("Failed to bind shortcuts: {}", e))?;
+    log::info!("Bound {} hotkeys through the Wayland portal", hotkeys.len());
+
+    let activated = portal
+        .receive_activated()
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|event| (event.shortcut_id().to_string(), true));
+    let deactivated = portal
+        .receive_deactivated()
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|event| (event.shortcut_id().to_string(), false));
+    let mut events = Box::pin(stream::select(activated, deactivated));
+
+    loop {
+        match future::select(events.next(), &mut close_rx).await {
+            Either::Left((Some((id, pressed)), _)) => {
+                let Some(action) = HotkeyAction::from_id(&id) else {
+                    continue;
+                };
+                // Actions block while recording starts or text is inserted
+                let app = app.clone();
+                let _ = tauri::async_runtime::spawn_blocking(move || {
+                    crate::run_hotkey_action(&app, action, pressed, "Wayland")
+                })
+                .await;
+            }
+            Either::Left((None, _)) | Either::Right(_) => break,
+        }
+    }
+
+    let _ = session.close().await;
+    log::info!("Closed the Wayland shortcuts session");
+    Ok(())
+}
+
+/// Bind the configured hotkeys through the portal. Returns the hotkeys it can't bind.
+pub fn register_from_store(app: &AppHandle) -> Vec<ShortcutRegistrationFailure> {
+    let (shortcuts, failures) = portal_shortcuts(&crate::sequence::configured_hotkeys(app));
+    app.state::<WaylandShortcuts>().bind(app, shortcuts);
+    failures
+}
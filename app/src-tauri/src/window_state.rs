@@ -0,0 +1,145 @@
+//! Size, position and maximized state of app windows across launches.
+//!
+//! Each window's geometry is kept by label in `window-state.json` in the app data
+//! directory, updated as windows are moved or resized and saved when they lose
+//! focus or close. The recording overlay sizes itself to its contents and saves
+//! its position with the overlay settings, so it isn't tracked here.
+
+use crate::overlay::MonitorArea;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Window state file in the app data directory
+pub const WINDOW_STATE_FILE: &str = "window-state.json";
+
+/// How far below its top edge a window must be on a monitor to be restored where
+/// it was, so its title bar can be grabbed, in physical pixels
+const TITLE_BAR_HEIGHT: f64 = 32.0;
+
+/// Geometry of a window that isn't maximized or minimized, in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    /// Outer position
+    pub x: i32,
+    pub y: i32,
+    /// Inner size
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Saved state of one window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowState {
+    /// Where the window is when not maximized
+    pub geometry: WindowGeometry,
+    pub maximized: bool,
+}
+
+/// Whether enough of a window at `geometry` would be on one of `monitors` to
+/// grab its title bar. Windows on a monitor that has since been unplugged aren't.
+pub fn is_on_screen(geometry: &WindowGeometry, monitors: &[MonitorArea]) -> bool {
+    let x = f64::from(geometry.x) + f64::from(geometry.width) / 2.0;
+    let y = f64::from(geometry.y) + TITLE_BAR_HEIGHT / 2.0;
+    monitors.iter().any(|monitor| {
+        x >= monitor.x
+            && x < monitor.x + monitor.width
+            && y >= monitor.y
+            && y < monitor.y + monitor.height
+    })
+}
+
+/// Saved window states by label, written to the app data directory
+pub struct WindowStateStore {
+    path: Option<PathBuf>,
+    states: Mutex<BTreeMap<String, WindowState>>,
+}
+
+impl WindowStateStore {
+    /// Load the saved states, starting over if the file is missing or unreadable
+    pub fn new(app_data_dir: &Path) -> Self {
+        let path = app_data_dir.join(WINDOW_STATE_FILE);
+        let states = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable window state: {}", e);
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+        Self {
+            path: Some(path),
+            states: Mutex::new(states),
+        }
+    }
+
+    /// States kept in memory only, discarded when dropped
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            states: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn get(&self, label: &str) -> Option<WindowState> {
+        self.states.lock().ok()?.get(label).copied()
+    }
+
+    /// Remember a window's geometry, or only that it is maximized when `geometry`
+    /// is `None`, keeping where it goes when unmaximized
+    pub fn update(&self, label: &str, geometry: Option<WindowGeometry>, maximized: bool) {
+        let Ok(mut states) = self.states.lock() else {
+            return;
+        };
+        match (states.get_mut(label), geometry) {
+            (Some(state), geometry) => {
+                state.geometry = geometry.unwrap_or(state.geometry);
+                state.maximized = maximized;
+            }
+            (None, Some(geometry)) => {
+                states.insert(
+                    label.to_string(),
+                    WindowState {
+                        geometry,
+                        maximized,
+                    },
+                );
+            }
+            // Nothing to restore the window to yet
+            (None, None) => {}
+        }
+    }
+
+    /// Write the states to the app data directory
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let content = {
+            let states = self
+                .states
+                .lock()
+                .map_err(|e| format!("Failed to access window state: {}", e))?;
+            serde_json::to_vec_pretty(&*states)
+                .map_err(|e| format!("Failed to serialize window state: {}", e))?
+        };
+        crate::integrity::write_atomic(path, &content)
+            .map_err(|e| format!("Failed to save window state: {}", e))
+    }
+
+    /// Forget every window's state and delete the file
+    pub fn reset(&self) -> Result<(), String> {
+        if let Ok(mut states) = self.states.lock() {
+            states.clear();
+        }
+        if let Some(path) = &self.path {
+            match fs::remove_file(path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to delete window state: {}", e)),
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,45 @@
-import { Kbd, Loader, NavLink, Text, Title, Tooltip } from "@mantine/core";
+import {
+	ActionIcon,
+	Button,
+	Kbd,
+	Loader,
+	NavLink,
+	Text,
+	Title,
+	Tooltip,
+} from "@mantine/core";
 import { notifications } from "@mantine/notifications";
-import { Home, Settings } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { Home, PauseCircle, Settings } from "lucide-react";
 import { useEffect, useState } from "react";
+import { DataFileIssuesNotice } from "./components/DataFileIssuesNotice";
+import { DictionarySuggestionList } from "./components/DictionarySuggestionList";
 import { HistoryFeed } from "./components/HistoryFeed";
 import { Logo } from "./components/Logo";
+import { PendingTranscriptionsNotice } from "./components/PendingTranscriptionsNotice";
+import { UsageStatsCard } from "./components/UsageStatsCard";
 import {
 	AudioSettings,
+	BlockedAppsSettings,
 	ConnectionSettings,
+	DictionarySettings,
+	ExperimentalSettings,
 	HotkeySettings,
+	InsertionSettings,
+	LanguageSettings,
+	NotesSettings,
+	OutputStyleSettings,
+	OverlaySettings,
 	PromptSettings,
 	ProvidersSettings,
+	RecordingSettings,
+	RemoteControlSettings,
+	SnippetSettings,
+	TelemetrySettings,
+	TextTransformSettings,
+	TroubleshootingSettings,
+	UpdateSettings,
+	VoiceCommandSettings,
 } from "./components/settings";
 import {
 	DEFAULT_HOLD_HOTKEY,
@@ -18,8 +48,10 @@ import {
 } from "./lib/hotkeyDefaults";
 import {
 	useAvailableProvidersListener,
+	useDictationPaused,
 	useRefreshServerQueriesOnConnect,
 	useSettings,
+	useSwitchDictationPaused,
 } from "./lib/queries";
 import { type ConfigResponse, type HotkeyConfig, tauriAPI } from "./lib/tauri";
 import { useRecordingStore } from "./stores/recordingStore";
@@ -27,11 +59,14 @@ import "./app-main.css";
 
 type View = "home" | "settings";
 
+const MIC_PERMISSION_NOTIFICATION_ID = "mic-permission";
+
 function ConnectionStatusIndicator() {
 	const state = useRecordingStore((s) => s.state);
 	const setState = useRecordingStore((s) => s.setState);
 
-	// Listen for connection state changes from the overlay window
+	// Listen for connection state changes, starting from the backend's status
+	// since the window may open long after the overlay connected
 	useEffect(() => {
 		let unlisten: (() => void) | undefined;
 
@@ -39,6 +74,8 @@ function ConnectionStatusIndicator() {
 			unlisten = await tauriAPI.onConnectionStateChanged((newState) => {
 				setState(newState);
 			});
+			const status = await tauriAPI.getConnectionStatus();
+			setState(status.state);
 		};
 
 		setup();
@@ -73,6 +110,32 @@ function ConnectionStatusIndicator() {
 	);
 }
 
+/** Pauses dictation for screen sharing and shows when it is paused */
+function DictationPauseToggle() {
+	const { data: paused = false } = useDictationPaused();
+	const switchPaused = useSwitchDictationPaused();
+
+	return (
+		<Tooltip
+			label={paused ? "Dictation paused, click to resume" : "Pause dictation"}
+			position="right"
+			withArrow
+		>
+			<ActionIcon
+				variant={paused ? "filled" : "subtle"}
+				color={paused ? "orange" : "gray"}
+				size="sm"
+				onClick={() => switchPaused.mutate()}
+				loading={switchPaused.isPending}
+				aria-label={paused ? "Resume dictation" : "Pause dictation"}
+				style={{ marginBottom: 8 }}
+			>
+				<PauseCircle size={16} />
+			</ActionIcon>
+		</Tooltip>
+	);
+}
+
 function Sidebar({
 	activeView,
 	onViewChange,
@@ -110,6 +173,7 @@ function Sidebar({
 			</nav>
 
 			<footer className="sidebar-footer">
+				<DictationPauseToggle />
 				<ConnectionStatusIndicator />
 				<p className="sidebar-footer-text">v0.1.0</p>
 			</footer>
@@ -131,6 +195,21 @@ function HotkeyDisplay({ config }: { config: HotkeyConfig }) {
 					{index < parts.length - 1 && <span className="kbd-plus">+</span>}
 				</span>
 			))}
+			{config.trigger === "double_tap" && <span className="kbd-plus">×2</span>}
+			{config.then && (
+				<>
+					<span className="kbd-plus">then</span>
+					{config.then.modifiers
+						.map((m) => m.charAt(0).toUpperCase() + m.slice(1))
+						.concat([config.then.key])
+						.map((part, index, all) => (
+							<span key={`then-${part}`}>
+								<Kbd>{part}</Kbd>
+								{index < all.length - 1 && <span className="kbd-plus">+</span>}
+							</span>
+						))}
+				</>
+			)}
 		</span>
 	);
 }
@@ -142,6 +221,16 @@ function InstructionsCard() {
 	const holdHotkey = settings?.hold_hotkey ?? DEFAULT_HOLD_HOTKEY;
 	const pasteLastHotkey =
 		settings?.paste_last_hotkey ?? DEFAULT_PASTE_LAST_HOTKEY;
+	const cycleHistoryHotkey = settings?.cycle_history_hotkey ?? null;
+	const cycleLanguageHotkey = settings?.cycle_language_hotkey ?? null;
+	const pauseResumeHotkey = settings?.pause_resume_hotkey ?? null;
+	const retryLastHotkey = settings?.retry_last_hotkey ?? null;
+	const clipboardOnlyHotkey = settings?.clipboard_only_hotkey ?? null;
+	const cycleSttProviderHotkey = settings?.cycle_stt_provider_hotkey ?? null;
+	const toggleFormattingHotkey = settings?.toggle_formatting_hotkey ?? null;
+	const cyclePromptPresetHotkey = settings?.cycle_prompt_preset_hotkey ?? null;
+	const openPaletteHotkey = settings?.open_palette_hotkey ?? null;
+	const doNotDisturbHotkey = settings?.do_not_disturb_hotkey ?? null;
 
 	return (
 		<div className="instructions-card animate-in">
@@ -150,7 +239,13 @@ function InstructionsCard() {
 				<div className="instruction-method">
 					<span className="instruction-label">Toggle:</span>
 					<HotkeyDisplay config={toggleHotkey} />
-					<span className="instruction-desc">Press to start/stop</span>
+					<span className="instruction-desc">
+						{toggleHotkey.trigger === "double_tap"
+							? "Double-tap to start/stop"
+							: toggleHotkey.trigger === "media_key"
+								? "Press or tap your headset to start/stop"
+								: "Press to start/stop"}
+					</span>
 				</div>
 				<div className="instruction-method">
 					<span className="instruction-label">Hold:</span>
@@ -162,6 +257,76 @@ function InstructionsCard() {
 					<HotkeyDisplay config={pasteLastHotkey} />
 					<span className="instruction-desc">Paste last result</span>
 				</div>
+				{cycleHistoryHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Cycle:</span>
+						<HotkeyDisplay config={cycleHistoryHotkey} />
+						<span className="instruction-desc">Paste older results</span>
+					</div>
+				)}
+				{cycleLanguageHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Language:</span>
+						<HotkeyDisplay config={cycleLanguageHotkey} />
+						<span className="instruction-desc">Switch language</span>
+					</div>
+				)}
+				{pauseResumeHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Pause:</span>
+						<HotkeyDisplay config={pauseResumeHotkey} />
+						<span className="instruction-desc">Pause or resume recording</span>
+					</div>
+				)}
+				{retryLastHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Retry:</span>
+						<HotkeyDisplay config={retryLastHotkey} />
+						<span className="instruction-desc">Transcribe the last again</span>
+					</div>
+				)}
+				{clipboardOnlyHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Clipboard:</span>
+						<HotkeyDisplay config={clipboardOnlyHotkey} />
+						<span className="instruction-desc">Copy instead of typing</span>
+					</div>
+				)}
+				{cycleSttProviderHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Provider:</span>
+						<HotkeyDisplay config={cycleSttProviderHotkey} />
+						<span className="instruction-desc">Switch STT provider</span>
+					</div>
+				)}
+				{toggleFormattingHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Formatting:</span>
+						<HotkeyDisplay config={toggleFormattingHotkey} />
+						<span className="instruction-desc">Turn LLM cleanup on/off</span>
+					</div>
+				)}
+				{cyclePromptPresetHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Prompt:</span>
+						<HotkeyDisplay config={cyclePromptPresetHotkey} />
+						<span className="instruction-desc">Switch prompt preset</span>
+					</div>
+				)}
+				{openPaletteHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Actions:</span>
+						<HotkeyDisplay config={openPaletteHotkey} />
+						<span className="instruction-desc">Open quick actions</span>
+					</div>
+				)}
+				{doNotDisturbHotkey && (
+					<div className="instruction-method">
+						<span className="instruction-label">Do not disturb:</span>
+						<HotkeyDisplay config={doNotDisturbHotkey} />
+						<span className="instruction-desc">Pause or resume dictation</span>
+					</div>
+				)}
 			</div>
 			<p className="instructions-card-text">
 				Speak clearly and your words will be typed wherever your cursor is. The
@@ -185,6 +350,12 @@ function HomeView() {
 
 			<InstructionsCard />
 
+			<DataFileIssuesNotice />
+
+			<PendingTranscriptionsNotice />
+
+			<UsageStatsCard />
+
 			<HistoryFeed />
 		</div>
 	);
@@ -203,10 +374,26 @@ function SettingsView() {
 			</header>
 
 			<ProvidersSettings />
+			<LanguageSettings />
 			<AudioSettings />
+			<RecordingSettings />
 			<HotkeySettings />
+			<OverlaySettings />
+			<InsertionSettings />
+			<BlockedAppsSettings />
+			<NotesSettings />
 			<PromptSettings />
+			<DictionarySettings />
+			<SnippetSettings />
+			<VoiceCommandSettings />
+			<OutputStyleSettings />
+			<TextTransformSettings />
 			<ConnectionSettings />
+			<RemoteControlSettings />
+			<UpdateSettings />
+			<TelemetrySettings />
+			<TroubleshootingSettings />
+			<ExperimentalSettings />
 		</div>
 	);
 }
@@ -217,6 +404,7 @@ function formatSettingName(setting: string): string {
 		"llm-provider": "LLM provider",
 		"prompt-sections": "Formatting prompt",
 		"stt-timeout": "STT timeout",
+		"formatting-enabled": "LLM formatting",
 	};
 	return names[setting] ?? setting;
 }
@@ -224,10 +412,17 @@ function formatSettingName(setting: string): string {
 export default function App() {
 	const [activeView, setActiveView] = useState<View>("home");
 	const connectionState = useRecordingStore((s) => s.state);
+	const queryClient = useQueryClient();
 
 	// Listen for available providers from overlay window (must stay mounted)
 	useAvailableProvidersListener();
 
+	// Ask the overlay for providers, since this window can be
+	// opened after the overlay has already connected
+	useEffect(() => {
+		tauriAPI.emitMainWindowReady();
+	}, []);
+
 	// Refresh server-side queries when connection is established
 	useRefreshServerQueriesOnConnect(connectionState);
 
@@ -269,6 +464,290 @@ export default function App() {
 		};
 	}, []);
 
+	// Warn when a shortcut could not be registered because another app owns it
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+
+		tauriAPI
+			.onShortcutRegistrationFailed((failures) => {
+				const shortcuts = failures.map((f) => f.shortcut).join(", ");
+				notifications.show({
+					title: "Hotkey Unavailable",
+					message: `${shortcuts} could not be registered and may be in use by another application`,
+					color: "red",
+					autoClose: false,
+				});
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, []);
+
+	// Tell the user when a recording was cut off at the length limit
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+
+		tauriAPI
+			.onRecordingLimitReached((maxSeconds) => {
+				const minutes = Math.round(maxSeconds / 60);
+				const limit =
+					maxSeconds % 60 === 0
+						? `${minutes} minute${minutes === 1 ? "" : "s"}`
+						: `${maxSeconds} seconds`;
+				notifications.show({
+					title: "Recording Limit Reached",
+					message: `Recording stopped after ${limit} and is being transcribed`,
+					color: "yellow",
+					autoClose: 5000,
+				});
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, []);
+
+	// Tell the user when recording switches microphones because one was plugged
+	// in or unplugged
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+
+		tauriAPI
+			.onMicFallbackChanged((change) => {
+				const device = change.label ?? "Your preferred microphone";
+				notifications.show(
+					change.is_fallback
+						? {
+								title: "Microphone Disconnected",
+								message: `${device} is unavailable, recording from the system default`,
+								color: "yellow",
+								autoClose: 5000,
+							}
+						: {
+								title: "Microphone Switched",
+								message: `Recording from ${device}`,
+								color: "green",
+								autoClose: 3000,
+							},
+				);
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, []);
+
+	// Settings also change outside this window: from the tray menu and hotkeys in
+	// Rust, and in settings.json when it is edited by hand
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onSettingsChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["settings"] });
+				queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	// Tell the user when the connection fails over to a fallback server, or
+	// returns to the configured one
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+		let onFallback = false;
+
+		tauriAPI
+			.onServerEndpointChanged((endpoint) => {
+				queryClient.invalidateQueries({ queryKey: ["serverUrl"] });
+				if (endpoint.priority > 0) {
+					notifications.show({
+						title: "Using Fallback Server",
+						message: `The server is unreachable, connected to ${endpoint.url}`,
+						color: "yellow",
+						autoClose: 5000,
+					});
+				} else if (onFallback) {
+					notifications.show({
+						title: "Server Reconnected",
+						message: `Connected to ${endpoint.url} again`,
+						color: "green",
+						autoClose: 3000,
+					});
+				}
+				onFallback = endpoint.priority > 0;
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	// Tell the user when the server auth token couldn't be refreshed, rather than
+	// letting the connection fail once it expires
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+
+		tauriAPI
+			.onAuthStatusChanged((status) => {
+				if (!status.is_set || !(status.refresh_error || status.is_expired)) {
+					return;
+				}
+				notifications.show({
+					title: status.is_expired
+						? "Sign-in Expired"
+						: "Sign-in Refresh Failed",
+					message:
+						status.refresh_error ??
+						"Enter a new auth token in Connection settings",
+					color: status.is_expired ? "red" : "yellow",
+					autoClose: status.is_expired ? false : 5000,
+				});
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, []);
+
+	// Tell the user when a recording was cancelled because mic access is denied
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+
+		const handleAllow = () => {
+			tauriAPI.requestMicPermission().catch((error: unknown) => {
+				notifications.show({
+					title: "Microphone Access",
+					message: String(error),
+					color: "red",
+				});
+			});
+		};
+
+		tauriAPI
+			.onMicPermissionChanged((granted) => {
+				if (granted) {
+					notifications.hide(MIC_PERMISSION_NOTIFICATION_ID);
+					return;
+				}
+				notifications.show({
+					id: MIC_PERMISSION_NOTIFICATION_ID,
+					title: "Microphone Access Denied",
+					message: (
+						<>
+							<Text size="sm">
+								The recording was cancelled because Tambourine can't use the
+								microphone.
+							</Text>
+							<Button
+								size="compact-xs"
+								variant="light"
+								mt={6}
+								onClick={handleAllow}
+							>
+								Allow microphone
+							</Button>
+						</>
+					),
+					color: "red",
+					autoClose: false,
+				});
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, []);
+
+	// Offer to add words corrected from the overlay to the personal dictionary
+	useEffect(() => {
+		let isMounted = true;
+		let unlisten: (() => void) | undefined;
+
+		tauriAPI
+			.onDictionarySuggested((suggestions) => {
+				queryClient.invalidateQueries({ queryKey: ["dictionarySuggestions"] });
+				notifications.show({
+					title: "Add to Dictionary?",
+					message: <DictionarySuggestionList suggestions={suggestions} />,
+					color: "blue",
+					autoClose: 10000,
+				});
+			})
+			.then((fn) => {
+				if (isMounted) {
+					unlisten = fn;
+				} else {
+					fn();
+				}
+			});
+
+		return () => {
+			isMounted = false;
+			unlisten?.();
+		};
+	}, [queryClient]);
+
 	return (
 		<div className="app-layout">
 			<Sidebar activeView={activeView} onViewChange={setActiveView} />
@@ -1,9 +1,10 @@
-import { Loader } from "@mantine/core";
+import { ActionIcon, Loader } from "@mantine/core";
 import { useResizeObserver, useTimeout } from "@mantine/hooks";
 import {
 	type BotLLMTextData,
 	PipecatClient,
 	RTVIEvent,
+	type TranscriptData,
 } from "@pipecat-ai/client-js";
 import {
 	PipecatClientProvider,
@@ -15,18 +16,44 @@ import { ThemeProvider, UserAudioComponent } from "@pipecat-ai/voice-ui-kit";
 import { useQueryClient } from "@tanstack/react-query";
 import { listen } from "@tauri-apps/api/event";
 import { useDrag } from "@use-gesture/react";
+import {
+	ClipboardCopy,
+	Lock,
+	MicOff,
+	Pause,
+	Pencil,
+	TimerOff,
+} from "lucide-react";
 import { useCallback, useEffect, useRef, useState } from "react";
 import { z } from "zod";
 import Logo from "./assets/logo.svg?react";
+import { CorrectionBox } from "./components/CorrectionBox";
+import {
+	flushLocalCapture,
+	startLocalCapture,
+	stopLocalCapture,
+} from "./lib/localCapture";
+import {
+	getMicPermission,
+	isMicPermissionError,
+	promptForMicPermission,
+} from "./lib/micPermission";
+import { installMicProcessing, setMicProcessing } from "./lib/micProcessing";
 import {
 	useAddHistoryEntry,
-	useServerUrl,
+	useCorrectLastEntry,
+	useInsertDictation,
 	useSettings,
-	useTypeText,
 } from "./lib/queries";
 import {
+	type ActiveApp,
 	type CleanupPromptSections,
 	type ConnectionState,
+	DEFAULT_MIC_GAIN,
+	dictationLanguageHint,
+	type InsertionProgress,
+	LOCAL_WHISPER_PROVIDER,
+	type QueuedAudioRequest,
 	tauriAPI,
 } from "./lib/tauri";
 import { useRecordingStore } from "./stores/recordingStore";
@@ -36,8 +63,97 @@ import "./overlay-global.css";
 const RecordingCompleteMessageSchema = z.object({
 	type: z.literal("recording-complete"),
 	hasContent: z.boolean().optional(),
+	turnId: z.number().nullable().optional(),
+	previewId: z.number().nullish(),
 });
 
+// Sent before a turn's formatted text, naming the turn (or preview) it belongs
+// to and the raw transcript being formatted
+const FormattingStartedMessageSchema = z.object({
+	type: z.literal("formatting-started"),
+	turnId: z.number().nullable(),
+	previewId: z.number().nullish(),
+	rawText: z.string().optional(),
+});
+
+// How long to wait for the server to answer a turn before giving up on it
+const RESPONSE_TIMEOUT_MS = 10000;
+
+// How long after a dictation the overlay offers to correct it
+const CORRECTION_WINDOW_MS = 15000;
+
+// How long the overlay must stay still after a drag before its position is saved
+const OVERLAY_MOVE_SETTLE_MS = 300;
+
+// How long the overlay shows that a recording was stopped at the length limit
+const RECORDING_LIMIT_NOTICE_MS = 5000;
+
+// How long the overlay shows that secure input blocked insertion
+const SECURE_INPUT_NOTICE_MS = 8000;
+const SECURE_INPUT_NOTICE =
+	"Secure input is on, so nothing was inserted. Use paste last once it's off";
+
+// How long the overlay shows what a hotkey or the tray switched, such as the
+// STT provider
+const SWITCH_NOTICE_MS = 2500;
+
+// Samples per message when replaying queued audio to the server (1 s at 16 kHz)
+const QUEUED_AUDIO_CHUNK_SAMPLES = 16000;
+
+// Packets per message when queued audio is Opus (1 s of 20 ms packets)
+const QUEUED_OPUS_CHUNK_PACKETS = 50;
+
+// Turns replayed from the offline queue use negative IDs, so they never collide
+// with live turns, and their results go back to Rust instead of being typed
+function queuedTurnId(previewId: number): number {
+	return -(previewId + 1);
+}
+
+function queuedTurnPreviewId(turnId: number | null): number | null {
+	return turnId !== null && turnId < 0 ? -turnId - 1 : null;
+}
+
+/** Base64 of 16-bit samples as PCM bytes */
+function encodePcm16(samples: number[]): string {
+	const bytes = new Uint8Array(new Int16Array(samples).buffer);
+	let binary = "";
+	for (const byte of bytes) binary += String.fromCharCode(byte);
+	return btoa(binary);
+}
+
+function chunked<T>(items: T[], size: number): T[][] {
+	const chunks: T[][] = [];
+	for (let start = 0; start < items.length; start += size) {
+		chunks.push(items.slice(start, start + size));
+	}
+	return chunks;
+}
+
+/** The audio of each queued-audio message replaying `request`, in order */
+function queuedAudioChunks(
+	request: QueuedAudioRequest,
+): Record<string, unknown>[] {
+	if (request.encoding === "opus") {
+		return chunked(request.packets, QUEUED_OPUS_CHUNK_PACKETS).map(
+			(packets) => ({ encoding: "opus", packets }),
+		);
+	}
+	return chunked(request.samples, QUEUED_AUDIO_CHUNK_SAMPLES).map(
+		(samples) => ({ encoding: "pcm16", audio: encodePcm16(samples) }),
+	);
+}
+
+// Rust captures the focused app when the hotkey is pressed, before the overlay
+// shows, and the turn still starts without it if the lookup fails
+async function getRecordingApp(): Promise<ActiveApp | null> {
+	try {
+		return await tauriAPI.getRecordingApp();
+	} catch (error) {
+		console.warn("[Recording] Could not get the target app:", error);
+		return null;
+	}
+}
+
 // Config response schemas (relayed to main window for notifications)
 const ConfigUpdatedMessageSchema = z.object({
 	type: z.literal("config-updated"),
@@ -71,6 +187,7 @@ const AvailableProvidersMessageSchema = z.object({
 			model: z.string().nullable(),
 		}),
 	),
+	audio_encodings: z.array(z.string()).optional(),
 });
 
 // Non-empty array type for type-safe batched sends
@@ -81,7 +198,10 @@ type ConfigMessage =
 	| { type: "set-prompt-sections"; data: { sections: CleanupPromptSections } }
 	| { type: "set-stt-provider"; data: { provider: string } }
 	| { type: "set-llm-provider"; data: { provider: string } }
-	| { type: "set-stt-timeout"; data: { timeout_seconds: number } };
+	| { type: "set-stt-timeout"; data: { timeout_seconds: number } }
+	| { type: "set-stt-language"; data: { language: string | null } }
+	| { type: "set-short-utterance-max-words"; data: { max_words: number } }
+	| { type: "set-formatting-enabled"; data: { enabled: boolean } };
 
 // Helper to send multiple config messages - only callable with non-empty list
 function sendConfigMessages(
@@ -98,12 +218,19 @@ function RecordingControl() {
 	const queryClient = useQueryClient();
 	const {
 		state,
+		pendingTurns,
 		setClient,
+		setState,
 		startRecording,
 		stopRecording,
 		handleResponse,
+		abandonPendingTurns,
 		handleConnected,
 		handleDisconnected,
+		micPermissionDenied,
+		setMicPermissionDenied,
+		pausedAt,
+		setPaused,
 	} = useRecordingStore();
 
 	// Use Mantine's useResizeObserver hook
@@ -112,14 +239,13 @@ function RecordingControl() {
 	// Ref for tracking drag state
 	const hasDragStartedRef = useRef(false);
 
-	const { data: serverUrl } = useServerUrl();
-	const { data: settings } = useSettings();
+	// Whether the window is being moved by the user rather than by a resize
+	const isUserMovingRef = useRef(false);
 
-	// Track if we've ever connected (to distinguish initial connection from reconnection)
-	const hasConnectedRef = useRef(false);
+	const { data: settings } = useSettings();
 
-	// Track previous server URL to detect changes
-	const previousServerUrlRef = useRef<string | null>(null);
+	// Whether the first connection has been requested from the backend
+	const hasRequestedConnectionRef = useRef(false);
 
 	// Accumulate LLM text chunks (RTVIObserver streams text in chunks)
 	const llmTextAccumulatorRef = useRef("");
@@ -127,63 +253,160 @@ function RecordingControl() {
 	// Track previous settings to detect actual changes (for syncing while connected)
 	const prevSettingsRef = useRef(settings);
 
-	// Connection management: handles initial connection and URL changes
-	// SmallWebRTC handles reconnection internally (3 attempts)
-	useEffect(() => {
-		if (!client || !serverUrl) return;
-
-		const previousUrl = previousServerUrlRef.current;
-		previousServerUrlRef.current = serverUrl;
-
-		// If URL changed, just disconnect - the Disconnected handler will reconnect
-		if (previousUrl && previousUrl !== serverUrl) {
-			console.log(
-				`[Pipecat] Server URL changed from ${previousUrl} to ${serverUrl}`,
-			);
-			client.disconnect();
-			return;
-		}
-
-		// Initial connection only
-		if (!previousUrl) {
-			client
-				.connect({
-					webrtcRequestParams: { endpoint: `${serverUrl}/api/offer` },
-				})
-				.catch((error: unknown) => {
-					console.error("[Pipecat] Connection failed:", error);
+	// Set while showing that the last recording was stopped at the length limit
+	const [limitReached, setLimitReached] = useState(false);
+
+	// Set while showing that macOS secure input blocked the last insertion
+	const [secureInputBlocked, setSecureInputBlocked] = useState(false);
+
+	// Whether the current recording goes to the clipboard only
+	const [clipboardOnly, setClipboardOnly] = useState(false);
+
+	// What a hotkey or the tray just switched, shown briefly
+	const [switchNotice, setSwitchNotice] = useState<{
+		text: string;
+		title: string;
+	} | null>(null);
+
+	// What has been heard so far in the turn being recorded
+	const [partialText, setPartialText] = useState<string | null>(null);
+	const [formattingText, setFormattingText] = useState("");
+
+	// Progress of a chunked insertion of long text (null when not inserting in chunks)
+	const [insertionProgress, setInsertionProgress] =
+		useState<InsertionProgress | null>(null);
+
+	// Turn results are inserted one after another, in the order turns were recorded
+	const turnResultsRef = useRef<Promise<void>>(Promise.resolve());
+	// Turn the server is currently sending formatted text for, and its transcript
+	const formattingTurnIdRef = useRef<number | null>(null);
+	const formattingRawTextRef = useRef<string | null>(null);
+	// Preview the server is formatting, whose result goes back to Rust uninserted
+	const formattingPreviewIdRef = useRef<number | null>(null);
+
+	// Open a session with a server. A failed attempt is reported as a
+	// disconnect, so the backend picks the next server or backs off.
+	const connectToServer = useCallback(
+		async (serverUrl: string) => {
+			if (!client) return;
+			setState("connecting");
+			try {
+				// Servers that require sign-in get the token stored in the keyring
+				const authHeader = await tauriAPI.getAuthHeader().catch(() => null);
+				await client.connect({
+					webrtcRequestParams: {
+						endpoint: `${serverUrl}/api/offer`,
+						headers: authHeader
+							? new Headers({ Authorization: authHeader })
+							: undefined,
+					},
 				});
-		}
-	}, [client, serverUrl]);
+			} catch (error: unknown) {
+				console.error("[Pipecat] Connection failed:", error);
+				handleDisconnected();
+			}
+		},
+		[client, setState, handleDisconnected],
+	);
 
 	// TanStack Query hooks
-	const typeTextMutation = useTypeText();
+	const insertDictation = useInsertDictation();
 	const addHistoryEntry = useAddHistoryEntry();
+	const correctLastEntry = useCorrectLastEntry();
 
-	// Response timeout (10s)
+	// Shortly after a dictation lands, it can be corrected from the overlay.
+	// The text being edited is null while the correction box is closed.
+	const [canCorrect, setCanCorrect] = useState(false);
+	const [correctionText, setCorrectionText] = useState<string | null>(null);
+	const { start: startCorrectionWindow, clear: clearCorrectionWindow } =
+		useTimeout(() => setCanCorrect(false), CORRECTION_WINDOW_MS);
+
+	// Response timeout: if the server stops answering, stop waiting for every turn
 	const { start: startResponseTimeout, clear: clearResponseTimeout } =
 		useTimeout(() => {
-			const currentState = useRecordingStore.getState().state;
-			if (currentState === "processing") {
-				handleResponse(); // Reset to idle
+			const { pendingTurns } = useRecordingStore.getState();
+			if (pendingTurns.length > 0) {
+				console.warn(
+					`[Recording] No response for turns ${pendingTurns.map((turn) => turn.id).join(", ")}`,
+				);
+				abandonPendingTurns();
+				tauriAPI.reportServerTurnsFinished(pendingTurns.length);
+				tauriAPI.reportTurnFailed("The server did not respond");
 			}
-		}, 10000);
+		}, RESPONSE_TIMEOUT_MS);
+
+	// Insert a turn's result (if any) once earlier turns are done, then complete it
+	const completeTurn = useCallback(
+		(text: string | null, turnId: number | null, rawText?: string) => {
+			clearResponseTimeout();
+			turnResultsRef.current = turnResultsRef.current.then(async () => {
+				const { pendingTurns } = useRecordingStore.getState();
+				const isCancelled =
+					turnId !== null && !pendingTurns.some((turn) => turn.id === turnId);
+				if (isCancelled) {
+					// The server had already finished this turn when it was cancelled
+					console.debug(
+						`[Recording] Ignoring result for cancelled turn ${turnId}`,
+					);
+					if (pendingTurns.length > 0) {
+						startResponseTimeout();
+					}
+					return;
+				}
+
+				// History gets what was inserted, after snippets and voice commands
+				let inserted = text;
+				if (text) {
+					try {
+						inserted = await insertDictation.mutateAsync(text);
+					} catch (error) {
+						console.error("[Pipecat] Failed to type text:", error);
+						tauriAPI.reportTurnFailed(String(error), "insertion");
+					}
+				}
+				const turn = handleResponse(turnId);
+				if (inserted) {
+					addHistoryEntry.mutate({
+						text: inserted,
+						durationMs: turn?.durationMs,
+						rawText,
+					});
+				}
+				if (turn !== null) {
+					console.debug(`[Recording] Turn ${turn.id} complete`);
+					tauriAPI.reportServerTurnsFinished(1);
+				}
+				// Keep waiting for any turns still in flight
+				if (useRecordingStore.getState().pendingTurns.length > 0) {
+					startResponseTimeout();
+				}
+			});
+		},
+		[
+			clearResponseTimeout,
+			startResponseTimeout,
+			insertDictation,
+			addHistoryEntry,
+			handleResponse,
+		],
+	);
 
 	// Keep store client in sync
 	useEffect(() => {
 		setClient(client ?? null);
 	}, [client, setClient]);
 
-	// Emit connection state changes to other windows (main window)
+	// Report connection state changes to the backend, which shares them with
+	// other windows and reconnects when the connection drops
 	useEffect(() => {
 		const unsubscribe = useRecordingStore.subscribe((newState, prevState) => {
 			if (newState.state !== prevState.state) {
-				tauriAPI.emitConnectionState(newState.state as ConnectionState);
+				tauriAPI.reportConnectionState(newState.state as ConnectionState);
 			}
 		});
-		// Emit initial state (get from store directly to avoid dependency issues)
+		// Report initial state (get from store directly to avoid dependency issues)
 		const initialState = useRecordingStore.getState().state;
-		tauriAPI.emitConnectionState(initialState as ConnectionState);
+		tauriAPI.reportConnectionState(initialState as ConnectionState);
 		return unsubscribe;
 	}, []);
 
@@ -194,10 +417,72 @@ function RecordingControl() {
 		}
 	}, [rect.width, rect.height]);
 
-	// Handle start/stop recording from hotkeys
+	// Zooming changes the window size the same contents need
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onOverlayScaleChanged(() => {
+				const bounds = containerRef.current?.getBoundingClientRect();
+				if (bounds && bounds.width > 0 && bounds.height > 0) {
+					tauriAPI.resizeOverlay(
+						Math.ceil(bounds.width),
+						Math.ceil(bounds.height),
+					);
+				}
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [containerRef]);
+
+	// Remember where the overlay was dragged to once it stops moving, so it
+	// opens there on the next launch
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+		let saveTimeout: ReturnType<typeof setTimeout> | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onOverlayMoved((x, y) => {
+				if (!isUserMovingRef.current) return;
+				clearTimeout(saveTimeout);
+				saveTimeout = setTimeout(() => {
+					isUserMovingRef.current = false;
+					tauriAPI.setOverlayPosition(x, y).catch((error: unknown) => {
+						console.error("[Overlay] Failed to save position:", error);
+					});
+				}, OVERLAY_MOVE_SETTLE_MS);
+			});
+		};
+
+		setup();
+
+		return () => {
+			clearTimeout(saveTimeout);
+			unlisten?.();
+		};
+	}, []);
+
+	// Handle start/stop recording from hotkeys.
+	// Access to the mic may have been revoked since launch, so it is checked
+	// for every turn and Rust cancels the recording if it is denied.
 	const onStartRecording = useCallback(async () => {
-		await startRecording();
-	}, [startRecording]);
+		if ((await getMicPermission()) === "denied") {
+			setMicPermissionDenied(true);
+		} else if (await startRecording(await getRecordingApp())) {
+			return;
+		}
+		// startRecording flags the mic as denied when opening it was refused
+		if (!useRecordingStore.getState().micPermissionDenied) return;
+		console.warn("[Recording] Microphone access denied");
+		tauriAPI.reportMicPermission(false, false).catch((error: unknown) => {
+			console.error("[Recording] Failed to report mic permission:", error);
+		});
+	}, [startRecording, setMicPermissionDenied]);
 
 	const onStopRecording = useCallback(() => {
 		if (stopRecording()) {
@@ -223,17 +508,330 @@ function RecordingControl() {
 		};
 	}, [onStartRecording, onStopRecording]);
 
-	// Listen for settings changes from main window and invalidate cache to trigger sync
+	// Chunked insertion progress and clipboard fallback events from Rust
+	useEffect(() => {
+		let unlistenProgress: (() => void) | undefined;
+		let unlistenFallback: (() => void) | undefined;
+
+		const setup = async () => {
+			unlistenProgress = await tauriAPI.onInsertionProgress(
+				setInsertionProgress,
+			);
+			unlistenFallback = await tauriAPI.onInsertionClipboardFallback(
+				(fallback) => {
+					console.warn(
+						`[Insertion] ${fallback.char_count} characters exceeds the ${fallback.max_chars} limit, copied to clipboard instead`,
+					);
+				},
+			);
+		};
+
+		setup();
+
+		return () => {
+			unlistenProgress?.();
+			unlistenFallback?.();
+		};
+	}, []);
+
+	// Rust stopped a recording that ran to the length limit
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+		let hideTimer: ReturnType<typeof setTimeout> | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onRecordingLimitReached((maxSeconds) => {
+				console.warn(`[Recording] Stopped at the ${maxSeconds}s limit`);
+				setLimitReached(true);
+				clearTimeout(hideTimer);
+				hideTimer = setTimeout(
+					() => setLimitReached(false),
+					RECORDING_LIMIT_NOTICE_MS,
+				);
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+			clearTimeout(hideTimer);
+		};
+	}, []);
+
+	// Rust refused to insert while macOS secure input was on
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+		let hideTimer: ReturnType<typeof setTimeout> | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onSecureInputBlocked(() => {
+				console.warn("[Insertion] Secure input is on, nothing was inserted");
+				setSecureInputBlocked(true);
+				clearTimeout(hideTimer);
+				hideTimer = setTimeout(
+					() => setSecureInputBlocked(false),
+					SECURE_INPUT_NOTICE_MS,
+				);
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+			clearTimeout(hideTimer);
+		};
+	}, []);
+
+	// A hotkey or the tray switched STT provider or prompt preset, or turned
+	// formatting on or off
+	useEffect(() => {
+		let unlistenProvider: (() => void) | undefined;
+		let unlistenFormatting: (() => void) | undefined;
+		let unlistenPreset: (() => void) | undefined;
+		let hideTimer: ReturnType<typeof setTimeout> | undefined;
+
+		const show = (text: string, title: string) => {
+			setSwitchNotice({ text, title });
+			clearTimeout(hideTimer);
+			hideTimer = setTimeout(() => setSwitchNotice(null), SWITCH_NOTICE_MS);
+		};
+
+		const setup = async () => {
+			unlistenProvider = await tauriAPI.onSttProviderChanged((provider) => {
+				show(provider.label, `Transcribing with ${provider.label}`);
+			});
+			unlistenFormatting = await tauriAPI.onFormattingChanged((enabled) => {
+				show(
+					enabled ? "Formatting on" : "Formatting off",
+					enabled
+						? "Dictations are cleaned up by the LLM"
+						: "Dictations are inserted as transcribed",
+				);
+			});
+			unlistenPreset = await tauriAPI.onPromptPresetChanged((preset) => {
+				show(preset.name, `Formatting with the "${preset.name}" prompt`);
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlistenProvider?.();
+			unlistenFormatting?.();
+			unlistenPreset?.();
+			clearTimeout(hideTimer);
+		};
+	}, []);
+
+	// The pause/resume hotkey mutes the microphone without ending the turn
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onRecordingPaused(setPaused);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [setPaused]);
+
+	// Interim transcripts from Rust, shown until the recording stops
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onTranscriptionPartial(setPartialText);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	useEffect(() => {
+		if (state !== "recording") setPartialText(null);
+	}, [state]);
+
+	// Text from an in-app formatter as it streams in, cleared once it is done
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onFormattingPartial(setFormattingText);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	const liveText = state === "recording" ? partialText : formattingText;
+
+	// The clipboard-only hotkey changes where the recording's text goes
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onClipboardOnlyChanged(setClipboardOnly);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	// Send locally transcribed text to the server for formatting; the result
+	// comes back through the normal bot-llm-text flow and is typed as usual
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onFormatTextRequest(({ text, preview_id }) => {
+				client?.sendClientMessage("format-text", { text, preview_id });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [client]);
+
+	// Replay dictations queued while the server was unreachable, as turns whose
+	// results go back to Rust to be added to history
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onQueuedAudioRequest(
+				async (request) => {
+					if (!client) return;
+					const { preview_id, sample_rate, stt_provider } = request;
+					const turnId = queuedTurnId(preview_id);
+					// A retry may ask for another STT provider for just this turn. The
+					// server handles messages in order, so switching back after the
+					// last chunk leaves the replayed audio with the requested one.
+					const selectedProvider = stt_provider
+						? (await tauriAPI.getSettings()).stt_provider
+						: null;
+					if (stt_provider) {
+						client.sendClientMessage("set-stt-provider", {
+							provider: stt_provider,
+						});
+					}
+					const chunks = queuedAudioChunks(request);
+					chunks.forEach((chunk, index) => {
+						client.sendClientMessage("queued-audio", {
+							turn_id: turnId,
+							sample_rate,
+							...chunk,
+							first: index === 0,
+							last: index === chunks.length - 1,
+						});
+					});
+					if (
+						selectedProvider &&
+						selectedProvider !== LOCAL_WHISPER_PROVIDER &&
+						selectedProvider !== stt_provider
+					) {
+						client.sendClientMessage("set-stt-provider", {
+							provider: selectedProvider,
+						});
+					}
+				},
+			);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [client]);
+
+	// Offer a correction whenever a dictation is recorded (or corrected)
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onHistoryChanged(() => {
+				clearCorrectionWindow();
+				setCanCorrect(true);
+				startCorrectionWindow();
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [clearCorrectionWindow, startCorrectionWindow]);
+
+	const handleOpenCorrection = useCallback(async () => {
+		clearCorrectionWindow();
+		setCanCorrect(false);
+		try {
+			const { entries } = await tauriAPI.getHistory({ page: 0, pageSize: 1 });
+			const latest = entries[0];
+			if (!latest) return;
+			await tauriAPI.setOverlayEditing(true);
+			setCorrectionText(latest.text);
+		} catch (error) {
+			console.error("[Correction] Failed to open correction box:", error);
+		}
+	}, [clearCorrectionWindow]);
+
+	const handleCancelCorrection = useCallback(() => {
+		setCorrectionText(null);
+		tauriAPI.setOverlayEditing(false);
+	}, []);
+
+	// Give focus back to the target app, then replace the text inserted there
+	const handleSaveCorrection = useCallback(
+		async (text: string, addToDictionary: boolean) => {
+			setCorrectionText(null);
+			try {
+				await tauriAPI.setOverlayEditing(false);
+				// Rust adds the corrected words to the dictionary when asked
+				await correctLastEntry.mutateAsync({ text, addToDictionary });
+			} catch (error) {
+				console.error("[Correction] Failed to correct text:", error);
+			}
+		},
+		[correctLastEntry],
+	);
+
+	// Clear insertion progress once processing finishes
+	useEffect(() => {
+		if (state !== "processing") {
+			setInsertionProgress(null);
+		}
+	}, [state]);
+
+	// Listen for settings changes from any window or the backend and invalidate the
+	// cache to trigger sync
 	useEffect(() => {
 		let unlisten: (() => void) | undefined;
 
 		const setup = async () => {
 			unlisten = await tauriAPI.onSettingsChanged(() => {
-				// Invalidate settings and serverUrl queries to trigger refetch from Tauri Store
+				// Invalidate settings to trigger refetch from Tauri Store
 				// The settings sync useEffect will then detect the change and sync to server
-				// serverUrl must be invalidated separately since useServerUrl() uses its own query key
+				// Server URL changes are handled by the backend, which requests a reconnect
 				queryClient.invalidateQueries({ queryKey: ["settings"] });
-				queryClient.invalidateQueries({ queryKey: ["serverUrl"] });
 			});
 		};
 
@@ -244,21 +842,20 @@ function RecordingControl() {
 		};
 	}, [queryClient]);
 
-	// Listen for reconnect request from main window (triggered by user in settings)
+	// The main window may be opened long after connecting (e.g. headless launch),
+	// so resend the providers it would otherwise have missed
 	useEffect(() => {
 		let unlisten: (() => void) | undefined;
 
 		const setup = async () => {
-			unlisten = await tauriAPI.onReconnect(() => {
-				console.log("[Pipecat] Received reconnect request from main window");
-				if (client) {
-					// Disconnect - the Disconnected event handler will auto-reconnect
-					client.disconnect().catch((error) => {
-						console.error(
-							"[Pipecat] Disconnect error during reconnect:",
-							error,
-						);
-					});
+			unlisten = await tauriAPI.onMainWindowReady(() => {
+				const currentState = useRecordingStore.getState().state;
+				const isConnected =
+					currentState === "idle" ||
+					currentState === "recording" ||
+					currentState === "processing";
+				if (client && isConnected) {
+					client.sendClientMessage("get-available-providers", {});
 				}
 			});
 		};
@@ -270,6 +867,63 @@ function RecordingControl() {
 		};
 	}, [client]);
 
+	// Listen for connection requests from Rust, which picks the server: the
+	// first one initially, when the URLs change or on a manual reconnect, and the
+	// next one or a retry with backoff after a failure. SmallWebRTC handles
+	// reconnection internally (3 attempts) before a drop is reported.
+	useEffect(() => {
+		if (!client) return;
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onReconnectRequest(async (url, attempt) => {
+				console.log(`[Pipecat] Connecting to ${url} (attempt ${attempt})`);
+				try {
+					await client.disconnect(); // Reset client state
+				} catch (error) {
+					console.error("[Pipecat] Disconnect error during reconnect:", error);
+				}
+				await connectToServer(url);
+			});
+
+			// Initial connection, once requests can be received
+			if (!hasRequestedConnectionRef.current) {
+				hasRequestedConnectionRef.current = true;
+				tauriAPI.reconnectServer().catch((error) => {
+					console.error("[Pipecat] No server to connect to:", error);
+				});
+			}
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [client, connectToServer]);
+
+	// Show the OS prompt when asked to re-request mic access; Rust opens the
+	// privacy settings instead if access stays denied
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onMicPermissionPrompt(async () => {
+				const granted = await promptForMicPermission().catch(() => false);
+				setMicPermissionDenied(!granted);
+				tauriAPI.reportMicPermission(granted, true).catch((error: unknown) => {
+					console.error("[Recording] Failed to open privacy settings:", error);
+				});
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [setMicPermissionDenied]);
+
 	// Listen for disconnect request from Rust (triggered on app quit)
 	useEffect(() => {
 		let unlisten: (() => void) | undefined;
@@ -334,7 +988,11 @@ function RecordingControl() {
 					},
 				});
 			}
-			if (hasChanged("stt_provider")) {
+			// Local Whisper transcribes in the app, so the server keeps its provider
+			if (
+				hasChanged("stt_provider") &&
+				currentSettings?.stt_provider !== LOCAL_WHISPER_PROVIDER
+			) {
 				messages.push({
 					type: "set-stt-provider",
 					data: { provider: currentSettings?.stt_provider as string },
@@ -354,6 +1012,32 @@ function RecordingControl() {
 					},
 				});
 			}
+			// The hint is derived from two settings; null lets the provider detect
+			// the language, which a new session does anyway
+			if (currentSettings) {
+				const language = dictationLanguageHint(currentSettings);
+				const isChanged =
+					prevSettings === undefined
+						? language !== null
+						: language !== dictationLanguageHint(prevSettings);
+				if (isChanged) {
+					messages.push({ type: "set-stt-language", data: { language } });
+				}
+			}
+			if (hasChanged("short_utterance_max_words")) {
+				messages.push({
+					type: "set-short-utterance-max-words",
+					data: {
+						max_words: currentSettings?.short_utterance_max_words as number,
+					},
+				});
+			}
+			if (hasChanged("formatting_enabled")) {
+				messages.push({
+					type: "set-formatting-enabled",
+					data: { enabled: currentSettings?.formatting_enabled as boolean },
+				});
+			}
 
 			return messages;
 		},
@@ -365,7 +1049,6 @@ function RecordingControl() {
 		RTVIEvent.Connected,
 		useCallback(() => {
 			console.debug("[Pipecat] Connected");
-			hasConnectedRef.current = true;
 			handleConnected();
 
 			// Sync settings to server via data channel (with delay to ensure connection is stable)
@@ -387,10 +1070,15 @@ function RecordingControl() {
 	// Sync settings when they change WHILE already connected
 	useEffect(() => {
 		const prevSettings = prevSettingsRef.current;
+		if (!client) {
+			prevSettingsRef.current = settings;
+			return;
+		}
+		// Changes made while recording (e.g. by a hotkey) are sent once idle again
+		if (state !== "idle") return;
 		prevSettingsRef.current = settings;
 
-		// Only sync if connected AND settings actually changed
-		if (!client || state !== "idle") return;
+		// Only sync if settings actually changed
 		if (prevSettings === settings) return;
 
 		const messages = buildConfigMessages(settings, prevSettings);
@@ -400,7 +1088,7 @@ function RecordingControl() {
 	}, [client, state, settings, buildConfigMessages]);
 
 	// Disconnection event handler
-	// Handles cleanup, state transition, and reconnection
+	// Handles cleanup and state transition; the backend schedules the reconnect
 	useRTVIClientEvent(
 		RTVIEvent.Disconnected,
 		useCallback(() => {
@@ -423,22 +1111,17 @@ function RecordingControl() {
 			}
 
 			handleDisconnected();
+		}, [client, handleDisconnected]),
+	);
 
-			// Reconnection: only if we've connected before (not on initial connection failure)
-			// SmallWebRTC already tried to reconnect (3 attempts) and gave up
-			if (hasConnectedRef.current && serverUrl && client) {
-				setTimeout(async () => {
-					try {
-						await client.disconnect(); // Reset client state
-						await client.connect({
-							webrtcRequestParams: { endpoint: `${serverUrl}/api/offer` },
-						});
-					} catch (error: unknown) {
-						console.error("[Pipecat] Reconnection failed:", error);
-					}
-				}, 3000);
-			}
-		}, [client, serverUrl, handleDisconnected]),
+	// Interim transcripts from the server go through Rust, which decides whether
+	// to show them
+	useRTVIClientEvent(
+		RTVIEvent.UserTranscript,
+		useCallback((data: TranscriptData) => {
+			if (data.final || !data.text.trim()) return;
+			tauriAPI.reportTranscriptionPartial(data.text).catch(() => {});
+		}, []),
 	);
 
 	// LLM text streaming handlers (using official RTVI protocol via RTVIObserver)
@@ -460,27 +1143,28 @@ function RecordingControl() {
 
 	useRTVIClientEvent(
 		RTVIEvent.BotLlmStopped,
-		useCallback(async () => {
-			clearResponseTimeout();
+		useCallback(() => {
 			const text = llmTextAccumulatorRef.current.trim();
 			llmTextAccumulatorRef.current = "";
 
+			const previewId = formattingPreviewIdRef.current;
+			if (previewId !== null) {
+				formattingPreviewIdRef.current = null;
+				tauriAPI.completeFormatPreview(previewId, text);
+				return;
+			}
+
 			if (text) {
 				console.debug("[Pipecat] LLM response:", text);
-				try {
-					await typeTextMutation.mutateAsync(text);
-				} catch (error) {
-					console.error("[Pipecat] Failed to type text:", error);
-				}
-				addHistoryEntry.mutate(text);
 			}
-			handleResponse();
-		}, [
-			clearResponseTimeout,
-			typeTextMutation,
-			addHistoryEntry,
-			handleResponse,
-		]),
+			completeTurn(
+				text || null,
+				formattingTurnIdRef.current,
+				formattingRawTextRef.current ?? undefined,
+			);
+			formattingTurnIdRef.current = null;
+			formattingRawTextRef.current = null;
+		}, [completeTurn]),
 	);
 
 	// Server message handler (for custom messages: config-updated, recording-complete, etc.)
@@ -491,8 +1175,32 @@ function RecordingControl() {
 				const recordingCompleteResult =
 					RecordingCompleteMessageSchema.safeParse(message);
 				if (recordingCompleteResult.success) {
-					clearResponseTimeout();
-					handleResponse();
+					const { turnId } = recordingCompleteResult.data;
+					const previewId =
+						recordingCompleteResult.data.previewId ??
+						queuedTurnPreviewId(turnId ?? null);
+					if (previewId != null) {
+						// The previewed transcript or queued audio had no words
+						tauriAPI.completeFormatPreview(previewId, "");
+						return;
+					}
+					completeTurn(null, turnId ?? null);
+					return;
+				}
+
+				const formattingStartedResult =
+					FormattingStartedMessageSchema.safeParse(message);
+				if (formattingStartedResult.success) {
+					const { rawText, turnId } = formattingStartedResult.data;
+					const previewId =
+						formattingStartedResult.data.previewId ??
+						queuedTurnPreviewId(turnId);
+					if (previewId != null) {
+						formattingPreviewIdRef.current = previewId;
+						return;
+					}
+					formattingTurnIdRef.current = turnId;
+					formattingRawTextRef.current = rawText ?? null;
 					return;
 				}
 
@@ -525,11 +1233,12 @@ function RecordingControl() {
 					tauriAPI.emitAvailableProviders({
 						stt: availableProvidersResult.data.stt,
 						llm: availableProvidersResult.data.llm,
+						audio_encodings: availableProvidersResult.data.audio_encodings,
 					});
 					return;
 				}
 			},
-			[clearResponseTimeout, handleResponse],
+			[completeTurn],
 		),
 	);
 
@@ -557,6 +1266,11 @@ function RecordingControl() {
 		}
 	}, [state, onStartRecording, onStopRecording]);
 
+	// Turns waiting for a response, shown when more than the spinner implies
+	const showQueueDepth =
+		pendingTurns.length > 1 ||
+		(state === "recording" && pendingTurns.length > 0);
+
 	// Drag handler using @use-gesture/react
 	// Handles unfocused window dragging (data-tauri-drag-region doesn't work on unfocused windows)
 	const bindDrag = useDrag(
@@ -572,6 +1286,7 @@ function RecordingControl() {
 			// Start dragging once threshold is exceeded
 			if (!memo && distance > DRAG_THRESHOLD) {
 				hasDragStartedRef.current = true;
+				isUserMovingRef.current = true;
 				tauriAPI.startDragging();
 				return true; // memo = true (dragging started)
 			}
@@ -599,11 +1314,35 @@ function RecordingControl() {
 				padding: 2,
 				cursor: "grab",
 				userSelect: "none",
+				position: "relative",
+				display: "flex",
+				alignItems: "center",
 			}}
 		>
-			{state === "processing" ||
-			state === "disconnected" ||
-			state === "connecting" ? (
+			{showQueueDepth && (
+				<span
+					style={{
+						position: "absolute",
+						top: 2,
+						right: 4,
+						color: "white",
+						fontSize: 9,
+						zIndex: 1,
+						pointerEvents: "none",
+					}}
+				>
+					{pendingTurns.length}
+				</span>
+			)}
+			{correctionText !== null ? (
+				<CorrectionBox
+					initialText={correctionText}
+					onSave={handleSaveCorrection}
+					onCancel={handleCancelCorrection}
+				/>
+			) : state === "processing" ||
+				state === "disconnected" ||
+				state === "connecting" ? (
 				<div
 					style={{
 						width: 48,
@@ -613,7 +1352,13 @@ function RecordingControl() {
 						justifyContent: "center",
 					}}
 				>
-					<Loader size="sm" color="white" />
+					{insertionProgress ? (
+						<span style={{ color: "white", fontSize: 11 }}>
+							{insertionProgress.chunk}/{insertionProgress.total_chunks}
+						</span>
+					) : (
+						<Loader size="sm" color="white" />
+					)}
 				</div>
 			) : (
 				<UserAudioComponent
@@ -633,6 +1378,106 @@ function RecordingControl() {
 					{state !== "recording" && <Logo className="size-5" />}
 				</UserAudioComponent>
 			)}
+			{micPermissionDenied && state === "idle" && correctionText === null && (
+				<ActionIcon
+					variant="subtle"
+					color="red"
+					size="sm"
+					onClick={() => tauriAPI.requestMicPermission()}
+					aria-label="Microphone access denied, click to allow it"
+					title="Microphone access denied, click to allow it"
+				>
+					<MicOff size={14} />
+				</ActionIcon>
+			)}
+			{liveText && (
+				<span
+					title={liveText}
+					style={{
+						maxWidth: 240,
+						padding: "0 8px",
+						color: "white",
+						fontSize: 11,
+						whiteSpace: "nowrap",
+						overflow: "hidden",
+						textOverflow: "ellipsis",
+						direction: "rtl",
+					}}
+				>
+					{/* Keep the latest words visible as the text grows */}
+					<bdi>{liveText}</bdi>
+				</span>
+			)}
+			{switchNotice && correctionText === null && (
+				<span
+					title={switchNotice.title}
+					style={{
+						padding: "0 8px",
+						color: "white",
+						fontSize: 11,
+						whiteSpace: "nowrap",
+					}}
+				>
+					{switchNotice.text}
+				</span>
+			)}
+			{state === "recording" && clipboardOnly && (
+				<ActionIcon
+					variant="subtle"
+					color="gray"
+					size="sm"
+					aria-label="This dictation goes to the clipboard only"
+					title="This dictation goes to the clipboard only"
+				>
+					<ClipboardCopy size={14} />
+				</ActionIcon>
+			)}
+			{state === "recording" && pausedAt !== null && (
+				<ActionIcon
+					variant="subtle"
+					color="gray"
+					size="sm"
+					aria-label="Recording paused"
+					title="Recording paused"
+				>
+					<Pause size={14} />
+				</ActionIcon>
+			)}
+			{limitReached && correctionText === null && (
+				<ActionIcon
+					variant="subtle"
+					color="yellow"
+					size="sm"
+					onClick={() => setLimitReached(false)}
+					aria-label="Recording stopped at the length limit"
+					title="Recording stopped at the length limit"
+				>
+					<TimerOff size={14} />
+				</ActionIcon>
+			)}
+			{secureInputBlocked && correctionText === null && (
+				<ActionIcon
+					variant="subtle"
+					color="yellow"
+					size="sm"
+					onClick={() => setSecureInputBlocked(false)}
+					aria-label={SECURE_INPUT_NOTICE}
+					title={SECURE_INPUT_NOTICE}
+				>
+					<Lock size={14} />
+				</ActionIcon>
+			)}
+			{canCorrect && state === "idle" && correctionText === null && (
+				<ActionIcon
+					variant="subtle"
+					color="gray"
+					size="sm"
+					onClick={handleOpenCorrection}
+					aria-label="Correct the last dictation"
+				>
+					<Pencil size={14} />
+				</ActionIcon>
+			)}
 		</div>
 	);
 }
@@ -640,10 +1485,43 @@ function RecordingControl() {
 export default function OverlayApp() {
 	const [client, setClient] = useState<PipecatClient | null>(null);
 	const [devicesReady, setDevicesReady] = useState(false);
+	const appliedMicIdRef = useRef<string | null>(null);
+	// How the applied microphone was opened, see captureKey
+	const appliedCaptureKeyRef = useRef<string | null>(null);
 	const { data: settings } = useSettings();
+	const nativeCapture = settings?.native_audio_capture ?? true;
+	const noiseSuppression = settings?.noise_suppression_enabled ?? false;
+	const echoCancellation = settings?.echo_cancellation_enabled ?? false;
+	// These only take effect when the microphone is opened
+	const captureKey = [
+		nativeCapture,
+		noiseSuppression,
+		echoCancellation,
+		settings?.sample_rate ?? null,
+		settings?.channels ?? null,
+	].join(":");
+
+	// Apply input gain and audio processing to the microphone
+	useEffect(() => {
+		setMicProcessing({
+			gain: settings?.mic_gain ?? DEFAULT_MIC_GAIN,
+			agcEnabled: settings?.agc_enabled ?? true,
+			nativeCapture,
+			noiseSuppression,
+			echoCancellation,
+		});
+	}, [
+		settings?.mic_gain,
+		settings?.agc_enabled,
+		nativeCapture,
+		noiseSuppression,
+		echoCancellation,
+	]);
 
 	// Initial client creation on mount
 	useEffect(() => {
+		// Must be installed before the transport first opens the microphone
+		installMicProcessing();
 		const transport = new SmallWebRTCTransport({
 			iceServers: [{ urls: "stun:stun.l.google.com:19302" }],
 		});
@@ -669,12 +1547,84 @@ export default function OverlayApp() {
 		};
 	}, []);
 
-	// Apply selected microphone when settings or client changes
+	// Record from the first preferred microphone plugged in, or the system
+	// default while none is. Rust decides which device is active and announces
+	// switches.
+	const preferredMicsKey = (settings?.preferred_mic_ids ?? []).join("\n");
 	useEffect(() => {
-		if (client && devicesReady && settings?.selected_mic_id) {
-			client.updateMic(settings.selected_mic_id);
-		}
-	}, [client, devicesReady, settings?.selected_mic_id]);
+		if (!client || !devicesReady) return;
+
+		const applyActiveMic = async () => {
+			const inputs = (await navigator.mediaDevices.enumerateDevices()).filter(
+				(device) => device.kind === "audioinput",
+			);
+			const activeMic = await tauriAPI.reportAudioDevices(
+				inputs.map((device) => ({
+					device_id: device.deviceId,
+					label: device.label,
+				})),
+			);
+			// The webview lists the system default input first
+			const micId = activeMic.device_id ?? inputs[0]?.deviceId;
+			// Changing how the mic is captured or processed opens it again
+			if (
+				micId &&
+				(micId !== appliedMicIdRef.current ||
+					captureKey !== appliedCaptureKeyRef.current)
+			) {
+				appliedMicIdRef.current = micId;
+				appliedCaptureKeyRef.current = captureKey;
+				client.updateMic(micId);
+			}
+		};
+
+		const handleDeviceChange = () => {
+			applyActiveMic().catch((error: unknown) => {
+				console.error("[Pipecat] Failed to apply microphone:", error);
+			});
+		};
+
+		handleDeviceChange();
+		navigator.mediaDevices.addEventListener("devicechange", handleDeviceChange);
+
+		return () => {
+			navigator.mediaDevices.removeEventListener(
+				"devicechange",
+				handleDeviceChange,
+			);
+		};
+	}, [client, devicesReady, preferredMicsKey, captureKey]);
+
+	// Capture audio for STT providers that transcribe in the app
+	useEffect(() => {
+		let unlistenStart: (() => void) | undefined;
+		let unlistenStop: (() => void) | undefined;
+
+		const setup = async () => {
+			unlistenStart = await tauriAPI.onLocalCaptureStart(() => {
+				startLocalCapture(appliedMicIdRef.current).catch((error: unknown) => {
+					console.error("[Audio] Failed to start local capture:", error);
+					if (isMicPermissionError(error)) {
+						useRecordingStore.getState().setMicPermissionDenied(true);
+						tauriAPI.reportMicPermission(false, false);
+					}
+				});
+			});
+			unlistenStop = await tauriAPI.onLocalCaptureStop((id) => {
+				flushLocalCapture(id).catch((error: unknown) => {
+					console.warn("[Audio] Failed to confirm captured audio:", error);
+				});
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlistenStart?.();
+			unlistenStop?.();
+			stopLocalCapture();
+		};
+	}, []);
 
 	if (!client || !devicesReady) {
 		return (
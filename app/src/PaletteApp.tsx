@@ -0,0 +1,155 @@
+import { Loader, Text, TextInput } from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { Search } from "lucide-react";
+import { type KeyboardEvent, useEffect, useRef, useState } from "react";
+import { usePaletteItems, useRunPaletteAction } from "./lib/queries";
+import { type PaletteItem, tauriAPI } from "./lib/tauri";
+
+export default function PaletteApp() {
+	const queryClient = useQueryClient();
+	const [query, setQuery] = useState("");
+	const [selected, setSelected] = useState(0);
+	const inputRef = useRef<HTMLInputElement>(null);
+	const { data: items = [], isLoading } = usePaletteItems(query);
+	const runAction = useRunPaletteAction();
+
+	// Start afresh each time the hotkey opens the palette, and close it when
+	// focus moves elsewhere
+	useEffect(() => {
+		let unlistenOpened: (() => void) | undefined;
+		let unlistenBlurred: (() => void) | undefined;
+
+		const setup = async () => {
+			unlistenOpened = await tauriAPI.onPaletteOpened(() => {
+				setQuery("");
+				setSelected(0);
+				runAction.reset();
+				queryClient.invalidateQueries({ queryKey: ["paletteItems"] });
+				inputRef.current?.focus();
+			});
+			unlistenBlurred = await tauriAPI.onPaletteBlurred(() => {
+				tauriAPI.hidePaletteWindow();
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlistenOpened?.();
+			unlistenBlurred?.();
+		};
+	}, [queryClient, runAction.reset]);
+
+	const run = (item: PaletteItem | undefined) => {
+		if (!item) return;
+		runAction.mutate(item.action, {
+			onSuccess: () => {
+				tauriAPI.hidePaletteWindow();
+			},
+		});
+	};
+
+	const handleKeyDown = (event: KeyboardEvent) => {
+		switch (event.key) {
+			case "ArrowDown":
+				event.preventDefault();
+				setSelected((index) => Math.min(index + 1, items.length - 1));
+				break;
+			case "ArrowUp":
+				event.preventDefault();
+				setSelected((index) => Math.max(index - 1, 0));
+				break;
+			case "Enter":
+				event.preventDefault();
+				run(items[selected]);
+				break;
+			case "Escape":
+				event.preventDefault();
+				tauriAPI.hidePaletteWindow();
+				break;
+		}
+	};
+
+	return (
+		<div
+			style={{
+				height: "100vh",
+				display: "flex",
+				flexDirection: "column",
+				padding: 12,
+				gap: 8,
+				backgroundColor: "var(--bg-card)",
+				border: "1px solid var(--border-default)",
+				borderRadius: 12,
+			}}
+		>
+			<TextInput
+				ref={inputRef}
+				value={query}
+				onChange={(event) => {
+					setQuery(event.currentTarget.value);
+					setSelected(0);
+				}}
+				onKeyDown={handleKeyDown}
+				placeholder="Paste a dictation, switch provider or preset..."
+				leftSection={<Search size={16} />}
+				autoFocus
+				styles={{
+					input: {
+						backgroundColor: "var(--bg-elevated)",
+						borderColor: "var(--border-default)",
+						color: "var(--text-primary)",
+					},
+				}}
+			/>
+			<div style={{ flex: 1, overflowY: "auto" }}>
+				{isLoading ? (
+					<div
+						style={{ display: "flex", justifyContent: "center", padding: 16 }}
+					>
+						<Loader size="sm" color="gray" />
+					</div>
+				) : items.length === 0 ? (
+					<Text size="sm" c="dimmed" p="sm">
+						Nothing matches
+					</Text>
+				) : (
+					items.map((item, index) => (
+						<button
+							key={`${item.action.kind}:${index}`}
+							type="button"
+							onClick={() => run(item)}
+							onMouseEnter={() => setSelected(index)}
+							style={{
+								display: "flex",
+								width: "100%",
+								justifyContent: "space-between",
+								gap: 12,
+								padding: "8px 10px",
+								border: "none",
+								borderRadius: 8,
+								textAlign: "left",
+								cursor: "pointer",
+								color: "var(--text-primary)",
+								backgroundColor:
+									index === selected ? "var(--bg-card-hover)" : "transparent",
+							}}
+						>
+							<Text size="sm" truncate fw={item.active ? 600 : undefined}>
+								{item.active ? `✓ ${item.title}` : item.title}
+							</Text>
+							<Text size="xs" c="dimmed" style={{ flexShrink: 0 }}>
+								{item.detail}
+							</Text>
+						</button>
+					))
+				)}
+			</div>
+			{runAction.error && (
+				<Text size="xs" c="red">
+					{String(runAction.error)}
+				</Text>
+			)}
+		</div>
+	);
+}
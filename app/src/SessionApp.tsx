@@ -0,0 +1,125 @@
+import { Button, Group, Text } from "@mantine/core";
+import { useClipboard } from "@mantine/hooks";
+import { useQueryClient } from "@tanstack/react-query";
+import { useEffect, useState } from "react";
+import {
+	useExportSessionTranscript,
+	useResetSessionTranscript,
+	useSessionTranscript,
+} from "./lib/queries";
+import { tauriAPI } from "./lib/tauri";
+
+export default function SessionApp() {
+	const queryClient = useQueryClient();
+	const { data: transcript, isLoading } = useSessionTranscript();
+	const resetSession = useResetSessionTranscript();
+	const exportSession = useExportSessionTranscript();
+	const clipboard = useClipboard();
+	const [exportedPath, setExportedPath] = useState<string | null>(null);
+
+	// Refresh live as dictations complete or the session is reset
+	useEffect(() => {
+		let unlistenEntry: (() => void) | undefined;
+		let unlistenReset: (() => void) | undefined;
+
+		const setup = async () => {
+			unlistenEntry = await tauriAPI.onSessionEntryAdded(() => {
+				queryClient.invalidateQueries({ queryKey: ["sessionTranscript"] });
+			});
+			unlistenReset = await tauriAPI.onSessionTranscriptUpdated(() => {
+				queryClient.invalidateQueries({ queryKey: ["sessionTranscript"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlistenEntry?.();
+			unlistenReset?.();
+		};
+	}, [queryClient]);
+
+	const text =
+		transcript?.entries.map((entry) => entry.text).join("\n\n") ?? "";
+
+	const handleExport = () => {
+		exportSession.mutate(undefined, {
+			onSuccess: (path) => setExportedPath(path),
+		});
+	};
+
+	return (
+		<div
+			style={{
+				height: "100vh",
+				display: "flex",
+				flexDirection: "column",
+				padding: 16,
+				gap: 12,
+			}}
+		>
+			<Group justify="space-between">
+				<Text size="sm" c="dimmed">
+					{transcript?.entries.length ?? 0} dictations this session
+				</Text>
+				<Group gap="xs">
+					<Button
+						variant="default"
+						size="compact-sm"
+						onClick={() => clipboard.copy(text)}
+						disabled={!text}
+					>
+						{clipboard.copied ? "Copied" : "Copy"}
+					</Button>
+					<Button
+						variant="default"
+						size="compact-sm"
+						onClick={handleExport}
+						disabled={!text}
+						loading={exportSession.isPending}
+					>
+						Export
+					</Button>
+					<Button
+						variant="subtle"
+						color="red"
+						size="compact-sm"
+						onClick={() => resetSession.mutate()}
+						loading={resetSession.isPending}
+					>
+						Reset
+					</Button>
+				</Group>
+			</Group>
+			{exportedPath && (
+				<Text size="xs" c="dimmed">
+					Exported to {exportedPath}
+				</Text>
+			)}
+			<div
+				style={{
+					flex: 1,
+					overflowY: "auto",
+					whiteSpace: "pre-wrap",
+					backgroundColor: "var(--bg-card)",
+					border: "1px solid var(--border-default)",
+					borderRadius: 8,
+					padding: 12,
+					userSelect: "text",
+				}}
+			>
+				{isLoading ? (
+					<Text size="sm" c="dimmed">
+						Loading transcript...
+					</Text>
+				) : text ? (
+					text
+				) : (
+					<Text size="sm" c="dimmed">
+						Everything you dictate this session will appear here.
+					</Text>
+				)}
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,82 @@
+import { Button, Checkbox, Textarea } from "@mantine/core";
+import { type KeyboardEvent, useState } from "react";
+
+interface CorrectionBoxProps {
+	initialText: string;
+	onSave: (text: string, addToDictionary: boolean) => void;
+	onCancel: () => void;
+}
+
+/**
+ * Small editor in the overlay for fixing the text that was just inserted.
+ * Enter saves, Shift+Enter adds a new line and Escape cancels.
+ */
+export function CorrectionBox({
+	initialText,
+	onSave,
+	onCancel,
+}: CorrectionBoxProps) {
+	const [text, setText] = useState(initialText);
+	const [addToDictionary, setAddToDictionary] = useState(false);
+
+	const handleKeyDown = (event: KeyboardEvent<HTMLTextAreaElement>) => {
+		if (event.key === "Escape") {
+			event.preventDefault();
+			onCancel();
+		} else if (event.key === "Enter" && !event.shiftKey) {
+			event.preventDefault();
+			onSave(text, addToDictionary);
+		}
+	};
+
+	return (
+		<div
+			// Keep clicks and text selection from dragging the overlay
+			onPointerDown={(event) => event.stopPropagation()}
+			style={{
+				width: 280,
+				padding: 6,
+				display: "flex",
+				flexDirection: "column",
+				gap: 6,
+				cursor: "auto",
+			}}
+		>
+			<Textarea
+				value={text}
+				onChange={(event) => setText(event.currentTarget.value)}
+				onKeyDown={handleKeyDown}
+				autosize
+				minRows={1}
+				maxRows={6}
+				size="xs"
+				autoFocus
+				styles={{ input: { userSelect: "text" } }}
+			/>
+			<div
+				style={{
+					display: "flex",
+					alignItems: "center",
+					justifyContent: "space-between",
+					gap: 6,
+				}}
+			>
+				<Checkbox
+					label="Add to dictionary"
+					checked={addToDictionary}
+					onChange={(event) => setAddToDictionary(event.currentTarget.checked)}
+					size="xs"
+					styles={{ label: { color: "white" } }}
+				/>
+				<Button
+					size="compact-xs"
+					variant="white"
+					color="dark"
+					onClick={() => onSave(text, addToDictionary)}
+				>
+					Fix
+				</Button>
+			</div>
+		</div>
+	);
+}
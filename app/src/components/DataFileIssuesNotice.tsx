@@ -0,0 +1,70 @@
+import { Button, Text } from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { FileWarning } from "lucide-react";
+import { useEffect } from "react";
+import { useDataFileIssues, useDismissDataFileIssues } from "../lib/queries";
+import { tauriAPI } from "../lib/tauri";
+
+/** Data files found corrupt at startup, which were moved aside and reset */
+export function DataFileIssuesNotice() {
+	const queryClient = useQueryClient();
+	const { data: issues } = useDataFileIssues();
+	const dismissIssues = useDismissDataFileIssues();
+
+	// Downloaded models are checked in the background after startup
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onDataFileIssuesChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["dataFileIssues"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	if (!issues || issues.length === 0) return null;
+
+	return (
+		<div
+			className="settings-card animate-in"
+			style={{
+				display: "flex",
+				alignItems: "flex-start",
+				gap: 12,
+				marginBottom: 24,
+			}}
+		>
+			<FileWarning size={18} color="var(--mantine-color-orange-6)" />
+			<div style={{ flex: 1 }}>
+				<p className="settings-label">
+					Some data files were damaged and have been reset or restored
+				</p>
+				{issues.map((issue) => (
+					<Text key={issue.file} size="xs" c="dimmed">
+						{issue.file}: {issue.problem}.{" "}
+						{issue.quarantined_as
+							? `The damaged file was kept as ${issue.quarantined_as}.`
+							: "The damaged file could not be moved aside."}
+						{issue.restored_from_backup &&
+							" Your last saved copy was restored."}
+					</Text>
+				))}
+			</div>
+			<Button
+				onClick={() => dismissIssues.mutate()}
+				loading={dismissIssues.isPending}
+				size="xs"
+				variant="light"
+				color="gray"
+			>
+				Dismiss
+			</Button>
+		</div>
+	);
+}
@@ -1,6 +1,8 @@
-import { Select } from "@mantine/core";
+import { ActionIcon, Select, Text } from "@mantine/core";
+import { ChevronDown, ChevronUp, Trash2 } from "lucide-react";
 import { useEffect, useState } from "react";
-import { useSettings, useUpdateSelectedMic } from "../lib/queries";
+import { useSettings, useUpdatePreferredMics } from "../lib/queries";
+import { activePreferredMic } from "../lib/tauri";
 
 interface AudioDevice {
 	deviceId: string;
@@ -9,7 +11,7 @@ interface AudioDevice {
 
 export function DeviceSelector() {
 	const { data: settings, isLoading: settingsLoading } = useSettings();
-	const updateSelectedMic = useUpdateSelectedMic();
+	const updatePreferredMics = useUpdatePreferredMics();
 	const [devices, setDevices] = useState<AudioDevice[]>([]);
 	const [isLoading, setIsLoading] = useState(true);
 	const [error, setError] = useState<string | null>(null);
@@ -54,16 +56,10 @@ export function DeviceSelector() {
 		};
 	}, []);
 
-	const handleChange = (value: string | null) => {
-		// null or empty string means "default"
-		const micId = value === "" || value === "default" ? null : value;
-		updateSelectedMic.mutate(micId);
-	};
-
 	if (isLoading || settingsLoading) {
 		return (
 			<div>
-				<p className="settings-label">Microphone</p>
+				<p className="settings-label">Microphones</p>
 				<p className="settings-description">Loading microphones...</p>
 			</div>
 		);
@@ -72,7 +68,7 @@ export function DeviceSelector() {
 	if (error) {
 		return (
 			<div>
-				<p className="settings-label">Microphone</p>
+				<p className="settings-label">Microphones</p>
 				<p className="settings-description" style={{ color: "#ef4444" }}>
 					{error}
 				</p>
@@ -80,25 +76,112 @@ export function DeviceSelector() {
 		);
 	}
 
-	const selectData = [
-		{ value: "default", label: "System Default" },
-		...devices
-			.filter((device) => device.deviceId !== "default")
-			.map((device) => ({
-				value: device.deviceId,
-				label: device.label,
-			})),
-	];
+	const preferred = settings?.preferred_mic_ids ?? [];
+	const active = activePreferredMic(
+		preferred,
+		devices.map((device) => device.deviceId),
+	);
+	const disabled = updatePreferredMics.isPending;
+
+	const move = (index: number, offset: number) => {
+		const reordered = [...preferred];
+		const [moved] = reordered.splice(index, 1);
+		reordered.splice(index + offset, 0, moved);
+		updatePreferredMics.mutate(reordered);
+	};
+
+	const labelFor = (micId: string) => {
+		const device = devices.find((d) => d.deviceId === micId);
+		if (!device) return `Microphone ${micId.slice(0, 8)} (not connected)`;
+		return micId === active ? `${device.label} (in use)` : device.label;
+	};
+
+	// The webview's "default" entry follows the system, which is the fallback
+	const addData = devices
+		.filter(
+			(device) =>
+				device.deviceId !== "default" && !preferred.includes(device.deviceId),
+		)
+		.map((device) => ({ value: device.deviceId, label: device.label }));
 
 	return (
-		<Select
-			label="Microphone"
-			description="Select which microphone to use for dictation"
-			data={selectData}
-			value={settings?.selected_mic_id ?? "default"}
-			onChange={handleChange}
-			allowDeselect={false}
-			className="device-selector"
-		/>
+		<div className="device-selector">
+			<p className="settings-label">Microphones</p>
+			<p className="settings-description">
+				Dictation records from the first microphone in the list that is
+				connected, and from the system default when none is
+			</p>
+			<div
+				style={{
+					display: "flex",
+					flexDirection: "column",
+					gap: 4,
+					marginTop: 8,
+				}}
+			>
+				{preferred.length === 0 && (
+					<Text size="sm" c="dimmed">
+						Using the system default
+					</Text>
+				)}
+				{preferred.map((micId, index) => (
+					<div
+						key={micId}
+						style={{ display: "flex", gap: 8, alignItems: "center" }}
+					>
+						<Text
+							size="sm"
+							c={micId === active ? undefined : "dimmed"}
+							style={{ flex: 1 }}
+						>
+							{index + 1}. {labelFor(micId)}
+						</Text>
+						<ActionIcon
+							variant="subtle"
+							color="gray"
+							onClick={() => move(index, -1)}
+							title="Prefer"
+							disabled={disabled || index === 0}
+						>
+							<ChevronUp size={14} />
+						</ActionIcon>
+						<ActionIcon
+							variant="subtle"
+							color="gray"
+							onClick={() => move(index, 1)}
+							title="Prefer less"
+							disabled={disabled || index === preferred.length - 1}
+						>
+							<ChevronDown size={14} />
+						</ActionIcon>
+						<ActionIcon
+							variant="subtle"
+							color="red"
+							onClick={() =>
+								updatePreferredMics.mutate(
+									preferred.filter((id) => id !== micId),
+								)
+							}
+							title="Remove"
+							disabled={disabled}
+						>
+							<Trash2 size={14} />
+						</ActionIcon>
+					</div>
+				))}
+				{addData.length > 0 && (
+					<Select
+						placeholder="Add a microphone"
+						data={addData}
+						value={null}
+						onChange={(value) => {
+							if (value) updatePreferredMics.mutate([...preferred, value]);
+						}}
+						disabled={disabled}
+						aria-label="Add a microphone"
+					/>
+				)}
+			</div>
+		</div>
 	);
 }
@@ -0,0 +1,82 @@
+import { ActionIcon, Button, Text } from "@mantine/core";
+import { X } from "lucide-react";
+import { useState } from "react";
+import {
+	useAcceptDictionarySuggestion,
+	useDismissDictionarySuggestion,
+} from "../lib/queries";
+import type { DictionarySuggestion } from "../lib/tauri";
+
+interface DictionarySuggestionListProps {
+	suggestions: DictionarySuggestion[];
+}
+
+function suggestionKey({ heard, corrected }: DictionarySuggestion): string {
+	return `${heard}\n${corrected}`;
+}
+
+/** Words corrected after dictation, each with buttons to add or dismiss it */
+export function DictionarySuggestionList({
+	suggestions,
+}: DictionarySuggestionListProps) {
+	const acceptSuggestion = useAcceptDictionarySuggestion();
+	const dismissSuggestion = useDismissDictionarySuggestion();
+	// Hide handled suggestions right away, even where the list isn't refetched
+	const [handled, setHandled] = useState<Set<string>>(new Set());
+
+	const markHandled = (suggestion: DictionarySuggestion) => {
+		setHandled((prev) => new Set(prev).add(suggestionKey(suggestion)));
+	};
+
+	const visible = suggestions.filter(
+		(suggestion) => !handled.has(suggestionKey(suggestion)),
+	);
+
+	return (
+		<div style={{ display: "flex", flexDirection: "column", gap: 6 }}>
+			{visible.map((suggestion) => (
+				<div
+					key={suggestionKey(suggestion)}
+					style={{ display: "flex", gap: 8, alignItems: "center" }}
+				>
+					<Text size="sm" style={{ flex: 1 }}>
+						{suggestion.heard} → {suggestion.corrected}
+						{suggestion.count > 1 && (
+							<Text span size="xs" c="dimmed">
+								{" "}
+								×{suggestion.count}
+							</Text>
+						)}
+					</Text>
+					<Button
+						size="compact-xs"
+						variant="light"
+						color="gray"
+						onClick={() =>
+							acceptSuggestion.mutate(suggestion, {
+								onSuccess: () => markHandled(suggestion),
+							})
+						}
+						disabled={acceptSuggestion.isPending}
+					>
+						Add
+					</Button>
+					<ActionIcon
+						size="sm"
+						variant="subtle"
+						color="gray"
+						title="Dismiss"
+						onClick={() =>
+							dismissSuggestion.mutate(suggestion, {
+								onSuccess: () => markHandled(suggestion),
+							})
+						}
+						disabled={dismissSuggestion.isPending}
+					>
+						<X size={14} />
+					</ActionIcon>
+				</div>
+			))}
+		</div>
+	);
+}
@@ -1,15 +1,44 @@
-import { ActionIcon, Button, Group, Modal, Text } from "@mantine/core";
-import { useClipboard, useDisclosure } from "@mantine/hooks";
+import {
+	ActionIcon,
+	Button,
+	Group,
+	Modal,
+	Text,
+	TextInput,
+} from "@mantine/core";
+import { useClipboard, useDebouncedValue, useDisclosure } from "@mantine/hooks";
+import { notifications } from "@mantine/notifications";
 import { useQueryClient } from "@tanstack/react-query";
 import { format, isToday, isYesterday } from "date-fns";
-import { Copy, MessageSquare, Trash2 } from "lucide-react";
-import { useEffect } from "react";
+import {
+	Copy,
+	FileDiff,
+	MessageSquare,
+	Pin,
+	PinOff,
+	RotateCcw,
+	Search,
+	Trash2,
+} from "lucide-react";
+import { type CSSProperties, type ReactNode, useEffect, useState } from "react";
 import {
 	useClearHistory,
 	useDeleteHistoryEntry,
 	useHistory,
+	usePinHistoryEntry,
+	usePinnedHistory,
+	useReRunWithCurrentSettings,
+	useTurnDiff,
 } from "../lib/queries";
-import { tauriAPI } from "../lib/tauri";
+import {
+	type DiffKind,
+	type HistoryEntry,
+	type ReRunResult,
+	tauriAPI,
+} from "../lib/tauri";
+
+/** Delay before a search is run while the user is still typing */
+const SEARCH_DEBOUNCE_MS = 300;
 
 function formatTime(timestamp: string): string {
 	return format(new Date(timestamp), "h:mm a");
@@ -22,18 +51,28 @@ function formatDate(timestamp: string): string {
 	return format(date, "MMM d");
 }
 
+function formatDuration(durationMs: number): string {
+	const seconds = Math.max(1, Math.round(durationMs / 1000));
+	return seconds < 60
+		? `${seconds}s`
+		: `${Math.floor(seconds / 60)}m ${seconds % 60}s`;
+}
+
+/** App name and recording length, for entries that recorded them */
+function formatEntryMeta(entry: HistoryEntry): string | null {
+	const parts = [
+		entry.app_name,
+		entry.duration_ms !== null ? formatDuration(entry.duration_ms) : null,
+	].filter(Boolean);
+	return parts.length > 0 ? parts.join(" · ") : null;
+}
+
 interface GroupedHistory {
 	date: string;
-	items: Array<{
-		id: string;
-		text: string;
-		timestamp: string;
-	}>;
+	items: HistoryEntry[];
 }
 
-function groupHistoryByDate(
-	history: Array<{ id: string; text: string; timestamp: string }>,
-): GroupedHistory[] {
+function groupHistoryByDate(history: HistoryEntry[]): GroupedHistory[] {
 	const groups: Record<string, GroupedHistory> = {};
 
 	for (const item of history) {
@@ -47,14 +86,129 @@ function groupHistoryByDate(
 	return Object.values(groups);
 }
 
+function describeSettingsChange(result: ReRunResult): string | null {
+	if (result.settings_changed === null) return null;
+	return result.settings_changed
+		? "Formatting settings have changed since this was dictated."
+		: "Formatting settings are the same as when this was dictated.";
+}
+
+function ReRunComparison({ result }: { result: ReRunResult }) {
+	const clipboard = useClipboard();
+	const settingsNote = describeSettingsChange(result);
+
+	return (
+		<>
+			<Text size="xs" c="dimmed" mb={4}>
+				Original
+			</Text>
+			<Text size="sm" mb="md">
+				{result.entry.text}
+			</Text>
+			<Text size="xs" c="dimmed" mb={4}>
+				With current settings
+			</Text>
+			<Text size="sm" mb="md">
+				{result.text || "(nothing left after formatting)"}
+			</Text>
+			<Group justify="space-between">
+				<Text size="xs" c="dimmed">
+					{settingsNote}
+				</Text>
+				<Button
+					variant="default"
+					size="compact-sm"
+					leftSection={<Copy size={14} />}
+					onClick={() => clipboard.copy(result.text)}
+					disabled={!result.text}
+				>
+					{clipboard.copied ? "Copied" : "Copy"}
+				</Button>
+			</Group>
+		</>
+	);
+}
+
+const DIFF_SEGMENT_STYLES: Record<DiffKind, CSSProperties> = {
+	equal: {},
+	delete: {
+		color: "#ef4444",
+		backgroundColor: "rgba(239, 68, 68, 0.12)",
+		textDecoration: "line-through",
+	},
+	insert: {
+		color: "#22c55e",
+		backgroundColor: "rgba(34, 197, 94, 0.12)",
+	},
+};
+
+/** Raw transcript merged with its formatted text, changed words marked */
+function TurnDiff({ id }: { id: string }) {
+	const { data: segments, isLoading, error } = useTurnDiff(id);
+
+	if (isLoading) {
+		return (
+			<Text size="sm" c="dimmed">
+				Comparing...
+			</Text>
+		);
+	}
+	if (error || !segments) {
+		return (
+			<Text size="sm" c="red">
+				{String(error ?? "Failed to compare")}
+			</Text>
+		);
+	}
+
+	const hasChanges = segments.some((segment) => segment.kind !== "equal");
+	return (
+		<>
+			<Text size="sm" mb="md" style={{ whiteSpace: "pre-wrap" }}>
+				{segments.map((segment, index) => (
+					<span
+						// biome-ignore lint/suspicious/noArrayIndexKey: segments never reorder
+						key={index}
+						style={DIFF_SEGMENT_STYLES[segment.kind]}
+					>
+						{segment.text}
+					</span>
+				))}
+			</Text>
+			<Text size="xs" c="dimmed">
+				{hasChanges
+					? "Struck-through words were removed and highlighted words were added by formatting."
+					: "Formatting did not change the transcript."}
+			</Text>
+		</>
+	);
+}
+
 export function HistoryFeed() {
 	const queryClient = useQueryClient();
-	const { data: history, isLoading, error } = useHistory(100);
+	const [search, setSearch] = useState("");
+	const [debouncedSearch] = useDebouncedValue(
+		search.trim(),
+		SEARCH_DEBOUNCE_MS,
+	);
+	const {
+		data,
+		isLoading,
+		error,
+		fetchNextPage,
+		hasNextPage,
+		isFetchingNextPage,
+	} = useHistory(debouncedSearch);
+	const { data: pinned = [] } = usePinnedHistory();
+	const pinEntry = usePinHistoryEntry();
 	const deleteEntry = useDeleteHistoryEntry();
 	const clearHistory = useClearHistory();
+	const reRun = useReRunWithCurrentSettings();
 	const clipboard = useClipboard();
 	const [confirmOpened, { open: openConfirm, close: closeConfirm }] =
 		useDisclosure(false);
+	const [reRunResult, setReRunResult] = useState<ReRunResult | null>(null);
+	const [diffEntryId, setDiffEntryId] = useState<string | null>(null);
 
 	// Listen for history changes from other windows (e.g., overlay after transcription)
 	useEffect(() => {
@@ -63,6 +217,8 @@ export function HistoryFeed() {
 		const setup = async () => {
 			unlisten = await tauriAPI.onHistoryChanged(() => {
 				queryClient.invalidateQueries({ queryKey: ["history"] });
+				// An entry's text changes when it is corrected from the overlay
+				queryClient.invalidateQueries({ queryKey: ["turnDiff"] });
 			});
 		};
 
@@ -77,6 +233,20 @@ export function HistoryFeed() {
 		deleteEntry.mutate(id);
 	};
 
+	const handleReRun = (id: string) => {
+		reRun.mutate(id, {
+			onSuccess: setReRunResult,
+			onError: (error) => {
+				notifications.show({
+					title: "Re-run Failed",
+					message: String(error),
+					color: "red",
+					autoClose: 5000,
+				});
+			},
+		});
+	};
+
 	const handleClearAll = () => {
 		clearHistory.mutate(undefined, {
 			onSuccess: () => {
@@ -85,54 +255,172 @@ export function HistoryFeed() {
 		});
 	};
 
-	if (isLoading) {
+	const history = data?.pages.flatMap((page) => page.entries) ?? [];
+	const total = data?.pages[0]?.total ?? 0;
+	const isSearching = debouncedSearch.length > 0;
+
+	const renderEntry = (entry: HistoryEntry) => {
+		const meta = formatEntryMeta(entry);
 		return (
-			<div className="animate-in animate-in-delay-2">
-				<div className="section-header">
-					<span className="section-title">History</span>
+			<div key={entry.id} className="history-item">
+				<span className="history-time">{formatTime(entry.timestamp)}</span>
+				<div style={{ flex: 1, minWidth: 0 }}>
+					<p className="history-text">{entry.text}</p>
+					{meta && (
+						<Text size="xs" c="dimmed" mt={4}>
+							{meta}
+						</Text>
+					)}
 				</div>
-				<div className="empty-state">
-					<p className="empty-state-text">Loading history...</p>
+				<div className="history-actions">
+					<ActionIcon
+						variant="subtle"
+						size="sm"
+						color="gray"
+						onClick={() => handleReRun(entry.id)}
+						title={
+							entry.raw_text
+								? "Re-run with current settings"
+								: "No raw transcript recorded for this entry"
+						}
+						disabled={!entry.raw_text}
+						loading={reRun.isPending && reRun.variables === entry.id}
+					>
+						<RotateCcw size={14} />
+					</ActionIcon>
+					<ActionIcon
+						variant="subtle"
+						size="sm"
+						color="gray"
+						onClick={() => setDiffEntryId(entry.id)}
+						title={
+							entry.raw_text
+								? "Show changes made by formatting"
+								: "No raw transcript recorded for this entry"
+						}
+						disabled={!entry.raw_text}
+					>
+						<FileDiff size={14} />
+					</ActionIcon>
+					<ActionIcon
+						variant="subtle"
+						size="sm"
+						color={entry.pinned ? "yellow" : "gray"}
+						onClick={() =>
+							pinEntry.mutate({ id: entry.id, pinned: !entry.pinned })
+						}
+						title={
+							entry.pinned
+								? "Unpin"
+								: "Pin to keep it when clearing history and in quick actions"
+						}
+						disabled={pinEntry.isPending}
+					>
+						{entry.pinned ? <PinOff size={14} /> : <Pin size={14} />}
+					</ActionIcon>
+					<ActionIcon
+						variant="subtle"
+						size="sm"
+						color="gray"
+						onClick={() => clipboard.copy(entry.text)}
+						title="Copy to clipboard"
+					>
+						<Copy size={14} />
+					</ActionIcon>
+					<ActionIcon
+						variant="subtle"
+						size="sm"
+						color="red"
+						onClick={() => handleDelete(entry.id)}
+						title="Delete"
+						disabled={deleteEntry.isPending}
+					>
+						<Trash2 size={14} />
+					</ActionIcon>
 				</div>
 			</div>
 		);
-	}
+	};
 
-	if (error) {
-		return (
-			<div className="animate-in animate-in-delay-2">
-				<div className="section-header">
-					<span className="section-title">History</span>
-				</div>
-				<div className="empty-state">
-					<p className="empty-state-text" style={{ color: "#ef4444" }}>
-						Failed to load history
-					</p>
-				</div>
+	let content: ReactNode;
+	if (isLoading) {
+		content = (
+			<div className="empty-state">
+				<p className="empty-state-text">Loading history...</p>
 			</div>
 		);
-	}
-
-	if (!history || history.length === 0) {
-		return (
-			<div className="animate-in animate-in-delay-2">
-				<div className="section-header">
-					<span className="section-title">History</span>
-				</div>
-				<div className="empty-state">
-					<MessageSquare className="empty-state-icon" />
-					<h4 className="empty-state-title">No dictation history yet</h4>
-					<p className="empty-state-text">
-						Your transcribed text will appear here after you use voice
-						dictation.
-					</p>
-				</div>
+	} else if (error) {
+		content = (
+			<div className="empty-state">
+				<p className="empty-state-text" style={{ color: "#ef4444" }}>
+					Failed to load history
+				</p>
 			</div>
 		);
+	} else if (history.length === 0 && isSearching) {
+		content = (
+			<div className="empty-state">
+				<Search className="empty-state-icon" />
+				<h4 className="empty-state-title">No matching dictations</h4>
+				<p className="empty-state-text">
+					Nothing in your history contains "{debouncedSearch}".
+				</p>
+			</div>
+		);
+	} else if (history.length === 0) {
+		content = (
+			<div className="empty-state">
+				<MessageSquare className="empty-state-icon" />
+				<h4 className="empty-state-title">No dictation history yet</h4>
+				<p className="empty-state-text">
+					Your transcribed text will appear here after you use voice dictation.
+				</p>
+			</div>
+		);
+	} else {
+		content = (
+			<>
+				{!isSearching && pinned.length > 0 && (
+					<div style={{ marginBottom: 24 }}>
+						<p
+							className="section-title"
+							style={{ marginBottom: 12, fontSize: 11 }}
+						>
+							Pinned
+						</p>
+						<div className="history-feed">{pinned.map(renderEntry)}</div>
+					</div>
+				)}
+				{groupHistoryByDate(history).map((group) => (
+					<div key={group.date} style={{ marginBottom: 24 }}>
+						<p
+							className="section-title"
+							style={{ marginBottom: 12, fontSize: 11 }}
+						>
+							{group.date}
+						</p>
+						<div className="history-feed">
+							{group.items.map(renderEntry)}
+						</div>
+					</div>
+				))}
+				{hasNextPage && (
+					<Group justify="center">
+						<Button
+							variant="subtle"
+							size="compact-sm"
+							color="gray"
+							onClick={() => fetchNextPage()}
+							loading={isFetchingNextPage}
+						>
+							Load more ({total - history.length} remaining)
+						</Button>
+					</Group>
+				)}
+			</>
+		);
 	}
 
-	const groupedHistory = groupHistoryByDate(history);
-
 	return (
 		<div className="animate-in animate-in-delay-2">
 			<div className="section-header">
@@ -142,12 +430,21 @@ export function HistoryFeed() {
 					size="compact-sm"
 					color="gray"
 					onClick={openConfirm}
-					disabled={clearHistory.isPending}
+					disabled={clearHistory.isPending || (!isSearching && total === 0)}
 				>
 					Clear All
 				</Button>
 			</div>
 
+			<TextInput
+				placeholder="Search history"
+				leftSection={<Search size={14} />}
+				value={search}
+				onChange={(event) => setSearch(event.currentTarget.value)}
+				size="sm"
+				mb="md"
+			/>
+
 			<Modal
 				opened={confirmOpened}
 				onClose={closeConfirm}
@@ -156,8 +453,8 @@ export function HistoryFeed() {
 				size="sm"
 			>
 				<Text size="sm" mb="lg">
-					Are you sure you want to clear all history? This action cannot be
-					undone.
+					Are you sure you want to clear all history? Pinned dictations are
+					kept. This action cannot be undone.
 				</Text>
 				<Group justify="flex-end">
 					<Button variant="default" onClick={closeConfirm}>
@@ -173,47 +470,25 @@ export function HistoryFeed() {
 				</Group>
 			</Modal>
 
-			{groupedHistory.map((group) => (
-				<div key={group.date} style={{ marginBottom: 24 }}>
-					<p
-						className="section-title"
-						style={{ marginBottom: 12, fontSize: 11 }}
-					>
-						{group.date}
-					</p>
-					<div className="history-feed">
-						{group.items.map((entry) => (
-							<div key={entry.id} className="history-item">
-								<span className="history-time">
-									{formatTime(entry.timestamp)}
-								</span>
-								<p className="history-text">{entry.text}</p>
-								<div className="history-actions">
-									<ActionIcon
-										variant="subtle"
-										size="sm"
-										color="gray"
-										onClick={() => clipboard.copy(entry.text)}
-										title="Copy to clipboard"
-									>
-										<Copy size={14} />
-									</ActionIcon>
-									<ActionIcon
-										variant="subtle"
-										size="sm"
-										color="red"
-										onClick={() => handleDelete(entry.id)}
-										title="Delete"
-										disabled={deleteEntry.isPending}
-									>
-										<Trash2 size={14} />
-									</ActionIcon>
-								</div>
-							</div>
-						))}
-					</div>
-				</div>
-			))}
+			<Modal
+				opened={reRunResult !== null}
+				onClose={() => setReRunResult(null)}
+				title="Re-run with Current Settings"
+				centered
+			>
+				{reRunResult && <ReRunComparison result={reRunResult} />}
+			</Modal>
+
+			<Modal
+				opened={diffEntryId !== null}
+				onClose={() => setDiffEntryId(null)}
+				title="Changes Made by Formatting"
+				centered
+			>
+				{diffEntryId && <TurnDiff id={diffEntryId} />}
+			</Modal>
+
+			{content}
 		</div>
 	);
 }
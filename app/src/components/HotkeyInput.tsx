@@ -1,7 +1,12 @@
-import { Kbd } from "@mantine/core";
-import { useEffect } from "react";
+import { Checkbox, Kbd } from "@mantine/core";
+import { useEffect, useState } from "react";
 import { useRecordHotkeys } from "react-hotkeys-hook";
-import type { HotkeyConfig } from "../lib/tauri";
+import { DEFAULT_DOUBLE_TAP_INTERVAL_MS } from "../lib/hotkeyDefaults";
+import {
+	type HotkeyConfig,
+	type HotkeyStep,
+	keyLabelForLayout,
+} from "../lib/tauri";
 
 interface HotkeyInputProps {
 	label: string;
@@ -9,6 +14,14 @@ interface HotkeyInputProps {
 	value: HotkeyConfig;
 	onChange: (config: HotkeyConfig) => void;
 	disabled?: boolean;
+	// Accept mouse buttons (middle and side buttons) as the trigger
+	allowMouse?: boolean;
+	// Accept a double-tapped modifier (e.g. Ctrl, Ctrl) as the trigger
+	allowDoubleTap?: boolean;
+	// Accept a media key or headset button (e.g. play/pause) as the trigger
+	allowMediaKey?: boolean;
+	// Accept a second key after the combination (e.g. Ctrl+Alt+D then T)
+	allowSequence?: boolean;
 	// Coordinated recording state (managed by parent)
 	isRecording?: boolean;
 	onStartRecording?: () => void;
@@ -123,9 +136,12 @@ function formatKeyForTauri(key: string): string {
 }
 
 /**
- * Convert recorded keys Set to HotkeyConfig
+ * Convert recorded keys Set to HotkeyConfig, keeping the position binding
  */
-function keysToConfig(keys: Set<string>): HotkeyConfig | null {
+function keysToConfig(
+	keys: Set<string>,
+	physical: boolean | undefined,
+): HotkeyConfig | null {
 	const keysArray = Array.from(keys);
 	const modifiers: string[] = [];
 	let mainKey: string | null = null;
@@ -147,6 +163,57 @@ function keysToConfig(keys: Set<string>): HotkeyConfig | null {
 	return {
 		modifiers,
 		key: formatKeyForTauri(mainKey),
+		...(physical ? { physical } : {}),
+	};
+}
+
+/**
+ * Map from MouseEvent.button to the mouse button names understood by Rust.
+ * Left and right clicks are excluded so the input can still be clicked.
+ */
+const MOUSE_BUTTON_MAP: Record<number, string> = {
+	1: "Mouse3",
+	3: "Mouse4",
+	4: "Mouse5",
+};
+
+/**
+ * Map from KeyboardEvent.key to the modifier names understood by Rust
+ */
+const TAP_MODIFIER_MAP: Record<string, string> = {
+	Control: "ctrl",
+	Alt: "alt",
+	Shift: "shift",
+	Meta: "meta",
+};
+
+/**
+ * Media keys understood by Rust, by KeyboardEvent.key. Headset buttons arrive
+ * as these keys too.
+ */
+const MEDIA_KEYS = new Set([
+	"MediaPlayPause",
+	"MediaTrackNext",
+	"MediaTrackPrevious",
+	"MediaStop",
+]);
+
+/** How long to wait for the second step of a sequence while recording */
+const SEQUENCE_CAPTURE_MS = 1500;
+
+/**
+ * Convert a keydown event to a sequence step, using the physical key code
+ * (e.g. "KeyT" -> "T") so modifiers like Shift don't change the key name
+ */
+function eventToStep(event: KeyboardEvent): HotkeyStep {
+	const modifiers: string[] = [];
+	if (event.ctrlKey) modifiers.push("ctrl");
+	if (event.altKey) modifiers.push("alt");
+	if (event.shiftKey) modifiers.push("shift");
+	if (event.metaKey) modifiers.push("meta");
+	return {
+		modifiers,
+		key: formatKeyForTauri(event.code.replace(/^(Key|Digit)/, "")),
 	};
 }
 
@@ -157,12 +224,51 @@ function formatKeyForDisplay(key: string): string {
 	return key.charAt(0).toUpperCase() + key.slice(1);
 }
 
+/** Character each key position types, by KeyboardEvent.code */
+type LayoutMap = ReadonlyMap<string, string>;
+
+/** Keyboard Map API, available in Chromium-based webviews only */
+interface KeyboardLayoutApi {
+	getLayoutMap(): Promise<LayoutMap>;
+}
+
+type NavigatorWithKeyboard = Navigator & { keyboard?: KeyboardLayoutApi };
+
+/**
+ * What the current keyboard layout types at each key position, refreshed when
+ * the window regains focus since the layout may have been switched meanwhile.
+ * Null where the webview can't tell.
+ */
+function useKeyboardLayoutMap(): LayoutMap | null {
+	const [layoutMap, setLayoutMap] = useState<LayoutMap | null>(null);
+
+	useEffect(() => {
+		const { keyboard } = navigator as NavigatorWithKeyboard;
+		if (!keyboard) return;
+		const refresh = () => {
+			keyboard
+				.getLayoutMap()
+				.then(setLayoutMap)
+				.catch(() => setLayoutMap(null));
+		};
+		refresh();
+		window.addEventListener("focus", refresh);
+		return () => window.removeEventListener("focus", refresh);
+	}, []);
+
+	return layoutMap;
+}
+
 export function HotkeyInput({
 	label,
 	description,
 	value,
 	onChange,
 	disabled,
+	allowMouse,
+	allowDoubleTap,
+	allowMediaKey,
+	allowSequence,
 	isRecording: externalIsRecording,
 	onStartRecording,
 	onStopRecording,
@@ -170,9 +276,19 @@ export function HotkeyInput({
 	const [keys, { start, stop, isRecording: internalIsRecording }] =
 		useRecordHotkeys();
 
+	// First step of a sequence, captured while waiting for the second step
+	const [firstStep, setFirstStep] = useState<HotkeyConfig | null>(null);
+
+	const layoutMap = useKeyboardLayoutMap();
+
 	// Use external state if provided, otherwise use internal
 	const isRecording = externalIsRecording ?? internalIsRecording;
 
+	// Drop a half-captured sequence when recording ends
+	useEffect(() => {
+		if (!isRecording) setFirstStep(null);
+	}, [isRecording]);
+
 	// Handle Escape key to cancel recording
 	useEffect(() => {
 		if (!isRecording) return;
@@ -180,6 +296,7 @@ export function HotkeyInput({
 		const handleEscape = (event: KeyboardEvent) => {
 			if (event.key === "Escape") {
 				event.preventDefault();
+				setFirstStep(null);
 				stop();
 				onStopRecording?.();
 			}
@@ -189,9 +306,114 @@ export function HotkeyInput({
 		return () => document.removeEventListener("keydown", handleEscape);
 	}, [isRecording, stop, onStopRecording]);
 
+	// Capture mouse buttons while recording, if enabled
+	useEffect(() => {
+		if (!isRecording || !allowMouse) return;
+
+		const handleMouseDown = (event: MouseEvent) => {
+			const button = MOUSE_BUTTON_MAP[event.button];
+			if (!button) return;
+			event.preventDefault();
+			onChange({ modifiers: [], key: button, trigger: "mouse" });
+			stop();
+			onStopRecording?.();
+		};
+
+		// Prevent side buttons from triggering back/forward navigation
+		const preventNavigation = (event: MouseEvent) => {
+			if (MOUSE_BUTTON_MAP[event.button]) event.preventDefault();
+		};
+
+		document.addEventListener("mousedown", handleMouseDown);
+		document.addEventListener("mouseup", preventNavigation);
+		return () => {
+			document.removeEventListener("mousedown", handleMouseDown);
+			document.removeEventListener("mouseup", preventNavigation);
+		};
+	}, [isRecording, allowMouse, onChange, stop, onStopRecording]);
+
+	// Capture a media key while recording, if enabled
+	useEffect(() => {
+		if (!isRecording || !allowMediaKey) return;
+
+		const handleKeyDown = (event: KeyboardEvent) => {
+			if (!MEDIA_KEYS.has(event.key)) return;
+			event.preventDefault();
+			onChange({ modifiers: [], key: event.key, trigger: "media_key" });
+			stop();
+			onStopRecording?.();
+		};
+
+		document.addEventListener("keydown", handleKeyDown);
+		return () => document.removeEventListener("keydown", handleKeyDown);
+	}, [isRecording, allowMediaKey, onChange, stop, onStopRecording]);
+
+	// Capture a double-tapped modifier while recording, if enabled
+	useEffect(() => {
+		if (!isRecording || !allowDoubleTap) return;
+
+		const tapIntervalMs =
+			value.tap_interval_ms ?? DEFAULT_DOUBLE_TAP_INTERVAL_MS;
+		// Modifier currently held alone, and the last completed tap
+		let pressed: { modifier: string; at: number } | null = null;
+		let lastTap: { modifier: string; at: number } | null = null;
+
+		const handleKeyDown = (event: KeyboardEvent) => {
+			const modifier = TAP_MODIFIER_MAP[event.key];
+			if (!modifier) {
+				// Another key means the modifier is part of a combination
+				pressed = null;
+				lastTap = null;
+				return;
+			}
+			if (!event.repeat) {
+				pressed = { modifier, at: event.timeStamp };
+			}
+		};
+
+		const handleKeyUp = (event: KeyboardEvent) => {
+			const modifier = TAP_MODIFIER_MAP[event.key];
+			if (!modifier || pressed?.modifier !== modifier) return;
+
+			const isTap = event.timeStamp - pressed.at <= tapIntervalMs;
+			const isSecondTap =
+				isTap &&
+				lastTap?.modifier === modifier &&
+				pressed.at - lastTap.at <= tapIntervalMs;
+			lastTap =
+				isTap && !isSecondTap ? { modifier, at: event.timeStamp } : null;
+			pressed = null;
+
+			if (isSecondTap) {
+				onChange({
+					modifiers: [],
+					key: modifier,
+					trigger: "double_tap",
+					tap_interval_ms: value.tap_interval_ms,
+				});
+				stop();
+				onStopRecording?.();
+			}
+		};
+
+		document.addEventListener("keydown", handleKeyDown);
+		document.addEventListener("keyup", handleKeyUp);
+		return () => {
+			document.removeEventListener("keydown", handleKeyDown);
+			document.removeEventListener("keyup", handleKeyUp);
+		};
+	}, [
+		isRecording,
+		allowDoubleTap,
+		value.tap_interval_ms,
+		onChange,
+		stop,
+		onStopRecording,
+	]);
+
 	// Watch for key changes and update when we have a valid combination
 	useEffect(() => {
-		if (!isRecording) return;
+		if (!isRecording || firstStep) return;
 		if (keys.size === 0) return;
 
 		// Check if Escape was pressed (handled separately)
@@ -199,13 +421,58 @@ export function HotkeyInput({
 			return;
 		}
 
-		const config = keysToConfig(keys);
+		const config = keysToConfig(keys, value.physical);
 		if (config) {
-			onChange(config);
 			stop();
+			if (allowSequence) {
+				// Give the user a moment to add a second step
+				setFirstStep(config);
+				return;
+			}
+			onChange(config);
 			onStopRecording?.();
 		}
-	}, [keys, isRecording, onChange, stop, onStopRecording]);
+	}, [
+		keys,
+		isRecording,
+		firstStep,
+		allowSequence,
+		value.physical,
+		onChange,
+		stop,
+		onStopRecording,
+	]);
+
+	// After the first step, wait for a second key or keep the single combination
+	useEffect(() => {
+		if (!firstStep) return;
+
+		const finish = (config: HotkeyConfig) => {
+			setFirstStep(null);
+			onChange(config);
+			stop();
+			onStopRecording?.();
+		};
+
+		const timer = window.setTimeout(
+			() => finish(firstStep),
+			SEQUENCE_CAPTURE_MS,
+		);
+
+		const handleKeyDown = (event: KeyboardEvent) => {
+			// Wait for a non-modifier key; Escape is handled separately
+			if (event.repeat || TAP_MODIFIER_MAP[event.key]) return;
+			if (event.key === "Escape") return;
+			event.preventDefault();
+			finish({ ...firstStep, then: eventToStep(event) });
+		};
+
+		document.addEventListener("keydown", handleKeyDown);
+		return () => {
+			window.clearTimeout(timer);
+			document.removeEventListener("keydown", handleKeyDown);
+		};
+	}, [firstStep, onChange, stop, onStopRecording]);
 
 	// Sync internal recording state with external state
 	useEffect(() => {
@@ -221,6 +488,7 @@ export function HotkeyInput({
 
 		if (isRecording) {
 			// Clicking again cancels
+			setFirstStep(null);
 			stop();
 			onStopRecording?.();
 		} else {
@@ -234,6 +502,13 @@ export function HotkeyInput({
 		.filter((k) => k !== "escape")
 		.map((k) => formatKeyForDisplay(k));
 
+	const isKeyboard = (value.trigger ?? "keyboard") === "keyboard";
+	// Keys bound by position show what the current layout types there
+	const keyLabel = (key: string) =>
+		value.physical && isKeyboard
+			? keyLabelForLayout(key, layoutMap)
+			: formatKeyForDisplay(key);
+
 	return (
 		<div>
 			<p className="settings-label">{label}</p>
@@ -259,7 +534,16 @@ export function HotkeyInput({
 							gap: 8,
 						}}
 					>
-						{livePreview.length > 0 ? (
+						{firstStep ? (
+							<>
+								{firstStep.modifiers.concat([firstStep.key]).map((part) => (
+									<Kbd key={part}>{formatKeyForDisplay(part)}</Kbd>
+								))}
+								<span style={{ color: "var(--accent-primary)", fontSize: 14 }}>
+									then press a key, or wait to keep this shortcut...
+								</span>
+							</>
+						) : livePreview.length > 0 ? (
 							<>
 								{livePreview.map((part) => (
 									<Kbd key={part}>{part}</Kbd>
@@ -276,7 +560,13 @@ export function HotkeyInput({
 							</>
 						) : (
 							<span style={{ color: "var(--accent-primary)", fontSize: 14 }}>
-								Press a key combination...
+								{allowMouse
+									? "Press a key combination or mouse button..."
+									: allowDoubleTap
+										? allowMediaKey
+											? "Press a key combination or media key, or double-tap a modifier..."
+											: "Press a key combination or double-tap a modifier..."
+										: "Press a key combination..."}
 							</span>
 						)}
 						<span
@@ -291,13 +581,39 @@ export function HotkeyInput({
 					</div>
 				) : (
 					<>
-						{value.modifiers.concat([value.key]).map((part) => (
+						{value.modifiers.map((part) => (
 							<Kbd key={part}>{formatKeyForDisplay(part)}</Kbd>
 						))}
+						<Kbd>{keyLabel(value.key)}</Kbd>
+						{value.trigger === "double_tap" && (
+							<span className="kbd-plus">×2</span>
+						)}
+						{value.then && (
+							<>
+								<span className="kbd-plus">then</span>
+								{value.then.modifiers.map((part) => (
+									<Kbd key={`then-${part}`}>{formatKeyForDisplay(part)}</Kbd>
+								))}
+								<Kbd>{keyLabel(value.then.key)}</Kbd>
+							</>
+						)}
 						<span className="hotkey-hint">Click to change</span>
 					</>
 				)}
 			</button>
+			{isKeyboard && (
+				<Checkbox
+					mt={8}
+					size="xs"
+					color="gray"
+					label="Bind to key position (same keys on any keyboard layout)"
+					checked={value.physical ?? false}
+					onChange={(event) =>
+						onChange({ ...value, physical: event.currentTarget.checked })
+					}
+					disabled={disabled || isRecording}
+				/>
+			)}
 		</div>
 	);
 }
@@ -0,0 +1,81 @@
+import { Button, Text } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+import { useQueryClient } from "@tanstack/react-query";
+import { CloudOff } from "lucide-react";
+import { useEffect } from "react";
+import {
+	usePendingTranscriptions,
+	useRetryPendingTranscriptions,
+} from "../lib/queries";
+import { tauriAPI } from "../lib/tauri";
+
+/** Dictations recorded while the server was unreachable, with a retry button */
+export function PendingTranscriptionsNotice() {
+	const queryClient = useQueryClient();
+	const { data: pending } = usePendingTranscriptions();
+	const retryPending = useRetryPendingTranscriptions();
+
+	// Turns are queued and transcribed by the backend
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onPendingTranscriptionsChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["pendingTranscriptions"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	if (!pending || pending.length === 0) return null;
+
+	const handleRetry = () => {
+		retryPending.mutate(undefined, {
+			onError: (error) => {
+				notifications.show({
+					title: "Retry failed",
+					message: String(error),
+					color: "red",
+				});
+			},
+		});
+	};
+
+	return (
+		<div
+			className="settings-card animate-in"
+			style={{
+				display: "flex",
+				alignItems: "center",
+				gap: 12,
+				marginBottom: 24,
+			}}
+		>
+			<CloudOff size={18} />
+			<div style={{ flex: 1 }}>
+				<p className="settings-label">
+					{pending.length === 1
+						? "1 dictation is waiting for the server"
+						: `${pending.length} dictations are waiting for the server`}
+				</p>
+				<Text size="xs" c="dimmed">
+					They are transcribed into history when the server is reachable again
+				</Text>
+			</div>
+			<Button
+				onClick={handleRetry}
+				loading={retryPending.isPending}
+				size="xs"
+				variant="light"
+				color="gray"
+			>
+				Retry now
+			</Button>
+		</div>
+	);
+}
@@ -0,0 +1,206 @@
+import { Button, Group, Modal, SegmentedControl, Text } from "@mantine/core";
+import { useDisclosure } from "@mantine/hooks";
+import { notifications } from "@mantine/notifications";
+import { useQueryClient } from "@tanstack/react-query";
+import { useEffect, useState } from "react";
+import {
+	useExportData,
+	useResetUsageStats,
+	useUsageStats,
+} from "../lib/queries";
+import { type ExportFormat, tauriAPI, type UsagePeriod } from "../lib/tauri";
+
+const PERIOD_OPTIONS: { value: UsagePeriod; label: string }[] = [
+	{ value: "day", label: "Today" },
+	{ value: "week", label: "Week" },
+	{ value: "month", label: "Month" },
+	{ value: "all", label: "All time" },
+];
+
+const PERIOD_PHRASES: Record<UsagePeriod, string> = {
+	day: "today",
+	week: "this week",
+	month: "this month",
+	all: "so far",
+};
+
+/** A duration as hours and minutes, or seconds when under a minute */
+function formatSpan(ms: number): string {
+	const minutes = Math.floor(ms / 60000);
+	if (minutes === 0) return `${Math.round(ms / 1000)}s`;
+	const hours = Math.floor(minutes / 60);
+	return hours > 0 ? `${hours}h ${minutes % 60}m` : `${minutes}m`;
+}
+
+function Stat({ label, value }: { label: string; value: string }) {
+	return (
+		<div>
+			<Text size="lg" fw={600}>
+				{value}
+			</Text>
+			<Text size="xs" c="dimmed">
+				{label}
+			</Text>
+		</div>
+	);
+}
+
+/** Words dictated and time saved over a chosen period */
+export function UsageStatsCard() {
+	const queryClient = useQueryClient();
+	const [period, setPeriod] = useState<UsagePeriod>("week");
+	const { data: stats } = useUsageStats(period);
+	const resetStats = useResetUsageStats();
+	const exportData = useExportData();
+	const [confirmOpened, { open: openConfirm, close: closeConfirm }] =
+		useDisclosure(false);
+
+	// Every dictation added to history also counts towards usage
+	useEffect(() => {
+		const unlisteners: (() => void)[] = [];
+		let isMounted = true;
+
+		const refresh = () => {
+			queryClient.invalidateQueries({ queryKey: ["usageStats"] });
+		};
+
+		Promise.all([
+			tauriAPI.onHistoryChanged(refresh),
+			tauriAPI.onUsageStatsChanged(refresh),
+		]).then((fns) => {
+			if (isMounted) {
+				unlisteners.push(...fns);
+			} else {
+				for (const fn of fns) fn();
+			}
+		});
+
+		return () => {
+			isMounted = false;
+			for (const fn of unlisteners) fn();
+		};
+	}, [queryClient]);
+
+	if (!stats) return null;
+
+	const handleReset = () => {
+		resetStats.mutate(undefined, { onSuccess: closeConfirm });
+	};
+
+	const handleExport = (format: ExportFormat) => {
+		exportData.mutate(
+			{ format, range: period },
+			{
+				onSuccess: (path) => {
+					notifications.show({
+						title: "Export Saved",
+						message: path,
+						color: "green",
+						autoClose: 5000,
+					});
+				},
+				onError: (error) => {
+					notifications.show({
+						title: "Export Failed",
+						message: String(error),
+						color: "red",
+						autoClose: 5000,
+					});
+				},
+			},
+		);
+	};
+
+	const providers = stats.by_provider
+		.map((usage) => `${usage.provider ?? "unknown"} (${usage.dictations})`)
+		.join(", ");
+
+	return (
+		<div className="settings-card animate-in" style={{ marginBottom: 24 }}>
+			<Group justify="space-between" align="flex-start" mb={12}>
+				<div>
+					<p className="settings-label">
+						{stats.dictations === 0
+							? `No dictations ${PERIOD_PHRASES[period]}`
+							: `You saved ${formatSpan(stats.time_saved_ms)} ${PERIOD_PHRASES[period]}`}
+					</p>
+					<Text size="xs" c="dimmed">
+						Compared with typing at 40 words per minute
+					</Text>
+				</div>
+				<SegmentedControl
+					size="xs"
+					value={period}
+					onChange={(value) => setPeriod(value as UsagePeriod)}
+					data={PERIOD_OPTIONS}
+				/>
+			</Group>
+			<Group gap={32}>
+				<Stat label="Words" value={stats.words.toLocaleString()} />
+				<Stat label="Dictations" value={stats.dictations.toLocaleString()} />
+				<Stat label="Speaking time" value={formatSpan(stats.speaking_ms)} />
+			</Group>
+			{providers && (
+				<Text size="xs" c="dimmed" mt={12}>
+					By provider: {providers}
+				</Text>
+			)}
+			<Group gap={4} mt={8}>
+				<Button
+					onClick={() => handleExport("csv")}
+					loading={
+						exportData.isPending && exportData.variables?.format === "csv"
+					}
+					size="compact-xs"
+					variant="subtle"
+					color="gray"
+				>
+					Export CSV
+				</Button>
+				<Button
+					onClick={() => handleExport("json")}
+					loading={
+						exportData.isPending && exportData.variables?.format === "json"
+					}
+					size="compact-xs"
+					variant="subtle"
+					color="gray"
+				>
+					Export JSON
+				</Button>
+				<Button
+					onClick={openConfirm}
+					disabled={stats.dictations === 0 && period === "all"}
+					size="compact-xs"
+					variant="subtle"
+					color="gray"
+				>
+					Reset statistics
+				</Button>
+			</Group>
+
+			<Modal
+				opened={confirmOpened}
+				onClose={closeConfirm}
+				title="Reset statistics?"
+				centered
+			>
+				<Text size="sm" mb={16}>
+					Usage statistics for all time will be deleted. Your history is kept.
+				</Text>
+				<Group justify="flex-end">
+					<Button variant="subtle" color="gray" onClick={closeConfirm}>
+						Cancel
+					</Button>
+					<Button
+						color="red"
+						onClick={handleReset}
+						loading={resetStats.isPending}
+					>
+						Reset
+					</Button>
+				</Group>
+			</Modal>
+		</div>
+	);
+}
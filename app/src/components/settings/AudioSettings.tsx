@@ -1,21 +1,351 @@
-import { Switch, Tooltip } from "@mantine/core";
 import {
+	Button,
+	NumberInput,
+	Progress,
+	Select,
+	Slider,
+	Switch,
+	Text,
+	Tooltip,
+} from "@mantine/core";
+import { useEffect, useState } from "react";
+import { deviceLabel } from "../../lib/nativeCapture";
+import {
+	useCaptureFormats,
 	useIsAudioMuteSupported,
 	useSettings,
+	useUpdateAgcEnabled,
+	useUpdateAudioDuckPercent,
 	useUpdateAutoMuteAudio,
-	useUpdateSoundEnabled,
+	useUpdateAutoStopSilence,
+	useUpdateCaptureFormat,
+	useUpdateEchoCancellationEnabled,
+	useUpdateMaxRecordingSeconds,
+	useUpdateMicGain,
+	useUpdateNativeAudioCapture,
+	useUpdateNoiseSuppressionEnabled,
+	useUpdatePreRoll,
 } from "../../lib/queries";
+import {
+	activePreferredMic,
+	DEFAULT_AUDIO_DUCK_PERCENT,
+	DEFAULT_MAX_RECORDING_SECONDS,
+	DEFAULT_MIC_GAIN,
+	DEFAULT_PRE_ROLL_MS,
+	describeError,
+	MAX_AUDIO_DUCK_PERCENT,
+	MAX_AUTO_STOP_SILENCE_SECONDS,
+	MAX_MIC_GAIN,
+	MAX_PRE_ROLL_MS,
+	MIN_MIC_GAIN,
+	type MicTestLevel,
+	supportedChannelCounts,
+	supportedSampleRates,
+	tauriAPI,
+} from "../../lib/tauri";
 import { DeviceSelector } from "../DeviceSelector";
+import { SoundFeedbackSettings } from "./SoundFeedbackSettings";
+
+/** Level of the microphone while it is captured in the backend */
+function MicLevelMeter() {
+	const [level, setLevel] = useState(0);
+
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onMicLevel(setLevel);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	// RMS levels of speech are low, so the square root spreads them out
+	return (
+		<Progress
+			value={Math.min(100, Math.sqrt(level) * 100)}
+			size="sm"
+			color="gray"
+			transitionDuration={50}
+			aria-label="Microphone level"
+		/>
+	);
+}
+
+/** The preferred microphone dictation records from, null for the default */
+function useActiveMicId(): string | null {
+	const { data: settings } = useSettings();
+	const preferredKey = (settings?.preferred_mic_ids ?? []).join("\n");
+	const [activeMicId, setActiveMicId] = useState<string | null>(null);
+
+	useEffect(() => {
+		const preferred = preferredKey ? preferredKey.split("\n") : [];
+		const resolve = async () => {
+			const devices = await navigator.mediaDevices.enumerateDevices();
+			const deviceIds = devices
+				.filter((device) => device.kind === "audioinput")
+				.map((device) => device.deviceId);
+			setActiveMicId(activePreferredMic(preferred, deviceIds));
+		};
+
+		resolve().catch(() => setActiveMicId(null));
+		navigator.mediaDevices.addEventListener("devicechange", resolve);
+
+		return () => {
+			navigator.mediaDevices.removeEventListener("devicechange", resolve);
+		};
+	}, [preferredKey]);
+
+	return activeMicId;
+}
+
+const SILENT_LEVEL: MicTestLevel = { rms: 0, peak: 0 };
+
+// Peaks this close to full scale are clipped
+const CLIPPING_PEAK = 0.99;
+
+function decibels(level: number): string {
+	return level > 0 ? `${Math.round(20 * Math.log10(level))} dB` : "-∞ dB";
+}
+
+/** Opens the active microphone and shows its level, without dictating */
+function MicTest() {
+	const activeMicId = useActiveMicId();
+	// Name of the microphone being tested
+	const [device, setDevice] = useState<string | null>(null);
+	const [level, setLevel] = useState(SILENT_LEVEL);
+	const [isStarting, setIsStarting] = useState(false);
+	const [error, setError] = useState<string | null>(null);
+
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onMicTestLevel(setLevel);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	// The test stops when another microphone becomes active or settings close
+	useEffect(() => {
+		return () => {
+			tauriAPI.stopMicTest().catch(() => {});
+			setDevice(null);
+			setLevel(SILENT_LEVEL);
+		};
+	}, [activeMicId]);
+
+	const start = async () => {
+		setIsStarting(true);
+		setError(null);
+		try {
+			const label = await deviceLabel(activeMicId);
+			setDevice(await tauriAPI.startMicTest(label));
+		} catch (err) {
+			setError(describeError(err));
+		} finally {
+			setIsStarting(false);
+		}
+	};
+
+	const stop = () => {
+		tauriAPI.stopMicTest().catch(() => {});
+		setDevice(null);
+		setLevel(SILENT_LEVEL);
+	};
+
+	return (
+		<div style={{ marginTop: 16 }}>
+			<div className="settings-row">
+				<div>
+					<p className="settings-label">Test microphone</p>
+					<p className="settings-description">
+						{device
+							? `Listening to ${device}. Speak to see the level`
+							: "Check the active microphone picks up your voice"}
+					</p>
+				</div>
+				<Button
+					onClick={device ? stop : start}
+					loading={isStarting}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					{device ? "Stop" : "Test"}
+				</Button>
+			</div>
+			{device && (
+				<div style={{ marginTop: 12 }}>
+					<Progress
+						value={Math.min(100, Math.sqrt(level.rms) * 100)}
+						size="sm"
+						color={level.peak >= CLIPPING_PEAK ? "red" : "gray"}
+						transitionDuration={50}
+						aria-label="Microphone test level"
+					/>
+					<Text size="xs" c="dimmed" style={{ marginTop: 4 }}>
+						Peak {decibels(level.peak)}
+						{level.peak >= CLIPPING_PEAK && ", clipping. Lower the input gain"}
+					</Text>
+				</div>
+			)}
+			{error && (
+				<p className="settings-description" style={{ color: "#ef4444" }}>
+					{error}
+				</p>
+			)}
+		</div>
+	);
+}
+
+const DEVICE_DEFAULT = "default";
+
+function channelsLabel(channels: number): string {
+	if (channels === 1) return "Mono";
+	if (channels === 2) return "Stereo";
+	return `${channels} channels`;
+}
+
+/** Sample rate and channels the active microphone is captured in */
+function CaptureFormatSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const activeMicId = useActiveMicId();
+	const [label, setLabel] = useState<string | null>(null);
+	const { data: capture } = useCaptureFormats(label);
+	const updateCaptureFormat = useUpdateCaptureFormat();
+	const sampleRate = settings?.sample_rate ?? null;
+	const channels = settings?.channels ?? null;
+
+	useEffect(() => {
+		deviceLabel(activeMicId)
+			.then(setLabel)
+			.catch(() => setLabel(null));
+	}, [activeMicId]);
+
+	// Only combinations the microphone supports are offered, along with the
+	// saved one, which the microphone may not support
+	const formats = capture?.formats ?? [];
+	const rates = supportedSampleRates(formats, channels);
+	if (sampleRate !== null && !rates.includes(sampleRate)) {
+		rates.push(sampleRate);
+	}
+	const channelCounts = supportedChannelCounts(formats, sampleRate);
+	if (channels !== null && !channelCounts.includes(channels)) {
+		channelCounts.push(channels);
+	}
+
+	const save = (format: {
+		sampleRate: number | null;
+		channels: number | null;
+	}) => {
+		updateCaptureFormat.mutate({ deviceLabel: label, ...format });
+	};
+	const parse = (value: string | null) =>
+		value === null || value === DEVICE_DEFAULT ? null : Number(value);
+
+	return (
+		<div style={{ marginTop: 16 }}>
+			<p className="settings-label">Capture format</p>
+			<p className="settings-description">
+				Sample rate and channels to open{" "}
+				{capture?.device ?? "the microphone"} with. Audio is converted for
+				transcription either way, so only change these if the default
+				misbehaves
+			</p>
+			<div style={{ display: "flex", gap: 12, marginTop: 12 }}>
+				<Select
+					aria-label="Sample rate"
+					data={[
+						{ value: DEVICE_DEFAULT, label: "Default rate" },
+						...rates.map((rate) => ({
+							value: String(rate),
+							label: `${rate / 1000} kHz`,
+						})),
+					]}
+					value={sampleRate === null ? DEVICE_DEFAULT : String(sampleRate)}
+					onChange={(value) => save({ sampleRate: parse(value), channels })}
+					allowDeselect={false}
+					disabled={isLoading}
+					style={{ flex: 1 }}
+				/>
+				<Select
+					aria-label="Channels"
+					data={[
+						{ value: DEVICE_DEFAULT, label: "Default channels" },
+						...channelCounts.map((count) => ({
+							value: String(count),
+							label: channelsLabel(count),
+						})),
+					]}
+					value={channels === null ? DEVICE_DEFAULT : String(channels)}
+					onChange={(value) => save({ sampleRate, channels: parse(value) })}
+					allowDeselect={false}
+					disabled={isLoading}
+					style={{ flex: 1 }}
+				/>
+			</div>
+			{updateCaptureFormat.error && (
+				<p className="settings-description" style={{ color: "#ef4444" }}>
+					{describeError(updateCaptureFormat.error)}
+				</p>
+			)}
+		</div>
+	);
+}
 
 export function AudioSettings() {
 	const { data: settings, isLoading } = useSettings();
 	const { data: isAudioMuteSupported } = useIsAudioMuteSupported();
-	const updateSoundEnabled = useUpdateSoundEnabled();
 	const updateAutoMuteAudio = useUpdateAutoMuteAudio();
+	const updateMicGain = useUpdateMicGain();
+	const updateAgcEnabled = useUpdateAgcEnabled();
+	const updateNativeAudioCapture = useUpdateNativeAudioCapture();
+	const updateNoiseSuppression = useUpdateNoiseSuppressionEnabled();
+	const updateEchoCancellation = useUpdateEchoCancellationEnabled();
+	const updateAutoStopSilence = useUpdateAutoStopSilence();
+	const updateMaxRecordingSeconds = useUpdateMaxRecordingSeconds();
+	const updatePreRoll = useUpdatePreRoll();
+	const updateAudioDuckPercent = useUpdateAudioDuckPercent();
 
-	const handleSoundToggle = (checked: boolean) => {
-		updateSoundEnabled.mutate(checked);
-	};
+	const currentMicGain = settings?.mic_gain ?? DEFAULT_MIC_GAIN;
+	const [micGainValue, setMicGainValue] = useState(currentMicGain);
+
+	useEffect(() => {
+		setMicGainValue(currentMicGain);
+	}, [currentMicGain]);
+
+	// 0 on the slider means auto-stop is off
+	const currentAutoStopSeconds = settings?.auto_stop_silence_seconds ?? 0;
+	const [autoStopValue, setAutoStopValue] = useState(currentAutoStopSeconds);
+
+	useEffect(() => {
+		setAutoStopValue(currentAutoStopSeconds);
+	}, [currentAutoStopSeconds]);
+
+	const currentPreRollMs = settings?.pre_roll_ms ?? DEFAULT_PRE_ROLL_MS;
+	const [preRollValue, setPreRollValue] = useState(currentPreRollMs);
+
+	useEffect(() => {
+		setPreRollValue(currentPreRollMs);
+	}, [currentPreRollMs]);
+
+	const currentDuckPercent =
+		settings?.audio_duck_percent ?? DEFAULT_AUDIO_DUCK_PERCENT;
+	const [duckValue, setDuckValue] = useState(currentDuckPercent);
+
+	useEffect(() => {
+		setDuckValue(currentDuckPercent);
+	}, [currentDuckPercent]);
 
 	const handleAutoMuteToggle = (checked: boolean) => {
 		updateAutoMuteAudio.mutate(checked);
@@ -26,16 +356,44 @@ export function AudioSettings() {
 			<h3 className="settings-section-title">Audio</h3>
 			<div className="settings-card">
 				<DeviceSelector />
+				<MicTest />
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Capture in the app</p>
+						<p className="settings-description">
+							Open the microphone in the app itself rather than its webview,
+							for lower latency. Automatic gain control only applies when
+							this is off
+						</p>
+					</div>
+					<Switch
+						checked={settings?.native_audio_capture ?? true}
+						onChange={(event) =>
+							updateNativeAudioCapture.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{(settings?.native_audio_capture ?? true) && (
+					<div style={{ marginTop: 12 }}>
+						<MicLevelMeter />
+					</div>
+				)}
+				{(settings?.native_audio_capture ?? true) && <CaptureFormatSettings />}
 				<div className="settings-row" style={{ marginTop: 16 }}>
 					<div>
-						<p className="settings-label">Sound feedback</p>
+						<p className="settings-label">Noise suppression</p>
 						<p className="settings-description">
-							Play sounds when recording starts and stops
+							Filter out background noise such as fans and keyboard clicks
 						</p>
 					</div>
 					<Switch
-						checked={settings?.sound_enabled ?? true}
-						onChange={(event) => handleSoundToggle(event.currentTarget.checked)}
+						checked={settings?.noise_suppression_enabled ?? false}
+						onChange={(event) =>
+							updateNoiseSuppression.mutate(event.currentTarget.checked)
+						}
 						disabled={isLoading}
 						color="gray"
 						size="md"
@@ -43,9 +401,198 @@ export function AudioSettings() {
 				</div>
 				<div className="settings-row" style={{ marginTop: 16 }}>
 					<div>
-						<p className="settings-label">Mute audio during recording</p>
+						<p className="settings-label">Echo cancellation</p>
 						<p className="settings-description">
-							Automatically mute system audio while dictating
+							Remove audio played by your speakers from the microphone. When
+							capturing in the app, this needs a system that can record its
+							own playback, such as Windows
+						</p>
+					</div>
+					<Switch
+						checked={settings?.echo_cancellation_enabled ?? false}
+						onChange={(event) =>
+							updateEchoCancellation.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div style={{ marginTop: 16 }}>
+					<p className="settings-label">Input gain</p>
+					<p className="settings-description">
+						Boost a quiet microphone before audio is sent for transcription
+					</p>
+					<div
+						style={{
+							marginTop: 12,
+							display: "flex",
+							alignItems: "center",
+							gap: 12,
+						}}
+					>
+						<Slider
+							value={micGainValue}
+							onChange={setMicGainValue}
+							onChangeEnd={(value) => updateMicGain.mutate(value)}
+							min={MIN_MIC_GAIN}
+							max={MAX_MIC_GAIN}
+							step={0.1}
+							marks={[
+								{ value: MIN_MIC_GAIN, label: `${MIN_MIC_GAIN}×` },
+								{ value: MAX_MIC_GAIN, label: `${MAX_MIC_GAIN}×` },
+							]}
+							disabled={isLoading}
+							styles={{
+								root: { flex: 1 },
+								track: { backgroundColor: "var(--bg-elevated)" },
+								bar: { backgroundColor: "var(--accent-primary)" },
+								thumb: { borderColor: "var(--accent-primary)" },
+								markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+							}}
+						/>
+						<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+							{micGainValue.toFixed(1)}×
+						</Text>
+					</div>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Automatic gain control</p>
+						<p className="settings-description">
+							Let the system even out your input volume as you speak
+						</p>
+					</div>
+					<Switch
+						checked={settings?.agc_enabled ?? true}
+						onChange={(event) =>
+							updateAgcEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div style={{ marginTop: 16 }}>
+					<p className="settings-label">Pre-roll</p>
+					<p className="settings-description">
+						Keep audio from just before the hotkey press so your first word
+						isn't cut off
+					</p>
+					<div
+						style={{
+							marginTop: 12,
+							display: "flex",
+							alignItems: "center",
+							gap: 12,
+						}}
+					>
+						<Slider
+							value={preRollValue}
+							onChange={setPreRollValue}
+							onChangeEnd={(value) => updatePreRoll.mutate(value)}
+							min={0}
+							max={MAX_PRE_ROLL_MS}
+							step={100}
+							marks={[
+								{ value: 0, label: "Off" },
+								{ value: MAX_PRE_ROLL_MS, label: `${MAX_PRE_ROLL_MS / 1000}s` },
+							]}
+							disabled={isLoading}
+							styles={{
+								root: { flex: 1 },
+								track: { backgroundColor: "var(--bg-elevated)" },
+								bar: { backgroundColor: "var(--accent-primary)" },
+								thumb: { borderColor: "var(--accent-primary)" },
+								markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+							}}
+						/>
+						<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+							{preRollValue > 0 ? `${preRollValue}ms` : "Off"}
+						</Text>
+					</div>
+				</div>
+				<div style={{ marginTop: 16 }}>
+					<p className="settings-label">Auto-stop on silence</p>
+					<p className="settings-description">
+						Stop toggle recordings after this long without speech
+					</p>
+					<div
+						style={{
+							marginTop: 12,
+							display: "flex",
+							alignItems: "center",
+							gap: 12,
+						}}
+					>
+						<Slider
+							value={autoStopValue}
+							onChange={setAutoStopValue}
+							onChangeEnd={(value) =>
+								updateAutoStopSilence.mutate(value > 0 ? value : null)
+							}
+							min={0}
+							max={MAX_AUTO_STOP_SILENCE_SECONDS}
+							step={1}
+							marks={[
+								{ value: 0, label: "Off" },
+								{
+									value: MAX_AUTO_STOP_SILENCE_SECONDS,
+									label: `${MAX_AUTO_STOP_SILENCE_SECONDS}s`,
+								},
+							]}
+							disabled={isLoading}
+							styles={{
+								root: { flex: 1 },
+								track: { backgroundColor: "var(--bg-elevated)" },
+								bar: { backgroundColor: "var(--accent-primary)" },
+								thumb: { borderColor: "var(--accent-primary)" },
+								markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+							}}
+						/>
+						<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+							{autoStopValue > 0 ? `${autoStopValue}s` : "Off"}
+						</Text>
+					</div>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Recording limit</p>
+						<p className="settings-description">
+							Longest a recording runs before it stops by itself and is
+							transcribed. 0 means no limit
+						</p>
+					</div>
+					<NumberInput
+						value={
+							settings?.max_recording_seconds ?? DEFAULT_MAX_RECORDING_SECONDS
+						}
+						onChange={(value) => {
+							if (typeof value === "number") {
+								updateMaxRecordingSeconds.mutate(value);
+							}
+						}}
+						min={0}
+						step={60}
+						allowDecimal={false}
+						suffix=" s"
+						w={110}
+						disabled={isLoading}
+						styles={{
+							input: {
+								backgroundColor: "var(--bg-elevated)",
+								borderColor: "var(--border-default)",
+								color: "var(--text-primary)",
+							},
+						}}
+					/>
+				</div>
+				<SoundFeedbackSettings />
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Quiet audio during recording</p>
+						<p className="settings-description">
+							Automatically mute or lower system audio while dictating
 						</p>
 					</div>
 					<Tooltip
@@ -64,6 +611,49 @@ export function AudioSettings() {
 						/>
 					</Tooltip>
 				</div>
+				{settings?.auto_mute_audio && isAudioMuteSupported !== false && (
+					<div style={{ marginTop: 12 }}>
+						<p className="settings-description">
+							System volume kept while recording. Lowered audio fades down and
+							back up instead of cutting out
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={duckValue}
+								onChange={setDuckValue}
+								onChangeEnd={(value) => updateAudioDuckPercent.mutate(value)}
+								min={0}
+								max={MAX_AUDIO_DUCK_PERCENT}
+								step={5}
+								marks={[
+									{ value: 0, label: "Mute" },
+									{
+										value: MAX_AUDIO_DUCK_PERCENT,
+										label: `${MAX_AUDIO_DUCK_PERCENT}%`,
+									},
+								]}
+								disabled={isLoading}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+								{duckValue > 0 ? `${duckValue}%` : "Mute"}
+							</Text>
+						</div>
+					</div>
+				)}
 			</div>
 		</div>
 	);
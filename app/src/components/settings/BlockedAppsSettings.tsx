@@ -0,0 +1,87 @@
+import { ActionIcon, Button, TextInput } from "@mantine/core";
+import { Plus, Trash2 } from "lucide-react";
+import { useState } from "react";
+import { useSettings, useUpdateBlockedApps } from "../../lib/queries";
+
+export function BlockedAppsSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateBlockedApps = useUpdateBlockedApps();
+	const [newApp, setNewApp] = useState("");
+
+	const blockedApps = settings?.blocked_apps ?? [];
+	const disabled = isLoading || updateBlockedApps.isPending;
+
+	const handleAdd = () => {
+		const app = newApp.trim();
+		if (!app || blockedApps.includes(app)) return;
+		updateBlockedApps.mutate([...blockedApps, app], {
+			onSuccess: () => setNewApp(""),
+		});
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Blocked Apps</h3>
+			<div className="settings-card">
+				<div
+					className="settings-row"
+					style={{ flexDirection: "column", alignItems: "stretch", gap: 8 }}
+				>
+					<div>
+						<p className="settings-label">Never dictate into</p>
+						<p className="settings-description">
+							While an app whose name, executable or bundle ID contains the
+							text is focused, hotkeys don't start recording and nothing is
+							typed or pasted, e.g. password managers
+						</p>
+					</div>
+					{blockedApps.map((app) => (
+						<div
+							key={app}
+							style={{ display: "flex", gap: 8, alignItems: "center" }}
+						>
+							<span className="settings-label" style={{ flex: 1 }}>
+								{app}
+							</span>
+							<ActionIcon
+								variant="subtle"
+								color="red"
+								onClick={() =>
+									updateBlockedApps.mutate(
+										blockedApps.filter((existing) => existing !== app),
+									)
+								}
+								title="Remove"
+								disabled={disabled}
+							>
+								<Trash2 size={14} />
+							</ActionIcon>
+						</div>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newApp}
+							onChange={(event) => setNewApp(event.currentTarget.value)}
+							onKeyDown={(event) => {
+								if (event.key === "Enter") handleAdd();
+							}}
+							placeholder="App name, e.g. 1Password"
+							disabled={isLoading}
+							style={{ flex: 1 }}
+						/>
+						<Button
+							onClick={handleAdd}
+							disabled={disabled || !newApp.trim()}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+				</div>
+			</div>
+		</div>
+	);
+}
@@ -1,17 +1,70 @@
-import { Button, Loader, TextInput } from "@mantine/core";
-import { Check, RefreshCw, X } from "lucide-react";
+import {
+	ActionIcon,
+	Button,
+	Loader,
+	NumberInput,
+	PasswordInput,
+	Switch,
+	Text,
+	TextInput,
+} from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { Check, Plus, RefreshCw, X } from "lucide-react";
 import { useCallback, useEffect, useState } from "react";
-import { useSettings, useUpdateServerUrl } from "../../lib/queries";
-import { DEFAULT_SERVER_URL, tauriAPI } from "../../lib/tauri";
+import {
+	useAuthStatus,
+	useClearAuthToken,
+	useServerUrl,
+	useSetAuthToken,
+	useSettings,
+	useUpdateAllowSelfSignedCerts,
+	useUpdateFallbackServerUrls,
+	useUpdateOpusBitrate,
+	useUpdatePinnedCertSha256,
+	useUpdateProxySettings,
+	useUpdateServerUrl,
+} from "../../lib/queries";
+import {
+	type AuthStatus,
+	type ConnectionTest,
+	DEFAULT_OPUS_BITRATE_KBPS,
+	DEFAULT_SERVER_URL,
+	MAX_OPUS_BITRATE_KBPS,
+	MIN_OPUS_BITRATE_KBPS,
+	tauriAPI,
+} from "../../lib/tauri";
 import { useRecordingStore } from "../../stores/recordingStore";
 
 type PingStatus = "idle" | "loading" | "success" | "error";
 
 export function ConnectionSettings() {
+	const queryClient = useQueryClient();
 	const { data: settings, isLoading } = useSettings();
 	const updateServerUrl = useUpdateServerUrl();
+	const updateFallbackServerUrls = useUpdateFallbackServerUrls();
+	const { data: activeServerUrl } = useServerUrl();
 	const [localUrl, setLocalUrl] = useState<string | null>(null);
+	const [newFallbackUrl, setNewFallbackUrl] = useState("");
 	const [pingStatus, setPingStatus] = useState<PingStatus>("idle");
+	const [testResult, setTestResult] = useState<ConnectionTest | null>(null);
+	const updateAllowSelfSignedCerts = useUpdateAllowSelfSignedCerts();
+	const updatePinnedCertSha256 = useUpdatePinnedCertSha256();
+	const [localPin, setLocalPin] = useState<string | null>(null);
+	const updateProxySettings = useUpdateProxySettings();
+	const updateOpusBitrate = useUpdateOpusBitrate();
+	const [localProxyUrl, setLocalProxyUrl] = useState<string | null>(null);
+	const [localProxyUsername, setLocalProxyUsername] = useState<string | null>(
+		null,
+	);
+	// Left blank to keep the saved password
+	const [proxyPassword, setProxyPassword] = useState("");
+	const [proxyError, setProxyError] = useState<string | null>(null);
+	const { data: authStatus } = useAuthStatus();
+	const setAuthToken = useSetAuthToken();
+	const clearAuthToken = useClearAuthToken();
+	const [accessToken, setAccessToken] = useState("");
+	const [refreshToken, setRefreshToken] = useState("");
+	const [authError, setAuthError] = useState<string | null>(null);
 
 	// Connection state from store
 	const connectionState = useRecordingStore((s) => s.state);
@@ -34,6 +87,23 @@ export function ConnectionSettings() {
 		};
 	}, [setConnectionState]);
 
+	// The backend refreshes the token and reports when it changes
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onAuthStatusChanged((status) => {
+				queryClient.setQueryData(["authStatus"], status);
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
 	// Use local state if user is editing, otherwise use saved value
 	const displayUrl = localUrl ?? settings?.server_url ?? DEFAULT_SERVER_URL;
 	const hasChanges = localUrl !== null && localUrl !== settings?.server_url;
@@ -65,26 +135,60 @@ export function ConnectionSettings() {
 		}
 	};
 
+	const fallbackUrls = settings?.fallback_server_urls ?? [];
+	const trimmedFallbackUrl = newFallbackUrl.trim().replace(/\/+$/, "");
+	const canAddFallback =
+		trimmedFallbackUrl !== "" &&
+		trimmedFallbackUrl !== settings?.server_url &&
+		!fallbackUrls.includes(trimmedFallbackUrl);
+
+	const handleAddFallback = () => {
+		if (!canAddFallback) return;
+		updateFallbackServerUrls.mutate([...fallbackUrls, trimmedFallbackUrl], {
+			onSuccess: () => setNewFallbackUrl(""),
+		});
+	};
+
+	const handleRemoveFallback = (url: string) => {
+		updateFallbackServerUrls.mutate(fallbackUrls.filter((u) => u !== url));
+	};
+
+	const handleSaveAuthToken = () => {
+		if (!accessToken.trim()) return;
+		setAuthToken.mutate(
+			{ accessToken, refreshToken: refreshToken.trim() || null },
+			{
+				onSuccess: () => {
+					setAccessToken("");
+					setRefreshToken("");
+					setAuthError(null);
+				},
+				onError: (error) => setAuthError(String(error)),
+			},
+		);
+	};
+
 	const handleReconnect = useCallback(() => {
-		tauriAPI.emitReconnect();
+		tauriAPI.reconnectServer();
 	}, []);
 
+	// Tested by the backend, which applies the certificate options below
 	const handlePing = useCallback(async () => {
-		const urlToTest = displayUrl;
 		setPingStatus("loading");
+		setTestResult(null);
 
 		try {
-			const response = await fetch(`${urlToTest}/health`, {
-				method: "GET",
-				signal: AbortSignal.timeout(5000),
+			const result = await tauriAPI.testServerConnection(displayUrl);
+			setTestResult(result);
+			setPingStatus(result.reachable ? "success" : "error");
+		} catch (error) {
+			setTestResult({
+				reachable: false,
+				latency_ms: null,
+				capabilities: null,
+				certificate_sha256: null,
+				error: String(error),
 			});
-
-			if (response.ok) {
-				setPingStatus("success");
-			} else {
-				setPingStatus("error");
-			}
-		} catch {
 			setPingStatus("error");
 		}
 
@@ -94,8 +198,51 @@ export function ConnectionSettings() {
 		}, 5000);
 	}, [displayUrl]);
 
+	const savedPin = settings?.pinned_cert_sha256 ?? "";
+	const displayPin = localPin ?? savedPin;
+	const hasPinChanges = localPin !== null && localPin.trim() !== savedPin;
+
+	const handleSavePin = (fingerprint: string) => {
+		updatePinnedCertSha256.mutate(fingerprint.trim() || null, {
+			onSuccess: () => setLocalPin(null),
+		});
+	};
+
+	const savedProxyUrl = settings?.proxy_url ?? "";
+	const savedProxyUsername = settings?.proxy_auth?.username ?? "";
+	const displayProxyUrl = localProxyUrl ?? savedProxyUrl;
+	const displayProxyUsername = localProxyUsername ?? savedProxyUsername;
+	const hasProxyChanges =
+		displayProxyUrl.trim() !== savedProxyUrl ||
+		displayProxyUsername.trim() !== savedProxyUsername ||
+		proxyPassword !== "";
+
+	const handleSaveProxy = (proxyUrl: string) => {
+		const username = displayProxyUsername.trim();
+		const password = proxyPassword || settings?.proxy_auth?.password || "";
+		updateProxySettings.mutate(
+			{
+				proxyUrl: proxyUrl.trim() || null,
+				proxyAuth: username ? { username, password } : null,
+			},
+			{
+				onSuccess: () => {
+					setLocalProxyUrl(null);
+					setLocalProxyUsername(null);
+					setProxyPassword("");
+					setProxyError(null);
+				},
+				onError: (error) => setProxyError(String(error)),
+			},
+		);
+	};
+
 	// Connection state display helpers
 	const isConnecting = connectionState === "connecting";
+	const isConnected =
+		connectionState === "idle" ||
+		connectionState === "recording" ||
+		connectionState === "processing";
 
 	const getStateDisplay = () => {
 		switch (connectionState) {
@@ -161,6 +308,9 @@ export function ConnectionSettings() {
 								{stateDisplay.text}
 							</span>
 						</div>
+						{isConnected && activeServerUrl && (
+							<p className="settings-description">to {activeServerUrl}</p>
+						)}
 					</div>
 					<Button
 						onClick={handleReconnect}
@@ -253,8 +403,403 @@ export function ConnectionSettings() {
 							</Button>
 						)}
 					</div>
+					{testResult && <ConnectionTestDetails result={testResult} />}
+					{testResult?.certificate_sha256 &&
+						testResult.certificate_sha256 !== savedPin && (
+							<div>
+								<Button
+									onClick={() =>
+										testResult.certificate_sha256 &&
+										handleSavePin(testResult.certificate_sha256)
+									}
+									loading={updatePinnedCertSha256.isPending}
+									size="xs"
+									variant="light"
+									color="gray"
+								>
+									Pin this certificate
+								</Button>
+							</div>
+						)}
+				</div>
+			</div>
+
+			{/* Auth Token Row */}
+			<div className="settings-card" style={{ marginTop: 12 }}>
+				<div
+					className="settings-row"
+					style={{ flexDirection: "column", alignItems: "stretch", gap: 8 }}
+				>
+					<div>
+						<p className="settings-label">Auth token</p>
+						<p className="settings-description">
+							For servers that require sign-in. Stored in the system keychain
+							and refreshed before it expires
+						</p>
+					</div>
+					{authStatus?.is_set && <AuthStatusDetails status={authStatus} />}
+					<PasswordInput
+						value={accessToken}
+						onChange={(e) => setAccessToken(e.currentTarget.value)}
+						placeholder={
+							authStatus?.is_set ? "Replace access token" : "Access token"
+						}
+					/>
+					<PasswordInput
+						value={refreshToken}
+						onChange={(e) => setRefreshToken(e.currentTarget.value)}
+						placeholder="Refresh token (optional)"
+					/>
+					{authError && (
+						<Text size="xs" c="red">
+							{authError}
+						</Text>
+					)}
+					<div style={{ display: "flex", gap: 8 }}>
+						<Button
+							onClick={handleSaveAuthToken}
+							disabled={!accessToken.trim()}
+							loading={setAuthToken.isPending}
+							size="sm"
+							color="gray"
+						>
+							Save
+						</Button>
+						{authStatus?.is_set && (
+							<Button
+								onClick={() => clearAuthToken.mutate()}
+								loading={clearAuthToken.isPending}
+								size="sm"
+								variant="subtle"
+								color="gray"
+							>
+								Sign out
+							</Button>
+						)}
+					</div>
 				</div>
 			</div>
+
+			{/* Certificate Row */}
+			<div className="settings-card" style={{ marginTop: 12 }}>
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Allow self-signed certificates</p>
+						<p className="settings-description">
+							Trust HTTPS servers whose certificate isn't issued by a known
+							authority, such as a self-hosted server
+						</p>
+					</div>
+					<Switch
+						checked={settings?.allow_self_signed_certs ?? false}
+						onChange={(event) =>
+							updateAllowSelfSignedCerts.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div
+					className="settings-row"
+					style={{
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+						marginTop: 16,
+					}}
+				>
+					<div>
+						<p className="settings-label">Pinned certificate</p>
+						<p className="settings-description">
+							SHA-256 fingerprint the server URL's certificate must have.
+							Connections to a server presenting any other certificate are
+							refused
+						</p>
+					</div>
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={displayPin}
+							onChange={(e) => setLocalPin(e.currentTarget.value)}
+							onKeyDown={(e) => {
+								if (e.key === "Enter" && hasPinChanges) {
+									handleSavePin(displayPin);
+								}
+							}}
+							placeholder="Not pinned"
+							disabled={isLoading}
+							style={{ flex: 1 }}
+							styles={{
+								input: {
+									fontFamily: "monospace",
+									fontSize: "13px",
+								},
+							}}
+						/>
+						{hasPinChanges && (
+							<Button
+								onClick={() => handleSavePin(displayPin)}
+								loading={updatePinnedCertSha256.isPending}
+								size="sm"
+								color="gray"
+							>
+								Save
+							</Button>
+						)}
+						{savedPin !== "" && !hasPinChanges && (
+							<Button
+								onClick={() => handleSavePin("")}
+								loading={updatePinnedCertSha256.isPending}
+								size="sm"
+								variant="subtle"
+								color="gray"
+							>
+								Clear
+							</Button>
+						)}
+					</div>
+				</div>
+			</div>
+
+			{/* Proxy Row */}
+			<div className="settings-card" style={{ marginTop: 12 }}>
+				<div
+					className="settings-row"
+					style={{ flexDirection: "column", alignItems: "stretch", gap: 8 }}
+				>
+					<div>
+						<p className="settings-label">Proxy</p>
+						<p className="settings-description">
+							Reach the server through an HTTP, HTTPS or SOCKS5 proxy. The
+							overlay uses a new proxy after a restart, and only HTTP or
+							SOCKS5 proxies without a username
+						</p>
+					</div>
+					<TextInput
+						value={displayProxyUrl}
+						onChange={(e) => setLocalProxyUrl(e.currentTarget.value)}
+						placeholder="http://proxy.example.com:3128"
+						disabled={isLoading}
+						styles={{
+							input: {
+								fontFamily: "monospace",
+								fontSize: "13px",
+							},
+						}}
+					/>
+					<div style={{ display: "flex", gap: 8 }}>
+						<TextInput
+							value={displayProxyUsername}
+							onChange={(e) => setLocalProxyUsername(e.currentTarget.value)}
+							placeholder="Username (optional)"
+							disabled={isLoading}
+							style={{ flex: 1 }}
+						/>
+						<PasswordInput
+							value={proxyPassword}
+							onChange={(e) => setProxyPassword(e.currentTarget.value)}
+							placeholder={
+								settings?.proxy_auth ? "Keep saved password" : "Password"
+							}
+							disabled={isLoading}
+							style={{ flex: 1 }}
+						/>
+					</div>
+					{proxyError && (
+						<Text size="xs" c="red">
+							{proxyError}
+						</Text>
+					)}
+					<div style={{ display: "flex", gap: 8 }}>
+						<Button
+							onClick={() => handleSaveProxy(displayProxyUrl)}
+							disabled={!hasProxyChanges}
+							loading={updateProxySettings.isPending}
+							size="sm"
+							color="gray"
+						>
+							Save
+						</Button>
+						{savedProxyUrl !== "" && (
+							<Button
+								onClick={() => handleSaveProxy("")}
+								loading={updateProxySettings.isPending}
+								size="sm"
+								variant="subtle"
+								color="gray"
+							>
+								Turn off
+							</Button>
+						)}
+					</div>
+				</div>
+			</div>
+
+			{/* Upload Bitrate Row */}
+			<div className="settings-card" style={{ marginTop: 12 }}>
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Upload bitrate</p>
+						<p className="settings-description">
+							Dictations queued while offline and retried recordings are sent
+							compressed with Opus when the server supports it. Lower uses
+							less data on slow connections
+						</p>
+					</div>
+					<NumberInput
+						value={settings?.opus_bitrate_kbps ?? DEFAULT_OPUS_BITRATE_KBPS}
+						onChange={(value) => {
+							if (typeof value === "number") {
+								updateOpusBitrate.mutate(value);
+							}
+						}}
+						min={MIN_OPUS_BITRATE_KBPS}
+						max={MAX_OPUS_BITRATE_KBPS}
+						step={8}
+						allowDecimal={false}
+						suffix=" kbps"
+						w={110}
+						disabled={isLoading}
+						styles={{
+							input: {
+								backgroundColor: "var(--bg-elevated)",
+								borderColor: "var(--border-default)",
+								color: "var(--text-primary)",
+							},
+						}}
+					/>
+				</div>
+			</div>
+
+			{/* Fallback Servers Row */}
+			<div className="settings-card" style={{ marginTop: 12 }}>
+				<div
+					className="settings-row"
+					style={{ flexDirection: "column", alignItems: "stretch", gap: 8 }}
+				>
+					<div>
+						<p className="settings-label">Fallback servers</p>
+						<p className="settings-description">
+							Tried in order when the server above is unreachable
+						</p>
+					</div>
+					{fallbackUrls.map((url) => (
+						<div
+							key={url}
+							style={{ display: "flex", gap: 8, alignItems: "center" }}
+						>
+							<span
+								style={{ flex: 1, fontFamily: "monospace", fontSize: "13px" }}
+							>
+								{url}
+							</span>
+							<ActionIcon
+								onClick={() => handleRemoveFallback(url)}
+								disabled={updateFallbackServerUrls.isPending}
+								variant="subtle"
+								color="gray"
+								aria-label={`Remove ${url}`}
+							>
+								<X size={14} />
+							</ActionIcon>
+						</div>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newFallbackUrl}
+							onChange={(e) => setNewFallbackUrl(e.currentTarget.value)}
+							onKeyDown={(e) => {
+								if (e.key === "Enter") handleAddFallback();
+							}}
+							placeholder="https://tambourine.example.com"
+							disabled={isLoading}
+							style={{ flex: 1 }}
+							styles={{
+								input: {
+									fontFamily: "monospace",
+									fontSize: "13px",
+								},
+							}}
+						/>
+						<Button
+							onClick={handleAddFallback}
+							disabled={!canAddFallback}
+							loading={updateFallbackServerUrls.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+				</div>
+			</div>
+		</div>
+	);
+}
+
+/** Latency, providers and certificate reported by a connection test */
+function ConnectionTestDetails({ result }: { result: ConnectionTest }) {
+	if (!result.reachable) {
+		return (
+			<Text size="xs" c="red">
+				{result.error ?? "Server is unreachable"}
+			</Text>
+		);
+	}
+
+	const capabilities = result.capabilities;
+	return (
+		<div>
+			<Text size="xs" c="dimmed">
+				Responded in {result.latency_ms ?? 0} ms
+			</Text>
+			{capabilities ? (
+				<Text size="xs" c="dimmed">
+					Speech-to-text: {capabilities.stt_providers.join(", ") || "none"}
+					{" · "}
+					Formatting: {capabilities.llm_providers.join(", ") || "none"}
+				</Text>
+			) : (
+				<Text size="xs" c="dimmed">
+					This server doesn't report its providers
+				</Text>
+			)}
+			{result.certificate_sha256 && (
+				<Text size="xs" c="dimmed" style={{ wordBreak: "break-all" }}>
+					Certificate SHA-256: {result.certificate_sha256}
+				</Text>
+			)}
+		</div>
+	);
+}
+
+/** Expiry and refresh state of the stored auth token */
+function AuthStatusDetails({ status }: { status: AuthStatus }) {
+	const expiry = status.expires_at
+		? new Date(status.expires_at).toLocaleString()
+		: null;
+	const refreshNote =
+		status.can_refresh && !status.is_expired
+			? " (refreshed automatically)"
+			: "";
+
+	return (
+		<div>
+			<Text size="xs" c={status.is_expired ? "red" : "dimmed"}>
+				{status.is_expired
+					? `Token expired ${expiry}`
+					: expiry
+						? `Token saved, expires ${expiry}`
+						: "Token saved, does not expire"}
+				{refreshNote}
+			</Text>
+			{status.refresh_error && (
+				<Text size="xs" c="red">
+					{status.refresh_error}
+				</Text>
+			)}
 		</div>
 	);
 }
@@ -0,0 +1,391 @@
+import {
+	ActionIcon,
+	Button,
+	Checkbox,
+	Select,
+	Switch,
+	Text,
+	TextInput,
+} from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useAddDictionaryEntry,
+	useDictionaryEntries,
+	useDictionarySuggestions,
+	useImportDictionary,
+	useRemoveDictionaryEntry,
+	useSettings,
+	useUpdateCleanupPromptSections,
+	useUpdateDictionaryEntry,
+} from "../../lib/queries";
+import {
+	DEFAULT_CLEANUP_PROMPT_SECTIONS,
+	type DictionaryEntry,
+	type DictionaryImport,
+	type DictionaryImportFormat,
+	dictionaryImportFormat,
+	tauriAPI,
+} from "../../lib/tauri";
+import { DictionarySuggestionList } from "../DictionarySuggestionList";
+
+const IMPORT_FORMAT_OPTIONS: {
+	value: DictionaryImportFormat;
+	label: string;
+}[] = [
+	{ value: "csv", label: "CSV" },
+	{ value: "text", label: "Text" },
+];
+
+const selectStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+function describeImport(result: DictionaryImport): string {
+	const parts = [
+		`${result.added.length} ${result.dry_run ? "to add" : "added"}`,
+		`${result.duplicates.length} already listed`,
+	];
+	if (result.conflicts.length > 0) {
+		parts.push(`${result.conflicts.length} written differently`);
+	}
+	if (result.skipped.length > 0) {
+		parts.push(`${result.skipped.length} unreadable lines`);
+	}
+	return parts.join(", ");
+}
+
+// Preview first, so duplicates and conflicts can be checked before importing
+function DictionaryImportRow() {
+	const importDictionary = useImportDictionary();
+	const [path, setPath] = useState("");
+	const [format, setFormat] = useState<DictionaryImportFormat>("text");
+	const [result, setResult] = useState<DictionaryImport | null>(null);
+
+	const handlePathChange = (value: string) => {
+		setPath(value);
+		setFormat(dictionaryImportFormat(value));
+		setResult(null);
+	};
+
+	const runImport = (dryRun: boolean) => {
+		importDictionary.mutate({ path, format, dryRun }, { onSuccess: setResult });
+	};
+
+	return (
+		<div style={{ display: "flex", flexDirection: "column", gap: 8 }}>
+			<div>
+				<p className="settings-label">Import from file</p>
+				<p className="settings-description">
+					A CSV of spoken form, written form and match case columns, or a text
+					file with one "spoken = written" entry per line
+				</p>
+			</div>
+			<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+				<TextInput
+					value={path}
+					onChange={(event) => handlePathChange(event.currentTarget.value)}
+					placeholder="/path/to/terms.csv"
+					style={{ flex: 1 }}
+				/>
+				<Select
+					data={IMPORT_FORMAT_OPTIONS}
+					value={format}
+					onChange={(value) => {
+						if (value === "csv" || value === "text") {
+							setFormat(value);
+							setResult(null);
+						}
+					}}
+					allowDeselect={false}
+					w={90}
+					styles={selectStyles}
+				/>
+				<Button
+					onClick={() => runImport(true)}
+					disabled={!path.trim() || importDictionary.isPending}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Preview
+				</Button>
+				<Button
+					onClick={() => runImport(false)}
+					disabled={
+						!result?.dry_run ||
+						result.added.length === 0 ||
+						importDictionary.isPending
+					}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Import
+				</Button>
+			</div>
+			{result && (
+				<Text size="xs" c="dimmed">
+					{describeImport(result)}
+				</Text>
+			)}
+			{result?.conflicts.slice(0, 5).map(({ entry, existing }) => (
+				<Text key={entry.line} size="xs" c="dimmed">
+					{`Line ${entry.line}: "${entry.spoken}" is already written "${existing.written}"`}
+				</Text>
+			))}
+			{importDictionary.error && (
+				<Text size="xs" c="red">
+					{String(importDictionary.error)}
+				</Text>
+			)}
+		</div>
+	);
+}
+
+interface DictionaryEntryRowProps {
+	entry: DictionaryEntry;
+	onSave: (entry: DictionaryEntry) => void;
+	onRemove: (id: string) => void;
+	disabled: boolean;
+}
+
+// Text fields save when they lose focus, the checkbox right away
+function DictionaryEntryRow({
+	entry,
+	onSave,
+	onRemove,
+	disabled,
+}: DictionaryEntryRowProps) {
+	const [spoken, setSpoken] = useState(entry.spoken);
+	const [written, setWritten] = useState(entry.written);
+
+	useEffect(() => {
+		setSpoken(entry.spoken);
+		setWritten(entry.written);
+	}, [entry.spoken, entry.written]);
+
+	const saveText = () => {
+		if (spoken === entry.spoken && written === entry.written) return;
+		onSave({ ...entry, spoken, written });
+	};
+
+	return (
+		<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+			<TextInput
+				value={spoken}
+				onChange={(event) => setSpoken(event.currentTarget.value)}
+				onBlur={saveText}
+				aria-label="Spoken form"
+				disabled={disabled}
+				style={{ flex: 1 }}
+			/>
+			<span className="settings-description">→</span>
+			<TextInput
+				value={written}
+				onChange={(event) => setWritten(event.currentTarget.value)}
+				onBlur={saveText}
+				aria-label="Written form"
+				disabled={disabled}
+				style={{ flex: 1 }}
+			/>
+			<Checkbox
+				checked={entry.case_sensitive}
+				onChange={(event) =>
+					onSave({ ...entry, case_sensitive: event.currentTarget.checked })
+				}
+				title="Match case"
+				disabled={disabled}
+				color="gray"
+			/>
+			<ActionIcon
+				variant="subtle"
+				color="red"
+				onClick={() => onRemove(entry.id)}
+				title="Remove"
+				disabled={disabled}
+			>
+				<Trash2 size={14} />
+			</ActionIcon>
+		</div>
+	);
+}
+
+export function DictionarySettings() {
+	const queryClient = useQueryClient();
+	const { data: settings, isLoading } = useSettings();
+	const { data: entries } = useDictionaryEntries();
+	const { data: dictionarySuggestions } = useDictionarySuggestions();
+	const addEntry = useAddDictionaryEntry();
+	const updateEntry = useUpdateDictionaryEntry();
+	const removeEntry = useRemoveDictionaryEntry();
+	const updateCleanupPromptSections = useUpdateCleanupPromptSections();
+	const [newSpoken, setNewSpoken] = useState("");
+	const [newWritten, setNewWritten] = useState("");
+	const [newCaseSensitive, setNewCaseSensitive] = useState(false);
+
+	// Corrections in the overlay add entries from another window
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onDictionaryChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["dictionaryEntries"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	const sections =
+		settings?.cleanup_prompt_sections ?? DEFAULT_CLEANUP_PROMPT_SECTIONS;
+	const hasEntries = (entries?.length ?? 0) > 0;
+	const error = addEntry.error || updateEntry.error || removeEntry.error;
+
+	const handleToggle = (enabled: boolean) => {
+		updateCleanupPromptSections.mutate(
+			{ ...sections, dictionary: { ...sections.dictionary, enabled } },
+			{
+				onSuccess: () => {
+					tauriAPI.emitSettingsChanged();
+				},
+			},
+		);
+	};
+
+	const handleAdd = () => {
+		if (!newSpoken.trim()) return;
+		addEntry.mutate(
+			{
+				spoken: newSpoken,
+				written: newWritten,
+				caseSensitive: newCaseSensitive,
+			},
+			{
+				onSuccess: () => {
+					setNewSpoken("");
+					setNewWritten("");
+					setNewCaseSensitive(false);
+				},
+			},
+		);
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Personal Dictionary</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Use when formatting</p>
+						<p className="settings-description">
+							Tell the LLM how to write the words below
+						</p>
+					</div>
+					<Switch
+						checked={sections.dictionary.enabled}
+						onChange={(event) => handleToggle(event.currentTarget.checked)}
+						disabled={
+							isLoading ||
+							!hasEntries ||
+							updateCleanupPromptSections.isPending
+						}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div
+					className="settings-row"
+					style={{
+						marginTop: 16,
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					<div>
+						<p className="settings-label">Entries</p>
+						<p className="settings-description">
+							What you say, and how to write it. Leave the written form empty
+							for a term to recognize. Tick to match case
+						</p>
+					</div>
+					{entries?.map((entry) => (
+						<DictionaryEntryRow
+							key={entry.id}
+							entry={entry}
+							onSave={(updated) => updateEntry.mutate(updated)}
+							onRemove={(id) => removeEntry.mutate(id)}
+							disabled={updateEntry.isPending || removeEntry.isPending}
+						/>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newSpoken}
+							onChange={(event) => setNewSpoken(event.currentTarget.value)}
+							onKeyDown={(event) => {
+								if (event.key === "Enter") handleAdd();
+							}}
+							placeholder="Spoken, e.g. ant row pick"
+							style={{ flex: 1 }}
+						/>
+						<span className="settings-description">→</span>
+						<TextInput
+							value={newWritten}
+							onChange={(event) => setNewWritten(event.currentTarget.value)}
+							onKeyDown={(event) => {
+								if (event.key === "Enter") handleAdd();
+							}}
+							placeholder="Written, e.g. Anthropic"
+							style={{ flex: 1 }}
+						/>
+						<Checkbox
+							checked={newCaseSensitive}
+							onChange={(event) =>
+								setNewCaseSensitive(event.currentTarget.checked)
+							}
+							title="Match case"
+							color="gray"
+						/>
+						<Button
+							onClick={handleAdd}
+							disabled={!newSpoken.trim() || addEntry.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+					{error && (
+						<Text size="xs" c="red">
+							{String(error)}
+						</Text>
+					)}
+				</div>
+				<div style={{ marginTop: 16 }}>
+					<DictionaryImportRow />
+				</div>
+				{dictionarySuggestions && dictionarySuggestions.length > 0 && (
+					<div style={{ marginTop: 16 }}>
+						<p className="settings-label">Suggested entries</p>
+						<p className="settings-description" style={{ marginBottom: 8 }}>
+							Words you corrected after dictating
+						</p>
+						<DictionarySuggestionList suggestions={dictionarySuggestions} />
+					</div>
+				)}
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,64 @@
+import { Switch } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+import { useFeatureFlags, useSetFeatureFlag } from "../../lib/queries";
+import { describeError, type FeatureFlag } from "../../lib/tauri";
+
+const FEATURE_FLAG_LABELS: Record<FeatureFlag, string> = {
+	streaming_insertion: "Streaming insertion",
+	wake_word: "Wake word",
+	always_listening: "Always listening",
+};
+
+export function ExperimentalSettings() {
+	const { data: flags, isLoading } = useFeatureFlags();
+	const setFeatureFlag = useSetFeatureFlag();
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Experimental</h3>
+			<div className="settings-card">
+				<p className="settings-description" style={{ marginBottom: 8 }}>
+					Features still in development. They may be unreliable and can change
+					or disappear in future versions.
+				</p>
+				{(flags ?? []).map((state) => (
+					<div
+						key={state.flag}
+						className="settings-row"
+						style={{ marginTop: 16 }}
+					>
+						<div>
+							<p className="settings-label">
+								{FEATURE_FLAG_LABELS[state.flag] ?? state.flag}
+							</p>
+							<p className="settings-description">{state.description}</p>
+						</div>
+						<Switch
+							checked={state.enabled}
+							onChange={(event) =>
+								setFeatureFlag.mutate(
+									{
+										flag: state.flag,
+										enabled: event.currentTarget.checked,
+									},
+									{
+										onError: (error) => {
+											notifications.show({
+												title: "Couldn't save setting",
+												message: describeError(error),
+												color: "red",
+											});
+										},
+									},
+								)
+							}
+							disabled={isLoading || setFeatureFlag.isPending}
+							color="gray"
+							size="md"
+						/>
+					</div>
+				))}
+			</div>
+		</div>
+	);
+}
@@ -1,29 +1,243 @@
-import { Alert, Button, Text } from "@mantine/core";
-import { AlertCircle, RotateCcw } from "lucide-react";
-import { useState } from "react";
 import {
+	ActionIcon,
+	Alert,
+	Button,
+	Select,
+	Slider,
+	Switch,
+	Text,
+} from "@mantine/core";
+import { AlertCircle, RotateCcw, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	DEFAULT_CLIPBOARD_ONLY_HOTKEY,
+	DEFAULT_CYCLE_HISTORY_HOTKEY,
+	DEFAULT_CYCLE_LANGUAGE_HOTKEY,
+	DEFAULT_CYCLE_PROMPT_PRESET_HOTKEY,
+	DEFAULT_CYCLE_STT_PROVIDER_HOTKEY,
+	DEFAULT_DOUBLE_TAP_INTERVAL_MS,
+	DEFAULT_DO_NOT_DISTURB_HOTKEY,
 	DEFAULT_HOLD_HOTKEY,
+	DEFAULT_OPEN_PALETTE_HOTKEY,
 	DEFAULT_PASTE_LAST_HOTKEY,
+	DEFAULT_PAUSE_RESUME_HOTKEY,
+	DEFAULT_RETRY_LAST_HOTKEY,
+	DEFAULT_TOGGLE_FORMATTING_HOTKEY,
 	DEFAULT_TOGGLE_HOTKEY,
 } from "../../lib/hotkeyDefaults";
 import {
+	useDetectHidButton,
+	useHidDevices,
 	useResetHotkeysToDefaults,
 	useSettings,
+	useUpdateClipboardOnlyHotkey,
+	useUpdateCycleHistoryHotkey,
+	useUpdateCycleLanguageHotkey,
+	useUpdateCyclePromptPresetHotkey,
+	useUpdateCycleSttProviderHotkey,
+	useUpdateDoNotDisturbHotkey,
+	useUpdateHidTriggers,
 	useUpdateHoldHotkey,
+	useUpdateHoldLatchEnabled,
+	useUpdateHoldLatchMaxSeconds,
+	useUpdateOpenPaletteHotkey,
 	useUpdatePasteLastHotkey,
+	useUpdatePauseResumeHotkey,
+	useUpdateRetryLastHotkey,
+	useUpdateSequenceTimeout,
+	useUpdateToggleFormattingHotkey,
 	useUpdateToggleHotkey,
 } from "../../lib/queries";
-import type { HotkeyConfig } from "../../lib/tauri";
+import {
+	DEFAULT_HOLD_LATCH_MAX_SECONDS,
+	DEFAULT_SEQUENCE_TIMEOUT_MS,
+	describeError,
+	type HidAction,
+	type HidDevice,
+	type HotkeyConfig,
+} from "../../lib/tauri";
 import { HotkeyInput } from "../HotkeyInput";
 
-type RecordingInput = "toggle" | "hold" | "paste_last" | null;
+const HID_ACTIONS = [
+	{ value: "hold", label: "Hold to record" },
+	{ value: "toggle", label: "Toggle recording" },
+];
+
+/** Key identifying a HID device interface in the device picker */
+function hidDeviceKey(device: Omit<HidDevice, "name">): string {
+	return [
+		device.vendor_id,
+		device.product_id,
+		device.usage_page,
+		device.usage,
+	].join(":");
+}
+
+/** Foot pedals and other HID devices bound to recording */
+function HidTriggerSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const { data: devices, refetch, isFetching } = useHidDevices();
+	const detectButton = useDetectHidButton();
+	const updateHidTriggers = useUpdateHidTriggers();
+	const [deviceKey, setDeviceKey] = useState<string | null>(null);
+	const [action, setAction] = useState<HidAction>("hold");
+
+	const bindings = settings?.hid_triggers ?? [];
+	const device = devices?.find((d) => hidDeviceKey(d) === deviceKey);
+	const disabled = isLoading || updateHidTriggers.isPending;
+
+	// The button is whichever one is pressed next on the device
+	const handleAdd = () => {
+		if (!device) return;
+		detectButton.mutate(device, {
+			onSuccess: (button) => {
+				updateHidTriggers.mutate([
+					...bindings.filter(
+						(binding) =>
+							hidDeviceKey(binding) !== deviceKey || binding.button !== button,
+					),
+					{ ...device, button, action },
+				]);
+			},
+		});
+	};
+
+	return (
+		<div style={{ marginTop: 20 }}>
+			<p className="settings-label">Foot Pedals</p>
+			<p className="settings-description">
+				Record with a foot pedal or another USB device. Pick the device, then
+				press the pedal to bind
+			</p>
+			{bindings.map((binding, index) => (
+				<div
+					key={`${hidDeviceKey(binding)}:${binding.button}`}
+					style={{
+						display: "flex",
+						gap: 8,
+						alignItems: "center",
+						marginTop: 8,
+					}}
+				>
+					<Text size="sm" style={{ flex: 1 }}>
+						{binding.name || hidDeviceKey(binding)}, button {binding.button + 1}
+					</Text>
+					<Select
+						data={HID_ACTIONS}
+						value={binding.action}
+						onChange={(value) => {
+							if (!value) return;
+							updateHidTriggers.mutate(
+								bindings.map((existing, i) =>
+									i === index
+										? { ...existing, action: value as HidAction }
+										: existing,
+								),
+							);
+						}}
+						allowDeselect={false}
+						disabled={disabled}
+						aria-label="Action"
+						w={170}
+					/>
+					<ActionIcon
+						variant="subtle"
+						color="red"
+						onClick={() =>
+							updateHidTriggers.mutate(bindings.filter((_, i) => i !== index))
+						}
+						title="Remove"
+						disabled={disabled}
+					>
+						<Trash2 size={14} />
+					</ActionIcon>
+				</div>
+			))}
+			<div
+				style={{ display: "flex", gap: 8, alignItems: "center", marginTop: 8 }}
+			>
+				<Select
+					placeholder={devices?.length ? "Device" : "No devices found"}
+					data={(devices ?? []).map((d) => ({
+						value: hidDeviceKey(d),
+						label: d.name,
+					}))}
+					value={deviceKey}
+					onChange={setDeviceKey}
+					onDropdownOpen={() => refetch()}
+					disabled={disabled || isFetching}
+					aria-label="Device"
+					style={{ flex: 1 }}
+				/>
+				<Select
+					data={HID_ACTIONS}
+					value={action}
+					onChange={(value) => value && setAction(value as HidAction)}
+					allowDeselect={false}
+					disabled={disabled}
+					aria-label="Action"
+					w={170}
+				/>
+				<Button
+					onClick={handleAdd}
+					loading={detectButton.isPending}
+					disabled={disabled || !device}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Bind
+				</Button>
+			</div>
+			{detectButton.isPending && (
+				<Text size="xs" c="dimmed" mt={4}>
+					Press the pedal or button to bind...
+				</Text>
+			)}
+			{detectButton.error && (
+				<Text size="xs" c="red" mt={4}>
+					{describeError(detectButton.error)}
+				</Text>
+			)}
+		</div>
+	);
+}
+
+type RecordingInput =
+	| "toggle"
+	| "hold"
+	| "paste_last"
+	| "cycle_history"
+	| "cycle_language"
+	| "pause_resume"
+	| "retry_last"
+	| "clipboard_only"
+	| "cycle_stt_provider"
+	| "toggle_formatting"
+	| "cycle_prompt_preset"
+	| "open_palette"
+	| "do_not_disturb"
+	| null;
 
 export function HotkeySettings() {
 	const { data: settings, isLoading } = useSettings();
 	const updateToggleHotkey = useUpdateToggleHotkey();
 	const updateHoldHotkey = useUpdateHoldHotkey();
 	const updatePasteLastHotkey = useUpdatePasteLastHotkey();
+	const updateCycleHistoryHotkey = useUpdateCycleHistoryHotkey();
+	const updateCycleLanguageHotkey = useUpdateCycleLanguageHotkey();
+	const updatePauseResumeHotkey = useUpdatePauseResumeHotkey();
+	const updateRetryLastHotkey = useUpdateRetryLastHotkey();
+	const updateClipboardOnlyHotkey = useUpdateClipboardOnlyHotkey();
+	const updateCycleSttProviderHotkey = useUpdateCycleSttProviderHotkey();
+	const updateToggleFormattingHotkey = useUpdateToggleFormattingHotkey();
+	const updateCyclePromptPresetHotkey = useUpdateCyclePromptPresetHotkey();
+	const updateOpenPaletteHotkey = useUpdateOpenPaletteHotkey();
+	const updateDoNotDisturbHotkey = useUpdateDoNotDisturbHotkey();
 	const resetHotkeys = useResetHotkeysToDefaults();
+	const updateSequenceTimeout = useUpdateSequenceTimeout();
+	const updateHoldLatchEnabled = useUpdateHoldLatchEnabled();
+	const updateHoldLatchMaxSeconds = useUpdateHoldLatchMaxSeconds();
 
 	// Track which input is currently recording (only one at a time)
 	const [recordingInput, setRecordingInput] = useState<RecordingInput>(null);
@@ -33,12 +247,96 @@ export function HotkeySettings() {
 		updateToggleHotkey.error ||
 		updateHoldHotkey.error ||
 		updatePasteLastHotkey.error ||
+		updateCycleHistoryHotkey.error ||
+		updateCycleLanguageHotkey.error ||
+		updatePauseResumeHotkey.error ||
+		updateRetryLastHotkey.error ||
+		updateClipboardOnlyHotkey.error ||
+		updateCycleSttProviderHotkey.error ||
+		updateToggleFormattingHotkey.error ||
+		updateCyclePromptPresetHotkey.error ||
+		updateOpenPaletteHotkey.error ||
+		updateDoNotDisturbHotkey.error ||
+		updateSequenceTimeout.error ||
+		updateHoldLatchEnabled.error ||
+		updateHoldLatchMaxSeconds.error ||
 		resetHotkeys.error;
 
 	const handleToggleHotkeyChange = (config: HotkeyConfig) => {
 		updateToggleHotkey.mutate(config);
 	};
 
+	const toggleHotkey = settings?.toggle_hotkey ?? DEFAULT_TOGGLE_HOTKEY;
+	const currentTapInterval =
+		toggleHotkey.tap_interval_ms ?? DEFAULT_DOUBLE_TAP_INTERVAL_MS;
+
+	// Local state for smooth slider dragging
+	const [tapIntervalValue, setTapIntervalValue] = useState(currentTapInterval);
+
+	// Sync local state when the stored value changes
+	useEffect(() => {
+		setTapIntervalValue(currentTapInterval);
+	}, [currentTapInterval]);
+
+	const handleTapIntervalChange = (value: number) => {
+		updateToggleHotkey.mutate({ ...toggleHotkey, tap_interval_ms: value });
+	};
+
+	const holdHotkey = settings?.hold_hotkey ?? DEFAULT_HOLD_HOTKEY;
+	const pasteLastHotkey =
+		settings?.paste_last_hotkey ?? DEFAULT_PASTE_LAST_HOTKEY;
+	const cycleHistoryHotkey = settings?.cycle_history_hotkey ?? null;
+	const cycleLanguageHotkey = settings?.cycle_language_hotkey ?? null;
+	const pauseResumeHotkey = settings?.pause_resume_hotkey ?? null;
+	const retryLastHotkey = settings?.retry_last_hotkey ?? null;
+	const clipboardOnlyHotkey = settings?.clipboard_only_hotkey ?? null;
+	const cycleSttProviderHotkey = settings?.cycle_stt_provider_hotkey ?? null;
+	const toggleFormattingHotkey = settings?.toggle_formatting_hotkey ?? null;
+	const cyclePromptPresetHotkey = settings?.cycle_prompt_preset_hotkey ?? null;
+	const openPaletteHotkey = settings?.open_palette_hotkey ?? null;
+	const doNotDisturbHotkey = settings?.do_not_disturb_hotkey ?? null;
+	const hasSequence = [
+		toggleHotkey,
+		holdHotkey,
+		pasteLastHotkey,
+		cycleHistoryHotkey,
+		cycleLanguageHotkey,
+		pauseResumeHotkey,
+		retryLastHotkey,
+		clipboardOnlyHotkey,
+		cycleSttProviderHotkey,
+		toggleFormattingHotkey,
+		cyclePromptPresetHotkey,
+		openPaletteHotkey,
+		doNotDisturbHotkey,
+	].some((hotkey) => hotkey?.then);
+	const currentSequenceTimeout =
+		settings?.sequence_timeout_ms ?? DEFAULT_SEQUENCE_TIMEOUT_MS;
+
+	const [sequenceTimeoutValue, setSequenceTimeoutValue] = useState(
+		currentSequenceTimeout,
+	);
+
+	useEffect(() => {
+		setSequenceTimeoutValue(currentSequenceTimeout);
+	}, [currentSequenceTimeout]);
+
+	const handleSequenceTimeoutChange = (value: number) => {
+		updateSequenceTimeout.mutate(value);
+	};
+
+	const holdLatchEnabled = settings?.hold_latch_enabled ?? false;
+	const currentHoldLatchMaxSeconds =
+		settings?.hold_latch_max_seconds ?? DEFAULT_HOLD_LATCH_MAX_SECONDS;
+
+	const [holdLatchMaxSecondsValue, setHoldLatchMaxSecondsValue] = useState(
+		currentHoldLatchMaxSeconds,
+	);
+
+	useEffect(() => {
+		setHoldLatchMaxSecondsValue(currentHoldLatchMaxSeconds);
+	}, [currentHoldLatchMaxSeconds]);
+
 	const handleHoldHotkeyChange = (config: HotkeyConfig) => {
 		updateHoldHotkey.mutate(config);
 	};
@@ -47,6 +345,102 @@ export function HotkeySettings() {
 		updatePasteLastHotkey.mutate(config);
 	};
 
+	const handleCycleHistoryToggle = (enabled: boolean) => {
+		updateCycleHistoryHotkey.mutate(
+			enabled ? DEFAULT_CYCLE_HISTORY_HOTKEY : null,
+		);
+	};
+
+	const handleCycleHistoryHotkeyChange = (config: HotkeyConfig) => {
+		updateCycleHistoryHotkey.mutate(config);
+	};
+
+	const handleCycleLanguageToggle = (enabled: boolean) => {
+		updateCycleLanguageHotkey.mutate(
+			enabled ? DEFAULT_CYCLE_LANGUAGE_HOTKEY : null,
+		);
+	};
+
+	const handleCycleLanguageHotkeyChange = (config: HotkeyConfig) => {
+		updateCycleLanguageHotkey.mutate(config);
+	};
+
+	const handlePauseResumeToggle = (enabled: boolean) => {
+		updatePauseResumeHotkey.mutate(
+			enabled ? DEFAULT_PAUSE_RESUME_HOTKEY : null,
+		);
+	};
+
+	const handlePauseResumeHotkeyChange = (config: HotkeyConfig) => {
+		updatePauseResumeHotkey.mutate(config);
+	};
+
+	const handleRetryLastToggle = (enabled: boolean) => {
+		updateRetryLastHotkey.mutate(enabled ? DEFAULT_RETRY_LAST_HOTKEY : null);
+	};
+
+	const handleRetryLastHotkeyChange = (config: HotkeyConfig) => {
+		updateRetryLastHotkey.mutate(config);
+	};
+
+	const handleClipboardOnlyToggle = (enabled: boolean) => {
+		updateClipboardOnlyHotkey.mutate(
+			enabled ? DEFAULT_CLIPBOARD_ONLY_HOTKEY : null,
+		);
+	};
+
+	const handleClipboardOnlyHotkeyChange = (config: HotkeyConfig) => {
+		updateClipboardOnlyHotkey.mutate(config);
+	};
+
+	const handleCycleSttProviderToggle = (enabled: boolean) => {
+		updateCycleSttProviderHotkey.mutate(
+			enabled ? DEFAULT_CYCLE_STT_PROVIDER_HOTKEY : null,
+		);
+	};
+
+	const handleCycleSttProviderHotkeyChange = (config: HotkeyConfig) => {
+		updateCycleSttProviderHotkey.mutate(config);
+	};
+
+	const handleToggleFormattingToggle = (enabled: boolean) => {
+		updateToggleFormattingHotkey.mutate(
+			enabled ? DEFAULT_TOGGLE_FORMATTING_HOTKEY : null,
+		);
+	};
+
+	const handleToggleFormattingHotkeyChange = (config: HotkeyConfig) => {
+		updateToggleFormattingHotkey.mutate(config);
+	};
+
+	const handleCyclePromptPresetToggle = (enabled: boolean) => {
+		updateCyclePromptPresetHotkey.mutate(
+			enabled ? DEFAULT_CYCLE_PROMPT_PRESET_HOTKEY : null,
+		);
+	};
+
+	const handleCyclePromptPresetHotkeyChange = (config: HotkeyConfig) => {
+		updateCyclePromptPresetHotkey.mutate(config);
+	};
+
+	const handleOpenPaletteToggle = (enabled: boolean) => {
+		updateOpenPaletteHotkey.mutate(enabled ? DEFAULT_OPEN_PALETTE_HOTKEY : null);
+	};
+
+	const handleOpenPaletteHotkeyChange = (config: HotkeyConfig) => {
+		updateOpenPaletteHotkey.mutate(config);
+	};
+
+	const handleDoNotDisturbToggle = (enabled: boolean) => {
+		updateDoNotDisturbHotkey.mutate(
+			enabled ? DEFAULT_DO_NOT_DISTURB_HOTKEY : null,
+		);
+	};
+
+	const handleDoNotDisturbHotkeyChange = (config: HotkeyConfig) => {
+		updateDoNotDisturbHotkey.mutate(config);
+	};
+
 	return (
 		<div className="settings-section animate-in animate-in-delay-3">
 			<h3 className="settings-section-title">Hotkeys</h3>
@@ -57,27 +451,72 @@ export function HotkeySettings() {
 					mb="md"
 					title="Error"
 				>
-					{error instanceof Error ? error.message : String(error)}
+					{describeError(error)}
 				</Alert>
 			)}
 			<div className="settings-card">
 				<HotkeyInput
 					label="Toggle Recording"
-					description="Press once to start recording, press again to stop"
-					value={settings?.toggle_hotkey ?? DEFAULT_TOGGLE_HOTKEY}
+					description="Press once to start recording, press again to stop. Double-tap a modifier to use it alone, or press a media key or headset button"
+					value={toggleHotkey}
 					onChange={handleToggleHotkeyChange}
+					allowDoubleTap
+					allowMediaKey
+					allowSequence
 					disabled={isLoading || updateToggleHotkey.isPending}
 					isRecording={recordingInput === "toggle"}
 					onStartRecording={() => setRecordingInput("toggle")}
 					onStopRecording={() => setRecordingInput(null)}
 				/>
 
+				{toggleHotkey.trigger === "double_tap" && (
+					<div style={{ marginTop: 12 }}>
+						<p className="settings-label">Double-Tap Speed</p>
+						<p className="settings-description">
+							Maximum time between the two taps
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={tapIntervalValue}
+								onChange={setTapIntervalValue}
+								onChangeEnd={handleTapIntervalChange}
+								min={150}
+								max={600}
+								step={25}
+								marks={[
+									{ value: 150, label: "150ms" },
+									{ value: 600, label: "600ms" },
+								]}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+								{tapIntervalValue}ms
+							</Text>
+						</div>
+					</div>
+				)}
+
 				<div style={{ marginTop: 20 }}>
 					<HotkeyInput
 						label="Hold to Record"
-						description="Hold to record, release to stop"
-						value={settings?.hold_hotkey ?? DEFAULT_HOLD_HOTKEY}
+						description="Hold a key or mouse button to record, release to stop"
+						value={holdHotkey}
 						onChange={handleHoldHotkeyChange}
+						allowMouse
+						allowSequence
 						disabled={isLoading || updateHoldHotkey.isPending}
 						isRecording={recordingInput === "hold"}
 						onStartRecording={() => setRecordingInput("hold")}
@@ -85,12 +524,72 @@ export function HotkeySettings() {
 					/>
 				</div>
 
+				<div className="settings-row" style={{ marginTop: 12 }}>
+					<div>
+						<p className="settings-label">Tap to Latch</p>
+						<p className="settings-description">
+							A quick tap of the hold hotkey keeps recording until you tap it
+							again
+						</p>
+					</div>
+					<Switch
+						checked={holdLatchEnabled}
+						onChange={(event) =>
+							updateHoldLatchEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateHoldLatchEnabled.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{holdLatchEnabled && (
+					<div style={{ marginTop: 12 }}>
+						<p className="settings-label">Latch Time Limit</p>
+						<p className="settings-description">
+							Stop a latched recording automatically after this long
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={holdLatchMaxSecondsValue}
+								onChange={setHoldLatchMaxSecondsValue}
+								onChangeEnd={(value) => updateHoldLatchMaxSeconds.mutate(value)}
+								min={5}
+								max={300}
+								step={5}
+								marks={[
+									{ value: 5, label: "5s" },
+									{ value: 300, label: "5m" },
+								]}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+								{holdLatchMaxSecondsValue}s
+							</Text>
+						</div>
+					</div>
+				)}
+
 				<div style={{ marginTop: 20 }}>
 					<HotkeyInput
 						label="Paste Last Transcription"
 						description="Paste the most recent transcription"
-						value={settings?.paste_last_hotkey ?? DEFAULT_PASTE_LAST_HOTKEY}
+						value={pasteLastHotkey}
 						onChange={handlePasteLastHotkeyChange}
+						allowSequence
 						disabled={isLoading || updatePasteLastHotkey.isPending}
 						isRecording={recordingInput === "paste_last"}
 						onStartRecording={() => setRecordingInput("paste_last")}
@@ -98,6 +597,395 @@ export function HotkeySettings() {
 					/>
 				</div>
 
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Cycle Through History</p>
+						<p className="settings-description">
+							Paste the latest transcription, then press again to replace it
+							with the one before
+						</p>
+					</div>
+					<Switch
+						checked={cycleHistoryHotkey !== null}
+						onChange={(event) =>
+							handleCycleHistoryToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateCycleHistoryHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{cycleHistoryHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Cycle History Hotkey"
+							description="Each press within a few seconds steps one transcription further back"
+							value={cycleHistoryHotkey}
+							onChange={handleCycleHistoryHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateCycleHistoryHotkey.isPending}
+							isRecording={recordingInput === "cycle_history"}
+							onStartRecording={() => setRecordingInput("cycle_history")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Switch Language</p>
+						<p className="settings-description">
+							Step through your quick switch languages with a hotkey
+						</p>
+					</div>
+					<Switch
+						checked={cycleLanguageHotkey !== null}
+						onChange={(event) =>
+							handleCycleLanguageToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateCycleLanguageHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{cycleLanguageHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Switch Language Hotkey"
+							description="Each press switches to the next language in the Language settings"
+							value={cycleLanguageHotkey}
+							onChange={handleCycleLanguageHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateCycleLanguageHotkey.isPending}
+							isRecording={recordingInput === "cycle_language"}
+							onStartRecording={() => setRecordingInput("cycle_language")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Pause and Resume</p>
+						<p className="settings-description">
+							Pause a recording to gather your thoughts, then carry on in the
+							same dictation
+						</p>
+					</div>
+					<Switch
+						checked={pauseResumeHotkey !== null}
+						onChange={(event) =>
+							handlePauseResumeToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updatePauseResumeHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{pauseResumeHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Pause and Resume Hotkey"
+							description="Audio isn't captured while paused; everything is transcribed together when you stop"
+							value={pauseResumeHotkey}
+							onChange={handlePauseResumeHotkeyChange}
+							allowSequence
+							disabled={isLoading || updatePauseResumeHotkey.isPending}
+							isRecording={recordingInput === "pause_resume"}
+							onStartRecording={() => setRecordingInput("pause_resume")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Retry Last Transcription</p>
+						<p className="settings-description">
+							Transcribe the last recording again instead of repeating it
+						</p>
+					</div>
+					<Switch
+						checked={retryLastHotkey !== null}
+						onChange={(event) =>
+							handleRetryLastToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateRetryLastHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{retryLastHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Retry Last Hotkey"
+							description="Inserts the new transcription where the cursor is; needs the last recording kept in Recordings"
+							value={retryLastHotkey}
+							onChange={handleRetryLastHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateRetryLastHotkey.isPending}
+							isRecording={recordingInput === "retry_last"}
+							onStartRecording={() => setRecordingInput("retry_last")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Clipboard Only</p>
+						<p className="settings-description">
+							Copy a dictation instead of inserting it, when the wrong window
+							is focused
+						</p>
+					</div>
+					<Switch
+						checked={clipboardOnlyHotkey !== null}
+						onChange={(event) =>
+							handleClipboardOnlyToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateClipboardOnlyHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{clipboardOnlyHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Clipboard Only Hotkey"
+							description="Press while recording, or before you start, to send that dictation to the clipboard only; press again to undo"
+							value={clipboardOnlyHotkey}
+							onChange={handleClipboardOnlyHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateClipboardOnlyHotkey.isPending}
+							isRecording={recordingInput === "clipboard_only"}
+							onStartRecording={() => setRecordingInput("clipboard_only")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Switch STT Provider</p>
+						<p className="settings-description">
+							Move to the next speech-to-text provider the server offers,
+							without opening settings
+						</p>
+					</div>
+					<Switch
+						checked={cycleSttProviderHotkey !== null}
+						onChange={(event) =>
+							handleCycleSttProviderToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateCycleSttProviderHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{cycleSttProviderHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Switch STT Provider Hotkey"
+							description="Each press selects the next provider; the overlay shows which one"
+							value={cycleSttProviderHotkey}
+							onChange={handleCycleSttProviderHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateCycleSttProviderHotkey.isPending}
+							isRecording={recordingInput === "cycle_stt_provider"}
+							onStartRecording={() => setRecordingInput("cycle_stt_provider")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Toggle Formatting</p>
+						<p className="settings-description">
+							Turn LLM cleanup on or off, inserting raw transcriptions while
+							it's off
+						</p>
+					</div>
+					<Switch
+						checked={toggleFormattingHotkey !== null}
+						onChange={(event) =>
+							handleToggleFormattingToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateToggleFormattingHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{toggleFormattingHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Toggle Formatting Hotkey"
+							description="Each press turns formatting on or off; the overlay says which"
+							value={toggleFormattingHotkey}
+							onChange={handleToggleFormattingHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateToggleFormattingHotkey.isPending}
+							isRecording={recordingInput === "toggle_formatting"}
+							onStartRecording={() => setRecordingInput("toggle_formatting")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Switch Prompt Preset</p>
+						<p className="settings-description">
+							Step through your saved formatting prompt presets
+						</p>
+					</div>
+					<Switch
+						checked={cyclePromptPresetHotkey !== null}
+						onChange={(event) =>
+							handleCyclePromptPresetToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateCyclePromptPresetHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{cyclePromptPresetHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Switch Prompt Preset Hotkey"
+							description="Each press applies the next saved preset"
+							value={cyclePromptPresetHotkey}
+							onChange={handleCyclePromptPresetHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateCyclePromptPresetHotkey.isPending}
+							isRecording={recordingInput === "cycle_prompt_preset"}
+							onStartRecording={() => setRecordingInput("cycle_prompt_preset")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Quick Actions</p>
+						<p className="settings-description">
+							Open a palette to paste a recent dictation, switch provider or
+							prompt preset, or turn formatting on or off
+						</p>
+					</div>
+					<Switch
+						checked={openPaletteHotkey !== null}
+						onChange={(event) =>
+							handleOpenPaletteToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateOpenPaletteHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{openPaletteHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Quick Actions Hotkey"
+							description="Press again or Escape to close the palette"
+							value={openPaletteHotkey}
+							onChange={handleOpenPaletteHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateOpenPaletteHotkey.isPending}
+							isRecording={recordingInput === "open_palette"}
+							onStartRecording={() => setRecordingInput("open_palette")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				<div className="settings-row" style={{ marginTop: 20 }}>
+					<div>
+						<p className="settings-label">Do Not Disturb</p>
+						<p className="settings-description">
+							Pause dictation so no hotkey, mouse button or pedal can start a
+							recording, e.g. while sharing your screen. Also in the tray menu
+						</p>
+					</div>
+					<Switch
+						checked={doNotDisturbHotkey !== null}
+						onChange={(event) =>
+							handleDoNotDisturbToggle(event.currentTarget.checked)
+						}
+						disabled={isLoading || updateDoNotDisturbHotkey.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+
+				{doNotDisturbHotkey && (
+					<div style={{ marginTop: 12 }}>
+						<HotkeyInput
+							label="Do Not Disturb Hotkey"
+							description="Press to pause dictation, and again to resume"
+							value={doNotDisturbHotkey}
+							onChange={handleDoNotDisturbHotkeyChange}
+							allowSequence
+							disabled={isLoading || updateDoNotDisturbHotkey.isPending}
+							isRecording={recordingInput === "do_not_disturb"}
+							onStartRecording={() => setRecordingInput("do_not_disturb")}
+							onStopRecording={() => setRecordingInput(null)}
+						/>
+					</div>
+				)}
+
+				{hasSequence && (
+					<div style={{ marginTop: 20 }}>
+						<p className="settings-label">Sequence Timeout</p>
+						<p className="settings-description">
+							Time allowed to press the second key of a sequence
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={sequenceTimeoutValue}
+								onChange={setSequenceTimeoutValue}
+								onChangeEnd={handleSequenceTimeoutChange}
+								min={300}
+								max={3000}
+								step={100}
+								marks={[
+									{ value: 300, label: "0.3s" },
+									{ value: 3000, label: "3.0s" },
+								]}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+								{(sequenceTimeoutValue / 1000).toFixed(1)}s
+							</Text>
+						</div>
+					</div>
+				)}
+
+				<HidTriggerSettings />
+
 				<div
 					style={{
 						marginTop: 24,
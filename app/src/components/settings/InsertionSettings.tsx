@@ -0,0 +1,354 @@
+import {
+	ActionIcon,
+	Alert,
+	Button,
+	NumberInput,
+	Select,
+	Slider,
+	Switch,
+	Text,
+	TextInput,
+} from "@mantine/core";
+import { AlertCircle, Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useInsertionDiagnostics,
+	useIsAccessibilityInsertionSupported,
+	useSettings,
+	useUpdateClipboardRestoreDelay,
+	useUpdateCopyAfterInsert,
+	useUpdateInsertionMethod,
+	useUpdateInsertionRules,
+	useUpdateMaxInsertionChars,
+	useUpdateOutputMode,
+} from "../../lib/queries";
+import {
+	DEFAULT_CLIPBOARD_RESTORE_DELAY_MS,
+	DEFAULT_MAX_INSERTION_CHARS,
+	type InsertionMethod,
+	type InsertionRule,
+	type OutputMode,
+} from "../../lib/tauri";
+
+const INSERTION_METHOD_LABELS: Record<InsertionMethod, string> = {
+	paste: "Paste from clipboard",
+	type: "Simulate typing",
+	accessibility: "Accessibility API",
+	clipboard_only: "Copy to clipboard only",
+};
+
+const OUTPUT_MODE_LABELS: Record<OutputMode, string> = {
+	insert: "Insert into the app",
+	clipboard_only: "Copy to clipboard only",
+	both: "Insert and copy",
+};
+
+const OUTPUT_MODE_OPTIONS = Object.entries(OUTPUT_MODE_LABELS).map(
+	([value, label]) => ({ value, label }),
+);
+
+const selectStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+function isInsertionMethod(value: string | null): value is InsertionMethod {
+	return value !== null && value in INSERTION_METHOD_LABELS;
+}
+
+function isOutputMode(value: string | null): value is OutputMode {
+	return value !== null && value in OUTPUT_MODE_LABELS;
+}
+
+export function InsertionSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const { data: isAccessibilitySupported } =
+		useIsAccessibilityInsertionSupported();
+	const { data: diagnostics } = useInsertionDiagnostics();
+	const updateInsertionMethod = useUpdateInsertionMethod();
+	const updateInsertionRules = useUpdateInsertionRules();
+	const updateClipboardRestoreDelay = useUpdateClipboardRestoreDelay();
+	const updateCopyAfterInsert = useUpdateCopyAfterInsert();
+	const updateMaxInsertionChars = useUpdateMaxInsertionChars();
+	const updateOutputMode = useUpdateOutputMode();
+	const [newRuleApp, setNewRuleApp] = useState("");
+	const [newRuleMethod, setNewRuleMethod] = useState<InsertionMethod>("type");
+
+	const rules = settings?.insertion_rules ?? [];
+
+	const currentRestoreDelay =
+		settings?.clipboard_restore_delay_ms ?? DEFAULT_CLIPBOARD_RESTORE_DELAY_MS;
+	const [restoreDelayValue, setRestoreDelayValue] =
+		useState(currentRestoreDelay);
+
+	useEffect(() => {
+		setRestoreDelayValue(currentRestoreDelay);
+	}, [currentRestoreDelay]);
+
+	// Nothing is restored when typing or when the text is kept on the clipboard
+	const showRestoreDelay =
+		settings?.insertion_method !== "type" &&
+		!settings?.copy_after_insert &&
+		(settings?.output_mode ?? "insert") === "insert";
+
+	// Accessibility insertion falls back to pasting where it is unsupported
+	const methodOptions = Object.entries(INSERTION_METHOD_LABELS).map(
+		([value, label]) => ({
+			value,
+			label:
+				value === "accessibility" && isAccessibilitySupported === false
+					? `${label} (pastes on this platform)`
+					: label,
+		}),
+	);
+
+	const handleMethodChange = (value: string | null) => {
+		if (!isInsertionMethod(value)) return;
+		updateInsertionMethod.mutate(value);
+	};
+
+	const saveRules = (nextRules: InsertionRule[]) => {
+		updateInsertionRules.mutate(nextRules);
+	};
+
+	const handleAddRule = () => {
+		const app = newRuleApp.trim();
+		if (!app) return;
+		saveRules([...rules, { app, method: newRuleMethod }]);
+		setNewRuleApp("");
+	};
+
+	const handleRuleMethodChange = (index: number, value: string | null) => {
+		if (!isInsertionMethod(value)) return;
+		saveRules(
+			rules.map((rule, i) => (i === index ? { ...rule, method: value } : rule)),
+		);
+	};
+
+	const handleRemoveRule = (index: number) => {
+		saveRules(rules.filter((_, i) => i !== index));
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-3">
+			<h3 className="settings-section-title">Text Insertion</h3>
+			{diagnostics && diagnostics.warnings.length > 0 && (
+				<Alert
+					icon={<AlertCircle size={16} />}
+					color="yellow"
+					mb="md"
+					title="Insertion may not work"
+				>
+					{diagnostics.warnings.map((warning) => (
+						<p key={warning}>{warning}</p>
+					))}
+				</Alert>
+			)}
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Output</p>
+						<p className="settings-description">
+							Where dictations go. The clipboard-only hotkey switches this for
+							one dictation
+						</p>
+					</div>
+					<Select
+						data={OUTPUT_MODE_OPTIONS}
+						value={settings?.output_mode ?? "insert"}
+						onChange={(value) => {
+							if (isOutputMode(value)) updateOutputMode.mutate(value);
+						}}
+						disabled={isLoading}
+						allowDeselect={false}
+						styles={selectStyles}
+					/>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Insertion method</p>
+						<p className="settings-description">
+							Use typing for terminals, remote desktops and apps that block
+							paste
+						</p>
+						{diagnostics?.display_server === "wayland" && (
+							<p className="settings-description">
+								Keys are sent with {diagnostics.key_backend} on Wayland
+							</p>
+						)}
+					</div>
+					<Select
+						data={methodOptions}
+						value={settings?.insertion_method ?? "paste"}
+						onChange={handleMethodChange}
+						disabled={isLoading}
+						allowDeselect={false}
+						styles={selectStyles}
+					/>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Copy after inserting</p>
+						<p className="settings-description">
+							Also leave the text on the clipboard so you can paste it again if
+							it landed in the wrong place
+						</p>
+					</div>
+					<Switch
+						checked={settings?.copy_after_insert ?? false}
+						onChange={(event) =>
+							updateCopyAfterInsert.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{showRestoreDelay && (
+					<div style={{ marginTop: 16 }}>
+						<p className="settings-label">Clipboard restore delay</p>
+						<p className="settings-description">
+							How long to wait after pasting before putting back what you had
+							copied. Increase this if slow apps paste your old clipboard
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={restoreDelayValue}
+								onChange={setRestoreDelayValue}
+								onChangeEnd={(value) =>
+									updateClipboardRestoreDelay.mutate(value)
+								}
+								min={50}
+								max={2000}
+								step={50}
+								marks={[
+									{ value: 50, label: "50ms" },
+									{ value: 2000, label: "2s" },
+								]}
+								disabled={isLoading}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+								{restoreDelayValue}ms
+							</Text>
+						</div>
+					</div>
+				)}
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Longest insertion</p>
+						<p className="settings-description">
+							Longer dictations are copied to the clipboard instead of inserted,
+							so editors that cut off big pastes don't lose text
+						</p>
+					</div>
+					<NumberInput
+						value={settings?.max_insertion_chars ?? DEFAULT_MAX_INSERTION_CHARS}
+						onChange={(value) => {
+							if (typeof value === "number" && value > 0) {
+								updateMaxInsertionChars.mutate(value);
+							}
+						}}
+						min={1000}
+						step={1000}
+						allowDecimal={false}
+						suffix=" chars"
+						w={130}
+						disabled={isLoading}
+						styles={selectStyles}
+					/>
+				</div>
+				<div
+					className="settings-row"
+					style={{
+						marginTop: 16,
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					<div>
+						<p className="settings-label">Per-app methods</p>
+						<p className="settings-description">
+							Apps whose name or executable contains the text use their own
+							method
+						</p>
+					</div>
+					{rules.map((rule, index) => (
+						<div
+							key={`${rule.app}-${index}`}
+							style={{ display: "flex", gap: 8, alignItems: "center" }}
+						>
+							<span className="settings-label" style={{ flex: 1 }}>
+								{rule.app}
+							</span>
+							<Select
+								data={methodOptions}
+								value={rule.method}
+								onChange={(value) => handleRuleMethodChange(index, value)}
+								allowDeselect={false}
+								styles={selectStyles}
+							/>
+							<ActionIcon
+								variant="subtle"
+								color="red"
+								onClick={() => handleRemoveRule(index)}
+								title="Remove"
+								disabled={updateInsertionRules.isPending}
+							>
+								<Trash2 size={14} />
+							</ActionIcon>
+						</div>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newRuleApp}
+							onChange={(event) => setNewRuleApp(event.currentTarget.value)}
+							onKeyDown={(event) => {
+								if (event.key === "Enter") handleAddRule();
+							}}
+							placeholder="App name, e.g. Code"
+							disabled={isLoading}
+							style={{ flex: 1 }}
+						/>
+						<Select
+							data={methodOptions}
+							value={newRuleMethod}
+							onChange={(value) => {
+								if (isInsertionMethod(value)) setNewRuleMethod(value);
+							}}
+							allowDeselect={false}
+							styles={selectStyles}
+						/>
+						<Button
+							onClick={handleAddRule}
+							disabled={!newRuleApp.trim() || updateInsertionRules.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+				</div>
+			</div>
+		</div>
+	);
+}
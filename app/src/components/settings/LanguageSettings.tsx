@@ -0,0 +1,90 @@
+import { MultiSelect, Select, Switch } from "@mantine/core";
+import {
+	useSettings,
+	useUpdateAutoDetectLanguage,
+	useUpdateDictationLanguages,
+	useUpdateLanguage,
+} from "../../lib/queries";
+import { DICTATION_LANGUAGES } from "../../lib/tauri";
+
+const LANGUAGE_OPTIONS = DICTATION_LANGUAGES.map(({ code, name }) => ({
+	value: code,
+	label: name,
+}));
+
+const selectStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+export function LanguageSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateLanguage = useUpdateLanguage();
+	const updateAutoDetectLanguage = useUpdateAutoDetectLanguage();
+	const updateDictationLanguages = useUpdateDictationLanguages();
+
+	const autoDetect = settings?.auto_detect_language ?? false;
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-2">
+			<h3 className="settings-section-title">Language</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Dictation language</p>
+						<p className="settings-description">
+							Tell the speech-to-text provider which language you speak
+						</p>
+					</div>
+					<Select
+						data={LANGUAGE_OPTIONS}
+						value={settings?.language ?? null}
+						onChange={(value) => updateLanguage.mutate(value)}
+						placeholder="Provider default"
+						searchable
+						clearable
+						disabled={isLoading || autoDetect}
+						styles={selectStyles}
+					/>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Detect language automatically</p>
+						<p className="settings-description">
+							Let the provider work out the language of each recording
+						</p>
+					</div>
+					<Switch
+						checked={autoDetect}
+						onChange={(event) =>
+							updateAutoDetectLanguage.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div style={{ marginTop: 16 }}>
+					<p className="settings-label">Quick switch languages</p>
+					<p className="settings-description">
+						Languages the switch language hotkey cycles through and the tray
+						menu offers
+					</p>
+					<MultiSelect
+						data={LANGUAGE_OPTIONS}
+						value={settings?.dictation_languages ?? []}
+						onChange={(value) => updateDictationLanguages.mutate(value)}
+						placeholder="Add a language"
+						searchable
+						disabled={isLoading}
+						mt={8}
+						styles={selectStyles}
+					/>
+				</div>
+			</div>
+		</div>
+	);
+}
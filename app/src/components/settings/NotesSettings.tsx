@@ -0,0 +1,115 @@
+import { Switch, TextInput } from "@mantine/core";
+import { useEffect, useState } from "react";
+import {
+	useNotesFilePath,
+	useSettings,
+	useUpdateNotesAlsoInsert,
+	useUpdateNotesEnabled,
+	useUpdateNotesFilePath,
+	useUpdateNotesIncludeApp,
+} from "../../lib/queries";
+
+export function NotesSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const { data: notesFilePath } = useNotesFilePath();
+	const updateNotesEnabled = useUpdateNotesEnabled();
+	const updateNotesFilePath = useUpdateNotesFilePath();
+	const updateNotesAlsoInsert = useUpdateNotesAlsoInsert();
+	const updateNotesIncludeApp = useUpdateNotesIncludeApp();
+
+	const storedPath = settings?.notes_file_path ?? "";
+	const [pathValue, setPathValue] = useState(storedPath);
+
+	useEffect(() => {
+		setPathValue(storedPath);
+	}, [storedPath]);
+
+	const savePath = () => {
+		const path = pathValue.trim();
+		if (path === storedPath) return;
+		updateNotesFilePath.mutate(path || null);
+	};
+
+	const notesEnabled = settings?.notes_enabled ?? false;
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Notes</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Dictate to notes</p>
+						<p className="settings-description">
+							Append each dictation to a file with its time, instead of typing
+							it. Also in the tray menu
+						</p>
+					</div>
+					<Switch
+						checked={notesEnabled}
+						onChange={(event) =>
+							updateNotesEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{notesEnabled && (
+					<>
+						<div style={{ marginTop: 16 }}>
+							<p className="settings-label">Notes file</p>
+							<p className="settings-description">
+								Files ending in .md get a heading per note
+							</p>
+							<TextInput
+								value={pathValue}
+								onChange={(event) => setPathValue(event.currentTarget.value)}
+								onBlur={savePath}
+								onKeyDown={(event) => {
+									if (event.key === "Enter") savePath();
+								}}
+								placeholder={notesFilePath}
+								disabled={isLoading}
+								style={{ marginTop: 8 }}
+							/>
+						</div>
+						<div className="settings-row" style={{ marginTop: 16 }}>
+							<div>
+								<p className="settings-label">Also insert</p>
+								<p className="settings-description">
+									Type dictations into the focused app as well
+								</p>
+							</div>
+							<Switch
+								checked={settings?.notes_also_insert ?? false}
+								onChange={(event) =>
+									updateNotesAlsoInsert.mutate(event.currentTarget.checked)
+								}
+								disabled={isLoading}
+								color="gray"
+								size="md"
+							/>
+						</div>
+						<div className="settings-row" style={{ marginTop: 16 }}>
+							<div>
+								<p className="settings-label">Include app</p>
+								<p className="settings-description">
+									Note which app was focused when you started dictating
+								</p>
+							</div>
+							<Switch
+								checked={settings?.notes_include_app ?? true}
+								onChange={(event) =>
+									updateNotesIncludeApp.mutate(event.currentTarget.checked)
+								}
+								disabled={isLoading}
+								color="gray"
+								size="md"
+							/>
+						</div>
+					</>
+				)}
+			</div>
+		</div>
+	);
+}
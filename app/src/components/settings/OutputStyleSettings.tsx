@@ -0,0 +1,225 @@
+import { ActionIcon, Button, Select, Switch, TextInput } from "@mantine/core";
+import { Plus, Trash2 } from "lucide-react";
+import { useState } from "react";
+import {
+	useSettings,
+	useUpdateOutputStyle,
+	useUpdateOutputStyleRules,
+} from "../../lib/queries";
+import {
+	DEFAULT_OUTPUT_STYLE,
+	type OutputCasing,
+	type OutputStyle,
+	type QuoteStyle,
+} from "../../lib/tauri";
+
+const CASING_LABELS: Record<OutputCasing, string> = {
+	as_formatted: "As formatted",
+	lowercase: "lowercase",
+	sentence_case: "Sentence case",
+};
+
+const QUOTE_LABELS: Record<QuoteStyle, string> = {
+	unchanged: "As formatted",
+	smart: "Smart “quotes”",
+	straight: 'Straight "quotes"',
+};
+
+const CASING_OPTIONS = Object.entries(CASING_LABELS).map(([value, label]) => ({
+	value,
+	label,
+}));
+
+const QUOTE_OPTIONS = Object.entries(QUOTE_LABELS).map(([value, label]) => ({
+	value,
+	label,
+}));
+
+const selectStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+function isOutputCasing(value: string | null): value is OutputCasing {
+	return value !== null && value in CASING_LABELS;
+}
+
+function isQuoteStyle(value: string | null): value is QuoteStyle {
+	return value !== null && value in QUOTE_LABELS;
+}
+
+interface OutputStyleControlsProps {
+	style: OutputStyle;
+	onChange: (style: OutputStyle) => void;
+	disabled: boolean;
+}
+
+function OutputStyleControls({
+	style,
+	onChange,
+	disabled,
+}: OutputStyleControlsProps) {
+	return (
+		<>
+			<Select
+				data={CASING_OPTIONS}
+				value={style.casing}
+				onChange={(value) => {
+					if (isOutputCasing(value)) onChange({ ...style, casing: value });
+				}}
+				allowDeselect={false}
+				aria-label="Casing"
+				w={150}
+				disabled={disabled}
+				styles={selectStyles}
+			/>
+			<Select
+				data={QUOTE_OPTIONS}
+				value={style.quotes}
+				onChange={(value) => {
+					if (isQuoteStyle(value)) onChange({ ...style, quotes: value });
+				}}
+				allowDeselect={false}
+				aria-label="Quotes"
+				w={150}
+				disabled={disabled}
+				styles={selectStyles}
+			/>
+			<Switch
+				checked={style.strip_trailing_punctuation}
+				onChange={(event) =>
+					onChange({
+						...style,
+						strip_trailing_punctuation: event.currentTarget.checked,
+					})
+				}
+				label="No final period"
+				disabled={disabled}
+				color="gray"
+				size="sm"
+			/>
+		</>
+	);
+}
+
+export function OutputStyleSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateStyle = useUpdateOutputStyle();
+	const updateRules = useUpdateOutputStyleRules();
+	const [newApp, setNewApp] = useState("");
+
+	const style = settings?.output_style ?? DEFAULT_OUTPUT_STYLE;
+	const rules = settings?.output_style_rules ?? [];
+	const disabled = isLoading || updateRules.isPending;
+
+	const handleAdd = () => {
+		if (!newApp.trim()) return;
+		updateRules.mutate([...rules, { app: newApp.trim(), style }], {
+			onSuccess: () => setNewApp(""),
+		});
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Output Style</h3>
+			<div className="settings-card">
+				<div
+					className="settings-row"
+					style={{ flexDirection: "column", alignItems: "stretch", gap: 8 }}
+				>
+					<div>
+						<p className="settings-label">Casing, quotes and punctuation</p>
+						<p className="settings-description">
+							Applied to every dictation, whether or not it was formatted by an
+							LLM
+						</p>
+					</div>
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<OutputStyleControls
+							style={style}
+							onChange={(updated) => updateStyle.mutate(updated)}
+							disabled={isLoading || updateStyle.isPending}
+						/>
+					</div>
+				</div>
+				<div
+					className="settings-row"
+					style={{
+						marginTop: 16,
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					<div>
+						<p className="settings-label">Per-app styles</p>
+						<p className="settings-description">
+							Used instead while a matching app has focus, e.g. lowercase
+							without a final period in chat apps
+						</p>
+					</div>
+					{rules.map((rule, index) => (
+						<div
+							key={`${index}-${rule.app}`}
+							style={{ display: "flex", gap: 8, alignItems: "center" }}
+						>
+							<TextInput
+								value={rule.app}
+								readOnly
+								aria-label="App"
+								style={{ flex: 1 }}
+							/>
+							<OutputStyleControls
+								style={rule.style}
+								onChange={(updated) =>
+									updateRules.mutate(
+										rules.map((existing, i) =>
+											i === index ? { ...existing, style: updated } : existing,
+										),
+									)
+								}
+								disabled={disabled}
+							/>
+							<ActionIcon
+								variant="subtle"
+								color="red"
+								onClick={() =>
+									updateRules.mutate(rules.filter((_, i) => i !== index))
+								}
+								title="Remove"
+								disabled={disabled}
+							>
+								<Trash2 size={14} />
+							</ActionIcon>
+						</div>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newApp}
+							onChange={(event) => setNewApp(event.currentTarget.value)}
+							onKeyDown={(event) => {
+								if (event.key === "Enter") handleAdd();
+							}}
+							placeholder="App name, e.g. slack"
+							disabled={disabled}
+							style={{ flex: 1 }}
+						/>
+						<Button
+							onClick={handleAdd}
+							disabled={disabled || !newApp.trim()}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+				</div>
+			</div>
+		</div>
+	);
+}
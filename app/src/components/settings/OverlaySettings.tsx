@@ -0,0 +1,114 @@
+import { Button, Slider, Switch, Text } from "@mantine/core";
+import { useEffect, useState } from "react";
+import {
+	useResetOverlayPosition,
+	useSettings,
+	useUpdateOverlayScale,
+	useUpdateShowPartialTranscripts,
+} from "../../lib/queries";
+import {
+	DEFAULT_OVERLAY_SCALE,
+	MAX_OVERLAY_SCALE,
+	MIN_OVERLAY_SCALE,
+} from "../../lib/tauri";
+
+export function OverlaySettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateOverlayScale = useUpdateOverlayScale();
+	const resetOverlayPosition = useResetOverlayPosition();
+	const updateShowPartialTranscripts = useUpdateShowPartialTranscripts();
+
+	const currentScale = settings?.overlay_scale ?? DEFAULT_OVERLAY_SCALE;
+	const [scaleValue, setScaleValue] = useState(currentScale);
+
+	useEffect(() => {
+		setScaleValue(currentScale);
+	}, [currentScale]);
+
+	const monitor = settings?.overlay_monitor;
+	let placement = "In the bottom-right corner of the primary monitor";
+	if (settings?.overlay_position) {
+		placement = monitor
+			? `Where you last dragged it, on ${monitor}`
+			: "Where you last dragged it";
+	}
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-2">
+			<h3 className="settings-section-title">Overlay</h3>
+			<div className="settings-card">
+				<div>
+					<p className="settings-label">Size</p>
+					<p className="settings-description">
+						Scale the recording overlay up or down
+					</p>
+					<div
+						style={{
+							marginTop: 12,
+							display: "flex",
+							alignItems: "center",
+							gap: 12,
+						}}
+					>
+						<Slider
+							value={scaleValue}
+							onChange={setScaleValue}
+							onChangeEnd={(value) => updateOverlayScale.mutate(value)}
+							min={MIN_OVERLAY_SCALE}
+							max={MAX_OVERLAY_SCALE}
+							step={0.1}
+							marks={[
+								{ value: MIN_OVERLAY_SCALE, label: `${MIN_OVERLAY_SCALE}×` },
+								{ value: MAX_OVERLAY_SCALE, label: `${MAX_OVERLAY_SCALE}×` },
+							]}
+							disabled={isLoading}
+							styles={{
+								root: { flex: 1 },
+								track: { backgroundColor: "var(--bg-elevated)" },
+								bar: { backgroundColor: "var(--accent-primary)" },
+								thumb: { borderColor: "var(--accent-primary)" },
+								markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+							}}
+						/>
+						<Text size="xs" c="dimmed" style={{ minWidth: 48 }}>
+							{scaleValue.toFixed(1)}×
+						</Text>
+					</div>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Position</p>
+						<p className="settings-description">{placement}</p>
+					</div>
+					<Button
+						onClick={() => resetOverlayPosition.mutate()}
+						loading={resetOverlayPosition.isPending}
+						disabled={isLoading || !settings?.overlay_position}
+						size="sm"
+						variant="light"
+						color="gray"
+					>
+						Reset position
+					</Button>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Live transcript</p>
+						<p className="settings-description">
+							Show what has been heard so far while you speak
+						</p>
+					</div>
+					<Switch
+						checked={settings?.show_partial_transcripts ?? true}
+						onChange={(event) =>
+							updateShowPartialTranscripts.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+			</div>
+		</div>
+	);
+}
@@ -1,20 +1,52 @@
-import { Accordion, Loader } from "@mantine/core";
+import {
+	Accordion,
+	Button,
+	Loader,
+	NumberInput,
+	Select,
+	Text,
+	TextInput,
+} from "@mantine/core";
 import { useEffect, useState } from "react";
 import {
+	useApplyPromptPreset,
 	useDefaultSections,
+	useDeletePromptPreset,
+	useExportPromptPresets,
+	useImportPromptPresets,
+	usePromptPresets,
+	useRenamePromptPreset,
+	useSavePromptPreset,
 	useSettings,
 	useUpdateCleanupPromptSections,
+	useUpdateMaxPromptTokens,
+	useUpdatePromptTruncation,
 } from "../../lib/queries";
-import { type CleanupPromptSections, tauriAPI } from "../../lib/tauri";
+import {
+	type CleanupPromptSections,
+	DEFAULT_CLEANUP_PROMPT_SECTIONS,
+	DEFAULT_MAX_PROMPT_TOKENS,
+	type PresetImport,
+	type PromptTruncation,
+	tauriAPI,
+} from "../../lib/tauri";
 import { PromptSectionEditor } from "./PromptSectionEditor";
 
-const DEFAULT_SECTIONS: CleanupPromptSections = {
-	main: { enabled: true, content: null },
-	advanced: { enabled: true, content: null },
-	dictionary: { enabled: false, content: null },
-};
+// The dictionary section is rendered from the dictionary entries by Rust
+type SectionKey = "main" | "advanced";
 
-type SectionKey = "main" | "advanced" | "dictionary";
+const TRUNCATION_OPTIONS: { value: PromptTruncation; label: string }[] = [
+	{ value: "trim", label: "Shorten sections" },
+	{ value: "drop", label: "Leave sections out" },
+];
+
+const inputStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
 
 interface LocalSectionState {
 	enabled: boolean;
@@ -24,33 +56,246 @@ interface LocalSectionState {
 interface LocalSections {
 	main: LocalSectionState;
 	advanced: LocalSectionState;
-	dictionary: LocalSectionState;
+}
+
+function describePresetImport(result: PresetImport): string {
+	const parts = [`${result.added.length} added`];
+	if (result.replaced.length > 0) {
+		parts.push(`replaced ${result.replaced.join(", ")}`);
+	}
+	return parts.join(", ");
+}
+
+// Presets hold the main and advanced sections; the dictionary section is kept
+function PromptPresetsRow() {
+	const { data } = usePromptPresets();
+	const applyPreset = useApplyPromptPreset();
+	const savePreset = useSavePromptPreset();
+	const renamePreset = useRenamePromptPreset();
+	const deletePreset = useDeletePromptPreset();
+	const exportPresets = useExportPromptPresets();
+	const importPresets = useImportPromptPresets();
+	const [selectedId, setSelectedId] = useState<string | null>(null);
+	const [name, setName] = useState("");
+	const [importPath, setImportPath] = useState("");
+	const [message, setMessage] = useState<string | null>(null);
+
+	const presets = data?.presets ?? [];
+	const activeId = data?.active_id ?? null;
+	// Follow the active preset until another one is picked
+	const selected =
+		presets.find((preset) => preset.id === (selectedId ?? activeId)) ?? null;
+
+	const error =
+		applyPreset.error ||
+		savePreset.error ||
+		renamePreset.error ||
+		deletePreset.error ||
+		exportPresets.error ||
+		importPresets.error;
+
+	const saveAsNew = () => {
+		savePreset.mutate(
+			{ name },
+			{
+				onSuccess: (preset) => {
+					setSelectedId(preset.id);
+					setName("");
+					setMessage(`Saved "${preset.name}"`);
+				},
+			},
+		);
+	};
+
+	const overwrite = (id: string, presetName: string) => {
+		savePreset.mutate(
+			{ name: presetName, id },
+			{ onSuccess: () => setMessage(`Updated "${presetName}"`) },
+		);
+	};
+
+	const remove = (id: string) => {
+		deletePreset.mutate(id, {
+			onSuccess: () => {
+				setSelectedId(null);
+				setMessage(null);
+			},
+		});
+	};
+
+	return (
+		<div
+			style={{
+				display: "flex",
+				flexDirection: "column",
+				gap: 8,
+				marginTop: 16,
+			}}
+		>
+			<div>
+				<p className="settings-label">Presets</p>
+				<p className="settings-description">
+					Save the prompt under a name to switch back to it from here, the tray
+					or a hotkey
+				</p>
+			</div>
+			<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+				<Select
+					data={presets.map((preset) => ({
+						value: preset.id,
+						label:
+							preset.id === activeId ? `${preset.name} (active)` : preset.name,
+					}))}
+					value={selected?.id ?? null}
+					onChange={(value) => {
+						setSelectedId(value);
+						setMessage(null);
+					}}
+					placeholder={presets.length ? "Pick a preset" : "No presets saved"}
+					disabled={presets.length === 0}
+					allowDeselect={false}
+					style={{ flex: 1 }}
+					styles={inputStyles}
+				/>
+				<Button
+					onClick={() => selected && applyPreset.mutate(selected.id)}
+					disabled={
+						!selected || selected.id === activeId || applyPreset.isPending
+					}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Apply
+				</Button>
+				<Button
+					onClick={() => selected && overwrite(selected.id, selected.name)}
+					disabled={
+						!selected || selected.id === activeId || savePreset.isPending
+					}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Update
+				</Button>
+				<Button
+					onClick={() => selected && remove(selected.id)}
+					disabled={!selected || deletePreset.isPending}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Delete
+				</Button>
+			</div>
+			<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+				<TextInput
+					value={name}
+					onChange={(event) => setName(event.currentTarget.value)}
+					placeholder="Preset name, e.g. Email"
+					style={{ flex: 1 }}
+					styles={inputStyles}
+				/>
+				<Button
+					onClick={saveAsNew}
+					disabled={!name.trim() || savePreset.isPending}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Save current
+				</Button>
+				<Button
+					onClick={() =>
+						selected &&
+						renamePreset.mutate(
+							{ id: selected.id, name },
+							{ onSuccess: () => setName("") },
+						)
+					}
+					disabled={!selected || !name.trim() || renamePreset.isPending}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Rename
+				</Button>
+			</div>
+			<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+				<TextInput
+					value={importPath}
+					onChange={(event) => setImportPath(event.currentTarget.value)}
+					placeholder="/path/to/presets.json"
+					style={{ flex: 1 }}
+					styles={inputStyles}
+				/>
+				<Button
+					onClick={() =>
+						importPresets.mutate(importPath, {
+							onSuccess: (result) => setMessage(describePresetImport(result)),
+						})
+					}
+					disabled={!importPath.trim() || importPresets.isPending}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Import
+				</Button>
+				<Button
+					onClick={() =>
+						exportPresets.mutate(undefined, {
+							onSuccess: (path) => setMessage(`Exported to ${path}`),
+						})
+					}
+					disabled={presets.length === 0 || exportPresets.isPending}
+					size="sm"
+					variant="light"
+					color="gray"
+				>
+					Export all
+				</Button>
+			</div>
+			{message && (
+				<Text size="xs" c="dimmed">
+					{message}
+				</Text>
+			)}
+			{error && (
+				<Text size="xs" c="red">
+					{String(error)}
+				</Text>
+			)}
+		</div>
+	);
 }
 
 export function PromptSettings() {
-	const { data: settings } = useSettings();
+	const { data: settings, isLoading } = useSettings();
 	const { data: defaultSections, isLoading: isLoadingDefaultSections } =
 		useDefaultSections();
 	const updateCleanupPromptSections = useUpdateCleanupPromptSections();
+	const updateMaxPromptTokens = useUpdateMaxPromptTokens();
+	const updatePromptTruncation = useUpdatePromptTruncation();
 
 	// Consolidated local state for all sections
 	const [localSections, setLocalSections] = useState<LocalSections>({
 		main: { enabled: true, content: "" },
 		advanced: { enabled: true, content: "" },
-		dictionary: { enabled: false, content: "" },
 	});
 
 	// Track if each section has custom content
 	const hasCustomContent = {
 		main: Boolean(settings?.cleanup_prompt_sections?.main?.content),
 		advanced: Boolean(settings?.cleanup_prompt_sections?.advanced?.content),
-		dictionary: Boolean(settings?.cleanup_prompt_sections?.dictionary?.content),
 	};
 
 	// Sync local state with settings when loaded
 	useEffect(() => {
 		if (settings !== undefined && defaultSections !== undefined) {
-			const sections = settings.cleanup_prompt_sections ?? DEFAULT_SECTIONS;
+			const sections =
+				settings.cleanup_prompt_sections ?? DEFAULT_CLEANUP_PROMPT_SECTIONS;
 
 			setLocalSections({
 				main: {
@@ -61,10 +306,6 @@ export function PromptSettings() {
 					enabled: sections.advanced.enabled,
 					content: sections.advanced.content ?? defaultSections.advanced,
 				},
-				dictionary: {
-					enabled: sections.dictionary.enabled,
-					content: sections.dictionary.content ?? defaultSections.dictionary,
-				},
 			});
 		}
 	}, [settings, defaultSections]);
@@ -100,10 +341,9 @@ export function PromptSettings() {
 				enabled: getEnabled("advanced"),
 				content: getContent("advanced"),
 			},
-			dictionary: {
-				enabled: getEnabled("dictionary"),
-				content: getContent("dictionary"),
-			},
+			dictionary:
+				settings?.cleanup_prompt_sections?.dictionary ??
+				DEFAULT_CLEANUP_PROMPT_SECTIONS.dictionary,
 		};
 	};
 
@@ -188,22 +428,48 @@ export function PromptSettings() {
 							onReset={() => handleReset("advanced")}
 							isSaving={updateCleanupPromptSections.isPending}
 						/>
-
-						<PromptSectionEditor
-							sectionKey="dictionary-prompt"
-							title="Personal Dictionary"
-							description="Custom word mappings for technical terms"
-							enabled={localSections.dictionary.enabled}
-							initialContent={localSections.dictionary.content}
-							defaultContent={defaultSections?.dictionary ?? ""}
-							hasCustom={hasCustomContent.dictionary}
-							onToggle={(checked) => handleToggle("dictionary", checked)}
-							onSave={(content) => handleSave("dictionary", content)}
-							onReset={() => handleReset("dictionary")}
-							isSaving={updateCleanupPromptSections.isPending}
-						/>
 					</Accordion>
 				)}
+				<PromptPresetsRow />
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Prompt size limit</p>
+						<p className="settings-description">
+							For formatting on this device. Advanced features, then the
+							dictionary, are cut to fit. 0 means no limit
+						</p>
+					</div>
+					<div style={{ display: "flex", gap: 8 }}>
+						<NumberInput
+							value={settings?.max_prompt_tokens ?? DEFAULT_MAX_PROMPT_TOKENS}
+							onChange={(value) => {
+								if (typeof value === "number") {
+									updateMaxPromptTokens.mutate(value);
+								}
+							}}
+							min={0}
+							step={512}
+							allowDecimal={false}
+							suffix=" tokens"
+							w={130}
+							disabled={isLoading}
+							styles={inputStyles}
+						/>
+						<Select
+							data={TRUNCATION_OPTIONS}
+							value={settings?.prompt_truncation ?? "trim"}
+							onChange={(value) => {
+								if (value === "trim" || value === "drop") {
+									updatePromptTruncation.mutate(value);
+								}
+							}}
+							allowDeselect={false}
+							w={170}
+							disabled={isLoading}
+							styles={inputStyles}
+						/>
+					</div>
+				</div>
 			</div>
 		</div>
 	);
@@ -1,15 +1,32 @@
-import { Badge, Loader, Select, Slider, Text } from "@mantine/core";
+import { Badge, Loader, Select, Slider, Switch, Text } from "@mantine/core";
 import { useEffect, useState } from "react";
 import {
 	useAvailableProviders,
 	useSettings,
+	useUpdateFormattingEnabled,
 	useUpdateLLMProvider,
+	useUpdateMaxConcurrentTurns,
+	useUpdateShortUtteranceMaxWords,
+	useUpdateSTTFallbackProvider,
 	useUpdateSTTProvider,
 	useUpdateSTTTimeout,
 } from "../../lib/queries";
-import { tauriAPI } from "../../lib/tauri";
+import {
+	DEFAULT_MAX_CONCURRENT_TURNS,
+	DEFAULT_SHORT_UTTERANCE_MAX_WORDS,
+	LOCAL_WHISPER_PROVIDER,
+	MAX_SHORT_UTTERANCE_MAX_WORDS,
+	tauriAPI,
+} from "../../lib/tauri";
+import { WhisperModelSettings } from "./WhisperModelSettings";
 
 const DEFAULT_STT_TIMEOUT = 0.8;
+const NO_FALLBACK = "none";
+
+const STT_FALLBACK_OPTIONS = [
+	{ value: NO_FALLBACK, label: "Off" },
+	{ value: LOCAL_WHISPER_PROVIDER, label: "Local Whisper" },
+];
 
 export function ProvidersSettings() {
 	const { data: settings, isLoading: isLoadingSettings } = useSettings();
@@ -19,8 +36,12 @@ export function ProvidersSettings() {
 	// Wait for settings (source of truth) and provider list (for options)
 	const isLoadingProviderData = isLoadingSettings || isLoadingProviders;
 	const updateSTTProvider = useUpdateSTTProvider();
+	const updateSTTFallbackProvider = useUpdateSTTFallbackProvider();
 	const updateLLMProvider = useUpdateLLMProvider();
 	const updateSTTTimeout = useUpdateSTTTimeout();
+	const updateShortUtteranceMaxWords = useUpdateShortUtteranceMaxWords();
+	const updateFormattingEnabled = useUpdateFormattingEnabled();
+	const updateMaxConcurrentTurns = useUpdateMaxConcurrentTurns();
 
 	const handleSTTProviderChange = (value: string | null) => {
 		if (!value) return;
@@ -32,6 +53,11 @@ export function ProvidersSettings() {
 		});
 	};
 
+	const handleSTTFallbackProviderChange = (value: string | null) => {
+		if (!value) return;
+		updateSTTFallbackProvider.mutate(value === NO_FALLBACK ? null : value);
+	};
+
 	const handleLLMProviderChange = (value: string | null) => {
 		if (!value) return;
 		// Save to local settings (Tauri) then notify overlay window to sync to server
@@ -51,6 +77,24 @@ export function ProvidersSettings() {
 		});
 	};
 
+	const handleShortUtteranceMaxWordsChange = (value: number) => {
+		// Save to local settings (Tauri) then notify overlay window to sync to server
+		updateShortUtteranceMaxWords.mutate(value, {
+			onSuccess: () => {
+				tauriAPI.emitSettingsChanged();
+			},
+		});
+	};
+
+	const handleMaxConcurrentTurnsChange = (value: number) => {
+		// Save to local settings (Tauri) then notify overlay window to apply it
+		updateMaxConcurrentTurns.mutate(value, {
+			onSuccess: () => {
+				tauriAPI.emitSettingsChanged();
+			},
+		});
+	};
+
 	// Get the current timeout value from settings, falling back to default
 	const currentTimeout = settings?.stt_timeout_seconds ?? DEFAULT_STT_TIMEOUT;
 
@@ -62,6 +106,28 @@ export function ProvidersSettings() {
 		setSliderValue(currentTimeout);
 	}, [currentTimeout]);
 
+	const currentShortUtteranceMaxWords =
+		settings?.short_utterance_max_words ?? DEFAULT_SHORT_UTTERANCE_MAX_WORDS;
+
+	const [shortUtteranceSliderValue, setShortUtteranceSliderValue] = useState(
+		currentShortUtteranceMaxWords,
+	);
+
+	useEffect(() => {
+		setShortUtteranceSliderValue(currentShortUtteranceMaxWords);
+	}, [currentShortUtteranceMaxWords]);
+
+	const currentMaxConcurrentTurns =
+		settings?.max_concurrent_turns ?? DEFAULT_MAX_CONCURRENT_TURNS;
+
+	const [concurrentTurnsSliderValue, setConcurrentTurnsSliderValue] = useState(
+		currentMaxConcurrentTurns,
+	);
+
+	useEffect(() => {
+		setConcurrentTurnsSliderValue(currentMaxConcurrentTurns);
+	}, [currentMaxConcurrentTurns]);
+
 	// Group providers by cloud/local for dropdown display
 	const sttCloudProviders =
 		availableProviders?.stt
@@ -70,13 +136,16 @@ export function ProvidersSettings() {
 				value: p.value,
 				label: p.model ? `${p.label} (${p.model})` : p.label,
 			})) ?? [];
-	const sttLocalProviders =
-		availableProviders?.stt
+	// Local Whisper runs in the app, so it is offered even without a server
+	const sttLocalProviders = [
+		...(availableProviders?.stt
 			.filter((p) => p.is_local)
 			.map((p) => ({
 				value: p.value,
 				label: p.model ? `${p.label} (${p.model})` : p.label,
-			})) ?? [];
+			})) ?? []),
+		{ value: LOCAL_WHISPER_PROVIDER, label: "Local Whisper (in-app)" },
+	];
 	const sttProviderOptions = [
 		{ group: "Cloud", items: sttCloudProviders },
 		{ group: "Local", items: sttLocalProviders },
@@ -108,7 +177,12 @@ export function ProvidersSettings() {
 	const selectedLlmProvider = availableProviders?.llm.find(
 		(p) => p.value === settings?.llm_provider,
 	);
-	const isSttProviderLocal = selectedSttProvider?.is_local ?? false;
+	const usesLocalWhisper =
+		settings?.stt_provider === LOCAL_WHISPER_PROVIDER ||
+		settings?.stt_fallback_provider === LOCAL_WHISPER_PROVIDER;
+	const isSttProviderLocal =
+		settings?.stt_provider === LOCAL_WHISPER_PROVIDER ||
+		(selectedSttProvider?.is_local ?? false);
 	const isLlmProviderLocal = selectedLlmProvider?.is_local ?? false;
 
 	return (
@@ -132,10 +206,6 @@ export function ProvidersSettings() {
 									value={settings?.stt_provider ?? null}
 									onChange={handleSTTProviderChange}
 									placeholder="Select provider"
-									disabled={
-										sttCloudProviders.length === 0 &&
-										sttLocalProviders.length === 0
-									}
 									styles={{
 										input: {
 											backgroundColor: "var(--bg-elevated)",
@@ -157,6 +227,29 @@ export function ProvidersSettings() {
 						)}
 					</div>
 				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">STT Fallback</p>
+						<p className="settings-description">
+							Retry with this provider when transcription fails or comes back
+							empty
+						</p>
+					</div>
+					<Select
+						data={STT_FALLBACK_OPTIONS}
+						value={settings?.stt_fallback_provider ?? NO_FALLBACK}
+						onChange={handleSTTFallbackProviderChange}
+						allowDeselect={false}
+						styles={{
+							input: {
+								backgroundColor: "var(--bg-elevated)",
+								borderColor: "var(--border-default)",
+								color: "var(--text-primary)",
+							},
+						}}
+					/>
+				</div>
+				{usesLocalWhisper && <WhisperModelSettings />}
 				<div className="settings-row" style={{ marginTop: 16 }}>
 					<div>
 						<p className="settings-label">Large Language Model (LLM)</p>
@@ -236,6 +329,108 @@ export function ProvidersSettings() {
 						</div>
 					</div>
 				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Format with LLM</p>
+						<p className="settings-description">
+							Clean up transcriptions before inserting them. Turn off to insert
+							raw speech-to-text output when speed matters more
+						</p>
+					</div>
+					<Switch
+						checked={settings?.formatting_enabled ?? true}
+						onChange={(event) =>
+							updateFormattingEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoadingSettings || updateFormattingEnabled.isPending}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div style={{ flex: 1 }}>
+						<p className="settings-label">Skip Formatting for Short Phrases</p>
+						<p className="settings-description">
+							Phrases up to this many words skip the LLM (0 to disable)
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={shortUtteranceSliderValue}
+								onChange={setShortUtteranceSliderValue}
+								onChangeEnd={handleShortUtteranceMaxWordsChange}
+								min={0}
+								max={MAX_SHORT_UTTERANCE_MAX_WORDS}
+								step={1}
+								marks={[
+									{ value: 0, label: "Off" },
+									{
+										value: MAX_SHORT_UTTERANCE_MAX_WORDS,
+										label: `${MAX_SHORT_UTTERANCE_MAX_WORDS}`,
+									},
+								]}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 32 }}>
+								{shortUtteranceSliderValue === 0
+									? "Off"
+									: `${shortUtteranceSliderValue}w`}
+							</Text>
+						</div>
+					</div>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div style={{ flex: 1 }}>
+						<p className="settings-label">Overlapping Turns</p>
+						<p className="settings-description">
+							Start recording again while up to this many earlier turns are
+							still being formatted
+						</p>
+						<div
+							style={{
+								marginTop: 12,
+								display: "flex",
+								alignItems: "center",
+								gap: 12,
+							}}
+						>
+							<Slider
+								value={concurrentTurnsSliderValue}
+								onChange={setConcurrentTurnsSliderValue}
+								onChangeEnd={handleMaxConcurrentTurnsChange}
+								min={1}
+								max={5}
+								step={1}
+								marks={[
+									{ value: 1, label: "1" },
+									{ value: 5, label: "5" },
+								]}
+								styles={{
+									root: { flex: 1 },
+									track: { backgroundColor: "var(--bg-elevated)" },
+									bar: { backgroundColor: "var(--accent-primary)" },
+									thumb: { borderColor: "var(--accent-primary)" },
+									markLabel: { color: "var(--text-secondary)", fontSize: 10 },
+								}}
+							/>
+							<Text size="xs" c="dimmed" style={{ minWidth: 32 }}>
+								{concurrentTurnsSliderValue}
+							</Text>
+						</div>
+					</div>
+				</div>
 			</div>
 		</div>
 	);
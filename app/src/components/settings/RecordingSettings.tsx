@@ -0,0 +1,229 @@
+import { ActionIcon, NumberInput, Select, Switch, Text } from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { format } from "date-fns";
+import { Trash2 } from "lucide-react";
+import { useEffect } from "react";
+import {
+	useAvailableProviders,
+	useDeleteRecording,
+	useRecordings,
+	useSettings,
+	useUpdateArchiveRecordings,
+	useUpdateKeepLastRecording,
+	useUpdateRecordingMaxCount,
+	useUpdateRecordingMaxDays,
+	useUpdateRetrySttProvider,
+} from "../../lib/queries";
+import {
+	DEFAULT_RECORDING_MAX_COUNT,
+	DEFAULT_RECORDING_MAX_DAYS,
+	LOCAL_WHISPER_PROVIDER,
+	type RecordingInfo,
+	tauriAPI,
+} from "../../lib/tauri";
+
+/** Retry option that uses the provider selected in Providers */
+const SELECTED_PROVIDER = "selected";
+
+const numberInputStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+function formatRecordingMeta(recording: RecordingInfo): string {
+	const seconds = (recording.duration_ms / 1000).toFixed(1);
+	const kilobytes = Math.max(1, Math.round(recording.size_bytes / 1024));
+	return `${seconds}s · ${kilobytes} KB`;
+}
+
+export function RecordingSettings() {
+	const queryClient = useQueryClient();
+	const { data: settings, isLoading } = useSettings();
+	const { data: recordings } = useRecordings();
+	const updateArchiveRecordings = useUpdateArchiveRecordings();
+	const updateRecordingMaxCount = useUpdateRecordingMaxCount();
+	const updateRecordingMaxDays = useUpdateRecordingMaxDays();
+	const deleteRecording = useDeleteRecording();
+	const updateKeepLastRecording = useUpdateKeepLastRecording();
+	const updateRetrySttProvider = useUpdateRetrySttProvider();
+	const { data: availableProviders } = useAvailableProviders();
+
+	// New recordings are saved by the backend after each dictation
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onRecordingsChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["recordings"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	const archiveEnabled = settings?.archive_recordings ?? false;
+	const keepLastEnabled = settings?.keep_last_recording ?? true;
+
+	const retryProviderOptions = [
+		{ value: SELECTED_PROVIDER, label: "Selected provider" },
+		...(availableProviders?.stt.map((provider) => ({
+			value: provider.value,
+			label: provider.label,
+		})) ?? []),
+		{ value: LOCAL_WHISPER_PROVIDER, label: "Local Whisper" },
+	];
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-2">
+			<h3 className="settings-section-title">Recordings</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Save recordings</p>
+						<p className="settings-description">
+							Keep the audio of each dictation as a WAV file on this device
+						</p>
+					</div>
+					<Switch
+						checked={archiveEnabled}
+						onChange={(event) =>
+							updateArchiveRecordings.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{archiveEnabled && (
+					<div className="settings-row" style={{ marginTop: 16 }}>
+						<div>
+							<p className="settings-label">Keep recordings</p>
+							<p className="settings-description">
+								Older recordings are deleted past either limit. 0 means no
+								limit
+							</p>
+						</div>
+						<div style={{ display: "flex", gap: 8 }}>
+							<NumberInput
+								value={
+									settings?.recording_max_count ?? DEFAULT_RECORDING_MAX_COUNT
+								}
+								onChange={(value) => {
+									if (typeof value === "number") {
+										updateRecordingMaxCount.mutate(value);
+									}
+								}}
+								min={0}
+								allowDecimal={false}
+								suffix=" files"
+								w={110}
+								disabled={isLoading}
+								styles={numberInputStyles}
+							/>
+							<NumberInput
+								value={
+									settings?.recording_max_days ?? DEFAULT_RECORDING_MAX_DAYS
+								}
+								onChange={(value) => {
+									if (typeof value === "number") {
+										updateRecordingMaxDays.mutate(value);
+									}
+								}}
+								min={0}
+								allowDecimal={false}
+								suffix=" days"
+								w={110}
+								disabled={isLoading}
+								styles={numberInputStyles}
+							/>
+						</div>
+					</div>
+				)}
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Keep last recording</p>
+						<p className="settings-description">
+							Hold the last dictation's audio in memory so it can be
+							transcribed again
+						</p>
+					</div>
+					<Switch
+						checked={keepLastEnabled}
+						onChange={(event) =>
+							updateKeepLastRecording.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{keepLastEnabled && (
+					<div className="settings-row" style={{ marginTop: 16 }}>
+						<div>
+							<p className="settings-label">Retry with</p>
+							<p className="settings-description">
+								Provider that transcribes the last recording again from the
+								retry hotkey or tray menu
+							</p>
+						</div>
+						<Select
+							data={retryProviderOptions}
+							value={settings?.retry_stt_provider ?? SELECTED_PROVIDER}
+							onChange={(value) =>
+								updateRetrySttProvider.mutate(
+									value === SELECTED_PROVIDER ? null : value,
+								)
+							}
+							allowDeselect={false}
+							disabled={isLoading}
+							w={190}
+							styles={numberInputStyles}
+						/>
+					</div>
+				)}
+				{recordings && recordings.length > 0 && (
+					<div
+						style={{
+							marginTop: 16,
+							maxHeight: 240,
+							overflowY: "auto",
+							display: "flex",
+							flexDirection: "column",
+							gap: 4,
+						}}
+					>
+						{recordings.map((recording) => (
+							<div
+								key={recording.id}
+								style={{ display: "flex", gap: 8, alignItems: "center" }}
+							>
+								<span className="settings-label" style={{ flex: 1 }}>
+									{format(new Date(recording.timestamp), "MMM d, h:mm:ss a")}
+								</span>
+								<Text size="xs" c="dimmed">
+									{formatRecordingMeta(recording)}
+								</Text>
+								<ActionIcon
+									variant="subtle"
+									color="red"
+									onClick={() => deleteRecording.mutate(recording.id)}
+									title="Delete"
+									disabled={deleteRecording.isPending}
+								>
+									<Trash2 size={14} />
+								</ActionIcon>
+							</div>
+						))}
+					</div>
+				)}
+			</div>
+		</div>
+	);
+}
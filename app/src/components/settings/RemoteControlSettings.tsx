@@ -0,0 +1,230 @@
+import { Button, NumberInput, Switch, Text } from "@mantine/core";
+import { useClipboard } from "@mantine/hooks";
+import { RefreshCw } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useRegenerateRemoteControlToken,
+	useRemoteControlToken,
+	useSettings,
+	useUpdateApiEnabled,
+	useUpdateApiPort,
+	useUpdateDeepLinksEnabled,
+	useUpdateRemoteControlEnabled,
+	useUpdateRemoteControlPort,
+} from "../../lib/queries";
+import { DEFAULT_API_PORT, DEFAULT_REMOTE_CONTROL_PORT } from "../../lib/tauri";
+
+const inputStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+/** Port input that saves when it loses focus or Enter is pressed */
+function PortInput({
+	port,
+	onSave,
+	disabled,
+}: {
+	port: number;
+	onSave: (port: number) => void;
+	disabled: boolean;
+}) {
+	const [value, setValue] = useState<number | string>(port);
+
+	useEffect(() => {
+		setValue(port);
+	}, [port]);
+
+	const save = () => {
+		if (typeof value !== "number" || value === port) return;
+		onSave(value);
+	};
+
+	return (
+		<NumberInput
+			value={value}
+			onChange={setValue}
+			onBlur={save}
+			onKeyDown={(event) => {
+				if (event.key === "Enter") save();
+			}}
+			min={1024}
+			max={65535}
+			allowDecimal={false}
+			thousandSeparator={false}
+			w={110}
+			disabled={disabled}
+			styles={inputStyles}
+		/>
+	);
+}
+
+export function RemoteControlSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateEnabled = useUpdateRemoteControlEnabled();
+	const updatePort = useUpdateRemoteControlPort();
+	const updateApiEnabled = useUpdateApiEnabled();
+	const updateApiPort = useUpdateApiPort();
+	const updateDeepLinksEnabled = useUpdateDeepLinksEnabled();
+	const regenerateToken = useRegenerateRemoteControlToken();
+	const clipboard = useClipboard();
+
+	const enabled = settings?.remote_control_enabled ?? false;
+	const port = settings?.remote_control_port ?? DEFAULT_REMOTE_CONTROL_PORT;
+	const apiEnabled = settings?.api_enabled ?? false;
+	const apiPort = settings?.api_port ?? DEFAULT_API_PORT;
+	const deepLinksEnabled = settings?.deep_links_enabled ?? false;
+	const { data: token, error: tokenError } = useRemoteControlToken(
+		enabled || apiEnabled,
+	);
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Remote Control</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Allow remote control</p>
+						<p className="settings-description">
+							Let Stream Deck plugins and scripts start, stop and check
+							dictation through a WebSocket on this computer
+						</p>
+					</div>
+					<Switch
+						checked={enabled}
+						onChange={(event) =>
+							updateEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{enabled && (
+					<div className="settings-row" style={{ marginTop: 16 }}>
+						<div>
+							<p className="settings-label">WebSocket port</p>
+							<p className="settings-description">
+								Clients connect to ws://127.0.0.1:{port} and send{" "}
+								{'{"token": "…", "command": "toggle"}'}. Commands are start,
+								stop, toggle and status
+							</p>
+						</div>
+						<PortInput
+							port={port}
+							onSave={(value) => updatePort.mutate(value)}
+							disabled={isLoading}
+						/>
+					</div>
+				)}
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">HTTP API</p>
+						<p className="settings-description">
+							Let scripts and editor plugins control dictation, read the last
+							transcript and insert text over HTTP on this computer
+						</p>
+					</div>
+					<Switch
+						checked={apiEnabled}
+						onChange={(event) =>
+							updateApiEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{apiEnabled && (
+					<div className="settings-row" style={{ marginTop: 16 }}>
+						<div>
+							<p className="settings-label">API port</p>
+							<p className="settings-description">
+								http://127.0.0.1:{apiPort}, also used by tambourine-cli. Send
+								the token as Authorization: Bearer
+							</p>
+						</div>
+						<PortInput
+							port={apiPort}
+							onSave={(value) => updateApiPort.mutate(value)}
+							disabled={isLoading}
+						/>
+					</div>
+				)}
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Launcher links</p>
+						<p className="settings-description">
+							Let Raycast, Alfred and PowerToys Run open tambourine://start,
+							stop and toggle
+						</p>
+						<Text size="sm" c="orange" style={{ marginTop: 4 }}>
+							Any website you visit can open these links too, and start
+							recording from your microphone
+						</Text>
+					</div>
+					<Switch
+						checked={deepLinksEnabled}
+						onChange={(event) =>
+							updateDeepLinksEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{(enabled || apiEnabled) && (
+					<div style={{ marginTop: 16 }}>
+						<p className="settings-label">Token</p>
+						<p className="settings-description">
+							Requests without it are refused
+						</p>
+						{tokenError ? (
+							<Text size="sm" c="red" style={{ marginTop: 8 }}>
+								{String(tokenError)}
+							</Text>
+						) : (
+							<div
+								style={{
+									display: "flex",
+									gap: 8,
+									alignItems: "center",
+									marginTop: 8,
+								}}
+							>
+								<Text
+									size="sm"
+									ff="monospace"
+									style={{ flex: 1, wordBreak: "break-all" }}
+								>
+									{token ?? "…"}
+								</Text>
+								<Button
+									variant="default"
+									size="xs"
+									onClick={() => token && clipboard.copy(token)}
+									disabled={!token}
+								>
+									{clipboard.copied ? "Copied" : "Copy"}
+								</Button>
+								<Button
+									variant="default"
+									size="xs"
+									leftSection={<RefreshCw size={14} />}
+									onClick={() => regenerateToken.mutate()}
+									loading={regenerateToken.isPending}
+									title="Clients using the current token stop working"
+								>
+									New token
+								</Button>
+							</div>
+						)}
+					</div>
+				)}
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,174 @@
+import { ActionIcon, Button, Text, Textarea, TextInput } from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useAddSnippet,
+	useRemoveSnippet,
+	useSnippets,
+	useUpdateSnippet,
+} from "../../lib/queries";
+import { type Snippet, tauriAPI } from "../../lib/tauri";
+
+interface SnippetRowProps {
+	snippet: Snippet;
+	onSave: (snippet: Snippet) => void;
+	onRemove: (id: string) => void;
+	disabled: boolean;
+}
+
+// Fields save when they lose focus
+function SnippetRow({ snippet, onSave, onRemove, disabled }: SnippetRowProps) {
+	const [trigger, setTrigger] = useState(snippet.trigger);
+	const [template, setTemplate] = useState(snippet.template);
+
+	useEffect(() => {
+		setTrigger(snippet.trigger);
+		setTemplate(snippet.template);
+	}, [snippet.trigger, snippet.template]);
+
+	const save = () => {
+		if (trigger === snippet.trigger && template === snippet.template) return;
+		onSave({ ...snippet, trigger, template });
+	};
+
+	return (
+		<div style={{ display: "flex", gap: 8, alignItems: "flex-start" }}>
+			<TextInput
+				value={trigger}
+				onChange={(event) => setTrigger(event.currentTarget.value)}
+				onBlur={save}
+				aria-label="Trigger phrase"
+				disabled={disabled}
+				style={{ flex: 1 }}
+			/>
+			<Textarea
+				value={template}
+				onChange={(event) => setTemplate(event.currentTarget.value)}
+				onBlur={save}
+				aria-label="Template"
+				disabled={disabled}
+				autosize
+				minRows={1}
+				maxRows={6}
+				style={{ flex: 2 }}
+			/>
+			<ActionIcon
+				variant="subtle"
+				color="red"
+				onClick={() => onRemove(snippet.id)}
+				title="Remove"
+				disabled={disabled}
+				mt={6}
+			>
+				<Trash2 size={14} />
+			</ActionIcon>
+		</div>
+	);
+}
+
+export function SnippetSettings() {
+	const queryClient = useQueryClient();
+	const { data: snippets } = useSnippets();
+	const addSnippet = useAddSnippet();
+	const updateSnippet = useUpdateSnippet();
+	const removeSnippet = useRemoveSnippet();
+	const [newTrigger, setNewTrigger] = useState("");
+	const [newTemplate, setNewTemplate] = useState("");
+
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onSnippetsChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["snippets"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	const error = addSnippet.error || updateSnippet.error || removeSnippet.error;
+
+	const handleAdd = () => {
+		if (!newTrigger.trim()) return;
+		addSnippet.mutate(
+			{ trigger: newTrigger, template: newTemplate },
+			{
+				onSuccess: () => {
+					setNewTrigger("");
+					setNewTemplate("");
+				},
+			},
+		);
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Snippets</h3>
+			<div className="settings-card">
+				<div
+					className="settings-row"
+					style={{
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					<div>
+						<p className="settings-label">Trigger phrases</p>
+						<p className="settings-description">
+							Say a phrase to insert its text instead. Templates can use{" "}
+							{"{date}"}, {"{time}"} and {"{clipboard}"}
+						</p>
+					</div>
+					{snippets?.map((snippet) => (
+						<SnippetRow
+							key={snippet.id}
+							snippet={snippet}
+							onSave={(updated) => updateSnippet.mutate(updated)}
+							onRemove={(id) => removeSnippet.mutate(id)}
+							disabled={updateSnippet.isPending || removeSnippet.isPending}
+						/>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "flex-start" }}>
+						<TextInput
+							value={newTrigger}
+							onChange={(event) => setNewTrigger(event.currentTarget.value)}
+							placeholder="e.g. insert my signature"
+							style={{ flex: 1 }}
+						/>
+						<Textarea
+							value={newTemplate}
+							onChange={(event) => setNewTemplate(event.currentTarget.value)}
+							placeholder={"Best regards,\nSam"}
+							autosize
+							minRows={1}
+							maxRows={6}
+							style={{ flex: 2 }}
+						/>
+						<Button
+							onClick={handleAdd}
+							disabled={!newTrigger.trim() || addSnippet.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+					{error && (
+						<Text size="xs" c="red">
+							{String(error)}
+						</Text>
+					)}
+				</div>
+			</div>
+		</div>
+	);
+}
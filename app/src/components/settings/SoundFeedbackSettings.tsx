@@ -0,0 +1,282 @@
+import { ActionIcon, Select, Slider, Switch, TextInput } from "@mantine/core";
+import { Play } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useSettings,
+	useUpdateSoundSettings,
+	useUpdateSoundTheme,
+} from "../../lib/queries";
+import {
+	DEFAULT_SOUND_SETTINGS,
+	DEFAULT_SOUND_THEME,
+	type EventSound,
+	type SoundSettings,
+	type SoundSource,
+	type SoundTheme,
+	type SoundType,
+	tauriAPI,
+} from "../../lib/tauri";
+
+type SoundEventKey = "start" | "stop" | "error" | "complete";
+
+const SOUND_EVENTS: {
+	key: SoundEventKey;
+	soundType: SoundType;
+	label: string;
+}[] = [
+	{ key: "start", soundType: "recording_start", label: "Recording starts" },
+	{ key: "stop", soundType: "recording_stop", label: "Recording stops" },
+	{ key: "error", soundType: "error", label: "Dictation fails" },
+	{ key: "complete", soundType: "complete", label: "Text inserted" },
+];
+
+const SOURCE_OPTIONS = [
+	{ value: "default", label: "Default" },
+	{ value: "silent", label: "Silent" },
+	{ value: "file", label: "WAV file" },
+];
+
+const inputStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+		fontFamily: "monospace",
+		fontSize: "12px",
+	},
+};
+
+const sliderStyles = {
+	root: { flex: 1 },
+	track: { backgroundColor: "var(--bg-elevated)" },
+	bar: { backgroundColor: "var(--accent-primary)" },
+	thumb: { borderColor: "var(--accent-primary)" },
+};
+
+/** A 0 to 1 volume slider that saves when released */
+function VolumeSlider({
+	volume,
+	disabled,
+	onChange,
+}: {
+	volume: number;
+	disabled: boolean;
+	onChange: (volume: number) => void;
+}) {
+	const [value, setValue] = useState(volume);
+
+	useEffect(() => {
+		setValue(volume);
+	}, [volume]);
+
+	return (
+		<Slider
+			value={value}
+			onChange={setValue}
+			onChangeEnd={onChange}
+			min={0}
+			max={1}
+			step={0.05}
+			label={(current) => `${Math.round(current * 100)}%`}
+			disabled={disabled}
+			styles={sliderStyles}
+		/>
+	);
+}
+
+interface SoundEventRowProps {
+	label: string;
+	soundType: SoundType;
+	sound: EventSound;
+	source: SoundSource;
+	disabled: boolean;
+	onSoundChange: (sound: EventSound) => void;
+	onSourceChange: (source: SoundSource) => void;
+}
+
+function SoundEventRow({
+	label,
+	soundType,
+	sound,
+	source,
+	disabled,
+	onSoundChange,
+	onSourceChange,
+}: SoundEventRowProps) {
+	const savedPath = source.source === "file" ? source.path : "";
+	const [mode, setMode] = useState(source.source);
+	const [path, setPath] = useState(savedPath);
+	const [error, setError] = useState<string | null>(null);
+
+	useEffect(() => {
+		setMode(source.source);
+		setPath(savedPath);
+	}, [source.source, savedPath]);
+
+	const handleModeChange = (value: string | null) => {
+		setError(null);
+		if (value === "default" || value === "silent") {
+			setMode(value);
+			onSourceChange({ source: value });
+		} else if (value === "file") {
+			// Saved once a valid file is entered
+			setMode("file");
+		}
+	};
+
+	const handlePathCommit = async () => {
+		const trimmed = path.trim();
+		if (!trimmed || trimmed === savedPath) return;
+		try {
+			await tauriAPI.validateSoundFile(trimmed);
+			setError(null);
+			onSourceChange({ source: "file", path: trimmed });
+		} catch (validationError) {
+			setError(String(validationError));
+		}
+	};
+
+	const isOff = !sound.enabled || mode === "silent";
+
+	return (
+		<div style={{ marginTop: 12 }}>
+			<div className="settings-row">
+				<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+					<Switch
+						checked={sound.enabled}
+						onChange={(event) =>
+							onSoundChange({ ...sound, enabled: event.currentTarget.checked })
+						}
+						disabled={disabled}
+						color="gray"
+						size="sm"
+					/>
+					<p className="settings-description">{label}</p>
+				</div>
+				<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+					<Select
+						data={SOURCE_OPTIONS}
+						value={mode}
+						onChange={handleModeChange}
+						disabled={disabled || !sound.enabled}
+						allowDeselect={false}
+						w={120}
+						styles={inputStyles}
+					/>
+					<ActionIcon
+						variant="subtle"
+						color="gray"
+						onClick={() => tauriAPI.previewSound(soundType)}
+						title="Preview"
+						disabled={disabled || mode === "silent"}
+					>
+						<Play size={14} />
+					</ActionIcon>
+				</div>
+			</div>
+			{!isOff && (
+				<div style={{ marginTop: 8, display: "flex" }}>
+					<VolumeSlider
+						volume={sound.volume}
+						disabled={disabled}
+						onChange={(volume) => onSoundChange({ ...sound, volume })}
+					/>
+				</div>
+			)}
+			{mode === "file" && sound.enabled && (
+				<TextInput
+					value={path}
+					onChange={(event) => {
+						setPath(event.currentTarget.value);
+						setError(null);
+					}}
+					onBlur={handlePathCommit}
+					onKeyDown={(event) => {
+						if (event.key === "Enter") handlePathCommit();
+					}}
+					placeholder="/path/to/sound.wav"
+					error={error}
+					disabled={disabled}
+					mt={8}
+					styles={inputStyles}
+				/>
+			)}
+		</div>
+	);
+}
+
+/** Master volume, and the sound, volume and on/off state of each event */
+export function SoundFeedbackSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateSoundSettings = useUpdateSoundSettings();
+	const updateSoundTheme = useUpdateSoundTheme();
+
+	const sounds = settings?.sound_settings ?? DEFAULT_SOUND_SETTINGS;
+	const theme = settings?.sound_theme ?? DEFAULT_SOUND_THEME;
+	const savedDirectory = theme.directory ?? "";
+	const [directory, setDirectory] = useState(savedDirectory);
+
+	useEffect(() => {
+		setDirectory(savedDirectory);
+	}, [savedDirectory]);
+
+	const saveSounds = (changes: Partial<SoundSettings>) => {
+		updateSoundSettings.mutate({ ...sounds, ...changes });
+	};
+
+	const saveTheme = (changes: Partial<SoundTheme>) => {
+		updateSoundTheme.mutate({ ...theme, ...changes });
+	};
+
+	const handleDirectoryCommit = () => {
+		const trimmed = directory.trim();
+		if (trimmed === savedDirectory) return;
+		saveTheme({ directory: trimmed || null });
+	};
+
+	return (
+		<div style={{ marginTop: 16 }}>
+			<p className="settings-label">Sound feedback</p>
+			<p className="settings-description">
+				Volume of every feedback sound. Each event can be turned off or made
+				quieter below
+			</p>
+			<div style={{ marginTop: 12, display: "flex" }}>
+				<VolumeSlider
+					volume={sounds.volume}
+					disabled={isLoading}
+					onChange={(volume) => saveSounds({ volume })}
+				/>
+			</div>
+			<p className="settings-description" style={{ marginTop: 12 }}>
+				Sound folder (start.wav, stop.wav, error.wav, complete.wav). Missing or
+				invalid files use the built-in sounds; errors and inserted text are
+				silent by default
+			</p>
+			<TextInput
+				value={directory}
+				onChange={(event) => setDirectory(event.currentTarget.value)}
+				onBlur={handleDirectoryCommit}
+				onKeyDown={(event) => {
+					if (event.key === "Enter") handleDirectoryCommit();
+				}}
+				placeholder="Built-in sounds"
+				disabled={isLoading}
+				mt={8}
+				styles={inputStyles}
+			/>
+			{SOUND_EVENTS.map(({ key, soundType, label }) => (
+				<SoundEventRow
+					key={key}
+					label={label}
+					soundType={soundType}
+					sound={sounds[key]}
+					source={theme[key]}
+					disabled={isLoading}
+					onSoundChange={(sound) => saveSounds({ [key]: sound })}
+					onSourceChange={(source) => saveTheme({ [key]: source })}
+				/>
+			))}
+		</div>
+	);
+}
@@ -0,0 +1,130 @@
+import { Button, Switch, Text } from "@mantine/core";
+import { useQueryClient } from "@tanstack/react-query";
+import { Trash2 } from "lucide-react";
+import { useEffect } from "react";
+import {
+	usePurgeTelemetry,
+	useSettings,
+	useTelemetry,
+	useUpdateTelemetryEnabled,
+} from "../../lib/queries";
+import { type FailureCategory, tauriAPI } from "../../lib/tauri";
+
+const FAILURE_LABELS: Record<FailureCategory, string> = {
+	transcription: "Transcription",
+	server: "Server timeout",
+	insertion: "Insertion",
+	notes: "Notes file",
+	secure_input: "Secure input",
+	connection: "Connection lost",
+};
+
+function formatBucket(
+	maxMs: number | null,
+	previousMaxMs: number | null,
+): string {
+	if (maxMs === null) return `> ${(previousMaxMs ?? 0) / 1000}s`;
+	return `≤ ${maxMs / 1000}s`;
+}
+
+export function TelemetrySettings() {
+	const queryClient = useQueryClient();
+	const { data: settings, isLoading } = useSettings();
+	const { data: report } = useTelemetry();
+	const updateTelemetryEnabled = useUpdateTelemetryEnabled();
+	const purgeTelemetry = usePurgeTelemetry();
+
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onTelemetryChanged(() => {
+				queryClient.invalidateQueries({ queryKey: ["telemetry"] });
+			});
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, [queryClient]);
+
+	const hasData =
+		report !== undefined &&
+		(report.sessions > 0 ||
+			report.dictations > 0 ||
+			report.failures.length > 0);
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Local Analytics</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Record local analytics</p>
+						<p className="settings-description">
+							Count dictations, failures and response times on this computer.
+							Nothing you say is recorded and nothing is sent anywhere
+						</p>
+					</div>
+					<Switch
+						checked={settings?.telemetry_enabled ?? false}
+						onChange={(event) =>
+							updateTelemetryEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				{report && hasData && (
+					<div style={{ marginTop: 16 }}>
+						<Text size="sm">
+							{report.sessions} sessions, {report.dictations} dictations
+							{report.failure_rate !== null &&
+								`, ${(report.failure_rate * 100).toFixed(1)}% failed`}
+						</Text>
+						{report.failures.length > 0 && (
+							<Text size="xs" c="dimmed" mt={4}>
+								Failures:{" "}
+								{report.failures
+									.map(
+										(failure) =>
+											`${FAILURE_LABELS[failure.category] ?? failure.category} (${failure.count})`,
+									)
+									.join(", ")}
+							</Text>
+						)}
+						<Text size="xs" c="dimmed" mt={4}>
+							Response times:{" "}
+							{report.latency
+								.map(
+									(bucket, index) =>
+										`${formatBucket(bucket.max_ms, report.latency[index - 1]?.max_ms ?? null)}: ${bucket.count}`,
+								)
+								.join(", ")}
+						</Text>
+						{report.since && (
+							<Text size="xs" c="dimmed" mt={4}>
+								Since {new Date(report.since).toLocaleDateString()}
+							</Text>
+						)}
+					</div>
+				)}
+				<Button
+					onClick={() => purgeTelemetry.mutate()}
+					loading={purgeTelemetry.isPending}
+					disabled={!hasData}
+					size="compact-xs"
+					variant="subtle"
+					color="gray"
+					leftSection={<Trash2 size={12} />}
+					mt={12}
+				>
+					Delete analytics
+				</Button>
+			</div>
+		</div>
+	);
+}
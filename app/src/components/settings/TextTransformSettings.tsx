@@ -0,0 +1,313 @@
+import {
+	ActionIcon,
+	Button,
+	Switch,
+	Text,
+	Textarea,
+	TextInput,
+} from "@mantine/core";
+import { ChevronDown, ChevronUp, Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useSettings,
+	useTestTextTransform,
+	useTestTextTransforms,
+	useUpdateTextTransforms,
+} from "../../lib/queries";
+import type { TransformRule } from "../../lib/tauri";
+
+const EMPTY_RULE: TransformRule = {
+	name: "",
+	pattern: "",
+	replacement: "",
+	enabled: true,
+};
+
+interface TransformRuleRowProps {
+	rule: TransformRule;
+	isFirst: boolean;
+	isLast: boolean;
+	onSave: (rule: TransformRule) => void;
+	onMove: (offset: number) => void;
+	onRemove: () => void;
+	disabled: boolean;
+}
+
+// Text fields save when they lose focus, the switch right away
+function TransformRuleRow({
+	rule,
+	isFirst,
+	isLast,
+	onSave,
+	onMove,
+	onRemove,
+	disabled,
+}: TransformRuleRowProps) {
+	const [draft, setDraft] = useState(rule);
+
+	useEffect(() => {
+		setDraft(rule);
+	}, [rule]);
+
+	const saveText = () => {
+		if (
+			draft.name === rule.name &&
+			draft.pattern === rule.pattern &&
+			draft.replacement === rule.replacement
+		) {
+			return;
+		}
+		onSave({ ...rule, ...draft, enabled: rule.enabled });
+	};
+
+	return (
+		<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+			<Switch
+				checked={rule.enabled}
+				onChange={(event) =>
+					onSave({ ...rule, enabled: event.currentTarget.checked })
+				}
+				disabled={disabled}
+				color="gray"
+				size="sm"
+				title="Enabled"
+			/>
+			<TextInput
+				value={draft.name}
+				onChange={(event) =>
+					setDraft({ ...draft, name: event.currentTarget.value })
+				}
+				onBlur={saveText}
+				placeholder="Name"
+				aria-label="Name"
+				disabled={disabled}
+				w={120}
+			/>
+			<TextInput
+				value={draft.pattern}
+				onChange={(event) =>
+					setDraft({ ...draft, pattern: event.currentTarget.value })
+				}
+				onBlur={saveText}
+				aria-label="Pattern"
+				disabled={disabled}
+				styles={{ input: { fontFamily: "monospace" } }}
+				style={{ flex: 1 }}
+			/>
+			<TextInput
+				value={draft.replacement}
+				onChange={(event) =>
+					setDraft({ ...draft, replacement: event.currentTarget.value })
+				}
+				onBlur={saveText}
+				aria-label="Replacement"
+				disabled={disabled}
+				styles={{ input: { fontFamily: "monospace" } }}
+				style={{ flex: 1 }}
+			/>
+			<ActionIcon
+				variant="subtle"
+				color="gray"
+				onClick={() => onMove(-1)}
+				title="Run earlier"
+				disabled={disabled || isFirst}
+			>
+				<ChevronUp size={14} />
+			</ActionIcon>
+			<ActionIcon
+				variant="subtle"
+				color="gray"
+				onClick={() => onMove(1)}
+				title="Run later"
+				disabled={disabled || isLast}
+			>
+				<ChevronDown size={14} />
+			</ActionIcon>
+			<ActionIcon
+				variant="subtle"
+				color="red"
+				onClick={onRemove}
+				title="Remove"
+				disabled={disabled}
+			>
+				<Trash2 size={14} />
+			</ActionIcon>
+		</div>
+	);
+}
+
+export function TextTransformSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateTextTransforms = useUpdateTextTransforms();
+	const testRule = useTestTextTransform();
+	const testRules = useTestTextTransforms();
+	const [newRule, setNewRule] = useState<TransformRule>(EMPTY_RULE);
+	const [sample, setSample] = useState("");
+
+	const rules = settings?.text_transforms ?? [];
+	const disabled = isLoading || updateTextTransforms.isPending;
+
+	const saveRules = (updated: TransformRule[]) => {
+		testRules.reset();
+		updateTextTransforms.mutate(updated);
+	};
+
+	const moveRule = (index: number, offset: number) => {
+		const reordered = [...rules];
+		const [moved] = reordered.splice(index, 1);
+		reordered.splice(index + offset, 0, moved);
+		saveRules(reordered);
+	};
+
+	// Check the pattern compiles before adding the rule
+	const handleAdd = () => {
+		testRule.mutate(
+			{ rule: newRule, sample },
+			{
+				onSuccess: () => {
+					saveRules([...rules, newRule]);
+					setNewRule(EMPTY_RULE);
+					testRule.reset();
+				},
+			},
+		);
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Text Transforms</h3>
+			<div className="settings-card">
+				<div
+					className="settings-row"
+					style={{
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					<div>
+						<p className="settings-label">Find and replace rules</p>
+						<p className="settings-description">
+							Regular expressions applied in order to every dictation before
+							it is inserted. Use $1 for groups, and (?i) to ignore case
+						</p>
+					</div>
+					{rules.map((rule, index) => (
+						<TransformRuleRow
+							// biome-ignore lint/suspicious/noArrayIndexKey: rows resync from their rule
+							key={index}
+							rule={rule}
+							isFirst={index === 0}
+							isLast={index === rules.length - 1}
+							onSave={(updated) =>
+								saveRules(
+									rules.map((existing, i) =>
+										i === index ? updated : existing,
+									),
+								)
+							}
+							onMove={(offset) => moveRule(index, offset)}
+							onRemove={() => saveRules(rules.filter((_, i) => i !== index))}
+							disabled={disabled}
+						/>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newRule.name}
+							onChange={(event) =>
+								setNewRule({ ...newRule, name: event.currentTarget.value })
+							}
+							placeholder="Name"
+							w={120}
+						/>
+						<TextInput
+							value={newRule.pattern}
+							onChange={(event) =>
+								setNewRule({ ...newRule, pattern: event.currentTarget.value })
+							}
+							placeholder="Pattern, e.g. (?i)\btodo:"
+							styles={{ input: { fontFamily: "monospace" } }}
+							style={{ flex: 1 }}
+						/>
+						<TextInput
+							value={newRule.replacement}
+							onChange={(event) =>
+								setNewRule({
+									...newRule,
+									replacement: event.currentTarget.value,
+								})
+							}
+							placeholder="Replacement, e.g. TODO:"
+							styles={{ input: { fontFamily: "monospace" } }}
+							style={{ flex: 1 }}
+						/>
+						<Button
+							onClick={handleAdd}
+							disabled={disabled || !newRule.pattern || testRule.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+					{testRule.error && (
+						<Text size="xs" c="red">
+							{String(testRule.error)}
+						</Text>
+					)}
+				</div>
+				<div
+					className="settings-row"
+					style={{
+						marginTop: 16,
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					<div>
+						<p className="settings-label">Try the rules</p>
+						<p className="settings-description">
+							Sample text, as the formatter would return it
+						</p>
+					</div>
+					<div style={{ display: "flex", gap: 8, alignItems: "flex-start" }}>
+						<Textarea
+							value={sample}
+							onChange={(event) => {
+								setSample(event.currentTarget.value);
+								testRules.reset();
+							}}
+							placeholder="todo: check the build."
+							autosize
+							minRows={2}
+							maxRows={6}
+							style={{ flex: 1 }}
+						/>
+						<Button
+							onClick={() => testRules.mutate({ rules, sample })}
+							disabled={!sample || testRules.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+						>
+							Test
+						</Button>
+					</div>
+					{testRules.data !== undefined && (
+						<Text size="sm" style={{ whiteSpace: "pre-wrap" }}>
+							{testRules.data}
+						</Text>
+					)}
+					{testRules.error && (
+						<Text size="xs" c="red">
+							{String(testRules.error)}
+						</Text>
+					)}
+				</div>
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,240 @@
+import { Button, SegmentedControl, Text } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+import {
+	AlertCircle,
+	CheckCircle2,
+	HelpCircle,
+	LifeBuoy,
+	RotateCcw,
+	Stethoscope,
+	Timer,
+	XCircle,
+} from "lucide-react";
+import { useState } from "react";
+import {
+	useGenerateSupportBundle,
+	useResetWindowState,
+	useRunDiagnostics,
+	useRunLatencyBenchmark,
+} from "../../lib/queries";
+import type {
+	BenchmarkSource,
+	CheckStatus,
+	LatencyBenchmark,
+} from "../../lib/tauri";
+
+const SOURCE_OPTIONS: { value: BenchmarkSource; label: string }[] = [
+	{ value: "synthetic", label: "Synthetic" },
+	{ value: "recorded", label: "Last recording" },
+];
+
+function benchmarkStages(result: LatencyBenchmark): [string, string][] {
+	const { timings } = result;
+	const ms = (value: number | null) =>
+		value === null ? "unreachable" : `${value} ms`;
+	return [
+		["Capture", ms(timings.capture_ms)],
+		["Network round trip", ms(timings.network_ms)],
+		[
+			result.stt_includes_formatting
+				? "Transcription and formatting (server)"
+				: "Transcription",
+			ms(timings.stt_ms),
+		],
+		[
+			result.stt_includes_formatting ? "Formatting on its own" : "Formatting",
+			ms(timings.formatting_ms),
+		],
+		["Insertion", ms(timings.insertion_ms)],
+	];
+}
+
+function BenchmarkResult({ result }: { result: LatencyBenchmark }) {
+	const seconds = (result.audio_ms / 1000).toFixed(1);
+	return (
+		<div style={{ marginTop: 12 }}>
+			<Text size="sm">
+				{result.total_ms} ms for {seconds}s of audio with{" "}
+				{result.stt_provider}
+				{result.llm_provider && ` and ${result.llm_provider}`}
+			</Text>
+			{benchmarkStages(result).map(([label, value]) => (
+				<Text key={label} size="xs" c="dimmed" mt={2}>
+					{label}: {value}
+				</Text>
+			))}
+		</div>
+	);
+}
+
+function StatusIcon({ status }: { status: CheckStatus }) {
+	switch (status) {
+		case "pass":
+			return <CheckCircle2 size={14} color="var(--mantine-color-green-6)" />;
+		case "warn":
+			return <AlertCircle size={14} color="var(--mantine-color-yellow-6)" />;
+		case "fail":
+			return <XCircle size={14} color="var(--mantine-color-red-6)" />;
+		default:
+			return <HelpCircle size={14} color="var(--mantine-color-gray-6)" />;
+	}
+}
+
+export function TroubleshootingSettings() {
+	const generateSupportBundle = useGenerateSupportBundle();
+	const runDiagnostics = useRunDiagnostics();
+	const runBenchmark = useRunLatencyBenchmark();
+	const resetWindowState = useResetWindowState();
+	const [benchmarkSource, setBenchmarkSource] =
+		useState<BenchmarkSource>("synthetic");
+
+	const handleGenerate = () => {
+		generateSupportBundle.mutate(undefined, {
+			onSuccess: (path) => {
+				notifications.show({
+					title: "Support Bundle Saved",
+					message: path,
+					color: "green",
+					autoClose: 5000,
+				});
+			},
+			onError: (error) => {
+				notifications.show({
+					title: "Support Bundle Failed",
+					message: String(error),
+					color: "red",
+					autoClose: 5000,
+				});
+			},
+		});
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Troubleshooting</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Diagnostics</p>
+						<p className="settings-description">
+							Check the microphone, server, hotkeys and text insertion
+						</p>
+					</div>
+					<Button
+						onClick={() => runDiagnostics.mutate()}
+						loading={runDiagnostics.isPending}
+						size="sm"
+						variant="light"
+						color="gray"
+						leftSection={<Stethoscope size={14} />}
+					>
+						Run
+					</Button>
+				</div>
+				{runDiagnostics.isError && (
+					<Text size="xs" c="red" mt={8}>
+						{String(runDiagnostics.error)}
+					</Text>
+				)}
+				{runDiagnostics.data && (
+					<div style={{ marginTop: 12 }}>
+						{runDiagnostics.data.checks.map((check) => (
+							<div
+								key={check.id}
+								style={{ display: "flex", gap: 8, marginTop: 8 }}
+							>
+								<div style={{ paddingTop: 2 }}>
+									<StatusIcon status={check.status} />
+								</div>
+								<div>
+									<Text size="sm">{check.label}</Text>
+									<Text size="xs" c="dimmed">
+										{check.detail}
+									</Text>
+									{check.fix && (
+										<Text size="xs" mt={2}>
+											{check.fix}
+										</Text>
+									)}
+								</div>
+							</div>
+						))}
+					</div>
+				)}
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Latency benchmark</p>
+						<p className="settings-description">
+							Time each stage of a dictation with the current providers.
+							Nothing is typed
+						</p>
+						<SegmentedControl
+							size="xs"
+							mt={8}
+							value={benchmarkSource}
+							onChange={(value) =>
+								setBenchmarkSource(value as BenchmarkSource)
+							}
+							data={SOURCE_OPTIONS}
+						/>
+					</div>
+					<Button
+						onClick={() => runBenchmark.mutate(benchmarkSource)}
+						loading={runBenchmark.isPending}
+						size="sm"
+						variant="light"
+						color="gray"
+						leftSection={<Timer size={14} />}
+					>
+						Run
+					</Button>
+				</div>
+				{runBenchmark.isError && (
+					<Text size="xs" c="red" mt={8}>
+						{String(runBenchmark.error)}
+					</Text>
+				)}
+				{runBenchmark.data && <BenchmarkResult result={runBenchmark.data} />}
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Support bundle</p>
+						<p className="settings-description">
+							Zip recent logs, settings and system details to attach to a bug
+							report. API keys, tokens, snippets and your home folder are left
+							out
+						</p>
+					</div>
+					<Button
+						onClick={handleGenerate}
+						loading={generateSupportBundle.isPending}
+						size="sm"
+						variant="light"
+						color="gray"
+						leftSection={<LifeBuoy size={14} />}
+					>
+						Create
+					</Button>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">Window positions</p>
+						<p className="settings-description">
+							Windows reopen where you left them. Reset to put them back at
+							their default size and place
+						</p>
+					</div>
+					<Button
+						onClick={() => resetWindowState.mutate()}
+						loading={resetWindowState.isPending}
+						size="sm"
+						variant="light"
+						color="gray"
+						leftSection={<RotateCcw size={14} />}
+					>
+						Reset
+					</Button>
+				</div>
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,168 @@
+import { Button, Progress, Select } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+import { Download, RefreshCw } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useCheckForUpdates,
+	useInstallUpdate,
+	useIsUpdaterAvailable,
+	useSettings,
+	useUpdateUpdateChannel,
+} from "../../lib/queries";
+import {
+	tauriAPI,
+	type UpdateChannel,
+	type UpdateProgress,
+} from "../../lib/tauri";
+
+const UPDATE_CHANNEL_LABELS: Record<UpdateChannel, string> = {
+	stable: "Stable",
+	beta: "Beta",
+};
+
+function isUpdateChannel(value: string | null): value is UpdateChannel {
+	return value !== null && value in UPDATE_CHANNEL_LABELS;
+}
+
+export function UpdateSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const { data: isUpdaterAvailable } = useIsUpdaterAvailable();
+	const updateUpdateChannel = useUpdateUpdateChannel();
+	const checkForUpdates = useCheckForUpdates();
+	const installUpdate = useInstallUpdate();
+	const [progress, setProgress] = useState<UpdateProgress | null>(null);
+
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onUpdateDownloadProgress(setProgress);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	if (isUpdaterAvailable === false) return null;
+
+	const handleChannelChange = (value: string | null) => {
+		if (!isUpdateChannel(value)) return;
+		// A check on the old channel may have found an update the new one doesn't offer
+		checkForUpdates.reset();
+		updateUpdateChannel.mutate(value);
+	};
+
+	const handleCheck = () => {
+		checkForUpdates.mutate(undefined, {
+			onError: (error) => {
+				notifications.show({
+					title: "Update check failed",
+					message: String(error),
+					color: "red",
+				});
+			},
+		});
+	};
+
+	const handleInstall = () => {
+		setProgress(null);
+		installUpdate.mutate(undefined, {
+			onError: (error) => {
+				notifications.show({
+					title: "Update failed",
+					message: String(error),
+					color: "red",
+				});
+			},
+		});
+	};
+
+	const update = checkForUpdates.data;
+	const progressPercent = progress?.total
+		? (progress.downloaded / progress.total) * 100
+		: 0;
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Updates</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Update channel</p>
+						<p className="settings-description">
+							Beta gets new features first, and may be less stable
+						</p>
+					</div>
+					<Select
+						data={Object.entries(UPDATE_CHANNEL_LABELS).map(
+							([value, label]) => ({ value, label }),
+						)}
+						value={settings?.update_channel ?? "stable"}
+						onChange={handleChannelChange}
+						disabled={isLoading || installUpdate.isPending}
+						allowDeselect={false}
+						styles={{
+							input: {
+								backgroundColor: "var(--bg-elevated)",
+								borderColor: "var(--border-default)",
+								color: "var(--text-primary)",
+							},
+						}}
+					/>
+				</div>
+				<div className="settings-row" style={{ marginTop: 16 }}>
+					<div>
+						<p className="settings-label">
+							{update
+								? `Version ${update.version} is available`
+								: checkForUpdates.isSuccess
+									? "You're up to date"
+									: "Check for updates"}
+						</p>
+						{update && (
+							<p className="settings-description">
+								You have version {update.current_version}. Tambourine restarts
+								after installing
+							</p>
+						)}
+					</div>
+					{update ? (
+						<Button
+							onClick={handleInstall}
+							loading={installUpdate.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Download size={14} />}
+						>
+							Install
+						</Button>
+					) : (
+						<Button
+							onClick={handleCheck}
+							loading={checkForUpdates.isPending}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<RefreshCw size={14} />}
+						>
+							Check
+						</Button>
+					)}
+				</div>
+				{installUpdate.isPending && (
+					<Progress
+						value={progressPercent}
+						animated={!progress?.total}
+						size="sm"
+						color="gray"
+						style={{ marginTop: 12 }}
+					/>
+				)}
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,218 @@
+import { ActionIcon, Button, Select, Switch, TextInput } from "@mantine/core";
+import { Plus, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useSettings,
+	useUpdateVoiceCommands,
+	useUpdateVoiceCommandsEnabled,
+} from "../../lib/queries";
+import {
+	DEFAULT_VOICE_COMMANDS,
+	type VoiceCommand,
+	type VoiceCommandAction,
+} from "../../lib/tauri";
+
+const ACTION_LABELS: Record<VoiceCommandAction, string> = {
+	new_line: "New line",
+	new_paragraph: "New paragraph",
+	press_enter: "Press Enter",
+	press_tab: "Press Tab",
+	delete_that: "Delete what was just said",
+	undo: "Undo",
+};
+
+const ACTION_OPTIONS = Object.entries(ACTION_LABELS).map(([value, label]) => ({
+	value,
+	label,
+}));
+
+const selectStyles = {
+	input: {
+		backgroundColor: "var(--bg-elevated)",
+		borderColor: "var(--border-default)",
+		color: "var(--text-primary)",
+	},
+};
+
+function isVoiceCommandAction(
+	value: string | null,
+): value is VoiceCommandAction {
+	return value !== null && value in ACTION_LABELS;
+}
+
+interface VoiceCommandRowProps {
+	command: VoiceCommand;
+	onSave: (command: VoiceCommand) => void;
+	onRemove: () => void;
+	disabled: boolean;
+}
+
+// The phrase saves when it loses focus, the action right away
+function VoiceCommandRow({
+	command,
+	onSave,
+	onRemove,
+	disabled,
+}: VoiceCommandRowProps) {
+	const [phrase, setPhrase] = useState(command.phrase);
+
+	useEffect(() => {
+		setPhrase(command.phrase);
+	}, [command.phrase]);
+
+	return (
+		<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+			<TextInput
+				value={phrase}
+				onChange={(event) => setPhrase(event.currentTarget.value)}
+				onBlur={() => {
+					if (phrase.trim() && phrase !== command.phrase) {
+						onSave({ ...command, phrase: phrase.trim() });
+					}
+				}}
+				aria-label="Spoken phrase"
+				disabled={disabled}
+				style={{ flex: 1 }}
+			/>
+			<Select
+				data={ACTION_OPTIONS}
+				value={command.action}
+				onChange={(value) => {
+					if (isVoiceCommandAction(value)) {
+						onSave({ ...command, action: value });
+					}
+				}}
+				allowDeselect={false}
+				w={210}
+				disabled={disabled}
+				styles={selectStyles}
+			/>
+			<ActionIcon
+				variant="subtle"
+				color="red"
+				onClick={onRemove}
+				title="Remove"
+				disabled={disabled}
+			>
+				<Trash2 size={14} />
+			</ActionIcon>
+		</div>
+	);
+}
+
+export function VoiceCommandSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const updateEnabled = useUpdateVoiceCommandsEnabled();
+	const updateCommands = useUpdateVoiceCommands();
+	const [newPhrase, setNewPhrase] = useState("");
+	const [newAction, setNewAction] = useState<VoiceCommandAction>("new_line");
+
+	const enabled = settings?.voice_commands_enabled ?? true;
+	const commands = settings?.voice_commands ?? DEFAULT_VOICE_COMMANDS;
+	const disabled = isLoading || !enabled || updateCommands.isPending;
+
+	const handleAdd = () => {
+		if (!newPhrase.trim()) return;
+		updateCommands.mutate(
+			[...commands, { phrase: newPhrase.trim(), action: newAction }],
+			{ onSuccess: () => setNewPhrase("") },
+		);
+	};
+
+	return (
+		<div className="settings-section animate-in animate-in-delay-4">
+			<h3 className="settings-section-title">Voice Commands</h3>
+			<div className="settings-card">
+				<div className="settings-row">
+					<div>
+						<p className="settings-label">Carry out voice commands</p>
+						<p className="settings-description">
+							Saying a phrase below does its action instead of typing the
+							words
+						</p>
+					</div>
+					<Switch
+						checked={enabled}
+						onChange={(event) =>
+							updateEnabled.mutate(event.currentTarget.checked)
+						}
+						disabled={isLoading}
+						color="gray"
+						size="md"
+					/>
+				</div>
+				<div
+					className="settings-row"
+					style={{
+						marginTop: 16,
+						flexDirection: "column",
+						alignItems: "stretch",
+						gap: 8,
+					}}
+				>
+					{commands.map((command, index) => (
+						<VoiceCommandRow
+							key={`${index}-${command.phrase}`}
+							command={command}
+							onSave={(updated) =>
+								updateCommands.mutate(
+									commands.map((existing, i) =>
+										i === index ? updated : existing,
+									),
+								)
+							}
+							onRemove={() =>
+								updateCommands.mutate(commands.filter((_, i) => i !== index))
+							}
+							disabled={disabled}
+						/>
+					))}
+					<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+						<TextInput
+							value={newPhrase}
+							onChange={(event) => setNewPhrase(event.currentTarget.value)}
+							onKeyDown={(event) => {
+								if (event.key === "Enter") handleAdd();
+							}}
+							placeholder="e.g. next field"
+							disabled={disabled}
+							style={{ flex: 1 }}
+						/>
+						<Select
+							data={ACTION_OPTIONS}
+							value={newAction}
+							onChange={(value) => {
+								if (isVoiceCommandAction(value)) setNewAction(value);
+							}}
+							allowDeselect={false}
+							w={210}
+							disabled={disabled}
+							styles={selectStyles}
+						/>
+						<Button
+							onClick={handleAdd}
+							disabled={disabled || !newPhrase.trim()}
+							size="sm"
+							variant="light"
+							color="gray"
+							leftSection={<Plus size={14} />}
+						>
+							Add
+						</Button>
+					</div>
+					<div>
+						<Button
+							onClick={() => updateCommands.mutate(DEFAULT_VOICE_COMMANDS)}
+							disabled={disabled}
+							size="xs"
+							variant="subtle"
+							color="gray"
+						>
+							Reset to defaults
+						</Button>
+					</div>
+				</div>
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,161 @@
+import { Button, Progress, Select } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+import { Download, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import {
+	useDeleteWhisperModel,
+	useDownloadWhisperModel,
+	useSettings,
+	useUpdateWhisperModel,
+	useWhisperModels,
+} from "../../lib/queries";
+import {
+	DEFAULT_WHISPER_MODEL,
+	type ModelDownloadProgress,
+	tauriAPI,
+	type WhisperModel,
+	type WhisperModelStatus,
+} from "../../lib/tauri";
+
+const WHISPER_MODEL_LABELS: Record<WhisperModel, string> = {
+	tiny: "Tiny",
+	base: "Base",
+	small: "Small",
+	medium: "Medium",
+	"large-v3-turbo": "Large v3 Turbo",
+};
+
+function isWhisperModel(value: string | null): value is WhisperModel {
+	return value !== null && value in WHISPER_MODEL_LABELS;
+}
+
+function formatSize(megabytes: number): string {
+	return megabytes >= 1000
+		? `${(megabytes / 1000).toFixed(1)} GB`
+		: `${megabytes} MB`;
+}
+
+function formatModelLabel(status: WhisperModelStatus): string {
+	const size = formatSize(status.approx_size_mb);
+	const label = `${WHISPER_MODEL_LABELS[status.model]} (${size})`;
+	return status.downloaded ? `${label} ✓` : label;
+}
+
+/** Choose, download and delete the model used for local Whisper transcription */
+export function WhisperModelSettings() {
+	const { data: settings, isLoading } = useSettings();
+	const { data: models } = useWhisperModels();
+	const updateWhisperModel = useUpdateWhisperModel();
+	const downloadWhisperModel = useDownloadWhisperModel();
+	const deleteWhisperModel = useDeleteWhisperModel();
+	const [progress, setProgress] = useState<ModelDownloadProgress | null>(null);
+
+	useEffect(() => {
+		let unlisten: (() => void) | undefined;
+
+		const setup = async () => {
+			unlisten = await tauriAPI.onWhisperModelDownloadProgress(setProgress);
+		};
+
+		setup();
+
+		return () => {
+			unlisten?.();
+		};
+	}, []);
+
+	const selectedModel = settings?.whisper_model ?? DEFAULT_WHISPER_MODEL;
+	const selectedStatus = models?.find(
+		(status) => status.model === selectedModel,
+	);
+	const isDownloading =
+		downloadWhisperModel.isPending || (selectedStatus?.downloading ?? false);
+
+	const modelOptions = (models ?? []).map((status) => ({
+		value: status.model,
+		label: formatModelLabel(status),
+	}));
+
+	const handleDownload = () => {
+		setProgress(null);
+		downloadWhisperModel.mutate(selectedModel, {
+			onError: (error) => {
+				notifications.show({
+					title: "Download failed",
+					message: String(error),
+					color: "red",
+				});
+			},
+		});
+	};
+
+	const progressPercent =
+		progress?.model === selectedModel && progress.total_bytes
+			? (progress.downloaded_bytes / progress.total_bytes) * 100
+			: 0;
+
+	return (
+		<div
+			className="settings-row"
+			style={{
+				marginTop: 16,
+				flexDirection: "column",
+				alignItems: "stretch",
+				gap: 8,
+			}}
+		>
+			<div>
+				<p className="settings-label">Whisper model</p>
+				<p className="settings-description">
+					Larger models are more accurate but slower. Models are downloaded
+					once and run on this device
+				</p>
+			</div>
+			<div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+				<Select
+					data={modelOptions}
+					value={selectedModel}
+					onChange={(value) => {
+						if (isWhisperModel(value)) updateWhisperModel.mutate(value);
+					}}
+					allowDeselect={false}
+					disabled={isLoading}
+					style={{ flex: 1 }}
+					styles={{
+						input: {
+							backgroundColor: "var(--bg-elevated)",
+							borderColor: "var(--border-default)",
+							color: "var(--text-primary)",
+						},
+					}}
+				/>
+				{selectedStatus?.downloaded ? (
+					<Button
+						onClick={() => deleteWhisperModel.mutate(selectedModel)}
+						disabled={deleteWhisperModel.isPending}
+						size="sm"
+						variant="light"
+						color="red"
+						leftSection={<Trash2 size={14} />}
+					>
+						Delete
+					</Button>
+				) : (
+					<Button
+						onClick={handleDownload}
+						loading={isDownloading}
+						size="sm"
+						variant="light"
+						color="gray"
+						leftSection={<Download size={14} />}
+					>
+						Download
+					</Button>
+				)}
+			</div>
+			{isDownloading && (
+				<Progress value={progressPercent} size="sm" color="gray" animated />
+			)}
+		</div>
+	);
+}
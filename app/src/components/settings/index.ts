@@ -1,7 +1,23 @@
 export { AudioSettings } from "./AudioSettings";
+export { BlockedAppsSettings } from "./BlockedAppsSettings";
 export { ConnectionSettings } from "./ConnectionSettings";
+export { DictionarySettings } from "./DictionarySettings";
+export { ExperimentalSettings } from "./ExperimentalSettings";
 export { HotkeySettings } from "./HotkeySettings";
+export { InsertionSettings } from "./InsertionSettings";
+export { LanguageSettings } from "./LanguageSettings";
+export { NotesSettings } from "./NotesSettings";
+export { OutputStyleSettings } from "./OutputStyleSettings";
+export { OverlaySettings } from "./OverlaySettings";
 export type { PromptSectionEditorProps } from "./PromptSectionEditor";
 export { PromptSectionEditor } from "./PromptSectionEditor";
 export { PromptSettings } from "./PromptSettings";
 export { ProvidersSettings } from "./ProvidersSettings";
+export { RecordingSettings } from "./RecordingSettings";
+export { RemoteControlSettings } from "./RemoteControlSettings";
+export { SnippetSettings } from "./SnippetSettings";
+export { TelemetrySettings } from "./TelemetrySettings";
+export { TextTransformSettings } from "./TextTransformSettings";
+export { TroubleshootingSettings } from "./TroubleshootingSettings";
+export { UpdateSettings } from "./UpdateSettings";
+export { VoiceCommandSettings } from "./VoiceCommandSettings";
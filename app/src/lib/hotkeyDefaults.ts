@@ -17,6 +17,39 @@ export const DEFAULT_HOLD_KEY = "Backquote";
 /** Default key for paste last transcription (Ctrl+Alt+.) */
 export const DEFAULT_PASTE_LAST_KEY = "Period";
 
+/** Suggested key for cycling through history (Ctrl+Alt+,), off until enabled */
+export const DEFAULT_CYCLE_HISTORY_KEY = "Comma";
+
+/** Suggested key for switching language (Ctrl+Alt+/), off until enabled */
+export const DEFAULT_CYCLE_LANGUAGE_KEY = "Slash";
+
+/** Suggested key for pausing and resuming recording (Ctrl+Alt+P), off until enabled */
+export const DEFAULT_PAUSE_RESUME_KEY = "P";
+
+/** Suggested key for retrying the last transcription (Ctrl+Alt+R), off until enabled */
+export const DEFAULT_RETRY_LAST_KEY = "R";
+
+/** Suggested key for a clipboard-only dictation (Ctrl+Alt+C), off until enabled */
+export const DEFAULT_CLIPBOARD_ONLY_KEY = "C";
+
+/** Suggested key for switching STT provider (Ctrl+Alt+S), off until enabled */
+export const DEFAULT_CYCLE_STT_PROVIDER_KEY = "S";
+
+/** Suggested key for toggling LLM formatting (Ctrl+Alt+F), off until enabled */
+export const DEFAULT_TOGGLE_FORMATTING_KEY = "F";
+
+/** Suggested key for switching prompt preset (Ctrl+Alt+M), off until enabled */
+export const DEFAULT_CYCLE_PROMPT_PRESET_KEY = "M";
+
+/** Suggested key for quick actions (Ctrl+Alt+K), off until enabled */
+export const DEFAULT_OPEN_PALETTE_KEY = "K";
+
+/** Suggested key for pausing dictation (Ctrl+Alt+D), off until enabled */
+export const DEFAULT_DO_NOT_DISTURB_KEY = "D";
+
+/** Default maximum gap between the two taps of a double-tap hotkey */
+export const DEFAULT_DOUBLE_TAP_INTERVAL_MS = 300;
+
 // ============================================================================
 
 /** Default toggle hotkey config */
@@ -36,3 +69,63 @@ export const DEFAULT_PASTE_LAST_HOTKEY: HotkeyConfig = {
 	modifiers: DEFAULT_HOTKEY_MODIFIERS,
 	key: DEFAULT_PASTE_LAST_KEY,
 };
+
+/** Suggested history cycle hotkey config, used when the hotkey is enabled */
+export const DEFAULT_CYCLE_HISTORY_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_CYCLE_HISTORY_KEY,
+};
+
+/** Suggested language cycle hotkey config, used when the hotkey is enabled */
+export const DEFAULT_CYCLE_LANGUAGE_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_CYCLE_LANGUAGE_KEY,
+};
+
+/** Suggested pause/resume hotkey config, used when the hotkey is enabled */
+export const DEFAULT_PAUSE_RESUME_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_PAUSE_RESUME_KEY,
+};
+
+/** Suggested retry hotkey config, used when the hotkey is enabled */
+export const DEFAULT_RETRY_LAST_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_RETRY_LAST_KEY,
+};
+
+/** Suggested clipboard-only hotkey config, used when the hotkey is enabled */
+export const DEFAULT_CLIPBOARD_ONLY_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_CLIPBOARD_ONLY_KEY,
+};
+
+/** Suggested STT provider hotkey config, used when the hotkey is enabled */
+export const DEFAULT_CYCLE_STT_PROVIDER_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_CYCLE_STT_PROVIDER_KEY,
+};
+
+/** Suggested formatting hotkey config, used when the hotkey is enabled */
+export const DEFAULT_TOGGLE_FORMATTING_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_TOGGLE_FORMATTING_KEY,
+};
+
+/** Suggested prompt preset hotkey config, used when the hotkey is enabled */
+export const DEFAULT_CYCLE_PROMPT_PRESET_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_CYCLE_PROMPT_PRESET_KEY,
+};
+
+/** Suggested quick actions hotkey config, used when the hotkey is enabled */
+export const DEFAULT_OPEN_PALETTE_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_OPEN_PALETTE_KEY,
+};
+
+/** Suggested do-not-disturb hotkey config, used when the hotkey is enabled */
+export const DEFAULT_DO_NOT_DISTURB_HOTKEY: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: DEFAULT_DO_NOT_DISTURB_KEY,
+};
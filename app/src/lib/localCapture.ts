@@ -0,0 +1,81 @@
+/**
+ * Microphone capture for STT providers that transcribe inside the app.
+ *
+ * With local Whisper the Pipecat transport isn't used for a turn, so Rust asks
+ * the overlay for audio with local-capture-start and local-capture-stop. The
+ * audio is captured as 16 kHz mono, the format Rust expects, and streamed back
+ * in small chunks. When asked to stop, the overlay confirms once the last chunk
+ * has reached Rust, so the turn isn't transcribed without it. Input gain and AGC
+ * apply as they do for the transport. Only
+ * used when the overlay owns the microphone; audio captured in the backend
+ * already reaches Rust.
+ */
+import { getWebviewUserMedia } from "./micProcessing";
+import { tauriAPI } from "./tauri";
+
+/** Sample rate of the audio Rust transcribes (Whisper's native rate) */
+const CAPTURE_SAMPLE_RATE = 16000;
+
+/** Samples per chunk sent to Rust (128 ms) */
+const CAPTURE_CHUNK_SIZE = 2048;
+
+interface LocalCapture {
+	stream: MediaStream;
+	context: AudioContext;
+	processor: ScriptProcessorNode;
+}
+
+let capture: LocalCapture | null = null;
+// Chunks sent to Rust that it hasn't received yet
+const pendingSends = new Set<Promise<void>>();
+// Bumped on every start and stop, so a stop during startup cancels it
+let generation = 0;
+
+export async function startLocalCapture(deviceId: string | null) {
+	if (capture) return;
+	const startedGeneration = ++generation;
+
+	const stream = await getWebviewUserMedia({
+		audio: deviceId ? { deviceId: { exact: deviceId } } : true,
+	});
+	if (startedGeneration !== generation) {
+		for (const track of stream.getTracks()) track.stop();
+		return;
+	}
+
+	const context = new AudioContext({ sampleRate: CAPTURE_SAMPLE_RATE });
+	// ScriptProcessorNode is deprecated, but needs no separate worklet module
+	const processor = context.createScriptProcessor(CAPTURE_CHUNK_SIZE, 1, 1);
+	processor.onaudioprocess = (event) => {
+		const samples = Array.from(event.inputBuffer.getChannelData(0));
+		const send = tauriAPI
+			.sendSttAudio(samples)
+			.catch((error: unknown) => {
+				console.warn("[Audio] Failed to send captured audio:", error);
+			})
+			.finally(() => pendingSends.delete(send));
+		pendingSends.add(send);
+	};
+	context.createMediaStreamSource(stream).connect(processor);
+	// The processor only runs while connected to an output, which stays silent
+	processor.connect(context.destination);
+	capture = { stream, context, processor };
+}
+
+export function stopLocalCapture() {
+	generation++;
+	if (!capture) return;
+	const { stream, context, processor } = capture;
+	capture = null;
+	processor.onaudioprocess = null;
+	processor.disconnect();
+	for (const track of stream.getTracks()) track.stop();
+	context.close().catch(() => {});
+}
+
+/** Stop capturing, then tell Rust once everything captured has been sent */
+export async function flushLocalCapture(id: number) {
+	stopLocalCapture();
+	await Promise.allSettled([...pendingSends]);
+	await tauriAPI.localCaptureFlushed(id);
+}
@@ -0,0 +1,44 @@
+/**
+ * Microphone permission checks for the overlay.
+ *
+ * Access can be revoked while the app is running, so it is checked as each
+ * recording starts rather than once at launch. A turn that can't open the
+ * microphone is cancelled instead of streaming silence to STT.
+ */
+
+export type MicPermission = "granted" | "denied" | "prompt" | "unknown";
+
+/** Permission state from the webview, or "unknown" where it can't be queried */
+export async function getMicPermission(): Promise<MicPermission> {
+	try {
+		const status = await navigator.permissions.query({
+			name: "microphone" as PermissionName,
+		});
+		return status.state;
+	} catch {
+		return "unknown";
+	}
+}
+
+/** Whether opening the microphone failed because access is denied */
+export function isMicPermissionError(error: unknown): boolean {
+	return (
+		error instanceof DOMException &&
+		(error.name === "NotAllowedError" || error.name === "SecurityError")
+	);
+}
+
+/**
+ * Open the microphone once, which shows the OS prompt if access is undecided.
+ * Returns whether access is granted.
+ */
+export async function promptForMicPermission(): Promise<boolean> {
+	try {
+		const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
+		for (const track of stream.getTracks()) track.stop();
+		return true;
+	} catch (error) {
+		if (isMicPermissionError(error)) return false;
+		throw error;
+	}
+}
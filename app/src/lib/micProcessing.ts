@@ -0,0 +1,130 @@
+/**
+ * Input gain and automatic gain control for the overlay's microphone.
+ *
+ * The Pipecat transport opens the microphone itself, so getUserMedia is
+ * wrapped. With native capture on, the microphone is opened in the backend
+ * and its audio returned instead, falling back to the webview if that fails.
+ * Otherwise the AGC, noise suppression and echo cancellation settings are
+ * added to the audio constraints, and the audio is routed through a gain node
+ * before the track reaches the transport.
+ */
+import { openNativeMic, requestedDeviceId } from "./nativeCapture";
+import { clampMicGain, DEFAULT_MIC_GAIN } from "./tauri";
+
+interface MicProcessingOptions {
+	gain: number;
+	agcEnabled: boolean;
+	nativeCapture: boolean;
+	noiseSuppression: boolean;
+	echoCancellation: boolean;
+}
+
+interface ProcessedMic {
+	source: MediaStreamTrack;
+	gainNode: GainNode;
+}
+
+let options: MicProcessingOptions = {
+	gain: DEFAULT_MIC_GAIN,
+	agcEnabled: true,
+	nativeCapture: true,
+	noiseSuppression: false,
+	echoCancellation: false,
+};
+let webviewGetUserMedia: MediaDevices["getUserMedia"] | null = null;
+const activeMics = new Set<ProcessedMic>();
+let isInstalled = false;
+
+function withAgcConstraint(
+	constraints: MediaStreamConstraints | undefined,
+): MediaStreamConstraints | undefined {
+	if (!constraints?.audio) return constraints;
+	const audio = constraints.audio === true ? {} : constraints.audio;
+	return {
+		...constraints,
+		audio: {
+			...audio,
+			autoGainControl: options.agcEnabled,
+			noiseSuppression: options.noiseSuppression,
+			echoCancellation: options.echoCancellation,
+		},
+	};
+}
+
+/** Replace the stream's microphone track with one passed through a gain node */
+function amplify(stream: MediaStream): MediaStream {
+	const [source] = stream.getAudioTracks();
+	if (!source) return stream;
+
+	const context = new AudioContext();
+	const gainNode = context.createGain();
+	gainNode.gain.value = options.gain;
+	const destination = context.createMediaStreamDestination();
+	context
+		.createMediaStreamSource(new MediaStream([source]))
+		.connect(gainNode)
+		.connect(destination);
+
+	const mic = { source, gainNode };
+	activeMics.add(mic);
+
+	// Stopping the processed track releases the microphone and the audio graph
+	const [output] = destination.stream.getAudioTracks();
+	const stopOutput = output.stop.bind(output);
+	output.stop = () => {
+		stopOutput();
+		source.stop();
+		activeMics.delete(mic);
+		context.close().catch(() => {});
+	};
+
+	return new MediaStream([output, ...stream.getVideoTracks()]);
+}
+
+/** Open the microphone in the webview, with gain and AGC applied */
+export async function getWebviewUserMedia(
+	constraints?: MediaStreamConstraints,
+): Promise<MediaStream> {
+	const getUserMedia =
+		webviewGetUserMedia ??
+		navigator.mediaDevices.getUserMedia.bind(navigator.mediaDevices);
+	const stream = await getUserMedia(withAgcConstraint(constraints));
+	return constraints?.audio ? amplify(stream) : stream;
+}
+
+/** Wrap getUserMedia so microphones opened from now on are processed */
+export function installMicProcessing() {
+	if (isInstalled || !navigator.mediaDevices?.getUserMedia) return;
+	isInstalled = true;
+
+	webviewGetUserMedia = navigator.mediaDevices.getUserMedia.bind(
+		navigator.mediaDevices,
+	);
+	navigator.mediaDevices.getUserMedia = async (constraints) => {
+		if (options.nativeCapture && constraints?.audio && !constraints.video) {
+			try {
+				return await openNativeMic(requestedDeviceId(constraints.audio));
+			} catch (error) {
+				console.warn("[Audio] Native capture failed, using webview:", error);
+			}
+		}
+		return getWebviewUserMedia(constraints);
+	};
+}
+
+/** Apply new gain and AGC settings, including to microphones already open */
+export function setMicProcessing(next: MicProcessingOptions) {
+	const agcChanged = next.agcEnabled !== options.agcEnabled;
+	options = { ...next, gain: clampMicGain(next.gain) };
+
+	for (const mic of activeMics) {
+		mic.gainNode.gain.value = options.gain;
+		if (agcChanged) {
+			mic.source
+				.applyConstraints({ autoGainControl: options.agcEnabled })
+				.catch((error: unknown) => {
+					console.warn("[Audio] Failed to change gain control:", error);
+				});
+		}
+	}
+}
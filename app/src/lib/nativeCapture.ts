@@ -0,0 +1,96 @@
+/**
+ * Microphone audio captured by the backend, as a MediaStream.
+ *
+ * Rust opens the microphone with cpal and sends each 16 kHz mono frame over
+ * an IPC channel as 16-bit PCM. The frames are scheduled back to back into a
+ * MediaStream destination, and its track stands in for the one getUserMedia
+ * would return, so the Pipecat transport sends the backend's audio to the
+ * server. Input gain is applied in Rust.
+ */
+import { tauriAPI } from "./tauri";
+
+/** Sample rate of the frames Rust sends */
+const FRAME_SAMPLE_RATE = 16000;
+
+/** Delay before frames play, absorbing jitter in when they arrive */
+const PLAYOUT_DELAY_SECONDS = 0.06;
+
+// Bumped on every open, so stopping a replaced track leaves the new capture on
+let generation = 0;
+
+/** The device getUserMedia constraints ask for, if any */
+export function requestedDeviceId(
+	audio: boolean | MediaTrackConstraints,
+): string | null {
+	if (typeof audio === "boolean" || !audio.deviceId) return null;
+	const { deviceId } = audio;
+	if (typeof deviceId === "string") return deviceId;
+	if (Array.isArray(deviceId)) return deviceId[0] ?? null;
+	const wanted = deviceId.exact ?? deviceId.ideal;
+	return (Array.isArray(wanted) ? wanted[0] : wanted) ?? null;
+}
+
+/** Rust matches microphones by name, which the webview gives as the label */
+export async function deviceLabel(
+	deviceId: string | null,
+): Promise<string | null> {
+	if (!deviceId) return null;
+	const devices = await navigator.mediaDevices.enumerateDevices();
+	const device = devices.find(
+		(d) => d.kind === "audioinput" && d.deviceId === deviceId,
+	);
+	return device?.label || null;
+}
+
+/** Open the microphone in the backend, replacing any open before */
+export async function openNativeMic(
+	deviceId: string | null,
+): Promise<MediaStream> {
+	const openedGeneration = ++generation;
+	const context = new AudioContext({ sampleRate: FRAME_SAMPLE_RATE });
+	const destination = context.createMediaStreamDestination();
+	let nextStartTime = 0;
+
+	// Frames still in flight when the track stops are dropped
+	let stopped = false;
+
+	const playFrame = (samples: Float32Array) => {
+		if (stopped) return;
+		const buffer = context.createBuffer(1, samples.length, FRAME_SAMPLE_RATE);
+		buffer.copyToChannel(samples, 0);
+		const source = context.createBufferSource();
+		source.buffer = buffer;
+		source.connect(destination);
+		// Start over after the delay when frames stopped or fell behind
+		if (nextStartTime < context.currentTime) {
+			nextStartTime = context.currentTime + PLAYOUT_DELAY_SECONDS;
+		}
+		source.start(nextStartTime);
+		nextStartTime += buffer.duration;
+	};
+
+	try {
+		const name = await tauriAPI.startNativeCapture(
+			await deviceLabel(deviceId),
+			playFrame,
+		);
+		console.log("[Audio] Capturing in the backend from", name);
+	} catch (error) {
+		stopped = true;
+		context.close().catch(() => {});
+		throw error;
+	}
+
+	// Stopping the track releases the microphone and the audio graph
+	const [track] = destination.stream.getAudioTracks();
+	const stopTrack = track.stop.bind(track);
+	track.stop = () => {
+		stopTrack();
+		stopped = true;
+		context.close().catch(() => {});
+		if (openedGeneration === generation) {
+			tauriAPI.stopNativeCapture().catch(() => {});
+		}
+	};
+	return destination.stream;
+}
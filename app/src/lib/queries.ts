@@ -1,13 +1,43 @@
-import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import {
+	keepPreviousData,
+	useInfiniteQuery,
+	useMutation,
+	useQuery,
+	useQueryClient,
+} from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 import { useEffect, useRef } from "react";
 import {
 	type AvailableProvidersData,
+	type BenchmarkSource,
 	type CleanupPromptSections,
 	configAPI,
+	type DictionaryEntry,
+	type DictionaryImportFormat,
+	type ExportFormat,
+	type FeatureFlag,
+	type HidBinding,
+	type HidDevice,
 	type HotkeyConfig,
+	type InsertionMethod,
+	type InsertionRule,
+	type OutputMode,
+	type OutputStyle,
+	type OutputStyleRule,
+	type PaletteAction,
+	type PromptTruncation,
+	type ProxyAuth,
+	type Snippet,
+	type SoundSettings,
+	type SoundTheme,
 	tauriAPI,
+	type TransformRule,
+	type UpdateChannel,
+	type UsagePeriod,
 	validateHotkeyNotDuplicate,
+	type VoiceCommand,
+	type WhisperModel,
+	type WordCorrection,
 } from "./tauri";
 
 type ConnectionState =
@@ -54,9 +84,9 @@ export function useServerUrl() {
 	});
 }
 
-export function useTypeText() {
+export function useInsertDictation() {
 	return useMutation({
-		mutationFn: (text: string) => invoke("type_text", { text }),
+		mutationFn: (text: string) => tauriAPI.insertDictation(text),
 	});
 }
 
@@ -83,11 +113,29 @@ export function useUpdateToggleHotkey() {
 					toggle: settings.toggle_hotkey,
 					hold: settings.hold_hotkey,
 					paste_last: settings.paste_last_hotkey,
+					cycle_history: settings.cycle_history_hotkey,
+					cycle_language: settings.cycle_language_hotkey,
+					pause_resume: settings.pause_resume_hotkey,
+					retry_last: settings.retry_last_hotkey,
+					clipboard_only: settings.clipboard_only_hotkey,
+					cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+					toggle_formatting: settings.toggle_formatting_hotkey,
+					cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+					open_palette: settings.open_palette_hotkey,
+					do_not_disturb: settings.do_not_disturb_hotkey,
 				},
 				"toggle",
 			);
 			if (error) throw new Error(error);
 
+			// Make sure no other application owns the combination
+			const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+			if (!availability.available) {
+				throw new Error(
+					availability.reason ?? "Hotkey is in use by another application",
+				);
+			}
+
 			// Save and re-register
 			await tauriAPI.updateToggleHotkey(hotkey);
 			await tauriAPI.registerShortcuts();
@@ -112,11 +160,29 @@ export function useUpdateHoldHotkey() {
 					toggle: settings.toggle_hotkey,
 					hold: settings.hold_hotkey,
 					paste_last: settings.paste_last_hotkey,
+					cycle_history: settings.cycle_history_hotkey,
+					cycle_language: settings.cycle_language_hotkey,
+					pause_resume: settings.pause_resume_hotkey,
+					retry_last: settings.retry_last_hotkey,
+					clipboard_only: settings.clipboard_only_hotkey,
+					cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+					toggle_formatting: settings.toggle_formatting_hotkey,
+					cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+					open_palette: settings.open_palette_hotkey,
+					do_not_disturb: settings.do_not_disturb_hotkey,
 				},
 				"hold",
 			);
 			if (error) throw new Error(error);
 
+			// Make sure no other application owns the combination
+			const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+			if (!availability.available) {
+				throw new Error(
+					availability.reason ?? "Hotkey is in use by another application",
+				);
+			}
+
 			// Save and re-register
 			await tauriAPI.updateHoldHotkey(hotkey);
 			await tauriAPI.registerShortcuts();
@@ -141,11 +207,29 @@ export function useUpdatePasteLastHotkey() {
 					toggle: settings.toggle_hotkey,
 					hold: settings.hold_hotkey,
 					paste_last: settings.paste_last_hotkey,
+					cycle_history: settings.cycle_history_hotkey,
+					cycle_language: settings.cycle_language_hotkey,
+					pause_resume: settings.pause_resume_hotkey,
+					retry_last: settings.retry_last_hotkey,
+					clipboard_only: settings.clipboard_only_hotkey,
+					cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+					toggle_formatting: settings.toggle_formatting_hotkey,
+					cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+					open_palette: settings.open_palette_hotkey,
+					do_not_disturb: settings.do_not_disturb_hotkey,
 				},
 				"paste_last",
 			);
 			if (error) throw new Error(error);
 
+			// Make sure no other application owns the combination
+			const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+			if (!availability.available) {
+				throw new Error(
+					availability.reason ?? "Hotkey is in use by another application",
+				);
+			}
+
 			// Save and re-register
 			await tauriAPI.updatePasteLastHotkey(hotkey);
 			await tauriAPI.registerShortcuts();
@@ -156,216 +240,2077 @@ export function useUpdatePasteLastHotkey() {
 	});
 }
 
-export function useUpdateSelectedMic() {
+export function useUpdateCycleHistoryHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (micId: string | null) => tauriAPI.updateSelectedMic(micId),
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"cycle_history",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateCycleHistoryHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useUpdateSoundEnabled() {
+export function useUpdateCycleLanguageHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (enabled: boolean) => tauriAPI.updateSoundEnabled(enabled),
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"cycle_language",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateCycleLanguageHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useUpdateAutoMuteAudio() {
+export function useUpdatePauseResumeHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (enabled: boolean) => tauriAPI.updateAutoMuteAudio(enabled),
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"pause_resume",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updatePauseResumeHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useIsAudioMuteSupported() {
-	return useQuery({
-		queryKey: ["audioMuteSupported"],
-		queryFn: () => tauriAPI.isAudioMuteSupported(),
-		staleTime: Number.POSITIVE_INFINITY,
+export function useUpdateRetryLastHotkey() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"retry_last",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateRetryLastHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
 	});
 }
 
-export function useUpdateCleanupPromptSections() {
+export function useUpdateClipboardOnlyHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (sections: CleanupPromptSections | null) =>
-			tauriAPI.updateCleanupPromptSections(sections),
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"clipboard_only",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateClipboardOnlyHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useResetHotkeysToDefaults() {
+export function useUpdateCycleSttProviderHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: async () => {
-			await tauriAPI.resetHotkeysToDefaults();
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"cycle_stt_provider",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateCycleSttProviderHotkey(hotkey);
 			await tauriAPI.registerShortcuts();
 		},
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
-		onError: (error) => {
-			console.error("Reset hotkeys failed:", error);
+	});
+}
+
+export function useUpdateToggleFormattingHotkey() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"toggle_formatting",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateToggleFormattingHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-// History queries and mutations
-export function useHistory(limit?: number) {
-	return useQuery({
-		queryKey: ["history", limit],
-		queryFn: () => tauriAPI.getHistory(limit),
+export function useUpdateCyclePromptPresetHotkey() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"cycle_prompt_preset",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateCyclePromptPresetHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
 	});
 }
 
-export function useAddHistoryEntry() {
+export function useUpdateOpenPaletteHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (text: string) => tauriAPI.addHistoryEntry(text),
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"open_palette",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateOpenPaletteHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
 		onSuccess: () => {
-			queryClient.invalidateQueries({ queryKey: ["history"] });
-			// Notify other windows about history change
-			tauriAPI.emitHistoryChanged();
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useDeleteHistoryEntry() {
+export function useUpdateDoNotDisturbHotkey() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (id: string) => tauriAPI.deleteHistoryEntry(id),
+		// null turns the hotkey off
+		mutationFn: async (hotkey: HotkeyConfig | null) => {
+			if (hotkey) {
+				const settings = await tauriAPI.getSettings();
+
+				const error = validateHotkeyNotDuplicate(
+					hotkey,
+					{
+						toggle: settings.toggle_hotkey,
+						hold: settings.hold_hotkey,
+						paste_last: settings.paste_last_hotkey,
+						cycle_history: settings.cycle_history_hotkey,
+						cycle_language: settings.cycle_language_hotkey,
+						pause_resume: settings.pause_resume_hotkey,
+						retry_last: settings.retry_last_hotkey,
+						clipboard_only: settings.clipboard_only_hotkey,
+						cycle_stt_provider: settings.cycle_stt_provider_hotkey,
+						toggle_formatting: settings.toggle_formatting_hotkey,
+						cycle_prompt_preset: settings.cycle_prompt_preset_hotkey,
+						open_palette: settings.open_palette_hotkey,
+						do_not_disturb: settings.do_not_disturb_hotkey,
+					},
+					"do_not_disturb",
+				);
+				if (error) throw new Error(error);
+
+				const availability = await tauriAPI.testHotkeyAvailability(hotkey);
+				if (!availability.available) {
+					throw new Error(
+						availability.reason ?? "Hotkey is in use by another application",
+					);
+				}
+			}
+
+			// Save and re-register
+			await tauriAPI.updateDoNotDisturbHotkey(hotkey);
+			await tauriAPI.registerShortcuts();
+		},
 		onSuccess: () => {
-			queryClient.invalidateQueries({ queryKey: ["history"] });
-			// Notify other windows about history change
-			tauriAPI.emitHistoryChanged();
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useClearHistory() {
+export function useUpdateHoldLatchEnabled() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: () => tauriAPI.clearHistory(),
+		mutationFn: (enabled: boolean) => tauriAPI.updateHoldLatchEnabled(enabled),
 		onSuccess: () => {
-			queryClient.invalidateQueries({ queryKey: ["history"] });
-			// Notify other windows about history change
-			tauriAPI.emitHistoryChanged();
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-// Config API queries and mutations (FastAPI server)
-export function useDefaultSections() {
-	const { data: serverUrl } = useServerUrl();
+export function useUpdateHoldLatchMaxSeconds() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (seconds: number) =>
+			tauriAPI.updateHoldLatchMaxSeconds(seconds),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
 
-	return useQuery({
-		queryKey: ["defaultSections", serverUrl],
-		queryFn: () => {
-			if (!serverUrl) {
-				throw new Error("Server URL not available");
-			}
-			return configAPI.getDefaultSections(serverUrl);
+export function useUpdateAutoStopSilence() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (seconds: number | null) =>
+			tauriAPI.updateAutoStopSilence(seconds),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
-		staleTime: Number.POSITIVE_INFINITY, // Default prompts never change
-		retry: false, // Don't retry if server not available
-		enabled: !!serverUrl, // Only fetch when server URL is available
 	});
 }
 
-// Provider queries - data comes from RTVI message via Tauri event
+export function useUpdateMaxRecordingSeconds() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (seconds: number) =>
+			tauriAPI.updateMaxRecordingSeconds(seconds),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
 
-/**
- * Hook to set up the available providers event listener.
- * Call this from a component that stays mounted (like App.tsx) to ensure
- * the listener is always active and data is cached properly.
- */
-export function useAvailableProvidersListener() {
+export function useUpdatePreRoll() {
 	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (preRollMs: number) => tauriAPI.updatePreRoll(preRollMs),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
 
-	// Listen for provider data from overlay window (relayed from server via RTVI)
-	useEffect(() => {
-		const unlistenPromise = tauriAPI.onAvailableProviders((data) => {
-			queryClient.setQueryData<AvailableProvidersData>(
-				["availableProviders"],
-				data,
-			);
-		});
+export function useUpdateSequenceTimeout() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (timeoutMs: number) =>
+			tauriAPI.updateSequenceTimeout(timeoutMs),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
 
-		return () => {
-			unlistenPromise.then((unlisten) => unlisten());
-		};
-	}, [queryClient]);
+export function useUpdateMaxPromptTokens() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (maxTokens: number) =>
+			tauriAPI.updateMaxPromptTokens(maxTokens),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
 }
 
-/**
- * Hook to read available providers from the cache.
- * The data is populated by useAvailableProvidersListener which should be
- * called from a parent component that stays mounted.
- */
-export function useAvailableProviders() {
-	return useQuery<AvailableProvidersData | null>({
-		queryKey: ["availableProviders"],
-		queryFn: () => Promise.resolve(null), // No initial fetch, data comes from event
-		staleTime: Number.POSITIVE_INFINITY,
-		enabled: false, // Don't auto-fetch
+export function useUpdatePromptTruncation() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (truncation: PromptTruncation) =>
+			tauriAPI.updatePromptTruncation(truncation),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
 	});
 }
 
-export function useUpdateSTTProvider() {
+export function useUpdateMaxConcurrentTurns() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (provider: string | null) =>
-			tauriAPI.updateSTTProvider(provider),
+		mutationFn: (maxTurns: number) =>
+			tauriAPI.updateMaxConcurrentTurns(maxTurns),
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-export function useUpdateLLMProvider() {
+export function useUpdatePreferredMics() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (provider: string | null) =>
-			tauriAPI.updateLLMProvider(provider),
+		mutationFn: (micIds: string[]) => tauriAPI.updatePreferredMics(micIds),
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-// STT Timeout mutation (local settings)
-export function useUpdateSTTTimeout() {
+export function useUpdateSoundSettings() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (timeoutSeconds: number | null) =>
-			tauriAPI.updateSTTTimeout(timeoutSeconds),
+		mutationFn: (sounds: SoundSettings) => tauriAPI.updateSoundSettings(sounds),
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
 		},
 	});
 }
 
-// Server URL mutation
-export function useUpdateServerUrl() {
+export function useUpdateSoundTheme() {
 	const queryClient = useQueryClient();
 	return useMutation({
-		mutationFn: (url: string) => tauriAPI.updateServerUrl(url),
+		mutationFn: (theme: SoundTheme) => tauriAPI.updateSoundTheme(theme),
 		onSuccess: () => {
 			queryClient.invalidateQueries({ queryKey: ["settings"] });
-			queryClient.invalidateQueries({ queryKey: ["serverUrl"] });
-			// Notify other windows about settings change
+		},
+	});
+}
+
+export function useUpdateAutoMuteAudio() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateAutoMuteAudio(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateAudioDuckPercent() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (percent: number) => tauriAPI.updateAudioDuckPercent(percent),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateLanguage() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (language: string | null) => {
+			await tauriAPI.updateLanguage(language);
+			// Rebuild in-app providers so local Whisper gets the new hint
+			await tauriAPI.refreshSttProvider();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// The overlay passes the hint to the server, Rust updates the tray
 			tauriAPI.emitSettingsChanged();
 		},
 	});
 }
+
+export function useUpdateAutoDetectLanguage() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (enabled: boolean) => {
+			await tauriAPI.updateAutoDetectLanguage(enabled);
+			// Rebuild in-app providers so local Whisper gets the new hint
+			await tauriAPI.refreshSttProvider();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// The overlay passes the hint to the server, Rust updates the tray
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateDictationLanguages() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (languages: string[]) => {
+			await tauriAPI.updateDictationLanguages(languages);
+			// Rebuild in-app providers so local Whisper gets the new hint
+			await tauriAPI.refreshSttProvider();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// The overlay passes the hint to the server, Rust updates the tray
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateMicGain() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (gain: number) => tauriAPI.updateMicGain(gain),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// The overlay applies the gain to the live microphone
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateOverlayScale() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (scale: number) => tauriAPI.updateOverlayScale(scale),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// Rust zooms the overlay when settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateShowPartialTranscripts() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateShowPartialTranscripts(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useResetOverlayPosition() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.resetOverlayPosition(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useResetWindowState() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.resetWindowState(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateNativeAudioCapture() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateNativeAudioCapture(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useCaptureFormats(deviceLabel: string | null) {
+	return useQuery({
+		queryKey: ["captureFormats", deviceLabel],
+		queryFn: () => tauriAPI.listCaptureFormats(deviceLabel),
+	});
+}
+
+export function useUpdateCaptureFormat() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			deviceLabel,
+			sampleRate,
+			channels,
+		}: {
+			deviceLabel: string | null;
+			sampleRate: number | null;
+			channels: number | null;
+		}) => tauriAPI.updateCaptureFormat(deviceLabel, sampleRate, channels),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useHidDevices() {
+	return useQuery({
+		queryKey: ["hidDevices"],
+		queryFn: () => tauriAPI.listHidDevices(),
+	});
+}
+
+export function useDetectHidButton() {
+	return useMutation({
+		mutationFn: (device: HidDevice) => tauriAPI.detectHidButton(device),
+	});
+}
+
+export function useUpdateHidTriggers() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (bindings: HidBinding[]) =>
+			tauriAPI.updateHidTriggers(bindings),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateOpusBitrate() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (kbps: number) => tauriAPI.updateOpusBitrate(kbps),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateNoiseSuppressionEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateNoiseSuppressionEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateEchoCancellationEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateEchoCancellationEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateAgcEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateAgcEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateInsertionMethod() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (method: InsertionMethod) =>
+			tauriAPI.updateInsertionMethod(method),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateInsertionRules() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (rules: InsertionRule[]) =>
+			tauriAPI.updateInsertionRules(rules),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateBlockedApps() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (apps: string[]) => tauriAPI.updateBlockedApps(apps),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateClipboardRestoreDelay() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (delayMs: number) =>
+			tauriAPI.updateClipboardRestoreDelay(delayMs),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateMaxInsertionChars() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (maxChars: number) =>
+			tauriAPI.updateMaxInsertionChars(maxChars),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateOutputMode() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (mode: OutputMode) => tauriAPI.updateOutputMode(mode),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateCopyAfterInsert() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateCopyAfterInsert(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useNotesFilePath() {
+	return useQuery({
+		queryKey: ["notesFilePath"],
+		queryFn: () => tauriAPI.getNotesFilePath(),
+	});
+}
+
+export function useUpdateNotesEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateNotesEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// Rust ticks the tray item when settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateNotesFilePath() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (path: string | null) => tauriAPI.updateNotesFilePath(path),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			queryClient.invalidateQueries({ queryKey: ["notesFilePath"] });
+		},
+	});
+}
+
+export function useUpdateNotesAlsoInsert() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateNotesAlsoInsert(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateNotesIncludeApp() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateNotesIncludeApp(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateVoiceCommandsEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateVoiceCommandsEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateVoiceCommands() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (commands: VoiceCommand[]) =>
+			tauriAPI.updateVoiceCommands(commands),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateOutputStyle() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (style: OutputStyle) => tauriAPI.updateOutputStyle(style),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateOutputStyleRules() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (rules: OutputStyleRule[]) =>
+			tauriAPI.updateOutputStyleRules(rules),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateTextTransforms() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (rules: TransformRule[]) =>
+			tauriAPI.updateTextTransforms(rules),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useTestTextTransform() {
+	return useMutation({
+		mutationFn: ({ rule, sample }: { rule: TransformRule; sample: string }) =>
+			tauriAPI.testTextTransform(rule, sample),
+	});
+}
+
+export function useTestTextTransforms() {
+	return useMutation({
+		mutationFn: ({
+			rules,
+			sample,
+		}: {
+			rules: TransformRule[];
+			sample: string;
+		}) => tauriAPI.testTextTransforms(rules, sample),
+	});
+}
+
+export function useUpdateArchiveRecordings() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateArchiveRecordings(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateKeepLastRecording() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateKeepLastRecording(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateRetrySttProvider() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (provider: string | null) =>
+			tauriAPI.updateRetrySttProvider(provider),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateRecordingMaxCount() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (maxCount: number) =>
+			tauriAPI.updateRecordingMaxCount(maxCount),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateRecordingMaxDays() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (maxDays: number) => tauriAPI.updateRecordingMaxDays(maxDays),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function usePendingTranscriptions() {
+	return useQuery({
+		queryKey: ["pendingTranscriptions"],
+		queryFn: () => tauriAPI.listPendingTranscriptions(),
+	});
+}
+
+export function useRetryPendingTranscriptions() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.retryPendingTranscriptions(),
+		onSettled: () => {
+			queryClient.invalidateQueries({ queryKey: ["pendingTranscriptions"] });
+		},
+	});
+}
+
+export function useDataFileIssues() {
+	return useQuery({
+		queryKey: ["dataFileIssues"],
+		queryFn: () => tauriAPI.getDataFileIssues(),
+	});
+}
+
+export function useDismissDataFileIssues() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.dismissDataFileIssues(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["dataFileIssues"] });
+		},
+	});
+}
+
+export function useRecordings() {
+	return useQuery({
+		queryKey: ["recordings"],
+		queryFn: () => tauriAPI.listRecordings(),
+	});
+}
+
+export function useDeleteRecording() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.deleteRecording(id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["recordings"] });
+		},
+	});
+}
+
+export function useIsAccessibilityInsertionSupported() {
+	return useQuery({
+		queryKey: ["accessibilityInsertionSupported"],
+		queryFn: () => tauriAPI.isAccessibilityInsertionSupported(),
+		staleTime: Number.POSITIVE_INFINITY,
+	});
+}
+
+export function useInsertionDiagnostics() {
+	return useQuery({
+		queryKey: ["insertionDiagnostics"],
+		queryFn: () => tauriAPI.getInsertionDiagnostics(),
+		staleTime: Number.POSITIVE_INFINITY,
+	});
+}
+
+export function useIsAudioMuteSupported() {
+	return useQuery({
+		queryKey: ["audioMuteSupported"],
+		queryFn: () => tauriAPI.isAudioMuteSupported(),
+		staleTime: Number.POSITIVE_INFINITY,
+	});
+}
+
+export function useUpdateCleanupPromptSections() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (sections: CleanupPromptSections | null) =>
+			tauriAPI.updateCleanupPromptSections(sections),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// The active preset is the one matching the prompt
+			queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+		},
+	});
+}
+
+export function usePromptPresets() {
+	return useQuery({
+		queryKey: ["promptPresets"],
+		queryFn: () => tauriAPI.listPromptPresets(),
+	});
+}
+
+export function useSavePromptPreset() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({ name, id }: { name: string; id?: string }) =>
+			tauriAPI.savePromptPreset(name, id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+		},
+	});
+}
+
+export function useRenamePromptPreset() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({ id, name }: { id: string; name: string }) =>
+			tauriAPI.renamePromptPreset(id, name),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+		},
+	});
+}
+
+export function useDeletePromptPreset() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.deletePromptPreset(id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+		},
+	});
+}
+
+export function useApplyPromptPreset() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.applyPromptPreset(id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+		},
+	});
+}
+
+export function useExportPromptPresets() {
+	return useMutation({
+		mutationFn: (path?: string) => tauriAPI.exportPromptPresets(path),
+	});
+}
+
+export function useImportPromptPresets() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (path: string) => tauriAPI.importPromptPresets(path),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["promptPresets"] });
+		},
+	});
+}
+
+export function useResetHotkeysToDefaults() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async () => {
+			await tauriAPI.resetHotkeysToDefaults();
+			await tauriAPI.registerShortcuts();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+		onError: (error) => {
+			console.error("Reset hotkeys failed:", error);
+		},
+	});
+}
+
+// History queries and mutations
+export function useHistory(query: string) {
+	return useInfiniteQuery({
+		queryKey: ["history", query],
+		queryFn: ({ pageParam }) => tauriAPI.getHistory({ page: pageParam, query }),
+		initialPageParam: 0,
+		getNextPageParam: (lastPage) =>
+			(lastPage.page + 1) * lastPage.page_size < lastPage.total
+				? lastPage.page + 1
+				: undefined,
+		// Keep showing the current results while a new search loads
+		placeholderData: keepPreviousData,
+	});
+}
+
+export function useAddHistoryEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			text,
+			durationMs,
+			rawText,
+		}: {
+			text: string;
+			durationMs?: number;
+			rawText?: string;
+		}) => tauriAPI.addHistoryEntry(text, durationMs, rawText),
+		onSuccess: () => {
+			// Other windows are notified by Rust once the turn is recorded
+			queryClient.invalidateQueries({ queryKey: ["history"] });
+		},
+	});
+}
+
+export function useReRunWithCurrentSettings() {
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.reRunWithCurrentSettings(id),
+	});
+}
+
+export function useTurnDiff(id: string | null) {
+	return useQuery({
+		queryKey: ["turnDiff", id],
+		queryFn: () => {
+			if (!id) {
+				throw new Error("No history entry selected");
+			}
+			return tauriAPI.getTurnDiff(id);
+		},
+		staleTime: Number.POSITIVE_INFINITY, // Refetched when history changes
+		retry: false,
+		enabled: id !== null,
+	});
+}
+
+export function useCorrectLastEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			text,
+			addToDictionary,
+		}: {
+			text: string;
+			addToDictionary: boolean;
+		}) => tauriAPI.correctLastEntry(text, addToDictionary),
+		onSuccess: () => {
+			// Other windows are notified by Rust once the entry is updated
+			queryClient.invalidateQueries({ queryKey: ["history"] });
+		},
+	});
+}
+
+export function useDictionaryEntries() {
+	return useQuery({
+		queryKey: ["dictionaryEntries"],
+		queryFn: () => tauriAPI.listDictionaryEntries(),
+	});
+}
+
+// Rust renders the entries into the prompt settings and tells the overlay
+export function useAddDictionaryEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			spoken,
+			written,
+			caseSensitive,
+		}: {
+			spoken: string;
+			written: string;
+			caseSensitive: boolean;
+		}) => tauriAPI.addDictionaryEntry(spoken, written, caseSensitive),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["dictionaryEntries"] });
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateDictionaryEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (entry: DictionaryEntry) =>
+			tauriAPI.updateDictionaryEntry(entry),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["dictionaryEntries"] });
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useRemoveDictionaryEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.removeDictionaryEntry(id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["dictionaryEntries"] });
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useImportDictionary() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			path,
+			format,
+			dryRun,
+		}: {
+			path: string;
+			format: DictionaryImportFormat;
+			dryRun: boolean;
+		}) => tauriAPI.importDictionary(path, format, dryRun),
+		onSuccess: (result) => {
+			if (result.dry_run) return;
+			queryClient.invalidateQueries({ queryKey: ["dictionaryEntries"] });
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useSnippets() {
+	return useQuery({
+		queryKey: ["snippets"],
+		queryFn: () => tauriAPI.listSnippets(),
+	});
+}
+
+export function useAddSnippet() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			trigger,
+			template,
+		}: {
+			trigger: string;
+			template: string;
+		}) => tauriAPI.addSnippet(trigger, template),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["snippets"] });
+		},
+	});
+}
+
+export function useUpdateSnippet() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (snippet: Snippet) => tauriAPI.updateSnippet(snippet),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["snippets"] });
+		},
+	});
+}
+
+export function useRemoveSnippet() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.removeSnippet(id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["snippets"] });
+		},
+	});
+}
+
+export function useDictionarySuggestions() {
+	return useQuery({
+		queryKey: ["dictionarySuggestions"],
+		queryFn: () => tauriAPI.getDictionarySuggestions(),
+	});
+}
+
+export function useAcceptDictionarySuggestion() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (suggestion: WordCorrection) => {
+			await tauriAPI.addDictionaryEntry(
+				suggestion.heard,
+				suggestion.corrected,
+				false,
+			);
+			await tauriAPI.resolveDictionarySuggestion(suggestion);
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["dictionaryEntries"] });
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			queryClient.invalidateQueries({ queryKey: ["dictionarySuggestions"] });
+		},
+	});
+}
+
+export function useDismissDictionarySuggestion() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (suggestion: WordCorrection) =>
+			tauriAPI.resolveDictionarySuggestion(suggestion),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["dictionarySuggestions"] });
+		},
+	});
+}
+
+export function useDeleteHistoryEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (id: string) => tauriAPI.deleteHistoryEntry(id),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["history"] });
+			// Notify other windows about history change
+			tauriAPI.emitHistoryChanged();
+		},
+	});
+}
+
+export function usePinnedHistory() {
+	return useQuery({
+		queryKey: ["history", "pinned"],
+		queryFn: () => tauriAPI.listPinned(),
+	});
+}
+
+export function usePinHistoryEntry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({ id, pinned }: { id: string; pinned: boolean }) =>
+			tauriAPI.pinHistoryEntry(id, pinned),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["history"] });
+		},
+	});
+}
+
+export function useClearHistory() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.clearHistory(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["history"] });
+			// Notify other windows about history change
+			tauriAPI.emitHistoryChanged();
+		},
+	});
+}
+
+// Config API queries and mutations (FastAPI server)
+export function useDefaultSections() {
+	const { data: serverUrl } = useServerUrl();
+
+	return useQuery({
+		queryKey: ["defaultSections", serverUrl],
+		queryFn: () => {
+			if (!serverUrl) {
+				throw new Error("Server URL not available");
+			}
+			return configAPI.getDefaultSections(serverUrl);
+		},
+		staleTime: Number.POSITIVE_INFINITY, // Default prompts never change
+		retry: false, // Don't retry if server not available
+		enabled: !!serverUrl, // Only fetch when server URL is available
+	});
+}
+
+// Provider queries - data comes from RTVI message via Tauri event
+
+/**
+ * Hook to set up the available providers event listener.
+ * Call this from a component that stays mounted (like App.tsx) to ensure
+ * the listener is always active and data is cached properly.
+ */
+export function useAvailableProvidersListener() {
+	const queryClient = useQueryClient();
+
+	// Listen for provider data from overlay window (relayed from server via RTVI)
+	useEffect(() => {
+		const unlistenPromise = tauriAPI.onAvailableProviders((data) => {
+			queryClient.setQueryData<AvailableProvidersData>(
+				["availableProviders"],
+				data,
+			);
+		});
+
+		return () => {
+			unlistenPromise.then((unlisten) => unlisten());
+		};
+	}, [queryClient]);
+}
+
+/**
+ * Hook to read available providers from the cache.
+ * The data is populated by useAvailableProvidersListener which should be
+ * called from a parent component that stays mounted.
+ */
+export function useAvailableProviders() {
+	return useQuery<AvailableProvidersData | null>({
+		queryKey: ["availableProviders"],
+		queryFn: () => Promise.resolve(null), // No initial fetch, data comes from event
+		staleTime: Number.POSITIVE_INFINITY,
+		enabled: false, // Don't auto-fetch
+	});
+}
+
+export function useUpdateSTTProvider() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (provider: string | null) => {
+			await tauriAPI.updateSTTProvider(provider);
+			// Rebuild the Rust-side provider so in-app backends pick up the change
+			await tauriAPI.refreshSttProvider();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateSTTFallbackProvider() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (provider: string | null) => {
+			await tauriAPI.updateSTTFallbackProvider(provider);
+			await tauriAPI.refreshSttProvider();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateWhisperModel() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (model: WhisperModel) => {
+			await tauriAPI.updateWhisperModel(model);
+			await tauriAPI.refreshSttProvider();
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useWhisperModels() {
+	return useQuery({
+		queryKey: ["whisperModels"],
+		queryFn: () => tauriAPI.listWhisperModels(),
+	});
+}
+
+export function useDownloadWhisperModel() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (model: WhisperModel) => tauriAPI.downloadWhisperModel(model),
+		onSettled: () => {
+			queryClient.invalidateQueries({ queryKey: ["whisperModels"] });
+		},
+	});
+}
+
+export function useIsUpdaterAvailable() {
+	return useQuery({
+		queryKey: ["updaterAvailable"],
+		queryFn: () => tauriAPI.isUpdaterAvailable(),
+		staleTime: Number.POSITIVE_INFINITY,
+	});
+}
+
+export function useUpdateTelemetryEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateTelemetryEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			queryClient.invalidateQueries({ queryKey: ["telemetry"] });
+		},
+	});
+}
+
+export function useUpdateRemoteControlEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateRemoteControlEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// Rust starts or stops the control server when settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateRemoteControlPort() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (port: number) => tauriAPI.updateRemoteControlPort(port),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateApiEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateApiEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// Rust starts or stops the API server when settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateApiPort() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (port: number) => tauriAPI.updateApiPort(port),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateDeepLinksEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateDeepLinksEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useRemoteControlToken(enabled: boolean) {
+	return useQuery({
+		queryKey: ["remoteControlToken"],
+		queryFn: () => tauriAPI.getRemoteControlToken(),
+		enabled,
+	});
+}
+
+export function useRegenerateRemoteControlToken() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.regenerateRemoteControlToken(),
+		onSuccess: (token) => {
+			queryClient.setQueryData(["remoteControlToken"], token);
+		},
+	});
+}
+
+export function useUpdateUpdateChannel() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (channel: UpdateChannel) =>
+			tauriAPI.updateUpdateChannel(channel),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useCheckForUpdates() {
+	return useMutation({
+		mutationFn: () => tauriAPI.checkForUpdates(),
+	});
+}
+
+export function useInstallUpdate() {
+	return useMutation({
+		mutationFn: () => tauriAPI.installUpdate(),
+	});
+}
+
+export function useDeleteWhisperModel() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (model: WhisperModel) => tauriAPI.deleteWhisperModel(model),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["whisperModels"] });
+		},
+	});
+}
+
+export function useUpdateLLMProvider() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (provider: string | null) =>
+			tauriAPI.updateLLMProvider(provider),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+// STT Timeout mutation (local settings)
+export function useUpdateSTTTimeout() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (timeoutSeconds: number | null) =>
+			tauriAPI.updateSTTTimeout(timeoutSeconds),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+// Short utterance fast path mutation (local settings)
+export function useUpdateFormattingEnabled() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) => tauriAPI.updateFormattingEnabled(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// Rust ticks the tray item when settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateShortUtteranceMaxWords() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (maxWords: number | null) =>
+			tauriAPI.updateShortUtteranceMaxWords(maxWords),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+// Feature flag queries and mutations
+export function useFeatureFlags() {
+	return useQuery({
+		queryKey: ["featureFlags"],
+		queryFn: () => tauriAPI.listFeatureFlags(),
+	});
+}
+
+export function useSetFeatureFlag() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({ flag, enabled }: { flag: FeatureFlag; enabled: boolean }) =>
+			tauriAPI.setFeatureFlag(flag, enabled),
+		onSuccess: (flags) => {
+			queryClient.setQueryData(["featureFlags"], flags);
+			// Notify other windows about settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+// Server URL mutation
+export function useUpdateServerUrl() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (url: string) => tauriAPI.updateServerUrl(url),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			queryClient.invalidateQueries({ queryKey: ["serverUrl"] });
+			// Notify other windows about settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateFallbackServerUrls() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (urls: string[]) => tauriAPI.updateFallbackServerUrls(urls),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+			// Notify other windows about settings change
+			tauriAPI.emitSettingsChanged();
+		},
+	});
+}
+
+export function useUpdateAllowSelfSignedCerts() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (enabled: boolean) =>
+			tauriAPI.updateAllowSelfSignedCerts(enabled),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdatePinnedCertSha256() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: (fingerprint: string | null) =>
+			tauriAPI.updatePinnedCertSha256(fingerprint),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+export function useUpdateProxySettings() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			proxyUrl,
+			proxyAuth,
+		}: {
+			proxyUrl: string | null;
+			proxyAuth: ProxyAuth | null;
+		}) => tauriAPI.updateProxySettings(proxyUrl, proxyAuth),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["settings"] });
+		},
+	});
+}
+
+// Usage statistics queries and mutations
+export function useUsageStats(period: UsagePeriod) {
+	return useQuery({
+		queryKey: ["usageStats", period],
+		queryFn: () => tauriAPI.getUsageStats(period),
+	});
+}
+
+export function useResetUsageStats() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.resetUsageStats(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["usageStats"] });
+		},
+	});
+}
+
+export function useTelemetry() {
+	return useQuery({
+		queryKey: ["telemetry"],
+		queryFn: () => tauriAPI.getTelemetry(),
+	});
+}
+
+export function usePurgeTelemetry() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.purgeTelemetry(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["telemetry"] });
+		},
+	});
+}
+
+export function useExportData() {
+	return useMutation({
+		mutationFn: ({
+			format,
+			range,
+		}: {
+			format: ExportFormat;
+			range: UsagePeriod;
+		}) => tauriAPI.exportData(format, range),
+	});
+}
+
+export function useGenerateSupportBundle() {
+	return useMutation({
+		mutationFn: () => tauriAPI.generateSupportBundle(),
+	});
+}
+
+export function useRunDiagnostics() {
+	return useMutation({
+		mutationFn: () => tauriAPI.runDiagnostics(),
+	});
+}
+
+export function useRunLatencyBenchmark() {
+	return useMutation({
+		mutationFn: (source: BenchmarkSource) =>
+			tauriAPI.runLatencyBenchmark(source),
+	});
+}
+
+// Server auth token queries and mutations
+export function useAuthStatus() {
+	return useQuery({
+		queryKey: ["authStatus"],
+		queryFn: () => tauriAPI.getAuthStatus(),
+	});
+}
+
+export function useSetAuthToken() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: ({
+			accessToken,
+			refreshToken,
+		}: {
+			accessToken: string;
+			refreshToken: string | null;
+		}) => tauriAPI.setAuthToken(accessToken, refreshToken, null),
+		onSuccess: (status) => {
+			queryClient.setQueryData(["authStatus"], status);
+		},
+	});
+}
+
+export function useClearAuthToken() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.clearAuthToken(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["authStatus"] });
+		},
+	});
+}
+
+// Do-not-disturb queries and mutations
+/** Whether dictation is paused, following the tray and hotkeys */
+export function useDictationPaused() {
+	const queryClient = useQueryClient();
+
+	useEffect(() => {
+		const unlistenPromise = tauriAPI.onDictationPausedChanged((paused) => {
+			queryClient.setQueryData(["dictationPaused"], paused);
+		});
+
+		return () => {
+			unlistenPromise.then((unlisten) => unlisten());
+		};
+	}, [queryClient]);
+
+	return useQuery({
+		queryKey: ["dictationPaused"],
+		queryFn: () => tauriAPI.getDictationPaused(),
+	});
+}
+
+export function useSwitchDictationPaused() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.switchDictationPaused(),
+		onSuccess: (paused) => {
+			queryClient.setQueryData(["dictationPaused"], paused);
+		},
+	});
+}
+
+// Command palette queries and mutations
+export function usePaletteItems(query: string) {
+	return useQuery({
+		queryKey: ["paletteItems", query],
+		queryFn: () => tauriAPI.listPaletteItems(query),
+	});
+}
+
+/** Run a palette item's action; pasting hides the palette first */
+export function useRunPaletteAction() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: async (action: PaletteAction) => {
+			switch (action.kind) {
+				case "paste_history":
+					return tauriAPI.pasteFromPalette(action.index);
+				case "paste_pinned":
+					return tauriAPI.pastePinnedFromPalette(action.id);
+				case "switch_stt_provider":
+					await tauriAPI.selectSttProvider(action.value);
+					return;
+				case "apply_prompt_preset":
+					await tauriAPI.applyPromptPreset(action.id);
+					return;
+				case "toggle_formatting":
+					await tauriAPI.switchFormatting();
+					return;
+			}
+		},
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["paletteItems"] });
+		},
+	});
+}
+
+// Session transcript queries and mutations
+export function useSessionTranscript() {
+	return useQuery({
+		queryKey: ["sessionTranscript"],
+		queryFn: () => tauriAPI.getSessionTranscript(),
+	});
+}
+
+export function useResetSessionTranscript() {
+	const queryClient = useQueryClient();
+	return useMutation({
+		mutationFn: () => tauriAPI.resetSessionTranscript(),
+		onSuccess: () => {
+			queryClient.invalidateQueries({ queryKey: ["sessionTranscript"] });
+		},
+	});
+}
+
+export function useExportSessionTranscript() {
+	return useMutation({
+		mutationFn: () => tauriAPI.exportSessionTranscript(),
+	});
+}
@@ -1,9 +1,24 @@
 import { describe, expect, it } from "vitest";
 import {
+	activePreferredMic,
+	clampMicGain,
 	createHotkeyDuplicateSchema,
+	DEFAULT_MIC_GAIN,
+	describeError,
+	dictationLanguageHint,
+	dictionaryImportFormat,
 	type HotkeyConfig,
 	HotkeyConfigSchema,
+	hotkeyConflictsWith,
 	hotkeyIsSameAs,
+	isAppError,
+	keyLabelForLayout,
+	keyPositionCode,
+	MAX_MIC_GAIN,
+	MIN_MIC_GAIN,
+	preferredMicsFromStored,
+	supportedChannelCounts,
+	supportedSampleRates,
 	validateHotkeyNotDuplicate,
 } from "./tauri";
 
@@ -32,6 +47,35 @@ describe("HotkeyConfigSchema", () => {
 		expect(result.success).toBe(true);
 	});
 
+	it("accepts a double-tap trigger with an interval", () => {
+		const result = HotkeyConfigSchema.safeParse({
+			modifiers: [],
+			key: "ctrl",
+			trigger: "double_tap",
+			tap_interval_ms: 300,
+		});
+		expect(result.success).toBe(true);
+	});
+
+	it("rejects a non-positive tap interval", () => {
+		const result = HotkeyConfigSchema.safeParse({
+			modifiers: [],
+			key: "ctrl",
+			trigger: "double_tap",
+			tap_interval_ms: 0,
+		});
+		expect(result.success).toBe(false);
+	});
+
+	it("accepts a hotkey bound by key position", () => {
+		const result = HotkeyConfigSchema.safeParse({
+			modifiers: ["ctrl", "alt"],
+			key: "Q",
+			physical: true,
+		});
+		expect(result.success).toBe(true);
+	});
+
 	it("rejects non-string modifiers", () => {
 		const result = HotkeyConfigSchema.safeParse({
 			modifiers: [123],
@@ -83,6 +127,103 @@ describe("hotkeyIsSameAs", () => {
 		const b: HotkeyConfig = { modifiers: ["ctrl"], key: "Space" };
 		expect(hotkeyIsSameAs(a, b)).toBe(false);
 	});
+
+	it("treats a missing trigger as keyboard", () => {
+		const a: HotkeyConfig = { modifiers: ["ctrl"], key: "Space" };
+		const b: HotkeyConfig = {
+			modifiers: ["ctrl"],
+			key: "Space",
+			trigger: "keyboard",
+		};
+		expect(hotkeyIsSameAs(a, b)).toBe(true);
+	});
+
+	it("returns false for different triggers", () => {
+		const a: HotkeyConfig = { modifiers: [], key: "Mouse4", trigger: "mouse" };
+		const b: HotkeyConfig = { modifiers: [], key: "Mouse4" };
+		expect(hotkeyIsSameAs(a, b)).toBe(false);
+	});
+
+	it("compares the second step of sequences", () => {
+		const a: HotkeyConfig = {
+			modifiers: ["ctrl", "alt"],
+			key: "D",
+			then: { modifiers: [], key: "t" },
+		};
+		const b: HotkeyConfig = {
+			modifiers: ["alt", "ctrl"],
+			key: "d",
+			then: { modifiers: [], key: "T" },
+		};
+		expect(hotkeyIsSameAs(a, b)).toBe(true);
+		const c: HotkeyConfig = { ...b, then: { modifiers: [], key: "P" } };
+		expect(hotkeyIsSameAs(a, c)).toBe(false);
+		expect(hotkeyIsSameAs(a, { ...b, then: undefined })).toBe(false);
+	});
+});
+
+describe("hotkeyConflictsWith", () => {
+	const sequence: HotkeyConfig = {
+		modifiers: ["ctrl", "alt"],
+		key: "D",
+		then: { modifiers: [], key: "T" },
+	};
+
+	it("conflicts when a shortcut is the first step of a sequence", () => {
+		const plain: HotkeyConfig = { modifiers: ["ctrl", "alt"], key: "D" };
+		expect(hotkeyConflictsWith(plain, sequence)).toBe(true);
+		expect(hotkeyConflictsWith(sequence, plain)).toBe(true);
+	});
+
+	it("allows sequences that share a first step", () => {
+		const other: HotkeyConfig = {
+			...sequence,
+			then: { modifiers: [], key: "P" },
+		};
+		expect(hotkeyConflictsWith(sequence, other)).toBe(false);
+	});
+
+	it("ignores first steps of non-keyboard triggers", () => {
+		const doubleTap: HotkeyConfig = {
+			modifiers: ["ctrl", "alt"],
+			key: "D",
+			trigger: "double_tap",
+		};
+		expect(hotkeyConflictsWith(doubleTap, sequence)).toBe(false);
+	});
+});
+
+describe("keyPositionCode", () => {
+	it("names letters and digits as KeyboardEvent codes", () => {
+		expect(keyPositionCode("Q")).toBe("KeyQ");
+		expect(keyPositionCode("q")).toBe("KeyQ");
+		expect(keyPositionCode("1")).toBe("Digit1");
+	});
+
+	it("keeps other keys, which are already codes", () => {
+		expect(keyPositionCode("Semicolon")).toBe("Semicolon");
+		expect(keyPositionCode("Space")).toBe("Space");
+		expect(keyPositionCode("F1")).toBe("F1");
+	});
+});
+
+describe("keyLabelForLayout", () => {
+	const azerty = new Map([
+		["KeyQ", "a"],
+		["Semicolon", "m"],
+		["Digit1", "&"],
+	]);
+
+	it("shows what the layout types at the position", () => {
+		expect(keyLabelForLayout("Q", azerty)).toBe("A");
+		expect(keyLabelForLayout("Semicolon", azerty)).toBe("M");
+		expect(keyLabelForLayout("1", azerty)).toBe("&");
+	});
+
+	it("falls back to the key name", () => {
+		expect(keyLabelForLayout("Space", azerty)).toBe("Space");
+		expect(keyLabelForLayout("Q", null)).toBe("Q");
+	});
 });
 
 describe("createHotkeyDuplicateSchema", () => {
@@ -124,6 +265,16 @@ describe("createHotkeyDuplicateSchema", () => {
 		}
 	});
 
+	it("rejects a sequence starting with another type's hotkey", () => {
+		const schema = createHotkeyDuplicateSchema(allHotkeys, "toggle");
+		const result = schema.safeParse({
+			modifiers: ["ctrl", "alt"],
+			key: "Period",
+			then: { modifiers: [], key: "T" },
+		});
+		expect(result.success).toBe(false);
+	});
+
 	it("rejects a hotkey that conflicts with paste_last", () => {
 		const schema = createHotkeyDuplicateSchema(allHotkeys, "hold");
 		const result = schema.safeParse({
@@ -179,4 +330,135 @@ describe("validateHotkeyNotDuplicate", () => {
 		);
 		expect(result).toBe("This shortcut is already used for the hold hotkey");
 	});
+
+	it("ignores optional hotkeys that are off", () => {
+		const result = validateHotkeyNotDuplicate(
+			{ modifiers: ["ctrl", "alt"], key: "Comma" },
+			{ ...allHotkeys, cycle_history: null },
+			"toggle",
+		);
+		expect(result).toBeNull();
+	});
+
+	it("rejects a hotkey used to cycle history", () => {
+		const result = validateHotkeyNotDuplicate(
+			{ modifiers: ["ctrl", "alt"], key: "Comma" },
+			{
+				...allHotkeys,
+				cycle_history: { modifiers: ["ctrl", "alt"], key: "Comma" },
+			},
+			"toggle",
+		);
+		expect(result).toBe(
+			"This shortcut is already used for the cycle history hotkey",
+		);
+	});
+});
+
+describe("clampMicGain", () => {
+	it("keeps gains within range unchanged", () => {
+		expect(clampMicGain(1.5)).toBe(1.5);
+	});
+
+	it("clamps gains outside the supported range", () => {
+		expect(clampMicGain(0)).toBe(MIN_MIC_GAIN);
+		expect(clampMicGain(10)).toBe(MAX_MIC_GAIN);
+	});
+
+	it("falls back to the default for invalid gains", () => {
+		expect(clampMicGain(Number.NaN)).toBe(DEFAULT_MIC_GAIN);
+	});
+});
+
+describe("dictationLanguageHint", () => {
+	it("sends the chosen language", () => {
+		expect(
+			dictationLanguageHint({ language: " DE ", auto_detect_language: false }),
+		).toBe("de");
+	});
+
+	it("sends no hint when detecting automatically", () => {
+		expect(
+			dictationLanguageHint({ language: "fr", auto_detect_language: true }),
+		).toBeNull();
+	});
+
+	it("ignores missing or unknown languages", () => {
+		expect(
+			dictationLanguageHint({ language: null, auto_detect_language: false }),
+		).toBeNull();
+		expect(
+			dictationLanguageHint({ language: "xx", auto_detect_language: false }),
+		).toBeNull();
+	});
+});
+
+describe("dictionaryImportFormat", () => {
+	it("reads .csv files as CSV", () => {
+		expect(dictionaryImportFormat("/home/me/Terms.CSV")).toBe("csv");
+	});
+
+	it("reads other files as text", () => {
+		expect(dictionaryImportFormat("/home/me/terms.txt")).toBe("text");
+		expect(dictionaryImportFormat("/home/me/terms")).toBe("text");
+	});
+});
+
+describe("describeError", () => {
+	const appError = {
+		code: "settings_io",
+		kind: "io",
+		message: "Failed to save settings: disk full",
+		context: { action: "save settings", reason: "disk full" },
+	};
+
+	it("recognizes structured command errors", () => {
+		expect(isAppError(appError)).toBe(true);
+		expect(isAppError("Failed to save settings")).toBe(false);
+		expect(isAppError(null)).toBe(false);
+	});
+
+	it("uses the message of structured errors and exceptions", () => {
+		expect(describeError(appError)).toBe("Failed to save settings: disk full");
+		expect(describeError(new Error("Key is required"))).toBe(
+			"Key is required",
+		);
+		expect(describeError("plain message")).toBe("plain message");
+	});
+});
+
+describe("capture formats", () => {
+	const formats = [
+		{ channels: 2, min_sample_rate: 44100, max_sample_rate: 48000 },
+		{ channels: 1, min_sample_rate: 8000, max_sample_rate: 16000 },
+	];
+
+	it("offers the rates some format supports", () => {
+		expect(supportedSampleRates(formats, null)).toEqual([16000, 44100, 48000]);
+		expect(supportedSampleRates(formats, 2)).toEqual([44100, 48000]);
+		expect(supportedSampleRates(formats, 4)).toEqual([]);
+	});
+
+	it("offers the channel counts supported at a rate, fewest first", () => {
+		expect(supportedChannelCounts(formats, null)).toEqual([1, 2]);
+		expect(supportedChannelCounts(formats, 48000)).toEqual([2]);
+		expect(supportedChannelCounts(formats, 96000)).toEqual([]);
+	});
+});
+
+describe("preferred microphones", () => {
+	it("uses the first preferred microphone that is plugged in", () => {
+		const devices = ["default", "built-in", "usb"];
+		expect(activePreferredMic(["headset", "usb", "built-in"], devices)).toBe(
+			"usb",
+		);
+		expect(activePreferredMic(["headset"], devices)).toBeNull();
+		expect(activePreferredMic([], devices)).toBeNull();
+	});
+
+	it("migrates the microphone selected before the list", () => {
+		expect(preferredMicsFromStored(undefined, "usb")).toEqual(["usb"]);
+		expect(preferredMicsFromStored(undefined, null)).toEqual([]);
+		expect(preferredMicsFromStored(["headset"], "usb")).toEqual(["headset"]);
+	});
 });
@@ -1,4 +1,4 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
 import { emit, listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { Store } from "@tauri-apps/plugin-store";
@@ -12,6 +12,41 @@ export type ConnectionState =
 	| "recording"
 	| "processing";
 
+/** Server connection state, tracked by the backend */
+export interface ConnectionStatus {
+	state: ConnectionState;
+	attempt: number; // Reconnect attempts since the connection was last up
+	retry_in_ms: number | null; // Delay of the scheduled reconnect, if any
+	endpoint: string | null; // Server in use, or being tried while disconnected
+}
+
+/** Providers a server reports it can use */
+export interface ServerCapabilities {
+	stt_providers: string[];
+	llm_providers: string[];
+}
+
+/** Result of testing the connection to a server */
+export interface ConnectionTest {
+	reachable: boolean;
+	latency_ms: number | null;
+	capabilities: ServerCapabilities | null; // Null for older servers
+	certificate_sha256: string | null; // Set when the server uses HTTPS
+	error: string | null;
+}
+
+/** Credentials for a proxy that requires them */
+export interface ProxyAuth {
+	username: string;
+	password: string;
+}
+
+/** Server the overlay connected to */
+export interface ServerEndpoint {
+	url: string;
+	priority: number; // 0 for the configured server URL, then each fallback
+}
+
 export interface ConfigResponse {
 	type: "config-updated" | "config-error";
 	setting: string;
@@ -24,21 +59,530 @@ interface TypeTextResult {
 	error?: string;
 }
 
+export type HotkeyTrigger = "keyboard" | "mouse" | "double_tap" | "media_key";
+
+/** Key combination pressed after the first step of a hotkey sequence */
+export interface HotkeyStep {
+	modifiers: string[];
+	key: string;
+}
+
 export interface HotkeyConfig {
 	modifiers: string[];
 	key: string;
+	/** Input that activates the hotkey, defaults to keyboard */
+	trigger?: HotkeyTrigger;
+	/** Maximum milliseconds between taps for a double-tap trigger */
+	tap_interval_ms?: number;
+	/** Second step of a sequence such as "Ctrl+Alt+D then T" */
+	then?: HotkeyStep;
+	/**
+	 * Bind the keys by position, named as on a US keyboard, so the shortcut
+	 * stays on the same keys when the keyboard layout changes
+	 */
+	physical?: boolean;
+}
+
+interface HotkeyAvailability {
+	available: boolean;
+	reason: string | null;
+}
+
+/** A connected HID device interface, such as a foot pedal */
+export interface HidDevice {
+	vendor_id: number;
+	product_id: number;
+	usage_page: number;
+	usage: number;
+	name: string;
+}
+
+export type HidAction = "hold" | "toggle";
+
+/** A button of a HID device bound to hold-to-record or toggle recording */
+export interface HidBinding {
+	vendor_id: number;
+	product_id: number;
+	usage_page: number;
+	usage: number;
+	name: string;
+	button: number; // Bit in the device's input reports
+	action: HidAction;
+}
+
+/** An audio input device as enumerated by the overlay webview */
+export interface AudioInputDevice {
+	device_id: string;
+	label: string;
+}
+
+/** The microphone to record from */
+export interface ActiveMic {
+	device_id: string | null; // null means the system default
+	is_fallback: boolean; // No preferred device is present, the default is used
+}
+
+/** Recording switched microphone as a preferred one was plugged in or out */
+export interface MicFallbackChange {
+	is_fallback: boolean;
+	label: string | null;
+}
+
+export interface ShortcutRegistrationFailure {
+	action: string;
+	shortcut: string;
+	error: string;
+}
+
+/** Broad class of a command error, so the UI can tell fixable input apart */
+export type AppErrorKind = "validation" | "conflict" | "io";
+
+/** Structured error some commands reject with instead of a bare message */
+export interface AppError {
+	code:
+		| "invalid_hotkey"
+		| "shortcuts_in_use"
+		| "shortcut_manager"
+		| "settings_io";
+	kind: AppErrorKind;
+	message: string;
+	context: Record<string, unknown>;
+}
+
+export function isAppError(error: unknown): error is AppError {
+	return (
+		typeof error === "object" &&
+		error !== null &&
+		"code" in error &&
+		"kind" in error &&
+		"message" in error
+	);
+}
+
+/** Message to show for anything a command or mutation rejects with */
+export function describeError(error: unknown): string {
+	if (isAppError(error) || error instanceof Error) return error.message;
+	return String(error);
 }
 
 // Zod schema for HotkeyConfig validation
 export const HotkeyConfigSchema = z.object({
 	modifiers: z.array(z.string()),
 	key: z.string().min(1, "Key is required"),
+	trigger: z.enum(["keyboard", "mouse", "double_tap", "media_key"]).optional(),
+	tap_interval_ms: z.number().int().positive().optional(),
+	then: z
+		.object({
+			modifiers: z.array(z.string()),
+			key: z.string().min(1, "Key is required"),
+		})
+		.optional(),
+	physical: z.boolean().optional(),
 });
 
-interface HistoryEntry {
+export type NewlineMode = "paste" | "enter" | "shift_enter";
+
+/** How text is inserted into the focused app */
+export type InsertionMethod =
+	| "paste"
+	| "type"
+	| "accessibility"
+	| "clipboard_only";
+
+/** Where a finished dictation goes */
+export type OutputMode = "insert" | "clipboard_only" | "both";
+
+/** How prompt sections are cut to fit the prompt size limit */
+export type PromptTruncation = "trim" | "drop";
+
+export interface InsertionRule {
+	app: string; // App name or executable (case-insensitive substring)
+	method: InsertionMethod;
+}
+
+export interface NewlineRule {
+	app: string;
+	mode: NewlineMode;
+}
+
+export type VoiceCommandAction =
+	| "new_line"
+	| "new_paragraph"
+	| "press_enter"
+	| "press_tab"
+	| "delete_that"
+	| "undo";
+
+/** A phrase carried out instead of typed when dictated */
+export interface VoiceCommand {
+	phrase: string;
+	action: VoiceCommandAction;
+}
+
+/** A regex find/replace rule applied to dictations before insertion */
+export interface TransformRule {
+	name: string;
+	pattern: string; // Rust regex syntax, with inline flags like (?i)
+	replacement: string; // $1 or ${name} for groups
+	enabled: boolean;
+}
+
+export interface TransformTest {
+	output: string;
+	matches: number;
+}
+
+export type OutputCasing = "as_formatted" | "lowercase" | "sentence_case";
+
+export type QuoteStyle = "unchanged" | "smart" | "straight";
+
+/** Deterministic formatting applied to every dictation, with or without an LLM */
+export interface OutputStyle {
+	casing: OutputCasing;
+	strip_trailing_punctuation: boolean;
+	quotes: QuoteStyle;
+}
+
+export const DEFAULT_OUTPUT_STYLE: OutputStyle = {
+	casing: "as_formatted",
+	strip_trailing_punctuation: false,
+	quotes: "unchanged",
+};
+
+/** Output style used while a matching app has focus */
+export interface OutputStyleRule {
+	app: string; // App name or executable, case-insensitive substring
+	style: OutputStyle;
+}
+
+/** The app focused when a recording started, where its text will be inserted */
+export interface ActiveApp {
+	app_name: string;
+	title: string; // Focused window title
+	process_path: string;
+	bundle_id: string | null; // macOS only
+}
+
+export interface InsertionProgress {
+	chunk: number;
+	total_chunks: number;
+}
+
+/** How text gets into other apps on this system */
+interface InsertionDiagnostics {
+	display_server: "x11" | "wayland" | "unknown" | null; // Linux only
+	key_backend: "enigo" | "wtype" | "ydotool";
+	clipboard_available: boolean;
+	warnings: string[];
+}
+
+interface ClipboardFallback {
+	char_count: number;
+	max_chars: number;
+}
+
+interface ApiKeyStatus {
+	provider: string;
+	is_set: boolean;
+}
+
+/** Which releases the app updates to; beta includes pre-releases */
+export type UpdateChannel = "stable" | "beta";
+
+/** A newer release on the selected update channel */
+interface UpdateInfo {
+	version: string;
+	current_version: string;
+	channel: UpdateChannel;
+	notes: string | null; // Release notes
+	date: string | null;
+}
+
+export interface UpdateProgress {
+	downloaded: number;
+	total: number | null; // Unknown when the server doesn't report the size
+}
+
+/** Time span usage statistics cover, ending now */
+export type UsagePeriod = "day" | "week" | "month" | "all";
+
+export type ExportFormat = "csv" | "json";
+
+interface ProviderUsage {
+	provider: string | null;
+	dictations: number;
+	words: number;
+	speaking_ms: number;
+}
+
+/** Dictation totals over a period */
+export interface UsageStats {
+	period: UsagePeriod;
+	dictations: number;
+	words: number;
+	speaking_ms: number;
+	time_saved_ms: number; // Estimated against typing at 40 words per minute
+	by_provider: ProviderUsage[]; // Most used first
+}
+
+/** What went wrong with a dictation turn */
+export type FailureCategory =
+	| "transcription"
+	| "server"
+	| "insertion"
+	| "notes"
+	| "secure_input"
+	| "connection";
+
+/** Local telemetry counters, recorded only while telemetry is enabled */
+export interface TelemetryReport {
+	enabled: boolean;
+	since: string | null; // When the first counter was recorded
+	sessions: number;
+	dictations: number;
+	failures: { category: FailureCategory; count: number }[]; // Most frequent first
+	failure_rate: number | null; // Share of turns that failed, null before any turn
+	latency: { max_ms: number | null; count: number }[]; // Slowest bucket has no bound
+}
+
+export type CheckStatus = "pass" | "warn" | "fail" | "unknown";
+
+/** One environment check from run_diagnostics */
+export interface DiagnosticCheck {
+	id:
+		| "microphone_access"
+		| "microphone_device"
+		| "server"
+		| "hotkeys"
+		| "insertion";
+	label: string;
+	status: CheckStatus;
+	detail: string;
+	fix: string | null; // What to do when the check didn't pass
+}
+
+export interface DiagnosticsReport {
+	checks: DiagnosticCheck[];
+	generated_at: string;
+}
+
+export type BenchmarkSource = "synthetic" | "recorded";
+
+/** Timings of one run_latency_benchmark run, in milliseconds */
+export interface LatencyBenchmark {
+	source: BenchmarkSource;
+	audio_ms: number;
+	stt_provider: string;
+	llm_provider: string | null; // null for the server's default
+	stt_includes_formatting: boolean; // The server transcribed and formatted at once
+	timings: {
+		capture_ms: number;
+		network_ms: number | null; // null when the server is unreachable
+		stt_ms: number;
+		formatting_ms: number;
+		insertion_ms: number; // Clipboard round trip, nothing is typed
+	};
+	total_ms: number;
+	transcript: string;
+	formatted: string;
+}
+
+/** One setting changed, null on the side where it was missing */
+export interface SettingChange {
+	key: string;
+	old_value: unknown;
+	new_value: unknown;
+}
+
+/** Payload of settings://changed, emitted by the backend to every window */
+export interface SettingsChangedEvent {
+	changes: SettingChange[]; // Sorted by key
+}
+
+/** Server auth token state, without the token itself */
+export interface AuthStatus {
+	is_set: boolean;
+	expires_at: string | null;
+	is_expired: boolean;
+	can_refresh: boolean;
+	refresh_error: string | null; // Why the last automatic refresh failed
+}
+
+interface SessionEntry {
+	timestamp: string;
+	text: string;
+}
+
+export interface SessionTranscript {
+	started_at: string;
+	entries: SessionEntry[];
+}
+
+/** What choosing a command palette item does */
+export type PaletteAction =
+	| { kind: "paste_history"; index: number } // 0 is the most recent
+	| { kind: "paste_pinned"; id: string }
+	| { kind: "switch_stt_provider"; value: string }
+	| { kind: "apply_prompt_preset"; id: string }
+	| { kind: "toggle_formatting" };
+
+export interface PaletteItem {
+	action: PaletteAction;
+	title: string;
+	detail: string; // Group or extra context, e.g. "STT provider"
+	active: boolean; // The current choice, e.g. the selected provider
+}
+
+export interface HistoryEntry {
+	id: string;
+	timestamp: string;
+	text: string;
+	app_name: string | null; // App the text was dictated into
+	provider: string | null; // STT provider that transcribed it
+	duration_ms: number | null; // Recording length
+	raw_text: string | null; // Transcript before formatting
+	snapshot: TurnSnapshot | null; // Formatting settings when recorded
+	pinned: boolean; // Kept when the history is cleared
+}
+
+/** Whisper model sizes that can be downloaded, from fastest to most accurate */
+export type WhisperModel =
+	| "tiny"
+	| "base"
+	| "small"
+	| "medium"
+	| "large-v3-turbo";
+
+export interface WhisperModelStatus {
+	model: WhisperModel;
+	approx_size_mb: number;
+	downloaded: boolean;
+	downloading: boolean;
+}
+
+export interface ModelDownloadProgress {
+	model: WhisperModel;
+	downloaded_bytes: number;
+	total_bytes: number | null;
+}
+
+/** A dictation's audio saved in the recordings folder */
+export interface RecordingInfo {
 	id: string;
 	timestamp: string;
+	duration_ms: number;
+	size_bytes: number;
+}
+
+/** Formatting settings in effect when a turn was dictated */
+export interface TurnSnapshot {
+	llm_provider: string | null;
+	prompt_sections: CleanupPromptSections | null;
+	settings_hash: string;
+}
+
+/** A history entry's transcript formatted again with the current settings */
+export interface ReRunResult {
+	entry: HistoryEntry;
+	text: string;
+	snapshot: TurnSnapshot;
+	settings_changed: boolean | null; // null when the entry has no snapshot
+}
+
+/** Word-level change between a raw transcript and its formatted text */
+export type DiffKind = "equal" | "delete" | "insert";
+
+export interface DiffSegment {
+	kind: DiffKind;
+	text: string;
+}
+
+/** A word or phrase the user corrected in a dictation */
+export interface WordCorrection {
+	heard: string;
+	corrected: string;
+}
+
+/** A personal dictionary mapping from what is heard to how it is written */
+export interface DictionaryEntry {
+	id: string;
+	spoken: string;
+	written: string; // Same as spoken for a term to recognize
+	case_sensitive: boolean;
+}
+
+export type DictionaryImportFormat = "csv" | "text";
+
+/** An entry read from a dictionary import file */
+export interface ImportedDictionaryEntry {
+	line: number;
+	spoken: string;
+	written: string;
+	case_sensitive: boolean;
+}
+
+/** What a dictionary import added, or would add in a dry run */
+export interface DictionaryImport {
+	dry_run: boolean;
+	added: ImportedDictionaryEntry[];
+	duplicates: ImportedDictionaryEntry[]; // Already listed, or earlier in the file
+	conflicts: { entry: ImportedDictionaryEntry; existing: DictionaryEntry }[];
+	skipped: { line: number; text: string }[];
+}
+
+/** A spoken trigger phrase and the template it expands to when dictated */
+export interface Snippet {
+	id: string;
+	trigger: string;
+	template: string; // May use {date}, {time} and {clipboard}
+}
+
+/** CSV for .csv files, "spoken = written" lines for anything else */
+export function dictionaryImportFormat(path: string): DictionaryImportFormat {
+	return path.trim().toLowerCase().endsWith(".csv") ? "csv" : "text";
+}
+
+/** A correction offered as a personal dictionary entry */
+export interface DictionarySuggestion extends WordCorrection {
+	count: number; // Times the word was corrected this way
+}
+
+/** Raw text for the overlay to send to the server for formatting */
+export interface FormatTextRequest {
 	text: string;
+	preview_id?: number; // Set when the result goes back to Rust, uninserted
+}
+
+/** Audio of a turn queued while offline, to be replayed to the server */
+export type QueuedAudioRequest = {
+	preview_id: number; // The result goes back to Rust like a preview
+	sample_rate: number;
+	stt_provider: string | null; // Server STT provider to use instead of the selected one
+} & (
+	| { encoding: "pcm16"; samples: number[] } // 16-bit mono
+	| { encoding: "opus"; packets: string[] } // Base64 packets of 20 ms
+);
+
+/** A data file found corrupt at startup and moved aside */
+export interface DataFileIssue {
+	file: string;
+	problem: string;
+	quarantined_as: string | null; // null if the file couldn't be moved
+	restored_from_backup: boolean; // The last good copy was put back
+}
+
+/** One page of history entries matching a search, newest first */
+export interface HistoryPage {
+	entries: HistoryEntry[];
+	total: number; // Matching entries across all pages
+	page: number;
+	page_size: number;
+}
+
+export interface HistoryQuery {
+	page?: number;
+	pageSize?: number;
+	query?: string;
 }
 
 export interface PromptSection {
@@ -52,276 +596,2213 @@ export interface CleanupPromptSections {
 	dictionary: PromptSection;
 }
 
+export const DEFAULT_CLEANUP_PROMPT_SECTIONS: CleanupPromptSections = {
+	main: { enabled: true, content: null },
+	advanced: { enabled: true, content: null },
+	dictionary: { enabled: false, content: null },
+};
+
+/** Main and advanced prompt sections saved under a name */
+export interface PromptPreset {
+	id: string;
+	name: string;
+	main: PromptSection;
+	advanced: PromptSection;
+}
+
+/** Saved presets and the one matching the current prompt, if any */
+export interface PromptPresets {
+	presets: PromptPreset[];
+	active_id: string | null;
+}
+
+/** Names of the presets an import added, and of those it replaced */
+export interface PresetImport {
+	added: string[];
+	replaced: string[];
+}
+
 export interface AppSettings {
 	toggle_hotkey: HotkeyConfig;
 	hold_hotkey: HotkeyConfig;
 	paste_last_hotkey: HotkeyConfig;
-	selected_mic_id: string | null;
-	sound_enabled: boolean;
+	cycle_history_hotkey: HotkeyConfig | null; // Off when null
+	cycle_language_hotkey: HotkeyConfig | null; // Off when null
+	pause_resume_hotkey: HotkeyConfig | null; // Off when null
+	retry_last_hotkey: HotkeyConfig | null; // Off when null
+	clipboard_only_hotkey: HotkeyConfig | null; // Off when null
+	cycle_stt_provider_hotkey: HotkeyConfig | null; // Off when null
+	toggle_formatting_hotkey: HotkeyConfig | null; // Off when null
+	cycle_prompt_preset_hotkey: HotkeyConfig | null; // Off when null
+	open_palette_hotkey: HotkeyConfig | null; // Off when null
+	do_not_disturb_hotkey: HotkeyConfig | null; // Off when null
+	preferred_mic_ids: string[]; // Most preferred first, empty for the default
+	hid_triggers: HidBinding[];
+	sound_settings: SoundSettings;
+	sound_theme: SoundTheme;
 	cleanup_prompt_sections: CleanupPromptSections | null;
 	stt_provider: string | null;
+	stt_fallback_provider: string | null; // Retries failed turns, off when null
+	whisper_model: WhisperModel; // Downloaded model used by local Whisper
 	llm_provider: string | null;
 	auto_mute_audio: boolean;
+	audio_duck_percent: number; // Share of system volume kept while recording, 0 mutes
+	mic_gain: number; // Multiplier applied to microphone input
+	agc_enabled: boolean; // Automatic gain control on the microphone
+	native_audio_capture: boolean; // Capture the microphone in the backend
+	noise_suppression_enabled: boolean;
+	echo_cancellation_enabled: boolean;
+	sample_rate: number | null; // Rate the microphone is opened at, its default when null
+	channels: number | null; // Channels the microphone is opened with, its default when null
 	stt_timeout_seconds: number | null;
+	short_utterance_max_words: number | null;
+	formatting_enabled: boolean; // Off inserts transcripts without LLM cleanup
+	language: string | null; // ISO 639-1 hint for speech-to-text, none when null
+	auto_detect_language: boolean; // Let providers detect the spoken language
+	dictation_languages: string[]; // Languages the hotkey and tray switch between
+	max_prompt_tokens: number; // Limit for in-app formatting, 0 for none
+	prompt_truncation: PromptTruncation;
+	max_insertion_chars: number;
+	newline_rules: NewlineRule[];
+	insertion_method: InsertionMethod;
+	insertion_rules: InsertionRule[];
+	output_mode: OutputMode; // The clipboard-only hotkey switches it for one dictation
+	blocked_apps: string[]; // Apps where hotkeys don't record and nothing is inserted
+	clipboard_restore_delay_ms: number;
+	copy_after_insert: boolean; // Also leave inserted text on the clipboard
+	notes_enabled: boolean; // Append dictations to the notes file
+	notes_file_path: string | null; // Notes file, in the documents directory when null
+	notes_also_insert: boolean; // Insert dictations as well as noting them
+	notes_include_app: boolean; // Note the app each dictation was made in
+	voice_commands_enabled: boolean;
+	voice_commands: VoiceCommand[];
+	output_style: OutputStyle;
+	output_style_rules: OutputStyleRule[]; // First matching app wins
+	text_transforms: TransformRule[]; // Run in order before snippets
 	server_url: string;
+	fallback_server_urls: string[]; // Tried in order when the server is unreachable
+	allow_self_signed_certs: boolean;
+	pinned_cert_sha256: string | null; // Required certificate of the server URL
+	proxy_url: string | null; // http(s):// or socks5:// proxy, none when null
+	proxy_auth: ProxyAuth | null;
+	opus_bitrate_kbps: number; // Bitrate of queued audio sent to the server as Opus
+	sequence_timeout_ms: number;
+	hold_latch_enabled: boolean; // A quick tap of the hold hotkey latches recording
+	hold_latch_max_seconds: number;
+	max_concurrent_turns: number;
+	auto_stop_silence_seconds: number | null; // Off when null
+	max_recording_seconds: number; // Recordings stop by themselves after this, 0 for no limit
+	pre_roll_ms: number; // Audio from before the hotkey press kept in a recording
+	archive_recordings: boolean; // Save each dictation's audio as a WAV file
+	keep_last_recording: boolean; // Keep the last dictation's audio in memory to retry it
+	retry_stt_provider: string | null; // Provider for retries, the selected one when null
+	recording_max_count: number; // 0 keeps any number of recordings
+	recording_max_days: number; // 0 keeps recordings forever
+	overlay_position: OverlayPosition | null; // Default corner when null
+	overlay_monitor: string | null; // Monitor the overlay was placed on
+	overlay_scale: number; // Zoom of the overlay's contents
+	show_partial_transcripts: boolean; // Show interim transcripts while recording
+	update_channel: UpdateChannel;
+	telemetry_enabled: boolean; // Keep local counters of dictations and failures
+	remote_control_enabled: boolean; // WebSocket for Stream Deck and scripts
+	remote_control_port: number;
+	api_enabled: boolean; // Local HTTP API for scripts and editor plugins
+	api_port: number;
+	deep_links_enabled: boolean; // tambourine:// links from launchers
+}
+
+export type SoundType =
+	| "recording_start"
+	| "recording_stop"
+	| "error"
+	| "complete";
+
+/** Where a feedback sound comes from */
+export type SoundSource =
+	| { source: "default" } // Theme directory file, else the built-in sound
+	| { source: "silent" }
+	| { source: "file"; path: string };
+
+export interface SoundTheme {
+	directory: string | null; // WAV files named start, stop, error and complete
+	start: SoundSource;
+	stop: SoundSource;
+	error: SoundSource;
+	complete: SoundSource;
+}
+
+/** Whether and how loud one event's sound plays */
+export interface EventSound {
+	enabled: boolean;
+	volume: number; // 0 to 1, scaled by the master volume
+}
+
+export interface SoundSettings {
+	volume: number; // Master volume, 0 to 1
+	start: EventSound;
+	stop: EventSound;
+	error: EventSound;
+	complete: EventSound;
+}
+
+export const DEFAULT_SOUND_SETTINGS: SoundSettings = {
+	volume: 1,
+	start: { enabled: true, volume: 1 },
+	stop: { enabled: true, volume: 1 },
+	error: { enabled: true, volume: 1 },
+	complete: { enabled: true, volume: 1 },
+};
+
+/** Saved sound settings, or every event on or off as the old flag was */
+function soundSettingsFromStored(
+	saved: Partial<SoundSettings> | undefined,
+	legacyEnabled: boolean | undefined,
+): SoundSettings {
+	if (saved) return { ...DEFAULT_SOUND_SETTINGS, ...saved };
+	const event = { enabled: legacyEnabled ?? true, volume: 1 };
+	return {
+		volume: 1,
+		start: event,
+		stop: event,
+		error: event,
+		complete: event,
+	};
+}
+
+export const DEFAULT_SOUND_THEME: SoundTheme = {
+	directory: null,
+	start: { source: "default" },
+	stop: { source: "default" },
+	error: { source: "default" },
+	complete: { source: "default" },
+};
+
+/** Offset of the overlay's center from its monitor's top-left corner */
+export interface OverlayPosition {
+	x: number;
+	y: number;
+}
+
+export const DEFAULT_SERVER_URL = "http://127.0.0.1:8765";
+
+export const DEFAULT_MAX_INSERTION_CHARS = 20000;
+
+export const DEFAULT_CLIPBOARD_RESTORE_DELAY_MS = 100;
+
+/** Voice commands used until the list is customized, matching the Rust defaults */
+export const DEFAULT_VOICE_COMMANDS: VoiceCommand[] = [
+	{ phrase: "new line", action: "new_line" },
+	{ phrase: "new paragraph", action: "new_paragraph" },
+	{ phrase: "press enter", action: "press_enter" },
+	{ phrase: "press tab", action: "press_tab" },
+	{ phrase: "scratch that", action: "delete_that" },
+	{ phrase: "delete that", action: "delete_that" },
+	{ phrase: "undo that", action: "undo" },
+];
+
+export const DEFAULT_SEQUENCE_TIMEOUT_MS = 1000;
+
+export const DEFAULT_HOLD_LATCH_MAX_SECONDS = 30;
+
+export const DEFAULT_MAX_RECORDING_SECONDS = 600;
+
+export const DEFAULT_MAX_CONCURRENT_TURNS = 1;
+
+export const DEFAULT_MAX_PROMPT_TOKENS = 4096;
+
+/** STT provider value for in-app Whisper transcription */
+export const LOCAL_WHISPER_PROVIDER = "local-whisper";
+
+export const DEFAULT_WHISPER_MODEL: WhisperModel = "base";
+
+export const DEFAULT_MIC_GAIN = 1;
+
+export const MIN_MIC_GAIN = 0.5;
+
+export const MAX_MIC_GAIN = 4;
+
+export const MAX_AUTO_STOP_SILENCE_SECONDS = 30;
+
+export const DEFAULT_PRE_ROLL_MS = 0;
+
+export const MAX_PRE_ROLL_MS = 2000;
+
+// 0 mutes system audio while recording instead of ducking it
+export const DEFAULT_AUDIO_DUCK_PERCENT = 0;
+
+export const MAX_AUDIO_DUCK_PERCENT = 90;
+
+export const DEFAULT_OPUS_BITRATE_KBPS = 24;
+
+export const MIN_OPUS_BITRATE_KBPS = 6;
+
+export const MAX_OPUS_BITRATE_KBPS = 128;
+
+export const DEFAULT_REMOTE_CONTROL_PORT = 47653;
+
+export const DEFAULT_API_PORT = 47654;
+
+export const DEFAULT_RECORDING_MAX_COUNT = 100;
+
+export const DEFAULT_RECORDING_MAX_DAYS = 30;
+
+export const DEFAULT_OVERLAY_SCALE = 1;
+
+export const MIN_OVERLAY_SCALE = 0.5;
+
+export const MAX_OVERLAY_SCALE = 2;
+
+export const DEFAULT_SHORT_UTTERANCE_MAX_WORDS = 0;
+
+// The server rejects larger values (MAX_SHORT_UTTERANCE_MAX_WORDS in server/processors/llm.py)
+export const MAX_SHORT_UTTERANCE_MAX_WORDS = 10;
+
+/** Keep a microphone gain within the supported range */
+/** Languages offered for dictation by ISO 639-1 code, matching language.rs */
+export const DICTATION_LANGUAGES: { code: string; name: string }[] = [
+	{ code: "ar", name: "Arabic" },
+	{ code: "zh", name: "Chinese" },
+	{ code: "cs", name: "Czech" },
+	{ code: "da", name: "Danish" },
+	{ code: "nl", name: "Dutch" },
+	{ code: "en", name: "English" },
+	{ code: "fi", name: "Finnish" },
+	{ code: "fr", name: "French" },
+	{ code: "de", name: "German" },
+	{ code: "el", name: "Greek" },
+	{ code: "he", name: "Hebrew" },
+	{ code: "hi", name: "Hindi" },
+	{ code: "hu", name: "Hungarian" },
+	{ code: "id", name: "Indonesian" },
+	{ code: "it", name: "Italian" },
+	{ code: "ja", name: "Japanese" },
+	{ code: "ko", name: "Korean" },
+	{ code: "no", name: "Norwegian" },
+	{ code: "pl", name: "Polish" },
+	{ code: "pt", name: "Portuguese" },
+	{ code: "ro", name: "Romanian" },
+	{ code: "ru", name: "Russian" },
+	{ code: "es", name: "Spanish" },
+	{ code: "sv", name: "Swedish" },
+	{ code: "th", name: "Thai" },
+	{ code: "tr", name: "Turkish" },
+	{ code: "uk", name: "Ukrainian" },
+	{ code: "vi", name: "Vietnamese" },
+];
+
+/** Language hint sent to the server, or null to let the provider detect it */
+export function dictationLanguageHint(
+	settings: Pick<AppSettings, "language" | "auto_detect_language">,
+): string | null {
+	if (settings.auto_detect_language || !settings.language) return null;
+	const code = settings.language.trim().toLowerCase();
+	return DICTATION_LANGUAGES.some((language) => language.code === code)
+		? code
+		: null;
+}
+
+/** RMS and peak level of a frame of the microphone test, from 0 to 1 */
+export interface MicTestLevel {
+	rms: number;
+	peak: number;
+}
+
+/** Channel count and sample rates a microphone can be captured in */
+export interface FormatRange {
+	channels: number;
+	min_sample_rate: number;
+	max_sample_rate: number;
+}
+
+export interface CaptureFormats {
+	device: string;
+	formats: FormatRange[];
+}
+
+/** Sample rates offered for capture, where the microphone supports them */
+export const CAPTURE_SAMPLE_RATES = [16000, 22050, 32000, 44100, 48000, 96000];
+
+function formatMatches(
+	format: FormatRange,
+	sampleRate: number | null,
+	channels: number | null,
+): boolean {
+	return (
+		(channels === null || format.channels === channels) &&
+		(sampleRate === null ||
+			(sampleRate >= format.min_sample_rate &&
+				sampleRate <= format.max_sample_rate))
+	);
+}
+
+/** The offered sample rates the microphone supports with `channels` */
+export function supportedSampleRates(
+	formats: FormatRange[],
+	channels: number | null,
+): number[] {
+	return CAPTURE_SAMPLE_RATES.filter((rate) =>
+		formats.some((format) => formatMatches(format, rate, channels)),
+	);
+}
+
+/** The channel counts the microphone supports at `sampleRate`, fewest first */
+export function supportedChannelCounts(
+	formats: FormatRange[],
+	sampleRate: number | null,
+): number[] {
+	const counts = formats
+		.filter((format) => formatMatches(format, sampleRate, null))
+		.map((format) => format.channels);
+	return [...new Set(counts)].sort((a, b) => a - b);
+}
+
+/** Saved preferred microphones, or the one selected before the list existed */
+export function preferredMicsFromStored(
+	saved: string[] | undefined,
+	legacySelected: string | null | undefined,
+): string[] {
+	if (saved) return saved;
+	return legacySelected ? [legacySelected] : [];
+}
+
+/** The first preferred microphone that is plugged in, null for the default */
+export function activePreferredMic(
+	preferred: string[],
+	deviceIds: string[],
+): string | null {
+	return preferred.find((id) => deviceIds.includes(id)) ?? null;
 }
 
-export const DEFAULT_SERVER_URL = "http://127.0.0.1:8765";
+export function clampMicGain(gain: number): number {
+	if (!Number.isFinite(gain)) return DEFAULT_MIC_GAIN;
+	return Math.min(MAX_MIC_GAIN, Math.max(MIN_MIC_GAIN, gain));
+}
+
+/** Keep an overlay zoom within the supported range */
+export function clampOverlayScale(scale: number): number {
+	if (!Number.isFinite(scale)) return DEFAULT_OVERLAY_SCALE;
+	return Math.min(MAX_OVERLAY_SCALE, Math.max(MIN_OVERLAY_SCALE, scale));
+}
+
+// ============================================================================
+// Default values - must match Rust defaults
+// ============================================================================
+
+const DEFAULT_HOTKEY_MODIFIERS = ["ctrl", "alt"];
+
+export const defaultToggleHotkey: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: "Space",
+};
+
+export const defaultHoldHotkey: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: "Backquote",
+};
+
+export const defaultPasteLastHotkey: HotkeyConfig = {
+	modifiers: DEFAULT_HOTKEY_MODIFIERS,
+	key: "Period",
+};
+
+/** Default hotkeys adjusted by the backend for the OS and keyboard layout */
+export interface DefaultHotkeys {
+	toggle_hotkey: HotkeyConfig;
+	hold_hotkey: HotkeyConfig;
+	paste_last_hotkey: HotkeyConfig;
+}
+
+/** Experimental subsystems that stay off unless the user opts in */
+export type FeatureFlag =
+	| "streaming_insertion"
+	| "wake_word"
+	| "always_listening";
+
+export interface FeatureFlagState {
+	flag: FeatureFlag;
+	enabled: boolean;
+	description: string;
+}
+
+// ============================================================================
+// Store helpers
+// ============================================================================
+
+let storeInstance: Store | null = null;
+
+async function getStore(): Promise<Store> {
+	if (!storeInstance) {
+		storeInstance = await Store.load("settings.json");
+	}
+	return storeInstance;
+}
+
+// ============================================================================
+// Hotkey validation helpers (Zod-based)
+// ============================================================================
+
+function stepIsSameAs(a: HotkeyStep, b: HotkeyStep): boolean {
+	if (a.key.toLowerCase() !== b.key.toLowerCase()) return false;
+	if (a.modifiers.length !== b.modifiers.length) return false;
+	return a.modifiers.every((mod) =>
+		b.modifiers.some((other) => mod.toLowerCase() === other.toLowerCase()),
+	);
+}
+
+/**
+ * Check if two hotkey configs are equivalent (case-insensitive comparison)
+ */
+export function hotkeyIsSameAs(a: HotkeyConfig, b: HotkeyConfig): boolean {
+	if ((a.trigger ?? "keyboard") !== (b.trigger ?? "keyboard")) return false;
+	if (!stepIsSameAs(a, b)) return false;
+	if (!a.then || !b.then) return !a.then && !b.then;
+	return stepIsSameAs(a.then, b.then);
+}
+
+/**
+ * Check if two hotkeys can't both be registered: they are the same, or one is a
+ * single shortcut that is also the first step of the other's sequence
+ */
+export function hotkeyConflictsWith(
+	a: HotkeyConfig,
+	b: HotkeyConfig,
+): boolean {
+	if (hotkeyIsSameAs(a, b)) return true;
+	const isKeyboard = (h: HotkeyConfig) =>
+		(h.trigger ?? "keyboard") === "keyboard";
+	if (!isKeyboard(a) || !isKeyboard(b)) return false;
+	// Sequences may share a first step as long as the second steps differ
+	if (a.then && b.then) return false;
+	return stepIsSameAs(a, b);
+}
+
+/** KeyboardEvent.code of the position a hotkey key names, e.g. "Q" -> "KeyQ" */
+export function keyPositionCode(key: string): string {
+	if (/^[a-z]$/i.test(key)) return `Key${key.toUpperCase()}`;
+	if (/^[0-9]$/.test(key)) return `Digit${key}`;
+	return key;
+}
+
+/**
+ * Label for a key bound by position: what the current keyboard layout types
+ * there, or the key's US name when the layout isn't known
+ */
+export function keyLabelForLayout(
+	key: string,
+	layoutMap: ReadonlyMap<string, string> | null,
+): string {
+	const label = layoutMap?.get(keyPositionCode(key));
+	return label ? label.toUpperCase() : key;
+}
+
+type HotkeyType =
+	| "toggle"
+	| "hold"
+	| "paste_last"
+	| "cycle_history"
+	| "cycle_language"
+	| "pause_resume"
+	| "retry_last"
+	| "clipboard_only"
+	| "cycle_stt_provider"
+	| "toggle_formatting"
+	| "cycle_prompt_preset"
+	| "open_palette"
+	| "do_not_disturb";
+
+const HOTKEY_LABELS: Record<HotkeyType, string> = {
+	toggle: "toggle",
+	hold: "hold",
+	paste_last: "paste last",
+	cycle_history: "cycle history",
+	cycle_language: "switch language",
+	pause_resume: "pause and resume",
+	retry_last: "retry last",
+	clipboard_only: "clipboard only",
+	cycle_stt_provider: "switch STT provider",
+	toggle_formatting: "formatting on/off",
+	cycle_prompt_preset: "switch prompt preset",
+	open_palette: "quick actions",
+	do_not_disturb: "pause dictation",
+};
+
+/** Configured hotkeys by type; optional hotkeys are null or missing when off */
+type HotkeySet = Partial<Record<HotkeyType, HotkeyConfig | null>>;
+
+/**
+ * Create a Zod schema for validating a hotkey doesn't conflict with existing hotkeys
+ */
+export function createHotkeyDuplicateSchema(
+	allHotkeys: HotkeySet,
+	excludeType: HotkeyType,
+) {
+	return HotkeyConfigSchema.superRefine((hotkey, ctx) => {
+		for (const [type, existing] of Object.entries(allHotkeys)) {
+			if (
+				type !== excludeType &&
+				existing &&
+				hotkeyConflictsWith(hotkey, existing)
+			) {
+				ctx.addIssue({
+					code: "custom",
+					message: `This shortcut is already used for the ${HOTKEY_LABELS[type as HotkeyType]} hotkey`,
+				});
+				return;
+			}
+		}
+	});
+}
+
+/**
+ * Validate that a hotkey doesn't conflict with other hotkeys
+ * Returns error message if invalid, null if valid
+ */
+export function validateHotkeyNotDuplicate(
+	newHotkey: HotkeyConfig,
+	allHotkeys: {
+		toggle: HotkeyConfig;
+		hold: HotkeyConfig;
+		paste_last: HotkeyConfig;
+		cycle_history?: HotkeyConfig | null;
+		cycle_language?: HotkeyConfig | null;
+		pause_resume?: HotkeyConfig | null;
+		retry_last?: HotkeyConfig | null;
+		clipboard_only?: HotkeyConfig | null;
+		cycle_stt_provider?: HotkeyConfig | null;
+		toggle_formatting?: HotkeyConfig | null;
+		cycle_prompt_preset?: HotkeyConfig | null;
+		open_palette?: HotkeyConfig | null;
+		do_not_disturb?: HotkeyConfig | null;
+	},
+	excludeType: HotkeyType,
+): string | null {
+	const schema = createHotkeyDuplicateSchema(allHotkeys, excludeType);
+	const result = schema.safeParse(newHotkey);
+	if (!result.success) {
+		return result.error.issues[0]?.message ?? "Invalid hotkey";
+	}
+	return null;
+}
+
+// ============================================================================
+// Tauri API
+// ============================================================================
+
+export const tauriAPI = {
+	/**
+	 * Insert a finished dictation, expanding snippets and carrying out voice
+	 * commands. Resolves to the text that was inserted, for history.
+	 */
+	async insertDictation(text: string): Promise<string> {
+		return invoke("insert_dictation", { text });
+	},
+
+	async typeText(text: string): Promise<TypeTextResult> {
+		try {
+			await invoke("type_text", { text });
+			return { success: true };
+		} catch (error) {
+			return { success: false, error: String(error) };
+		}
+	},
+
+	async getServerUrl(): Promise<string> {
+		return invoke("get_server_url");
+	},
+
+	/** App the current recording was started in, if it could be detected */
+	async getRecordingApp(): Promise<ActiveApp | null> {
+		return invoke("get_recording_app");
+	},
+
+	async onStartRecording(callback: () => void): Promise<UnlistenFn> {
+		return listen("recording-start", callback);
+	},
+
+	async onStopRecording(callback: () => void): Promise<UnlistenFn> {
+		return listen("recording-stop", callback);
+	},
+
+	/** Rust needs microphone audio for a provider that transcribes in-app */
+	async onLocalCaptureStart(callback: () => void): Promise<UnlistenFn> {
+		return listen("local-capture-start", callback);
+	},
+
+	/** Rust stopped the turn; the payload is the stop request to confirm */
+	async onLocalCaptureStop(
+		callback: (id: number) => void,
+	): Promise<UnlistenFn> {
+		return listen<number>("local-capture-stop", (event) =>
+			callback(event.payload),
+		);
+	},
+
+	/** Confirm every captured chunk before stop request `id` has been sent */
+	async localCaptureFlushed(id: number): Promise<void> {
+		return invoke("local_capture_flushed", { id });
+	},
+
+	/** Open a microphone by its webview label to test it, returning its name */
+	async startMicTest(deviceLabel: string | null): Promise<string> {
+		return invoke("start_mic_test", { deviceLabel });
+	},
+
+	async stopMicTest(): Promise<void> {
+		return invoke("stop_mic_test");
+	},
+
+	/** Levels of each frame of the microphone test */
+	async onMicTestLevel(
+		callback: (level: MicTestLevel) => void,
+	): Promise<UnlistenFn> {
+		return listen<MicTestLevel>("mic-test-level", (event) =>
+			callback(event.payload),
+		);
+	},
+
+	/** Level of each frame captured by the backend, from 0 to 1 */
+	async onMicLevel(callback: (level: number) => void): Promise<UnlistenFn> {
+		return listen<number>("mic-level", (event) => callback(event.payload));
+	},
+
+	async onFormatTextRequest(
+		callback: (request: FormatTextRequest) => void,
+	): Promise<UnlistenFn> {
+		return listen<FormatTextRequest>("format-text-request", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	/** Return a server formatting preview to the Rust caller waiting for it */
+	async completeFormatPreview(previewId: number, text: string): Promise<void> {
+		await invoke("complete_format_preview", { previewId, text });
+	},
+
+	async onInsertionProgress(
+		callback: (progress: InsertionProgress) => void,
+	): Promise<UnlistenFn> {
+		return listen<InsertionProgress>("insertion-progress", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onInsertionClipboardFallback(
+		callback: (fallback: ClipboardFallback) => void,
+	): Promise<UnlistenFn> {
+		return listen<ClipboardFallback>(
+			"insertion-clipboard-fallback",
+			(event) => {
+				callback(event.payload);
+			},
+		);
+	},
+
+	// Settings API - using store plugin directly
+	async getSettings(): Promise<AppSettings> {
+		const store = await getStore();
+		return {
+			toggle_hotkey:
+				(await store.get<HotkeyConfig>("toggle_hotkey")) ?? defaultToggleHotkey,
+			hold_hotkey:
+				(await store.get<HotkeyConfig>("hold_hotkey")) ?? defaultHoldHotkey,
+			paste_last_hotkey:
+				(await store.get<HotkeyConfig>("paste_last_hotkey")) ??
+				defaultPasteLastHotkey,
+			cycle_history_hotkey:
+				(await store.get<HotkeyConfig | null>("cycle_history_hotkey")) ?? null,
+			cycle_language_hotkey:
+				(await store.get<HotkeyConfig | null>("cycle_language_hotkey")) ??
+				null,
+			pause_resume_hotkey:
+				(await store.get<HotkeyConfig | null>("pause_resume_hotkey")) ?? null,
+			retry_last_hotkey:
+				(await store.get<HotkeyConfig | null>("retry_last_hotkey")) ?? null,
+			clipboard_only_hotkey:
+				(await store.get<HotkeyConfig | null>("clipboard_only_hotkey")) ??
+				null,
+			cycle_stt_provider_hotkey:
+				(await store.get<HotkeyConfig | null>("cycle_stt_provider_hotkey")) ??
+				null,
+			toggle_formatting_hotkey:
+				(await store.get<HotkeyConfig | null>("toggle_formatting_hotkey")) ??
+				null,
+			cycle_prompt_preset_hotkey:
+				(await store.get<HotkeyConfig | null>("cycle_prompt_preset_hotkey")) ??
+				null,
+			open_palette_hotkey:
+				(await store.get<HotkeyConfig | null>("open_palette_hotkey")) ?? null,
+			do_not_disturb_hotkey:
+				(await store.get<HotkeyConfig | null>("do_not_disturb_hotkey")) ??
+				null,
+			preferred_mic_ids: preferredMicsFromStored(
+				await store.get<string[]>("preferred_mic_ids"),
+				await store.get<string | null>("selected_mic_id"),
+			),
+			hid_triggers: (await store.get<HidBinding[]>("hid_triggers")) ?? [],
+			sound_settings: soundSettingsFromStored(
+				await store.get<Partial<SoundSettings>>("sound_settings"),
+				await store.get<boolean>("sound_enabled"),
+			),
+			sound_theme: {
+				...DEFAULT_SOUND_THEME,
+				...(await store.get<Partial<SoundTheme>>("sound_theme")),
+			},
+			cleanup_prompt_sections:
+				(await store.get<CleanupPromptSections | null>(
+					"cleanup_prompt_sections",
+				)) ?? null,
+			stt_provider: (await store.get<string | null>("stt_provider")) ?? null,
+			stt_fallback_provider:
+				(await store.get<string | null>("stt_fallback_provider")) ?? null,
+			whisper_model:
+				(await store.get<WhisperModel>("whisper_model")) ??
+				DEFAULT_WHISPER_MODEL,
+			llm_provider: (await store.get<string | null>("llm_provider")) ?? null,
+			auto_mute_audio: (await store.get<boolean>("auto_mute_audio")) ?? false,
+			audio_duck_percent:
+				(await store.get<number>("audio_duck_percent")) ??
+				DEFAULT_AUDIO_DUCK_PERCENT,
+			mic_gain: clampMicGain(
+				(await store.get<number>("mic_gain")) ?? DEFAULT_MIC_GAIN,
+			),
+			agc_enabled: (await store.get<boolean>("agc_enabled")) ?? true,
+			native_audio_capture:
+				(await store.get<boolean>("native_audio_capture")) ?? true,
+			noise_suppression_enabled:
+				(await store.get<boolean>("noise_suppression_enabled")) ?? false,
+			echo_cancellation_enabled:
+				(await store.get<boolean>("echo_cancellation_enabled")) ?? false,
+			sample_rate: (await store.get<number | null>("sample_rate")) ?? null,
+			channels: (await store.get<number | null>("channels")) ?? null,
+			stt_timeout_seconds:
+				(await store.get<number | null>("stt_timeout_seconds")) ?? null,
+			short_utterance_max_words:
+				(await store.get<number | null>("short_utterance_max_words")) ?? null,
+			formatting_enabled:
+				(await store.get<boolean>("formatting_enabled")) ?? true,
+			language: (await store.get<string | null>("language")) ?? null,
+			auto_detect_language:
+				(await store.get<boolean>("auto_detect_language")) ?? false,
+			dictation_languages:
+				(await store.get<string[]>("dictation_languages")) ?? [],
+			max_insertion_chars:
+				(await store.get<number>("max_insertion_chars")) ??
+				DEFAULT_MAX_INSERTION_CHARS,
+			newline_rules: (await store.get<NewlineRule[]>("newline_rules")) ?? [],
+			insertion_method:
+				(await store.get<InsertionMethod>("insertion_method")) ?? "paste",
+			insertion_rules:
+				(await store.get<InsertionRule[]>("insertion_rules")) ?? [],
+			output_mode: (await store.get<OutputMode>("output_mode")) ?? "insert",
+			blocked_apps: (await store.get<string[]>("blocked_apps")) ?? [],
+			clipboard_restore_delay_ms:
+				(await store.get<number>("clipboard_restore_delay_ms")) ??
+				DEFAULT_CLIPBOARD_RESTORE_DELAY_MS,
+			copy_after_insert:
+				(await store.get<boolean>("copy_after_insert")) ?? false,
+			notes_enabled: (await store.get<boolean>("notes_enabled")) ?? false,
+			notes_file_path:
+				(await store.get<string | null>("notes_file_path")) ?? null,
+			notes_also_insert:
+				(await store.get<boolean>("notes_also_insert")) ?? false,
+			notes_include_app:
+				(await store.get<boolean>("notes_include_app")) ?? true,
+			voice_commands_enabled:
+				(await store.get<boolean>("voice_commands_enabled")) ?? true,
+			voice_commands:
+				(await store.get<VoiceCommand[]>("voice_commands")) ??
+				DEFAULT_VOICE_COMMANDS,
+			output_style: {
+				...DEFAULT_OUTPUT_STYLE,
+				...(await store.get<Partial<OutputStyle>>("output_style")),
+			},
+			output_style_rules:
+				(await store.get<OutputStyleRule[]>("output_style_rules")) ?? [],
+			text_transforms:
+				(await store.get<TransformRule[]>("text_transforms")) ?? [],
+			server_url: (await store.get<string>("server_url")) ?? DEFAULT_SERVER_URL,
+			fallback_server_urls:
+				(await store.get<string[]>("fallback_server_urls")) ?? [],
+			allow_self_signed_certs:
+				(await store.get<boolean>("allow_self_signed_certs")) ?? false,
+			pinned_cert_sha256:
+				(await store.get<string | null>("pinned_cert_sha256")) ?? null,
+			proxy_url: (await store.get<string | null>("proxy_url")) ?? null,
+			proxy_auth: (await store.get<ProxyAuth | null>("proxy_auth")) ?? null,
+			opus_bitrate_kbps:
+				(await store.get<number>("opus_bitrate_kbps")) ??
+				DEFAULT_OPUS_BITRATE_KBPS,
+			sequence_timeout_ms:
+				(await store.get<number>("sequence_timeout_ms")) ??
+				DEFAULT_SEQUENCE_TIMEOUT_MS,
+			hold_latch_enabled:
+				(await store.get<boolean>("hold_latch_enabled")) ?? false,
+			hold_latch_max_seconds:
+				(await store.get<number>("hold_latch_max_seconds")) ??
+				DEFAULT_HOLD_LATCH_MAX_SECONDS,
+			max_concurrent_turns:
+				(await store.get<number>("max_concurrent_turns")) ??
+				DEFAULT_MAX_CONCURRENT_TURNS,
+			max_prompt_tokens:
+				(await store.get<number>("max_prompt_tokens")) ??
+				DEFAULT_MAX_PROMPT_TOKENS,
+			prompt_truncation:
+				(await store.get<PromptTruncation>("prompt_truncation")) ?? "trim",
+			auto_stop_silence_seconds:
+				(await store.get<number | null>("auto_stop_silence_seconds")) ?? null,
+			max_recording_seconds:
+				(await store.get<number>("max_recording_seconds")) ??
+				DEFAULT_MAX_RECORDING_SECONDS,
+			pre_roll_ms:
+				(await store.get<number>("pre_roll_ms")) ?? DEFAULT_PRE_ROLL_MS,
+			archive_recordings:
+				(await store.get<boolean>("archive_recordings")) ?? false,
+			keep_last_recording:
+				(await store.get<boolean>("keep_last_recording")) ?? true,
+			retry_stt_provider:
+				(await store.get<string | null>("retry_stt_provider")) ?? null,
+			recording_max_count:
+				(await store.get<number>("recording_max_count")) ??
+				DEFAULT_RECORDING_MAX_COUNT,
+			recording_max_days:
+				(await store.get<number>("recording_max_days")) ??
+				DEFAULT_RECORDING_MAX_DAYS,
+			overlay_position:
+				(await store.get<OverlayPosition>("overlay_position")) ?? null,
+			overlay_monitor: (await store.get<string>("overlay_monitor")) ?? null,
+			overlay_scale: clampOverlayScale(
+				(await store.get<number>("overlay_scale")) ?? DEFAULT_OVERLAY_SCALE,
+			),
+			show_partial_transcripts:
+				(await store.get<boolean>("show_partial_transcripts")) ?? true,
+			update_channel:
+				(await store.get<UpdateChannel>("update_channel")) ?? "stable",
+			telemetry_enabled:
+				(await store.get<boolean>("telemetry_enabled")) ?? false,
+			remote_control_enabled:
+				(await store.get<boolean>("remote_control_enabled")) ?? false,
+			remote_control_port:
+				(await store.get<number>("remote_control_port")) ??
+				DEFAULT_REMOTE_CONTROL_PORT,
+			api_enabled: (await store.get<boolean>("api_enabled")) ?? false,
+			api_port: (await store.get<number>("api_port")) ?? DEFAULT_API_PORT,
+			deep_links_enabled:
+				(await store.get<boolean>("deep_links_enabled")) ?? false,
+		};
+	},
+
+	async updateToggleHotkey(hotkey: HotkeyConfig): Promise<void> {
+		const store = await getStore();
+		await store.set("toggle_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateHoldHotkey(hotkey: HotkeyConfig): Promise<void> {
+		const store = await getStore();
+		await store.set("hold_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updatePasteLastHotkey(hotkey: HotkeyConfig): Promise<void> {
+		const store = await getStore();
+		await store.set("paste_last_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateCycleHistoryHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("cycle_history_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateCycleLanguageHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("cycle_language_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updatePauseResumeHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("pause_resume_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateRetryLastHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("retry_last_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateClipboardOnlyHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("clipboard_only_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateCycleSttProviderHotkey(
+		hotkey: HotkeyConfig | null,
+	): Promise<void> {
+		const store = await getStore();
+		await store.set("cycle_stt_provider_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateToggleFormattingHotkey(
+		hotkey: HotkeyConfig | null,
+	): Promise<void> {
+		const store = await getStore();
+		await store.set("toggle_formatting_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateCyclePromptPresetHotkey(
+		hotkey: HotkeyConfig | null,
+	): Promise<void> {
+		const store = await getStore();
+		await store.set("cycle_prompt_preset_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateOpenPaletteHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("open_palette_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateDoNotDisturbHotkey(hotkey: HotkeyConfig | null): Promise<void> {
+		const store = await getStore();
+		await store.set("do_not_disturb_hotkey", hotkey);
+		await store.save();
+	},
+
+	async updateHidTriggers(bindings: HidBinding[]): Promise<void> {
+		const store = await getStore();
+		await store.set("hid_triggers", bindings);
+		await store.save();
+	},
+
+	/** HID devices that can be bound, other than keyboards and mice */
+	async listHidDevices(): Promise<HidDevice[]> {
+		return invoke("list_hid_devices");
+	},
+
+	/** Wait for a button of the device to be pressed and return its bit */
+	async detectHidButton(device: HidDevice): Promise<number> {
+		return invoke("detect_hid_button", {
+			vendorId: device.vendor_id,
+			productId: device.product_id,
+			usagePage: device.usage_page,
+			usage: device.usage,
+		});
+	},
+
+	async updateSequenceTimeout(timeoutMs: number): Promise<void> {
+		const store = await getStore();
+		await store.set("sequence_timeout_ms", timeoutMs);
+		await store.save();
+	},
+
+	async updateHoldLatchEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("hold_latch_enabled", enabled);
+		await store.save();
+	},
+
+	async updateHoldLatchMaxSeconds(seconds: number): Promise<void> {
+		const store = await getStore();
+		await store.set("hold_latch_max_seconds", seconds);
+		await store.save();
+	},
+
+	async updateAutoStopSilence(seconds: number | null): Promise<void> {
+		const store = await getStore();
+		await store.set("auto_stop_silence_seconds", seconds);
+		await store.save();
+	},
+
+	async updateMaxRecordingSeconds(seconds: number): Promise<void> {
+		const store = await getStore();
+		await store.set("max_recording_seconds", seconds);
+		await store.save();
+	},
+
+	async updatePreRoll(preRollMs: number): Promise<void> {
+		const store = await getStore();
+		await store.set("pre_roll_ms", preRollMs);
+		await store.save();
+	},
+
+	async updateArchiveRecordings(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("archive_recordings", enabled);
+		await store.save();
+	},
+
+	async updateKeepLastRecording(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("keep_last_recording", enabled);
+		await store.save();
+	},
+
+	async updateRetrySttProvider(provider: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("retry_stt_provider", provider);
+		await store.save();
+	},
+
+	async updateRecordingMaxCount(maxCount: number): Promise<void> {
+		const store = await getStore();
+		await store.set("recording_max_count", maxCount);
+		await store.save();
+	},
+
+	async updateRecordingMaxDays(maxDays: number): Promise<void> {
+		const store = await getStore();
+		await store.set("recording_max_days", maxDays);
+		await store.save();
+	},
+
+	async updateOverlayScale(scale: number): Promise<void> {
+		const store = await getStore();
+		await store.set("overlay_scale", clampOverlayScale(scale));
+		await store.save();
+	},
+
+	async updateShowPartialTranscripts(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("show_partial_transcripts", enabled);
+		await store.save();
+	},
+
+	async updateUpdateChannel(channel: UpdateChannel): Promise<void> {
+		const store = await getStore();
+		await store.set("update_channel", channel);
+		await store.save();
+	},
+
+	async updateTelemetryEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("telemetry_enabled", enabled);
+		await store.save();
+	},
+
+	async updateRemoteControlEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("remote_control_enabled", enabled);
+		await store.save();
+	},
+
+	async updateRemoteControlPort(port: number): Promise<void> {
+		const store = await getStore();
+		await store.set("remote_control_port", port);
+		await store.save();
+	},
+
+	async updateApiEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("api_enabled", enabled);
+		await store.save();
+	},
+
+	async updateApiPort(port: number): Promise<void> {
+		const store = await getStore();
+		await store.set("api_port", port);
+		await store.save();
+	},
+
+	async updateDeepLinksEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("deep_links_enabled", enabled);
+		await store.save();
+	},
+
+	/** Token remote control and API clients must send, generated on first use */
+	async getRemoteControlToken(): Promise<string> {
+		return invoke("get_remote_control_token");
+	},
+
+	async regenerateRemoteControlToken(): Promise<string> {
+		return invoke("regenerate_remote_control_token");
+	},
+
+	async updateMaxConcurrentTurns(maxTurns: number): Promise<void> {
+		const store = await getStore();
+		await store.set("max_concurrent_turns", maxTurns);
+		await store.save();
+	},
+
+	async updateMaxPromptTokens(maxTokens: number): Promise<void> {
+		const store = await getStore();
+		await store.set("max_prompt_tokens", maxTokens);
+		await store.save();
+	},
+
+	async updatePromptTruncation(truncation: PromptTruncation): Promise<void> {
+		const store = await getStore();
+		await store.set("prompt_truncation", truncation);
+		await store.save();
+	},
+
+	async updatePreferredMics(micIds: string[]): Promise<void> {
+		const store = await getStore();
+		await store.set("preferred_mic_ids", micIds);
+		// Replaced by the list
+		await store.delete("selected_mic_id");
+		await store.save();
+	},
+
+	async updateSoundSettings(sounds: SoundSettings): Promise<void> {
+		const store = await getStore();
+		await store.set("sound_settings", sounds);
+		// Replaced by per-event settings
+		await store.delete("sound_enabled");
+		await store.save();
+	},
+
+	async updateSoundTheme(theme: SoundTheme): Promise<void> {
+		const store = await getStore();
+		await store.set("sound_theme", theme);
+		await store.save();
+	},
+
+	async updateCleanupPromptSections(
+		sections: CleanupPromptSections | null,
+	): Promise<void> {
+		const store = await getStore();
+		await store.set("cleanup_prompt_sections", sections);
+		await store.save();
+	},
+
+	async updateSTTProvider(provider: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("stt_provider", provider);
+		await store.save();
+	},
+
+	async updateSTTFallbackProvider(provider: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("stt_fallback_provider", provider);
+		await store.save();
+	},
+
+	async refreshSttProvider(): Promise<string> {
+		return invoke("refresh_stt_provider");
+	},
+
+	/** Switch to the next STT provider the server offers */
+	async cycleSttProvider(): Promise<ProviderInfo> {
+		return invoke("cycle_stt_provider");
+	},
+
+	async updateWhisperModel(model: WhisperModel): Promise<void> {
+		const store = await getStore();
+		await store.set("whisper_model", model);
+		await store.save();
+	},
+
+	// Local Whisper models
+	async listWhisperModels(): Promise<WhisperModelStatus[]> {
+		return invoke("list_whisper_models");
+	},
+
+	/** Resolves once the model is downloaded; progress arrives as events */
+	async downloadWhisperModel(model: WhisperModel): Promise<void> {
+		return invoke("download_whisper_model", { model });
+	},
+
+	async deleteWhisperModel(model: WhisperModel): Promise<boolean> {
+		return invoke("delete_whisper_model", { model });
+	},
+
+	/** Whether this build can update itself */
+	async isUpdaterAvailable(): Promise<boolean> {
+		return invoke("is_updater_available");
+	},
+
+	/** Check the selected channel for a newer release, null when up to date */
+	async checkForUpdates(): Promise<UpdateInfo | null> {
+		return invoke("check_for_updates");
+	},
+
+	/** Install the update found by the last check and restart */
+	async installUpdate(): Promise<void> {
+		return invoke("install_update");
+	},
+
+	async onUpdateDownloadProgress(
+		callback: (progress: UpdateProgress) => void,
+	): Promise<UnlistenFn> {
+		return listen<UpdateProgress>("update-download-progress", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onWhisperModelDownloadProgress(
+		callback: (progress: ModelDownloadProgress) => void,
+	): Promise<UnlistenFn> {
+		return listen<ModelDownloadProgress>(
+			"whisper-model-download-progress",
+			(event) => {
+				callback(event.payload);
+			},
+		);
+	},
+
+	/** Send captured microphone samples to a provider that transcribes in-app */
+	async sendSttAudio(samples: number[]): Promise<void> {
+		return invoke("send_stt_audio", { samples });
+	},
+
+	async updateLLMProvider(provider: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("llm_provider", provider);
+		await store.save();
+	},
+
+	async updateAutoMuteAudio(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("auto_mute_audio", enabled);
+		await store.save();
+	},
+
+	async updateAudioDuckPercent(percent: number): Promise<void> {
+		const store = await getStore();
+		await store.set(
+			"audio_duck_percent",
+			Math.round(Math.min(Math.max(percent, 0), MAX_AUDIO_DUCK_PERCENT)),
+		);
+		await store.save();
+	},
+
+	async updateMicGain(gain: number): Promise<void> {
+		const store = await getStore();
+		await store.set("mic_gain", clampMicGain(gain));
+		await store.save();
+	},
+
+	async updateAgcEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("agc_enabled", enabled);
+		await store.save();
+	},
+
+	async updateNativeAudioCapture(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("native_audio_capture", enabled);
+		await store.save();
+	},
+
+	async updateNoiseSuppressionEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("noise_suppression_enabled", enabled);
+		await store.save();
+	},
+
+	async updateEchoCancellationEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("echo_cancellation_enabled", enabled);
+		await store.save();
+	},
+
+	/** The formats a microphone can be captured in, the default one when null */
+	async listCaptureFormats(
+		deviceLabel: string | null,
+	): Promise<CaptureFormats> {
+		return invoke("list_capture_formats", { deviceLabel });
+	},
+
+	/** Save the format to capture in if the microphone supports it */
+	async updateCaptureFormat(
+		deviceLabel: string | null,
+		sampleRate: number | null,
+		channels: number | null,
+	): Promise<void> {
+		return invoke("update_capture_format", {
+			deviceLabel,
+			sampleRate,
+			channels,
+		});
+	},
+
+	/**
+	 * Open a backend microphone by its webview label, returning its name.
+	 * Its 16 kHz mono frames arrive on onFrame, sent as 16-bit PCM.
+	 */
+	async startNativeCapture(
+		deviceLabel: string | null,
+		onFrame: (samples: Float32Array) => void,
+	): Promise<string> {
+		const frames = new Channel<ArrayBuffer>();
+		frames.onmessage = (pcm) => {
+			onFrame(Float32Array.from(new Int16Array(pcm), (s) => s / 32767));
+		};
+		return invoke("start_native_capture", { deviceLabel, frames });
+	},
+
+	async stopNativeCapture(): Promise<void> {
+		return invoke("stop_native_capture");
+	},
+
+	async updateSTTTimeout(timeoutSeconds: number | null): Promise<void> {
+		const store = await getStore();
+		await store.set("stt_timeout_seconds", timeoutSeconds);
+		await store.save();
+	},
+
+	async updateShortUtteranceMaxWords(maxWords: number | null): Promise<void> {
+		const store = await getStore();
+		await store.set(
+			"short_utterance_max_words",
+			maxWords === null
+				? null
+				: Math.round(
+						Math.min(Math.max(maxWords, 0), MAX_SHORT_UTTERANCE_MAX_WORDS),
+					),
+		);
+		await store.save();
+	},
+
+	async updateFormattingEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("formatting_enabled", enabled);
+		await store.save();
+	},
+
+	async updateLanguage(language: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("language", language);
+		await store.save();
+	},
+
+	async updateAutoDetectLanguage(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("auto_detect_language", enabled);
+		await store.save();
+	},
+
+	async updateDictationLanguages(languages: string[]): Promise<void> {
+		const store = await getStore();
+		await store.set("dictation_languages", languages);
+		await store.save();
+	},
+
+	async updateMaxInsertionChars(maxChars: number): Promise<void> {
+		const store = await getStore();
+		await store.set("max_insertion_chars", maxChars);
+		await store.save();
+	},
+
+	async updateNewlineRules(rules: NewlineRule[]): Promise<void> {
+		const store = await getStore();
+		await store.set("newline_rules", rules);
+		await store.save();
+	},
+
+	async updateInsertionMethod(method: InsertionMethod): Promise<void> {
+		const store = await getStore();
+		await store.set("insertion_method", method);
+		await store.save();
+	},
+
+	async updateInsertionRules(rules: InsertionRule[]): Promise<void> {
+		const store = await getStore();
+		await store.set("insertion_rules", rules);
+		await store.save();
+	},
+
+	async updateBlockedApps(apps: string[]): Promise<void> {
+		const store = await getStore();
+		await store.set("blocked_apps", apps);
+		await store.save();
+	},
+
+	async updateClipboardRestoreDelay(delayMs: number): Promise<void> {
+		const store = await getStore();
+		await store.set("clipboard_restore_delay_ms", delayMs);
+		await store.save();
+	},
+
+	async updateOutputMode(mode: OutputMode): Promise<void> {
+		const store = await getStore();
+		await store.set("output_mode", mode);
+		await store.save();
+	},
+
+	async updateCopyAfterInsert(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("copy_after_insert", enabled);
+		await store.save();
+	},
+
+	async updateNotesEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("notes_enabled", enabled);
+		await store.save();
+	},
+
+	async updateNotesFilePath(path: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("notes_file_path", path);
+		await store.save();
+	},
+
+	async updateNotesAlsoInsert(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("notes_also_insert", enabled);
+		await store.save();
+	},
+
+	async updateNotesIncludeApp(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("notes_include_app", enabled);
+		await store.save();
+	},
+
+	/** The file dictations are appended to in notes mode */
+	async getNotesFilePath(): Promise<string> {
+		return invoke("get_notes_file_path");
+	},
+
+	async updateVoiceCommandsEnabled(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("voice_commands_enabled", enabled);
+		await store.save();
+	},
+
+	async updateVoiceCommands(commands: VoiceCommand[]): Promise<void> {
+		const store = await getStore();
+		await store.set("voice_commands", commands);
+		await store.save();
+	},
+
+	async updateOutputStyle(style: OutputStyle): Promise<void> {
+		const store = await getStore();
+		await store.set("output_style", style);
+		await store.save();
+	},
+
+	async updateOutputStyleRules(rules: OutputStyleRule[]): Promise<void> {
+		const store = await getStore();
+		await store.set("output_style_rules", rules);
+		await store.save();
+	},
+
+	async updateTextTransforms(rules: TransformRule[]): Promise<void> {
+		const store = await getStore();
+		await store.set("text_transforms", rules);
+		await store.save();
+	},
+
+	/** Try one rule on sample text, enabled or not */
+	async testTextTransform(
+		rule: TransformRule,
+		sample: string,
+	): Promise<TransformTest> {
+		return invoke("test_text_transform", { rule, sample });
+	},
+
+	/** Run rules over sample text as a dictation would be */
+	async testTextTransforms(
+		rules: TransformRule[],
+		sample: string,
+	): Promise<string> {
+		return invoke("test_text_transforms", { rules, sample });
+	},
+
+	async isAccessibilityInsertionSupported(): Promise<boolean> {
+		return invoke("is_accessibility_insertion_supported");
+	},
+
+	async getInsertionDiagnostics(): Promise<InsertionDiagnostics> {
+		return invoke("get_insertion_diagnostics");
+	},
+
+	async updateServerUrl(url: string): Promise<void> {
+		const store = await getStore();
+		await store.set("server_url", url);
+		await store.save();
+	},
+
+	async updateFallbackServerUrls(urls: string[]): Promise<void> {
+		const store = await getStore();
+		await store.set("fallback_server_urls", urls);
+		await store.save();
+	},
+
+	async updateAllowSelfSignedCerts(enabled: boolean): Promise<void> {
+		const store = await getStore();
+		await store.set("allow_self_signed_certs", enabled);
+		await store.save();
+	},
+
+	async updatePinnedCertSha256(fingerprint: string | null): Promise<void> {
+		const store = await getStore();
+		await store.set("pinned_cert_sha256", fingerprint);
+		await store.save();
+	},
+
+	/** Check and save the proxy, turning it off when the URL is null */
+	async updateProxySettings(
+		proxyUrl: string | null,
+		proxyAuth: ProxyAuth | null,
+	): Promise<void> {
+		return invoke("update_proxy_settings", { proxyUrl, proxyAuth });
+	},
+
+	async updateOpusBitrate(kbps: number): Promise<void> {
+		const store = await getStore();
+		await store.set("opus_bitrate_kbps", kbps);
+		await store.save();
+	},
+
+	/** Check a server's health and capabilities, the server URL by default */
+	async testServerConnection(url?: string): Promise<ConnectionTest> {
+		return invoke("test_server_connection", { url: url ?? null });
+	},
+
+	async isAudioMuteSupported(): Promise<boolean> {
+		return invoke("is_audio_mute_supported");
+	},
+
+	/** Report the overlay's input devices and get the microphone to use */
+	async reportAudioDevices(devices: AudioInputDevice[]): Promise<ActiveMic> {
+		return invoke("report_audio_devices", { devices });
+	},
+
+	async onMicFallbackChanged(
+		callback: (change: MicFallbackChange) => void,
+	): Promise<UnlistenFn> {
+		return listen<MicFallbackChange>("mic-fallback-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	/** A recording was stopped at the length limit and is being transcribed */
+	async onRecordingLimitReached(
+		callback: (maxSeconds: number) => void,
+	): Promise<UnlistenFn> {
+		return listen<number>("recording-limit-reached", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	/** macOS secure input was on, so a dictation or paste wasn't inserted */
+	async onSecureInputBlocked(callback: () => void): Promise<UnlistenFn> {
+		return listen("secure-input-blocked", () => {
+			callback();
+		});
+	},
+
+	/** Share an interim transcript from the server with the overlay */
+	async reportTranscriptionPartial(text: string): Promise<void> {
+		return invoke("report_transcription_partial", { text });
+	},
+
+	async onTranscriptionPartial(
+		callback: (text: string) => void,
+	): Promise<UnlistenFn> {
+		return listen<{ text: string }>("transcription-partial", (event) => {
+			callback(event.payload.text);
+		});
+	},
+
+	async onFormattingPartial(
+		callback: (text: string) => void,
+	): Promise<UnlistenFn> {
+		return listen<{ text: string }>("formatting-partial", (event) => {
+			callback(event.payload.text);
+		});
+	},
+
+	async onSttProviderChanged(
+		callback: (provider: ProviderInfo) => void,
+	): Promise<UnlistenFn> {
+		return listen<ProviderInfo>("stt-provider-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onFormattingChanged(
+		callback: (enabled: boolean) => void,
+	): Promise<UnlistenFn> {
+		return listen<boolean>("formatting-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onPromptPresetChanged(
+		callback: (preset: PromptPreset) => void,
+	): Promise<UnlistenFn> {
+		return listen<PromptPreset>("prompt-preset-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onClipboardOnlyChanged(
+		callback: (clipboardOnly: boolean) => void,
+	): Promise<UnlistenFn> {
+		return listen<boolean>("clipboard-only-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onDictationPausedChanged(
+		callback: (paused: boolean) => void,
+	): Promise<UnlistenFn> {
+		return listen<boolean>("dictation-paused-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	async onRecordingPaused(
+		callback: (paused: boolean) => void,
+	): Promise<UnlistenFn> {
+		return listen<boolean>("recording-paused", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	/** Tell Rust whether the overlay could open the microphone */
+	/** The server answered, or was given up on, for the `count` oldest turns */
+	async reportServerTurnsFinished(count: number): Promise<void> {
+		return invoke("report_server_turns_finished", { count });
+	},
+
+	async reportMicPermission(
+		granted: boolean,
+		prompted: boolean,
+	): Promise<void> {
+		return invoke("report_mic_permission", { granted, prompted });
+	},
+
+	/** Ask for microphone access again, via the OS prompt or privacy settings */
+	async requestMicPermission(): Promise<void> {
+		return invoke("request_mic_permission");
+	},
+
+	async onMicPermissionPrompt(callback: () => void): Promise<UnlistenFn> {
+		return listen("mic-permission-prompt", () => {
+			callback();
+		});
+	},
+
+	async onMicPermissionChanged(
+		callback: (granted: boolean) => void,
+	): Promise<UnlistenFn> {
+		return listen<{ granted: boolean }>("mic-permission-changed", (event) => {
+			callback(event.payload.granted);
+		});
+	},
+
+	async getDefaultHotkeys(): Promise<DefaultHotkeys> {
+		return invoke("get_default_hotkeys");
+	},
+
+	async listFeatureFlags(): Promise<FeatureFlagState[]> {
+		return invoke("list_feature_flags");
+	},
+
+	async setFeatureFlag(
+		flag: FeatureFlag,
+		enabled: boolean,
+	): Promise<FeatureFlagState[]> {
+		return invoke("set_feature_flag", { flag, enabled });
+	},
+
+	async resetHotkeysToDefaults(): Promise<void> {
+		const defaults = await tauriAPI.getDefaultHotkeys();
+		const store = await getStore();
+		await store.set("toggle_hotkey", defaults.toggle_hotkey);
+		await store.set("hold_hotkey", defaults.hold_hotkey);
+		await store.set("paste_last_hotkey", defaults.paste_last_hotkey);
+		await store.set("cycle_history_hotkey", null);
+		await store.set("cycle_language_hotkey", null);
+		await store.set("pause_resume_hotkey", null);
+		await store.set("retry_last_hotkey", null);
+		await store.set("clipboard_only_hotkey", null);
+		await store.set("cycle_stt_provider_hotkey", null);
+		await store.set("toggle_formatting_hotkey", null);
+		await store.set("cycle_prompt_preset_hotkey", null);
+		await store.set("open_palette_hotkey", null);
+		await store.set("do_not_disturb_hotkey", null);
+		await store.save();
+	},
+
+	async registerShortcuts(): Promise<void> {
+		return invoke("register_shortcuts");
+	},
+
+	async unregisterShortcuts(): Promise<void> {
+		return invoke("unregister_shortcuts");
+	},
+
+	async testHotkeyAvailability(
+		hotkey: HotkeyConfig,
+	): Promise<HotkeyAvailability> {
+		return invoke("test_hotkey_availability", { hotkey });
+	},
+
+	async onShortcutRegistrationFailed(
+		callback: (failures: ShortcutRegistrationFailure[]) => void,
+	): Promise<UnlistenFn> {
+		return listen<ShortcutRegistrationFailure[]>(
+			"shortcut-registration-failed",
+			(event) => {
+				callback(event.payload);
+			},
+		);
+	},
+
+	// History API
+	/** Check that a file can be used as a feedback sound */
+	async validateSoundFile(path: string): Promise<void> {
+		return invoke("validate_sound_file", { path });
+	},
+
+	/** Play the chosen sound for an event, even if sounds are turned off */
+	async previewSound(soundType: SoundType): Promise<void> {
+		return invoke("preview_sound", { soundType });
+	},
+
+	/** Tell Rust a turn failed so the error sound can play */
+	async reportTurnFailed(
+		error: string,
+		category?: FailureCategory,
+	): Promise<void> {
+		return invoke("report_turn_failed", { error, category });
+	},
+
+	async addHistoryEntry(
+		text: string,
+		durationMs?: number,
+		rawText?: string,
+	): Promise<HistoryEntry> {
+		return invoke("add_history_entry", { text, durationMs, rawText });
+	},
+
+	async getHistory({
+		page,
+		pageSize,
+		query,
+	}: HistoryQuery = {}): Promise<HistoryPage> {
+		return invoke("get_history", { page, pageSize, query: query || null });
+	},
+
+	async deleteHistoryEntry(id: string): Promise<boolean> {
+		return invoke("delete_history_entry", { id });
+	},
+
+	/** Clear the history, keeping pinned entries */
+	async clearHistory(): Promise<void> {
+		return invoke("clear_history");
+	},
+
+	async pinHistoryEntry(id: string, pinned: boolean): Promise<boolean> {
+		return invoke("pin_history_entry", { id, pinned });
+	},
+
+	/** Pinned entries, newest first */
+	async listPinned(): Promise<HistoryEntry[]> {
+		return invoke("list_pinned");
+	},
+
+	/** Format an entry's raw transcript again with the current settings */
+	async reRunWithCurrentSettings(id: string): Promise<ReRunResult> {
+		return invoke("re_run_with_current_settings", { id });
+	},
+
+	/** Word-level diff between an entry's raw transcript and its formatted text */
+	async getTurnDiff(id: string): Promise<DiffSegment[]> {
+		return invoke("get_turn_diff", { id });
+	},
+
+	/** Replace the last dictation in the focused app and history with a correction */
+	async correctLastEntry(
+		text: string,
+		addToDictionary: boolean,
+	): Promise<WordCorrection[]> {
+		return invoke("correct_last_entry", { text, addToDictionary });
+	},
+
+	// Personal dictionary API
+	async listDictionaryEntries(): Promise<DictionaryEntry[]> {
+		return invoke("list_dictionary_entries");
+	},
+
+	/** Add an entry; an empty written form adds the spoken form as a term */
+	async addDictionaryEntry(
+		spoken: string,
+		written: string,
+		caseSensitive: boolean,
+	): Promise<DictionaryEntry> {
+		return invoke("add_dictionary_entry", { spoken, written, caseSensitive });
+	},
+
+	async updateDictionaryEntry(
+		entry: DictionaryEntry,
+	): Promise<DictionaryEntry> {
+		return invoke("update_dictionary_entry", {
+			id: entry.id,
+			spoken: entry.spoken,
+			written: entry.written,
+			caseSensitive: entry.case_sensitive,
+		});
+	},
+
+	async removeDictionaryEntry(id: string): Promise<boolean> {
+		return invoke("remove_dictionary_entry", { id });
+	},
+
+	/** Import entries from a file, or preview the import with `dryRun` */
+	async importDictionary(
+		path: string,
+		format: DictionaryImportFormat,
+		dryRun: boolean,
+	): Promise<DictionaryImport> {
+		return invoke("import_dictionary", { path, format, dryRun });
+	},
+
+	async onDictionaryChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("dictionary-changed", () => {
+			callback();
+		});
+	},
+
+	// Snippets API
+	async listSnippets(): Promise<Snippet[]> {
+		return invoke("list_snippets");
+	},
+
+	async addSnippet(trigger: string, template: string): Promise<Snippet> {
+		return invoke("add_snippet", { trigger, template });
+	},
+
+	async updateSnippet(snippet: Snippet): Promise<Snippet> {
+		return invoke("update_snippet", {
+			id: snippet.id,
+			trigger: snippet.trigger,
+			template: snippet.template,
+		});
+	},
+
+	async removeSnippet(id: string): Promise<boolean> {
+		return invoke("remove_snippet", { id });
+	},
+
+	async onSnippetsChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("snippets-changed", () => {
+			callback();
+		});
+	},
+
+	/** Dictionary entries suggested from corrections, most often corrected first */
+	async getDictionarySuggestions(): Promise<DictionarySuggestion[]> {
+		return invoke("get_dictionary_suggestions");
+	},
+
+	/** Stop offering a suggestion once it is added or dismissed */
+	async resolveDictionarySuggestion(
+		suggestion: WordCorrection,
+	): Promise<boolean> {
+		return invoke("resolve_dictionary_suggestion", {
+			heard: suggestion.heard,
+			corrected: suggestion.corrected,
+		});
+	},
+
+	async onDictionarySuggested(
+		callback: (suggestions: DictionarySuggestion[]) => void,
+	): Promise<UnlistenFn> {
+		return listen<DictionarySuggestion[]>("dictionary-suggested", (event) => {
+			callback(event.payload);
+		});
+	},
+
+	// Prompt presets API
+	async listPromptPresets(): Promise<PromptPresets> {
+		return invoke("list_prompt_presets");
+	},
+
+	/** Save the current prompt as a new preset, or over the preset with `id` */
+	async savePromptPreset(name: string, id?: string): Promise<PromptPreset> {
+		return invoke("save_prompt_preset", { name, id: id ?? null });
+	},
+
+	async renamePromptPreset(id: string, name: string): Promise<PromptPreset> {
+		return invoke("rename_prompt_preset", { id, name });
+	},
+
+	async deletePromptPreset(id: string): Promise<boolean> {
+		return invoke("delete_prompt_preset", { id });
+	},
+
+	async applyPromptPreset(id: string): Promise<PromptPreset> {
+		return invoke("apply_prompt_preset", { id });
+	},
+
+	/** Export presets, all unless `ids` are given; returns the path written */
+	async exportPromptPresets(path?: string, ids?: string[]): Promise<string> {
+		return invoke("export_prompt_presets", {
+			path: path ?? null,
+			ids: ids ?? null,
+		});
+	},
+
+	async importPromptPresets(path: string): Promise<PresetImport> {
+		return invoke("import_prompt_presets", { path });
+	},
+
+	// Recording archive API
+	async listRecordings(): Promise<RecordingInfo[]> {
+		return invoke("list_recordings");
+	},
+
+	async deleteRecording(id: string): Promise<boolean> {
+		return invoke("delete_recording", { id });
+	},
+
+	async onRecordingsChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("recordings-changed", () => {
+			callback();
+		});
+	},
+
+	/** Dictations recorded while the server was unreachable, oldest first */
+	async listPendingTranscriptions(): Promise<RecordingInfo[]> {
+		return invoke("list_pending_transcriptions");
+	},
+
+	/** Transcribe queued dictations now, returning how many produced text */
+	async retryPendingTranscriptions(): Promise<number> {
+		return invoke("retry_pending_transcriptions");
+	},
+
+	/**
+	 * Transcribe the last recording again and insert it into the focused app,
+	 * with the given STT provider or the one chosen for retries
+	 */
+	async retryLastTranscription(provider: string | null = null): Promise<void> {
+		return invoke("retry_last_transcription", { provider });
+	},
 
-// ============================================================================
-// Default values - must match Rust defaults
-// ============================================================================
+	async onPendingTranscriptionsChanged(
+		callback: () => void,
+	): Promise<UnlistenFn> {
+		return listen("pending-transcriptions-changed", () => {
+			callback();
+		});
+	},
 
-const DEFAULT_HOTKEY_MODIFIERS = ["ctrl", "alt"];
+	/** Data files found corrupt at startup and moved aside */
+	async getDataFileIssues(): Promise<DataFileIssue[]> {
+		return invoke("get_data_file_issues");
+	},
 
-export const defaultToggleHotkey: HotkeyConfig = {
-	modifiers: DEFAULT_HOTKEY_MODIFIERS,
-	key: "Space",
-};
+	async dismissDataFileIssues(): Promise<void> {
+		return invoke("dismiss_data_file_issues");
+	},
 
-export const defaultHoldHotkey: HotkeyConfig = {
-	modifiers: DEFAULT_HOTKEY_MODIFIERS,
-	key: "Backquote",
-};
+	async onDataFileIssuesChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("data-file-issues-changed", () => {
+			callback();
+		});
+	},
 
-export const defaultPasteLastHotkey: HotkeyConfig = {
-	modifiers: DEFAULT_HOTKEY_MODIFIERS,
-	key: "Period",
-};
+	async onQueuedAudioRequest(
+		callback: (request: QueuedAudioRequest) => void,
+	): Promise<UnlistenFn> {
+		return listen<QueuedAudioRequest>("queued-audio-request", (event) => {
+			callback(event.payload);
+		});
+	},
 
-// ============================================================================
-// Store helpers
-// ============================================================================
+	/** Paste the transcription `index` entries back (0 is the most recent) */
+	async pasteHistoryEntry(index: number): Promise<void> {
+		return invoke("paste_history_entry", { index });
+	},
 
-let storeInstance: Store | null = null;
+	// API key storage (OS keyring)
+	async setApiKey(provider: string, apiKey: string): Promise<void> {
+		return invoke("set_api_key", { provider, apiKey });
+	},
 
-async function getStore(): Promise<Store> {
-	if (!storeInstance) {
-		storeInstance = await Store.load("settings.json");
-	}
-	return storeInstance;
-}
+	async getApiKeyStatus(provider: string): Promise<ApiKeyStatus> {
+		return invoke("get_api_key_status", { provider });
+	},
 
-// ============================================================================
-// Hotkey validation helpers (Zod-based)
-// ============================================================================
+	async deleteApiKey(provider: string): Promise<boolean> {
+		return invoke("delete_api_key", { provider });
+	},
 
-/**
- * Check if two hotkey configs are equivalent (case-insensitive comparison)
- */
-export function hotkeyIsSameAs(a: HotkeyConfig, b: HotkeyConfig): boolean {
-	if (a.key.toLowerCase() !== b.key.toLowerCase()) return false;
-	if (a.modifiers.length !== b.modifiers.length) return false;
-	return a.modifiers.every((mod) =>
-		b.modifiers.some((other) => mod.toLowerCase() === other.toLowerCase()),
-	);
-}
+	// Usage statistics (kept apart from history)
+	async getUsageStats(period: UsagePeriod): Promise<UsageStats> {
+		return invoke("get_usage_stats", { period });
+	},
 
-type HotkeyType = "toggle" | "hold" | "paste_last";
+	async resetUsageStats(): Promise<void> {
+		return invoke("reset_usage_stats");
+	},
 
-const HOTKEY_LABELS: Record<HotkeyType, string> = {
-	toggle: "toggle",
-	hold: "hold",
-	paste_last: "paste last",
-};
+	/** Write history and usage statistics to `path`, or the downloads folder */
+	async exportData(
+		format: ExportFormat,
+		range: UsagePeriod,
+		path?: string,
+	): Promise<string> {
+		return invoke("export_data", { format, range, path: path ?? null });
+	},
 
-/**
- * Create a Zod schema for validating a hotkey doesn't conflict with existing hotkeys
- */
-export function createHotkeyDuplicateSchema(
-	allHotkeys: Record<HotkeyType, HotkeyConfig>,
-	excludeType: HotkeyType,
-) {
-	return HotkeyConfigSchema.superRefine((hotkey, ctx) => {
-		for (const [type, existing] of Object.entries(allHotkeys)) {
-			if (type !== excludeType && hotkeyIsSameAs(hotkey, existing)) {
-				ctx.addIssue({
-					code: "custom",
-					message: `This shortcut is already used for the ${HOTKEY_LABELS[type as HotkeyType]} hotkey`,
-				});
-				return;
-			}
-		}
-	});
-}
+	async onUsageStatsChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("usage-stats-changed", () => {
+			callback();
+		});
+	},
 
-/**
- * Validate that a hotkey doesn't conflict with other hotkeys
- * Returns error message if invalid, null if valid
- */
-export function validateHotkeyNotDuplicate(
-	newHotkey: HotkeyConfig,
-	allHotkeys: {
-		toggle: HotkeyConfig;
-		hold: HotkeyConfig;
-		paste_last: HotkeyConfig;
+	async getTelemetry(): Promise<TelemetryReport> {
+		return invoke("get_telemetry");
 	},
-	excludeType: HotkeyType,
-): string | null {
-	const schema = createHotkeyDuplicateSchema(allHotkeys, excludeType);
-	const result = schema.safeParse(newHotkey);
-	if (!result.success) {
-		return result.error.issues[0]?.message ?? "Invalid hotkey";
-	}
-	return null;
-}
 
-// ============================================================================
-// Tauri API
-// ============================================================================
+	async purgeTelemetry(): Promise<void> {
+		return invoke("purge_telemetry");
+	},
 
-export const tauriAPI = {
-	async typeText(text: string): Promise<TypeTextResult> {
-		try {
-			await invoke("type_text", { text });
-			return { success: true };
-		} catch (error) {
-			return { success: false, error: String(error) };
-		}
+	/** Zip logs, redacted settings and environment details for a bug report */
+	async generateSupportBundle(path?: string): Promise<string> {
+		return invoke("generate_support_bundle", { path: path ?? null });
 	},
 
-	async getServerUrl(): Promise<string> {
-		return invoke("get_server_url");
+	async runDiagnostics(): Promise<DiagnosticsReport> {
+		return invoke("run_diagnostics");
 	},
 
-	async onStartRecording(callback: () => void): Promise<UnlistenFn> {
-		return listen("recording-start", callback);
+	async runLatencyBenchmark(
+		source: BenchmarkSource,
+	): Promise<LatencyBenchmark> {
+		return invoke("run_latency_benchmark", { source });
 	},
 
-	async onStopRecording(callback: () => void): Promise<UnlistenFn> {
-		return listen("recording-stop", callback);
+	async onTelemetryChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("telemetry-changed", () => {
+			callback();
+		});
 	},
 
-	// Settings API - using store plugin directly
-	async getSettings(): Promise<AppSettings> {
-		const store = await getStore();
-		return {
-			toggle_hotkey:
-				(await store.get<HotkeyConfig>("toggle_hotkey")) ?? defaultToggleHotkey,
-			hold_hotkey:
-				(await store.get<HotkeyConfig>("hold_hotkey")) ?? defaultHoldHotkey,
-			paste_last_hotkey:
-				(await store.get<HotkeyConfig>("paste_last_hotkey")) ??
-				defaultPasteLastHotkey,
-			selected_mic_id:
-				(await store.get<string | null>("selected_mic_id")) ?? null,
-			sound_enabled: (await store.get<boolean>("sound_enabled")) ?? true,
-			cleanup_prompt_sections:
-				(await store.get<CleanupPromptSections | null>(
-					"cleanup_prompt_sections",
-				)) ?? null,
-			stt_provider: (await store.get<string | null>("stt_provider")) ?? null,
-			llm_provider: (await store.get<string | null>("llm_provider")) ?? null,
-			auto_mute_audio: (await store.get<boolean>("auto_mute_audio")) ?? false,
-			stt_timeout_seconds:
-				(await store.get<number | null>("stt_timeout_seconds")) ?? null,
-			server_url: (await store.get<string>("server_url")) ?? DEFAULT_SERVER_URL,
-		};
+	// Server auth token (OS keyring, refreshed by the backend)
+	async setAuthToken(
+		accessToken: string,
+		refreshToken: string | null,
+		expiresInSeconds: number | null,
+	): Promise<AuthStatus> {
+		return invoke("set_auth_token", {
+			accessToken,
+			refreshToken,
+			expiresInSeconds,
+		});
 	},
 
-	async updateToggleHotkey(hotkey: HotkeyConfig): Promise<void> {
-		const store = await getStore();
-		await store.set("toggle_hotkey", hotkey);
-		await store.save();
+	async getAuthStatus(): Promise<AuthStatus> {
+		return invoke("get_auth_status");
 	},
 
-	async updateHoldHotkey(hotkey: HotkeyConfig): Promise<void> {
-		const store = await getStore();
-		await store.set("hold_hotkey", hotkey);
-		await store.save();
+	async clearAuthToken(): Promise<boolean> {
+		return invoke("clear_auth_token");
 	},
 
-	async updatePasteLastHotkey(hotkey: HotkeyConfig): Promise<void> {
-		const store = await getStore();
-		await store.set("paste_last_hotkey", hotkey);
-		await store.save();
+	/** `Authorization` header value for connecting, null without a valid token */
+	async getAuthHeader(): Promise<string | null> {
+		return invoke("get_auth_header");
 	},
 
-	async updateSelectedMic(micId: string | null): Promise<void> {
-		const store = await getStore();
-		await store.set("selected_mic_id", micId);
-		await store.save();
+	async onAuthStatusChanged(
+		callback: (status: AuthStatus) => void,
+	): Promise<UnlistenFn> {
+		return listen<AuthStatus>("auth-status-changed", (event) => {
+			callback(event.payload);
+		});
 	},
 
-	async updateSoundEnabled(enabled: boolean): Promise<void> {
-		const store = await getStore();
-		await store.set("sound_enabled", enabled);
-		await store.save();
+	// Session transcript API
+	async getSessionTranscript(): Promise<SessionTranscript> {
+		return invoke("get_session_transcript");
 	},
 
-	async updateCleanupPromptSections(
-		sections: CleanupPromptSections | null,
-	): Promise<void> {
-		const store = await getStore();
-		await store.set("cleanup_prompt_sections", sections);
-		await store.save();
+	async resetSessionTranscript(): Promise<SessionTranscript> {
+		return invoke("reset_session_transcript");
 	},
 
-	async updateSTTProvider(provider: string | null): Promise<void> {
-		const store = await getStore();
-		await store.set("stt_provider", provider);
-		await store.save();
+	async exportSessionTranscript(): Promise<string> {
+		return invoke("export_session_transcript");
 	},
 
-	async updateLLMProvider(provider: string | null): Promise<void> {
-		const store = await getStore();
-		await store.set("llm_provider", provider);
-		await store.save();
+	async openSessionWindow(): Promise<void> {
+		return invoke("open_session_window");
 	},
 
-	async updateAutoMuteAudio(enabled: boolean): Promise<void> {
-		const store = await getStore();
-		await store.set("auto_mute_audio", enabled);
-		await store.save();
+	async onSessionEntryAdded(callback: () => void): Promise<UnlistenFn> {
+		return listen("session-entry-added", () => {
+			callback();
+		});
 	},
 
-	async updateSTTTimeout(timeoutSeconds: number | null): Promise<void> {
-		const store = await getStore();
-		await store.set("stt_timeout_seconds", timeoutSeconds);
-		await store.save();
+	async onSessionTranscriptUpdated(callback: () => void): Promise<UnlistenFn> {
+		return listen("session-transcript-updated", () => {
+			callback();
+		});
 	},
 
-	async updateServerUrl(url: string): Promise<void> {
-		const store = await getStore();
-		await store.set("server_url", url);
-		await store.save();
+	// Command palette API
+	async openPaletteWindow(): Promise<void> {
+		return invoke("open_palette_window");
 	},
 
-	async isAudioMuteSupported(): Promise<boolean> {
-		return invoke("is_audio_mute_supported");
+	async hidePaletteWindow(): Promise<void> {
+		return invoke("hide_palette_window");
 	},
 
-	async resetHotkeysToDefaults(): Promise<void> {
-		const store = await getStore();
-		await store.set("toggle_hotkey", defaultToggleHotkey);
-		await store.set("hold_hotkey", defaultHoldHotkey);
-		await store.set("paste_last_hotkey", defaultPasteLastHotkey);
-		await store.save();
+	/** Palette items matching `query`, all of them when it is empty */
+	async listPaletteItems(query: string): Promise<PaletteItem[]> {
+		return invoke("list_palette_items", { query });
 	},
 
-	async registerShortcuts(): Promise<void> {
-		return invoke("register_shortcuts");
+	/** Hide the palette and paste an entry into the app it was opened over */
+	async pasteFromPalette(index: number): Promise<void> {
+		return invoke("paste_from_palette", { index });
 	},
 
-	async unregisterShortcuts(): Promise<void> {
-		return invoke("unregister_shortcuts");
+	async pastePinnedFromPalette(id: string): Promise<void> {
+		return invoke("paste_pinned_from_palette", { id });
 	},
 
-	// History API
-	async addHistoryEntry(text: string): Promise<HistoryEntry> {
-		return invoke("add_history_entry", { text });
+	async selectSttProvider(value: string): Promise<ProviderInfo> {
+		return invoke("select_stt_provider", { value });
 	},
 
-	async getHistory(limit?: number): Promise<HistoryEntry[]> {
-		return invoke("get_history", { limit });
+	/** Turn LLM formatting on or off, returning whether it is now on */
+	async switchFormatting(): Promise<boolean> {
+		return invoke("switch_formatting");
 	},
 
-	async deleteHistoryEntry(id: string): Promise<boolean> {
-		return invoke("delete_history_entry", { id });
+	/** Whether dictation is paused, so recording hotkeys are ignored */
+	async getDictationPaused(): Promise<boolean> {
+		return invoke("get_dictation_paused");
 	},
 
-	async clearHistory(): Promise<void> {
-		return invoke("clear_history");
+	/** Pause or resume dictation, returning whether it is now paused */
+	async switchDictationPaused(): Promise<boolean> {
+		return invoke("switch_dictation_paused");
+	},
+
+	async onPaletteOpened(callback: () => void): Promise<UnlistenFn> {
+		return listen("palette-opened", () => {
+			callback();
+		});
+	},
+
+	/** Called when the palette loses focus, e.g. on a click into another app */
+	async onPaletteBlurred(callback: () => void): Promise<UnlistenFn> {
+		return getCurrentWindow().onFocusChanged(({ payload: focused }) => {
+			if (!focused) callback();
+		});
 	},
 
 	// Overlay API
@@ -329,25 +2810,61 @@ export const tauriAPI = {
 		return invoke("resize_overlay", { width, height });
 	},
 
+	/** Let the overlay take keyboard focus while its correction box is open */
+	async setOverlayEditing(editing: boolean): Promise<void> {
+		return invoke("set_overlay_editing", { editing });
+	},
+
+	/** Move the overlay to a physical screen position and remember it */
+	async setOverlayPosition(x: number, y: number): Promise<void> {
+		return invoke("set_overlay_position", { x, y });
+	},
+
+	/** Move the overlay back to its default corner */
+	async resetOverlayPosition(): Promise<void> {
+		return invoke("reset_overlay_position");
+	},
+
+	/** Forget saved window sizes and positions and put open windows back */
+	async resetWindowState(): Promise<void> {
+		return invoke("reset_window_state");
+	},
+
+	/** Called with the overlay's physical position whenever the window moves */
+	async onOverlayMoved(
+		callback: (x: number, y: number) => void,
+	): Promise<UnlistenFn> {
+		return getCurrentWindow().onMoved(({ payload }) => {
+			callback(payload.x, payload.y);
+		});
+	},
+
+	async onOverlayScaleChanged(callback: () => void): Promise<UnlistenFn> {
+		return listen("overlay-scale-changed", () => {
+			callback();
+		});
+	},
+
 	async startDragging(): Promise<void> {
 		const window = getCurrentWindow();
 		return window.startDragging();
 	},
 
-	// Connection state sync between windows
-	async emitConnectionState(state: ConnectionState): Promise<void> {
-		return emit("connection-state-changed", { state });
+	// Connection state (overlay -> Rust -> all windows)
+	async reportConnectionState(state: ConnectionState): Promise<void> {
+		return invoke("report_connection_state", { state });
+	},
+
+	async getConnectionStatus(): Promise<ConnectionStatus> {
+		return invoke("get_connection_status");
 	},
 
 	async onConnectionStateChanged(
 		callback: (state: ConnectionState) => void,
 	): Promise<UnlistenFn> {
-		return listen<{ state: ConnectionState }>(
-			"connection-state-changed",
-			(event) => {
-				callback(event.payload.state);
-			},
-		);
+		return listen<ConnectionStatus>("connection-state-changed", (event) => {
+			callback(event.payload.state);
+		});
 	},
 
 	// History sync between windows
@@ -361,28 +2878,58 @@ export const tauriAPI = {
 		});
 	},
 
-	// Settings sync between windows (main -> overlay)
+	// Ask the backend to broadcast settings changes right away, e.g. after
+	// saving. It also notices writes to the store on its own.
 	async emitSettingsChanged(): Promise<void> {
 		return emit("settings-changed", {});
 	},
 
-	async onSettingsChanged(callback: () => void): Promise<UnlistenFn> {
-		return listen("settings-changed", () => {
-			callback();
+	// Settings changed in any window, in Rust or in settings.json
+	async onSettingsChanged(
+		callback: (event: SettingsChangedEvent) => void,
+	): Promise<UnlistenFn> {
+		return listen<SettingsChangedEvent>("settings://changed", (event) => {
+			callback(event.payload);
 		});
 	},
 
-	// Reconnect request (main -> overlay)
-	async emitReconnect(): Promise<void> {
-		return emit("request-reconnect", {});
+	// Main window opened after the overlay connected (main -> overlay)
+	async emitMainWindowReady(): Promise<void> {
+		return emit("main-window-ready", {});
 	},
 
-	async onReconnect(callback: () => void): Promise<UnlistenFn> {
-		return listen("request-reconnect", () => {
+	async onMainWindowReady(callback: () => void): Promise<UnlistenFn> {
+		return listen("main-window-ready", () => {
 			callback();
 		});
 	},
 
+	/** Connect to the first server right away, resetting the backoff */
+	async reconnectServer(): Promise<void> {
+		return invoke("reconnect_server");
+	},
+
+	// Reconnect request (Rust -> overlay), manual or scheduled with backoff
+	async onReconnectRequest(
+		callback: (url: string, attempt: number) => void,
+	): Promise<UnlistenFn> {
+		return listen<{ attempt: number; url: string }>(
+			"connection-reconnect",
+			(event) => {
+				callback(event.payload.url, event.payload.attempt);
+			},
+		);
+	},
+
+	// Connected to a different server than before (Rust -> all windows)
+	async onServerEndpointChanged(
+		callback: (endpoint: ServerEndpoint) => void,
+	): Promise<UnlistenFn> {
+		return listen<ServerEndpoint>("server-endpoint-changed", (event) => {
+			callback(event.payload);
+		});
+	},
+
 	// Config response sync between windows (overlay -> main)
 	async emitConfigResponse(response: ConfigResponse): Promise<void> {
 		return emit("config-response", response);
@@ -430,6 +2977,7 @@ export interface ProviderInfo {
 export interface AvailableProvidersData {
 	stt: ProviderInfo[];
 	llm: ProviderInfo[];
+	audio_encodings?: string[]; // Queued audio the server decodes, PCM if missing
 }
 
 // Create ky instance with sensible defaults for API calls
@@ -0,0 +1,24 @@
+import { MantineProvider } from "@mantine/core";
+import "@mantine/core/styles.css";
+import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
+import { StrictMode } from "react";
+import { createRoot } from "react-dom/client";
+import PaletteApp from "./PaletteApp";
+import "./app-main.css";
+
+const queryClient = new QueryClient();
+
+const rootElement = document.getElementById("root");
+if (!rootElement) {
+	throw new Error("Root element not found");
+}
+
+createRoot(rootElement).render(
+	<StrictMode>
+		<QueryClientProvider client={queryClient}>
+			<MantineProvider defaultColorScheme="dark">
+				<PaletteApp />
+			</MantineProvider>
+		</QueryClientProvider>
+	</StrictMode>,
+);
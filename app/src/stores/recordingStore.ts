@@ -1,5 +1,7 @@
 import type { PipecatClient } from "@pipecat-ai/client-js";
 import { create } from "zustand";
+import { isMicPermissionError } from "../lib/micPermission";
+import type { ActiveApp } from "../lib/tauri";
 
 /**
  * Explicit state machine for connection and recording states.
@@ -12,20 +14,40 @@ type ConnectionState =
 	| "recording" // Mic enabled, streaming audio
 	| "processing"; // Waiting for server response
 
+/** A stopped turn waiting for its response */
+export interface PendingTurn {
+	id: number;
+	durationMs: number; // How long the turn was recorded for
+}
+
 interface RecordingState {
 	state: ConnectionState;
 	client: PipecatClient | null;
 
+	// Turns: each recording gets an ID so responses can be matched in order
+	nextTurnId: number;
+	recordingTurnId: number | null; // Turn currently being recorded
+	recordingStartedAt: number | null; // When the current turn started recording
+	pausedAt: number | null; // When the current turn was paused, null while capturing
+	pendingTurns: PendingTurn[]; // Stopped turns awaiting a response, oldest first
+	micPermissionDenied: boolean; // The last attempt to open the mic was refused
+
 	// Actions
 	setClient: (client: PipecatClient | null) => void;
 	setState: (state: ConnectionState) => void;
+	setMicPermissionDenied: (denied: boolean) => void;
 
 	// State transitions
 	handleConnected: () => void;
 	handleDisconnected: () => void;
-	startRecording: () => Promise<boolean>; // Returns false if not in valid state
+	// Returns false if not in valid state
+	startRecording: (targetApp?: ActiveApp | null) => Promise<boolean>;
 	stopRecording: () => boolean; // Returns false if not in valid state
-	handleResponse: () => void;
+	setPaused: (paused: boolean) => boolean; // Returns false if not recording
+	// Returns the completed turn, or null for a late response to a cancelled one
+	handleResponse: (turnId: number | null) => PendingTurn | null;
+	cancelTurn: (turnId: number) => void;
+	abandonPendingTurns: () => void;
 
 	// Configuration via data channel
 	sendConfigMessage: (type: string, data: unknown) => boolean;
@@ -34,9 +56,16 @@ interface RecordingState {
 export const useRecordingStore = create<RecordingState>((set, get) => ({
 	state: "disconnected",
 	client: null,
+	nextTurnId: 1,
+	recordingTurnId: null,
+	recordingStartedAt: null,
+	pausedAt: null,
+	pendingTurns: [],
+	micPermissionDenied: false,
 
 	setClient: (client) => set({ client }),
 	setState: (state) => set({ state }),
+	setMicPermissionDenied: (denied) => set({ micPermissionDenied: denied }),
 
 	handleConnected: () => {
 		const currentState = get().state;
@@ -47,18 +76,34 @@ export const useRecordingStore = create<RecordingState>((set, get) => ({
 
 	handleDisconnected: () => {
 		// Only reset connection state - keep client reference since we reuse it for reconnection
-		set({ state: "disconnected" });
+		// Responses for in-flight turns will never arrive
+		set({
+			state: "disconnected",
+			recordingTurnId: null,
+			pausedAt: null,
+			pendingTurns: [],
+		});
 	},
 
-	startRecording: async () => {
-		const { state, client } = get();
-		if (state !== "idle" || !client) {
+	startRecording: async (targetApp = null) => {
+		const { state, client, nextTurnId } = get();
+		// A new turn may start while earlier ones are processing. Rust only asks for
+		// one when the concurrent turn limit allows it.
+		if ((state !== "idle" && state !== "processing") || !client) {
 			return false;
 		}
 
-		// Signal server to reset buffer and enable mic
+		// Signal server to reset buffer and enable mic. The target app lets the
+		// server format for where the text goes; its path stays on this machine.
 		try {
-			client.sendClientMessage("start-recording", {});
+			client.sendClientMessage("start-recording", {
+				turn_id: nextTurnId,
+				app_context: targetApp && {
+					app_name: targetApp.app_name,
+					window_title: targetApp.title,
+					bundle_id: targetApp.bundle_id,
+				},
+			});
 
 			// Re-acquire mic track if it was stopped (uses replaceTrack internally)
 			const selectedMic = client.selectedMic;
@@ -67,16 +112,39 @@ export const useRecordingStore = create<RecordingState>((set, get) => ({
 			}
 
 			client.enableMic(true);
-			set({ state: "recording" });
+			set({
+				state: "recording",
+				nextTurnId: nextTurnId + 1,
+				recordingTurnId: nextTurnId,
+				recordingStartedAt: Date.now(),
+				pausedAt: null,
+				micPermissionDenied: false,
+			});
 			return true;
 		} catch (error) {
 			console.error("[Recording] Error starting:", error);
+			if (isMicPermissionError(error)) {
+				// Drop the turn the server started, so it doesn't wait for audio
+				try {
+					client.sendClientMessage("cancel-turn", { turn_id: nextTurnId });
+				} catch {
+					// The server drops unfinished turns on the next start anyway
+				}
+				set({ micPermissionDenied: true, nextTurnId: nextTurnId + 1 });
+			}
 			return false;
 		}
 	},
 
 	stopRecording: () => {
-		const { state, client } = get();
+		const {
+			state,
+			client,
+			recordingTurnId,
+			recordingStartedAt,
+			pausedAt,
+			pendingTurns,
+		} = get();
 		if (state !== "recording" || !client) {
 			return false;
 		}
@@ -102,21 +170,102 @@ export const useRecordingStore = create<RecordingState>((set, get) => ({
 
 		// Try to send stop message to server
 		try {
-			client.sendClientMessage("stop-recording", {});
-			set({ state: "processing" });
+			client.sendClientMessage("stop-recording", { turn_id: recordingTurnId });
+			// Time spent paused doesn't count towards the duration
+			const durationMs =
+				(pausedAt ?? Date.now()) - (recordingStartedAt ?? Date.now());
+			set({
+				state: "processing",
+				recordingTurnId: null,
+				recordingStartedAt: null,
+				pausedAt: null,
+				pendingTurns:
+					recordingTurnId === null
+						? pendingTurns
+						: [...pendingTurns, { id: recordingTurnId, durationMs }],
+			});
 			return true;
 		} catch (error) {
 			console.warn("[Recording] Failed to send stop message:", error);
-			set({ state: "disconnected" });
+			set({
+			state: "disconnected",
+			recordingTurnId: null,
+			pausedAt: null,
+			pendingTurns: [],
+		});
 			return true;
 		}
 	},
 
-	handleResponse: () => {
-		const { state } = get();
-		if (state === "processing") {
+	setPaused: (paused) => {
+		const { state, client, recordingStartedAt, pausedAt } = get();
+		if (state !== "recording" || !client || paused === (pausedAt !== null)) {
+			return false;
+		}
+
+		// The turn stays open on the server, so the audio on either side of the
+		// pause is transcribed as one dictation
+		try {
+			client.enableMic(!paused);
+		} catch (error) {
+			console.warn("[Recording] Failed to toggle mic:", error);
+			return false;
+		}
+		if (paused) {
+			set({ pausedAt: Date.now() });
+		} else {
+			// Move the start forward so the pause isn't counted in the duration
+			set({
+				pausedAt: null,
+				recordingStartedAt:
+					recordingStartedAt === null || pausedAt === null
+						? recordingStartedAt
+						: recordingStartedAt + (Date.now() - pausedAt),
+			});
+		}
+		return true;
+	},
+
+	handleResponse: (turnId) => {
+		const { state, pendingTurns } = get();
+		// Untagged responses answer the oldest turn, since the server answers in order
+		const completedTurn =
+			turnId === null
+				? (pendingTurns[0] ?? null)
+				: (pendingTurns.find((turn) => turn.id === turnId) ?? null);
+		if (completedTurn === null) {
+			return null; // Late response for a turn that was cancelled
+		}
+
+		const remaining = pendingTurns.filter((turn) => turn !== completedTurn);
+		set({
+			pendingTurns: remaining,
 			// Track is already stopped in stopRecording(), just transition state
-			set({ state: "idle" });
+			state: state === "processing" && remaining.length === 0 ? "idle" : state,
+		});
+		return completedTurn;
+	},
+
+	cancelTurn: (turnId) => {
+		const { state, client, pendingTurns } = get();
+		// Tell the server to stop transcribing/formatting; any late result is ignored
+		try {
+			client?.sendClientMessage("cancel-turn", { turn_id: turnId });
+		} catch (error) {
+			console.warn("[Recording] Failed to send cancel message:", error);
+		}
+
+		const remaining = pendingTurns.filter((turn) => turn.id !== turnId);
+		set({
+			pendingTurns: remaining,
+			state: state === "processing" && remaining.length === 0 ? "idle" : state,
+		});
+	},
+
+	abandonPendingTurns: () => {
+		const { pendingTurns, cancelTurn } = get();
+		for (const turn of pendingTurns) {
+			cancelTurn(turn.id);
 		}
 	},
 
@@ -37,6 +37,8 @@ export default defineConfig({
 			input: {
 				main: "index.html",
 				overlay: "overlay.html",
+				session: "session.html",
+				palette: "palette.html",
 			},
 		},
 	},
@@ -10,8 +10,10 @@ Usage:
 """
 
 import asyncio
+import base64
 from contextlib import asynccontextmanager
 from dataclasses import dataclass
+from datetime import UTC, datetime
 from typing import Annotated, Any, Final, cast
 
 import typer
@@ -20,7 +22,13 @@ from fastapi import FastAPI, Request
 from fastapi.middleware.cors import CORSMiddleware
 from loguru import logger
 from pipecat.audio.vad.silero import SileroVADAnalyzer
-from pipecat.frames.frames import HeartbeatFrame
+from pipecat.frames.frames import (
+    HeartbeatFrame,
+    InputAudioRawFrame,
+    TranscriptionFrame,
+    UserStartedSpeakingFrame,
+    UserStoppedSpeakingFrame,
+)
 from pipecat.observers.loggers.user_bot_latency_log_observer import UserBotLatencyLogObserver
 from pipecat.pipeline.llm_switcher import LLMSwitcher
 from pipecat.pipeline.pipeline import Pipeline
@@ -40,7 +48,7 @@ from pipecat.transports.smallwebrtc.transport import SmallWebRTCTransport
 from api.config_server import config_router
 from config.settings import Settings
 from processors.configuration import ConfigurationHandler
-from processors.llm import TranscriptionToLLMConverter
+from processors.llm import PREVIEW_ID_METADATA_KEY, TranscriptionToLLMConverter
 from processors.transcription_buffer import TranscriptionBufferProcessor
 from services.providers import (
     create_all_available_llm_services,
@@ -48,6 +56,7 @@ from services.providers import (
     get_available_llm_providers,
     get_available_stt_providers,
 )
+from utils.audio_encoding import OpusPacketDecoder
 from utils.logger import configure_logging
 from utils.observers import PipelineLogObserver
 
@@ -56,6 +65,9 @@ ICE_SERVERS: Final[list[IceServer]] = [
     IceServer(urls="stun:stun.l.google.com:19302"),
 ]
 
+# Length of the audio frames queued audio is split into, like the transport's input
+QUEUED_AUDIO_FRAME_MS: Final[int] = 20
+
 
 @dataclass
 class AppServices:
@@ -123,6 +135,9 @@ async def run_pipeline(
     # RTVIProcessor handles the RTVI protocol (client messages, server responses)
     rtvi_processor = RTVIProcessor()
 
+    # Opus decoders of queued turns being replayed, by turn ID
+    opus_decoders: dict[int | None, OpusPacketDecoder] = {}
+
     # ConfigurationHandler processes config messages from RTVI client messages
     config_handler = ConfigurationHandler(
         rtvi_processor=rtvi_processor,
@@ -138,7 +153,6 @@ async def run_pipeline(
     @rtvi_processor.event_handler("on_client_message")
     async def on_client_message(processor: RTVIProcessor, message: Any) -> None:
         """Handle RTVI client messages for configuration and recording control."""
-        _ = processor  # Unused, required by event handler signature
 
         # Extract message type and data from RTVI client message
         msg_type = message.type if hasattr(message, "type") else None
@@ -147,12 +161,75 @@ async def run_pipeline(
             return
 
         # Handle recording control messages
+        turn_id = data.get("turn_id") if isinstance(data, dict) else None
+        if not isinstance(turn_id, int):
+            turn_id = None
         if msg_type == "start-recording":
-            await transcription_buffer.start_recording()
+            # The app focused when recording started, so formatting can suit it
+            app_context = data.get("app_context")
+            if turn_id is not None and isinstance(app_context, dict):
+                transcription_to_llm.set_app_context(turn_id, app_context)
+            await transcription_buffer.start_recording(turn_id)
             return
         if msg_type == "stop-recording":
             await transcription_buffer.stop_recording()
             return
+        if msg_type == "cancel-turn":
+            # Drop the turn before STT finishes, or stop its LLM formatting if it got that far
+            if turn_id is not None and not transcription_buffer.cancel_turn(turn_id):
+                await transcription_to_llm.cancel_turn(turn_id)
+            return
+
+        # Handle audio the client recorded while the server was unreachable, or a recording
+        # the user retries, replayed as a turn in chunks of 16-bit mono PCM or Opus packets. The transport's VAD never hears it, so speech
+        # start and stop are signalled here (segmented STT services transcribe on stop).
+        if msg_type == "queued-audio":
+            sample_rate = data.get("sample_rate") if isinstance(data, dict) else None
+            if not isinstance(sample_rate, int) or sample_rate <= 0:
+                return
+            if data.get("first"):
+                await transcription_buffer.start_recording(turn_id, speech_detected=True)
+                await processor.push_frame(UserStartedSpeakingFrame())
+            if data.get("encoding") == "opus":
+                if data.get("first") or turn_id not in opus_decoders:
+                    opus_decoders[turn_id] = OpusPacketDecoder(sample_rate)
+                decoder = opus_decoders[turn_id]
+                audio = b"".join(
+                    decoder.decode(base64.b64decode(packet)) for packet in data.get("packets", [])
+                )
+                if data.get("last"):
+                    audio += opus_decoders.pop(turn_id).flush()
+            else:
+                audio = base64.b64decode(data.get("audio", ""))
+            frame_bytes = sample_rate * QUEUED_AUDIO_FRAME_MS // 1000 * 2
+            for start in range(0, len(audio), frame_bytes):
+                await processor.push_frame(
+                    InputAudioRawFrame(
+                        audio=audio[start : start + frame_bytes],
+                        sample_rate=sample_rate,
+                        num_channels=1,
+                    )
+                )
+            if data.get("last"):
+                await transcription_buffer.stop_recording()
+                await processor.push_frame(UserStoppedSpeakingFrame())
+            return
+
+        # Handle text transcribed on the client (e.g. local Whisper) that only needs formatting,
+        # or a past transcript re-formatted as a preview that the client won't insert
+        if msg_type == "format-text":
+            text = data.get("text", "") if isinstance(data, dict) else ""
+            preview_id = data.get("preview_id") if isinstance(data, dict) else None
+            if text.strip():
+                frame = TranscriptionFrame(
+                    text=text.strip(),
+                    user_id="",
+                    timestamp=datetime.now(UTC).isoformat(),
+                )
+                if isinstance(preview_id, int):
+                    frame.metadata[PREVIEW_ID_METADATA_KEY] = preview_id
+                await transcription_to_llm.queue_frame(frame)
+            return
 
         # Handle configuration messages
         await config_handler.handle_client_message(msg_type, data)
@@ -287,6 +364,16 @@ async def health_check() -> dict[str, str]:
     return {"status": "ok"}
 
 
+@app.get("/api/capabilities")
+async def capabilities(request: Request) -> dict[str, list[str]]:
+    """Report the providers this server can use, for clients testing a connection."""
+    services: AppServices = request.app.state.services
+    return {
+        "stt_providers": [p.value for p in get_available_stt_providers(services.settings)],
+        "llm_providers": [p.value for p in get_available_llm_providers(services.settings)],
+    }
+
+
 # =============================================================================
 # WebRTC Endpoints
 # =============================================================================
@@ -13,8 +13,11 @@ from loguru import logger
 from pipecat.frames.frames import ManuallySwitchServiceFrame
 from pipecat.processors.frame_processor import FrameDirection
 from pipecat.processors.frameworks.rtvi import RTVIProcessor, RTVIServerMessageFrame
+from pipecat.transcriptions.language import Language
 
+from processors.llm import MAX_SHORT_UTTERANCE_MAX_WORDS
 from services.provider_registry import LLMProviderId, STTProviderId
+from utils.audio_encoding import QUEUED_AUDIO_ENCODINGS
 
 if TYPE_CHECKING:
     from pipecat.pipeline.llm_switcher import LLMSwitcher
@@ -35,6 +38,8 @@ class ConfigurationHandler:
     - set-llm-provider: Switch LLM service
     - set-prompt-sections: Update LLM prompt
     - set-stt-timeout: Update transcription timeout
+    - set-stt-language: Hint the spoken language to STT services
+    - set-short-utterance-max-words: Update the no-LLM fast path threshold
 
     All configuration is scoped to this pipeline instance.
     """
@@ -95,6 +100,11 @@ class ConfigurationHandler:
             ),
             "set-prompt-sections": lambda: self._set_prompt_sections(data.get("sections")),
             "set-stt-timeout": lambda: self._set_stt_timeout(data.get("timeout_seconds")),
+            "set-stt-language": lambda: self._set_stt_language(data.get("language")),
+            "set-short-utterance-max-words": lambda: self._set_short_utterance_max_words(
+                data.get("max_words")
+            ),
+            "set-formatting-enabled": lambda: self._set_formatting_enabled(data.get("enabled")),
             "get-available-providers": self._send_available_providers,
         }
 
@@ -194,8 +204,69 @@ class ConfigurationHandler:
         logger.info(f"Set STT timeout to: {timeout_seconds}s")
         await self._send_config_success("stt-timeout", timeout_seconds)
 
+    async def _set_stt_language(self, language: str | None) -> None:
+        """Hint the spoken language to every STT service, so switching provider
+        keeps the hint.
+
+        Args:
+            language: ISO 639-1 code such as "en", or None to detect the language
+        """
+        hint: Language | None = None
+        if language:
+            try:
+                hint = Language(language)
+            except ValueError:
+                await self._send_config_error("stt-language", f"Unknown language: {language}")
+                return
+
+        for provider_id, service in self._stt_services.items():
+            try:
+                # Services detect the language when they have no hint
+                await service.set_language(hint)  # type: ignore[arg-type]
+            except Exception as e:
+                logger.warning(f"{provider_id.value} can't use language {language}: {e}")
+
+        logger.info(f"Set STT language to: {language or 'auto-detect'}")
+        await self._send_config_success("stt-language", language)
+
+    async def _set_short_utterance_max_words(self, max_words: int | None) -> None:
+        """Set the word count at or below which LLM formatting is skipped.
+
+        Args:
+            max_words: Maximum number of words for the fast path (0 disables it)
+        """
+        if max_words is None:
+            await self._send_config_error(
+                "short-utterance-max-words", "Max words value is required"
+            )
+            return
+
+        if max_words < 0 or max_words > MAX_SHORT_UTTERANCE_MAX_WORDS:
+            await self._send_config_error(
+                "short-utterance-max-words",
+                f"Max words must be between 0 and {MAX_SHORT_UTTERANCE_MAX_WORDS}",
+            )
+            return
+
+        self._llm_converter.set_short_utterance_max_words(max_words)
+        logger.info(f"Set short utterance max words to: {max_words}")
+        await self._send_config_success("short-utterance-max-words", max_words)
+
+    async def _set_formatting_enabled(self, enabled: bool | None) -> None:
+        """Turn LLM formatting on or off, so transcriptions are sent as transcribed.
+
+        Args:
+            enabled: Whether to format transcriptions with the LLM
+        """
+        if not isinstance(enabled, bool):
+            await self._send_config_error("formatting-enabled", "Enabled must be true or false")
+            return
+
+        self._llm_converter.set_formatting_enabled(enabled)
+        await self._send_config_success("formatting-enabled", enabled)
+
     async def _send_available_providers(self) -> None:
-        """Send available providers with model info from instantiated services."""
+        """Send available providers with model info, and the queued audio encodings decoded."""
         from services.provider_registry import get_llm_provider_labels, get_stt_provider_labels
 
         stt_providers = self._build_provider_list(
@@ -214,6 +285,7 @@ class ConfigurationHandler:
                 "type": "available-providers",
                 "stt": stt_providers,
                 "llm": llm_providers,
+                "audio_encodings": QUEUED_AUDIO_ENCODINGS,
             }
         )
         await self._rtvi.push_frame(frame)
@@ -1,5 +1,7 @@
 """LLM-based text formatting processor for dictation using idiomatic Pipecat patterns."""
 
+import re
+from collections import deque
 from typing import Any, Final
 
 from openai.types.chat import (
@@ -8,6 +10,10 @@ from openai.types.chat import (
 )
 from pipecat.frames.frames import (
     Frame,
+    InterruptionFrame,
+    LLMFullResponseEndFrame,
+    LLMFullResponseStartFrame,
+    LLMTextFrame,
     TranscriptionFrame,
 )
 from pipecat.processors.aggregators.openai_llm_context import (
@@ -15,9 +21,35 @@ from pipecat.processors.aggregators.openai_llm_context import (
     OpenAILLMContextFrame,
 )
 from pipecat.processors.frame_processor import FrameDirection, FrameProcessor
+from pipecat.processors.frameworks.rtvi import RTVIServerMessageFrame
 
+from processors.transcription_buffer import TURN_ID_METADATA_KEY
 from utils.logger import logger
 
+# Utterances with at most this many words skip LLM formatting (0 disables the fast path)
+DEFAULT_SHORT_UTTERANCE_MAX_WORDS: Final[int] = 0
+
+# Largest word count the fast path can be set to, matching the app's settings slider
+MAX_SHORT_UTTERANCE_MAX_WORDS: Final[int] = 10
+
+# Filler words stripped by local post-processing when the LLM is skipped
+FILLER_WORD_PATTERN: Final[re.Pattern[str]] = re.compile(
+    r"\b(?:um+|uh+|erm+|err+|hmm+)\b[,.]?\s*", re.IGNORECASE
+)
+
+# Characters that already end a sentence, so no period needs to be appended
+SENTENCE_TERMINATORS: Final[str] = ".!?"
+
+# Number of cancelled turn IDs remembered while their transcription may still be in flight
+MAX_CANCELLED_TURNS: Final[int] = 32
+
+# Number of turns whose target app is remembered until their transcription arrives
+MAX_APP_CONTEXTS: Final[int] = 32
+
+# Frame metadata key marking text formatted as a preview, which the client returns
+# to the app instead of inserting
+PREVIEW_ID_METADATA_KEY: Final[str] = "preview_id"
+
 # Main prompt section - Core rules, punctuation, new lines
 MAIN_PROMPT_DEFAULT: Final[
     str
@@ -161,12 +193,65 @@ def combine_prompt_sections(
     return "\n\n".join(parts)
 
 
+def describe_app_context(context: dict[str, Any]) -> str | None:
+    """Describe the app the text will be typed into, for the formatting prompt.
+
+    The client sends the focused app's name, window title and (on macOS) bundle
+    identifier when a recording starts. Returns None if the app name is missing.
+    """
+    app_name = context.get("app_name")
+    if not isinstance(app_name, str) or not app_name.strip():
+        return None
+    description = f"The text will be typed into {app_name.strip()}"
+    bundle_id = context.get("bundle_id")
+    if isinstance(bundle_id, str) and bundle_id.strip():
+        description += f" ({bundle_id.strip()})"
+    window_title = context.get("window_title")
+    if isinstance(window_title, str) and window_title.strip():
+        description += f', in a window titled "{window_title.strip()}"'
+    return description + ". Match the conventions of that app."
+
+
+def count_words(text: str) -> int:
+    """Count whitespace-separated words in the text."""
+    return len(text.split())
+
+
+def format_short_utterance(text: str) -> str:
+    """Apply local post-processing to a short utterance that skips the LLM.
+
+    Removes filler words, collapses whitespace, capitalizes the first letter,
+    and ends the sentence with a period if it has no terminal punctuation.
+    Returns an empty string if nothing remains after removing filler words.
+    """
+    cleaned = FILLER_WORD_PATTERN.sub("", text)
+    cleaned = " ".join(cleaned.split()).strip(" ,")
+    if not cleaned:
+        return ""
+
+    cleaned = cleaned[0].upper() + cleaned[1:]
+    if cleaned[-1] not in SENTENCE_TERMINATORS:
+        cleaned += "."
+    return cleaned
+
+
 class TranscriptionToLLMConverter(FrameProcessor):
     """Converts TranscriptionFrame to OpenAILLMContextFrame for LLM formatting.
 
     This processor receives accumulated transcription text and converts it
     to an LLM context with the formatting system prompt, triggering the LLM
     service to generate formatted text.
+
+    Short utterances (at most `short_utterance_max_words` words) bypass the LLM
+    and are emitted directly as LLM response frames after local post-processing,
+    so the client receives them through the same bot-llm-text flow. With formatting
+    turned off, every transcription is emitted that way as transcribed, except
+    previews, which the client asks for explicitly.
+
+    Each result is preceded by a `formatting-started` server message naming the
+    client's turn (or preview) and the raw transcript, so the client can tell which
+    turn the following LLM text belongs to and keep the transcript for history.
+    Cancelled turns are dropped, or interrupted if already formatting.
     """
 
     def __init__(self, **kwargs: Any) -> None:
@@ -178,6 +263,13 @@ class TranscriptionToLLMConverter(FrameProcessor):
         self._advanced_custom: str | None = None
         self._dictionary_enabled: bool = False
         self._dictionary_custom: str | None = None
+        self._short_utterance_max_words: int = DEFAULT_SHORT_UTTERANCE_MAX_WORDS
+        self._formatting_enabled: bool = True
+        # Turn whose transcription was most recently sent for formatting
+        self._formatting_turn_id: int | None = None
+        self._cancelled_turn_ids: deque[int] = deque(maxlen=MAX_CANCELLED_TURNS)
+        # Description of the app each turn's text is going to, by turn ID
+        self._app_contexts: dict[int, str] = {}
 
     @property
     def system_prompt(self) -> str:
@@ -217,6 +309,90 @@ class TranscriptionToLLMConverter(FrameProcessor):
         self._dictionary_custom = dictionary_custom
         logger.info("Formatting prompt sections updated")
 
+    def set_short_utterance_max_words(self, max_words: int) -> None:
+        """Set the word count at or below which LLM formatting is skipped.
+
+        Args:
+            max_words: Maximum number of words for the fast path, or 0 to disable it.
+        """
+        self._short_utterance_max_words = max_words
+        logger.info(f"Short utterance fast path max words set to: {max_words}")
+
+    def set_formatting_enabled(self, enabled: bool) -> None:
+        """Turn LLM formatting on or off.
+
+        Args:
+            enabled: Whether to format transcriptions, or pass them through as transcribed.
+        """
+        self._formatting_enabled = enabled
+        logger.info(f"LLM formatting {'enabled' if enabled else 'disabled'}")
+
+    def set_app_context(self, turn_id: int, context: dict[str, Any]) -> None:
+        """Remember the app a turn's text is going to, for its formatting prompt.
+
+        Args:
+            turn_id: The client's ID for the turn.
+            context: The app context sent with the turn's start-recording message.
+        """
+        description = describe_app_context(context)
+        if description is None:
+            return
+        # Turns with no speech never reach formatting, so drop the oldest contexts
+        while len(self._app_contexts) >= MAX_APP_CONTEXTS:
+            del self._app_contexts[next(iter(self._app_contexts))]
+        self._app_contexts[turn_id] = description
+        logger.debug(f"Turn {turn_id} app context: {description}")
+
+    async def cancel_turn(self, turn_id: int) -> None:
+        """Stop formatting the given turn, or drop its transcription when it arrives.
+
+        Args:
+            turn_id: The client's ID for the cancelled turn.
+        """
+        if self._formatting_turn_id == turn_id:
+            logger.info(f"Turn {turn_id} cancelled, interrupting formatting")
+            self._formatting_turn_id = None
+            # Interruptions cancel in-progress LLM generation downstream
+            await self.push_frame(InterruptionFrame())
+            return
+        logger.info(f"Turn {turn_id} cancelled, will drop its transcription")
+        self._cancelled_turn_ids.append(turn_id)
+
+    def _is_short_utterance(self, text: str) -> bool:
+        """Check whether the text qualifies for the no-LLM fast path."""
+        return 0 < count_words(text) <= self._short_utterance_max_words
+
+    async def _emit_short_utterance(
+        self, text: str, turn_id: int | None, preview_id: int | None, direction: FrameDirection
+    ) -> None:
+        """Emit a locally formatted short utterance, bypassing the LLM.
+
+        Wraps the text in LLM response frames so RTVIObserver forwards it to the
+        client exactly like an LLM-formatted response.
+        """
+        formatted = format_short_utterance(text)
+        if not formatted:
+            logger.debug("Short utterance contained only filler words, sending empty response")
+            frame = RTVIServerMessageFrame(
+                data={
+                    "type": "recording-complete",
+                    "hasContent": False,
+                    "turnId": turn_id,
+                    "previewId": preview_id,
+                }
+            )
+            await self.push_frame(frame, direction)
+            return
+
+        logger.debug(f"Short utterance fast path, skipping LLM: {formatted}")
+        await self._emit_response(formatted, direction)
+
+    async def _emit_response(self, text: str, direction: FrameDirection) -> None:
+        """Emit text as a complete LLM response without calling the LLM."""
+        await self.push_frame(LLMFullResponseStartFrame(), direction)
+        await self.push_frame(LLMTextFrame(text=text), direction)
+        await self.push_frame(LLMFullResponseEndFrame(), direction)
+
     async def process_frame(self, frame: Frame, direction: FrameDirection) -> None:
         """Convert transcription frames to LLM context frames.
 
@@ -228,13 +404,47 @@ class TranscriptionToLLMConverter(FrameProcessor):
 
         if isinstance(frame, TranscriptionFrame):
             text = frame.text
+            turn_id = frame.metadata.get(TURN_ID_METADATA_KEY)
+            preview_id = frame.metadata.get(PREVIEW_ID_METADATA_KEY)
+            app_context = self._app_contexts.pop(turn_id, None) if turn_id is not None else None
+            if turn_id is not None and turn_id in self._cancelled_turn_ids:
+                logger.info(f"Dropping transcription for cancelled turn {turn_id}")
+                self._cancelled_turn_ids.remove(turn_id)
+                return
+
             if text and text.strip():
+                self._formatting_turn_id = turn_id
+                await self.push_frame(
+                    RTVIServerMessageFrame(
+                        data={
+                            "type": "formatting-started",
+                            "turnId": turn_id,
+                            "previewId": preview_id,
+                            "rawText": text,
+                        }
+                    ),
+                    direction,
+                )
+
+                if not self._formatting_enabled and preview_id is None:
+                    logger.debug("Formatting disabled, sending the raw transcription")
+                    await self._emit_response(text, direction)
+                    return
+
+                if self._is_short_utterance(text):
+                    await self._emit_short_utterance(text, turn_id, preview_id, direction)
+                    return
+
                 logger.debug(f"Converting transcription to LLM context: {text[:50]}...")
 
+                system_prompt = self.system_prompt
+                if app_context:
+                    system_prompt += f"\n\n{app_context}"
+
                 # Create OpenAI-compatible context with formatting prompt
                 context = OpenAILLMContext(
                     messages=[
-                        ChatCompletionSystemMessageParam(role="system", content=self.system_prompt),
+                        ChatCompletionSystemMessageParam(role="system", content=system_prompt),
                         ChatCompletionUserMessageParam(role="user", content=text),
                     ]
                 )
@@ -7,6 +7,10 @@ Uses a state machine pattern with tagged unions for explicit state management:
 - IdleState: Not recording
 - RecordingState: Actively buffering transcriptions
 - WaitingForSTTState: Stop received, waiting for STT to catch up
+
+Each recording carries the client's turn ID, which is attached to the emitted
+transcription (and empty responses) so results can be matched to their turn
+and a cancelled turn can be dropped before it reaches the LLM.
 """
 
 from __future__ import annotations
@@ -31,6 +35,9 @@ from utils.logger import logger
 # Default timeout for waiting for STT transcriptions (can be overridden at runtime)
 DEFAULT_TRANSCRIPTION_WAIT_TIMEOUT_SECONDS: Final[float] = 0.5
 
+# Frame metadata key carrying the client's turn ID
+TURN_ID_METADATA_KEY: Final[str] = "turn_id"
+
 
 # =============================================================================
 # State Machine Types
@@ -52,6 +59,7 @@ class RecordingState:
     user_id: str = "user"
     language: Language | None = None
     speech_detected: bool = False
+    turn_id: int | None = None
 
 
 @dataclass(frozen=True)
@@ -67,6 +75,7 @@ class WaitingForSTTState:
     user_id: str
     language: Language | None
     direction: FrameDirection
+    turn_id: int | None = None
 
 
 @dataclass(frozen=True)
@@ -83,6 +92,7 @@ class DrainingState:
     user_id: str
     language: Language | None
     direction: FrameDirection
+    turn_id: int | None = None
 
 
 # Tagged union of all possible states
@@ -164,25 +174,67 @@ class TranscriptionBufferProcessor(FrameProcessor):
     # Public API for RTVI Event Handler
     # =========================================================================
 
-    async def start_recording(self) -> None:
-        """Start recording - called from RTVI on_client_message handler."""
-        await self._handle_start_recording()
+    async def start_recording(
+        self, turn_id: int | None = None, speech_detected: bool = False
+    ) -> None:
+        """Start recording - called from RTVI on_client_message handler.
+
+        Args:
+            turn_id: The client's ID for the turn
+            speech_detected: Treat the turn as containing speech from the start, for
+                replayed audio that the transport's VAD never hears
+        """
+        await self._handle_start_recording(turn_id, speech_detected)
 
     async def stop_recording(self, direction: FrameDirection = FrameDirection.DOWNSTREAM) -> None:
         """Stop recording - called from RTVI on_client_message handler."""
         await self._handle_stop_recording(direction)
 
+    def cancel_turn(self, turn_id: int) -> bool:
+        """Discard the given turn if it is still being recorded or waiting for STT.
+
+        Returns:
+            True if the turn was discarded here, False if it is not buffered
+            (already emitted downstream, or unknown).
+        """
+        match self._state:
+            case (
+                RecordingState(turn_id=current)
+                | WaitingForSTTState(turn_id=current)
+                | DrainingState(turn_id=current)
+            ) if current == turn_id:
+                self._cancel_timeout()
+                self._cancel_draining()
+                logger.info(f"Turn {turn_id} cancelled, discarding buffer")
+                self._state = IdleState()
+                return True
+            case _:
+                return False
+
     # =========================================================================
     # State Transition Handlers
     # =========================================================================
 
-    async def _handle_start_recording(self) -> None:
-        """Transition to RecordingState from any state."""
+    async def _handle_start_recording(self, turn_id: int | None, speech_detected: bool) -> None:
+        """Transition to RecordingState from any state.
+
+        The client may start a new turn while the previous one is still waiting
+        for STT, so that turn is emitted with what it has instead of being dropped.
+        """
         # Cancel any pending tasks from previous states
         self._cancel_timeout()
         self._cancel_draining()
-        logger.info("Start-recording received, entering RecordingState")
-        self._state = RecordingState()
+        match self._state:
+            case WaitingForSTTState(buffer=buffer) | DrainingState(buffer=buffer) as state:
+                logger.info(f"New recording started, emitting previous turn: '{buffer.strip()}'")
+                if buffer.strip():
+                    await self._emit_transcription(state, state.direction)
+                else:
+                    await self._emit_empty_response(state.direction, state.turn_id)
+            case _:
+                pass
+        logger.info(f"Start-recording received for turn {turn_id}, entering RecordingState")
+        self._state = RecordingState(turn_id=turn_id, speech_detected=speech_detected)
 
     async def _handle_stop_recording(self, direction: FrameDirection) -> None:
         """Handle stop-recording based on current state."""
@@ -201,12 +253,13 @@ class TranscriptionBufferProcessor(FrameProcessor):
                         user_id=state.user_id,
                         language=state.language,
                         direction=direction,
+                        turn_id=state.turn_id,
                     )
                     self._timeout_task = asyncio.create_task(self._stt_timeout_handler(direction))
                 else:
                     # No speech detected - send empty response
                     logger.info("Stop-recording received, no speech detected, sending empty")
-                    await self._emit_empty_response(direction)
+                    await self._emit_empty_response(direction, state.turn_id)
                     self._state = IdleState()
 
             case WaitingForSTTState():
@@ -227,6 +280,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                     user_id=state.user_id,
                     language=state.language,
                     speech_detected=True,
+                    turn_id=state.turn_id,
                 )
             case _:
                 pass  # Ignore speech events in other states
@@ -244,6 +298,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                     user_id=state.user_id,
                     language=state.language,
                     direction=state.direction,
+                    turn_id=state.turn_id,
                 )
                 # Start draining task with adaptive timeout
                 self._draining_event.clear()
@@ -272,6 +327,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                     user_id=frame.user_id,
                     language=frame.language,
                     speech_detected=state.speech_detected,
+                    turn_id=state.turn_id,
                 )
                 logger.debug(f"Buffered transcription: '{frame.text}' (total: '{new_buffer}')")
 
@@ -285,6 +341,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                     user_id=frame.user_id,
                     language=frame.language,
                     direction=state.direction,
+                    turn_id=state.turn_id,
                 )
 
             case DrainingState() as state:
@@ -297,6 +354,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                     user_id=frame.user_id,
                     language=frame.language,
                     direction=state.direction,
+                    turn_id=state.turn_id,
                 )
                 # Signal the draining task to reset its timeout
                 self._draining_event.set()
@@ -324,7 +382,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                         logger.info(f"Timeout, emitting buffer: '{buffer.strip()}'")
                         await self._emit_transcription(state, state.direction)
                     else:
-                        await self._emit_empty_response(direction)
+                        await self._emit_empty_response(direction, state.turn_id)
                     self._state = IdleState()
                 case _:
                     pass  # State changed, nothing to do
@@ -367,7 +425,7 @@ class TranscriptionBufferProcessor(FrameProcessor):
                         await self._emit_transcription(state, state.direction)
                     else:
                         logger.info("Draining complete with empty buffer, sending empty")
-                        await self._emit_empty_response(direction)
+                        await self._emit_empty_response(direction, state.turn_id)
                     self._state = IdleState()
                 case _:
                     pass  # State changed, nothing to do
@@ -397,9 +455,15 @@ class TranscriptionBufferProcessor(FrameProcessor):
             timestamp=datetime.now(UTC).isoformat(),
             language=state.language,
         )
+        if state.turn_id is not None:
+            consolidated_frame.metadata[TURN_ID_METADATA_KEY] = state.turn_id
         await self.push_frame(consolidated_frame, direction)
 
-    async def _emit_empty_response(self, direction: FrameDirection) -> None:
+    async def _emit_empty_response(
+        self, direction: FrameDirection, turn_id: int | None = None
+    ) -> None:
         """Send an empty response message to the client."""
-        frame = RTVIServerMessageFrame(data={"type": "recording-complete", "hasContent": False})
+        frame = RTVIServerMessageFrame(
+            data={"type": "recording-complete", "hasContent": False, "turnId": turn_id}
+        )
         await self.push_frame(frame, direction)
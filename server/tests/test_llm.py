@@ -5,6 +5,9 @@ from processors.llm import (
     DICTIONARY_PROMPT_DEFAULT,
     MAIN_PROMPT_DEFAULT,
     combine_prompt_sections,
+    count_words,
+    describe_app_context,
+    format_short_utterance,
 )
 
 
@@ -118,3 +121,57 @@ class TestCombinePromptSections:
         assert MAIN_PROMPT_DEFAULT in result
         assert ADVANCED_PROMPT_DEFAULT in result
         assert DICTIONARY_PROMPT_DEFAULT not in result
+
+
+class TestFormatShortUtterance:
+    """Tests for format_short_utterance() local post-processing."""
+
+    def test_capitalizes_and_adds_period(self) -> None:
+        """A bare word is capitalized and terminated with a period."""
+        assert format_short_utterance("thanks") == "Thanks."
+
+    def test_keeps_existing_terminal_punctuation(self) -> None:
+        """Existing sentence-ending punctuation is not doubled."""
+        assert format_short_utterance("sounds good!") == "Sounds good!"
+
+    def test_removes_filler_words(self) -> None:
+        """Filler words and their trailing commas are stripped."""
+        assert format_short_utterance("um, yes please") == "Yes please."
+
+    def test_filler_only_returns_empty(self) -> None:
+        """Utterances made only of filler words produce no text."""
+        assert format_short_utterance("uh um") == ""
+
+    def test_does_not_strip_words_containing_fillers(self) -> None:
+        """Words that merely start with a filler sequence are preserved."""
+        assert format_short_utterance("umbrella") == "Umbrella."
+
+    def test_count_words_ignores_extra_whitespace(self) -> None:
+        """Word counting splits on any whitespace."""
+        assert count_words("  ok   thanks \n") == 2
+
+
+class TestDescribeAppContext:
+    """Tests for describe_app_context() prompt text."""
+
+    def test_includes_name_bundle_id_and_title(self) -> None:
+        """Every known detail of the app is described."""
+        result = describe_app_context(
+            {
+                "app_name": "Slack",
+                "window_title": "general",
+                "bundle_id": "com.tinyspeck.slackmacgap",
+            }
+        )
+        assert result is not None
+        assert 'Slack (com.tinyspeck.slackmacgap), in a window titled "general"' in result
+
+    def test_missing_details_are_left_out(self) -> None:
+        """Empty or missing fields don't appear in the description."""
+        result = describe_app_context({"app_name": "Notes", "window_title": "", "bundle_id": None})
+        assert result == "The text will be typed into Notes. Match the conventions of that app."
+
+    def test_no_app_name_returns_none(self) -> None:
+        """Without an app name there is nothing useful to describe."""
+        assert describe_app_context({"window_title": "Untitled"}) is None
+        assert describe_app_context({"app_name": "  "}) is None
@@ -0,0 +1,60 @@
+"""Decoding of audio the client uploads over the data channel.
+
+Queued and retried dictations arrive either as 16-bit mono PCM or as raw Opus
+packets, whichever the client picked from the encodings listed with the
+available providers. Live audio arrives over WebRTC and is decoded by the transport.
+"""
+
+from typing import Final
+
+import av
+from av.audio.codeccontext import AudioCodecContext
+
+# Encodings of queued audio the server decodes, listed to the client
+QUEUED_AUDIO_ENCODINGS: Final[list[str]] = ["pcm16", "opus"]
+
+# Rate Opus decodes at, whatever rate the audio was encoded at
+OPUS_DECODE_SAMPLE_RATE: Final[int] = 48000
+
+
+class OpusPacketDecoder:
+    """Decodes the Opus packets of one queued turn to 16-bit mono PCM.
+
+    Opus packets depend on the ones before them, so one decoder is used per turn.
+    """
+
+    def __init__(self, sample_rate: int) -> None:
+        """Initialize the decoder.
+
+        Args:
+            sample_rate: Sample rate of the PCM to decode to
+        """
+        codec = av.CodecContext.create("opus", "r")
+        if not isinstance(codec, AudioCodecContext):
+            raise TypeError("Opus codec is not an audio codec")
+        codec.sample_rate = OPUS_DECODE_SAMPLE_RATE
+        codec.layout = "mono"
+        self._codec = codec
+        self._resampler = av.AudioResampler(format="s16", layout="mono", rate=sample_rate)
+
+    def decode(self, packet: bytes) -> bytes:
+        """Decode one Opus packet.
+
+        Args:
+            packet: A raw Opus packet, without any container
+
+        Returns:
+            The packet's audio as 16-bit mono PCM
+        """
+        pcm = bytearray()
+        for frame in self._codec.decode(av.Packet(packet)):
+            for resampled in self._resampler.resample(frame):
+                pcm += resampled.to_ndarray().tobytes()
+        return bytes(pcm)
+
+    def flush(self) -> bytes:
+        """Return the audio still held by the resampler, at the end of the turn."""
+        pcm = bytearray()
+        for resampled in self._resampler.resample(None):
+            pcm += resampled.to_ndarray().tobytes()
+        return bytes(pcm)